@@ -1,5 +1,6 @@
 //! ABI decoder for Solidity/EVM contracts
 
+use super::selector::AbiSelector;
 use super::types::*;
 
 /// ABI decoder
@@ -8,7 +9,7 @@ pub struct AbiDecoder;
 impl AbiDecoder {
     /// Decode a single value from bytes according to its type
     pub fn decode_value(data: &[u8], abi_type: &AbiType, offset: usize) -> Result<(AbiValue, usize), AbiError> {
-        if data.len() < offset + 32 {
+        if offset.checked_add(32).is_none_or(|end| data.len() < end) {
             return Err(AbiError::DecodingError("Insufficient data".to_string()));
         }
         
@@ -121,12 +122,9 @@ impl AbiDecoder {
         for abi_type in types {
             if abi_type.is_dynamic() {
                 // For dynamic types, read offset from head
-                if data.len() < head_offset + 32 {
-                    return Err(AbiError::DecodingError("Insufficient data for offset".to_string()));
-                }
-                
-                let offset_value = U256::from_be_bytes(&data[head_offset..head_offset + 32]);
-                let data_offset = base_offset + offset_value.as_u64() as usize;
+                let data_offset = base_offset
+                    .checked_add(Self::read_usize(data, head_offset, "offset")?)
+                    .ok_or_else(|| AbiError::DecodingError("Offset out of range".to_string()))?;
                 
                 let (value, _) = Self::decode_value(data, abi_type, data_offset)?;
                 values.push(value);
@@ -142,39 +140,48 @@ impl AbiDecoder {
         Ok((AbiValue::Tuple(values), head_offset))
     }
     
+    /// Read a word used as an offset or length
+    ///
+    /// Values that don't fit in the data itself are rejected rather than
+    /// truncated, so a hostile word can't wrap around or force a huge allocation.
+    fn read_usize(data: &[u8], offset: usize, what: &str) -> Result<usize, AbiError> {
+        let word = offset.checked_add(32)
+            .and_then(|end| data.get(offset..end))
+            .ok_or_else(|| AbiError::DecodingError(format!("Insufficient data for {}", what)))?;
+        let value = U256::from_be_bytes(word);
+        if value.0[1..].iter().any(|&limb| limb != 0) || value.0[0] > data.len() as u64 {
+            return Err(AbiError::DecodingError(format!("{} out of range", what)));
+        }
+        Ok(value.0[0] as usize)
+    }
+    
     /// Decode dynamic bytes
     fn decode_dynamic_bytes(data: &[u8], offset: usize) -> Result<(AbiValue, usize), AbiError> {
-        if data.len() < offset + 32 {
-            return Err(AbiError::DecodingError("Insufficient data for length".to_string()));
-        }
-        
         // Read length
-        let length_u256 = U256::from_be_bytes(&data[offset..offset + 32]);
-        let length = length_u256.as_u64() as usize;
+        let length = Self::read_usize(data, offset, "length")?;
         
-        // Read data
-        if data.len() < offset + 32 + length {
-            return Err(AbiError::DecodingError("Insufficient data for bytes".to_string()));
-        }
-        
-        let bytes = data[offset + 32..offset + 32 + length].to_vec();
+        // Read data; `read_usize` succeeded so `offset + 32` can't overflow
+        let start = offset + 32;
+        let bytes = start.checked_add(length)
+            .and_then(|end| data.get(start..end))
+            .ok_or_else(|| AbiError::DecodingError("Insufficient data for bytes".to_string()))?
+            .to_vec();
         
         // Calculate padded length
-        let padded_length = ((length + 31) / 32) * 32;
+        let padded_length = length.div_ceil(32) * 32;
         
-        Ok((AbiValue::Bytes(bytes), offset + 32 + padded_length))
+        Ok((AbiValue::Bytes(bytes), start + padded_length))
     }
     
     /// Decode a dynamic array
     fn decode_dynamic_array(data: &[u8], offset: usize, inner_type: &AbiType) -> Result<(AbiValue, usize), AbiError> {
-        if data.len() < offset + 32 {
-            return Err(AbiError::DecodingError("Insufficient data for array length".to_string()));
+        // Read length; every element takes at least one head word, so a
+        // length beyond the remaining words can't be satisfied
+        let length = Self::read_usize(data, offset, "array length")?;
+        if length > (data.len() - offset - 32) / 32 {
+            return Err(AbiError::DecodingError("Insufficient data for array elements".to_string()));
         }
         
-        // Read length
-        let length_u256 = U256::from_be_bytes(&data[offset..offset + 32]);
-        let length = length_u256.as_u64() as usize;
-        
         // Decode elements as a tuple
         let element_types: Vec<AbiType> = (0..length).map(|_| inner_type.clone()).collect();
         let (tuple, end_offset) = Self::decode_tuple(data, offset + 32, &element_types)?;
@@ -211,59 +218,145 @@ impl AbiDecoder {
     }
     
     /// Decode an event log
+    ///
+    /// Values are returned in declaration order, indexed parameters taken
+    /// from `topics` and the rest ABI-decoded from `data`.
     pub fn decode_event(
         event: &AbiEvent,
         topics: &[Vec<u8>],
         data: &[u8],
     ) -> Result<Vec<(String, AbiValue)>, AbiError> {
-        let mut result = Vec::new();
-        
         // First topic is event signature (skip for anonymous events)
         let topic_offset = if event.anonymous { 0 } else { 1 };
-        let mut topic_idx = topic_offset;
+        let mut indexed_topics = topics.iter().skip(topic_offset);
         
-        // Collect indexed and non-indexed parameters
-        let mut data_types = Vec::new();
+        // Non-indexed parameters are ABI-encoded together in data
+        let data_types: Vec<AbiType> = event.inputs.iter()
+            .filter(|p| !p.indexed)
+            .map(|p| p.param.param_type.clone())
+            .collect();
+        let mut data_values = if data_types.is_empty() {
+            Vec::new()
+        } else {
+            Self::decode(data, &data_types)?
+        }
+        .into_iter();
         
+        let mut result = Vec::with_capacity(event.inputs.len());
         for param in &event.inputs {
-            if param.indexed {
-                // Indexed parameters come from topics
-                if topic_idx >= topics.len() {
-                    return Err(AbiError::DecodingError("Missing indexed parameter topic".to_string()));
-                }
+            let value = if param.indexed {
+                let topic = indexed_topics.next()
+                    .ok_or_else(|| AbiError::DecodingError("Missing indexed parameter topic".to_string()))?;
                 
-                let topic = &topics[topic_idx];
-                topic_idx += 1;
-                
-                // For dynamic types, indexed value is hash, not actual value
-                if param.param.param_type.is_dynamic() {
-                    // Return the hash as bytes32
-                    result.push((param.param.name.clone(), AbiValue::FixedBytes(topic.clone())));
+                // For reference types, indexed value is hash, not actual value
+                if is_hashed_when_indexed(&param.param.param_type) {
+                    AbiValue::FixedBytes(topic.clone())
                 } else {
-                    // Decode static type from topic
-                    let (value, _) = Self::decode_value(topic, &param.param.param_type, 0)?;
-                    result.push((param.param.name.clone(), value));
+                    Self::decode_value(topic, &param.param.param_type, 0)?.0
                 }
             } else {
-                // Non-indexed parameters come from data
-                data_types.push((param.param.name.clone(), param.param.param_type.clone()));
-            }
-        }
-        
-        // Decode non-indexed parameters from data
-        if !data_types.is_empty() {
-            let types: Vec<AbiType> = data_types.iter().map(|(_, t)| t.clone()).collect();
-            let values = Self::decode(data, &types)?;
-            
-            for ((name, _), value) in data_types.into_iter().zip(values.into_iter()) {
-                result.push((name, value));
-            }
+                data_values.next()
+                    .ok_or_else(|| AbiError::DecodingError("Missing data value".to_string()))?
+            };
+            result.push((param.param.name.clone(), value));
         }
         
         Ok(result)
     }
 }
 
+/// A decoded event parameter
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedLogParam {
+    /// Parameter name (can be empty)
+    pub name: String,
+    /// Declared parameter type
+    pub param_type: AbiType,
+    /// Decoded value; the 32-byte topic when `hashed`
+    pub value: AbiValue,
+    /// Whether the value came from a topic
+    pub indexed: bool,
+    /// Indexed string, bytes, array or tuple: only its Keccak-256 hash is logged
+    pub hashed: bool,
+}
+
+/// A decoded event log
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedLog {
+    /// Event name
+    pub name: String,
+    /// Canonical event signature
+    pub signature: String,
+    /// Parameters in declaration order
+    pub params: Vec<DecodedLogParam>,
+}
+
+impl DecodedLog {
+    /// Look up a parameter value by name
+    pub fn get(&self, name: &str) -> Option<&AbiValue> {
+        self.params.iter().find(|p| p.name == name).map(|p| &p.value)
+    }
+}
+
+/// Whether an indexed parameter of this type is logged as its hash
+///
+/// Solidity stores the Keccak-256 of reference types in topics, including
+/// static arrays and tuples.
+fn is_hashed_when_indexed(abi_type: &AbiType) -> bool {
+    matches!(
+        abi_type,
+        AbiType::String | AbiType::Bytes | AbiType::Array(_) | AbiType::FixedArray(_, _) | AbiType::Tuple(_)
+    )
+}
+
+/// Decode an event log (e.g. from a transaction receipt) by its ABI
+///
+/// For non-anonymous events `log_topics[0]` must be the event signature
+/// hash, and the remaining topics hold the indexed parameters in order.
+/// Non-indexed parameters are ABI-decoded from `data`.
+pub fn decode_log(log_topics: &[[u8; 32]], data: &[u8], event: &AbiEvent) -> Result<DecodedLog, AbiError> {
+    let signature = event.signature();
+    
+    let indexed_topics = if event.anonymous {
+        log_topics
+    } else {
+        match log_topics.split_first() {
+            Some((topic0, rest)) if *topic0 == AbiSelector::topic_from_signature(&signature) => rest,
+            Some(_) => return Err(AbiError::EventNotFound(format!("topic0 does not match {}", signature))),
+            None => return Err(AbiError::DecodingError("Log has no topics".to_string())),
+        }
+    };
+    
+    let indexed_count = event.inputs.iter().filter(|p| p.indexed).count();
+    if indexed_topics.len() != indexed_count {
+        return Err(AbiError::DecodingError(format!(
+            "Expected {} indexed topics, got {}",
+            indexed_count,
+            indexed_topics.len()
+        )));
+    }
+    
+    let topics: Vec<Vec<u8>> = log_topics.iter().map(|t| t.to_vec()).collect();
+    let values = AbiDecoder::decode_event(event, &topics, data)?;
+    
+    let params = event.inputs.iter()
+        .zip(values)
+        .map(|(input, (name, value))| DecodedLogParam {
+            name,
+            param_type: input.param.param_type.clone(),
+            value,
+            indexed: input.indexed,
+            hashed: input.indexed && is_hashed_when_indexed(&input.param.param_type),
+        })
+        .collect();
+    
+    Ok(DecodedLog {
+        name: event.name.clone(),
+        signature,
+        params,
+    })
+}
+
 /// Common function result decoders
 pub struct FunctionResult;
 
@@ -442,7 +535,25 @@ mod tests {
             panic!("Expected Array");
         }
     }
-    
+
+    #[test]
+    fn test_decode_rejects_oversized_offsets_and_lengths() {
+        // Array length far beyond the data
+        let mut data = vec![0u8; 64];
+        data[24..32].copy_from_slice(&u64::MAX.to_be_bytes());
+        assert!(AbiDecoder::decode_value(&data, &AbiType::Array(Box::new(AbiType::Uint256)), 0).is_err());
+
+        // Bytes length with high bits set, which would truncate to 1
+        let mut data = vec![0u8; 64];
+        data[0] = 1;
+        data[31] = 1;
+        assert!(AbiDecoder::decode_value(&data, &AbiType::Bytes, 0).is_err());
+
+        // Head offset that would wrap around
+        let data = vec![0xffu8; 32];
+        assert!(AbiDecoder::decode(&data, &[AbiType::Bytes]).is_err());
+    }
+
     #[test]
     fn test_decode_mixed_types() {
         // (uint256, string, uint256) = (42, "test", 100)
@@ -481,6 +592,70 @@ mod tests {
         assert_eq!(balance.as_u64(), 1000000);
     }
     
+    #[test]
+    fn test_decode_log_erc20_transfer() {
+        use super::super::parser::KnownAbis;
+        use super::super::selector::KnownTopics;
+        
+        let abi = KnownAbis::erc20();
+        let transfer = abi.event("Transfer").unwrap();
+        
+        let mut from = [0u8; 32];
+        from[12..].copy_from_slice(&[0x11; 20]);
+        let mut to = [0u8; 32];
+        to[12..].copy_from_slice(&[0x22; 20]);
+        let data = U256::from(1_000_000).to_be_bytes();
+        
+        let log = decode_log(&[KnownTopics::TRANSFER, from, to], &data, transfer).unwrap();
+        
+        assert_eq!(log.name, "Transfer");
+        assert_eq!(log.signature, "Transfer(address,address,uint256)");
+        assert_eq!(log.params.len(), 3);
+        assert_eq!(log.params[0].value, AbiValue::Address([0x11; 20]));
+        assert_eq!(log.params[1].value, AbiValue::Address([0x22; 20]));
+        assert!(log.params[0].indexed && !log.params[2].indexed);
+        assert_eq!(log.params[2].value, AbiValue::Uint(U256::from(1_000_000)));
+        
+        // Wrong event signature and missing topics are rejected
+        assert!(decode_log(&[KnownTopics::APPROVAL, from, to], &data, transfer).is_err());
+        assert!(decode_log(&[KnownTopics::TRANSFER, from], &data, transfer).is_err());
+        assert!(decode_log(&[], &data, transfer).is_err());
+    }
+    
+    #[test]
+    fn test_decode_log_indexed_string() {
+        use super::super::parser::ContractAbi;
+        
+        let abi = ContractAbi::from_json(r#"[{
+            "type": "event",
+            "name": "NameRegistered",
+            "inputs": [
+                {"name": "name", "type": "string", "indexed": true},
+                {"name": "owner", "type": "address", "indexed": false},
+                {"name": "label", "type": "string", "indexed": false}
+            ]
+        }]"#).unwrap();
+        let event = abi.event("NameRegistered").unwrap();
+        
+        let topic0 = AbiSelector::topic_from_signature("NameRegistered(string,address,string)");
+        let name_hash = AbiSelector::keccak256(b"alice");
+        
+        // (address, string) = (0x33.., "alice")
+        let mut data = vec![0u8; 128];
+        data[12..32].copy_from_slice(&[0x33; 20]);
+        data[63] = 64; // offset to string
+        data[95] = 5; // string length
+        data[96..101].copy_from_slice(b"alice");
+        
+        let log = decode_log(&[topic0, name_hash], &data, event).unwrap();
+        
+        assert!(log.params[0].hashed);
+        assert_eq!(log.get("name"), Some(&AbiValue::FixedBytes(name_hash.to_vec())));
+        assert_eq!(log.get("owner"), Some(&AbiValue::Address([0x33; 20])));
+        assert_eq!(log.get("label"), Some(&AbiValue::String("alice".to_string())));
+        assert!(!log.params[2].hashed);
+    }
+    
     #[test]
     fn test_encode_decode_roundtrip() {
         use super::super::encoder::AbiEncoder;
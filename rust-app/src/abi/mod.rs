@@ -6,12 +6,14 @@
 //! - Event log decoding
 //! - JSON ABI parsing
 //! - Function selector calculation
+//! - Multicall3 batch encoding
 
 pub mod types;
 pub mod encoder;
 pub mod decoder;
 pub mod parser;
 pub mod selector;
+pub mod multicall;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,161 @@
+//! Multicall3 batching
+//!
+//! Encodes many contract reads as one `aggregate3` call and decodes the
+//! per-call results. Every call is sent with `allowFailure = true`, so a
+//! reverting read shows up as a failed result instead of reverting the batch.
+
+use super::decoder::AbiDecoder;
+use super::encoder::AbiEncoder;
+use super::selector::AbiSelector;
+use super::types::*;
+
+/// `aggregate3((address,bool,bytes)[])`
+pub const AGGREGATE3_SIGNATURE: &str = "aggregate3((address,bool,bytes)[])";
+
+/// Outcome of one call in an `aggregate3` batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call3Result {
+    pub success: bool,
+    pub return_data: Vec<u8>,
+}
+
+fn call3_type() -> AbiType {
+    AbiType::Tuple(vec![AbiType::Address, AbiType::Bool, AbiType::Bytes])
+}
+
+fn result_type() -> AbiType {
+    AbiType::Tuple(vec![AbiType::Bool, AbiType::Bytes])
+}
+
+/// Encode `aggregate3` calldata for `(target, calldata)` pairs
+pub fn build_aggregate3(calls: &[([u8; 20], Vec<u8>)]) -> Vec<u8> {
+    let call3s = calls
+        .iter()
+        .map(|(target, calldata)| AbiValue::Tuple(vec![
+            AbiValue::Address(*target),
+            AbiValue::Bool(true),
+            AbiValue::Bytes(calldata.clone()),
+        ]))
+        .collect();
+
+    let params = AbiEncoder::encode(&[AbiValue::Array(call3s)], &[AbiType::Array(Box::new(call3_type()))])
+        .expect("aggregate3 arguments match their types");
+
+    let mut data = Vec::with_capacity(4 + params.len());
+    data.extend_from_slice(&AbiSelector::selector_from_signature(AGGREGATE3_SIGNATURE));
+    data.extend_from_slice(&params);
+    data
+}
+
+/// Decode the `(bool,bytes)[]` returned by `aggregate3`
+pub fn decode_aggregate3(data: &[u8]) -> Result<Vec<Call3Result>, AbiError> {
+    let values = AbiDecoder::decode(data, &[AbiType::Array(Box::new(result_type()))])?;
+    let Some(AbiValue::Array(results)) = values.into_iter().next() else {
+        return Err(AbiError::DecodingError("Expected (bool,bytes)[]".to_string()));
+    };
+
+    results
+        .into_iter()
+        .map(|result| match result {
+            AbiValue::Tuple(fields) => match fields.as_slice() {
+                [AbiValue::Bool(success), AbiValue::Bytes(return_data)] => Ok(Call3Result {
+                    success: *success,
+                    return_data: return_data.clone(),
+                }),
+                _ => Err(AbiError::DecodingError("Expected (bool,bytes)".to_string())),
+            },
+            _ => Err(AbiError::DecodingError("Expected (bool,bytes)".to_string())),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(value: usize) -> Vec<u8> {
+        let mut w = vec![0u8; 32];
+        w[24..].copy_from_slice(&(value as u64).to_be_bytes());
+        w
+    }
+
+    fn padded(bytes: &[u8]) -> Vec<u8> {
+        let mut out = bytes.to_vec();
+        out.resize(bytes.len().div_ceil(32) * 32, 0);
+        out
+    }
+
+    #[test]
+    fn test_build_aggregate3() {
+        let calls = vec![
+            ([0x11; 20], hex::decode("06fdde03").unwrap()),
+            ([0x22; 20], hex::decode("95d89b41").unwrap()),
+            ([0x33; 20], hex::decode("70a08231000000000000000000000000abababababababababababababababababababab").unwrap()),
+        ];
+        let data = build_aggregate3(&calls);
+
+        assert_eq!(data[..4], [0x82, 0xad, 0x56, 0xcb]);
+        // Head: offset of the array, then its length, then one offset per tuple
+        assert_eq!(data[4..36], word(32)[..]);
+        assert_eq!(data[36..68], word(3)[..]);
+        assert_eq!(data[68..100], word(96)[..]);
+        // Each tuple with 4-byte calldata is 5 words: target, flag, offset, length, data
+        assert_eq!(data[100..132], word(96 + 160)[..]);
+        assert_eq!(data.len(), 4 + 32 * 2 + 32 * 3 + 160 * 2 + 192);
+
+        // First tuple: target, allowFailure, offset of calldata, length, calldata
+        let first = &data[164..];
+        assert_eq!(first[12..32], [0x11; 20]);
+        assert_eq!(first[32..64], word(1)[..]);
+        assert_eq!(first[64..96], word(96)[..]);
+        assert_eq!(first[96..128], word(4)[..]);
+        assert_eq!(first[128..132], [0x06, 0xfd, 0xde, 0x03]);
+
+        let decoded = AbiDecoder::decode(&data[4..], &[AbiType::Array(Box::new(call3_type()))]).unwrap();
+        let AbiValue::Array(tuples) = &decoded[0] else { panic!("expected array") };
+        assert_eq!(tuples[2], AbiValue::Tuple(vec![
+            AbiValue::Address([0x33; 20]),
+            AbiValue::Bool(true),
+            AbiValue::Bytes(calls[2].1.clone()),
+        ]));
+    }
+
+    #[test]
+    fn test_decode_aggregate3() {
+        // Three results: 32-byte uint, a failed call with revert data, and empty data
+        let results: [(bool, Vec<u8>); 3] = [
+            (true, word(6)),
+            (false, hex::decode("08c379a0").unwrap()),
+            (true, Vec::new()),
+        ];
+
+        let mut tails: Vec<Vec<u8>> = Vec::new();
+        for (success, return_data) in &results {
+            let mut tail = word(*success as usize);
+            tail.extend(word(64));
+            tail.extend(word(return_data.len()));
+            tail.extend(padded(return_data));
+            tails.push(tail);
+        }
+
+        let mut data = word(32);
+        data.extend(word(results.len()));
+        let mut offset = 32 * results.len();
+        for tail in &tails {
+            data.extend(word(offset));
+            offset += tail.len();
+        }
+        for tail in &tails {
+            data.extend(tail);
+        }
+
+        let decoded = decode_aggregate3(&data).unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[0], Call3Result { success: true, return_data: word(6) });
+        assert!(!decoded[1].success);
+        assert_eq!(decoded[1].return_data, hex::decode("08c379a0").unwrap());
+        assert_eq!(decoded[2], Call3Result { success: true, return_data: Vec::new() });
+
+        assert!(decode_aggregate3(&data[..40]).is_err());
+    }
+}
@@ -266,6 +266,55 @@ impl AbiType {
     }
 }
 
+impl AbiType {
+    /// Bit width of an integer type (256 for anything else)
+    fn int_bits(&self) -> usize {
+        match self {
+            AbiType::Uint8 | AbiType::Int8 => 8,
+            AbiType::Uint16 | AbiType::Int16 => 16,
+            AbiType::Uint32 | AbiType::Int32 => 32,
+            AbiType::Uint64 | AbiType::Int64 => 64,
+            AbiType::Uint128 | AbiType::Int128 => 128,
+            _ => 256,
+        }
+    }
+
+    /// Byte length of a fixed-size bytes type (32 for anything else)
+    fn fixed_bytes_size(&self) -> usize {
+        match self {
+            AbiType::Bytes1 => 1,
+            AbiType::Bytes2 => 2,
+            AbiType::Bytes3 => 3,
+            AbiType::Bytes4 => 4,
+            AbiType::Bytes8 => 8,
+            AbiType::Bytes16 => 16,
+            AbiType::Bytes20 => 20,
+            AbiType::FixedBytes(size) => *size,
+            _ => 32,
+        }
+    }
+}
+
+/// Whether a big-endian 32-byte value is below 2^bits
+fn fits_bits(be: &[u8; 32], bits: usize) -> bool {
+    if bits >= 256 {
+        return true;
+    }
+    let mut limit = [0u8; 32];
+    limit[31 - bits / 8] = 1 << (bits % 8);
+    *be < limit
+}
+
+/// Whether a big-endian 32-byte value is exactly 2^bits
+fn is_power_of_two(be: &[u8; 32], bits: usize) -> bool {
+    if bits >= 256 {
+        return false;
+    }
+    let mut limit = [0u8; 32];
+    limit[31 - bits / 8] = 1 << (bits % 8);
+    *be == limit
+}
+
 impl fmt::Display for AbiType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.canonical_type())
@@ -340,6 +389,164 @@ impl AbiValue {
         Ok(AbiValue::Bytes(bytes))
     }
     
+    /// JSON representation: integers as decimal strings, addresses and
+    /// bytes as 0x-prefixed hex
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value;
+        
+        match self {
+            AbiValue::Uint(u) => Value::String(u.to_dec()),
+            AbiValue::Int(i) => {
+                let sign = if i.1 && !i.0.is_zero() { "-" } else { "" };
+                Value::String(format!("{}{}", sign, i.0.to_dec()))
+            }
+            AbiValue::Address(addr) => Value::String(format!("0x{}", hex::encode(addr))),
+            AbiValue::Bool(b) => Value::Bool(*b),
+            AbiValue::FixedBytes(bytes) | AbiValue::Bytes(bytes) => {
+                Value::String(format!("0x{}", hex::encode(bytes)))
+            }
+            AbiValue::String(s) => Value::String(s.clone()),
+            AbiValue::Array(values) | AbiValue::Tuple(values) => {
+                Value::Array(values.iter().map(|v| v.to_json()).collect())
+            }
+        }
+    }
+    
+    /// Parse a JSON argument as a value of `abi_type`
+    ///
+    /// The inverse of [`to_json`](Self::to_json): integers may be JSON
+    /// numbers or decimal/0x-hex strings, addresses and bytes are hex
+    /// strings, arrays and tuples are JSON arrays. Integers are range-checked
+    /// against their bit width.
+    pub fn from_json(value: &serde_json::Value, abi_type: &AbiType) -> Result<Self, AbiError> {
+        use serde_json::Value;
+
+        let mismatch = || AbiError::TypeMismatch {
+            expected: abi_type.canonical_type(),
+            got: value.to_string(),
+        };
+
+        match abi_type {
+            AbiType::Uint8 | AbiType::Uint16 | AbiType::Uint32 |
+            AbiType::Uint64 | AbiType::Uint128 | AbiType::Uint256 => {
+                let parsed = match value {
+                    Value::Number(n) => n.as_u64().map(U256::from).ok_or_else(mismatch)?,
+                    Value::String(s) => match Self::uint256_from_str(s.trim())? {
+                        AbiValue::Uint(u) => u,
+                        _ => unreachable!("uint256_from_str returns a Uint"),
+                    },
+                    _ => return Err(mismatch()),
+                };
+                if !fits_bits(&parsed.to_be_bytes(), abi_type.int_bits()) {
+                    return Err(AbiError::Overflow);
+                }
+                Ok(AbiValue::Uint(parsed))
+            }
+
+            AbiType::Int8 | AbiType::Int16 | AbiType::Int32 |
+            AbiType::Int64 | AbiType::Int128 | AbiType::Int256 => {
+                let parsed = match value {
+                    Value::Number(n) => n.as_i64().map(|v| I256::from_i128(v.into())).ok_or_else(mismatch)?,
+                    Value::String(s) => {
+                        let s = s.trim();
+                        let (negative, digits) = match s.strip_prefix('-') {
+                            Some(rest) => (true, rest),
+                            None => (false, s),
+                        };
+                        let abs = match Self::uint256_from_str(digits)? {
+                            AbiValue::Uint(u) => u,
+                            _ => unreachable!("uint256_from_str returns a Uint"),
+                        };
+                        I256(abs, negative && !abs.is_zero())
+                    }
+                    _ => return Err(mismatch()),
+                };
+                // |min| is one more than max, so a negative value gets one extra unit
+                let bits = abi_type.int_bits() - 1;
+                let fits = if parsed.1 {
+                    fits_bits(&parsed.0.to_be_bytes(), bits) || is_power_of_two(&parsed.0.to_be_bytes(), bits)
+                } else {
+                    fits_bits(&parsed.0.to_be_bytes(), bits)
+                };
+                if !fits {
+                    return Err(AbiError::Overflow);
+                }
+                Ok(AbiValue::Int(parsed))
+            }
+
+            AbiType::Address => match value {
+                Value::String(s) => Self::address_from_str(s.trim()),
+                _ => Err(mismatch()),
+            },
+
+            AbiType::Bool => match value {
+                Value::Bool(b) => Ok(AbiValue::Bool(*b)),
+                Value::String(s) if s == "true" || s == "false" => Ok(AbiValue::Bool(s == "true")),
+                _ => Err(mismatch()),
+            },
+
+            AbiType::Bytes1 | AbiType::Bytes2 | AbiType::Bytes3 | AbiType::Bytes4 |
+            AbiType::Bytes8 | AbiType::Bytes16 | AbiType::Bytes20 | AbiType::Bytes32 |
+            AbiType::FixedBytes(_) => {
+                let Value::String(s) = value else { return Err(mismatch()) };
+                let AbiValue::Bytes(bytes) = Self::bytes_from_hex(s.trim())? else {
+                    unreachable!("bytes_from_hex returns Bytes")
+                };
+                let size = abi_type.fixed_bytes_size();
+                if bytes.len() != size {
+                    return Err(AbiError::InvalidValue(format!(
+                        "{} needs {} bytes, got {}",
+                        abi_type.canonical_type(),
+                        size,
+                        bytes.len()
+                    )));
+                }
+                Ok(AbiValue::FixedBytes(bytes))
+            }
+
+            AbiType::Bytes => match value {
+                Value::String(s) => Self::bytes_from_hex(s.trim()),
+                _ => Err(mismatch()),
+            },
+
+            AbiType::String => match value {
+                Value::String(s) => Ok(AbiValue::String(s.clone())),
+                _ => Err(mismatch()),
+            },
+
+            AbiType::Array(inner) => {
+                let Value::Array(items) = value else { return Err(mismatch()) };
+                items.iter()
+                    .map(|item| Self::from_json(item, inner))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(AbiValue::Array)
+            }
+
+            AbiType::FixedArray(inner, size) => {
+                let Value::Array(items) = value else { return Err(mismatch()) };
+                if items.len() != *size {
+                    return Err(mismatch());
+                }
+                items.iter()
+                    .map(|item| Self::from_json(item, inner))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(AbiValue::Array)
+            }
+
+            AbiType::Tuple(components) => {
+                let Value::Array(items) = value else { return Err(mismatch()) };
+                if items.len() != components.len() {
+                    return Err(mismatch());
+                }
+                items.iter()
+                    .zip(components)
+                    .map(|(item, component)| Self::from_json(item, component))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(AbiValue::Tuple)
+            }
+        }
+    }
+
     /// Get the type of this value
     pub fn get_type(&self) -> AbiType {
         match self {
@@ -485,6 +692,37 @@ impl U256 {
     pub fn to_hex(&self) -> String {
         hex::encode(self.to_be_bytes())
     }
+    
+    /// Divide by a u64, returning (quotient, remainder)
+    pub fn div_rem_u64(&self, divisor: u64) -> (U256, u64) {
+        let mut quotient = [0u64; 4];
+        let mut rem = 0u128;
+        
+        for i in (0..4).rev() {
+            let cur = (rem << 64) | self.0[i] as u128;
+            quotient[i] = (cur / divisor as u128) as u64;
+            rem = cur % divisor as u128;
+        }
+        
+        (U256(quotient), rem as u64)
+    }
+    
+    /// To decimal string
+    pub fn to_dec(&self) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        
+        let mut digits = Vec::new();
+        let mut value = *self;
+        while !value.is_zero() {
+            let (quotient, rem) = value.div_rem_u64(10);
+            digits.push(b'0' + rem as u8);
+            value = quotient;
+        }
+        digits.reverse();
+        String::from_utf8(digits).unwrap()
+    }
 }
 
 /// 256-bit signed integer
@@ -794,6 +1032,28 @@ mod tests {
         assert_eq!(bytes[31], 0);
     }
     
+    #[test]
+    fn test_u256_to_dec() {
+        assert_eq!(U256::ZERO.to_dec(), "0");
+        assert_eq!(U256::from(1_000_000).to_dec(), "1000000");
+        assert_eq!(
+            U256::MAX.to_dec(),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+        let large = "340282366920938463463374607431768211456"; // 2^128
+        assert_eq!(U256::from_dec(large).unwrap().to_dec(), large);
+    }
+    
+    #[test]
+    fn test_abi_value_to_json() {
+        assert_eq!(AbiValue::Int(I256::from(-42)).to_json(), serde_json::json!("-42"));
+        assert_eq!(AbiValue::Address([0xab; 20]).to_json(), serde_json::json!(format!("0x{}", "ab".repeat(20))));
+        assert_eq!(
+            AbiValue::Tuple(vec![AbiValue::Bool(true), AbiValue::Bytes(vec![0xde, 0xad])]).to_json(),
+            serde_json::json!([true, "0xdead"])
+        );
+    }
+    
     #[test]
     fn test_abi_value_address() {
         let addr = AbiValue::address_from_str("0x1234567890123456789012345678901234567890").unwrap();
@@ -814,4 +1074,33 @@ mod tests {
             "(uint256,bool)"
         );
     }
+    
+    #[test]
+    fn test_abi_value_from_json() {
+        use serde_json::json;
+        
+        assert_eq!(AbiValue::from_json(&json!(255), &AbiType::Uint8).unwrap(), AbiValue::uint256(255));
+        assert_eq!(AbiValue::from_json(&json!("0xff"), &AbiType::Uint256).unwrap(), AbiValue::uint256(255));
+        assert!(AbiValue::from_json(&json!(256), &AbiType::Uint8).is_err());
+        assert!(AbiValue::from_json(&json!(-1), &AbiType::Uint256).is_err());
+        
+        assert_eq!(AbiValue::from_json(&json!(-128), &AbiType::Int8).unwrap(), AbiValue::Int(I256::from(-128)));
+        assert_eq!(AbiValue::from_json(&json!("-5"), &AbiType::Int256).unwrap(), AbiValue::Int(I256::from(-5)));
+        assert!(AbiValue::from_json(&json!(128), &AbiType::Int8).is_err());
+        assert!(AbiValue::from_json(&json!(-129), &AbiType::Int8).is_err());
+        
+        assert_eq!(
+            AbiValue::from_json(&json!("0x01020304"), &AbiType::Bytes4).unwrap(),
+            AbiValue::FixedBytes(vec![1, 2, 3, 4])
+        );
+        assert!(AbiValue::from_json(&json!("0x0102"), &AbiType::Bytes4).is_err());
+        
+        let tuple_type = AbiType::Tuple(vec![
+            AbiType::Array(Box::new(AbiType::Bool)),
+            AbiType::String,
+        ]);
+        let value = AbiValue::from_json(&json!([[true, false], "hi"]), &tuple_type).unwrap();
+        assert_eq!(value.to_json(), json!([[true, false], "hi"]));
+        assert!(AbiValue::from_json(&json!([[true]]), &tuple_type).is_err());
+    }
 }
@@ -0,0 +1,303 @@
+//! ENS (Ethereum Name Service) Resolution
+//!
+//! Forward resolution (`vitalik.eth` -> address) and reverse lookup
+//! (address -> primary name) through the ENS registry on Ethereum mainnet.
+//!
+//! Names are normalized by lowercasing only; full UTS-46 (ENSIP-15)
+//! normalization of emoji and confusables is not applied.
+
+use crate::error::{HawalaError, HawalaResult};
+use crate::types::Chain;
+use crate::utils::network_config::{service_url, ServiceKind};
+use crate::utils::{keccak256, to_checksum_address};
+use std::time::Duration;
+
+/// ENS registry (same address on mainnet and testnets)
+pub const ENS_REGISTRY: &str = "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+
+/// Default mainnet RPC used for lookups
+const ENS_RPC_URL: &str = "https://eth.llamarpc.com";
+
+/// `resolver(bytes32)` on the registry
+const RESOLVER_SELECTOR: &str = "0178b8bf";
+/// `addr(bytes32)` on a resolver
+const ADDR_SELECTOR: &str = "3b3b57de";
+/// `name(bytes32)` on a reverse resolver
+const NAME_SELECTOR: &str = "691f3431";
+
+/// EIP-137 namehash
+pub fn namehash(name: &str) -> [u8; 32] {
+    let mut node = [0u8; 32];
+    if name.is_empty() {
+        return node;
+    }
+    for label in name.rsplit('.') {
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(&node);
+        buf[32..].copy_from_slice(&keccak256(label.as_bytes()));
+        node = keccak256(&buf);
+    }
+    node
+}
+
+/// Resolve an ENS name to a checksummed address
+///
+/// Returns `None` when the name has no resolver or no address record.
+pub fn resolve(name: &str) -> HawalaResult<Option<String>> {
+    resolve_with(name, mainnet_eth_call)
+}
+
+/// Look up the primary ENS name of an address
+///
+/// Returns `None` when no reverse record is set, or when the claimed name
+/// does not resolve back to the address (reverse records are unverified).
+pub fn reverse_lookup(address: &str) -> HawalaResult<Option<String>> {
+    reverse_lookup_with(address, mainnet_eth_call)
+}
+
+/// [`resolve`] with a caller-supplied `eth_call(to, data) -> result`
+pub fn resolve_with(
+    name: &str,
+    eth_call: impl Fn(&str, &str) -> HawalaResult<String>,
+) -> HawalaResult<Option<String>> {
+    let name = normalize_name(name)?;
+    let node = namehash(&name);
+
+    let Some(resolver) = lookup_resolver(&node, &eth_call)? else {
+        return Ok(None);
+    };
+
+    let result = decode_result(&eth_call(&resolver, &call_data(ADDR_SELECTOR, &node))?)?;
+    Ok(decode_nonzero_address(&result))
+}
+
+/// [`reverse_lookup`] with a caller-supplied `eth_call(to, data) -> result`
+pub fn reverse_lookup_with(
+    address: &str,
+    eth_call: impl Fn(&str, &str) -> HawalaResult<String>,
+) -> HawalaResult<Option<String>> {
+    let address_bytes = parse_address(address)?;
+    let node = namehash(&format!("{}.addr.reverse", hex::encode(address_bytes)));
+
+    let Some(resolver) = lookup_resolver(&node, &eth_call)? else {
+        return Ok(None);
+    };
+
+    let result = decode_result(&eth_call(&resolver, &call_data(NAME_SELECTOR, &node))?)?;
+    let name = match decode_string(&result) {
+        Some(name) if !name.is_empty() => name,
+        _ => return Ok(None),
+    };
+
+    // Anyone can claim any name in their reverse record; only trust it if
+    // the name points back at this address
+    let forward = match resolve_with(&name, &eth_call) {
+        Ok(forward) => forward,
+        Err(_) => return Ok(None),
+    };
+    match forward {
+        Some(forward) if forward.eq_ignore_ascii_case(&to_checksum_address(&address_bytes)) => Ok(Some(name)),
+        _ => Ok(None),
+    }
+}
+
+fn normalize_name(name: &str) -> HawalaResult<String> {
+    let name = name.trim().trim_end_matches('.').to_lowercase();
+    if name.is_empty() || name.split('.').any(|label| label.is_empty()) {
+        return Err(HawalaError::invalid_input(format!("Invalid ENS name: {}", name)));
+    }
+    Ok(name)
+}
+
+fn parse_address(address: &str) -> HawalaResult<[u8; 20]> {
+    let hex_str = address.trim().strip_prefix("0x").unwrap_or(address.trim());
+    let bytes = hex::decode(hex_str)
+        .map_err(|_| HawalaError::invalid_input(format!("Invalid address: {}", address)))?;
+    bytes
+        .try_into()
+        .map_err(|_| HawalaError::invalid_input(format!("Invalid address: {}", address)))
+}
+
+/// Registry lookup; `None` when no resolver is set
+fn lookup_resolver(
+    node: &[u8; 32],
+    eth_call: &impl Fn(&str, &str) -> HawalaResult<String>,
+) -> HawalaResult<Option<String>> {
+    let result = decode_result(&eth_call(ENS_REGISTRY, &call_data(RESOLVER_SELECTOR, node))?)?;
+    Ok(decode_nonzero_address(&result))
+}
+
+fn call_data(selector: &str, node: &[u8; 32]) -> String {
+    format!("0x{}{}", selector, hex::encode(node))
+}
+
+fn decode_result(result: &str) -> HawalaResult<Vec<u8>> {
+    hex::decode(result.trim_start_matches("0x"))
+        .map_err(|e| HawalaError::parse_error(format!("Invalid eth_call result: {}", e)))
+}
+
+/// Checksummed ABI `address` return value; `None` for the zero address or
+/// empty data (the target has no code)
+fn decode_nonzero_address(data: &[u8]) -> Option<String> {
+    let addr = data.get(12..32)?;
+    if addr.iter().all(|b| *b == 0) {
+        return None;
+    }
+    Some(to_checksum_address(addr))
+}
+
+/// ABI `string` return value
+fn decode_string(data: &[u8]) -> Option<String> {
+    let read_usize = |at: usize| -> Option<usize> {
+        let word = data.get(at..at.checked_add(32)?)?;
+        if word[..24].iter().any(|b| *b != 0) {
+            return None;
+        }
+        Some(u64::from_be_bytes(word[24..].try_into().ok()?) as usize)
+    };
+
+    let offset = read_usize(0)?;
+    let len = read_usize(offset)?;
+    let start = offset.checked_add(32)?;
+    let bytes = data.get(start..start.checked_add(len)?)?;
+    String::from_utf8(bytes.to_vec()).ok()
+}
+
+fn mainnet_eth_call(to: &str, data: &str) -> HawalaResult<String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let payload = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "eth_call",
+        "params": [{ "to": to, "data": data }, "latest"],
+        "id": 1
+    });
+
+    let json: serde_json::Value = client
+        .post(service_url(Chain::Ethereum, ServiceKind::Rpc, ENS_RPC_URL))
+        .json(&payload)
+        .send()
+        .map_err(|e| HawalaError::network_error(e.to_string()))?
+        .json()
+        .map_err(|e| HawalaError::parse_error(e.to_string()))?;
+
+    if let Some(error) = json.get("error") {
+        return Err(HawalaError::network_error(format!("eth_call failed: {}", error)));
+    }
+    json["result"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| HawalaError::parse_error("eth_call returned no result"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const VITALIK: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+    const PUBLIC_RESOLVER: &str = "0x231b0Ee14048e9dCcD1d247744d114a4EB5E8E63";
+
+    fn word(bytes: &[u8]) -> String {
+        format!("{:0>64}", hex::encode(bytes))
+    }
+
+    fn abi_string(value: &str) -> String {
+        let mut data = hex::encode(value.as_bytes());
+        while !data.len().is_multiple_of(64) {
+            data.push('0');
+        }
+        format!("0x{}{}{}", word(&[0x20]), word(&[value.len() as u8]), data)
+    }
+
+    /// eth_call backed by a fixed (to, data) -> result table
+    fn mock_rpc(responses: HashMap<(String, String), String>) -> impl Fn(&str, &str) -> HawalaResult<String> {
+        move |to, data| {
+            Ok(responses
+                .get(&(to.to_lowercase(), data.to_lowercase()))
+                .cloned()
+                .unwrap_or_else(|| format!("0x{}", word(&[])))) // Unset records read as zero
+        }
+    }
+
+    fn records() -> HashMap<(String, String), String> {
+        let forward = namehash("vitalik.eth");
+        let reverse = namehash("d8da6bf26964af9d7eed9e03e53415d37aa96045.addr.reverse");
+        let resolver = format!("0x{}", word(&hex::decode(&PUBLIC_RESOLVER[2..]).unwrap()));
+        let key = |to: &str, selector: &str, node: &[u8; 32]| (to.to_lowercase(), call_data(selector, node));
+
+        HashMap::from([
+            (key(ENS_REGISTRY, RESOLVER_SELECTOR, &forward), resolver.clone()),
+            (key(PUBLIC_RESOLVER, ADDR_SELECTOR, &forward), format!("0x{}", word(&hex::decode(&VITALIK[2..]).unwrap()))),
+            (key(ENS_REGISTRY, RESOLVER_SELECTOR, &reverse), resolver),
+            (key(PUBLIC_RESOLVER, NAME_SELECTOR, &reverse), abi_string("vitalik.eth")),
+        ])
+    }
+
+    #[test]
+    fn test_namehash() {
+        assert_eq!(namehash(""), [0u8; 32]);
+        assert_eq!(hex::encode(namehash("eth")), "93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae");
+        assert_eq!(
+            hex::encode(namehash("vitalik.eth")),
+            "ee6c4522aab0003e8d14cd40a6af439055fd2577951148c14b6cea9a53475835"
+        );
+    }
+
+    #[test]
+    fn test_forward_resolution() {
+        let rpc = mock_rpc(records());
+        assert_eq!(resolve_with("vitalik.eth", &rpc).unwrap(), Some(VITALIK.to_string()));
+        assert_eq!(resolve_with(" Vitalik.ETH. ", &rpc).unwrap(), Some(VITALIK.to_string()));
+    }
+
+    #[test]
+    fn test_name_without_resolver() {
+        let rpc = mock_rpc(records());
+        assert_eq!(resolve_with("unregistered-name.eth", &rpc).unwrap(), None);
+
+        // Registry call against a node with no code returns empty data
+        assert_eq!(resolve_with("vitalik.eth", |_: &str, _: &str| Ok("0x".to_string())).unwrap(), None);
+    }
+
+    #[test]
+    fn test_reverse_lookup() {
+        let rpc = mock_rpc(records());
+        assert_eq!(reverse_lookup_with(VITALIK, &rpc).unwrap(), Some("vitalik.eth".to_string()));
+        assert_eq!(
+            reverse_lookup_with("0x742d35Cc6634C0532925a3b844Bc454e4438f44e", &rpc).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_reverse_lookup_requires_forward_match() {
+        // Reverse record claims a name that resolves elsewhere
+        let other = "742d35cc6634c0532925a3b844bc454e4438f44e";
+        let reverse = namehash(&format!("{}.addr.reverse", other));
+        let resolver = format!("0x{}", word(&hex::decode(&PUBLIC_RESOLVER[2..]).unwrap()));
+
+        let mut responses = records();
+        responses.insert((ENS_REGISTRY.to_lowercase(), call_data(RESOLVER_SELECTOR, &reverse)), resolver);
+        responses.insert((PUBLIC_RESOLVER.to_lowercase(), call_data(NAME_SELECTOR, &reverse)), abi_string("vitalik.eth"));
+
+        assert_eq!(reverse_lookup_with(&format!("0x{}", other), mock_rpc(responses)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let rpc = mock_rpc(HashMap::new());
+        assert!(resolve_with("", &rpc).is_err());
+        assert!(resolve_with("vitalik..eth", &rpc).is_err());
+        assert!(reverse_lookup_with("0x1234", &rpc).is_err());
+    }
+
+    #[test]
+    fn test_decode_string() {
+        let data = hex::decode(&abi_string("nick.eth")[2..]).unwrap();
+        assert_eq!(decode_string(&data), Some("nick.eth".to_string()));
+        assert_eq!(decode_string(&data[..40]), None);
+    }
+}
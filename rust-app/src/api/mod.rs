@@ -2,7 +2,9 @@
 //!
 //! Unified blockchain API clients with fallback support.
 
-mod providers;
+pub mod providers;
+pub mod ens;
+pub mod multicall;
 
 pub use providers::*;
 
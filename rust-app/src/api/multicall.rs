@@ -0,0 +1,142 @@
+//! Batched EVM Reads
+//!
+//! Runs many contract reads as a single `eth_call` to the chain's Multicall3
+//! deployment, so a portfolio screen costs one RPC round-trip instead of one
+//! per token.
+
+use crate::abi::multicall::{build_aggregate3, decode_aggregate3, Call3Result};
+use crate::balances::{create_http_client, get_rpc_endpoints};
+use crate::error::{HawalaError, HawalaResult};
+use crate::types::Chain;
+use crate::utils::network_config::{evm_rpc_error, with_failover};
+
+/// Canonical Multicall3 address (same on nearly every EVM chain)
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+
+/// Multicall3 on zkSync Era, which can't use the canonical deployment
+const MULTICALL3_ZKSYNC: &str = "0xF9cda624FBC7e059355ce98a31693d299FACd963";
+
+/// Multicall3 address for `chain`
+pub fn multicall3_address(chain: Chain) -> HawalaResult<&'static str> {
+    match chain {
+        Chain::ZkSync => Ok(MULTICALL3_ZKSYNC),
+        c if c.is_evm() => Ok(MULTICALL3_ADDRESS),
+        other => Err(HawalaError::invalid_input(format!("Multicall is EVM-only, got {:?}", other))),
+    }
+}
+
+/// Execute `(target, calldata)` reads in one Multicall3 `aggregate3` call
+///
+/// Results are in call order; a reverted read has `success == false`
+/// rather than failing the batch.
+pub fn multicall(chain: Chain, calls: &[([u8; 20], Vec<u8>)]) -> HawalaResult<Vec<Call3Result>> {
+    let endpoints = get_rpc_endpoints(chain);
+    multicall_with(chain, calls, |to, data| {
+        with_failover(&endpoints, |endpoint| eth_call(endpoint, to, data))
+    })
+}
+
+/// [`multicall`] with a caller-supplied `eth_call(to, data) -> result`
+pub fn multicall_with(
+    chain: Chain,
+    calls: &[([u8; 20], Vec<u8>)],
+    eth_call: impl Fn(&str, &str) -> HawalaResult<String>,
+) -> HawalaResult<Vec<Call3Result>> {
+    let target = multicall3_address(chain)?;
+    if calls.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let data = format!("0x{}", hex::encode(build_aggregate3(calls)));
+    let result = eth_call(target, &data)?;
+    let bytes = hex::decode(result.trim_start_matches("0x"))
+        .map_err(|e| HawalaError::parse_error(format!("Invalid eth_call result: {}", e)))?;
+
+    let results = decode_aggregate3(&bytes)
+        .map_err(|e| HawalaError::parse_error(format!("Invalid aggregate3 result: {}", e)))?;
+    if results.len() != calls.len() {
+        return Err(HawalaError::parse_error(format!(
+            "aggregate3 returned {} results for {} calls",
+            results.len(),
+            calls.len()
+        )));
+    }
+    Ok(results)
+}
+
+fn eth_call(rpc_url: &str, to: &str, data: &str) -> HawalaResult<String> {
+    let client = create_http_client()?;
+
+    let payload = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "eth_call",
+        "params": [{ "to": to, "data": data }, "latest"],
+        "id": 1
+    });
+
+    let resp: serde_json::Value = client.post(rpc_url)
+        .json(&payload)
+        .send()
+        .map_err(|e| HawalaError::network_error(format!("RPC request failed: {}", e)))?
+        .json()
+        .map_err(|e| HawalaError::parse_error(format!("Failed to parse RPC response: {}", e)))?;
+
+    if let Some(error) = resp.get("error") {
+        return Err(evm_rpc_error("eth_call", error));
+    }
+    resp["result"].as_str()
+        .map(str::to_string)
+        .ok_or_else(|| HawalaError::parse_error("eth_call returned no result"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multicall_with_canned_result() {
+        let calls = vec![
+            ([0x11; 20], vec![0x31, 0x3c, 0xe5, 0x67]),
+            ([0x22; 20], vec![0x31, 0x3c, 0xe5, 0x67]),
+        ];
+        // (bool,bytes)[] with [(true, uint 6), (false, 0x)]
+        let canned = [
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "00000000000000000000000000000000000000000000000000000000000000c0",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000006",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        ]
+        .concat();
+
+        let expected_data = format!("0x{}", hex::encode(build_aggregate3(&calls)));
+        let results = multicall_with(Chain::Base, &calls, |to, data| {
+            assert_eq!(to, MULTICALL3_ADDRESS);
+            assert_eq!(data, expected_data);
+            Ok(format!("0x{}", canned))
+        })
+        .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].success);
+        assert_eq!(results[0].return_data[31], 6);
+        assert!(!results[1].success);
+        assert!(results[1].return_data.is_empty());
+
+        // A result count that doesn't match the calls is rejected
+        assert!(multicall_with(Chain::Base, &calls[..1], |_, _| Ok(format!("0x{}", canned))).is_err());
+    }
+
+    #[test]
+    fn test_multicall3_address() {
+        assert_eq!(multicall3_address(Chain::Ethereum).unwrap(), MULTICALL3_ADDRESS);
+        assert_eq!(multicall3_address(Chain::ZkSync).unwrap(), MULTICALL3_ZKSYNC);
+        assert!(multicall3_address(Chain::Solana).is_err());
+    }
+}
@@ -0,0 +1,331 @@
+//! Electrum Protocol Provider
+//!
+//! Balance and UTXO lookups against a self-hosted Electrum/Fulcrum server,
+//! speaking newline-delimited JSON-RPC over TCP or TLS. When a server is
+//! configured for Bitcoin or Litecoin it is tried before the HTTP explorers.
+
+use crate::error::{HawalaError, HawalaResult};
+use crate::types::*;
+use bitcoin::ScriptBuf;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+use std::time::Duration;
+
+/// Connect, read and write timeout
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Protocol version requested in the `server.version` handshake
+const PROTOCOL_VERSION: &str = "1.4";
+
+/// Longest response line accepted, so a hostile server cannot exhaust memory
+const MAX_LINE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Electrum server for a chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectrumServer {
+    pub host: String,
+    pub port: u16,
+    /// Verify the server certificate against the public web PKI; self-signed
+    /// certificates are not accepted
+    #[serde(default)]
+    pub tls: bool,
+}
+
+/// Confirmed and unconfirmed balance in base units; the unconfirmed part is
+/// negative while a spend is in the mempool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectrumBalance {
+    pub confirmed: u64,
+    pub unconfirmed: i64,
+}
+
+impl ElectrumBalance {
+    pub fn total(&self) -> u64 {
+        self.confirmed.saturating_add_signed(self.unconfirmed)
+    }
+}
+
+/// Unspent output as returned by `blockchain.scripthash.listunspent`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectrumUnspent {
+    pub tx_hash: String,
+    pub tx_pos: u32,
+    /// Confirmation height; 0 (or -1 with unconfirmed parents) in the mempool
+    pub height: i64,
+    pub value: u64,
+}
+
+static SERVERS: OnceLock<RwLock<HashMap<Chain, ElectrumServer>>> = OnceLock::new();
+
+fn servers() -> &'static RwLock<HashMap<Chain, ElectrumServer>> {
+    SERVERS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Use `server` for a chain's balance and UTXO lookups, or go back to the
+/// HTTP explorers with `None`
+pub fn set_electrum_server(chain: Chain, server: Option<ElectrumServer>) -> HawalaResult<()> {
+    if !matches!(chain, Chain::Bitcoin | Chain::BitcoinTestnet | Chain::Litecoin) {
+        return Err(HawalaError::invalid_input(format!(
+            "Electrum servers are only supported for Bitcoin and Litecoin, not {:?}",
+            chain
+        )));
+    }
+    if let Some(server) = &server {
+        if server.host.trim().is_empty() || server.port == 0 {
+            return Err(HawalaError::invalid_input("Electrum server needs a host and port"));
+        }
+    }
+
+    let mut servers = servers().write().unwrap_or_else(PoisonError::into_inner);
+    match server {
+        Some(server) => servers.insert(chain, server),
+        None => servers.remove(&chain),
+    };
+    Ok(())
+}
+
+/// The server configured for a chain, if any
+pub fn electrum_server(chain: Chain) -> Option<ElectrumServer> {
+    servers().read().unwrap_or_else(PoisonError::into_inner).get(&chain).cloned()
+}
+
+/// Balance of an address from the chain's configured server
+pub fn fetch_electrum_balance(address: &str, chain: Chain) -> HawalaResult<ElectrumBalance> {
+    let script = address_script(address, chain)?;
+    connect_configured(chain)?.get_balance(script.as_bytes())
+}
+
+/// Unspent outputs of an address from the chain's configured server
+pub fn fetch_electrum_utxos(address: &str, chain: Chain) -> HawalaResult<Vec<ElectrumUnspent>> {
+    let script = address_script(address, chain)?;
+    connect_configured(chain)?.list_unspent(script.as_bytes())
+}
+
+/// Electrum script hash: SHA-256 of the script pubkey, byte-reversed, in hex
+pub fn script_hash(script_pubkey: &[u8]) -> String {
+    let mut hash = Sha256::digest(script_pubkey).to_vec();
+    hash.reverse();
+    hex::encode(hash)
+}
+
+fn connect_configured(chain: Chain) -> HawalaResult<ElectrumClient> {
+    let server = electrum_server(chain)
+        .ok_or_else(|| HawalaError::invalid_input(format!("No Electrum server configured for {:?}", chain)))?;
+    ElectrumClient::connect(&server)
+}
+
+fn address_script(address: &str, chain: Chain) -> HawalaResult<ScriptBuf> {
+    let invalid = |e: &dyn std::fmt::Display| HawalaError::invalid_input(format!("Invalid address {}: {}", address, e));
+    match chain {
+        Chain::Bitcoin | Chain::BitcoinTestnet => {
+            let network = if chain == Chain::Bitcoin { bitcoin::Network::Bitcoin } else { bitcoin::Network::Testnet };
+            let address = bitcoin::Address::from_str(address)
+                .map_err(|e| invalid(&e))?
+                .require_network(network)
+                .map_err(|e| invalid(&e))?;
+            Ok(address.script_pubkey())
+        }
+        Chain::Litecoin => crate::litecoin_wallet::decode_litecoin_address(address).map_err(|e| invalid(&e)),
+        _ => Err(HawalaError::invalid_input(format!("Electrum not supported for {:?}", chain))),
+    }
+}
+
+trait Stream: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Stream for T {}
+
+/// Connection to one Electrum server
+pub struct ElectrumClient {
+    stream: BufReader<Box<dyn Stream>>,
+    next_id: u64,
+}
+
+impl ElectrumClient {
+    /// Connect and negotiate the protocol version
+    pub fn connect(server: &ElectrumServer) -> HawalaResult<Self> {
+        let addr = (server.host.as_str(), server.port)
+            .to_socket_addrs()
+            .map_err(|e| HawalaError::network_error(format!("Cannot resolve {}: {}", server.host, e)))?
+            .next()
+            .ok_or_else(|| HawalaError::network_error(format!("Cannot resolve {}", server.host)))?;
+
+        let tcp = TcpStream::connect_timeout(&addr, TIMEOUT).map_err(io_error)?;
+        tcp.set_read_timeout(Some(TIMEOUT)).map_err(io_error)?;
+        tcp.set_write_timeout(Some(TIMEOUT)).map_err(io_error)?;
+
+        let stream: Box<dyn Stream> = if server.tls {
+            Box::new(tls_stream(&server.host, tcp)?)
+        } else {
+            Box::new(tcp)
+        };
+
+        let mut client = Self { stream: BufReader::new(stream), next_id: 0 };
+        client.call::<serde_json::Value>("server.version", serde_json::json!(["hawala", PROTOCOL_VERSION]))?;
+        Ok(client)
+    }
+
+    /// `blockchain.scripthash.get_balance`
+    pub fn get_balance(&mut self, script_pubkey: &[u8]) -> HawalaResult<ElectrumBalance> {
+        self.call("blockchain.scripthash.get_balance", serde_json::json!([script_hash(script_pubkey)]))
+    }
+
+    /// `blockchain.scripthash.listunspent`
+    pub fn list_unspent(&mut self, script_pubkey: &[u8]) -> HawalaResult<Vec<ElectrumUnspent>> {
+        self.call("blockchain.scripthash.listunspent", serde_json::json!([script_hash(script_pubkey)]))
+    }
+
+    fn call<T: DeserializeOwned>(&mut self, method: &str, params: serde_json::Value) -> HawalaResult<T> {
+        self.next_id += 1;
+        let id = self.next_id;
+        let request = serde_json::json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+
+        let stream = self.stream.get_mut();
+        stream.write_all(format!("{}\n", request).as_bytes()).map_err(io_error)?;
+        stream.flush().map_err(io_error)?;
+
+        loop {
+            let line = read_line_bounded(&mut self.stream, MAX_LINE_BYTES)?
+                .ok_or_else(|| HawalaError::network_error("Electrum server closed the connection"))?;
+            let response: serde_json::Value = serde_json::from_str(&line)
+                .map_err(|e| HawalaError::parse_error(format!("Invalid Electrum response: {}", e)))?;
+
+            // Skip subscription notifications and stale replies
+            if response.get("id").and_then(|v| v.as_u64()) != Some(id) {
+                continue;
+            }
+            if let Some(error) = response.get("error").filter(|e| !e.is_null()) {
+                return Err(HawalaError::network_error(format!("{} failed: {}", method, error)));
+            }
+            return serde_json::from_value(response["result"].clone())
+                .map_err(|e| HawalaError::parse_error(format!("Unexpected {} result: {}", method, e)));
+        }
+    }
+}
+
+/// Read one newline-terminated line of at most `limit` bytes; `None` at EOF
+fn read_line_bounded<R: BufRead>(reader: &mut R, limit: u64) -> HawalaResult<Option<String>> {
+    let mut line = String::new();
+    // One extra byte tells a line of exactly `limit` bytes from a longer one
+    if reader.take(limit + 1).read_line(&mut line).map_err(io_error)? == 0 {
+        return Ok(None);
+    }
+    if line.len() as u64 > limit {
+        return Err(HawalaError::network_error(format!(
+            "Electrum response exceeds {} bytes", limit
+        )));
+    }
+    Ok(Some(line))
+}
+
+fn tls_stream(host: &str, tcp: TcpStream) -> HawalaResult<rustls::StreamOwned<rustls::ClientConnection, TcpStream>> {
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+        rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(ta.subject, ta.spki, ta.name_constraints)
+    }));
+    let config = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    let name = rustls::ServerName::try_from(host)
+        .map_err(|_| HawalaError::invalid_input(format!("Invalid TLS server name: {}", host)))?;
+    let connection = rustls::ClientConnection::new(Arc::new(config), name)
+        .map_err(|e| HawalaError::network_error(format!("TLS setup failed: {}", e)))?;
+    Ok(rustls::StreamOwned::new(connection, tcp))
+}
+
+fn io_error(e: std::io::Error) -> HawalaError {
+    HawalaError::network_error(format!("Electrum connection failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    /// Serve one connection, answering the handshake and the two scripthash
+    /// methods; a notification precedes every reply
+    fn mock_server() -> ElectrumServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            for line in BufReader::new(stream).lines() {
+                let request: serde_json::Value = serde_json::from_str(&line.unwrap()).unwrap();
+                let result = match request["method"].as_str().unwrap() {
+                    "server.version" => serde_json::json!(["Fulcrum 1.9.8", "1.4"]),
+                    "blockchain.scripthash.get_balance" => serde_json::json!({ "confirmed": 150000, "unconfirmed": -20000 }),
+                    "blockchain.scripthash.listunspent" => serde_json::json!([
+                        { "tx_hash": "aa".repeat(32), "tx_pos": 1, "height": 840000, "value": 100000 },
+                        { "tx_hash": "bb".repeat(32), "tx_pos": 0, "height": 0, "value": 30000 },
+                    ]),
+                    _ => serde_json::Value::Null,
+                };
+                let notification = serde_json::json!({ "jsonrpc": "2.0", "method": "blockchain.headers.subscribe", "params": [] });
+                let reply = serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result });
+                writer.write_all(format!("{}\n{}\n", notification, reply).as_bytes()).unwrap();
+            }
+        });
+
+        ElectrumServer { host: "127.0.0.1".to_string(), port, tls: false }
+    }
+
+    #[test]
+    fn test_read_line_bounded() {
+        let mut reader = std::io::Cursor::new(b"{\"id\":1}\n{\"id\":2}\n".to_vec());
+        assert_eq!(read_line_bounded(&mut reader, 9).unwrap().as_deref(), Some("{\"id\":1}\n"));
+        assert!(read_line_bounded(&mut reader, 8).is_err());
+
+        let mut reader = std::io::Cursor::new(Vec::new());
+        assert_eq!(read_line_bounded(&mut reader, 8).unwrap(), None);
+    }
+
+    #[test]
+    fn test_script_hash() {
+        // Example from the Electrum protocol documentation
+        let script = hex::decode("76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac").unwrap();
+        assert_eq!(script_hash(&script), "8b01df4e368ea28f8dc0423bcf7a4923e3a12d307c875e47a0cfbf90b5c39161");
+    }
+
+    #[test]
+    fn test_mock_server_balance_and_utxos() {
+        let mut client = ElectrumClient::connect(&mock_server()).unwrap();
+        let script = hex::decode("0014751e76e8199196d454941c45d1b3a323f1433bd6").unwrap();
+
+        let balance = client.get_balance(&script).unwrap();
+        assert_eq!(balance, ElectrumBalance { confirmed: 150000, unconfirmed: -20000 });
+        assert_eq!(balance.total(), 130000);
+
+        let utxos = client.list_unspent(&script).unwrap();
+        assert_eq!(utxos.len(), 2);
+        assert_eq!(utxos[0].tx_pos, 1);
+        assert_eq!(utxos[0].value, 100000);
+        assert_eq!(utxos[1].height, 0);
+    }
+
+    #[test]
+    fn test_configured_server_lookup() {
+        let address = "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx";
+        assert!(fetch_electrum_balance(address, Chain::BitcoinTestnet).is_err());
+
+        set_electrum_server(Chain::BitcoinTestnet, Some(mock_server())).unwrap();
+        let balance = fetch_electrum_balance(address, Chain::BitcoinTestnet);
+        set_electrum_server(Chain::BitcoinTestnet, None).unwrap();
+
+        assert_eq!(balance.unwrap().confirmed, 150000);
+        assert!(electrum_server(Chain::BitcoinTestnet).is_none());
+        assert!(set_electrum_server(Chain::Ethereum, Some(ElectrumServer {
+            host: "localhost".to_string(),
+            port: 50001,
+            tls: false,
+        })).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+//! Provider Health Checks
+//!
+//! Pings every configured RPC/explorer endpoint for a chain with a cheap
+//! block-tip query and reports whether it answered, how fast, and at what
+//! height. Results are also fed into the network config's health cache.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{HawalaError, HawalaResult};
+use crate::types::*;
+use crate::utils::network_config::{get_network_config, NetworkConfig, ServiceKind};
+
+use super::default_evm_rpc;
+
+/// Per-provider timeout for a health check
+pub const PROVIDER_HEALTH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Health of one provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderHealth {
+    pub url: String,
+    pub kind: ServiceKind,
+    pub up: bool,
+    /// Round-trip time of the probe, when it answered
+    pub latency_ms: Option<u64>,
+    /// Chain tip reported by the provider (slot height on Solana, ledger index on XRP)
+    pub block_height: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Queries a provider for its chain tip
+pub trait ProviderProbe {
+    fn block_height(&self, chain: Chain, url: &str) -> HawalaResult<u64>;
+}
+
+/// Check every configured provider for `chain`
+///
+/// Providers are probed in priority order (overrides and custom endpoints
+/// first). A provider is down if the probe fails or takes longer than
+/// [`PROVIDER_HEALTH_TIMEOUT`].
+pub fn health_check(chain: Chain) -> Vec<ProviderHealth> {
+    match HttpProbe::new(PROVIDER_HEALTH_TIMEOUT) {
+        Ok(probe) => health_check_with(get_network_config(), chain, &probe, PROVIDER_HEALTH_TIMEOUT),
+        Err(e) => chain_providers(get_network_config(), chain)
+            .into_iter()
+            .map(|(url, kind)| ProviderHealth {
+                url,
+                kind,
+                up: false,
+                latency_ms: None,
+                block_height: None,
+                error: Some(e.message.clone()),
+            })
+            .collect(),
+    }
+}
+
+/// [`health_check`] against `config`, with a caller-supplied probe and timeout
+pub fn health_check_with(
+    config: &NetworkConfig,
+    chain: Chain,
+    probe: &impl ProviderProbe,
+    timeout: Duration,
+) -> Vec<ProviderHealth> {
+    chain_providers(config, chain)
+        .into_iter()
+        .map(|(url, kind)| {
+            let started = Instant::now();
+            let result = probe.block_height(chain, &url);
+            let elapsed = started.elapsed();
+            let latency_ms = Some(elapsed.as_millis() as u64);
+
+            let health = match result {
+                Ok(_) if elapsed > timeout => ProviderHealth {
+                    url,
+                    kind,
+                    up: false,
+                    latency_ms,
+                    block_height: None,
+                    error: Some(format!("Timed out after {} ms", timeout.as_millis())),
+                },
+                Ok(height) => ProviderHealth {
+                    url,
+                    kind,
+                    up: true,
+                    latency_ms,
+                    block_height: Some(height),
+                    error: None,
+                },
+                Err(e) => ProviderHealth {
+                    url,
+                    kind,
+                    up: false,
+                    latency_ms: None,
+                    block_height: None,
+                    error: Some(e.message),
+                },
+            };
+
+            config.update_health(&health.url, health.up, health.latency_ms.filter(|_| health.up));
+            health
+        })
+        .collect()
+}
+
+/// Provider URLs for `chain` in priority order, without duplicates
+fn chain_providers(config: &NetworkConfig, chain: Chain) -> Vec<(String, ServiceKind)> {
+    // Bitcoin-family endpoints are Esplora explorers; everything else is JSON-RPC
+    let kind = if chain.is_utxo() { ServiceKind::Explorer } else { ServiceKind::Rpc };
+
+    let mut urls: Vec<String> = config.url_override(chain, kind).into_iter().collect();
+    urls.extend(config.get_endpoints(chain).into_iter().map(|e| e.url));
+    if chain.is_evm() {
+        urls.push(default_evm_rpc(chain).to_string());
+    }
+
+    let mut providers: Vec<(String, ServiceKind)> = Vec::new();
+    for url in urls {
+        let url = url.trim_end_matches('/').to_string();
+        if !providers.iter().any(|(known, _)| *known == url) {
+            providers.push((url, kind));
+        }
+    }
+    providers
+}
+
+/// Probe over HTTP: Esplora `blocks/tip/height`, `eth_blockNumber`,
+/// Solana `getBlockHeight` or XRP `ledger_closed`
+struct HttpProbe {
+    client: reqwest::blocking::Client,
+}
+
+impl HttpProbe {
+    fn new(timeout: Duration) -> HawalaResult<Self> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(timeout)
+            .build()?;
+        Ok(Self { client })
+    }
+
+    fn rpc(&self, url: &str, payload: serde_json::Value) -> HawalaResult<serde_json::Value> {
+        let response: serde_json::Value = self.client
+            .post(url)
+            .json(&payload)
+            .send()?
+            .error_for_status()?
+            .json()?;
+        response.get("result").cloned()
+            .ok_or_else(|| HawalaError::network_error(format!("No result from {}", url)))
+    }
+}
+
+impl ProviderProbe for HttpProbe {
+    fn block_height(&self, chain: Chain, url: &str) -> HawalaResult<u64> {
+        let invalid = || HawalaError::network_error(format!("Unexpected block height from {}", url));
+
+        match chain {
+            c if c.is_utxo() => {
+                let text = self.client
+                    .get(format!("{}/blocks/tip/height", url))
+                    .send()?
+                    .error_for_status()?
+                    .text()?;
+                text.trim().parse().map_err(|_| invalid())
+            }
+            Chain::Solana | Chain::SolanaDevnet => {
+                let result = self.rpc(url, serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "getBlockHeight",
+                    "params": [],
+                    "id": 1
+                }))?;
+                result.as_u64().ok_or_else(invalid)
+            }
+            Chain::Xrp | Chain::XrpTestnet => {
+                let result = self.rpc(url, serde_json::json!({
+                    "method": "ledger_closed",
+                    "params": [{}]
+                }))?;
+                result.get("ledger_index").and_then(|v| v.as_u64()).ok_or_else(invalid)
+            }
+            c if c.is_evm() => {
+                let result = self.rpc(url, serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_blockNumber",
+                    "params": [],
+                    "id": 1
+                }))?;
+                result.as_str()
+                    .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+                    .ok_or_else(invalid)
+            }
+            other => Err(HawalaError::not_implemented(format!(
+                "Provider health checks not supported for {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `fast` answers at once, `slow` after 60 ms, anything else is down
+    struct MockProbe {
+        fast: String,
+        slow: Option<String>,
+    }
+
+    impl ProviderProbe for MockProbe {
+        fn block_height(&self, _chain: Chain, url: &str) -> HawalaResult<u64> {
+            if url == self.fast {
+                Ok(19_000_000)
+            } else if self.slow.as_deref() == Some(url) {
+                std::thread::sleep(Duration::from_millis(60));
+                Ok(19_000_000)
+            } else {
+                Err(HawalaError::network_error("connection refused"))
+            }
+        }
+    }
+
+    #[test]
+    fn test_health_check_reports_slow_and_down_providers() {
+        let config = NetworkConfig::new();
+        let providers = chain_providers(&config, Chain::Ethereum);
+        assert!(providers.len() >= 2);
+        let (first, second) = (providers[0].0.clone(), providers[1].0.clone());
+
+        let probe = MockProbe { fast: first.clone(), slow: Some(second.clone()) };
+        let report = health_check_with(&config, Chain::Ethereum, &probe, Duration::from_millis(30));
+        assert_eq!(report.len(), providers.len());
+        assert!(report[0].up);
+        assert_eq!(report[0].block_height, Some(19_000_000));
+        assert!(report[0].latency_ms.is_some());
+        assert!(!report[1].up);
+        assert!(report[1].error.as_deref().unwrap().contains("Timed out"));
+        assert!(report[1].latency_ms.unwrap() >= 30);
+
+        let probe = MockProbe { fast: second.clone(), slow: None };
+        let report = health_check_with(&config, Chain::Ethereum, &probe, Duration::from_millis(30));
+        assert!(!report[0].up);
+        assert_eq!(report[0].error.as_deref(), Some("connection refused"));
+        assert_eq!(report[0].kind, ServiceKind::Rpc);
+        assert!(report[1].up);
+
+        // Outcomes are recorded in the config passed in
+        assert!(!config.get_health(&first).unwrap().is_healthy);
+        assert!(config.get_health(&second).unwrap().is_healthy);
+    }
+
+    #[test]
+    fn test_chain_providers_deduplicates() {
+        let providers = chain_providers(&NetworkConfig::new(), Chain::Bitcoin);
+        assert!(!providers.is_empty());
+        assert!(providers.iter().all(|(_, kind)| *kind == ServiceKind::Explorer));
+        for (i, (url, _)) in providers.iter().enumerate() {
+            assert!(providers[i + 1..].iter().all(|(other, _)| other != url));
+        }
+    }
+}
@@ -2,8 +2,14 @@
 //!
 //! Unified interface for multiple blockchain data providers.
 
+pub mod electrum;
+mod health;
+
+pub use health::*;
+
 use crate::error::HawalaResult;
 use crate::types::*;
+use crate::utils::network_config::{service_url, ServiceKind};
 use std::time::Duration;
 
 /// Fetch balances for all requested addresses
@@ -79,11 +85,17 @@ fn fetch_bitcoin_balance(
     address: &str,
     testnet: bool,
 ) -> HawalaResult<Balance> {
-    let base_url = if testnet {
+    let chain = if testnet { Chain::BitcoinTestnet } else { Chain::Bitcoin };
+    if let Some(balance) = electrum_balance(address, chain) {
+        return Ok(balance);
+    }
+    
+    let default_url = if testnet {
         "https://mempool.space/testnet/api"
     } else {
         "https://mempool.space/api"
     };
+    let base_url = service_url(chain, ServiceKind::Explorer, default_url);
     
     let url = format!("{}/address/{}", base_url, address);
     
@@ -105,8 +117,6 @@ fn fetch_bitcoin_balance(
     let unconfirmed = info.mempool_stats.funded_txo_sum - info.mempool_stats.spent_txo_sum;
     let total = confirmed + unconfirmed;
     
-    let chain = if testnet { Chain::BitcoinTestnet } else { Chain::Bitcoin };
-    
     Ok(Balance {
         chain,
         address: address.to_string(),
@@ -119,6 +129,10 @@ fn fetch_litecoin_balance(
     _client: &reqwest::blocking::Client,
     address: &str,
 ) -> HawalaResult<Balance> {
+    if let Some(balance) = electrum_balance(address, Chain::Litecoin) {
+        return Ok(balance);
+    }
+    
     // Note: Litecoin API integration planned for Phase 6
     // For now, returns zero balance - Litecoin balance requires
     // integration with blockcypher or similar API
@@ -130,24 +144,26 @@ fn fetch_litecoin_balance(
     })
 }
 
+/// Balance from the chain's Electrum server, when one is configured and
+/// answers; `None` falls through to the HTTP explorers
+fn electrum_balance(address: &str, chain: Chain) -> Option<Balance> {
+    electrum::electrum_server(chain)?;
+    let total = electrum::fetch_electrum_balance(address, chain).ok()?.total();
+    
+    Some(Balance {
+        chain,
+        address: address.to_string(),
+        balance: format!("{:.8}", total as f64 / 100_000_000.0),
+        balance_raw: total.to_string(),
+    })
+}
+
 fn fetch_evm_balance(
     client: &reqwest::blocking::Client,
     address: &str,
     chain: Chain,
 ) -> HawalaResult<Balance> {
-    // Use public RPC endpoints
-    let rpc_url = match chain {
-        Chain::Ethereum => "https://eth.llamarpc.com",
-        Chain::EthereumSepolia => "https://rpc.sepolia.org",
-        Chain::Bnb => "https://bsc-dataseed.binance.org",
-        Chain::Polygon => "https://polygon-rpc.com",
-        Chain::Arbitrum => "https://arb1.arbitrum.io/rpc",
-        Chain::Optimism => "https://mainnet.optimism.io",
-        Chain::Base => "https://mainnet.base.org",
-        Chain::Avalanche => "https://api.avax.network/ext/bc/C/rpc",
-        // For other EVM chains, try to use a default public RPC
-        _ => "https://eth.llamarpc.com",
-    };
+    let rpc_url = service_url(chain, ServiceKind::Rpc, default_evm_rpc(chain));
     
     let payload = serde_json::json!({
         "jsonrpc": "2.0",
@@ -181,15 +197,31 @@ fn fetch_evm_balance(
     })
 }
 
+/// Public RPC endpoint used for an EVM chain when no override is set
+fn default_evm_rpc(chain: Chain) -> &'static str {
+    match chain {
+        Chain::Ethereum => "https://eth.llamarpc.com",
+        Chain::EthereumSepolia => "https://rpc.sepolia.org",
+        Chain::Bnb => "https://bsc-dataseed.binance.org",
+        Chain::Polygon => "https://polygon-rpc.com",
+        Chain::Arbitrum => "https://arb1.arbitrum.io/rpc",
+        Chain::Optimism => "https://mainnet.optimism.io",
+        Chain::Base => "https://mainnet.base.org",
+        Chain::Avalanche => "https://api.avax.network/ext/bc/C/rpc",
+        // For other EVM chains, try to use a default public RPC
+        _ => "https://eth.llamarpc.com",
+    }
+}
+
 fn fetch_solana_balance(
     client: &reqwest::blocking::Client,
     address: &str,
     devnet: bool,
 ) -> HawalaResult<Balance> {
     let rpc_url = if devnet {
-        "https://api.devnet.solana.com"
+        service_url(Chain::SolanaDevnet, ServiceKind::Rpc, "https://api.devnet.solana.com")
     } else {
-        "https://api.mainnet-beta.solana.com"
+        service_url(Chain::Solana, ServiceKind::Rpc, "https://api.mainnet-beta.solana.com")
     };
     
     let payload = serde_json::json!({
@@ -234,9 +266,9 @@ fn fetch_xrp_balance(
     testnet: bool,
 ) -> HawalaResult<Balance> {
     let rpc_url = if testnet {
-        "https://s.altnet.rippletest.net:51234"
+        service_url(Chain::XrpTestnet, ServiceKind::Rpc, "https://s.altnet.rippletest.net:51234")
     } else {
-        "https://xrplcluster.com"
+        service_url(Chain::Xrp, ServiceKind::Rpc, "https://xrplcluster.com")
     };
     
     let payload = serde_json::json!({
@@ -147,6 +147,20 @@ impl AptosKeyPair {
     }
 }
 
+/// Build Aptos keys from an ed25519 signing key
+///
+/// address = sha3_256(public_key || 0x00)
+pub fn generate_aptos_keys(signing_key: &SigningKey) -> crate::types::AptosKeys {
+    let public_key = signing_key.verifying_key().to_bytes();
+    let address = AptosAddress::from_public_key(&public_key);
+
+    crate::types::AptosKeys {
+        private_hex: hex::encode(signing_key.to_bytes()),
+        public_hex: hex::encode(public_key),
+        address: address.to_hex(),
+    }
+}
+
 /// Aptos transaction types
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum AptosTransactionPayload {
@@ -398,6 +412,21 @@ pub fn get_address_from_seed(seed: &[u8; 64], account: u32) -> HawalaResult<Stri
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_generate_aptos_keys_known_vector() {
+        // Aptos TS SDK ed25519 vector
+        let seed: [u8; 32] = hex::decode("c5338cd251c22daa8c9c9cc94f498cc8a5c7e1d2e75287a5dda91096fe64efa5")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let keys = generate_aptos_keys(&SigningKey::from_bytes(&seed));
+
+        assert_eq!(keys.public_hex, "de19e5d1880cac87d57484ce9ed2e84cf0f9599f12e7cc3a52e4e7657a763f2c");
+        assert_eq!(keys.address, "0x978c213990c4833df71548df7ce49d54c759d6b6d932de22b24d56060b7af2aa");
+        assert_eq!(keys.address, AptosKeyPair::from_seed(&seed).unwrap().address.to_hex());
+    }
+
     #[test]
     fn test_address_derivation() {
         let public_key = [1u8; 32];
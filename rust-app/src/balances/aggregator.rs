@@ -7,6 +7,7 @@
 
 use crate::error::{HawalaError, HawalaResult, ErrorCode};
 use crate::types::Chain;
+use crate::utils::network_config::{service_url, ServiceKind};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
@@ -470,16 +471,17 @@ impl Default for BalanceAggregator {
 // =============================================================================
 
 fn get_rpc_url(chain: Chain) -> String {
-    match chain {
-        Chain::Ethereum => "https://eth.llamarpc.com".to_string(),
-        Chain::Arbitrum => "https://arb1.arbitrum.io/rpc".to_string(),
-        Chain::Optimism => "https://mainnet.optimism.io".to_string(),
-        Chain::Base => "https://mainnet.base.org".to_string(),
-        Chain::Polygon => "https://polygon-rpc.com".to_string(),
-        Chain::Bnb => "https://bsc-dataseed.binance.org".to_string(),
-        Chain::Avalanche => "https://api.avax.network/ext/bc/C/rpc".to_string(),
-        _ => "https://eth.llamarpc.com".to_string(),
-    }
+    let default_url = match chain {
+        Chain::Ethereum => "https://eth.llamarpc.com",
+        Chain::Arbitrum => "https://arb1.arbitrum.io/rpc",
+        Chain::Optimism => "https://mainnet.optimism.io",
+        Chain::Base => "https://mainnet.base.org",
+        Chain::Polygon => "https://polygon-rpc.com",
+        Chain::Bnb => "https://bsc-dataseed.binance.org",
+        Chain::Avalanche => "https://api.avax.network/ext/bc/C/rpc",
+        _ => "https://eth.llamarpc.com",
+    };
+    service_url(chain, ServiceKind::Rpc, default_url)
 }
 
 fn parse_balance(balance_str: &str) -> Option<u128> {
@@ -5,6 +5,7 @@
 
 use crate::error::{HawalaError, HawalaResult};
 use crate::types::*;
+use crate::utils::network_config::{service_url, service_urls, with_failover, ServiceKind};
 use std::time::Duration;
 
 // =============================================================================
@@ -58,10 +59,11 @@ pub fn fetch_balance(address: &str, chain: Chain) -> HawalaResult<Balance> {
 
 /// Fetch Bitcoin balance from mempool.space/blockstream
 pub fn fetch_bitcoin_balance(address: &str, chain: Chain) -> HawalaResult<Balance> {
-    let base_url = match chain {
+    let default_url = match chain {
         Chain::BitcoinTestnet => "https://mempool.space/testnet/api",
         _ => "https://mempool.space/api",
     };
+    let base_url = service_url(chain, ServiceKind::Explorer, default_url);
     
     let url = format!("{}/address/{}", base_url, address);
     let client = create_http_client()?;
@@ -95,7 +97,8 @@ pub fn fetch_bitcoin_balance(address: &str, chain: Chain) -> HawalaResult<Balanc
 
 /// Fetch Litecoin balance from litecoinspace.org
 pub fn fetch_litecoin_balance(address: &str) -> HawalaResult<Balance> {
-    let url = format!("https://litecoinspace.org/api/address/{}", address);
+    let base_url = service_url(Chain::Litecoin, ServiceKind::Explorer, "https://litecoinspace.org/api");
+    let url = format!("{}/address/{}", base_url, address);
     let client = create_http_client()?;
     
     let resp: serde_json::Value = client.get(&url)
@@ -128,13 +131,8 @@ pub fn fetch_litecoin_balance(address: &str) -> HawalaResult<Balance> {
 pub fn fetch_evm_balance(address: &str, chain: Chain) -> HawalaResult<Balance> {
     let rpc_endpoints = get_rpc_endpoints(chain);
     
-    for endpoint in &rpc_endpoints {
-        if let Ok(balance) = fetch_evm_balance_from_rpc(address, chain, endpoint) {
-            return Ok(balance);
-        }
-    }
-    
-    Err(HawalaError::network_error(format!("All RPC endpoints failed for {:?}", chain)))
+    with_failover(&rpc_endpoints, |endpoint| fetch_evm_balance_from_rpc(address, chain, endpoint))
+        .map_err(|_| HawalaError::network_error(format!("All RPC endpoints failed for {:?}", chain)))
 }
 
 fn fetch_evm_balance_from_rpc(address: &str, chain: Chain, rpc_url: &str) -> HawalaResult<Balance> {
@@ -176,8 +174,8 @@ fn fetch_evm_balance_from_rpc(address: &str, chain: Chain, rpc_url: &str) -> Haw
     }
 }
 
-fn get_rpc_endpoints(chain: Chain) -> Vec<&'static str> {
-    match chain {
+pub(crate) fn get_rpc_endpoints(chain: Chain) -> Vec<String> {
+    let defaults = match chain {
         Chain::Ethereum => vec![
             "https://eth.llamarpc.com",
             "https://ethereum.publicnode.com",
@@ -216,7 +214,8 @@ fn get_rpc_endpoints(chain: Chain) -> Vec<&'static str> {
             "https://avalanche.publicnode.com",
         ],
         _ => vec!["https://eth.llamarpc.com"],
-    }
+    };
+    service_urls(chain, ServiceKind::Rpc, &defaults)
 }
 
 // =============================================================================
@@ -225,10 +224,11 @@ fn get_rpc_endpoints(chain: Chain) -> Vec<&'static str> {
 
 /// Fetch Solana balance using JSON-RPC
 pub fn fetch_solana_balance(address: &str, chain: Chain) -> HawalaResult<Balance> {
-    let rpc_url = match chain {
+    let default_url = match chain {
         Chain::SolanaDevnet => "https://api.devnet.solana.com",
         _ => "https://api.mainnet-beta.solana.com",
     };
+    let rpc_url = service_url(chain, ServiceKind::Rpc, default_url);
     
     let client = create_http_client()?;
     
@@ -239,7 +239,7 @@ pub fn fetch_solana_balance(address: &str, chain: Chain) -> HawalaResult<Balance
         "params": [address]
     });
     
-    let resp: serde_json::Value = client.post(rpc_url)
+    let resp: serde_json::Value = client.post(&rpc_url)
         .json(&payload)
         .send()
         .map_err(|e| HawalaError::network_error(format!("Failed to fetch SOL balance: {}", e)))?
@@ -275,10 +275,11 @@ pub fn fetch_solana_balance(address: &str, chain: Chain) -> HawalaResult<Balance
 
 /// Fetch XRP balance using XRPL RPC
 pub fn fetch_xrp_balance(address: &str, chain: Chain) -> HawalaResult<Balance> {
-    let rpc_urls = match chain {
+    let defaults = match chain {
         Chain::XrpTestnet => vec!["https://s.altnet.rippletest.net:51234"],
         _ => vec!["https://s1.ripple.com:51234", "https://xrplcluster.com"],
     };
+    let rpc_urls = service_urls(chain, ServiceKind::Rpc, &defaults);
     
     let client = create_http_client()?;
     
@@ -291,7 +292,7 @@ pub fn fetch_xrp_balance(address: &str, chain: Chain) -> HawalaResult<Balance> {
             }]
         });
         
-        if let Ok(resp) = client.post(*rpc_url)
+        if let Ok(resp) = client.post(rpc_url)
             .json(&payload)
             .send()
         {
@@ -357,7 +358,7 @@ pub fn fetch_erc20_balance(address: &str, token_contract: &str, chain: Chain) ->
             "id": 1
         });
         
-        if let Ok(resp) = client.post(*endpoint).json(&payload).send() {
+        if let Ok(resp) = client.post(endpoint).json(&payload).send() {
             if let Ok(json) = resp.json::<serde_json::Value>() {
                 if let Some(hex_balance) = json["result"].as_str() {
                     let balance = u128::from_str_radix(hex_balance.trim_start_matches("0x"), 16)
@@ -380,10 +381,11 @@ pub fn fetch_erc20_balance(address: &str, token_contract: &str, chain: Chain) ->
 
 /// Fetch SPL token balance (Solana)
 pub fn fetch_spl_balance(address: &str, mint: &str, chain: Chain) -> HawalaResult<TokenBalance> {
-    let rpc_url = match chain {
+    let default_url = match chain {
         Chain::SolanaDevnet => "https://api.devnet.solana.com",
         _ => "https://api.mainnet-beta.solana.com",
     };
+    let rpc_url = service_url(chain, ServiceKind::Rpc, default_url);
     
     let client = create_http_client()?;
     
@@ -398,7 +400,7 @@ pub fn fetch_spl_balance(address: &str, mint: &str, chain: Chain) -> HawalaResul
         ]
     });
     
-    let resp: serde_json::Value = client.post(rpc_url)
+    let resp: serde_json::Value = client.post(&rpc_url)
         .json(&payload)
         .send()
         .map_err(|e| HawalaError::network_error(format!("Failed to fetch SPL balance: {}", e)))?
@@ -443,11 +445,126 @@ pub fn fetch_spl_balance(address: &str, mint: &str, chain: Chain) -> HawalaResul
     })
 }
 
+/// SPL Token program id
+pub const SPL_TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// Holdings of one SPL mint, summed over the owner's token accounts
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SplBalance {
+    pub mint: String,
+    /// Token accounts holding this mint
+    pub token_accounts: Vec<String>,
+    pub balance_raw: String,
+    pub decimals: u8,
+    /// `balance_raw` scaled by `decimals`, e.g. "12.5"
+    pub balance: String,
+    /// From the built-in token list, when the mint is known
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+}
+
+/// Fetch every SPL token held by `owner` (Solana)
+///
+/// Queries `getTokenAccountsByOwner` filtered by the Token program, so no
+/// mint needs to be known in advance. Empty token accounts are included.
+pub fn fetch_all_spl_balances(owner: &str, chain: Chain) -> HawalaResult<Vec<SplBalance>> {
+    let default_url = match chain {
+        Chain::SolanaDevnet => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let rpc_url = service_url(chain, ServiceKind::Rpc, default_url);
+
+    let client = create_http_client()?;
+
+    let payload = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "getTokenAccountsByOwner",
+        "params": [
+            owner,
+            { "programId": SPL_TOKEN_PROGRAM_ID },
+            { "encoding": "jsonParsed" }
+        ]
+    });
+
+    let resp: serde_json::Value = client.post(&rpc_url)
+        .json(&payload)
+        .send()
+        .map_err(|e| HawalaError::network_error(format!("Failed to fetch SPL balances: {}", e)))?
+        .json()
+        .map_err(|e| HawalaError::parse_error(format!("Failed to parse SPL balances: {}", e)))?;
+
+    parse_spl_token_accounts(&resp, chain)
+}
+
+/// Parse a jsonParsed `getTokenAccountsByOwner` response into per-mint balances
+fn parse_spl_token_accounts(resp: &serde_json::Value, chain: Chain) -> HawalaResult<Vec<SplBalance>> {
+    if let Some(error) = resp.get("error") {
+        return Err(HawalaError::network_error(format!(
+            "getTokenAccountsByOwner failed: {}",
+            error["message"].as_str().unwrap_or("unknown error")
+        )));
+    }
+    let accounts = resp["result"]["value"].as_array()
+        .ok_or_else(|| HawalaError::parse_error("Missing token accounts in response"))?;
+
+    let known_tokens = crate::swap::get_popular_tokens(Chain::Solana).unwrap_or_default();
+    let mut balances: Vec<(SplBalance, u128)> = Vec::new();
+
+    for account in accounts {
+        let info = &account["account"]["data"]["parsed"]["info"];
+        let (Some(mint), Some(amount)) = (info["mint"].as_str(), info["tokenAmount"].as_object()) else {
+            continue;
+        };
+        let raw: u128 = amount.get("amount")
+            .and_then(|a| a.as_str())
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        let decimals = amount.get("decimals")
+            .and_then(|d| d.as_u64())
+            .unwrap_or(0) as u8;
+        let pubkey = account["pubkey"].as_str().unwrap_or_default().to_string();
+
+        match balances.iter_mut().find(|(b, _)| b.mint == mint) {
+            Some((balance, total)) => {
+                *total += raw;
+                balance.token_accounts.push(pubkey);
+            }
+            None => {
+                // Mainnet mints only; devnet mints won't match the list
+                let known = known_tokens.iter()
+                    .find(|t| chain == Chain::Solana && t.address == mint);
+                balances.push((
+                    SplBalance {
+                        mint: mint.to_string(),
+                        token_accounts: vec![pubkey],
+                        balance_raw: String::new(),
+                        decimals,
+                        balance: String::new(),
+                        symbol: known.map(|t| t.symbol.clone()),
+                        name: known.map(|t| t.name.clone()),
+                    },
+                    raw,
+                ));
+            }
+        }
+    }
+
+    Ok(balances
+        .into_iter()
+        .map(|(mut balance, total)| {
+            balance.balance_raw = total.to_string();
+            balance.balance = crate::wallet::format_amount(total, balance.decimals);
+            balance
+        })
+        .collect())
+}
+
 // =============================================================================
 // Helper Functions
 // =============================================================================
 
-fn create_http_client() -> HawalaResult<reqwest::blocking::Client> {
+pub(crate) fn create_http_client() -> HawalaResult<reqwest::blocking::Client> {
     reqwest::blocking::Client::builder()
         .timeout(Duration::from_secs(15))
         .build()
@@ -504,6 +621,68 @@ mod tests {
         assert!(balance.formatted_balance().starts_with("1.0"));
     }
     
+    #[test]
+    fn test_parse_spl_token_accounts() {
+        let token_account = |pubkey: &str, mint: &str, amount: &str, decimals: u8| serde_json::json!({
+            "pubkey": pubkey,
+            "account": {
+                "data": {
+                    "parsed": {
+                        "info": {
+                            "isNative": false,
+                            "mint": mint,
+                            "owner": "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T",
+                            "state": "initialized",
+                            "tokenAmount": {
+                                "amount": amount,
+                                "decimals": decimals,
+                                "uiAmountString": "0"
+                            }
+                        },
+                        "type": "account"
+                    },
+                    "program": "spl-token",
+                    "space": 165
+                },
+                "executable": false,
+                "lamports": 2039280,
+                "owner": SPL_TOKEN_PROGRAM_ID
+            }
+        });
+        let usdc = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+        let other = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+        let resp = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "context": { "slot": 250000000 },
+                "value": [
+                    token_account("AccountA", usdc, "12500000", 6),
+                    token_account("AccountB", other, "0", 9),
+                    token_account("AccountC", usdc, "500000", 6)
+                ]
+            }
+        });
+
+        let balances = parse_spl_token_accounts(&resp, Chain::Solana).unwrap();
+        assert_eq!(balances.len(), 2);
+
+        assert_eq!(balances[0].mint, usdc);
+        assert_eq!(balances[0].token_accounts, vec!["AccountA", "AccountC"]);
+        assert_eq!(balances[0].balance_raw, "13000000");
+        assert_eq!(balances[0].balance, "13");
+        assert_eq!(balances[0].decimals, 6);
+        assert_eq!(balances[0].symbol.as_deref(), Some("USDC"));
+
+        assert_eq!(balances[1].mint, other);
+        assert_eq!(balances[1].balance_raw, "0");
+        assert_eq!(balances[1].decimals, 9);
+        assert_eq!(balances[1].symbol, None);
+
+        let error = serde_json::json!({ "error": { "code": -32602, "message": "Invalid param" } });
+        assert!(parse_spl_token_accounts(&error, Chain::Solana).is_err());
+    }
+
     #[test]
     fn test_rpc_endpoints() {
         let endpoints = get_rpc_endpoints(Chain::Ethereum);
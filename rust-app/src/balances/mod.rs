@@ -4,6 +4,8 @@
 
 pub mod aggregator;
 pub mod legacy;
+pub mod token_metadata;
 
 pub use aggregator::*;
 pub use legacy::*;
+pub use token_metadata::*;
@@ -0,0 +1,193 @@
+//! ERC-20 Token Metadata
+//!
+//! Fetches `name()`, `symbol()` and `decimals()` for a token contract in one
+//! Multicall3 request. Results are cached per chain and contract since token
+//! metadata never changes.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::abi::multicall::Call3Result;
+use crate::abi::FunctionResult;
+use crate::api::multicall::multicall;
+use crate::error::{HawalaError, HawalaResult};
+use crate::types::*;
+
+const NAME_SELECTOR: [u8; 4] = [0x06, 0xfd, 0xde, 0x03];
+const SYMBOL_SELECTOR: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
+const DECIMALS_SELECTOR: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+
+/// Display metadata of an ERC-20 token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    pub chain: Chain,
+    pub contract: String,
+    /// Empty if the token doesn't implement `name()`
+    pub name: String,
+    /// Empty if the token doesn't implement `symbol()`
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+lazy_static::lazy_static! {
+    /// Fetched metadata by (chain, lowercase contract)
+    static ref METADATA_CACHE: Mutex<HashMap<(Chain, String), TokenMetadata>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Fetch name, symbol and decimals of an ERC-20 contract
+///
+/// Served from cache after the first successful fetch. Tokens that return
+/// `bytes32` instead of `string` for name/symbol (e.g. MKR) are handled.
+pub fn fetch_token_metadata(contract: &str, chain: Chain) -> HawalaResult<TokenMetadata> {
+    if !chain.is_evm() {
+        return Err(HawalaError::invalid_input(format!("Token metadata is EVM-only, got {:?}", chain)));
+    }
+    let target: [u8; 20] = hex::decode(contract.strip_prefix("0x").unwrap_or(contract))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| HawalaError::invalid_input(format!("Invalid token contract: {}", contract)))?;
+
+    let key = (chain, contract.to_lowercase());
+    if let Some(cached) = lock_cache()?.get(&key) {
+        return Ok(cached.clone());
+    }
+
+    let calls: Vec<([u8; 20], Vec<u8>)> = [NAME_SELECTOR, SYMBOL_SELECTOR, DECIMALS_SELECTOR]
+        .iter()
+        .map(|selector| (target, selector.to_vec()))
+        .collect();
+    let metadata = decode_token_metadata(chain, contract, &multicall(chain, &calls)?)?;
+
+    lock_cache()?.insert(key, metadata.clone());
+    Ok(metadata)
+}
+
+fn lock_cache() -> HawalaResult<std::sync::MutexGuard<'static, HashMap<(Chain, String), TokenMetadata>>> {
+    METADATA_CACHE.lock().map_err(|_| HawalaError::internal("Lock failed"))
+}
+
+/// Build metadata from the `[name, symbol, decimals]` multicall results
+fn decode_token_metadata(chain: Chain, contract: &str, results: &[Call3Result]) -> HawalaResult<TokenMetadata> {
+    let data = |i: usize| {
+        results.get(i)
+            .filter(|r| r.success && !r.return_data.is_empty())
+            .map(|r| r.return_data.as_slice())
+    };
+    let (name, symbol, decimals) = (data(0), data(1), data(2));
+
+    let decimals = decimals
+        .and_then(|data| FunctionResult::erc20_decimals(data).ok())
+        .ok_or_else(|| HawalaError::parse_error(format!(
+            "{} did not return decimals(); is it an ERC-20 token?",
+            contract
+        )))?;
+
+    Ok(TokenMetadata {
+        chain,
+        contract: contract.to_string(),
+        name: name.and_then(decode_string_or_bytes32).unwrap_or_default(),
+        symbol: symbol.and_then(decode_string_or_bytes32).unwrap_or_default(),
+        decimals,
+    })
+}
+
+/// Decode an ABI `string`, or a right-padded `bytes32` as returned by some
+/// pre-standard tokens
+fn decode_string_or_bytes32(data: &[u8]) -> Option<String> {
+    if data.len() == 32 {
+        let end = data.iter().position(|b| *b == 0).unwrap_or(32);
+        return String::from_utf8(data[..end].to_vec()).ok();
+    }
+    FunctionResult::erc20_string(data).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(value: u64) -> String {
+        format!("{:064x}", value)
+    }
+
+    /// ABI-encoded `string` return data
+    fn abi_string(s: &str) -> String {
+        let mut padded = hex::encode(s);
+        while !padded.len().is_multiple_of(64) {
+            padded.push('0');
+        }
+        format!("0x{}{}{}", word(32), word(s.len() as u64), padded)
+    }
+
+    fn bytes32(s: &str) -> String {
+        format!("0x{:0<64}", hex::encode(s))
+    }
+
+    fn ok(hex_data: &str) -> Call3Result {
+        Call3Result { success: true, return_data: hex::decode(hex_data.trim_start_matches("0x")).unwrap() }
+    }
+
+    fn decode(results: [Call3Result; 3], contract: &str) -> HawalaResult<TokenMetadata> {
+        decode_token_metadata(Chain::Ethereum, contract, &results)
+    }
+
+    #[test]
+    fn test_decode_string_token() {
+        let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        let name = abi_string("USD Coin");
+        let symbol = abi_string("USDC");
+        let decimals = format!("0x{}", word(6));
+        let metadata = decode([ok(&name), ok(&symbol), ok(&decimals)], usdc).unwrap();
+
+        assert_eq!(metadata.name, "USD Coin");
+        assert_eq!(metadata.symbol, "USDC");
+        assert_eq!(metadata.decimals, 6);
+        assert_eq!(metadata.contract, usdc);
+    }
+
+    #[test]
+    fn test_decode_bytes32_token() {
+        let mkr = "0x9f8F72aA9304c8B593d555F12eF6589cC3A579A2";
+        let name = bytes32("Maker");
+        let symbol = bytes32("MKR");
+        let decimals = format!("0x{}", word(18));
+        let metadata = decode([ok(&name), ok(&symbol), ok(&decimals)], mkr).unwrap();
+
+        assert_eq!(metadata.name, "Maker");
+        assert_eq!(metadata.symbol, "MKR");
+        assert_eq!(metadata.decimals, 18);
+    }
+
+    #[test]
+    fn test_decode_missing_calls() {
+        let decimals = format!("0x{}", word(0));
+        let reverted = Call3Result { success: false, return_data: Vec::new() };
+        let metadata = decode([reverted.clone(), ok("0x"), ok(&decimals)], "0x0000000000000000000000000000000000000001").unwrap();
+        assert_eq!(metadata.name, "");
+        assert_eq!(metadata.symbol, "");
+        assert_eq!(metadata.decimals, 0);
+
+        let name = abi_string("No Decimals");
+        assert!(decode([ok(&name), ok(&name), reverted], "0x0000000000000000000000000000000000000001").is_err());
+    }
+
+    #[test]
+    fn test_fetch_token_metadata_uses_cache() {
+        let contract = "0x00000000000000000000000000000000000000Aa";
+        let cached = TokenMetadata {
+            chain: Chain::Polygon,
+            contract: contract.to_string(),
+            name: "Cached".to_string(),
+            symbol: "CCH".to_string(),
+            decimals: 8,
+        };
+        lock_cache().unwrap().insert((Chain::Polygon, contract.to_lowercase()), cached.clone());
+
+        // Served without touching the network, regardless of address case
+        assert_eq!(fetch_token_metadata(&contract.to_lowercase(), Chain::Polygon).unwrap(), cached);
+        assert!(fetch_token_metadata(contract, Chain::Solana).is_err());
+        assert!(fetch_token_metadata("0x1234", Chain::Polygon).is_err());
+    }
+}
@@ -104,46 +104,36 @@ pub fn fetch_utxos(address: &str, network: Network) -> Result<Vec<Utxo>, Box<dyn
     Err(last_error.unwrap_or_else(|| "All UTXO APIs failed".into()))
 }
 
-pub fn prepare_transaction(
-    recipient: &str,
-    amount_sats: u64,
-    fee_rate_sats_per_vbyte: u64,
-    sender_wif: &str,
-    manual_utxos: Option<Vec<Utxo>>,
-) -> Result<String, Box<dyn Error>> {
-    let secp = Secp256k1::new();
-    let private_key = bitcoin::PrivateKey::from_wif(sender_wif)?;
-    let network = match private_key.network {
-        NetworkKind::Main => Network::Bitcoin,
-        NetworkKind::Test => Network::Testnet,
-    };
-    let public_key = private_key.public_key(&secp);
-    // Convert to CompressedPublicKey (P2WPKH requires compressed keys)
-    let compressed_public_key = bitcoin::key::CompressedPublicKey::try_from(public_key)
-        .map_err(|_| "Failed to compress public key")?;
+/// Change at or below this value is left to the miner instead of creating an output
+pub const CHANGE_DUST_LIMIT: u64 = 546;
 
-    let sender_address = Address::p2wpkh(&compressed_public_key, network);
-    debug_log!("Sender address: {}", sender_address);
-
-    // 1. Fetch UTXOs (or use manual)
-    let (utxos, is_manual) = if let Some(u) = manual_utxos {
-        debug_log!("Using {} manual UTXOs", u.len());
-        (u, true)
-    } else {
-        (fetch_utxos(&sender_address.to_string(), network)?, false)
-    };
-    debug_log!("Available UTXOs: {}", utxos.len());
-    
-    if utxos.is_empty() {
-        return Err("No UTXOs available".into());
-    }
+/// Inputs, size and fee chosen for a P2WPKH spend
+#[derive(Debug)]
+pub struct SpendPlan {
+    pub inputs: Vec<Utxo>,
+    /// Estimated vsize (recipient + change outputs)
+    pub vsize: u64,
+    /// Total fee, including any dust change left to the miner
+    pub fee: u64,
+    /// Change output value (0 when there is no change output)
+    pub change: u64,
+}
 
-    // 2. Select Inputs
+/// Select inputs and size the fee for sending `amount_sats`
+///
+/// With `use_all` every UTXO is spent (coin control); otherwise UTXOs are
+/// taken in order until the amount is covered.
+pub fn plan_spend(
+    utxos: Vec<Utxo>,
+    amount_sats: u64,
+    fee_rate_sats_per_vbyte: u64,
+    use_all: bool,
+) -> Result<SpendPlan, Box<dyn Error>> {
     let mut inputs: Vec<Utxo> = Vec::new();
     let mut total_input_value: u64 = 0;
     let target_value = amount_sats; // We'll add fee later
 
-    if is_manual {
+    if use_all {
         // If manual UTXOs provided, use ALL of them (Coin Control)
         for utxo in utxos {
             debug_log!("Using Manual UTXO: txid={}, vout={}, value={} sats", utxo.txid, utxo.vout, utxo.value);
@@ -172,7 +162,7 @@ pub fn prepare_transaction(
         return Err(format!("Insufficient funds: have {} sats, need {} sats", total_input_value, target_value).into());
     }
 
-    // 3. Estimate Fee (Simple approximation: 1 input ~68 vbytes, 1 output ~31 vbytes, overhead ~10 vbytes)
+    // Estimate fee (simple approximation: 1 input ~68 vbytes, 1 output ~31 vbytes, overhead ~10 vbytes)
     // P2WPKH Input: ~68 vbytes
     // P2WPKH Output: ~31 vbytes
     let estimated_size = 10 + (inputs.len() as u64 * 68) + (2 * 31); // 2 outputs (recipient + change)
@@ -187,10 +177,149 @@ pub fn prepare_transaction(
         .into());
     }
 
+    let remainder = total_input_value - target_value - fee;
+    let (fee, change) = if remainder > CHANGE_DUST_LIMIT {
+        (fee, remainder)
+    } else {
+        (fee + remainder, 0)
+    };
+
+    Ok(SpendPlan {
+        inputs,
+        vsize: estimated_size,
+        fee,
+        change,
+    })
+}
+
+/// Plan a sweep: spend every UTXO to a single output with no change
+///
+/// Returns the amount the recipient receives (total minus fee) and the plan.
+pub fn plan_sweep(
+    utxos: Vec<Utxo>,
+    fee_rate_sats_per_vbyte: u64,
+) -> Result<(u64, SpendPlan), Box<dyn Error>> {
+    if utxos.is_empty() {
+        return Err("No inputs selected - no UTXOs available".into());
+    }
+    let total_input_value: u64 = utxos.iter().map(|u| u.value).sum();
+
+    // Same approximation as plan_spend, with only the recipient output
+    let estimated_size = 10 + (utxos.len() as u64 * 68) + 31;
+    let fee = estimated_size * fee_rate_sats_per_vbyte;
+
+    let amount = match total_input_value.checked_sub(fee) {
+        Some(amount) if amount > CHANGE_DUST_LIMIT => amount,
+        Some(amount) => {
+            return Err(format!(
+                "Sweeping {} sats leaves {} sats after the {} sat fee, below the dust limit",
+                total_input_value, amount, fee
+            )
+            .into())
+        }
+        None => {
+            return Err(format!(
+                "Insufficient funds: balance of {} sats does not cover the {} sat fee",
+                total_input_value, fee
+            )
+            .into())
+        }
+    };
+
+    Ok((
+        amount,
+        SpendPlan {
+            inputs: utxos,
+            vsize: estimated_size,
+            fee,
+            change: 0,
+        },
+    ))
+}
+
+pub fn prepare_transaction(
+    recipient: &str,
+    amount_sats: u64,
+    fee_rate_sats_per_vbyte: u64,
+    sender_wif: &str,
+    manual_utxos: Option<Vec<Utxo>>,
+) -> Result<String, Box<dyn Error>> {
+    prepare_transaction_with_change(recipient, amount_sats, fee_rate_sats_per_vbyte, sender_wif, manual_utxos, None)
+}
+
+/// Like `prepare_transaction`, but sends change to `change_address` instead of
+/// back to the sender address when one is given
+pub fn prepare_transaction_with_change(
+    recipient: &str,
+    amount_sats: u64,
+    fee_rate_sats_per_vbyte: u64,
+    sender_wif: &str,
+    manual_utxos: Option<Vec<Utxo>>,
+    change_address: Option<&str>,
+) -> Result<String, Box<dyn Error>> {
+    prepare_spend(recipient, Some(amount_sats), fee_rate_sats_per_vbyte, sender_wif, manual_utxos, change_address)
+}
+
+/// Send every available UTXO to `recipient`, less the fee, with no change output
+pub fn prepare_sweep_transaction(
+    recipient: &str,
+    fee_rate_sats_per_vbyte: u64,
+    sender_wif: &str,
+    manual_utxos: Option<Vec<Utxo>>,
+) -> Result<String, Box<dyn Error>> {
+    prepare_spend(recipient, None, fee_rate_sats_per_vbyte, sender_wif, manual_utxos, None)
+}
+
+// Build and sign a P2WPKH spend; `amount_sats` of None sweeps all UTXOs
+fn prepare_spend(
+    recipient: &str,
+    amount_sats: Option<u64>,
+    fee_rate_sats_per_vbyte: u64,
+    sender_wif: &str,
+    manual_utxos: Option<Vec<Utxo>>,
+    change_address: Option<&str>,
+) -> Result<String, Box<dyn Error>> {
+    let secp = Secp256k1::new();
+    let private_key = bitcoin::PrivateKey::from_wif(sender_wif)?;
+    let network = match private_key.network {
+        NetworkKind::Main => Network::Bitcoin,
+        NetworkKind::Test => Network::Testnet,
+    };
+    let public_key = private_key.public_key(&secp);
+    // Convert to CompressedPublicKey (P2WPKH requires compressed keys)
+    let compressed_public_key = bitcoin::key::CompressedPublicKey::try_from(public_key)
+        .map_err(|_| "Failed to compress public key")?;
+
+    let sender_address = Address::p2wpkh(&compressed_public_key, network);
+    debug_log!("Sender address: {}", sender_address);
+
+    // 1. Fetch UTXOs (or use manual)
+    let (utxos, is_manual) = if let Some(u) = manual_utxos {
+        debug_log!("Using {} manual UTXOs", u.len());
+        (u, true)
+    } else {
+        (fetch_utxos(&sender_address.to_string(), network)?, false)
+    };
+    debug_log!("Available UTXOs: {}", utxos.len());
+    
+    if utxos.is_empty() {
+        return Err("No UTXOs available".into());
+    }
+
+    // 2-3. Select inputs and estimate fee
+    let (target_value, plan) = match amount_sats {
+        Some(amount_sats) => (amount_sats, plan_spend(utxos, amount_sats, fee_rate_sats_per_vbyte, is_manual)?),
+        None => plan_sweep(utxos, fee_rate_sats_per_vbyte)?,
+    };
+    let inputs = plan.inputs;
+    
     // 4. Build Transaction
     let recipient_address = Address::from_str(recipient)?.require_network(network)?;
-    let change_address = sender_address.clone();
-    let change_amount = total_input_value - target_value - fee;
+    let change_address = match change_address {
+        Some(addr) => Address::from_str(addr)?.require_network(network)?,
+        None => sender_address.clone(),
+    };
+    let change_amount = plan.change;
 
     let mut tx_inputs = Vec::new();
     for utxo in &inputs {
@@ -208,9 +337,8 @@ pub fn prepare_transaction(
         value: Amount::from_sat(target_value),
         script_pubkey: recipient_address.script_pubkey(),
     });
-    // Change Output (if dust, maybe skip? for now keep simple)
-    if change_amount > 546 {
-        // Dust limit
+    // Change Output (dust change is left to the fee)
+    if change_amount > 0 {
         tx_outputs.push(TxOut {
             value: Amount::from_sat(change_amount),
             script_pubkey: change_address.script_pubkey(),
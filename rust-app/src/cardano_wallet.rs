@@ -6,115 +6,123 @@ use serde::{Deserialize, Serialize};
 use ed25519_dalek::{SigningKey, VerifyingKey};
 use bech32::{self, Variant, ToBase32};
 
+/// Shelley address header types (CIP-19), upper nibble of the header byte
+const HEADER_BASE_KEY_KEY: u8 = 0x00;
+const HEADER_ENTERPRISE_KEY: u8 = 0x60;
+const HEADER_REWARD_KEY: u8 = 0xe0;
+
+/// Network id, lower nibble of the header byte
+pub const NETWORK_MAINNET: u8 = 1;
+pub const NETWORK_TESTNET: u8 = 0;
+
 /// Cardano keys structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CardanoKeys {
     pub private_hex: String,
     pub public_hex: String,
-    pub address: String, // Shelley-era addr1...
+    pub stake_public_hex: String,
+    pub address: String, // Shelley-era base address addr1q...
+    pub enterprise_address: String, // Payment credential only, addr1v...
+    pub stake_address: String,
 }
 
 /// Derive Cardano keys from a BIP39 seed using simplified approach
 /// Note: Full Cardano uses PBKDF2 + BIP32-Ed25519 which is complex
 /// This implementation uses a simplified ed25519 derivation
 pub fn derive_cardano_keys(seed: &[u8]) -> Result<CardanoKeys, String> {
-    // Cardano uses a specific derivation scheme (Icarus/Shelley)
-    // For now, we use the first 32 bytes of HMAC-SHA512 of the seed
+    let signing_key = derive_signing_key(seed, b"ed25519 cardano seed")?;
+    let verifying_key = signing_key.verifying_key();
+    let stake_key = derive_signing_key(seed, b"ed25519 cardano stake")?.verifying_key();
+
+    Ok(CardanoKeys {
+        private_hex: hex::encode(signing_key.to_bytes()),
+        public_hex: hex::encode(verifying_key.as_bytes()),
+        stake_public_hex: hex::encode(stake_key.as_bytes()),
+        address: encode_base_address(&verifying_key, &stake_key, NETWORK_MAINNET)?,
+        enterprise_address: encode_cardano_address(&verifying_key)?,
+        stake_address: encode_reward_address(&stake_key, NETWORK_MAINNET)?,
+    })
+}
+
+/// Ed25519 key from the first 32 bytes of HMAC-SHA512(domain, seed)
+fn derive_signing_key(seed: &[u8], domain: &[u8]) -> Result<SigningKey, String> {
     use hmac::{Hmac, Mac};
     use sha2::Sha512;
 
     type HmacSha512 = Hmac<Sha512>;
 
-    // Derive a deterministic key using the Cardano path indicator
-    let mut mac = HmacSha512::new_from_slice(b"ed25519 cardano seed")
+    let mut mac = HmacSha512::new_from_slice(domain)
         .map_err(|e| format!("HMAC error: {}", e))?;
     mac.update(seed);
     let result = mac.finalize().into_bytes();
 
-    // Use first 32 bytes as private key
     let private_bytes: [u8; 32] = result[..32]
         .try_into()
         .map_err(|_| "Failed to extract private key bytes")?;
 
-    // Create signing key
-    let signing_key = SigningKey::from_bytes(&private_bytes);
-    let verifying_key = signing_key.verifying_key();
-
-    // Private key hex
-    let private_hex = hex::encode(private_bytes);
-
-    // Public key hex (32 bytes)
-    let public_hex = hex::encode(verifying_key.as_bytes());
-
-    // Generate Shelley-era address (simplified - enterprise address)
-    let address = encode_cardano_address(&verifying_key)?;
-
-    Ok(CardanoKeys {
-        private_hex,
-        public_hex,
-        address,
-    })
+    Ok(SigningKey::from_bytes(&private_bytes))
 }
 
-/// Encode a Cardano Shelley-era address (enterprise address - no stake part)
-fn encode_cardano_address(public_key: &VerifyingKey) -> Result<String, String> {
+/// Key credential: Blake2b-224 of the public key
+fn key_hash(public_key: &VerifyingKey) -> [u8; 28] {
     use blake2::{Blake2b, Digest};
     use blake2::digest::consts::U28;
 
-    // Hash public key with Blake2b-224
     let mut hasher = Blake2b::<U28>::new();
     hasher.update(public_key.as_bytes());
-    let hash = hasher.finalize();
-
-    // Enterprise address format: header byte (0x61 for mainnet) + 28-byte key hash
-    let mut address_bytes = Vec::with_capacity(29);
-    address_bytes.push(0x61); // Enterprise address, mainnet
-    address_bytes.extend_from_slice(&hash);
-
-    // Bech32 encode with "addr" prefix (bech32 v0.9 API)
-    let address = bech32::encode("addr", address_bytes.to_base32(), Variant::Bech32)
-        .map_err(|e| format!("Bech32 encoding failed: {}", e))?;
-
-    Ok(address)
+    hasher.finalize().into()
 }
 
-/// Generate a Cardano staking address
-pub fn derive_staking_address(seed: &[u8]) -> Result<String, String> {
-    use hmac::{Hmac, Mac};
-    use sha2::Sha512;
-    use blake2::{Blake2b, Digest};
-    use blake2::digest::consts::U28;
-
-    type HmacSha512 = Hmac<Sha512>;
-
-    // Use a different derivation for staking key
-    let mut mac = HmacSha512::new_from_slice(b"ed25519 cardano stake")
-        .map_err(|e| format!("HMAC error: {}", e))?;
-    mac.update(seed);
-    let result = mac.finalize().into_bytes();
-
-    let private_bytes: [u8; 32] = result[..32]
-        .try_into()
-        .map_err(|_| "Failed to extract private key bytes")?;
+/// Bech32-encode `header || credentials` with the network's HRP
+fn encode_shelley(header: u8, credentials: &[&[u8; 28]], hrp: &str) -> Result<String, String> {
+    let mut address_bytes = Vec::with_capacity(1 + 28 * credentials.len());
+    address_bytes.push(header);
+    for credential in credentials {
+        address_bytes.extend_from_slice(&credential[..]);
+    }
 
-    let signing_key = SigningKey::from_bytes(&private_bytes);
-    let verifying_key = signing_key.verifying_key();
+    // Base addresses exceed the 90-character BIP-173 limit, which Cardano
+    // does not apply (bech32 v0.9 API)
+    bech32::encode(hrp, address_bytes.to_base32(), Variant::Bech32)
+        .map_err(|e| format!("Bech32 encoding failed: {}", e))
+}
 
-    // Hash public key with Blake2b-224
-    let mut hasher = Blake2b::<U28>::new();
-    hasher.update(verifying_key.as_bytes());
-    let hash = hasher.finalize();
+/// Encode a Shelley base address: payment and stake key credentials
+///
+/// `network` is [`NETWORK_MAINNET`] (`addr1q...`) or [`NETWORK_TESTNET`]
+/// (`addr_test1q...`).
+pub fn encode_base_address(
+    payment_key: &VerifyingKey,
+    stake_key: &VerifyingKey,
+    network: u8,
+) -> Result<String, String> {
+    let hrp = match network {
+        NETWORK_MAINNET => "addr",
+        NETWORK_TESTNET => "addr_test",
+        _ => return Err(format!("Unsupported Cardano network id {}", network)),
+    };
+    encode_shelley(
+        HEADER_BASE_KEY_KEY | network,
+        &[&key_hash(payment_key), &key_hash(stake_key)],
+        hrp,
+    )
+}
 
-    // Reward address format: header byte (0xe1 for mainnet) + 28-byte key hash
-    let mut address_bytes = Vec::with_capacity(29);
-    address_bytes.push(0xe1); // Reward address, mainnet
-    address_bytes.extend_from_slice(&hash);
+/// Encode a Cardano Shelley-era address (enterprise address - no stake part)
+fn encode_cardano_address(public_key: &VerifyingKey) -> Result<String, String> {
+    encode_shelley(HEADER_ENTERPRISE_KEY | NETWORK_MAINNET, &[&key_hash(public_key)], "addr")
+}
 
-    // Bech32 encode with "stake" prefix (bech32 v0.9 API)
-    let address = bech32::encode("stake", address_bytes.to_base32(), Variant::Bech32)
-        .map_err(|e| format!("Bech32 encoding failed: {}", e))?;
+/// Encode a reward (stake) address for a stake key
+fn encode_reward_address(stake_key: &VerifyingKey, network: u8) -> Result<String, String> {
+    let hrp = if network == NETWORK_MAINNET { "stake" } else { "stake_test" };
+    encode_shelley(HEADER_REWARD_KEY | network, &[&key_hash(stake_key)], hrp)
+}
 
-    Ok(address)
+/// Generate a Cardano staking address
+pub fn derive_staking_address(seed: &[u8]) -> Result<String, String> {
+    let stake_key = derive_signing_key(seed, b"ed25519 cardano stake")?.verifying_key();
+    encode_reward_address(&stake_key, NETWORK_MAINNET)
 }
 
 #[cfg(test)]
@@ -134,6 +142,33 @@ mod tests {
         assert!(!keys.private_hex.is_empty());
         assert!(!keys.public_hex.is_empty());
         assert_eq!(keys.public_hex.len(), 64); // 32 bytes = 64 hex chars
+        assert!(keys.address.starts_with("addr1q"));
+        assert!(keys.enterprise_address.starts_with("addr1v"));
+        assert_eq!(keys.stake_address, derive_staking_address(&seed).unwrap());
+    }
+
+    #[test]
+    fn test_base_address_cip19_vector() {
+        // CIP-19 test keys (addr_vk1w0l2sr2..., stake_vk1px4j0r2...)
+        let payment = VerifyingKey::from_bytes(&hex::decode(
+            "73fea80d424276ad0978d4fe5310e8bc2d485f5f6bb3bf87612989f112ad5a7d"
+        ).unwrap().try_into().unwrap()).unwrap();
+        let stake = VerifyingKey::from_bytes(&hex::decode(
+            "09ab278d49b7b86a055185c474c4942281ddfa05a54684c7e8a6f230625aee57"
+        ).unwrap().try_into().unwrap()).unwrap();
+
+        assert_eq!(
+            hex::encode(key_hash(&payment)),
+            "9493315cd92eb5d8c4304e67b7e16ae36d61d34502694657811a2c8e"
+        );
+        assert_eq!(
+            encode_base_address(&payment, &stake, NETWORK_MAINNET).unwrap(),
+            "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x"
+        );
+        assert_eq!(
+            encode_base_address(&payment, &stake, NETWORK_TESTNET).unwrap(),
+            "addr_test1qz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgs68faae"
+        );
     }
 
     #[test]
@@ -142,47 +142,12 @@ impl KeyDerivation for Ed25519Curve {
     }
     
     fn derive_path(seed: &[u8], path: &str) -> Result<(Vec<u8>, Vec<u8>), CurveError> {
-        // Parse path like "m/44'/501'/0'/0'"
-        let parts: Vec<&str> = path.split('/').collect();
+        // Parse path like "m/44'/501'/0'/0'"; every level must be hardened
+        let path = crate::wallet::DerivationPath::parse(path)
+            .map_err(|e| CurveError::DerivationFailed(e.message))?;
+        let (private_key, public_key, _) = crate::wallet::derive_ed25519_key(seed, &path)?;
         
-        if parts.is_empty() || parts[0] != "m" {
-            return Err(CurveError::DerivationFailed("Path must start with 'm'".into()));
-        }
-        
-        // Derive master key from seed using SLIP-0010
-        let mut mac = HmacSha512::new_from_slice(b"ed25519 seed")
-            .map_err(|e| CurveError::DerivationFailed(e.to_string()))?;
-        mac.update(seed);
-        let result = mac.finalize().into_bytes();
-        
-        let (master_key, master_chain_code) = result.split_at(32);
-        let mut current_key = [0u8; 32];
-        current_key.copy_from_slice(master_key);
-        let mut current_chain_code = [0u8; 32];
-        current_chain_code.copy_from_slice(master_chain_code);
-        
-        // Derive each level (all hardened for ed25519)
-        for part in parts.iter().skip(1) {
-            let index_str = part.trim_end_matches('\'').trim_end_matches('h');
-            let index: u32 = index_str.parse()
-                .map_err(|_| CurveError::DerivationFailed(format!("Invalid index: {}", part)))?;
-            
-            // Ed25519 always uses hardened derivation
-            let (new_key, new_chain) = Self::derive_child(
-                &current_key,
-                &current_chain_code,
-                index,
-                true,
-            )?;
-            
-            current_key = new_key;
-            current_chain_code = new_chain;
-        }
-        
-        // Get public key
-        let public_key = Self::public_key_from_private(&current_key)?;
-        
-        Ok((current_key.to_vec(), public_key.to_vec()))
+        Ok((private_key.to_vec(), public_key.to_vec()))
     }
 }
 
@@ -7,6 +7,7 @@
 //! - `ed25519`: Solana, Stellar, Cardano, TON, Near, Aptos, Sui
 //! - `sr25519`: Polkadot, Kusama (Substrate-based chains)
 //! - `secp256r1` (P-256/NIST): NEO, some hardware wallets
+//! - `x25519`: key agreement for encrypted messaging and backups (no signing)
 //!
 //! # Architecture
 //!
@@ -35,13 +36,15 @@ pub mod secp256k1;
 pub mod ed25519;
 pub mod sr25519;
 pub mod secp256r1;
+pub mod x25519;
 pub mod traits;
 
 pub use traits::*;
 pub use secp256k1::Secp256k1Curve;
 pub use ed25519::Ed25519Curve;
-pub use sr25519::Sr25519Curve;
+pub use sr25519::{Sr25519Curve, VrfOutput, VrfProof};
 pub use secp256r1::Secp256r1Curve;
+pub use x25519::X25519Curve;
 
 use serde::{Deserialize, Serialize};
 
@@ -59,6 +62,8 @@ pub enum CurveType {
     Sr25519,
     /// secp256r1 (P-256/NIST) - NEO, some hardware wallets
     Secp256r1,
+    /// X25519 - key agreement only (encrypted messaging, backups)
+    X25519,
 }
 
 impl CurveType {
@@ -69,6 +74,7 @@ impl CurveType {
             Self::Ed25519 => "ed25519",
             Self::Sr25519 => "sr25519",
             Self::Secp256r1 => "secp256r1",
+            Self::X25519 => "x25519",
         }
     }
     
@@ -79,6 +85,7 @@ impl CurveType {
             Self::Ed25519 => 32,
             Self::Sr25519 => 64, // MiniSecretKey is 32, but full SecretKey is 64
             Self::Secp256r1 => 32,
+            Self::X25519 => 32,
         }
     }
     
@@ -89,6 +96,7 @@ impl CurveType {
             Self::Ed25519 => 32,
             Self::Sr25519 => 32,
             Self::Secp256r1 => 33, // Compressed
+            Self::X25519 => 32,
         }
     }
     
@@ -99,6 +107,7 @@ impl CurveType {
             Self::Ed25519 => 64,
             Self::Sr25519 => 64,
             Self::Secp256r1 => 64,
+            Self::X25519 => 0, // Cannot sign
         }
     }
     
@@ -109,6 +118,7 @@ impl CurveType {
             "ed25519" => Some(Self::Ed25519),
             "sr25519" => Some(Self::Sr25519),
             "secp256r1" | "p256" | "nist256p1" => Some(Self::Secp256r1),
+            "x25519" | "curve25519" => Some(Self::X25519),
             _ => None,
         }
     }
@@ -120,6 +130,7 @@ impl CurveType {
             Self::Ed25519 => &["solana", "stellar", "cardano", "ton", "near", "aptos", "sui", "algorand"],
             Self::Sr25519 => &["polkadot", "kusama"],
             Self::Secp256r1 => &["neo"],
+            Self::X25519 => &[],
         }
     }
 }
@@ -133,7 +144,11 @@ impl std::fmt::Display for CurveType {
 // MARK: - Curve Errors
 
 /// Errors that can occur during curve operations
+///
+/// Serializes as `{"code": "invalid_private_key", "message": "..."}`, with
+/// `code` matching [`CurveError::code`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "code", content = "message", rename_all = "snake_case")]
 pub enum CurveError {
     InvalidPrivateKey(String),
     InvalidPublicKey(String),
@@ -160,6 +175,22 @@ impl std::fmt::Display for CurveError {
     }
 }
 
+impl CurveError {
+    /// Stable machine-readable code for the error kind
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidPrivateKey(_) => "invalid_private_key",
+            Self::InvalidPublicKey(_) => "invalid_public_key",
+            Self::InvalidSignature(_) => "invalid_signature",
+            Self::InvalidSeed(_) => "invalid_seed",
+            Self::SigningFailed(_) => "signing_failed",
+            Self::VerificationFailed(_) => "verification_failed",
+            Self::UnsupportedCurve(_) => "unsupported_curve",
+            Self::DerivationFailed(_) => "derivation_failed",
+        }
+    }
+}
+
 impl std::error::Error for CurveError {}
 
 // MARK: - Unified Interface
@@ -183,6 +214,10 @@ pub fn generate_keypair(curve: CurveType, seed: &[u8]) -> Result<(Vec<u8>, Vec<u
             let (sk, pk) = Secp256r1Curve::generate_keypair(seed)?;
             Ok((sk.to_vec(), pk.to_vec()))
         }
+        CurveType::X25519 => {
+            let (sk, pk) = X25519Curve::generate_keypair(seed)?;
+            Ok((sk.to_vec(), pk.to_vec()))
+        }
     }
 }
 
@@ -205,6 +240,10 @@ pub fn public_key_from_private(curve: CurveType, private_key: &[u8]) -> Result<V
             let pk = Secp256r1Curve::public_key_from_private(private_key)?;
             Ok(pk.to_vec())
         }
+        CurveType::X25519 => {
+            let pk = X25519Curve::public_key_from_private(private_key)?;
+            Ok(pk.to_vec())
+        }
     }
 }
 
@@ -227,6 +266,7 @@ pub fn sign(curve: CurveType, private_key: &[u8], message: &[u8]) -> Result<Vec<
             let sig = Secp256r1Curve::sign(private_key, message)?;
             Ok(sig.to_vec())
         }
+        CurveType::X25519 => X25519Curve::sign(private_key, message),
     }
 }
 
@@ -237,6 +277,21 @@ pub fn verify(curve: CurveType, public_key: &[u8], message: &[u8], signature: &[
         CurveType::Ed25519 => Ed25519Curve::verify(public_key, message, signature),
         CurveType::Sr25519 => Sr25519Curve::verify(public_key, message, signature),
         CurveType::Secp256r1 => Secp256r1Curve::verify(public_key, message, signature),
+        CurveType::X25519 => X25519Curve::verify(public_key, message, signature),
+    }
+}
+
+/// Derive a 32-byte shared secret with the specified curve
+///
+/// Supported for secp256k1, secp256r1 and X25519.
+pub fn key_exchange(curve: CurveType, private_key: &[u8], other_public_key: &[u8]) -> Result<[u8; 32], CurveError> {
+    match curve {
+        CurveType::Secp256k1 => Secp256k1Curve::ecdh(private_key, other_public_key),
+        CurveType::Secp256r1 => Secp256r1Curve::ecdh(private_key, other_public_key),
+        CurveType::X25519 => X25519Curve::ecdh(private_key, other_public_key),
+        CurveType::Ed25519 | CurveType::Sr25519 => Err(CurveError::UnsupportedCurve(
+            format!("{} does not support key exchange", curve.name())
+        )),
     }
 }
 
@@ -273,6 +328,49 @@ mod tests {
         assert_eq!(CurveType::from_str("secp256k1"), Some(CurveType::Secp256k1));
         assert_eq!(CurveType::from_str("Ed25519"), Some(CurveType::Ed25519));
         assert_eq!(CurveType::from_str("P256"), Some(CurveType::Secp256r1));
+        assert_eq!(CurveType::from_str("X25519"), Some(CurveType::X25519));
         assert_eq!(CurveType::from_str("invalid"), None);
     }
+    
+    #[test]
+    fn test_x25519_key_exchange() {
+        let (sk_a, pk_a) = generate_keypair(CurveType::X25519, &[7u8; 32]).unwrap();
+        let (sk_b, pk_b) = generate_keypair(CurveType::X25519, &[9u8; 32]).unwrap();
+        
+        let shared_a = key_exchange(CurveType::X25519, &sk_a, &pk_b).unwrap();
+        let shared_b = key_exchange(CurveType::X25519, &sk_b, &pk_a).unwrap();
+        assert_eq!(shared_a, shared_b);
+        
+        assert!(matches!(key_exchange(CurveType::Ed25519, &sk_a, &pk_b), Err(CurveError::UnsupportedCurve(_))));
+    }
+    
+    #[test]
+    fn test_x25519_cannot_sign() {
+        let (sk, pk) = generate_keypair(CurveType::X25519, &[7u8; 32]).unwrap();
+        
+        assert!(matches!(sign(CurveType::X25519, &sk, b"msg"), Err(CurveError::UnsupportedCurve(_))));
+        assert!(matches!(verify(CurveType::X25519, &pk, b"msg", &[0u8; 64]), Err(CurveError::UnsupportedCurve(_))));
+        assert!(sign_with_pubkey(CurveType::X25519, &sk, b"msg").is_err());
+    }
+
+    #[test]
+    fn test_curve_error_codes() {
+        let cases = [
+            (CurveError::InvalidPrivateKey("x".into()), "invalid_private_key"),
+            (CurveError::InvalidPublicKey("x".into()), "invalid_public_key"),
+            (CurveError::InvalidSignature("x".into()), "invalid_signature"),
+            (CurveError::InvalidSeed("x".into()), "invalid_seed"),
+            (CurveError::SigningFailed("x".into()), "signing_failed"),
+            (CurveError::VerificationFailed("x".into()), "verification_failed"),
+            (CurveError::UnsupportedCurve("x".into()), "unsupported_curve"),
+            (CurveError::DerivationFailed("x".into()), "derivation_failed"),
+        ];
+        for (error, code) in cases {
+            assert_eq!(error.code(), code);
+            let json = serde_json::to_value(&error).unwrap();
+            assert_eq!(json, serde_json::json!({ "code": code, "message": "x" }));
+            let parsed: CurveError = serde_json::from_value(json).unwrap();
+            assert_eq!(parsed.code(), code);
+        }
+    }
 }
@@ -8,6 +8,10 @@
 //! - Compressed and uncompressed public keys
 //! - ECDH key exchange
 //! - BIP-32 key derivation
+//!
+//! ECDSA nonces are derived deterministically per RFC-6979 (HMAC-SHA256) by
+//! libsecp256k1, so signing the same message with the same key always yields
+//! the same low-S signature.
 
 use super::{CurveError, EllipticCurve, RecoverableSignature, KeyExchange, KeyDerivation};
 use bitcoin::secp256k1::{Secp256k1, SecretKey, PublicKey, Message};
@@ -62,6 +66,8 @@ impl EllipticCurve for Secp256k1Curve {
         Ok(pk.serialize())
     }
     
+    /// Sign with an RFC-6979 deterministic nonce (low-S normalized).
+    /// Messages that are not 32 bytes are hashed with SHA-256 first.
     fn sign(private_key: &[u8], message: &[u8]) -> Result<Self::Signature, CurveError> {
         if private_key.len() != 32 {
             return Err(CurveError::InvalidPrivateKey(
@@ -270,11 +276,8 @@ impl KeyDerivation for Secp256k1Curve {
     
     fn derive_path(seed: &[u8], path: &str) -> Result<(Vec<u8>, Vec<u8>), CurveError> {
         // Parse path like "m/44'/0'/0'/0/0"
-        let parts: Vec<&str> = path.split('/').collect();
-        
-        if parts.is_empty() || parts[0] != "m" {
-            return Err(CurveError::DerivationFailed("Path must start with 'm'".into()));
-        }
+        let path = crate::wallet::DerivationPath::parse(path)
+            .map_err(|e| CurveError::DerivationFailed(e.message))?;
         
         // Derive master key from seed
         let mut mac = HmacSha512::new_from_slice(b"Bitcoin seed")
@@ -289,23 +292,12 @@ impl KeyDerivation for Secp256k1Curve {
         current_chain_code.copy_from_slice(master_chain_code);
         
         // Derive each level
-        for part in parts.iter().skip(1) {
-            let (index, hardened) = if part.ends_with('\'') || part.ends_with('h') {
-                let index_str = &part[..part.len() - 1];
-                let index: u32 = index_str.parse()
-                    .map_err(|_| CurveError::DerivationFailed(format!("Invalid index: {}", part)))?;
-                (index, true)
-            } else {
-                let index: u32 = part.parse()
-                    .map_err(|_| CurveError::DerivationFailed(format!("Invalid index: {}", part)))?;
-                (index, false)
-            };
-            
+        for component in &path.components {
             let (new_key, new_chain) = Self::derive_child(
                 &current_key,
                 &current_chain_code,
-                index,
-                hardened,
+                component.index,
+                component.hardened,
             )?;
             
             current_key = new_key;
@@ -482,3 +474,109 @@ mod tests {
         assert_eq!(signature, decoded);
     }
 }
+
+#[cfg(test)]
+mod rfc6979_tests {
+    //! RFC-6979 deterministic nonce vectors for secp256k1 + SHA-256.
+    //!
+    //! Vectors are the widely used set from bitcoinjs/python-ecdsa; `k` is the
+    //! expected nonce, checked by comparing `k·G` against the signature's `r`.
+
+    use super::*;
+
+    struct Vector {
+        private_key: &'static str,
+        message: &'static str,
+        k: &'static str,
+        signature: &'static str,
+    }
+
+    const VECTORS: &[Vector] = &[
+        Vector {
+            private_key: "0000000000000000000000000000000000000000000000000000000000000001",
+            message: "Satoshi Nakamoto",
+            k: "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15",
+            signature: "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8\
+                        2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5",
+        },
+        Vector {
+            private_key: "0000000000000000000000000000000000000000000000000000000000000001",
+            message: "All those moments will be lost in time, like tears in rain. Time to die...",
+            k: "38aa22d72376b4dbc472e06c3ba403ee0a394da63fc58d88686c611aba98d6b3",
+            signature: "8600dbd41e348fe5c9465ab92d23e3db8b98b873beecd930736488696438cb6b\
+                        547fe64427496db33bf66019dacbf0039c04199abb0122918601db38a72cfc21",
+        },
+        Vector {
+            private_key: "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+            message: "Satoshi Nakamoto",
+            k: "33a19b60e25fb6f4435af53a3d42d493644827367e6453928554f43e49aa6f90",
+            signature: "fd567d121db66e382991534ada77a6bd3106f0a1098c231e47993447cd6af2d0\
+                        6b39cd0eb1bc8603e159ef5c20a5c8ad685a45b06ce9bebed3f153d10d93bed5",
+        },
+        Vector {
+            private_key: "69ec59eaa1f4f2e36b639716b7c30ca86d9a5375c7b38d8918bd9c0ebc80ba64",
+            message: "Computer science is no more about computers than astronomy is about telescopes.",
+            k: "6bb4a594ad57c1aa22dbe991a9d8501daf4688bf50a4892ef21bd7c711afda97",
+            signature: "7186363571d65e084e7f02b0b77c3ec44fb1b257dee26274c38c928986fea45d\
+                        0de0b38e06807e46bda1f1e293f4f6323e854c86d58abdd00c46c16441085df6",
+        },
+    ];
+
+    #[test]
+    fn test_rfc6979_signatures_match_vectors() {
+        for v in VECTORS {
+            let sk = hex::decode(v.private_key).unwrap();
+            let sig = Secp256k1Curve::sign(&sk, v.message.as_bytes()).unwrap();
+            assert_eq!(hex::encode(sig), v.signature, "signature mismatch for {:?}", v.message);
+        }
+    }
+
+    #[test]
+    fn test_rfc6979_nonce_matches_r() {
+        let secp = Secp256k1::new();
+        for v in VECTORS {
+            let k = SecretKey::from_slice(&hex::decode(v.k).unwrap()).unwrap();
+            let r_point = PublicKey::from_secret_key(&secp, &k).serialize();
+            let sig = hex::decode(v.signature).unwrap();
+            // r = x(k·G) mod n; x < n for all vectors here
+            assert_eq!(&r_point[1..33], &sig[..32], "nonce mismatch for {:?}", v.message);
+        }
+    }
+
+    #[test]
+    fn test_signing_is_deterministic() {
+        let sk = [0x11u8; 32];
+        let message = b"deterministic nonce";
+
+        let sig1 = Secp256k1Curve::sign(&sk, message).unwrap();
+        let sig2 = Secp256k1Curve::sign(&sk, message).unwrap();
+        assert_eq!(sig1, sig2);
+
+        let (rsig1, rid1) = Secp256k1Curve::sign_recoverable(&sk, message).unwrap();
+        let (rsig2, rid2) = Secp256k1Curve::sign_recoverable(&sk, message).unwrap();
+        assert_eq!(rsig1, rsig2);
+        assert_eq!(rid1, rid2);
+        assert_eq!(rsig1, sig1);
+    }
+
+    #[test]
+    fn test_distinct_messages_use_distinct_nonces() {
+        let sk = [0x11u8; 32];
+        let sig1 = Secp256k1Curve::sign(&sk, b"message one").unwrap();
+        let sig2 = Secp256k1Curve::sign(&sk, b"message two").unwrap();
+        // A repeated r across different messages would leak the private key
+        assert_ne!(sig1[..32], sig2[..32]);
+    }
+
+    #[test]
+    fn test_signatures_are_low_s() {
+        // n / 2
+        let half_n = hex::decode(
+            "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0"
+        ).unwrap();
+        for v in VECTORS {
+            let sig = hex::decode(v.signature).unwrap();
+            assert!(sig[32..] <= half_n[..]);
+        }
+    }
+}
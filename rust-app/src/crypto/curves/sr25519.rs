@@ -13,6 +13,7 @@ use schnorrkel::{
     Keypair, MiniSecretKey, PublicKey, SecretKey, Signature,
     derive::{ChainCode, Derivation},
     signing_context,
+    vrf::{VRFPreOut, VRFProof},
 };
 use hmac::{Hmac, Mac};
 
@@ -24,6 +25,26 @@ const SUBSTRATE_SIGNING_CONTEXT: &[u8] = b"substrate";
 /// Sr25519 curve implementation
 pub struct Sr25519Curve;
 
+/// VRF pre-output (compressed Ristretto point), deterministic for a key and input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VrfOutput(pub [u8; 32]);
+
+/// VRF proof (DLEQ proof that the output was produced by the key holder)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VrfProof(pub [u8; 64]);
+
+impl AsRef<[u8]> for VrfOutput {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for VrfProof {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 impl EllipticCurve for Sr25519Curve {
     type PrivateKey = [u8; 64]; // SecretKey is 64 bytes
     type PublicKey = [u8; 32];
@@ -314,20 +335,56 @@ impl Sr25519Curve {
         Ok(pk.verify(ctx.bytes(message), &sig).is_ok())
     }
     
-    /// Generate VRF output
+    /// Generate VRF output and proof for `input` (Substrate signing context)
+    ///
+    /// The output is deterministic for a given key and input; the proof is not.
     pub fn vrf_sign(
         private_key: &[u8],
-        transcript_data: &[u8],
-    ) -> Result<([u8; 32], [u8; 64]), CurveError> {
+        input: &[u8],
+    ) -> Result<(VrfOutput, VrfProof), CurveError> {
         let keypair = Self::keypair_from_bytes(private_key)?;
         
-        let ctx = signing_context(b"substrate");
-        let (inout, proof, _) = keypair.vrf_sign(ctx.bytes(transcript_data));
+        let ctx = signing_context(SUBSTRATE_SIGNING_CONTEXT);
+        let (inout, proof, _) = keypair.vrf_sign(ctx.bytes(input));
+        
+        Ok((VrfOutput(inout.to_preout().to_bytes()), VrfProof(proof.to_bytes())))
+    }
+    
+    /// Verify a VRF output and proof against a public key and input
+    pub fn vrf_verify(
+        public_key: &[u8],
+        input: &[u8],
+        output: &[u8],
+        proof: &[u8],
+    ) -> Result<bool, CurveError> {
+        if public_key.len() != 32 {
+            return Err(CurveError::InvalidPublicKey(
+                format!("Public key must be 32 bytes, got {}", public_key.len())
+            ));
+        }
+        if output.len() != 32 {
+            return Err(CurveError::InvalidSignature(
+                format!("VRF output must be 32 bytes, got {}", output.len())
+            ));
+        }
+        if proof.len() != 64 {
+            return Err(CurveError::InvalidSignature(
+                format!("VRF proof must be 64 bytes, got {}", proof.len())
+            ));
+        }
+        
+        let pk = PublicKey::from_bytes(public_key)
+            .map_err(|e| CurveError::InvalidPublicKey(format!("Invalid public key: {:?}", e)))?;
+        
+        let preout = VRFPreOut::from_bytes(output)
+            .map_err(|e| CurveError::InvalidSignature(format!("Invalid VRF output: {:?}", e)))?;
+        
+        let proof = VRFProof::from_bytes(proof)
+            .map_err(|e| CurveError::InvalidSignature(format!("Invalid VRF proof: {:?}", e)))?;
         
-        let output: [u8; 32] = inout.to_preout().to_bytes();
-        let proof_bytes: [u8; 64] = proof.to_bytes();
+        let ctx = signing_context(SUBSTRATE_SIGNING_CONTEXT);
         
-        Ok((output, proof_bytes))
+        Ok(pk.vrf_verify(ctx.bytes(input), &preout, &proof).is_ok())
     }
     
     /// Convert to SS58 address (Substrate format)
@@ -453,7 +510,45 @@ mod tests {
         
         let (output, proof) = Sr25519Curve::vrf_sign(&sk, b"randomness seed").unwrap();
         
-        assert_eq!(output.len(), 32);
-        assert_eq!(proof.len(), 64);
+        assert_eq!(output.0.len(), 32);
+        assert_eq!(proof.0.len(), 64);
+    }
+    
+    #[test]
+    fn test_sr25519_vrf_verify() {
+        let seed = [42u8; 32];
+        let (sk, pk) = Sr25519Curve::generate_keypair(&seed).unwrap();
+        
+        let input = b"epoch 42 slot 7";
+        let (output, proof) = Sr25519Curve::vrf_sign(&sk, input).unwrap();
+        
+        assert!(Sr25519Curve::vrf_verify(&pk, input, &output.0, &proof.0).unwrap());
+        
+        // Wrong input should fail
+        assert!(!Sr25519Curve::vrf_verify(&pk, b"epoch 42 slot 8", &output.0, &proof.0).unwrap());
+        
+        // Wrong key should fail
+        let (_, other_pk) = Sr25519Curve::generate_keypair(&[7u8; 32]).unwrap();
+        assert!(!Sr25519Curve::vrf_verify(&other_pk, input, &output.0, &proof.0).unwrap());
+    }
+    
+    #[test]
+    fn test_sr25519_vrf_output_deterministic() {
+        let seed = [42u8; 32];
+        let (sk, _) = Sr25519Curve::generate_keypair(&seed).unwrap();
+        
+        let (output1, _) = Sr25519Curve::vrf_sign(&sk, b"lottery").unwrap();
+        let (output2, _) = Sr25519Curve::vrf_sign(&sk, b"lottery").unwrap();
+        let (output3, _) = Sr25519Curve::vrf_sign(&sk, b"other lottery").unwrap();
+        
+        assert_eq!(output1, output2);
+        assert_ne!(output1, output3);
+    }
+    
+    #[test]
+    fn test_sr25519_vrf_rejects_bad_lengths() {
+        let (_, pk) = Sr25519Curve::generate_keypair(&[42u8; 32]).unwrap();
+        assert!(Sr25519Curve::vrf_verify(&pk, b"x", &[0u8; 31], &[0u8; 64]).is_err());
+        assert!(Sr25519Curve::vrf_verify(&pk, b"x", &[0u8; 32], &[0u8; 63]).is_err());
     }
 }
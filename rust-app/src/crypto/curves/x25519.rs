@@ -0,0 +1,124 @@
+//! X25519 Curve Implementation
+//!
+//! Used by: encrypted messaging and backups (RFC 7748 key agreement)
+//!
+//! Features:
+//! - X25519 Diffie-Hellman directly on Curve25519, without converting
+//!   Ed25519 keys
+//! - No signing: `sign`/`verify` return `UnsupportedCurve`
+
+use super::{CurveError, EllipticCurve, KeyExchange};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// X25519 curve implementation
+pub struct X25519Curve;
+
+impl EllipticCurve for X25519Curve {
+    type PrivateKey = [u8; 32];
+    type PublicKey = [u8; 32];
+    type Signature = Vec<u8>;
+
+    fn generate_keypair(seed: &[u8]) -> Result<(Self::PrivateKey, Self::PublicKey), CurveError> {
+        if seed.len() < 32 {
+            return Err(CurveError::InvalidSeed(
+                format!("Seed must be at least 32 bytes, got {}", seed.len())
+            ));
+        }
+
+        let mut sk_bytes = [0u8; 32];
+        sk_bytes.copy_from_slice(&seed[..32]);
+
+        let public_key = PublicKey::from(&StaticSecret::from(sk_bytes));
+
+        Ok((sk_bytes, public_key.to_bytes()))
+    }
+
+    fn public_key_from_private(private_key: &[u8]) -> Result<Self::PublicKey, CurveError> {
+        let secret = Self::parse_private_key(private_key)?;
+        Ok(PublicKey::from(&secret).to_bytes())
+    }
+
+    fn sign(_private_key: &[u8], _message: &[u8]) -> Result<Self::Signature, CurveError> {
+        Err(CurveError::UnsupportedCurve(
+            "x25519 is a key-agreement curve and cannot sign".to_string()
+        ))
+    }
+
+    fn verify(_public_key: &[u8], _message: &[u8], _signature: &[u8]) -> Result<bool, CurveError> {
+        Err(CurveError::UnsupportedCurve(
+            "x25519 is a key-agreement curve and cannot verify".to_string()
+        ))
+    }
+}
+
+impl KeyExchange for X25519Curve {
+    fn ecdh(private_key: &[u8], other_public_key: &[u8]) -> Result<[u8; 32], CurveError> {
+        let secret = Self::parse_private_key(private_key)?;
+
+        let peer: [u8; 32] = other_public_key.try_into().map_err(|_| {
+            CurveError::InvalidPublicKey(
+                format!("Public key must be 32 bytes, got {}", other_public_key.len())
+            )
+        })?;
+
+        let shared = secret.diffie_hellman(&PublicKey::from(peer));
+
+        // Low-order peer points give an all-zero secret
+        if !shared.was_contributory() {
+            return Err(CurveError::InvalidPublicKey("Low-order public key".to_string()));
+        }
+
+        Ok(shared.to_bytes())
+    }
+}
+
+impl X25519Curve {
+    fn parse_private_key(private_key: &[u8]) -> Result<StaticSecret, CurveError> {
+        let sk_bytes: [u8; 32] = private_key.try_into().map_err(|_| {
+            CurveError::InvalidPrivateKey(
+                format!("Private key must be 32 bytes, got {}", private_key.len())
+            )
+        })?;
+        Ok(StaticSecret::from(sk_bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_x25519_rfc7748_vector() {
+        // RFC 7748 section 6.1
+        let alice_sk = hex::decode("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a").unwrap();
+        let bob_sk = hex::decode("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb").unwrap();
+
+        let alice_pk = X25519Curve::public_key_from_private(&alice_sk).unwrap();
+        let bob_pk = X25519Curve::public_key_from_private(&bob_sk).unwrap();
+        assert_eq!(hex::encode(alice_pk), "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a");
+        assert_eq!(hex::encode(bob_pk), "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f");
+
+        let shared = X25519Curve::ecdh(&alice_sk, &bob_pk).unwrap();
+        assert_eq!(hex::encode(shared), "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    }
+
+    #[test]
+    fn test_x25519_shared_secret() {
+        let (sk_a, pk_a) = X25519Curve::generate_keypair(&[1u8; 32]).unwrap();
+        let (sk_b, pk_b) = X25519Curve::generate_keypair(&[2u8; 32]).unwrap();
+
+        let shared_a = X25519Curve::ecdh(&sk_a, &pk_b).unwrap();
+        let shared_b = X25519Curve::ecdh(&sk_b, &pk_a).unwrap();
+
+        assert_eq!(shared_a, shared_b);
+    }
+
+    #[test]
+    fn test_x25519_rejects_low_order_point() {
+        let (sk, _) = X25519Curve::generate_keypair(&[1u8; 32]).unwrap();
+        assert!(matches!(
+            X25519Curve::ecdh(&sk, &[0u8; 32]),
+            Err(CurveError::InvalidPublicKey(_))
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+//! ECIES: Encrypt to a Public Key
+//!
+//! Encrypts a blob so only the holder of the recipient's private key can
+//! read it (encrypted wallet sync, backups and messaging):
+//!
+//! 1. Generate an ephemeral keypair on the recipient's curve
+//! 2. ECDH between the ephemeral private key and the recipient public key
+//! 3. HKDF-SHA256 (salt = ephemeral public key) expands the shared secret
+//!    into an AES-256-GCM key and nonce
+//!
+//! # Format
+//!
+//! ```text
+//! ephemeral_pubkey  33 bytes (secp256k1, compressed) or 32 bytes (x25519)
+//! nonce             12 bytes
+//! ciphertext         N bytes
+//! tag               16 bytes
+//! ```
+//!
+//! Output is fully determined by the ephemeral key, so
+//! [`encrypt_with_ephemeral`] gives reproducible test vectors. The ephemeral
+//! key must never be reused.
+
+#![allow(deprecated)] // GenericArray::from_slice deprecated in generic-array 1.x
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, OsRng},
+    Aes256Gcm, Nonce,
+};
+use hkdf::Hkdf;
+use rand::RngCore;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use super::curves::{generate_keypair, key_exchange, public_key_from_private, CurveType};
+use crate::error::{HawalaError, HawalaResult};
+
+/// HKDF info string, binds derived keys to this scheme
+const HKDF_INFO: &[u8] = b"hawala-ecies-v1";
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// Encrypt `plaintext` to `recipient_pubkey` with a fresh ephemeral key
+///
+/// `curve` must be secp256k1 (33- or 65-byte public key) or x25519.
+pub fn encrypt(curve: CurveType, recipient_pubkey: &[u8], plaintext: &[u8]) -> HawalaResult<Vec<u8>> {
+    check_curve(curve)?;
+
+    // A random 32-byte secp256k1 scalar is out of range with negligible
+    // probability; retry rather than fail
+    loop {
+        let mut ephemeral = Zeroizing::new([0u8; 32]);
+        OsRng.fill_bytes(&mut ephemeral[..]);
+        if generate_keypair(curve, &ephemeral[..]).is_ok() {
+            return encrypt_with_ephemeral(curve, &ephemeral[..], recipient_pubkey, plaintext);
+        }
+    }
+}
+
+/// Encrypt with a caller-supplied ephemeral private key
+pub fn encrypt_with_ephemeral(
+    curve: CurveType,
+    ephemeral_private: &[u8],
+    recipient_pubkey: &[u8],
+    plaintext: &[u8],
+) -> HawalaResult<Vec<u8>> {
+    check_curve(curve)?;
+
+    let ephemeral_pubkey = public_key_from_private(curve, ephemeral_private)
+        .map_err(|e| HawalaError::invalid_input(format!("Invalid ephemeral key: {}", e)))?;
+    let shared = Zeroizing::new(
+        key_exchange(curve, ephemeral_private, recipient_pubkey)
+            .map_err(|e| HawalaError::invalid_input(format!("Invalid recipient key: {}", e)))?,
+    );
+
+    let (key, nonce_bytes) = derive_key_nonce(&shared[..], &ephemeral_pubkey)?;
+    let cipher = Aes256Gcm::new_from_slice(&key[..])
+        .map_err(|e| HawalaError::crypto_error(format!("Failed to create cipher: {}", e)))?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), plaintext)
+        .map_err(|e| HawalaError::crypto_error(format!("Encryption failed: {}", e)))?;
+
+    let mut blob = Vec::with_capacity(ephemeral_pubkey.len() + NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(&ephemeral_pubkey);
+    blob.extend_from_slice(&nonce_bytes);
+    blob.extend_from_slice(&ciphertext);
+    Ok(blob)
+}
+
+/// Decrypt a blob produced by [`encrypt`]
+pub fn decrypt(curve: CurveType, recipient_privkey: &[u8], blob: &[u8]) -> HawalaResult<Vec<u8>> {
+    check_curve(curve)?;
+
+    let pubkey_len = curve.public_key_size();
+    if blob.len() < pubkey_len + NONCE_LEN + TAG_LEN {
+        return Err(HawalaError::invalid_input("Encrypted blob too short"));
+    }
+    let (ephemeral_pubkey, rest) = blob.split_at(pubkey_len);
+    let (nonce_bytes, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let shared = Zeroizing::new(
+        key_exchange(curve, recipient_privkey, ephemeral_pubkey)
+            .map_err(|e| HawalaError::invalid_input(format!("Key exchange failed: {}", e)))?,
+    );
+    let (key, _) = derive_key_nonce(&shared[..], ephemeral_pubkey)?;
+
+    let cipher = Aes256Gcm::new_from_slice(&key[..])
+        .map_err(|e| HawalaError::crypto_error(format!("Failed to create cipher: {}", e)))?;
+    cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| HawalaError::crypto_error("Decryption failed: wrong key or tampered data"))
+}
+
+fn check_curve(curve: CurveType) -> HawalaResult<()> {
+    match curve {
+        CurveType::Secp256k1 | CurveType::X25519 => Ok(()),
+        _ => Err(HawalaError::invalid_input(format!(
+            "ECIES supports secp256k1 and x25519, not {}",
+            curve.name()
+        ))),
+    }
+}
+
+/// HKDF-SHA256 expansion of the shared secret into a 32-byte key and nonce
+fn derive_key_nonce(shared: &[u8], ephemeral_pubkey: &[u8]) -> HawalaResult<(Zeroizing<[u8; 32]>, [u8; NONCE_LEN])> {
+    let mut okm = Zeroizing::new([0u8; 32 + NONCE_LEN]);
+    Hkdf::<Sha256>::new(Some(ephemeral_pubkey), shared)
+        .expand(HKDF_INFO, &mut okm[..])
+        .map_err(|e| HawalaError::crypto_error(format!("HKDF expansion failed: {}", e)))?;
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    key.copy_from_slice(&okm[..32]);
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce.copy_from_slice(&okm[32..]);
+    Ok((key, nonce))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipient(curve: CurveType) -> (Vec<u8>, Vec<u8>) {
+        generate_keypair(curve, &[0x42; 32]).unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip_both_curves() {
+        for curve in [CurveType::Secp256k1, CurveType::X25519] {
+            let (sk, pk) = recipient(curve);
+            let plaintext = b"encrypted wallet sync payload";
+
+            let blob = encrypt(curve, &pk, plaintext).unwrap();
+            assert_eq!(blob.len(), curve.public_key_size() + NONCE_LEN + plaintext.len() + TAG_LEN);
+            assert_eq!(decrypt(curve, &sk, &blob).unwrap(), plaintext);
+
+            // Fresh ephemeral key each time
+            assert_ne!(encrypt(curve, &pk, plaintext).unwrap(), blob);
+        }
+    }
+
+    #[test]
+    fn test_deterministic_with_ephemeral() {
+        let (sk, pk) = recipient(CurveType::X25519);
+        let a = encrypt_with_ephemeral(CurveType::X25519, &[7u8; 32], &pk, b"hello").unwrap();
+        let b = encrypt_with_ephemeral(CurveType::X25519, &[7u8; 32], &pk, b"hello").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(decrypt(CurveType::X25519, &sk, &a).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_tampered_blob_rejected() {
+        for curve in [CurveType::Secp256k1, CurveType::X25519] {
+            let (sk, pk) = recipient(curve);
+            let blob = encrypt(curve, &pk, b"secret").unwrap();
+
+            for index in [0, curve.public_key_size(), blob.len() - 1] {
+                let mut tampered = blob.clone();
+                tampered[index] ^= 0x01;
+                assert!(decrypt(curve, &sk, &tampered).is_err(), "byte {} flip accepted", index);
+            }
+
+            // Truncated tag
+            assert!(decrypt(curve, &sk, &blob[..blob.len() - 1]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_wrong_key_and_curve_rejected() {
+        let (_, pk) = recipient(CurveType::X25519);
+        let blob = encrypt(CurveType::X25519, &pk, b"secret").unwrap();
+
+        assert!(decrypt(CurveType::X25519, &[0x43; 32], &blob).is_err());
+        assert!(encrypt(CurveType::Ed25519, &pk, b"secret").is_err());
+        assert!(decrypt(CurveType::X25519, &[0x42; 32], &blob[..40]).is_err());
+    }
+}
@@ -4,15 +4,17 @@
 //! - Schnorr signatures (BIP-340) for Bitcoin Taproot
 //! - Taproot key tweaking and script trees
 //! - Tagged hash functions
-//! - Multi-curve abstractions (secp256k1, ed25519, sr25519, secp256r1)
+//! - Multi-curve abstractions (secp256k1, ed25519, sr25519, secp256r1, x25519)
+//! - ECIES encryption to a public key
 
 pub mod curves;
+pub mod ecies;
 pub mod schnorr;
 pub mod taproot;
 
 pub use curves::{
     CurveType, CurveError,
-    Secp256k1Curve, Ed25519Curve, Sr25519Curve, Secp256r1Curve,
+    Secp256k1Curve, Ed25519Curve, Sr25519Curve, Secp256r1Curve, X25519Curve,
     EllipticCurve, RecoverableSignature, KeyExchange, KeyDerivation,
 };
 pub use schnorr::*;
@@ -18,6 +18,8 @@ use bitcoin::secp256k1::{
 };
 use serde::{Deserialize, Serialize};
 
+pub mod adaptor;
+
 // MARK: - Tagged Hash Functions
 
 /// BIP-340 tagged hash computation
@@ -0,0 +1,316 @@
+//! BIP-340 Schnorr Adaptor Signatures
+//!
+//! An adaptor signature is a Schnorr signature "encrypted" to an adaptor
+//! point `T = t·G`. Anyone can check it against `T`, only the holder of `t`
+//! can complete it into a valid BIP-340 signature, and once the completed
+//! signature is published the signer can recover `t` from it. This links
+//! two transactions for HTLC-free atomic swaps.
+//!
+//! With private key `x`, nonce `k` and `R = k·G + T` (even y):
+//!
+//! - `s' = k + e·x` where `e = H_challenge(R || P || m)`
+//! - completed `s = s' + t`
+//! - recovered `t = s - s'`
+//!
+//! If `k·G + T` has odd y the nonce is negated, so `R = -(k·G + T)` and the
+//! secret enters negated: `s = s' - t`. The `negated` flag records this.
+
+use bitcoin::secp256k1::{Parity, PublicKey, Scalar, Secp256k1, SecretKey, XOnlyPublicKey};
+use zeroize::Zeroizing;
+
+use super::{tagged_hash, tags, SchnorrError, SchnorrSig, XOnlyPubKey};
+
+/// Tag for deterministic adaptor nonces
+pub const ADAPTOR_NONCE_TAG: &str = "SchnorrAdaptor/nonce";
+
+/// secp256k1 group order
+const CURVE_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b,
+    0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Schnorr adaptor signature (65 bytes: R.x || s' || negated)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdaptorSignature {
+    /// x-coordinate of the final nonce point R (even y)
+    pub r: [u8; 32],
+    /// Pre-signature scalar s'
+    pub s_hat: [u8; 32],
+    /// The nonce was negated, so the adaptor secret is subtracted
+    pub negated: bool,
+}
+
+impl AdaptorSignature {
+    /// Serialize to 65 bytes
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut bytes = [0u8; 65];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..64].copy_from_slice(&self.s_hat);
+        bytes[64] = self.negated as u8;
+        bytes
+    }
+
+    /// Parse from 65 bytes
+    pub fn from_slice(slice: &[u8]) -> Result<Self, SchnorrError> {
+        if slice.len() != 65 || slice[64] > 1 {
+            return Err(SchnorrError::InvalidSignature(
+                "Adaptor signature must be 65 bytes ending in 0x00 or 0x01".to_string()
+            ));
+        }
+        let mut r = [0u8; 32];
+        r.copy_from_slice(&slice[..32]);
+        let mut s_hat = [0u8; 32];
+        s_hat.copy_from_slice(&slice[32..64]);
+        Ok(Self { r, s_hat, negated: slice[64] == 1 })
+    }
+
+    /// Convert to hex string
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /// Parse from hex string
+    pub fn from_hex(s: &str) -> Result<Self, SchnorrError> {
+        let bytes = hex::decode(s)
+            .map_err(|e| SchnorrError::InvalidSignature(e.to_string()))?;
+        Self::from_slice(&bytes)
+    }
+}
+
+/// Compressed adaptor point `T = t·G` for a secret `t`
+pub fn adaptor_point(secret: &[u8]) -> Result<[u8; 33], SchnorrError> {
+    let t = SecretKey::from_slice(secret)
+        .map_err(|e| SchnorrError::InvalidPrivateKey(e.to_string()))?;
+    Ok(PublicKey::from_secret_key(&Secp256k1::new(), &t).serialize())
+}
+
+/// Create an adaptor signature on a 32-byte message, encrypted to
+/// `adaptor_point` (33- or 65-byte SEC1 point)
+///
+/// The nonce is derived deterministically from the key, adaptor point and
+/// message.
+pub fn encrypt_sign(
+    message: &[u8; 32],
+    private_key: &[u8],
+    adaptor_point: &[u8],
+) -> Result<AdaptorSignature, SchnorrError> {
+    let secp = Secp256k1::new();
+
+    let mut x = SecretKey::from_slice(private_key)
+        .map_err(|e| SchnorrError::InvalidPrivateKey(e.to_string()))?;
+    let (p, parity) = x.x_only_public_key(&secp);
+    if parity == Parity::Odd {
+        x = x.negate();
+    }
+    let p_bytes = p.serialize();
+    let t = parse_point(adaptor_point)?;
+
+    let mut nonce_input = Zeroizing::new(Vec::with_capacity(32 + 32 + 33 + 32));
+    nonce_input.extend_from_slice(&x.secret_bytes());
+    nonce_input.extend_from_slice(&p_bytes);
+    nonce_input.extend_from_slice(&t.serialize());
+    nonce_input.extend_from_slice(message);
+    let mut k = SecretKey::from_slice(&reduce(tagged_hash(ADAPTOR_NONCE_TAG, &nonce_input)))
+        .map_err(|e| SchnorrError::SigningFailed(format!("Invalid nonce: {}", e)))?;
+
+    let r = PublicKey::from_secret_key(&secp, &k)
+        .combine(&t)
+        .map_err(|e| SchnorrError::SigningFailed(e.to_string()))?;
+    let (r_x, r_parity) = r.x_only_public_key();
+    let negated = r_parity == Parity::Odd;
+    if negated {
+        k = k.negate();
+    }
+    let r_bytes = r_x.serialize();
+
+    let e = challenge(&r_bytes, &p_bytes, message);
+    let s_hat = x
+        .mul_tweak(&e)
+        .and_then(|ex| k.add_tweak(&Scalar::from(ex)))
+        .map_err(|e| SchnorrError::SigningFailed(e.to_string()))?;
+
+    Ok(AdaptorSignature {
+        r: r_bytes,
+        s_hat: s_hat.secret_bytes(),
+        negated,
+    })
+}
+
+/// Check that `signature` completes to a valid BIP-340 signature by
+/// `public_key` on `message` once the secret behind `adaptor_point` is added
+pub fn verify_adaptor(
+    message: &[u8; 32],
+    public_key: &XOnlyPubKey,
+    adaptor_point: &[u8],
+    signature: &AdaptorSignature,
+) -> Result<bool, SchnorrError> {
+    let secp = Secp256k1::new();
+
+    let p = public_key.to_secp256k1()?.public_key(Parity::Even);
+    let t = parse_point(adaptor_point)?;
+    let r = match XOnlyPublicKey::from_slice(&signature.r) {
+        Ok(r) => r.public_key(Parity::Even),
+        Err(_) => return Ok(false),
+    };
+    let s_hat = match SecretKey::from_slice(&signature.s_hat) {
+        Ok(s) => s,
+        Err(_) => return Ok(false),
+    };
+
+    // s'·G - e·P must equal R - T (or R + T when the nonce was negated)
+    let e = challenge(&signature.r, public_key.as_bytes(), message);
+    let lhs = p
+        .mul_tweak(&secp, &e)
+        .and_then(|ep| PublicKey::from_secret_key(&secp, &s_hat).combine(&ep.negate(&secp)));
+    let t_signed = if signature.negated { t } else { t.negate(&secp) };
+    let rhs = r.combine(&t_signed);
+
+    match (lhs, rhs) {
+        (Ok(lhs), Ok(rhs)) => Ok(lhs == rhs),
+        _ => Ok(false),
+    }
+}
+
+/// Complete an adaptor signature with the adaptor secret
+pub fn decrypt(signature: &AdaptorSignature, secret: &[u8]) -> Result<SchnorrSig, SchnorrError> {
+    let mut t = SecretKey::from_slice(secret)
+        .map_err(|e| SchnorrError::InvalidPrivateKey(e.to_string()))?;
+    if signature.negated {
+        t = t.negate();
+    }
+    let s = SecretKey::from_slice(&signature.s_hat)
+        .and_then(|s_hat| s_hat.add_tweak(&Scalar::from(t)))
+        .map_err(|e| SchnorrError::InvalidSignature(e.to_string()))?;
+
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(&signature.r);
+    bytes[32..].copy_from_slice(&s.secret_bytes());
+    Ok(SchnorrSig(bytes))
+}
+
+/// Recover the adaptor secret from an adaptor signature and the completed
+/// signature published on-chain
+pub fn recover_secret(signature: &AdaptorSignature, final_sig: &SchnorrSig) -> Result<[u8; 32], SchnorrError> {
+    if final_sig.r() != signature.r {
+        return Err(SchnorrError::InvalidSignature(
+            "Signature does not complete this adaptor signature".to_string()
+        ));
+    }
+
+    let s = SecretKey::from_slice(final_sig.s())
+        .map_err(|e| SchnorrError::InvalidSignature(e.to_string()))?;
+    let s_hat = SecretKey::from_slice(&signature.s_hat)
+        .map_err(|e| SchnorrError::InvalidSignature(e.to_string()))?;
+    let diff = s
+        .add_tweak(&Scalar::from(s_hat.negate()))
+        .map_err(|e| SchnorrError::InvalidSignature(e.to_string()))?;
+
+    let t = if signature.negated { diff.negate() } else { diff };
+    Ok(t.secret_bytes())
+}
+
+fn parse_point(bytes: &[u8]) -> Result<PublicKey, SchnorrError> {
+    PublicKey::from_slice(bytes)
+        .map_err(|e| SchnorrError::InvalidPublicKey(format!("Invalid adaptor point: {}", e)))
+}
+
+/// BIP-340 challenge `e = H_challenge(R || P || m) mod n`
+fn challenge(r: &[u8; 32], p: &[u8; 32], message: &[u8; 32]) -> Scalar {
+    let mut data = [0u8; 96];
+    data[..32].copy_from_slice(r);
+    data[32..64].copy_from_slice(p);
+    data[64..].copy_from_slice(message);
+    Scalar::from_be_bytes(reduce(tagged_hash(tags::BIP0340_CHALLENGE, &data)))
+        .expect("reduced below the curve order")
+}
+
+/// Reduce a 32-byte big-endian integer modulo the curve order (a single
+/// subtraction suffices since 2^256 < 2n)
+fn reduce(mut bytes: [u8; 32]) -> [u8; 32] {
+    if bytes < CURVE_ORDER {
+        return bytes;
+    }
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = bytes[i] as i16 - CURVE_ORDER[i] as i16 - borrow;
+        borrow = (diff < 0) as i16;
+        bytes[i] = diff.rem_euclid(256) as u8;
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::schnorr::{schnorr_public_key, schnorr_verify};
+
+    #[test]
+    fn test_adaptor_swap_flow() {
+        let private_key = [0x11u8; 32];
+        let public_key = schnorr_public_key(&private_key).unwrap();
+        let message = [0xAAu8; 32];
+
+        // Several secrets so both nonce parities are exercised
+        for i in 1..=8u8 {
+            let secret = [i; 32];
+            let point = adaptor_point(&secret).unwrap();
+
+            let adaptor_sig = encrypt_sign(&message, &private_key, &point).unwrap();
+            assert!(verify_adaptor(&message, &public_key, &point, &adaptor_sig).unwrap());
+
+            // The pre-signature alone is not a valid signature
+            let mut pre = [0u8; 64];
+            pre[..32].copy_from_slice(&adaptor_sig.r);
+            pre[32..].copy_from_slice(&adaptor_sig.s_hat);
+            assert!(!schnorr_verify(&message, &SchnorrSig(pre), &public_key).unwrap());
+
+            let final_sig = decrypt(&adaptor_sig, &secret).unwrap();
+            assert!(schnorr_verify(&message, &final_sig, &public_key).unwrap());
+
+            assert_eq!(recover_secret(&adaptor_sig, &final_sig).unwrap(), secret);
+        }
+    }
+
+    #[test]
+    fn test_verify_adaptor_rejects_wrong_inputs() {
+        let private_key = [0x11u8; 32];
+        let public_key = schnorr_public_key(&private_key).unwrap();
+        let message = [0xAAu8; 32];
+        let point = adaptor_point(&[0x07; 32]).unwrap();
+        let adaptor_sig = encrypt_sign(&message, &private_key, &point).unwrap();
+
+        let other_point = adaptor_point(&[0x08; 32]).unwrap();
+        assert!(!verify_adaptor(&message, &public_key, &other_point, &adaptor_sig).unwrap());
+        assert!(!verify_adaptor(&[0xBB; 32], &public_key, &point, &adaptor_sig).unwrap());
+
+        let mut tampered = adaptor_sig.clone();
+        tampered.negated = !tampered.negated;
+        assert!(!verify_adaptor(&message, &public_key, &point, &tampered).unwrap());
+
+        // Completing with the wrong secret gives an invalid signature
+        let wrong = decrypt(&adaptor_sig, &[0x08; 32]).unwrap();
+        assert!(!schnorr_verify(&message, &wrong, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_adaptor_signature_bytes_roundtrip() {
+        let point = adaptor_point(&[0x07; 32]).unwrap();
+        let adaptor_sig = encrypt_sign(&[0xAA; 32], &[0x11; 32], &point).unwrap();
+
+        let parsed = AdaptorSignature::from_hex(&adaptor_sig.to_hex()).unwrap();
+        assert_eq!(parsed, adaptor_sig);
+        assert!(AdaptorSignature::from_slice(&[0u8; 64]).is_err());
+    }
+
+    #[test]
+    fn test_reduce() {
+        assert_eq!(reduce([0x01; 32]), [0x01; 32]);
+        let mut order_plus_one = CURVE_ORDER;
+        order_plus_one[31] += 1;
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        assert_eq!(reduce(order_plus_one), one);
+    }
+}
@@ -95,35 +95,55 @@ pub fn type_hash(
 }
 
 /// Encode a value according to its type
+///
+/// Structs return `typeHash || encodeData`, arrays the concatenation of their
+/// encoded members and dynamic types their raw bytes; `encode_member` turns
+/// these into the 32-byte word used inside a parent struct or array.
 pub fn encode_value(
     type_name: &str,
     value: &serde_json::Value,
     types: &HashMap<String, Vec<TypedDataField>>,
 ) -> Result<Vec<u8>, Eip712Error> {
-    let base_type = get_base_type(type_name);
-    
     // Check if it's an array type
-    if type_name.contains('[') {
+    if type_name.ends_with(']') {
         return encode_array(type_name, value, types);
     }
     
     // Dynamic types
-    if base_type == "bytes" {
+    if type_name == "bytes" {
         return encode_bytes(value);
     }
-    if base_type == "string" {
+    if type_name == "string" {
         return encode_string(value);
     }
     
     // Struct types (referenced types)
-    if types.contains_key(base_type) {
-        return encode_struct(base_type, value, types);
+    if types.contains_key(type_name) {
+        return encode_struct(type_name, value, types);
     }
     
     // Atomic types
     encode_atomic(type_name, value)
 }
 
+/// Encode a struct member or array element as a single 32-byte word
+///
+/// Structs, arrays and dynamic types are hashed; atomic types are encoded
+/// in place.
+fn encode_member(
+    type_name: &str,
+    value: &serde_json::Value,
+    types: &HashMap<String, Vec<TypedDataField>>,
+) -> Result<Vec<u8>, Eip712Error> {
+    let encoded = encode_value(type_name, value, types)?;
+    
+    if type_name.ends_with(']') || types.contains_key(type_name) || is_dynamic_type(type_name) {
+        Ok(keccak256(&encoded).to_vec())
+    } else {
+        Ok(encoded)
+    }
+}
+
 /// Encode a struct value
 fn encode_struct(
     type_name: &str,
@@ -152,23 +172,16 @@ fn encode_struct(
             Eip712Error::MissingField(format!("{}.{}", type_name, field.name))
         })?;
         
-        let encoded_field = encode_value(&field.type_name, field_value, types)?;
-        
-        // For struct references, we encode the hash
-        if types.contains_key(get_base_type(&field.type_name)) || field.type_name.contains('[') {
-            encoded.extend_from_slice(&keccak256(&encoded_field));
-        } else if field.type_name == "bytes" || field.type_name == "string" {
-            // Dynamic types are hashed
-            encoded.extend_from_slice(&keccak256(&encoded_field));
-        } else {
-            encoded.extend(encoded_field);
-        }
+        encoded.extend(encode_member(&field.type_name, field_value, types)?);
     }
     
     Ok(encoded)
 }
 
 /// Encode an array value
+///
+/// Only the last dimension is peeled off, so `T[2][]` is a dynamic array
+/// whose elements are `T[2]` arrays (hashed like any other member).
 fn encode_array(
     type_name: &str,
     value: &serde_json::Value,
@@ -181,25 +194,28 @@ fn encode_array(
         }
     })?;
     
-    // Get the element type
-    let bracket_pos = type_name.find('[').ok_or_else(|| {
+    // Get the element type and declared length
+    let bracket_pos = type_name.rfind('[').ok_or_else(|| {
         Eip712Error::InvalidType(type_name.to_string())
     })?;
     let element_type = &type_name[..bracket_pos];
+    let length = &type_name[bracket_pos + 1..type_name.len() - 1];
     
-    let mut encoded = Vec::new();
+    if !length.is_empty() {
+        let expected: usize = length.parse().map_err(|_| {
+            Eip712Error::InvalidType(type_name.to_string())
+        })?;
+        if arr.len() != expected {
+            return Err(Eip712Error::InvalidValue {
+                type_name: type_name.to_string(),
+                value: format!("expected {} elements, got {}", expected, arr.len()),
+            });
+        }
+    }
     
+    let mut encoded = Vec::with_capacity(arr.len() * 32);
     for item in arr {
-        let item_encoded = encode_value(element_type, item, types)?;
-        
-        // For structs and dynamic types, we include the hash
-        if types.contains_key(element_type) {
-            encoded.extend_from_slice(&keccak256(&item_encoded));
-        } else if element_type == "bytes" || element_type == "string" {
-            encoded.extend_from_slice(&keccak256(&item_encoded));
-        } else {
-            encoded.extend(item_encoded);
-        }
+        encoded.extend(encode_member(element_type, item, types)?);
     }
     
     Ok(encoded)
@@ -234,22 +250,32 @@ fn encode_atomic(type_name: &str, value: &serde_json::Value) -> Result<Vec<u8>,
         return Ok(result.to_vec());
     }
     
+    let out_of_range = || Eip712Error::InvalidValue {
+        type_name: type_name.to_string(),
+        value: value.to_string(),
+    };
+    
     // uintN
-    if type_name.starts_with("uint") {
+    if let Some(bits) = type_name.strip_prefix("uint") {
+        let width = integer_width_bytes(type_name, bits)?;
         let bytes = parse_uint(value)?;
+        if bytes.iter().skip_while(|&&b| b == 0).count() > width {
+            return Err(out_of_range());
+        }
         result[32 - bytes.len()..].copy_from_slice(&bytes);
         return Ok(result.to_vec());
     }
     
-    // intN (handled same as uint for encoding, sign extension handled separately)
-    if type_name.starts_with("int") {
-        let bytes = parse_int(value)?;
-        // Sign extend if negative
-        if bytes[0] & 0x80 != 0 {
-            result = [0xff; 32];
+    // intN (two's complement, sign-extended to 256 bits)
+    if let Some(bits) = type_name.strip_prefix("int") {
+        let width = integer_width_bytes(type_name, bits)?;
+        let word = parse_int(value)?;
+        // Everything above the low `width` bytes must be sign extension
+        let sign = if word[32 - width] & 0x80 != 0 { 0xff } else { 0x00 };
+        if word[..32 - width].iter().any(|&b| b != sign) {
+            return Err(out_of_range());
         }
-        result[32 - bytes.len()..].copy_from_slice(&bytes);
-        return Ok(result.to_vec());
+        return Ok(word.to_vec());
     }
     
     // bytesN (fixed-size bytes, right-padded)
@@ -325,56 +351,131 @@ fn parse_address(addr: &str) -> Result<[u8; 20], Eip712Error> {
     Ok(result)
 }
 
+/// Byte width of an `intN`/`uintN` type; N must be a multiple of 8 in 8..=256,
+/// and a bare `int`/`uint` is 256 bits as in Solidity
+fn integer_width_bytes(type_name: &str, bits: &str) -> Result<usize, Eip712Error> {
+    if bits.is_empty() {
+        return Ok(32);
+    }
+    match bits.parse::<usize>() {
+        Ok(n) if (8..=256).contains(&n) && n.is_multiple_of(8) && !bits.starts_with('0') => Ok(n / 8),
+        _ => Err(Eip712Error::InvalidType(type_name.to_string())),
+    }
+}
+
 /// Parse a uint value (supports decimal string, hex string, or number)
+///
+/// Returns at most 32 big-endian bytes.
 fn parse_uint(value: &serde_json::Value) -> Result<Vec<u8>, Eip712Error> {
-    match value {
+    let bytes = match value {
         serde_json::Value::Number(n) => {
             if let Some(u) = n.as_u64() {
                 return Ok(u.to_be_bytes().to_vec());
             }
-            if let Some(i) = n.as_i64() {
-                if i >= 0 {
-                    return Ok((i as u64).to_be_bytes().to_vec());
-                }
-            }
             // For very large numbers, try to parse as string
-            Ok(parse_big_uint(&n.to_string())?)
+            parse_big_uint(&n.to_string())?
         }
         serde_json::Value::String(s) => {
-            if s.starts_with("0x") || s.starts_with("0X") {
-                parse_hex(s)
+            if let Some(hex_digits) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                // Quantities like "0x1" have an odd number of digits
+                if hex_digits.len() % 2 == 1 {
+                    parse_hex(&format!("0{}", hex_digits))?
+                } else {
+                    parse_hex(hex_digits)?
+                }
             } else {
-                parse_big_uint(s)
+                parse_big_uint(s)?
             }
         }
-        _ => Err(Eip712Error::InvalidValue {
+        _ => {
+            return Err(Eip712Error::InvalidValue {
+                type_name: "uint256".to_string(),
+                value: value.to_string(),
+            })
+        }
+    };
+    
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    if bytes.len() - start > 32 {
+        return Err(Eip712Error::InvalidValue {
             type_name: "uint256".to_string(),
             value: value.to_string(),
-        }),
+        });
     }
+    Ok(bytes[start..].to_vec())
 }
 
-/// Parse a signed int value
-fn parse_int(value: &serde_json::Value) -> Result<Vec<u8>, Eip712Error> {
-    // For simplicity, handle same as uint for now
-    // Full implementation would handle negative values properly
-    parse_uint(value)
+/// Parse a signed int value as a 256-bit two's complement word
+fn parse_int(value: &serde_json::Value) -> Result<[u8; 32], Eip712Error> {
+    let out_of_range = || Eip712Error::InvalidValue {
+        type_name: "int256".to_string(),
+        value: value.to_string(),
+    };
+    
+    let (negative, magnitude) = match value {
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => (i < 0, i.unsigned_abs().to_be_bytes().to_vec()),
+            None => (false, parse_uint(value)?),
+        },
+        serde_json::Value::String(s) => match s.strip_prefix('-') {
+            Some(abs) => (true, parse_uint(&serde_json::Value::String(abs.to_string()))?),
+            None => (false, parse_uint(value)?),
+        },
+        _ => return Err(out_of_range()),
+    };
+    
+    let mut result = [0u8; 32];
+    result[32 - magnitude.len()..].copy_from_slice(&magnitude);
+    
+    if !negative {
+        // Positive values must leave the sign bit clear
+        if result[0] & 0x80 != 0 {
+            return Err(out_of_range());
+        }
+        return Ok(result);
+    }
+    
+    // Negate: invert and add one
+    let mut carry = true;
+    for byte in result.iter_mut().rev() {
+        let (sum, overflow) = (!*byte).overflowing_add(carry as u8);
+        *byte = sum;
+        carry = overflow;
+    }
+    // Magnitudes above 2^255 wrap back to a positive word
+    if result[0] & 0x80 == 0 && result.iter().any(|&b| b != 0) {
+        return Err(out_of_range());
+    }
+    Ok(result)
 }
 
-/// Parse a big unsigned integer from decimal string
+/// Parse a big unsigned integer from decimal string (up to 256 bits)
 fn parse_big_uint(s: &str) -> Result<Vec<u8>, Eip712Error> {
-    // Simple implementation using u128 for now
-    // For full EIP-712 support, would need arbitrary precision
-    let n: u128 = s.parse().map_err(|_| {
-        Eip712Error::InvalidValue {
-            type_name: "uint256".to_string(),
-            value: s.to_string(),
+    let invalid = || Eip712Error::InvalidValue {
+        type_name: "uint256".to_string(),
+        value: s.to_string(),
+    };
+    
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    
+    // Multiply-and-add in base 256
+    let mut bytes = [0u8; 32];
+    for digit in s.bytes() {
+        let mut carry = (digit - b'0') as u32;
+        for byte in bytes.iter_mut().rev() {
+            let v = *byte as u32 * 10 + carry;
+            *byte = v as u8;
+            carry = v >> 8;
         }
-    })?;
+        if carry != 0 {
+            return Err(invalid());
+        }
+    }
     
     // Convert to big-endian bytes, trimming leading zeros
-    let bytes = n.to_be_bytes();
-    let start = bytes.iter().position(|&b| b != 0).unwrap_or(15);
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(31);
     Ok(bytes[start..].to_vec())
 }
 
@@ -446,6 +547,63 @@ mod encoder_tests {
         );
     }
     
+    #[test]
+    fn test_integer_widths() {
+        for type_name in ["uint7", "uint0", "uint264", "int12", "int512", "uint08"] {
+            assert!(
+                matches!(encode_atomic(type_name, &serde_json::json!(1)), Err(Eip712Error::InvalidType(_))),
+                "{}", type_name
+            );
+        }
+
+        assert_eq!(encode_atomic("uint8", &serde_json::json!(255)).unwrap()[31], 0xff);
+        assert!(encode_atomic("uint8", &serde_json::json!(256)).is_err());
+        assert!(encode_atomic("uint256", &serde_json::json!("0x01")).is_ok());
+
+        // Bare uint/int are aliases for uint256/int256
+        assert_eq!(
+            encode_atomic("uint", &serde_json::json!("0x0100")).unwrap(),
+            encode_atomic("uint256", &serde_json::json!("0x0100")).unwrap()
+        );
+        assert_eq!(encode_atomic("int", &serde_json::json!(-1)).unwrap(), vec![0xff; 32]);
+
+        let minus_128 = encode_atomic("int8", &serde_json::json!(-128)).unwrap();
+        assert!(minus_128[..31].iter().all(|&b| b == 0xff));
+        assert_eq!(minus_128[31], 0x80);
+        assert!(encode_atomic("int8", &serde_json::json!(127)).is_ok());
+        assert!(encode_atomic("int8", &serde_json::json!(128)).is_err());
+        assert!(encode_atomic("int8", &serde_json::json!(-129)).is_err());
+    }
+    
+    #[test]
+    fn test_parse_int_twos_complement() {
+        let minus_one = parse_int(&serde_json::json!(-1)).unwrap();
+        assert_eq!(minus_one, [0xff; 32]);
+        
+        // Positive values with the low byte's high bit set are not sign-extended
+        let positive = parse_int(&serde_json::json!(200)).unwrap();
+        assert_eq!(positive[31], 200);
+        assert!(positive[..31].iter().all(|&b| b == 0));
+        
+        let min = parse_int(&serde_json::json!("-57896044618658097711785492504343953926634992332820282019728792003956564819968")).unwrap();
+        assert_eq!(min[0], 0x80);
+        assert!(min[1..].iter().all(|&b| b == 0));
+        
+        // 2^255 doesn't fit in int256
+        assert!(parse_int(&serde_json::json!("57896044618658097711785492504343953926634992332820282019728792003956564819968")).is_err());
+    }
+    
+    #[test]
+    fn test_parse_big_uint() {
+        let max = parse_big_uint("115792089237316195423570985008687907853269984665640564039457584007913129639935").unwrap();
+        assert_eq!(max, vec![0xff; 32]);
+        assert_eq!(parse_big_uint("256").unwrap(), vec![0x01, 0x00]);
+        
+        // 2^256 overflows
+        assert!(parse_big_uint("115792089237316195423570985008687907853269984665640564039457584007913129639936").is_err());
+        assert!(parse_big_uint("-1").is_err());
+    }
+    
     #[test]
     fn test_get_base_type() {
         assert_eq!(get_base_type("Person[]"), "Person");
@@ -2,7 +2,7 @@
 //!
 //! Implements domain separator and struct hashing for EIP-712.
 
-use super::encoder::{encode_value, keccak256, type_hash};
+use super::encoder::{encode_value, keccak256};
 use super::types::*;
 use std::collections::HashMap;
 
@@ -12,59 +12,20 @@ const EIP712_PREFIX: &[u8] = b"\x19\x01";
 /// Calculate the domain separator hash
 ///
 /// domainSeparator = hashStruct(eip712Domain)
+///
+/// The `EIP712Domain` type is built from the fields present in the domain,
+/// so salted domains and domains without `chainId` or `verifyingContract`
+/// hash correctly.
 pub fn domain_separator(domain: &Eip712Domain, types: &HashMap<String, Vec<TypedDataField>>) -> Result<[u8; 32], Eip712Error> {
-    // Build domain type based on which fields are present
-    let domain_fields = get_domain_fields(domain);
-    
     // Create a temporary types map with EIP712Domain
     let mut types_with_domain = types.clone();
-    types_with_domain.insert("EIP712Domain".to_string(), domain_fields.clone());
+    types_with_domain.insert("EIP712Domain".to_string(), domain.fields());
     
     // Build the domain data as JSON Value
-    let _domain_value = domain_to_json(domain)?;
+    let domain_value = domain_to_json(domain)?;
     
     // Hash the domain struct
-    let encoded = encode_domain_struct(domain, &domain_fields, &types_with_domain)?;
-    
-    Ok(keccak256(&encoded))
-}
-
-/// Get the domain fields based on which values are present
-fn get_domain_fields(domain: &Eip712Domain) -> Vec<TypedDataField> {
-    let mut fields = Vec::new();
-    
-    if domain.name.is_some() {
-        fields.push(TypedDataField {
-            name: "name".to_string(),
-            type_name: "string".to_string(),
-        });
-    }
-    if domain.version.is_some() {
-        fields.push(TypedDataField {
-            name: "version".to_string(),
-            type_name: "string".to_string(),
-        });
-    }
-    if domain.chain_id.is_some() {
-        fields.push(TypedDataField {
-            name: "chainId".to_string(),
-            type_name: "uint256".to_string(),
-        });
-    }
-    if domain.verifying_contract.is_some() {
-        fields.push(TypedDataField {
-            name: "verifyingContract".to_string(),
-            type_name: "address".to_string(),
-        });
-    }
-    if domain.salt.is_some() {
-        fields.push(TypedDataField {
-            name: "salt".to_string(),
-            type_name: "bytes32".to_string(),
-        });
-    }
-    
-    fields
+    hash_struct("EIP712Domain", &domain_value, &types_with_domain)
 }
 
 /// Convert domain to JSON value
@@ -72,65 +33,6 @@ fn domain_to_json(domain: &Eip712Domain) -> Result<serde_json::Value, Eip712Erro
     serde_json::to_value(domain).map_err(|e| Eip712Error::InvalidJson(e.to_string()))
 }
 
-/// Encode the domain struct specially
-fn encode_domain_struct(
-    domain: &Eip712Domain,
-    fields: &[TypedDataField],
-    types: &HashMap<String, Vec<TypedDataField>>,
-) -> Result<Vec<u8>, Eip712Error> {
-    let mut encoded = Vec::new();
-    
-    // Add the type hash
-    encoded.extend_from_slice(&type_hash("EIP712Domain", types)?);
-    
-    // Encode each field
-    for field in fields {
-        match field.name.as_str() {
-            "name" => {
-                if let Some(ref name) = domain.name {
-                    encoded.extend_from_slice(&keccak256(name.as_bytes()));
-                }
-            }
-            "version" => {
-                if let Some(ref version) = domain.version {
-                    encoded.extend_from_slice(&keccak256(version.as_bytes()));
-                }
-            }
-            "chainId" => {
-                if let Some(bytes) = domain.chain_id_bytes() {
-                    encoded.extend_from_slice(&bytes);
-                }
-            }
-            "verifyingContract" => {
-                if let Some(ref addr) = domain.verifying_contract {
-                    let addr = addr.strip_prefix("0x").unwrap_or(addr);
-                    let addr_bytes = hex::decode(addr).map_err(|e| {
-                        Eip712Error::InvalidAddress(e.to_string())
-                    })?;
-                    let mut padded = [0u8; 32];
-                    padded[12..].copy_from_slice(&addr_bytes);
-                    encoded.extend_from_slice(&padded);
-                }
-            }
-            "salt" => {
-                if let Some(ref salt) = domain.salt {
-                    let salt = salt.strip_prefix("0x").unwrap_or(salt);
-                    let salt_bytes = hex::decode(salt).map_err(|e| {
-                        Eip712Error::EncodingError(e.to_string())
-                    })?;
-                    let mut padded = [0u8; 32];
-                    let len = salt_bytes.len().min(32);
-                    padded[..len].copy_from_slice(&salt_bytes[..len]);
-                    encoded.extend_from_slice(&padded);
-                }
-            }
-            _ => {}
-        }
-    }
-    
-    Ok(encoded)
-}
-
 /// Hash a struct according to EIP-712
 ///
 /// hashStruct(s) = keccak256(typeHash || encodeData(s))
@@ -253,8 +155,72 @@ mod hasher_tests {
         let typed_data = create_mail_example();
         let separator = domain_separator(&typed_data.domain, &typed_data.types).unwrap();
         
-        // The domain separator is deterministic
-        assert_eq!(separator.len(), 32);
+        // Domain separator from the EIP-712 reference implementation
+        assert_eq!(
+            hex::encode(separator),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+    }
+    
+    #[test]
+    fn test_domain_separator_salt_only() {
+        let domain = Eip712Domain {
+            salt: Some("0xf2d857f4a3edcb9b78b4d503bfe733db1e3f6cdc2b7971ee739626c97e86a558".to_string()),
+            ..Default::default()
+        };
+        
+        let separator = domain_separator(&domain, &HashMap::new()).unwrap();
+        assert_eq!(
+            hex::encode(separator),
+            "4834bc18e91a71c310397259fd68d0978abab63f54d06ffe0c7229cd7e9c5887"
+        );
+    }
+    
+    #[test]
+    fn test_domain_separator_name_version_only() {
+        let domain = Eip712Domain {
+            name: Some("Ether Mail".to_string()),
+            version: Some("1".to_string()),
+            ..Default::default()
+        };
+        
+        let separator = domain_separator(&domain, &HashMap::new()).unwrap();
+        assert_eq!(
+            hex::encode(separator),
+            "3672940656dbbfdd066ff6a32e08597dc0389bb88feb714e9eb8d8b151f25aec"
+        );
+    }
+    
+    #[test]
+    fn test_domain_separator_salted_without_contract() {
+        let domain = Eip712Domain {
+            name: Some("Ether Mail".to_string()),
+            version: Some("1".to_string()),
+            chain_id: Some(serde_json::json!(1)),
+            salt: Some("0xf2d857f4a3edcb9b78b4d503bfe733db1e3f6cdc2b7971ee739626c97e86a558".to_string()),
+            ..Default::default()
+        };
+        
+        let separator = domain_separator(&domain, &HashMap::new()).unwrap();
+        assert_eq!(
+            hex::encode(separator),
+            "06d8b0992592ae77be8e3235512571e5c8373960e3a5df936efdc20089c828b7"
+        );
+    }
+    
+    #[test]
+    fn test_domain_separator_rejects_malformed_fields() {
+        let short_contract = Eip712Domain {
+            verifying_contract: Some("0x1234".to_string()),
+            ..Default::default()
+        };
+        assert!(domain_separator(&short_contract, &HashMap::new()).is_err());
+        
+        let long_salt = Eip712Domain {
+            salt: Some(format!("0x{}", "11".repeat(33))),
+            ..Default::default()
+        };
+        assert!(domain_separator(&long_salt, &HashMap::new()).is_err());
     }
     
     #[test]
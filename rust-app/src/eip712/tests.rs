@@ -165,7 +165,171 @@ fn test_eip712_struct_arrays() {
     typed_data.validate().unwrap();
     
     let hash = hash_typed_data(&typed_data).unwrap();
-    assert_eq!(hash.len(), 32);
+    assert_eq!(
+        hex::encode(hash),
+        "63cac3e242e5304ba9207557c30b2dcf8ad59b3e05c30944f64b7948031c4b48"
+    );
+}
+
+/// Test the eth_signTypedData_v4 Mail example (Person[] recipients, address[] wallets)
+#[test]
+fn test_eip712_mail_v4_struct_arrays() {
+    let json = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "version", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"}
+            ],
+            "Group": [
+                {"name": "name", "type": "string"},
+                {"name": "members", "type": "Person[]"}
+            ],
+            "Mail": [
+                {"name": "from", "type": "Person"},
+                {"name": "to", "type": "Person[]"},
+                {"name": "contents", "type": "string"}
+            ],
+            "Person": [
+                {"name": "name", "type": "string"},
+                {"name": "wallets", "type": "address[]"}
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": {
+                "name": "Cow",
+                "wallets": [
+                    "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+                    "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF"
+                ]
+            },
+            "to": [
+                {
+                    "name": "Bob",
+                    "wallets": [
+                        "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB",
+                        "0xB0BdaBea57B0BDABeA57b0bdABEA57b0BDabEa57",
+                        "0xB0B0b0b0b0b0B000000000000000000000000000"
+                    ]
+                }
+            ],
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+    
+    let typed_data = TypedData::from_json(json).unwrap();
+    
+    // Unused types (Group) are not part of the encoded type
+    assert_eq!(
+        encode_type("Mail", &typed_data.types).unwrap(),
+        "Mail(Person from,Person[] to,string contents)Person(string name,address[] wallets)"
+    );
+    
+    // Reference digest from eth-sig-util / MetaMask
+    let hash = hash_typed_data(&typed_data).unwrap();
+    assert_eq!(
+        hex::encode(hash),
+        "a85c2e2b118698e88db68a8105b794a8cc7cec074e89ef991cb4f5f533819cc2"
+    );
+}
+
+/// Test multi-dimensional and fixed-size arrays
+#[test]
+fn test_eip712_nested_arrays() {
+    let json = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "chainId", "type": "uint256"}
+            ],
+            "Person": [
+                {"name": "name", "type": "string"},
+                {"name": "wallet", "type": "address"}
+            ],
+            "Board": [
+                {"name": "cells", "type": "uint256[2][]"},
+                {"name": "tags", "type": "string[]"},
+                {"name": "owners", "type": "Person[2]"},
+                {"name": "teams", "type": "Person[][]"}
+            ]
+        },
+        "primaryType": "Board",
+        "domain": {
+            "name": "Grid",
+            "chainId": 1
+        },
+        "message": {
+            "cells": [[1, 2], [3, 4], [5, 6]],
+            "tags": ["a", "bc"],
+            "owners": [
+                {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+                {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"}
+            ],
+            "teams": [
+                [{"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"}],
+                []
+            ]
+        }
+    }"#;
+    
+    let typed_data = TypedData::from_json(json).unwrap();
+    let hash = hash_typed_data(&typed_data).unwrap();
+    assert_eq!(
+        hex::encode(hash),
+        "fee0ce92d27dac3b4706e6bc3e58839624f277a27a510e14f28ebf976e954f71"
+    );
+    
+    // Fixed-size arrays must have exactly the declared length
+    let mut short = typed_data.clone();
+    short.message["owners"].as_array_mut().unwrap().pop();
+    assert!(hash_typed_data(&short).is_err());
+}
+
+/// Test negative ints and full-width uint256 values
+#[test]
+fn test_eip712_signed_and_large_integers() {
+    let json = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "chainId", "type": "uint256"}
+            ],
+            "Values": [
+                {"name": "delta", "type": "int256"},
+                {"name": "positive", "type": "int256"},
+                {"name": "min", "type": "int8"},
+                {"name": "allowance", "type": "uint256"},
+                {"name": "nonce", "type": "uint256"}
+            ]
+        },
+        "primaryType": "Values",
+        "domain": {
+            "name": "Ints",
+            "chainId": 1
+        },
+        "message": {
+            "delta": -1,
+            "positive": 200,
+            "min": "-128",
+            "allowance": "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+            "nonce": "0x1"
+        }
+    }"#;
+    
+    let typed_data = TypedData::from_json(json).unwrap();
+    let hash = hash_typed_data(&typed_data).unwrap();
+    assert_eq!(
+        hex::encode(hash),
+        "e6557640f738f37967284a22798f6020ce0a4c1d5b67e61a65f69051257e9367"
+    );
 }
 
 /// Test OpenSea-style order
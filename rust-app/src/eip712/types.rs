@@ -68,6 +68,44 @@ impl Eip712Domain {
             bytes
         })
     }
+    
+    /// The `EIP712Domain` type for the fields present, in canonical order
+    pub fn fields(&self) -> Vec<TypedDataField> {
+        let mut fields = Vec::new();
+        
+        if self.name.is_some() {
+            fields.push(TypedDataField {
+                name: "name".to_string(),
+                type_name: "string".to_string(),
+            });
+        }
+        if self.version.is_some() {
+            fields.push(TypedDataField {
+                name: "version".to_string(),
+                type_name: "string".to_string(),
+            });
+        }
+        if self.chain_id.is_some() {
+            fields.push(TypedDataField {
+                name: "chainId".to_string(),
+                type_name: "uint256".to_string(),
+            });
+        }
+        if self.verifying_contract.is_some() {
+            fields.push(TypedDataField {
+                name: "verifyingContract".to_string(),
+                type_name: "address".to_string(),
+            });
+        }
+        if self.salt.is_some() {
+            fields.push(TypedDataField {
+                name: "salt".to_string(),
+                type_name: "bytes32".to_string(),
+            });
+        }
+        
+        fields
+    }
 }
 
 /// Complete EIP-712 typed data structure
@@ -100,40 +138,7 @@ impl TypedData {
     
     /// Get the domain type fields based on which fields are present
     pub fn get_domain_type(&self) -> Vec<TypedDataField> {
-        let mut fields = Vec::new();
-        
-        if self.domain.name.is_some() {
-            fields.push(TypedDataField {
-                name: "name".to_string(),
-                type_name: "string".to_string(),
-            });
-        }
-        if self.domain.version.is_some() {
-            fields.push(TypedDataField {
-                name: "version".to_string(),
-                type_name: "string".to_string(),
-            });
-        }
-        if self.domain.chain_id.is_some() {
-            fields.push(TypedDataField {
-                name: "chainId".to_string(),
-                type_name: "uint256".to_string(),
-            });
-        }
-        if self.domain.verifying_contract.is_some() {
-            fields.push(TypedDataField {
-                name: "verifyingContract".to_string(),
-                type_name: "address".to_string(),
-            });
-        }
-        if self.domain.salt.is_some() {
-            fields.push(TypedDataField {
-                name: "salt".to_string(),
-                type_name: "bytes32".to_string(),
-            });
-        }
-        
-        fields
+        self.domain.fields()
     }
     
     /// Validate the typed data structure
@@ -271,15 +276,16 @@ pub fn is_atomic_type(type_name: &str) -> bool {
         return true;
     }
     
-    // uintN and intN
-    if (type_name.starts_with("uint") || type_name.starts_with("int")) 
-        && type_name.len() > 3 
-    {
+    // uintN and intN (bare uint/int are 256 bits)
+    if type_name.starts_with("uint") || type_name.starts_with("int") {
         let bits: &str = if type_name.starts_with("uint") {
             &type_name[4..]
         } else {
             &type_name[3..]
         };
+        if bits.is_empty() {
+            return true;
+        }
         if let Ok(n) = bits.parse::<u32>() {
             return n > 0 && n <= 256 && n % 8 == 0;
         }
@@ -317,7 +323,8 @@ mod type_tests {
         
         assert!(!is_atomic_type("string"));
         assert!(!is_atomic_type("bytes"));
-        assert!(!is_atomic_type("uint"));
+        assert!(is_atomic_type("uint"));
+        assert!(is_atomic_type("int"));
         assert!(!is_atomic_type("uint257"));
         assert!(!is_atomic_type("bytes33"));
     }
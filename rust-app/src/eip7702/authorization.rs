@@ -140,6 +140,23 @@ pub fn sign_authorization(
     Ok(Authorization::with_signature(chain_id, address, nonce, y_parity, r, s))
 }
 
+/// Sign a revocation authorization
+/// 
+/// Delegating to the zero address clears the account's delegation and
+/// restores it to a plain EOA.
+pub fn build_revocation(
+    chain_id: u64,
+    nonce: u64,
+    private_key: &[u8],
+) -> Eip7702Result<Authorization> {
+    sign_authorization(chain_id, [0u8; 20], nonce, private_key)
+}
+
+/// Whether an authorization revokes delegation (delegates to the zero address)
+pub fn is_revocation(auth: &Authorization) -> bool {
+    auth.address == [0u8; 20]
+}
+
 /// Recover the signer address from a signed authorization
 /// 
 /// # Arguments
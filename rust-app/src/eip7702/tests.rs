@@ -98,6 +98,26 @@ mod tests {
         assert_ne!(hash1, hash2);
     }
     
+    #[test]
+    fn test_build_revocation() {
+        let revocation = authorization::build_revocation(1, 7, &test_key_1()).unwrap();
+        
+        assert_eq!(revocation.address, [0u8; 20]);
+        assert_eq!(revocation.nonce, 7);
+        assert!(authorization::is_revocation(&revocation));
+        
+        // Private key 1 -> 0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf
+        let expected = hex::decode("7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap();
+        let recovered = authorization::recover_authorization_signer(&revocation).unwrap();
+        assert_eq!(recovered.to_vec(), expected);
+    }
+    
+    #[test]
+    fn test_is_revocation_rejects_delegation() {
+        let signed = authorization::sign_authorization(1, test_address(), 0, &test_key_1()).unwrap();
+        assert!(!authorization::is_revocation(&signed));
+    }
+    
     // === Transaction Tests ===
     
     #[test]
@@ -185,6 +205,75 @@ mod tests {
         assert_eq!(hash1, hash2);
     }
     
+    #[test]
+    fn test_add_authorizations_from_distinct_signers() {
+        let key_1 = test_key_1();
+        let key_2 = test_key_2();
+        let auths = transaction::collect_authorizations(
+            1,
+            test_address(),
+            &[(&key_1[..], 0), (&key_2[..], 0)],
+        )
+        .unwrap();
+        
+        let mut tx = Eip7702Transaction::new(1);
+        let added = transaction::add_authorizations(&mut tx, auths).unwrap();
+        assert_eq!(added, 2);
+        assert_eq!(tx.authorization_list.len(), 2);
+        
+        // Private keys 1 and 2
+        let expected_1 = hex::decode("7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap();
+        let expected_2 = hex::decode("2b5ad5c4795c026514f8317c7a215e218dccd6cf").unwrap();
+        let signer_1 = authorization::recover_authorization_signer(&tx.authorization_list[0]).unwrap();
+        let signer_2 = authorization::recover_authorization_signer(&tx.authorization_list[1]).unwrap();
+        assert_eq!(signer_1.to_vec(), expected_1);
+        assert_eq!(signer_2.to_vec(), expected_2);
+        
+        let signed = signer::sign_eip7702_transaction(&tx, &key_1).unwrap();
+        assert_eq!(signer::serialize_for_broadcast(&signed)[0], 0x04);
+    }
+    
+    #[test]
+    fn test_add_authorizations_deduplicates() {
+        let auth = authorization::sign_authorization(1, test_address(), 0, &test_key_1()).unwrap();
+        let mut tx = Eip7702Transaction::new(1);
+        
+        let added = transaction::add_authorizations(&mut tx, vec![auth.clone(), auth.clone()]).unwrap();
+        assert_eq!(added, 1);
+        
+        let added = transaction::add_authorizations(&mut tx, vec![auth]).unwrap();
+        assert_eq!(added, 0);
+        assert_eq!(tx.authorization_list.len(), 1);
+    }
+    
+    #[test]
+    fn test_add_authorizations_rejects_malformed() {
+        let mut tx = Eip7702Transaction::new(1);
+        
+        let unsigned = Authorization::new(1, test_address(), 0);
+        assert!(transaction::add_authorizations(&mut tx, vec![unsigned]).is_err());
+        
+        let wrong_chain = authorization::sign_authorization(5, test_address(), 0, &test_key_1()).unwrap();
+        assert!(transaction::add_authorizations(&mut tx, vec![wrong_chain]).is_err());
+        
+        let mut bad_parity = authorization::sign_authorization(1, test_address(), 0, &test_key_1()).unwrap();
+        bad_parity.y_parity = 2;
+        assert!(transaction::add_authorizations(&mut tx, vec![bad_parity]).is_err());
+        
+        let mut high_s = authorization::sign_authorization(1, test_address(), 0, &test_key_1()).unwrap();
+        high_s.s = [0xff; 32];
+        assert!(transaction::add_authorizations(&mut tx, vec![high_s]).is_err());
+        
+        assert!(tx.authorization_list.is_empty());
+    }
+    
+    #[test]
+    fn test_collect_authorizations_rejects_duplicate_signer() {
+        let key = test_key_1();
+        let result = transaction::collect_authorizations(1, test_address(), &[(&key[..], 0), (&key[..], 1)]);
+        assert!(result.is_err());
+    }
+    
     // === Builder Tests ===
     
     #[test]
@@ -389,4 +478,58 @@ mod tests {
         // Should still serialize correctly
         assert_eq!(serialized[0], 0x04);
     }
+    
+    // === Batch Execute Tests ===
+    
+    #[test]
+    fn test_batch_execute_roundtrip() {
+        let token: [u8; 20] = hex::decode("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap().try_into().unwrap();
+        let router: [u8; 20] = hex::decode("3fc91a3afd70395cd496c647d5a6cc9d4b2b7fad").unwrap().try_into().unwrap();
+        
+        // approve(router, 1_000_000)
+        let mut approve = hex::decode("095ea7b3").unwrap();
+        approve.extend_from_slice(&[0u8; 12]);
+        approve.extend_from_slice(&router);
+        approve.extend_from_slice(&[0u8; 29]);
+        approve.extend_from_slice(&[0x0f, 0x42, 0x40]);
+        
+        let mut swap = hex::decode("3593564c").unwrap();
+        swap.extend_from_slice(&[0x11; 32]);
+        
+        let calls = vec![
+            (token, 0u128, approve),
+            (router, 100_000_000_000_000_000u128, swap),
+        ];
+        let calldata = transaction::build_batch_execute(&calls).unwrap();
+        
+        assert_eq!(hex::encode(&calldata[..4]), "3f707e6b");
+        // selector + array offset + length + 2 offsets + (4 + 3) words + (4 + 2) words
+        assert_eq!(calldata.len(), 4 + 32 * 17);
+        assert_eq!(calldata[4 + 31], 0x20);
+        assert_eq!(calldata[4 + 63], 2);
+        assert_eq!(calldata[4 + 95], 0x40);
+        assert_eq!(&calldata[4 + 126..4 + 128], &[0x01, 0x20]);
+        
+        let decoded = transaction::decode_batch_execute(&calldata).unwrap();
+        assert_eq!(decoded, calls);
+        
+        // Goes straight into a 7702 transaction
+        let signed = signer::Eip7702TransactionBuilder::new(1)
+            .data(calldata.clone())
+            .sign(&test_key_1())
+            .unwrap();
+        assert_eq!(signed.tx.data, calldata);
+    }
+    
+    #[test]
+    fn test_batch_execute_rejects_malformed() {
+        assert!(transaction::decode_batch_execute(&[]).is_err());
+        assert!(transaction::decode_batch_execute(&hex::decode("a9059cbb").unwrap()).is_err());
+        
+        let calldata = transaction::build_batch_execute(&[(test_address(), 1, vec![0xAB; 40])]).unwrap();
+        assert!(transaction::decode_batch_execute(&calldata[..calldata.len() - 32]).is_err());
+        
+        let empty = transaction::build_batch_execute(&[]).unwrap();
+        assert!(transaction::decode_batch_execute(&empty).unwrap().is_empty());
+    }
 }
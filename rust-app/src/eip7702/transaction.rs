@@ -2,8 +2,15 @@
 //!
 //! Implements RLP encoding for EIP-7702 transactions.
 
-use super::types::{Eip7702Transaction, SignedEip7702Transaction, AccessListEntry, Eip7702Error, Eip7702Result, EIP7702_TX_TYPE};
-use super::authorization::{rlp_encode_authorization, rlp_encode_u64, rlp_encode_u128, rlp_encode_bytes, keccak256};
+use super::types::{Authorization, Eip7702Transaction, SignedEip7702Transaction, AccessListEntry, Eip7702Error, Eip7702Result, EIP7702_TX_TYPE};
+use super::authorization::{rlp_encode_authorization, rlp_encode_u64, rlp_encode_u128, rlp_encode_bytes, keccak256, recover_authorization_signer, sign_authorization};
+use crate::abi::{AbiDecoder, AbiEncoder, AbiSelector, AbiType, AbiValue, U256};
+
+/// secp256k1 curve order / 2; signatures with a larger `s` are rejected (EIP-2)
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
 
 /// RLP encode an access list entry
 fn rlp_encode_access_list_entry(entry: &AccessListEntry) -> Vec<u8> {
@@ -223,6 +230,93 @@ pub fn transaction_hash(signed: &SignedEip7702Transaction) -> [u8; 32] {
     keccak256(&encoded)
 }
 
+/// Add signed authorizations to a transaction's authorization list
+/// 
+/// Each entry must be signed with `y_parity` 0 or 1, a low `s`, a chain ID of 0
+/// (any chain) or the transaction's chain, and a signature that recovers to an
+/// authority. Entries repeating a (chainId, address, nonce) tuple already
+/// present for the same authority are skipped.
+/// 
+/// Returns the number of authorizations added. Nothing is added if any entry is invalid.
+pub fn add_authorizations(
+    tx: &mut Eip7702Transaction,
+    auths: Vec<Authorization>,
+) -> Eip7702Result<usize> {
+    let mut seen = Vec::with_capacity(tx.authorization_list.len() + auths.len());
+    for existing in &tx.authorization_list {
+        seen.push(authorization_key(existing)?);
+    }
+    
+    let mut accepted = Vec::new();
+    for (i, auth) in auths.into_iter().enumerate() {
+        validate_authorization(tx.chain_id, &auth)
+            .map_err(|e| Eip7702Error::InvalidAuthorization(format!("entry {}: {}", i, e)))?;
+        
+        let key = authorization_key(&auth)?;
+        if !seen.contains(&key) {
+            seen.push(key);
+            accepted.push(auth);
+        }
+    }
+    
+    let added = accepted.len();
+    tx.authorization_list.extend(accepted);
+    Ok(added)
+}
+
+/// Sign the same delegation with several keys, one authorization per authority
+/// 
+/// `signers` pairs each private key with that account's current nonce.
+/// Keys that resolve to the same authority are rejected.
+pub fn collect_authorizations(
+    chain_id: u64,
+    address: [u8; 20],
+    signers: &[(&[u8], u64)],
+) -> Eip7702Result<Vec<Authorization>> {
+    let mut authorities: Vec<[u8; 20]> = Vec::with_capacity(signers.len());
+    let mut auths = Vec::with_capacity(signers.len());
+    
+    for (private_key, nonce) in signers {
+        let auth = sign_authorization(chain_id, address, *nonce, private_key)?;
+        let authority = recover_authorization_signer(&auth)?;
+        
+        if authorities.contains(&authority) {
+            return Err(Eip7702Error::InvalidAuthorization(format!(
+                "Duplicate signer 0x{}",
+                hex::encode(authority)
+            )));
+        }
+        authorities.push(authority);
+        auths.push(auth);
+    }
+    
+    Ok(auths)
+}
+
+fn validate_authorization(chain_id: u64, auth: &Authorization) -> Eip7702Result<()> {
+    if !auth.is_signed() {
+        return Err(Eip7702Error::UnsignedAuthorization);
+    }
+    if auth.chain_id != 0 && auth.chain_id != chain_id {
+        return Err(Eip7702Error::ChainIdMismatch);
+    }
+    if auth.y_parity > 1 {
+        return Err(Eip7702Error::InvalidSignature(format!("y_parity must be 0 or 1, got {}", auth.y_parity)));
+    }
+    if auth.r == [0u8; 32] || auth.s == [0u8; 32] {
+        return Err(Eip7702Error::InvalidSignature("r and s must be non-zero".to_string()));
+    }
+    if auth.s > SECP256K1_HALF_ORDER {
+        return Err(Eip7702Error::InvalidSignature("s is not in the lower half of the curve order".to_string()));
+    }
+    Ok(())
+}
+
+fn authorization_key(auth: &Authorization) -> Eip7702Result<(u64, [u8; 20], u64, [u8; 20])> {
+    let authority = recover_authorization_signer(auth)?;
+    Ok((auth.chain_id, auth.address, auth.nonce, authority))
+}
+
 /// Build a transaction from JSON parameters
 pub fn build_transaction_from_json(json: &serde_json::Value) -> Eip7702Result<Eip7702Transaction> {
     let chain_id = json.get("chainId")
@@ -265,6 +359,76 @@ pub fn build_transaction_from_json(json: &serde_json::Value) -> Eip7702Result<Ei
     Ok(tx)
 }
 
+/// Solidity signature of the batch executor entry point
+pub const BATCH_EXECUTE_SIGNATURE: &str = "execute((address,uint256,bytes)[])";
+
+/// A single call in a batch: `(to, value, data)`
+pub type BatchCall = ([u8; 20], u128, Vec<u8>);
+
+/// 4-byte selector of `execute((address,uint256,bytes)[])`
+pub fn batch_execute_selector() -> [u8; 4] {
+    AbiSelector::selector_from_signature(BATCH_EXECUTE_SIGNATURE)
+}
+
+/// `(address,uint256,bytes)`
+fn batch_call_type() -> AbiType {
+    AbiType::Tuple(vec![AbiType::Address, AbiType::Uint256, AbiType::Bytes])
+}
+
+/// ABI-encode a batch of calls for a delegated batch executor
+/// 
+/// The result goes in the `data` field of an EIP-7702 transaction sent to the
+/// delegating EOA itself, so the calls execute with the EOA as `msg.sender`.
+pub fn build_batch_execute(calls: &[BatchCall]) -> Eip7702Result<Vec<u8>> {
+    let tuples = calls
+        .iter()
+        .map(|(to, value, data)| AbiValue::Tuple(vec![
+            AbiValue::Address(*to),
+            AbiValue::Uint(U256::from_u128(*value)),
+            AbiValue::Bytes(data.clone()),
+        ]))
+        .collect();
+
+    let params = AbiEncoder::encode(&[AbiValue::Array(tuples)], &[AbiType::Array(Box::new(batch_call_type()))])
+        .map_err(|e| Eip7702Error::InvalidCalldata(e.to_string()))?;
+
+    let mut out = Vec::with_capacity(4 + params.len());
+    out.extend_from_slice(&batch_execute_selector());
+    out.extend_from_slice(&params);
+    Ok(out)
+}
+
+/// Decode `execute((address,uint256,bytes)[])` calldata back into its calls
+pub fn decode_batch_execute(calldata: &[u8]) -> Eip7702Result<Vec<BatchCall>> {
+    let selector = calldata.get(..4)
+        .ok_or_else(|| Eip7702Error::InvalidCalldata("Calldata too short".to_string()))?;
+    if selector != batch_execute_selector() {
+        return Err(Eip7702Error::InvalidCalldata("Not a batch execute call".to_string()));
+    }
+
+    let values = AbiDecoder::decode(&calldata[4..], &[AbiType::Array(Box::new(batch_call_type()))])
+        .map_err(|e| Eip7702Error::InvalidCalldata(e.to_string()))?;
+    let Some(AbiValue::Array(tuples)) = values.into_iter().next() else {
+        return Err(Eip7702Error::InvalidCalldata("Expected (address,uint256,bytes)[]".to_string()));
+    };
+
+    tuples
+        .into_iter()
+        .map(|tuple| match tuple {
+            AbiValue::Tuple(fields) => match fields.as_slice() {
+                [AbiValue::Address(to), AbiValue::Uint(value), AbiValue::Bytes(data)] => {
+                    if value.0[2] != 0 || value.0[3] != 0 {
+                        return Err(Eip7702Error::InvalidCalldata("Call value exceeds u128".to_string()));
+                    }
+                    Ok((*to, value.as_u128(), data.clone()))
+                }
+                _ => Err(Eip7702Error::InvalidCalldata("Expected (address,uint256,bytes)".to_string())),
+            },
+            _ => Err(Eip7702Error::InvalidCalldata("Expected (address,uint256,bytes)".to_string())),
+        })
+        .collect()
+}
+
 fn parse_u128(v: &serde_json::Value) -> Option<u128> {
     if let Some(n) = v.as_u64() {
         Some(n as u128)
@@ -262,11 +262,17 @@ pub enum Eip7702Error {
     #[error("Chain ID mismatch")]
     ChainIdMismatch,
     
+    #[error("Invalid authorization: {0}")]
+    InvalidAuthorization(String),
+    
     #[error("Signing error: {0}")]
     SigningError(String),
     
     #[error("Signature error: {0}")]
     SignatureError(String),
+    
+    #[error("Invalid calldata: {0}")]
+    InvalidCalldata(String),
 }
 
 pub type Eip7702Result<T> = Result<T, Eip7702Error>;
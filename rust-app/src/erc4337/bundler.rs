@@ -3,6 +3,7 @@
 //! Handles communication with bundler services (Pimlico, Alchemy, Stackup).
 
 use crate::error::{HawalaError, HawalaResult, ErrorCode};
+use crate::utils::network_config::{evm_service_url, ServiceKind};
 use super::{UserOperation, UserOperationReceipt, ERC4337Chain};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -44,9 +45,12 @@ struct RpcError {
 
 impl BundlerClient {
     /// Create a new bundler client
+    ///
+    /// Uses the configured bundler override for the chain, falling back to
+    /// the chain's default bundler.
     pub fn new(chain: ERC4337Chain) -> Self {
         Self {
-            url: chain.default_bundler_url().to_string(),
+            url: evm_service_url(chain.chain_id(), ServiceKind::Bundler, chain.default_bundler_url()),
             api_key: None,
             chain,
         }
@@ -315,6 +319,22 @@ mod tests {
         assert!(client.url.contains("pimlico"));
     }
 
+    #[test]
+    fn test_bundler_url_override() {
+        use crate::types::Chain;
+        use crate::utils::network_config::get_network_config;
+
+        let config = get_network_config();
+        config
+            .set_url_override(Chain::Polygon, ServiceKind::Bundler, Some("https://bundler.example.org/rpc"))
+            .unwrap();
+        let client = BundlerClient::new(ERC4337Chain::Polygon);
+        config.set_url_override(Chain::Polygon, ServiceKind::Bundler, None).unwrap();
+
+        assert_eq!(client.url, "https://bundler.example.org/rpc");
+        assert!(BundlerClient::new(ERC4337Chain::Polygon).url.contains("pimlico"));
+    }
+
     #[test]
     fn test_gas_estimate_parsing() {
         let estimate = GasEstimate {
@@ -6,18 +6,21 @@
 //! - Bundler interaction
 //! - Paymaster integration for gasless transactions
 //! - Gas account management
+//! - Safe transaction hashing for multisig co-signing
 
 pub mod user_operation;
 pub mod account;
 pub mod bundler;
 pub mod paymaster;
 pub mod gas_account;
+pub mod safe;
 
 pub use user_operation::*;
 pub use account::*;
 pub use bundler::*;
 pub use paymaster::*;
 pub use gas_account::*;
+pub use safe::*;
 
 use crate::error::{HawalaError, ErrorCode};
 use serde::{Deserialize, Serialize};
@@ -3,7 +3,8 @@
 //! Enables gasless transactions by sponsoring UserOperations.
 
 use crate::error::{HawalaError, HawalaResult, ErrorCode};
-use super::{UserOperation, ERC4337Chain};
+use crate::utils::network_config::{get_network_config, ServiceKind};
+use super::{UserOperation, UserOpVersion, ERC4337Chain};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -86,16 +87,15 @@ impl PaymasterManager {
             .map_err(|e| HawalaError::new(ErrorCode::ParseError, format!("Invalid response: {}", e)))?;
         
         match result.result {
-            Some(data) => Ok(SponsorshipResult {
-                is_sponsored: true,
-                paymaster: Some(data.paymaster),
-                paymaster_data: Some(PaymasterData {
-                    paymaster_verification_gas_limit: data.paymaster_verification_gas_limit,
-                    paymaster_post_op_gas_limit: data.paymaster_post_op_gas_limit,
-                    paymaster_data: data.paymaster_data,
-                }),
-                reason: None,
-            }),
+            Some(data) => {
+                let (paymaster, paymaster_data) = data.paymaster_fields()?;
+                Ok(SponsorshipResult {
+                    is_sponsored: true,
+                    paymaster: Some(paymaster),
+                    paymaster_data: Some(paymaster_data),
+                    reason: None,
+                })
+            }
             None => Ok(SponsorshipResult {
                 is_sponsored: false,
                 paymaster: None,
@@ -106,31 +106,62 @@ impl PaymasterManager {
     }
     
     /// Sponsor a UserOperation (returns modified UserOp with paymaster data)
+    ///
+    /// Calls `pm_sponsorUserOperation` against `entry_point` under the given
+    /// sponsorship policy. The paymaster fields and the gas limits it signed
+    /// over are written back to the UserOp, which must then be re-signed.
     pub fn sponsor_user_operation(
         &self,
-        mut user_op: UserOperation,
+        user_op: UserOperation,
         chain: ERC4337Chain,
+        entry_point: &str,
+        policy_id: Option<&str>,
     ) -> HawalaResult<UserOperation> {
-        let result = self.check_sponsorship(&user_op, chain)?;
+        let url = self.get_url(chain)?;
         
-        if !result.is_sponsored {
-            return Err(HawalaError::new(
-                ErrorCode::InvalidInput,
-                result.reason.unwrap_or_else(|| "Sponsorship denied".to_string()),
-            ));
-        }
+        let request = SponsorshipRequest {
+            jsonrpc: "2.0",
+            method: "pm_sponsorUserOperation",
+            params: SponsorParams {
+                user_operation: user_op.clone(),
+                entry_point: entry_point.to_string(),
+                context: SponsorContext {
+                    policy_id: policy_id.map(str::to_string),
+                },
+            },
+            id: 1,
+        };
         
-        if let Some(paymaster) = result.paymaster {
-            user_op.paymaster = Some(paymaster);
-        }
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .map_err(|e| HawalaError::new(ErrorCode::NetworkError, format!("Client error: {}", e)))?;
         
-        if let Some(data) = result.paymaster_data {
-            user_op.paymaster_verification_gas_limit = Some(data.paymaster_verification_gas_limit);
-            user_op.paymaster_post_op_gas_limit = Some(data.paymaster_post_op_gas_limit);
-            user_op.paymaster_data = Some(data.paymaster_data);
-        }
+        let response = client
+            .post(&url)
+            .json(&request)
+            .send()
+            .map_err(|e| HawalaError::new(ErrorCode::NetworkError, format!("Request failed: {}", e)))?;
         
-        Ok(user_op)
+        // Policy rejections may come back with a 4xx status and a JSON-RPC error body
+        let status = response.status();
+        let body = response.text()
+            .map_err(|e| HawalaError::new(ErrorCode::NetworkError, format!("Request failed: {}", e)))?;
+        
+        let result: SponsorshipResponse = match serde_json::from_str(&body) {
+            Ok(result) => result,
+            Err(_) if !status.is_success() => {
+                return Err(HawalaError::new(
+                    ErrorCode::NetworkError,
+                    format!("Paymaster returned {}", status),
+                ));
+            }
+            Err(e) => {
+                return Err(HawalaError::new(ErrorCode::ParseError, format!("Invalid response: {}", e)));
+            }
+        };
+        
+        apply_sponsorship(user_op, result, UserOpVersion::for_entry_point(entry_point))
     }
     
     /// Get ERC-20 token paymaster quote
@@ -206,7 +237,14 @@ impl PaymasterManager {
     }
     
     /// Get paymaster URL for chain
+    ///
+    /// A paymaster URL set in the network config replaces the provider's
+    /// endpoint, and needs no API key.
     fn get_url(&self, chain: ERC4337Chain) -> HawalaResult<String> {
+        if let Some(url) = get_network_config().evm_url_override(chain.chain_id(), ServiceKind::Paymaster) {
+            return Ok(url);
+        }
+        
         let api_key = self.api_key.as_ref()
             .ok_or_else(|| HawalaError::new(ErrorCode::InvalidInput, "API key required"))?;
         
@@ -226,7 +264,7 @@ impl PaymasterManager {
             PaymasterProvider::Custom => {
                 return Err(HawalaError::new(
                     ErrorCode::InvalidInput,
-                    "Custom paymaster requires explicit URL",
+                    "Custom paymaster requires a paymaster URL in the network config",
                 ));
             }
         })
@@ -285,7 +323,7 @@ struct SponsorParams {
 
 #[derive(Serialize, Default)]
 struct SponsorContext {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "sponsorshipPolicyId", skip_serializing_if = "Option::is_none")]
     policy_id: Option<String>,
 }
 
@@ -295,22 +333,152 @@ struct SponsorshipResponse {
     error: Option<RpcError>,
 }
 
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
+/// Sponsorship result; paymasters return either the split v0.7 fields or
+/// the packed `paymasterAndData`, plus the gas limits they signed over
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
 struct SponsorshipData {
-    paymaster: String,
-    paymaster_verification_gas_limit: String,
-    paymaster_post_op_gas_limit: String,
-    paymaster_data: String,
+    paymaster: Option<String>,
+    paymaster_verification_gas_limit: Option<String>,
+    paymaster_post_op_gas_limit: Option<String>,
+    paymaster_data: Option<String>,
+    paymaster_and_data: Option<String>,
+    call_gas_limit: Option<String>,
+    verification_gas_limit: Option<String>,
+    pre_verification_gas: Option<String>,
+}
+
+impl SponsorshipData {
+    /// Paymaster address and data
+    fn paymaster_fields(&self) -> HawalaResult<(String, PaymasterData)> {
+        if let Some(packed) = &self.paymaster_and_data {
+            return unpack_paymaster_and_data(packed);
+        }
+        
+        match (
+            &self.paymaster,
+            &self.paymaster_verification_gas_limit,
+            &self.paymaster_post_op_gas_limit,
+        ) {
+            (Some(paymaster), Some(verification_gas), Some(post_op_gas)) => Ok((
+                paymaster.clone(),
+                PaymasterData {
+                    paymaster_verification_gas_limit: verification_gas.clone(),
+                    paymaster_post_op_gas_limit: post_op_gas.clone(),
+                    paymaster_data: self.paymaster_data.clone().unwrap_or_else(|| "0x".to_string()),
+                },
+            )),
+            _ => Err(HawalaError::new(
+                ErrorCode::ParseError,
+                "Sponsorship response missing paymaster fields",
+            )),
+        }
+    }
 }
 
 #[derive(Deserialize)]
 struct RpcError {
-    #[allow(dead_code)]
     code: i64,
     message: String,
 }
 
+/// Write a sponsorship response into the UserOp
+///
+/// v0.6 responses carry `paymasterAndData` as paymaster || data, which is
+/// kept byte-for-byte; v0.7 responses are split into the gas limit fields.
+fn apply_sponsorship(
+    mut user_op: UserOperation,
+    response: SponsorshipResponse,
+    version: UserOpVersion,
+) -> HawalaResult<UserOperation> {
+    if let Some(error) = response.error {
+        return Err(HawalaError::new(
+            ErrorCode::InvalidInput,
+            format!("Sponsorship rejected: {}", error.message),
+        ).with_details(format!("RPC error {}", error.code)));
+    }
+    
+    let data = response.result
+        .ok_or_else(|| HawalaError::new(ErrorCode::ParseError, "Empty sponsorship response"))?;
+    
+    match version {
+        UserOpVersion::V06 => {
+            let packed = data.paymaster_and_data.as_deref().ok_or_else(|| {
+                HawalaError::new(ErrorCode::ParseError, "Sponsorship response missing paymasterAndData")
+            })?;
+            let (paymaster, paymaster_data) = split_v06_paymaster_and_data(packed)?;
+            user_op.paymaster = Some(paymaster);
+            user_op.paymaster_verification_gas_limit = None;
+            user_op.paymaster_post_op_gas_limit = None;
+            user_op.paymaster_data = Some(paymaster_data);
+        }
+        UserOpVersion::V07 => {
+            let (paymaster, paymaster_data) = data.paymaster_fields()?;
+            user_op.paymaster = Some(paymaster);
+            user_op.paymaster_verification_gas_limit = Some(paymaster_data.paymaster_verification_gas_limit);
+            user_op.paymaster_post_op_gas_limit = Some(paymaster_data.paymaster_post_op_gas_limit);
+            user_op.paymaster_data = Some(paymaster_data.paymaster_data);
+        }
+    }
+    
+    if let Some(gas) = data.call_gas_limit {
+        user_op.call_gas_limit = gas;
+    }
+    if let Some(gas) = data.verification_gas_limit {
+        user_op.verification_gas_limit = gas;
+    }
+    if let Some(gas) = data.pre_verification_gas {
+        user_op.pre_verification_gas = gas;
+    }
+    
+    Ok(user_op)
+}
+
+/// Split v0.6 `paymasterAndData`: paymaster (20) || data
+fn split_v06_paymaster_and_data(packed: &str) -> HawalaResult<(String, String)> {
+    let bytes = hex::decode(packed.strip_prefix("0x").unwrap_or(packed))
+        .map_err(|e| HawalaError::new(ErrorCode::ParseError, format!("Invalid paymasterAndData: {}", e)))?;
+    if bytes.len() < 20 {
+        return Err(HawalaError::new(
+            ErrorCode::ParseError,
+            format!("paymasterAndData too short: {} bytes", bytes.len()),
+        ));
+    }
+    
+    Ok((
+        format!("0x{}", hex::encode(&bytes[..20])),
+        format!("0x{}", hex::encode(&bytes[20..])),
+    ))
+}
+
+/// Split v0.7 `paymasterAndData`:
+/// paymaster (20) || verificationGasLimit (16) || postOpGasLimit (16) || data
+fn unpack_paymaster_and_data(packed: &str) -> HawalaResult<(String, PaymasterData)> {
+    let bytes = hex::decode(packed.strip_prefix("0x").unwrap_or(packed))
+        .map_err(|e| HawalaError::new(ErrorCode::ParseError, format!("Invalid paymasterAndData: {}", e)))?;
+    if bytes.len() < 52 {
+        return Err(HawalaError::new(
+            ErrorCode::ParseError,
+            format!("paymasterAndData too short: {} bytes", bytes.len()),
+        ));
+    }
+    
+    let gas = |range: std::ops::Range<usize>| {
+        let mut word = [0u8; 16];
+        word.copy_from_slice(&bytes[range]);
+        format!("0x{:x}", u128::from_be_bytes(word))
+    };
+    
+    Ok((
+        format!("0x{}", hex::encode(&bytes[..20])),
+        PaymasterData {
+            paymaster_verification_gas_limit: gas(20..36),
+            paymaster_post_op_gas_limit: gas(36..52),
+            paymaster_data: format!("0x{}", hex::encode(&bytes[52..])),
+        },
+    ))
+}
+
 /// Sponsorship policy for limiting sponsored transactions
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -337,6 +505,25 @@ mod tests {
         assert!(manager.api_key.is_some());
     }
 
+    #[test]
+    fn test_paymaster_url_override() {
+        use crate::types::Chain;
+
+        let manager = PaymasterManager::new(PaymasterProvider::Custom);
+        assert!(manager.get_url(ERC4337Chain::Arbitrum).is_err());
+
+        let config = get_network_config();
+        config
+            .set_url_override(Chain::Arbitrum, ServiceKind::Paymaster, Some("https://paymaster.example.org/rpc"))
+            .unwrap();
+        let url = manager.get_url(ERC4337Chain::Arbitrum);
+        config.set_url_override(Chain::Arbitrum, ServiceKind::Paymaster, None).unwrap();
+
+        assert_eq!(url.unwrap(), "https://paymaster.example.org/rpc");
+        let pimlico = PaymasterManager::new(PaymasterProvider::Pimlico).with_api_key("test_key");
+        assert!(pimlico.get_url(ERC4337Chain::Arbitrum).unwrap().starts_with("https://api.pimlico.io/v2/42161/"));
+    }
+
     #[test]
     fn test_sponsorship_result() {
         let result = SponsorshipResult {
@@ -348,4 +535,107 @@ mod tests {
         
         assert!(result.is_sponsored);
     }
+
+    #[test]
+    fn test_apply_packed_paymaster_and_data() {
+        let user_op = UserOperation::new("0x1234567890123456789012345678901234567890", 0, "0x");
+        
+        // Canned pm_sponsorUserOperation response
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "paymasterAndData": "0x00000000000000fb866daaa79352cc568a005d9600000000000000000000000000008a8800000000000000000000000000000001deadbeef",
+                "callGasLimit": "0x1d4c0",
+                "verificationGasLimit": "0x2dc6c",
+                "preVerificationGas": "0xb6e4"
+            }
+        }"#;
+        let response: SponsorshipResponse = serde_json::from_str(json).unwrap();
+        
+        let sponsored = apply_sponsorship(user_op, response, UserOpVersion::V07).unwrap();
+        
+        assert_eq!(sponsored.paymaster.as_deref(), Some("0x00000000000000fb866daaa79352cc568a005d96"));
+        assert_eq!(sponsored.paymaster_verification_gas_limit.as_deref(), Some("0x8a88"));
+        assert_eq!(sponsored.paymaster_post_op_gas_limit.as_deref(), Some("0x1"));
+        assert_eq!(sponsored.paymaster_data.as_deref(), Some("0xdeadbeef"));
+        assert_eq!(sponsored.call_gas_limit, "0x1d4c0");
+        assert_eq!(sponsored.verification_gas_limit, "0x2dc6c");
+        assert_eq!(sponsored.pre_verification_gas, "0xb6e4");
+    }
+
+    #[test]
+    fn test_apply_split_paymaster_fields() {
+        let user_op = UserOperation::new("0x1234567890123456789012345678901234567890", 0, "0x");
+        let original_call_gas = user_op.call_gas_limit.clone();
+        
+        let json = r#"{
+            "result": {
+                "paymaster": "0x00000000000000fb866daaa79352cc568a005d96",
+                "paymasterVerificationGasLimit": "0x8a88",
+                "paymasterPostOpGasLimit": "0x1",
+                "paymasterData": "0xdeadbeef"
+            }
+        }"#;
+        let response: SponsorshipResponse = serde_json::from_str(json).unwrap();
+        
+        let sponsored = apply_sponsorship(user_op, response, UserOpVersion::V07).unwrap();
+        
+        assert_eq!(sponsored.paymaster_data.as_deref(), Some("0xdeadbeef"));
+        assert_eq!(sponsored.call_gas_limit, original_call_gas);
+    }
+
+    #[test]
+    fn test_apply_v06_paymaster_and_data() {
+        let user_op = UserOperation::new("0x1234567890123456789012345678901234567890", 0, "0x");
+        
+        // v0.6 paymasterAndData has no gas limits: paymaster || data
+        let packed = "0xe93eca6595fe94091dc1af46aac2a8b5d79907700000000000000000000000000000000000000000000000000000000065f1d2a0deadbeef";
+        let json = format!(r#"{{"result": {{"paymasterAndData": "{}", "preVerificationGas": "0xb6e4"}}}}"#, packed);
+        let response: SponsorshipResponse = serde_json::from_str(&json).unwrap();
+        
+        let sponsored = apply_sponsorship(user_op, response, UserOpVersion::V06).unwrap();
+        
+        assert_eq!(sponsored.paymaster.as_deref(), Some("0xe93eca6595fe94091dc1af46aac2a8b5d7990770"));
+        assert!(sponsored.paymaster_verification_gas_limit.is_none());
+        assert!(sponsored.paymaster_post_op_gas_limit.is_none());
+        assert_eq!(
+            format!("0x{}", hex::encode(sponsored.paymaster_and_data(UserOpVersion::V06).unwrap())),
+            packed,
+        );
+        assert_eq!(sponsored.pre_verification_gas, "0xb6e4");
+        
+        let user_op = UserOperation::new("0x1234567890123456789012345678901234567890", 0, "0x");
+        let short = serde_json::from_str(r#"{"result": {"paymasterAndData": "0xe93eca65"}}"#).unwrap();
+        assert!(apply_sponsorship(user_op, short, UserOpVersion::V06).is_err());
+    }
+
+    #[test]
+    fn test_policy_rejection() {
+        let user_op = UserOperation::new("0x1234567890123456789012345678901234567890", 0, "0x");
+        
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": {"code": -32602, "message": "UserOperation rejected by sponsorship policy"}
+        }"#;
+        let response: SponsorshipResponse = serde_json::from_str(json).unwrap();
+        
+        let err = apply_sponsorship(user_op, response, UserOpVersion::V07).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+        assert!(err.message.contains("sponsorship policy"));
+        assert_eq!(err.details.as_deref(), Some("RPC error -32602"));
+    }
+
+    #[test]
+    fn test_policy_id_context() {
+        let context = SponsorContext { policy_id: Some("sp_test".to_string()) };
+        assert_eq!(serde_json::to_string(&context).unwrap(), r#"{"sponsorshipPolicyId":"sp_test"}"#);
+        assert_eq!(serde_json::to_string(&SponsorContext::default()).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_short_paymaster_and_data() {
+        assert!(unpack_paymaster_and_data("0x00000000000000fb866daaa79352cc568a005d96").is_err());
+    }
 }
@@ -0,0 +1,247 @@
+//! Safe (Gnosis Safe) Transaction Hashing
+//!
+//! Safe owners sign the EIP-712 hash of a `SafeTx` struct. The domain is the
+//! Safe itself: `EIP712Domain(uint256 chainId,address verifyingContract)`
+//! (Safe v1.3.0 and later).
+
+use crate::eip712::{hash_typed_data, Eip712Domain, TypedData, TypedDataField};
+use crate::error::{HawalaError, HawalaResult, ErrorCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Zero address, used for `gasToken` (ETH) and `refundReceiver` (tx.origin)
+pub const SAFE_ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// Safe call type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum SafeOperation {
+    /// Regular CALL
+    #[default]
+    Call,
+    /// DELEGATECALL (runs the target code in the Safe's context)
+    DelegateCall,
+}
+
+impl TryFrom<u8> for SafeOperation {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Call),
+            1 => Ok(Self::DelegateCall),
+            _ => Err(format!("Invalid Safe operation: {}", value)),
+        }
+    }
+}
+
+impl From<SafeOperation> for u8 {
+    fn from(operation: SafeOperation) -> Self {
+        match operation {
+            SafeOperation::Call => 0,
+            SafeOperation::DelegateCall => 1,
+        }
+    }
+}
+
+/// Safe transaction data (same shape as the Safe SDK's `SafeTransactionData`)
+///
+/// Amounts are decimal or 0x-prefixed hex strings so full uint256 values fit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTransaction {
+    /// Target address
+    pub to: String,
+    /// Wei value
+    #[serde(default = "zero")]
+    pub value: String,
+    /// Calldata (hex)
+    #[serde(default = "empty_data")]
+    pub data: String,
+    /// Call or delegate call
+    #[serde(default)]
+    pub operation: SafeOperation,
+    /// Gas for the inner call (0 = all available)
+    #[serde(default = "zero")]
+    pub safe_tx_gas: String,
+    /// Gas independent of the inner call, used for refunds
+    #[serde(default = "zero")]
+    pub base_gas: String,
+    /// Gas price for refunds (0 = no refund)
+    #[serde(default = "zero")]
+    pub gas_price: String,
+    /// Refund token (zero address = ETH)
+    #[serde(default = "zero_address")]
+    pub gas_token: String,
+    /// Refund receiver (zero address = tx.origin)
+    #[serde(default = "zero_address")]
+    pub refund_receiver: String,
+    /// Safe nonce
+    pub nonce: u64,
+}
+
+fn zero() -> String {
+    "0".to_string()
+}
+
+fn empty_data() -> String {
+    "0x".to_string()
+}
+
+fn zero_address() -> String {
+    SAFE_ZERO_ADDRESS.to_string()
+}
+
+impl SafeTransaction {
+    /// Transaction without gas refunds
+    pub fn new(to: &str, value: &str, data: &str, operation: SafeOperation, nonce: u64) -> Self {
+        Self {
+            to: to.to_string(),
+            value: value.to_string(),
+            data: data.to_string(),
+            operation,
+            safe_tx_gas: zero(),
+            base_gas: zero(),
+            gas_price: zero(),
+            gas_token: zero_address(),
+            refund_receiver: zero_address(),
+            nonce,
+        }
+    }
+
+    /// EIP-712 typed data for this transaction on `safe_address`
+    ///
+    /// This is what wallets receive for `eth_signTypedData_v4`.
+    pub fn typed_data(&self, safe_address: &str, chain_id: u64) -> TypedData {
+        let field = |name: &str, type_name: &str| TypedDataField {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+        };
+
+        let mut types = HashMap::new();
+        types.insert("SafeTx".to_string(), vec![
+            field("to", "address"),
+            field("value", "uint256"),
+            field("data", "bytes"),
+            field("operation", "uint8"),
+            field("safeTxGas", "uint256"),
+            field("baseGas", "uint256"),
+            field("gasPrice", "uint256"),
+            field("gasToken", "address"),
+            field("refundReceiver", "address"),
+            field("nonce", "uint256"),
+        ]);
+        types.insert("EIP712Domain".to_string(), vec![
+            field("chainId", "uint256"),
+            field("verifyingContract", "address"),
+        ]);
+
+        TypedData {
+            types,
+            primary_type: "SafeTx".to_string(),
+            domain: Eip712Domain {
+                chain_id: Some(serde_json::json!(chain_id)),
+                verifying_contract: Some(safe_address.to_string()),
+                ..Default::default()
+            },
+            message: serde_json::json!({
+                "to": self.to,
+                "value": self.value,
+                "data": self.data,
+                "operation": u8::from(self.operation),
+                "safeTxGas": self.safe_tx_gas,
+                "baseGas": self.base_gas,
+                "gasPrice": self.gas_price,
+                "gasToken": self.gas_token,
+                "refundReceiver": self.refund_receiver,
+                "nonce": self.nonce,
+            }),
+        }
+    }
+}
+
+/// Compute the `safeTxHash` that Safe owners sign
+pub fn compute_safe_tx_hash(safe_address: &str, chain_id: u64, tx: &SafeTransaction) -> HawalaResult<[u8; 32]> {
+    hash_typed_data(&tx.typed_data(safe_address, chain_id)).map_err(|e| {
+        HawalaError::new(ErrorCode::InvalidInput, format!("Invalid Safe transaction: {}", e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAFE: &str = "0x5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe";
+
+    #[test]
+    fn test_safe_tx_type_hash() {
+        let typed_data = SafeTransaction::new(SAFE, "0", "0x", SafeOperation::Call, 0).typed_data(SAFE, 1);
+        let encoded = crate::eip712::encode_type("SafeTx", &typed_data.types).unwrap();
+
+        // SAFE_TX_TYPEHASH from the Safe contracts
+        assert_eq!(
+            hex::encode(crate::eip712::keccak256(encoded.as_bytes())),
+            "bb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8"
+        );
+    }
+
+    #[test]
+    fn test_domain_type_hash() {
+        let typed_data = SafeTransaction::new(SAFE, "0", "0x", SafeOperation::Call, 0).typed_data(SAFE, 1);
+        let encoded = crate::eip712::encode_type("EIP712Domain", &typed_data.types).unwrap();
+
+        // DOMAIN_SEPARATOR_TYPEHASH from the Safe contracts (v1.3.0+)
+        assert_eq!(
+            hex::encode(crate::eip712::keccak256(encoded.as_bytes())),
+            "47e79534a245952e8b16893a336b85a3d9ea9fa8c573f3d803afb92a79469218"
+        );
+    }
+
+    #[test]
+    fn test_eth_transfer_hash() {
+        let tx = SafeTransaction::new(
+            "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+            "1000000000000000000",
+            "0x",
+            SafeOperation::Call,
+            0,
+        );
+
+        let hash = compute_safe_tx_hash(SAFE, 1, &tx).unwrap();
+        assert_eq!(hex::encode(hash), "2a2ac284d582810ad3a244eb672d62184147099e85fb507e7f054a6f5c453827");
+    }
+
+    #[test]
+    fn test_token_transfer_from_sdk_json() {
+        // USDC transfer of 100 tokens, as serialized by the Safe SDK
+        let json = r#"{
+            "to": "0x1c7D4B196Cb0C7B01d743Fbc6116a902379C7238",
+            "value": "0",
+            "data": "0xa9059cbb000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa960450000000000000000000000000000000000000000000000000000000005f5e100",
+            "operation": 0,
+            "safeTxGas": "0",
+            "baseGas": "0",
+            "gasPrice": "0",
+            "gasToken": "0x0000000000000000000000000000000000000000",
+            "refundReceiver": "0x0000000000000000000000000000000000000000",
+            "nonce": 7
+        }"#;
+        let tx: SafeTransaction = serde_json::from_str(json).unwrap();
+
+        let hash = compute_safe_tx_hash(SAFE, 11155111, &tx).unwrap();
+        assert_eq!(hex::encode(hash), "cf39d1be8c5b9a4d22506ce8a7c064f467465db4d66e59ff3baa62587015cc33");
+    }
+
+    #[test]
+    fn test_operation_serde() {
+        assert_eq!(serde_json::to_string(&SafeOperation::DelegateCall).unwrap(), "1");
+        assert_eq!(serde_json::from_str::<SafeOperation>("0").unwrap(), SafeOperation::Call);
+        assert!(serde_json::from_str::<SafeOperation>("2").is_err());
+    }
+
+    #[test]
+    fn test_invalid_safe_address() {
+        let tx = SafeTransaction::new(SAFE, "0", "0x", SafeOperation::Call, 0);
+        assert!(compute_safe_tx_hash("0x1234", 1, &tx).is_err());
+    }
+}
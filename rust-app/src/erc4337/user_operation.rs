@@ -1,6 +1,7 @@
 //! ERC-4337 UserOperation structure and building
 //!
-//! Implements the UserOperation (v0.7) format for account abstraction.
+//! Implements the UserOperation format for account abstraction, hashed for
+//! either EntryPoint v0.6 or v0.7.
 
 use crate::error::{HawalaError, HawalaResult, ErrorCode};
 use serde::{Deserialize, Serialize};
@@ -108,6 +109,16 @@ impl UserOperation {
     
     /// Pack the UserOperation for hashing (v0.7 format)
     pub fn pack_for_hash(&self) -> HawalaResult<Vec<u8>> {
+        self.pack_for_signing(UserOpVersion::V07)
+    }
+    
+    /// Pack the UserOperation for hashing under an EntryPoint version
+    ///
+    /// v0.6 encodes each gas field as its own word and hashes
+    /// `paymasterAndData` as paymaster || data. v0.7 packs the gas limits and
+    /// fees into two `bytes32` words and puts the paymaster gas limits inside
+    /// `paymasterAndData`.
+    pub fn pack_for_signing(&self, version: UserOpVersion) -> HawalaResult<Vec<u8>> {
         let mut packed = Vec::new();
         
         // sender (address)
@@ -116,58 +127,53 @@ impl UserOperation {
         // nonce (uint256)
         packed.extend(Self::pad_uint256(&self.nonce)?);
         
-        // hash(factory || factoryData)
-        let init_code_hash = if let (Some(factory), Some(data)) = (&self.factory, &self.factory_data) {
-            let mut init_code = hex::decode(factory.strip_prefix("0x").unwrap_or(factory))
-                .map_err(|e| HawalaError::new(ErrorCode::InvalidInput, format!("Invalid factory: {}", e)))?;
-            init_code.extend(hex::decode(data.strip_prefix("0x").unwrap_or(data))
-                .map_err(|e| HawalaError::new(ErrorCode::InvalidInput, format!("Invalid factory data: {}", e)))?);
-            Self::keccak256(&init_code)
-        } else {
-            Self::keccak256(&[])
-        };
-        packed.extend(init_code_hash);
+        // hash(initCode), initCode = factory || factoryData
+        packed.extend(Self::keccak256(&self.init_code()?));
         
         // hash(callData)
-        let call_data_bytes = hex::decode(self.call_data.strip_prefix("0x").unwrap_or(&self.call_data))
-            .map_err(|e| HawalaError::new(ErrorCode::InvalidInput, format!("Invalid call data: {}", e)))?;
-        packed.extend(Self::keccak256(&call_data_bytes));
-        
-        // accountGasLimits: bytes32 = callGasLimit || verificationGasLimit
-        packed.extend(Self::pack_gas_limits(&self.call_gas_limit, &self.verification_gas_limit)?);
+        packed.extend(Self::keccak256(&Self::decode_hex(&self.call_data, "call data")?));
         
-        // preVerificationGas
-        packed.extend(Self::pad_uint256(&self.pre_verification_gas)?);
-        
-        // gasFees: bytes32 = maxPriorityFeePerGas || maxFeePerGas
-        packed.extend(Self::pack_gas_limits(&self.max_priority_fee_per_gas, &self.max_fee_per_gas)?);
-        
-        // hash(paymasterAndData)
-        let paymaster_hash = if let Some(paymaster) = &self.paymaster {
-            let mut pm_data = hex::decode(paymaster.strip_prefix("0x").unwrap_or(paymaster))
-                .map_err(|e| HawalaError::new(ErrorCode::InvalidInput, format!("Invalid paymaster: {}", e)))?;
-            if let Some(ver_gas) = &self.paymaster_verification_gas_limit {
-                pm_data.extend(Self::pad_uint128(ver_gas)?);
+        match version {
+            UserOpVersion::V06 => {
+                packed.extend(Self::pad_uint256(&self.call_gas_limit)?);
+                packed.extend(Self::pad_uint256(&self.verification_gas_limit)?);
+                packed.extend(Self::pad_uint256(&self.pre_verification_gas)?);
+                packed.extend(Self::pad_uint256(&self.max_fee_per_gas)?);
+                packed.extend(Self::pad_uint256(&self.max_priority_fee_per_gas)?);
             }
-            if let Some(post_gas) = &self.paymaster_post_op_gas_limit {
-                pm_data.extend(Self::pad_uint128(post_gas)?);
+            UserOpVersion::V07 => {
+                // accountGasLimits: bytes32 = verificationGasLimit || callGasLimit
+                packed.extend(Self::pack_gas_limits(&self.verification_gas_limit, &self.call_gas_limit)?);
+                
+                // preVerificationGas
+                packed.extend(Self::pad_uint256(&self.pre_verification_gas)?);
+                
+                // gasFees: bytes32 = maxPriorityFeePerGas || maxFeePerGas
+                packed.extend(Self::pack_gas_limits(&self.max_priority_fee_per_gas, &self.max_fee_per_gas)?);
             }
-            if let Some(data) = &self.paymaster_data {
-                pm_data.extend(hex::decode(data.strip_prefix("0x").unwrap_or(data))
-                    .map_err(|e| HawalaError::new(ErrorCode::InvalidInput, format!("Invalid paymaster data: {}", e)))?);
-            }
-            Self::keccak256(&pm_data)
-        } else {
-            Self::keccak256(&[])
-        };
-        packed.extend(paymaster_hash);
+        }
+        
+        // hash(paymasterAndData)
+        packed.extend(Self::keccak256(&self.paymaster_and_data(version)?));
         
         Ok(packed)
     }
     
     /// Get the hash of this UserOperation for signing
+    ///
+    /// The packing follows the EntryPoint version of `entry_point`.
     pub fn get_hash(&self, entry_point: &str, chain_id: u64) -> HawalaResult<[u8; 32]> {
-        let packed = self.pack_for_hash()?;
+        self.get_hash_for_version(UserOpVersion::for_entry_point(entry_point), entry_point, chain_id)
+    }
+    
+    /// Get the userOpHash for an explicit EntryPoint version
+    pub fn get_hash_for_version(
+        &self,
+        version: UserOpVersion,
+        entry_point: &str,
+        chain_id: u64,
+    ) -> HawalaResult<[u8; 32]> {
+        let packed = self.pack_for_signing(version)?;
         let user_op_hash = Self::keccak256(&packed);
         
         // keccak256(userOpHash || entryPoint || chainId)
@@ -182,6 +188,36 @@ impl UserOperation {
         Ok(result)
     }
     
+    /// initCode (factory || factoryData), empty when no factory is set
+    pub fn init_code(&self) -> HawalaResult<Vec<u8>> {
+        match (&self.factory, &self.factory_data) {
+            (Some(factory), Some(data)) => {
+                let mut init_code = Self::decode_hex(factory, "factory")?;
+                init_code.extend(Self::decode_hex(data, "factory data")?);
+                Ok(init_code)
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+    
+    /// paymasterAndData as packed by the given EntryPoint version
+    pub fn paymaster_and_data(&self, version: UserOpVersion) -> HawalaResult<Vec<u8>> {
+        let Some(paymaster) = &self.paymaster else {
+            return Ok(Vec::new());
+        };
+        
+        let mut pm_data = Self::decode_hex(paymaster, "paymaster")?;
+        if version == UserOpVersion::V07 {
+            let verification_gas = self.paymaster_verification_gas_limit.as_deref().unwrap_or("0x0");
+            let post_op_gas = self.paymaster_post_op_gas_limit.as_deref().unwrap_or("0x0");
+            pm_data.extend(Self::pack_gas_limits(verification_gas, post_op_gas)?);
+        }
+        if let Some(data) = &self.paymaster_data {
+            pm_data.extend(Self::decode_hex(data, "paymaster data")?);
+        }
+        Ok(pm_data)
+    }
+    
     /// Set the signature
     pub fn with_signature(mut self, signature: &str) -> Self {
         self.signature = signature.to_string();
@@ -195,44 +231,85 @@ impl UserOperation {
         hasher.finalize().to_vec()
     }
     
+    /// Decode hex, accepting quantities with an odd number of digits ("0x30d40")
+    fn decode_hex(value: &str, what: &str) -> HawalaResult<Vec<u8>> {
+        let digits = value.strip_prefix("0x").unwrap_or(value);
+        let result = if digits.len() % 2 == 1 {
+            hex::decode(format!("0{}", digits))
+        } else {
+            hex::decode(digits)
+        };
+        result.map_err(|e| HawalaError::new(ErrorCode::InvalidInput, format!("Invalid {}: {}", what, e)))
+    }
+    
     fn pad_address(addr: &str) -> HawalaResult<Vec<u8>> {
-        let addr_bytes = hex::decode(addr.strip_prefix("0x").unwrap_or(addr))
-            .map_err(|e| HawalaError::new(ErrorCode::InvalidInput, format!("Invalid address: {}", e)))?;
+        let addr_bytes = Self::decode_hex(addr, "address")?;
+        if addr_bytes.len() != 20 {
+            return Err(HawalaError::new(ErrorCode::InvalidInput, format!("Invalid address: {}", addr)));
+        }
         let mut padded = vec![0u8; 12]; // 32 - 20 = 12 bytes padding
         padded.extend(addr_bytes);
         Ok(padded)
     }
     
-    fn pad_uint256(hex_val: &str) -> HawalaResult<Vec<u8>> {
-        let val_bytes = hex::decode(hex_val.strip_prefix("0x").unwrap_or(hex_val))
-            .map_err(|e| HawalaError::new(ErrorCode::InvalidInput, format!("Invalid uint256: {}", e)))?;
-        let mut padded = vec![0u8; 32 - val_bytes.len()];
-        padded.extend(val_bytes);
+    fn pad_uint(hex_val: &str, size: usize) -> HawalaResult<Vec<u8>> {
+        let val_bytes = Self::decode_hex(hex_val, "integer")?;
+        let start = val_bytes.iter().position(|b| *b != 0).unwrap_or(val_bytes.len());
+        let significant = &val_bytes[start..];
+        if significant.len() > size {
+            return Err(HawalaError::new(
+                ErrorCode::InvalidInput,
+                format!("{} does not fit in uint{}", hex_val, size * 8),
+            ));
+        }
+        let mut padded = vec![0u8; size - significant.len()];
+        padded.extend(significant);
         Ok(padded)
     }
     
-    fn pad_uint128(hex_val: &str) -> HawalaResult<Vec<u8>> {
-        let val_bytes = hex::decode(hex_val.strip_prefix("0x").unwrap_or(hex_val))
-            .map_err(|e| HawalaError::new(ErrorCode::InvalidInput, format!("Invalid uint128: {}", e)))?;
-        let mut padded = vec![0u8; 16 - val_bytes.len().min(16)];
-        padded.extend(&val_bytes[..val_bytes.len().min(16)]);
-        Ok(padded)
+    fn pad_uint256(hex_val: &str) -> HawalaResult<Vec<u8>> {
+        Self::pad_uint(hex_val, 32)
     }
     
-    fn pack_gas_limits(gas1: &str, gas2: &str) -> HawalaResult<Vec<u8>> {
-        let g1 = hex::decode(gas1.strip_prefix("0x").unwrap_or(gas1))
-            .map_err(|e| HawalaError::new(ErrorCode::InvalidInput, format!("Invalid gas1: {}", e)))?;
-        let g2 = hex::decode(gas2.strip_prefix("0x").unwrap_or(gas2))
-            .map_err(|e| HawalaError::new(ErrorCode::InvalidInput, format!("Invalid gas2: {}", e)))?;
-        
-        let mut result = vec![0u8; 16 - g1.len().min(16)];
-        result.extend(&g1[..g1.len().min(16)]);
-        result.extend(vec![0u8; 16 - g2.len().min(16)]);
-        result.extend(&g2[..g2.len().min(16)]);
+    /// Two uint128 values packed into one word (high || low)
+    fn pack_gas_limits(high: &str, low: &str) -> HawalaResult<Vec<u8>> {
+        let mut result = Self::pad_uint(high, 16)?;
+        result.extend(Self::pad_uint(low, 16)?);
         Ok(result)
     }
 }
 
+/// EntryPoint version a UserOperation is hashed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UserOpVersion {
+    /// EntryPoint v0.6
+    #[serde(rename = "v0.6")]
+    V06,
+    /// EntryPoint v0.7
+    #[default]
+    #[serde(rename = "v0.7")]
+    V07,
+}
+
+impl UserOpVersion {
+    /// Canonical EntryPoint address (same on all chains)
+    pub fn entry_point(&self) -> &'static str {
+        match self {
+            Self::V06 => "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789",
+            Self::V07 => "0x0000000071727De22E5E9d8BAf0edAc6f37da032",
+        }
+    }
+    
+    /// Version for an EntryPoint address (v0.7 unless it is the v0.6 EntryPoint)
+    pub fn for_entry_point(entry_point: &str) -> Self {
+        if entry_point.eq_ignore_ascii_case(Self::V06.entry_point()) {
+            Self::V06
+        } else {
+            Self::V07
+        }
+    }
+}
+
 /// Signed UserOperation ready for submission
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignedUserOperation {
@@ -415,6 +492,7 @@ impl Default for CallDataBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::erc4337::ERC4337Chain;
 
     #[test]
     fn test_user_operation_new() {
@@ -445,6 +523,78 @@ mod tests {
         assert_eq!(user_op.paymaster_verification_gas_limit, Some("0xc350".to_string()));
     }
 
+    fn sponsored_op() -> UserOperation {
+        UserOperation::new("0x1234567890123456789012345678901234567890", 1, "0xb61d27f6")
+            .with_factory(
+                "0x9406Cc6185a346906296840746125a0E44976454",
+                "0x5fbfb9cf000000000000000000000000abcdefabcdefabcdefabcdefabcdefabcdefabcd0000000000000000000000000000000000000000000000000000000000000000",
+            )
+            .with_paymaster("0x00000000000000fb866daaa79352cc568a005d96", 0x8a88, 1, "0xdeadbeef")
+    }
+
+    #[test]
+    fn test_user_op_hash_v06() {
+        let user_op = UserOperation::new("0x1234567890123456789012345678901234567890", 1, "0xb61d27f6");
+        let entry_point = UserOpVersion::V06.entry_point();
+        
+        let hash = user_op.get_hash_for_version(UserOpVersion::V06, entry_point, 1).unwrap();
+        assert_eq!(hex::encode(hash), "c91ea98eeeba3f2418464c48f1b3f99192b6b8e3d0134bec8ab5031934c32f64");
+        
+        let hash = sponsored_op().get_hash_for_version(UserOpVersion::V06, entry_point, 11155111).unwrap();
+        assert_eq!(hex::encode(hash), "e1893a07570aad7278f24d8db9c692ffd7dc17fde19802d73c42c3b321c8e61f");
+    }
+
+    #[test]
+    fn test_user_op_hash_v07() {
+        let user_op = UserOperation::new("0x1234567890123456789012345678901234567890", 1, "0xb61d27f6");
+        let entry_point = UserOpVersion::V07.entry_point();
+        
+        let hash = user_op.get_hash_for_version(UserOpVersion::V07, entry_point, 1).unwrap();
+        assert_eq!(hex::encode(hash), "712a30361b64060f16ac1e9891c5d1ee953e9d91fb23363d62e48b96727bfa0e");
+        
+        let hash = sponsored_op().get_hash_for_version(UserOpVersion::V07, entry_point, 11155111).unwrap();
+        assert_eq!(hex::encode(hash), "4bc36cbef84ca4f946fac91415fd50800da22e98fe468cabf593cd1cadf4a120");
+    }
+
+    #[test]
+    fn test_packing_differs_by_version() {
+        let user_op = sponsored_op();
+        
+        let v06 = user_op.pack_for_signing(UserOpVersion::V06).unwrap();
+        let v07 = user_op.pack_for_signing(UserOpVersion::V07).unwrap();
+        assert_eq!(v06.len(), 32 * 10);
+        assert_eq!(v07.len(), 32 * 8);
+        
+        assert_eq!(user_op.paymaster_and_data(UserOpVersion::V06).unwrap().len(), 20 + 4);
+        assert_eq!(user_op.paymaster_and_data(UserOpVersion::V07).unwrap().len(), 20 + 32 + 4);
+    }
+
+    #[test]
+    fn test_get_hash_detects_entry_point_version() {
+        let user_op = sponsored_op();
+        let v06 = ERC4337Chain::Ethereum.entry_point_v06();
+        
+        assert_eq!(UserOpVersion::for_entry_point(v06), UserOpVersion::V06);
+        assert_eq!(UserOpVersion::for_entry_point(&v06.to_lowercase()), UserOpVersion::V06);
+        assert_eq!(UserOpVersion::for_entry_point(ERC4337Chain::Ethereum.entry_point()), UserOpVersion::V07);
+        assert_eq!(
+            user_op.get_hash(v06, 1).unwrap(),
+            user_op.get_hash_for_version(UserOpVersion::V06, v06, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_gas_overflow_rejected() {
+        let user_op = UserOperation::new("0x1234567890123456789012345678901234567890", 1, "0x")
+            .with_fees(1, 1);
+        let mut overflowing = user_op.clone();
+        overflowing.call_gas_limit = format!("0x1{}", "00".repeat(16));
+        
+        assert!(user_op.pack_for_signing(UserOpVersion::V07).is_ok());
+        assert!(overflowing.pack_for_signing(UserOpVersion::V07).is_err());
+        assert!(overflowing.pack_for_signing(UserOpVersion::V06).is_ok());
+    }
+
     #[test]
     fn test_call_data_builder_single() {
         let call_data = CallDataBuilder::new()
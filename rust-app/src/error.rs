@@ -119,11 +119,13 @@ pub enum ErrorCode {
     // Transaction errors
     InsufficientFunds,
     InsufficientFee,
+    FeeTooHigh,
     NonceTooLow,
     NonceTooHigh,
     BroadcastFailed,
     TransactionNotFound,
     TransactionRejected,
+    ChainIdMismatch,
     
     // Crypto errors
     CryptoError,
@@ -212,6 +214,25 @@ impl From<bip39::Error> for HawalaError {
     }
 }
 
+impl From<crate::crypto::curves::CurveError> for HawalaError {
+    /// The curve error's own code goes in `details`
+    fn from(e: crate::crypto::curves::CurveError) -> Self {
+        use crate::crypto::curves::CurveError;
+
+        let code = match &e {
+            CurveError::InvalidPrivateKey(_) => ErrorCode::InvalidPrivateKey,
+            CurveError::InvalidPublicKey(_)
+            | CurveError::InvalidSignature(_)
+            | CurveError::InvalidSeed(_) => ErrorCode::InvalidInput,
+            CurveError::SigningFailed(_) => ErrorCode::SigningFailed,
+            CurveError::VerificationFailed(_) => ErrorCode::VerificationFailed,
+            CurveError::UnsupportedCurve(_) => ErrorCode::NotImplemented,
+            CurveError::DerivationFailed(_) => ErrorCode::CryptoError,
+        };
+        HawalaError::new(code, e.to_string()).with_details(e.code())
+    }
+}
+
 impl From<Box<dyn std::error::Error>> for HawalaError {
     fn from(e: Box<dyn std::error::Error>) -> Self {
         HawalaError::new(ErrorCode::Internal, e.to_string())
@@ -237,4 +258,18 @@ mod tests {
         assert!(json.contains("insufficient_funds"));
         assert!(json.contains("Not enough BTC"));
     }
+
+    #[test]
+    fn test_curve_error_conversion() {
+        use crate::crypto::curves::CurveError;
+
+        let err = HawalaError::from(CurveError::InvalidPrivateKey("zero scalar".into()));
+        assert_eq!(err.code, ErrorCode::InvalidPrivateKey);
+        assert_eq!(err.details.as_deref(), Some("invalid_private_key"));
+        assert!(err.message.contains("zero scalar"));
+
+        let err = HawalaError::from(CurveError::VerificationFailed("bad sig".into()));
+        assert_eq!(err.code, ErrorCode::VerificationFailed);
+        assert_eq!(err.details.as_deref(), Some("verification_failed"));
+    }
 }
@@ -1,3 +1,5 @@
+use ethers_core::types::transaction::eip2718::TypedTransaction;
+use ethers_core::types::transaction::eip2930::{AccessList, Eip2930TransactionRequest};
 use ethers_core::types::{Address, TransactionRequest, Eip1559TransactionRequest, U256};
 use ethers_signers::{LocalWallet, Signer};
 use std::error::Error;
@@ -14,6 +16,39 @@ pub async fn prepare_ethereum_transaction(
     max_fee_per_gas_wei: Option<String>,
     max_priority_fee_per_gas_wei: Option<String>,
     data_hex: &str,
+) -> Result<String, Box<dyn Error>> {
+    prepare_ethereum_transaction_with_access_list(
+        recipient,
+        amount_wei,
+        chain_id,
+        sender_key_hex,
+        nonce,
+        gas_limit,
+        gas_price_wei,
+        max_fee_per_gas_wei,
+        max_priority_fee_per_gas_wei,
+        data_hex,
+        None,
+    )
+    .await
+}
+
+/// Like [`prepare_ethereum_transaction`], with an optional EIP-2930 access list.
+/// A legacy-priced transaction with an access list is built as type 0x01;
+/// an EIP-1559 transaction carries the list in its own access list field.
+#[allow(clippy::too_many_arguments)]
+pub async fn prepare_ethereum_transaction_with_access_list(
+    recipient: &str,
+    amount_wei: &str,
+    chain_id: u64,
+    sender_key_hex: &str,
+    nonce: u64,
+    gas_limit: u64,
+    gas_price_wei: Option<String>,
+    max_fee_per_gas_wei: Option<String>,
+    max_priority_fee_per_gas_wei: Option<String>,
+    data_hex: &str,
+    access_list: Option<AccessList>,
 ) -> Result<String, Box<dyn Error>> {
     // 1. Create Wallet
     let wallet = LocalWallet::from_str(sender_key_hex)?.with_chain_id(chain_id);
@@ -35,7 +70,7 @@ pub async fn prepare_ethereum_transaction(
     };
 
     // 3. Build Transaction
-    let typed_tx: ethers_core::types::transaction::eip2718::TypedTransaction = if let Some(max_fee) = max_fee_per_gas_wei {
+    let typed_tx: TypedTransaction = if let Some(max_fee) = max_fee_per_gas_wei {
         // EIP-1559
         let max_fee_per_gas = if max_fee.starts_with("0x") {
             U256::from_str_radix(max_fee.trim_start_matches("0x"), 16)?
@@ -62,9 +97,10 @@ pub async fn prepare_ethereum_transaction(
             .chain_id(chain_id)
             .nonce(nonce)
             .data(data)
+            .access_list(access_list.unwrap_or_default())
             .into()
     } else {
-        // Legacy
+        // Legacy, or EIP-2930 when an access list is given
         let gas_price_str = gas_price_wei.ok_or("Missing gas_price for legacy transaction")?;
         let gas_price = if gas_price_str.starts_with("0x") {
             U256::from_str_radix(gas_price_str.trim_start_matches("0x"), 16)?
@@ -72,15 +108,19 @@ pub async fn prepare_ethereum_transaction(
             U256::from_dec_str(&gas_price_str)?
         };
 
-        TransactionRequest::new()
+        let legacy = TransactionRequest::new()
             .to(to_address)
             .value(value)
             .gas(gas_limit)
             .gas_price(gas_price)
             .chain_id(chain_id)
             .nonce(nonce)
-            .data(data)
-            .into()
+            .data(data);
+
+        match access_list {
+            Some(access_list) => TypedTransaction::Eip2930(Eip2930TransactionRequest::new(legacy, access_list)),
+            None => legacy.into(),
+        }
     };
 
     // 4. Sign
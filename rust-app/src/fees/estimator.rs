@@ -4,9 +4,12 @@
 //! Migrated from Swift FeeEstimationService.swift
 
 use crate::error::{HawalaError, HawalaResult, ErrorCode};
+use crate::fees::units::{SatPerVb, Wei};
 use crate::types::*;
+use crate::utils::network_config::{evm_service_urls, service_url, with_failover, ServiceKind};
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock, PoisonError};
 use std::time::Duration;
 
 // =============================================================================
@@ -38,6 +41,11 @@ pub fn get_fee_estimate(chain: Chain) -> HawalaResult<FeeEstimate> {
     }
 }
 
+/// Bitcoin mainnet fee tiers from mempool.space, cached for a minute
+pub fn estimate_bitcoin_fees() -> HawalaResult<BitcoinFeeTiers> {
+    bitcoin_fee_tiers(false)
+}
+
 /// Estimate gas limit for an EVM transaction
 pub fn estimate_gas_limit(
     chain_id: u64,
@@ -48,10 +56,8 @@ pub fn estimate_gas_limit(
 ) -> HawalaResult<GasEstimateResult> {
     let endpoints = get_rpc_endpoints(chain_id);
     
-    for endpoint in &endpoints {
-        if let Ok(result) = estimate_gas_single(endpoint, from, to, value, data) {
-            return Ok(result);
-        }
+    if let Ok(result) = with_failover(&endpoints, |endpoint| estimate_gas_single(endpoint, from, to, value, data)) {
+        return Ok(result);
     }
     
     // Return default for simple transfer
@@ -67,13 +73,8 @@ pub fn estimate_gas_limit(
 pub fn get_gas_price(chain_id: u64) -> HawalaResult<u64> {
     let endpoints = get_rpc_endpoints(chain_id);
     
-    for endpoint in &endpoints {
-        if let Ok(price) = get_gas_price_single(endpoint) {
-            return Ok(price);
-        }
-    }
-    
-    Err(HawalaError::network_error("Failed to fetch gas price from all endpoints"))
+    with_failover(&endpoints, get_gas_price_single)
+        .map_err(|_| HawalaError::network_error("Failed to fetch gas price from all endpoints"))
 }
 
 /// Get base fee for EIP-1559 chains
@@ -85,13 +86,8 @@ pub fn get_base_fee(chain_id: u64) -> HawalaResult<u64> {
     
     let endpoints = get_rpc_endpoints(chain_id);
     
-    for endpoint in &endpoints {
-        if let Ok(fee) = get_base_fee_single(endpoint) {
-            return Ok(fee);
-        }
-    }
-    
-    Err(HawalaError::network_error("Failed to fetch base fee from all endpoints"))
+    with_failover(&endpoints, get_base_fee_single)
+        .map_err(|_| HawalaError::network_error("Failed to fetch base fee from all endpoints"))
 }
 
 /// Recommended gas limit for common transaction types
@@ -110,16 +106,44 @@ pub fn recommended_gas_limit(tx_type: EvmTransactionType) -> u64 {
 // Bitcoin Fee Estimation (mempool.space)
 // =============================================================================
 
-fn get_bitcoin_fees(testnet: bool) -> HawalaResult<FeeEstimate> {
-    let base_url = if testnet {
-        "https://mempool.space/testnet/api"
+/// How long mempool fee tiers are reused before refetching
+const BITCOIN_FEE_CACHE_SECS: u64 = 60;
+
+static BITCOIN_FEE_CACHE: OnceLock<Mutex<crate::utils::Cache<BitcoinFeeTiers>>> = OnceLock::new();
+
+fn bitcoin_fee_tiers(testnet: bool) -> HawalaResult<BitcoinFeeTiers> {
+    let cache = BITCOIN_FEE_CACHE.get_or_init(|| Mutex::new(crate::utils::Cache::new(BITCOIN_FEE_CACHE_SECS)));
+    let key = if testnet { "testnet" } else { "mainnet" };
+    
+    if let Some(tiers) = cache.lock().unwrap_or_else(PoisonError::into_inner).get(key) {
+        return Ok(tiers);
+    }
+    
+    let (chain, default_url) = if testnet {
+        (Chain::BitcoinTestnet, "https://mempool.space/testnet/api")
     } else {
-        "https://mempool.space/api"
+        (Chain::Bitcoin, "https://mempool.space/api")
     };
+    let base_url = service_url(chain, ServiceKind::Explorer, default_url);
     
     let url = format!("{}/v1/fees/recommended", base_url);
     let client = create_client()?;
     
+    let body = client.get(&url)
+        .header("User-Agent", "HawalaApp/1.0")
+        .send()
+        .map_err(|e| HawalaError::network_error(format!("Mempool request failed: {}", e)))?
+        .text()
+        .map_err(|e| HawalaError::network_error(format!("Mempool request failed: {}", e)))?;
+    
+    let tiers = parse_mempool_fees(&body)?;
+    cache.lock().unwrap_or_else(PoisonError::into_inner).set(key.to_string(), tiers);
+    Ok(tiers)
+}
+
+/// Parse a `/v1/fees/recommended` response. Tiers are forced to be at least
+/// 1 sat/vB and never cheaper than a slower tier.
+fn parse_mempool_fees(body: &str) -> HawalaResult<BitcoinFeeTiers> {
     #[derive(Deserialize)]
     #[serde(rename_all = "camelCase")]
     struct MempoolFees {
@@ -130,37 +154,51 @@ fn get_bitcoin_fees(testnet: bool) -> HawalaResult<FeeEstimate> {
         minimum_fee: u64,
     }
     
-    let response: MempoolFees = client.get(&url)
-        .header("User-Agent", "HawalaApp/1.0")
-        .send()
-        .map_err(|e| HawalaError::network_error(format!("Mempool request failed: {}", e)))?
-        .json()
+    let fees: MempoolFees = serde_json::from_str(body)
         .map_err(|e| HawalaError::parse_error(format!("Failed to parse fees: {}", e)))?;
     
+    let minimum = fees.minimum_fee.max(1);
+    let economy = fees.economy_fee.max(minimum);
+    let hour = fees.hour_fee.max(economy);
+    let half_hour = fees.half_hour_fee.max(hour);
+    let fastest = fees.fastest_fee.max(half_hour);
+    
+    Ok(BitcoinFeeTiers {
+        fastest: SatPerVb(fastest),
+        half_hour: SatPerVb(half_hour),
+        hour: SatPerVb(hour),
+        economy: SatPerVb(economy),
+        minimum: SatPerVb(minimum),
+    })
+}
+
+fn get_bitcoin_fees(testnet: bool) -> HawalaResult<FeeEstimate> {
+    let tiers = bitcoin_fee_tiers(testnet)?;
+    
     Ok(FeeEstimate::Bitcoin(BitcoinFeeEstimate {
         fastest: FeeLevel {
             label: "Fastest (~10 min)".to_string(),
-            rate: response.fastest_fee,
+            rate: tiers.fastest,
             estimated_minutes: 10,
         },
         fast: FeeLevel {
             label: "Fast (~30 min)".to_string(),
-            rate: response.half_hour_fee,
+            rate: tiers.half_hour,
             estimated_minutes: 30,
         },
         medium: FeeLevel {
             label: "Medium (~1 hour)".to_string(),
-            rate: response.hour_fee,
+            rate: tiers.hour,
             estimated_minutes: 60,
         },
         slow: FeeLevel {
             label: "Economy (~2 hours)".to_string(),
-            rate: response.economy_fee,
+            rate: tiers.economy,
             estimated_minutes: 120,
         },
         minimum: FeeLevel {
             label: "Minimum".to_string(),
-            rate: response.minimum_fee,
+            rate: tiers.minimum,
             estimated_minutes: 1440,
         },
     }))
@@ -203,17 +241,17 @@ fn get_litecoin_fees() -> HawalaResult<FeeEstimate> {
     Ok(FeeEstimate::Litecoin(LitecoinFeeEstimate {
         fast: FeeLevel {
             label: "Fast (~10 min)".to_string(),
-            rate: (base_fee * 2.0) as u64,
+            rate: SatPerVb((base_fee * 2.0) as u64),
             estimated_minutes: 10,
         },
         medium: FeeLevel {
             label: "Medium (~30 min)".to_string(),
-            rate: base_fee as u64,
+            rate: SatPerVb(base_fee as u64),
             estimated_minutes: 30,
         },
         slow: FeeLevel {
             label: "Slow (~2 hours)".to_string(),
-            rate: std::cmp::max(1, (base_fee * 0.5) as u64),
+            rate: SatPerVb(std::cmp::max(1, (base_fee * 0.5) as u64)),
             estimated_minutes: 120,
         },
         mempool_congestion: congestion,
@@ -242,11 +280,11 @@ fn get_evm_fees(chain: Chain) -> HawalaResult<FeeEstimate> {
     };
     
     Ok(FeeEstimate::Evm(EvmFeeEstimate {
-        base_fee: base_fee.to_string(),
-        priority_fee_low: priority_low.to_string(),
-        priority_fee_medium: priority_med.to_string(),
-        priority_fee_high: priority_high.to_string(),
-        gas_price_legacy: gas_price.to_string(),
+        base_fee: Wei(u128::from(base_fee)),
+        priority_fee_low: Wei(u128::from(priority_low)),
+        priority_fee_medium: Wei(u128::from(priority_med)),
+        priority_fee_high: Wei(u128::from(priority_high)),
+        gas_price_legacy: Wei(u128::from(gas_price)),
         chain_id,
     }))
 }
@@ -411,9 +449,9 @@ fn get_base_fee_single(rpc_url: &str) -> HawalaResult<u64> {
 
 fn get_solana_fees(devnet: bool) -> HawalaResult<FeeEstimate> {
     let rpc_url = if devnet {
-        "https://api.devnet.solana.com"
+        service_url(Chain::SolanaDevnet, ServiceKind::Rpc, "https://api.devnet.solana.com")
     } else {
-        "https://api.mainnet-beta.solana.com"
+        service_url(Chain::Solana, ServiceKind::Rpc, "https://api.mainnet-beta.solana.com")
     };
     
     let client = create_client()?;
@@ -464,7 +502,7 @@ fn get_solana_fees(devnet: bool) -> HawalaResult<FeeEstimate> {
     };
     
     Ok(FeeEstimate::Solana(SolanaFeeEstimate {
-        base_fee_lamports: 5000, // Fixed base fee
+        base_fee_lamports: super::solana::LAMPORTS_PER_SIGNATURE,
         priority_fee_low: std::cmp::max(1000, avg_fee / 2),
         priority_fee_medium: std::cmp::max(10000, avg_fee),
         priority_fee_high: std::cmp::max(100000, std::cmp::max(avg_fee * 2, max_fee)),
@@ -477,9 +515,9 @@ fn get_solana_fees(devnet: bool) -> HawalaResult<FeeEstimate> {
 
 fn get_xrp_fees(testnet: bool) -> HawalaResult<FeeEstimate> {
     let rpc_url = if testnet {
-        "https://s.altnet.rippletest.net:51234"
+        service_url(Chain::XrpTestnet, ServiceKind::Rpc, "https://s.altnet.rippletest.net:51234")
     } else {
-        "https://s1.ripple.com:51234"
+        service_url(Chain::Xrp, ServiceKind::Rpc, "https://s1.ripple.com:51234")
     };
     
     let client = create_client()?;
@@ -559,8 +597,9 @@ fn create_client() -> HawalaResult<Client> {
         .map_err(|e| HawalaError::internal(format!("Failed to create HTTP client: {}", e)))
 }
 
-fn get_rpc_endpoints(chain_id: u64) -> Vec<&'static str> {
-    match chain_id {
+// Configured RPC override first, then the public endpoints best-first
+fn get_rpc_endpoints(chain_id: u64) -> Vec<String> {
+    let defaults = match chain_id {
         1 => vec![
             "https://eth.llamarpc.com",
             "https://ethereum.publicnode.com",
@@ -595,7 +634,8 @@ fn get_rpc_endpoints(chain_id: u64) -> Vec<&'static str> {
             "https://avalanche.llamarpc.com",
         ],
         _ => vec!["https://eth.llamarpc.com"],
-    }
+    };
+    evm_service_urls(chain_id, ServiceKind::Rpc, &defaults)
 }
 
 fn parse_hex_u64(hex: &str) -> HawalaResult<u64> {
@@ -644,6 +684,41 @@ mod tests {
         assert_eq!(recommended_gas_limit(EvmTransactionType::Swap), 250000);
     }
 
+    #[test]
+    fn test_parse_mempool_fees() {
+        let body = r#"{"fastestFee":42,"halfHourFee":30,"hourFee":21,"economyFee":8,"minimumFee":4}"#;
+        assert_eq!(
+            parse_mempool_fees(body).unwrap(),
+            BitcoinFeeTiers {
+                fastest: SatPerVb(42),
+                half_hour: SatPerVb(30),
+                hour: SatPerVb(21),
+                economy: SatPerVb(8),
+                minimum: SatPerVb(4),
+            }
+        );
+        
+        // Out-of-order or zero tiers are clamped
+        let body = r#"{"fastestFee":5,"halfHourFee":6,"hourFee":2,"economyFee":0,"minimumFee":0}"#;
+        assert_eq!(
+            parse_mempool_fees(body).unwrap(),
+            BitcoinFeeTiers {
+                fastest: SatPerVb(6),
+                half_hour: SatPerVb(6),
+                hour: SatPerVb(2),
+                economy: SatPerVb(1),
+                minimum: SatPerVb(1),
+            }
+        );
+        
+        assert!(parse_mempool_fees(r#"{"fastestFee":5}"#).is_err());
+        
+        let json = serde_json::to_value(parse_mempool_fees(
+            r#"{"fastestFee":3,"halfHourFee":3,"hourFee":2,"economyFee":1,"minimumFee":1}"#
+        ).unwrap()).unwrap();
+        assert_eq!(json["halfHour"], 3);
+    }
+
     #[test]
     fn test_parse_hex() {
         assert_eq!(parse_hex_u64("0x5208").unwrap(), 21000);
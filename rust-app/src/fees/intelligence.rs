@@ -49,7 +49,7 @@ pub struct TimeEstimates {
 /// Analyze Bitcoin fees and provide recommendations
 pub fn analyze_bitcoin_fees(estimate: &BitcoinFeeEstimate) -> HawalaResult<FeeIntelligence> {
     // Calculate congestion from fee spread
-    let fee_spread = estimate.fastest.rate as f64 / estimate.slow.rate.max(1) as f64;
+    let fee_spread = estimate.fastest.rate.0 as f64 / estimate.slow.rate.0.max(1) as f64;
     
     let congestion = if fee_spread > 10.0 {
         CongestionLevel::Extreme
@@ -70,7 +70,7 @@ pub fn analyze_bitcoin_fees(estimate: &BitcoinFeeEstimate) -> HawalaResult<FeeIn
     };
     
     // Confidence based on fee data quality
-    let confidence = if estimate.fastest.rate > 0 && estimate.slow.rate > 0 {
+    let confidence = if estimate.fastest.rate.0 > 0 && estimate.slow.rate.0 > 0 {
         0.9
     } else {
         0.5
@@ -110,8 +110,7 @@ pub fn analyze_bitcoin_fees(estimate: &BitcoinFeeEstimate) -> HawalaResult<FeeIn
 /// Analyze EVM fees and provide recommendations
 pub fn analyze_evm_fees(estimate: &EvmFeeEstimate, chain: Chain) -> HawalaResult<FeeIntelligence> {
     // Parse base fee
-    let base_fee: u64 = estimate.base_fee.parse().unwrap_or(0);
-    let base_fee_gwei = base_fee as f64 / 1_000_000_000.0;
+    let base_fee_gwei = estimate.base_fee.0 as f64 / 1_000_000_000.0;
     
     // Determine congestion based on base fee (for Ethereum mainnet)
     let congestion = if chain.chain_id() == Some(1) {
@@ -272,15 +271,16 @@ pub fn analyze_fees(estimate: &FeeEstimate) -> HawalaResult<FeeIntelligence> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fees::units::SatPerVb;
     
     #[test]
     fn test_bitcoin_congestion_detection() {
         let high_congestion = BitcoinFeeEstimate {
-            fastest: FeeLevel { label: "".to_string(), rate: 100, estimated_minutes: 10 },
-            fast: FeeLevel { label: "".to_string(), rate: 80, estimated_minutes: 30 },
-            medium: FeeLevel { label: "".to_string(), rate: 50, estimated_minutes: 60 },
-            slow: FeeLevel { label: "".to_string(), rate: 10, estimated_minutes: 120 },
-            minimum: FeeLevel { label: "".to_string(), rate: 5, estimated_minutes: 1440 },
+            fastest: FeeLevel { label: "".to_string(), rate: SatPerVb(100), estimated_minutes: 10 },
+            fast: FeeLevel { label: "".to_string(), rate: SatPerVb(80), estimated_minutes: 30 },
+            medium: FeeLevel { label: "".to_string(), rate: SatPerVb(50), estimated_minutes: 60 },
+            slow: FeeLevel { label: "".to_string(), rate: SatPerVb(10), estimated_minutes: 120 },
+            minimum: FeeLevel { label: "".to_string(), rate: SatPerVb(5), estimated_minutes: 1440 },
         };
         
         let intel = analyze_bitcoin_fees(&high_congestion).unwrap();
@@ -4,6 +4,8 @@
 
 mod estimator;
 mod intelligence;
+pub mod solana;
+pub mod units;
 
 pub use estimator::*;
 pub use intelligence::*;
@@ -0,0 +1,82 @@
+//! Solana Transaction Cost
+//!
+//! Total lamports a transaction takes from the fee payer: the per-signature
+//! base fee, any priority fee, and the rent-exempt deposit for accounts the
+//! transaction creates.
+
+use crate::error::{HawalaError, HawalaResult};
+
+use super::units::Lamports;
+
+/// Base fee charged per transaction signature
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Bytes of account metadata counted toward rent on top of the data length
+pub const ACCOUNT_STORAGE_OVERHEAD: u64 = 128;
+
+/// Rent per byte-year at the default rent rate
+const LAMPORTS_PER_BYTE_YEAR: u64 = 3_480;
+
+/// Years of rent an account must hold to be rent exempt
+const EXEMPTION_THRESHOLD_YEARS: u64 = 2;
+
+/// Rent-exempt minimum for a system account with no data
+pub const RENT_EXEMPT_MINIMUM: Lamports = Lamports(890_880);
+
+/// Minimum balance for an account holding `data_len` bytes to be rent exempt
+pub fn rent_exempt_minimum(data_len: u64) -> HawalaResult<Lamports> {
+    ACCOUNT_STORAGE_OVERHEAD
+        .checked_add(data_len)
+        .and_then(|bytes| bytes.checked_mul(LAMPORTS_PER_BYTE_YEAR * EXEMPTION_THRESHOLD_YEARS))
+        .map(Lamports)
+        .ok_or_else(|| HawalaError::invalid_input("Account size overflows rent calculation"))
+}
+
+/// Total lamports spent by a transaction
+///
+/// `priority_fee` is the total priority fee (compute unit price times the
+/// compute unit limit). When `creates_account` is set, the rent-exempt
+/// minimum for a data-less account is included: a transfer of less than that
+/// to a new address fails.
+pub fn compute_transaction_cost(
+    num_signatures: u64,
+    priority_fee: Lamports,
+    creates_account: bool,
+) -> HawalaResult<Lamports> {
+    let rent = if creates_account { RENT_EXEMPT_MINIMUM.0 } else { 0 };
+
+    num_signatures
+        .checked_mul(LAMPORTS_PER_SIGNATURE)
+        .and_then(|fee| fee.checked_add(priority_fee.0))
+        .and_then(|fee| fee.checked_add(rent))
+        .map(Lamports)
+        .ok_or_else(|| HawalaError::invalid_input("Transaction cost overflows"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_transfer_cost() {
+        assert_eq!(compute_transaction_cost(1, Lamports(0), false).unwrap(), Lamports(5_000));
+        assert_eq!(compute_transaction_cost(2, Lamports(1_200), false).unwrap(), Lamports(11_200));
+    }
+
+    #[test]
+    fn test_transfer_creating_account() {
+        assert_eq!(rent_exempt_minimum(0).unwrap(), RENT_EXEMPT_MINIMUM);
+        assert_eq!(compute_transaction_cost(1, Lamports(0), true).unwrap(), Lamports(895_880));
+        assert_eq!(compute_transaction_cost(1, Lamports(10_000), true).unwrap(), Lamports(905_880));
+
+        // SPL token accounts hold 165 bytes
+        assert_eq!(rent_exempt_minimum(165).unwrap(), Lamports(2_039_280));
+    }
+
+    #[test]
+    fn test_cost_overflow() {
+        assert!(compute_transaction_cost(u64::MAX, Lamports(0), false).is_err());
+        assert!(compute_transaction_cost(1, Lamports(u64::MAX), false).is_err());
+        assert!(rent_exempt_minimum(u64::MAX).is_err());
+    }
+}
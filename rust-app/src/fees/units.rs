@@ -0,0 +1,243 @@
+//! Fee Units
+//!
+//! Typed wrappers for the units fees are quoted in, so a signature says
+//! whether it wants wei or gwei, a rate or a total. Conversions are exact
+//! integer arithmetic and error instead of wrapping.
+
+use std::fmt;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::{HawalaError, HawalaResult};
+use crate::wallet::{format_amount, parse_decimal_amount};
+
+/// Wei in one gwei
+pub const WEI_PER_GWEI: u128 = 1_000_000_000;
+
+/// Lamports in one SOL
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+const GWEI_DECIMALS: u8 = 9;
+const SOL_DECIMALS: u8 = 9;
+
+/// An amount of wei (EVM base unit). Serialized as a decimal string, since
+/// wei values overflow JSON numbers; deserializes from decimal or `0x` hex
+/// strings and from integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Wei(pub u128);
+
+/// An amount of gwei (10^9 wei), the unit gas prices are usually quoted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Gwei(pub u64);
+
+/// A UTXO fee rate in satoshis per virtual byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SatPerVb(pub u64);
+
+/// An absolute amount of satoshis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Sats(pub u64);
+
+/// An amount of lamports (10^-9 SOL)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Lamports(pub u64);
+
+impl Wei {
+    /// Parse a wei quantity given as decimal or `0x`-prefixed hex
+    pub fn parse(value: &str) -> HawalaResult<Self> {
+        let parsed = match value.strip_prefix("0x") {
+            Some(hex_digits) => u128::from_str_radix(hex_digits, 16),
+            None => value.parse(),
+        };
+        parsed
+            .map(Wei)
+            .map_err(|_| HawalaError::invalid_input(format!("Invalid wei amount: {}", value)))
+    }
+
+    /// Total for `gas` units at this price per gas
+    pub fn for_gas(self, gas: u64) -> HawalaResult<Wei> {
+        self.0
+            .checked_mul(u128::from(gas))
+            .map(Wei)
+            .ok_or_else(|| HawalaError::invalid_input("Fee overflows"))
+    }
+}
+
+/// Convert gwei to wei (always fits: `u64::MAX` gwei is below `u128::MAX` wei)
+pub fn gwei_to_wei(gwei: Gwei) -> Wei {
+    Wei(u128::from(gwei.0) * WEI_PER_GWEI)
+}
+
+/// Parse a decimal gwei amount such as `"1.5"` into wei
+pub fn parse_gwei(value: &str) -> HawalaResult<Wei> {
+    parse_decimal_amount(value, GWEI_DECIMALS).map(Wei)
+}
+
+/// Format wei as a decimal gwei string, e.g. `1500000000` → `"1.5"`
+pub fn wei_to_gwei(wei: Wei) -> String {
+    format_amount(wei.0, GWEI_DECIMALS)
+}
+
+/// Absolute fee for a transaction of `vsize` virtual bytes at `rate`
+pub fn sat_per_vb_to_total(rate: SatPerVb, vsize: u64) -> HawalaResult<Sats> {
+    rate.0
+        .checked_mul(vsize)
+        .map(Sats)
+        .ok_or_else(|| HawalaError::invalid_input(format!(
+            "Fee of {} for {} vB overflows",
+            rate, vsize
+        )))
+}
+
+/// Format lamports as a decimal SOL string, e.g. `1500000000` → `"1.5"`
+pub fn lamports_to_sol(lamports: Lamports) -> String {
+    format_amount(u128::from(lamports.0), SOL_DECIMALS)
+}
+
+/// Parse a decimal SOL amount into lamports
+pub fn sol_to_lamports(value: &str) -> HawalaResult<Lamports> {
+    let lamports = parse_decimal_amount(value, SOL_DECIMALS)?;
+    u64::try_from(lamports)
+        .map(Lamports)
+        .map_err(|_| HawalaError::invalid_input(format!("SOL amount too large: {}", value)))
+}
+
+impl Serialize for Wei {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Wei {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct WeiVisitor;
+
+        impl Visitor<'_> for WeiVisitor {
+            type Value = Wei;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a wei amount as a decimal or 0x-hex string, or an integer")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Wei, E> {
+                Wei::parse(value).map_err(|e| E::custom(e.message))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Wei, E> {
+                Ok(Wei(u128::from(value)))
+            }
+
+            fn visit_u128<E: de::Error>(self, value: u128) -> Result<Wei, E> {
+                Ok(Wei(value))
+            }
+        }
+
+        deserializer.deserialize_any(WeiVisitor)
+    }
+}
+
+impl fmt::Display for Wei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} wei", self.0)
+    }
+}
+
+impl fmt::Display for Gwei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} gwei", self.0)
+    }
+}
+
+impl fmt::Display for SatPerVb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} sats/vB", self.0)
+    }
+}
+
+impl fmt::Display for Sats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} sats", self.0)
+    }
+}
+
+impl fmt::Display for Lamports {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} lamports", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gwei_to_wei() {
+        assert_eq!(gwei_to_wei(Gwei(0)), Wei(0));
+        assert_eq!(gwei_to_wei(Gwei(30)), Wei(30_000_000_000));
+        assert_eq!(gwei_to_wei(Gwei(u64::MAX)), Wei(u128::from(u64::MAX) * 1_000_000_000));
+
+        assert_eq!(parse_gwei("1.5").unwrap(), Wei(1_500_000_000));
+        assert_eq!(parse_gwei("0.000000001").unwrap(), Wei(1));
+        // Sub-wei precision is an error, not a silent truncation
+        assert!(parse_gwei("0.0000000001").is_err());
+        assert_eq!(wei_to_gwei(Wei(1_500_000_000)), "1.5");
+        assert_eq!(wei_to_gwei(Wei(1)), "0.000000001");
+    }
+
+    #[test]
+    fn test_wei_parse_and_gas() {
+        assert_eq!(Wei::parse("21000").unwrap(), Wei(21_000));
+        assert_eq!(Wei::parse("0x5208").unwrap(), Wei(21_000));
+        assert!(Wei::parse("1.5").is_err());
+        assert!(Wei::parse("340282366920938463463374607431768211456").is_err());
+
+        assert_eq!(Wei(30_000_000_000).for_gas(21_000).unwrap(), Wei(630_000_000_000_000));
+        assert_eq!(Wei(u128::MAX).for_gas(1).unwrap(), Wei(u128::MAX));
+        assert!(Wei(u128::MAX).for_gas(2).is_err());
+    }
+
+    #[test]
+    fn test_wei_serde() {
+        assert_eq!(serde_json::to_value(Wei(1_500_000_000)).unwrap(), serde_json::json!("1500000000"));
+        assert_eq!(serde_json::from_str::<Wei>(r#""1500000000""#).unwrap(), Wei(1_500_000_000));
+        assert_eq!(serde_json::from_str::<Wei>(r#""0x5208""#).unwrap(), Wei(21_000));
+        assert_eq!(serde_json::from_str::<Wei>("21000").unwrap(), Wei(21_000));
+        assert!(serde_json::from_str::<Wei>(r#""1.5""#).is_err());
+        assert!(serde_json::from_str::<Wei>("-1").is_err());
+
+        let big = Wei(u128::from(u64::MAX) * 1_000);
+        let json = serde_json::to_string(&big).unwrap();
+        assert_eq!(serde_json::from_str::<Wei>(&json).unwrap(), big);
+    }
+
+    #[test]
+    fn test_sat_per_vb_to_total() {
+        assert_eq!(sat_per_vb_to_total(SatPerVb(2), 141).unwrap(), Sats(282));
+        assert_eq!(sat_per_vb_to_total(SatPerVb(0), 141).unwrap(), Sats(0));
+        assert_eq!(sat_per_vb_to_total(SatPerVb(u64::MAX), 1).unwrap(), Sats(u64::MAX));
+        assert!(sat_per_vb_to_total(SatPerVb(u64::MAX / 2 + 1), 2).is_err());
+    }
+
+    #[test]
+    fn test_lamports_sol() {
+        assert_eq!(lamports_to_sol(Lamports(LAMPORTS_PER_SOL)), "1");
+        assert_eq!(lamports_to_sol(Lamports(5000)), "0.000005");
+        assert_eq!(lamports_to_sol(Lamports(u64::MAX)), "18446744073.709551615");
+
+        assert_eq!(sol_to_lamports("1.5").unwrap(), Lamports(1_500_000_000));
+        assert_eq!(sol_to_lamports("18446744073.709551615").unwrap(), Lamports(u64::MAX));
+        assert!(sol_to_lamports("18446744073.709551616").is_err());
+    }
+
+    #[test]
+    fn test_display_units() {
+        assert_eq!(SatPerVb(5).to_string(), "5 sats/vB");
+        assert_eq!(Wei(21).to_string(), "21 wei");
+        assert_eq!(Lamports(5000).to_string(), "5000 lamports");
+    }
+}
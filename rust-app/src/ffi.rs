@@ -101,7 +101,13 @@ pub extern "C" fn hawala_health_check() -> *mut c_char {
 /// Generate a new wallet with mnemonic and keys for all chains
 /// 
 /// # Input
-/// None (empty string or "{}")
+/// Null, empty string, or:
+/// ```json
+/// { "strength": 256, "language": "japanese" }
+/// ```
+/// `strength` is optional (128/160/192/224/256 bits, default 128 = 12 words).
+/// `language` is optional (english, japanese, spanish, french, italian, korean,
+/// chinese_simplified, chinese_traditional, czech, portuguese; default english).
 /// 
 /// # Output
 /// ```json
@@ -114,8 +120,34 @@ pub extern "C" fn hawala_health_check() -> *mut c_char {
 /// }
 /// ```
 #[unsafe(no_mangle)]
-pub extern "C" fn hawala_generate_wallet() -> *mut c_char {
-    match wallet::create_new_wallet() {
+pub extern "C" fn hawala_generate_wallet(input: *const c_char) -> *mut c_char {
+    #[derive(serde::Deserialize, Default)]
+    struct GenerateRequest {
+        #[serde(default)]
+        strength: Option<usize>,
+        #[serde(default)]
+        language: wallet::MnemonicLanguage,
+    }
+
+    let request = if input.is_null() {
+        GenerateRequest::default()
+    } else {
+        let json_str = match parse_input(input) {
+            Ok(s) => s,
+            Err(ptr) => return ptr,
+        };
+        if json_str.trim().is_empty() {
+            GenerateRequest::default()
+        } else {
+            match serde_json::from_str(json_str) {
+                Ok(r) => r,
+                Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+            }
+        }
+    };
+
+    let strength = request.strength.unwrap_or(wallet::DEFAULT_MNEMONIC_STRENGTH);
+    match wallet::create_new_wallet_in(strength, request.language) {
         Ok((mnemonic, keys)) => {
             success_response(WalletResponse { mnemonic, keys })
         }
@@ -127,8 +159,9 @@ pub extern "C" fn hawala_generate_wallet() -> *mut c_char {
 /// 
 /// # Input
 /// ```json
-/// { "mnemonic": "word1 word2 ...", "passphrase": "" }
+/// { "mnemonic": "word1 word2 ...", "passphrase": "", "language": null }
 /// ```
+/// `language` is optional; the wordlist is detected from the words when omitted.
 /// 
 /// # Output
 /// ```json
@@ -149,6 +182,8 @@ pub extern "C" fn hawala_restore_wallet(input: *const c_char) -> *mut c_char {
         mnemonic: String,
         #[serde(default)]
         passphrase: String,
+        #[serde(default)]
+        language: Option<wallet::MnemonicLanguage>,
     }
 
     let request: RestoreRequest = match serde_json::from_str(json_str) {
@@ -156,22 +191,166 @@ pub extern "C" fn hawala_restore_wallet(input: *const c_char) -> *mut c_char {
         Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
     };
 
-    match wallet::restore_from_mnemonic_with_passphrase(&request.mnemonic, &request.passphrase) {
+    match wallet::restore_from_mnemonic_in(&request.mnemonic, &request.passphrase, request.language) {
         Ok(keys) => success_response(keys),
         Err(e) => error_response(e),
     }
 }
 
+/// Derive wallet keys at a BIP-44 account and address index
+/// 
+/// Only chains actually derived at `account`/`index` are returned: the
+/// BIP-44 chains, plus Solana at `m/44'/501'/index'/0'` for account 0.
+/// 
+/// # Input
+/// ```json
+/// { "mnemonic": "word1 word2 ...", "passphrase": "", "account": 0, "index": 1 }
+/// ```
+/// 
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "account": 0,
+///     "index": 1,
+///     "keys": { "bitcoin": {...}, "ethereum": {...}, "solana": {...}, ... }
+///   }
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_derive_account(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct DeriveAccountRequest {
+        mnemonic: String,
+        #[serde(default)]
+        passphrase: String,
+        #[serde(default)]
+        account: u32,
+        #[serde(default)]
+        index: u32,
+    }
+
+    let request: DeriveAccountRequest = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match wallet::restore_account(&request.mnemonic, &request.passphrase, request.account, request.index) {
+        Ok(keys) => success_response(serde_json::json!({
+            "account": request.account,
+            "index": request.index,
+            "keys": keys
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Discover used addresses with BIP-44 gap-limit scanning
+/// 
+/// `used_addresses` stands in for a chain lookup: an address counts as used
+/// when it appears in the list (case-insensitively for EVM chains). Provide
+/// either `mnemonic` or an account-level `xpub`.
+/// 
+/// # Input
+/// ```json
+/// {
+///   "mnemonic": "word1 word2 ...",
+///   "passphrase": "",
+///   "xpub": null,
+///   "chain": "bitcoin",
+///   "gap_limit": 20,
+///   "used_addresses": ["bc1q..."]
+/// }
+/// ```
+///
+/// `gap_limit` defaults to 20 and is capped at 1000 (`MAX_DERIVED_ADDRESSES`).
+/// 
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "used": [{ "address": "bc1q...", "path": "0/0", "change": false, "index": 0 }],
+///     "next_receive_index": 1,
+///     "next_change_index": 0
+///   }
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_scan_addresses(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct ScanRequest {
+        #[serde(default)]
+        mnemonic: Option<String>,
+        #[serde(default)]
+        passphrase: String,
+        #[serde(default)]
+        xpub: Option<String>,
+        chain: Chain,
+        #[serde(default = "default_gap_limit")]
+        gap_limit: u32,
+        #[serde(default)]
+        used_addresses: Vec<String>,
+    }
+
+    fn default_gap_limit() -> u32 {
+        wallet::DEFAULT_GAP_LIMIT
+    }
+
+    let request: ScanRequest = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    // EVM addresses may come back EIP-55 checksummed or lowercase
+    let normalize = |address: &str| if request.chain.is_evm() { address.to_lowercase() } else { address.to_string() };
+    let used: std::collections::HashSet<String> =
+        request.used_addresses.iter().map(|a| normalize(a)).collect();
+    let check_used = |address: &str| used.contains(&normalize(address));
+
+    let result = match (&request.mnemonic, &request.xpub) {
+        (Some(phrase), None) => {
+            let mnemonic = match bip39::Mnemonic::parse(phrase) {
+                Ok(m) => m,
+                Err(e) => return error_response(HawalaError::new(ErrorCode::InvalidMnemonic, format!("Invalid mnemonic: {}", e))),
+            };
+            let seed = zeroize::Zeroizing::new(mnemonic.to_seed(&request.passphrase));
+            wallet::scan_addresses(wallet::ScanSource::Seed(seed.as_ref()), request.chain, request.gap_limit, check_used)
+        }
+        (None, Some(xpub)) => {
+            wallet::scan_addresses(wallet::ScanSource::Xpub(xpub), request.chain, request.gap_limit, check_used)
+        }
+        _ => return error_response(HawalaError::invalid_input("Provide exactly one of mnemonic or xpub")),
+    };
+
+    match result {
+        Ok(scan) => success_response(scan),
+        Err(e) => error_response(e),
+    }
+}
+
 /// Validate a mnemonic phrase
 /// 
 /// # Input
 /// ```json
-/// { "mnemonic": "word1 word2 ..." }
+/// { "mnemonic": "word1 word2 ...", "language": null }
 /// ```
+/// `language` is optional; any supported wordlist is accepted when omitted.
 /// 
 /// # Output
 /// ```json
-/// { "success": true, "data": { "valid": true } }
+/// { "success": true, "data": { "valid": true, "language": "english" } }
 /// ```
 #[unsafe(no_mangle)]
 pub extern "C" fn hawala_validate_mnemonic(input: *const c_char) -> *mut c_char {
@@ -183,11 +362,14 @@ pub extern "C" fn hawala_validate_mnemonic(input: *const c_char) -> *mut c_char
     #[derive(serde::Deserialize)]
     struct ValidateRequest {
         mnemonic: String,
+        #[serde(default)]
+        language: Option<wallet::MnemonicLanguage>,
     }
 
     #[derive(serde::Serialize)]
     struct ValidateResponse {
         valid: bool,
+        language: Option<wallet::MnemonicLanguage>,
     }
 
     let request: ValidateRequest = match serde_json::from_str(json_str) {
@@ -195,8 +377,131 @@ pub extern "C" fn hawala_validate_mnemonic(input: *const c_char) -> *mut c_char
         Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
     };
 
-    let valid = wallet::validate_mnemonic(&request.mnemonic);
-    success_response(ValidateResponse { valid })
+    let language = match request.language {
+        Some(language) => Some(language).filter(|l| wallet::is_valid_mnemonic_in(&request.mnemonic, *l)),
+        None => wallet::detect_mnemonic_language(&request.mnemonic),
+    };
+    success_response(ValidateResponse { valid: language.is_some(), language })
+}
+
+/// Report the quality of a mnemonic phrase
+/// 
+/// # Input
+/// ```json
+/// { "mnemonic": "word1 word2 ..." }
+/// ```
+/// 
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "valid": false, "word_count": 12, "bits_of_entropy": 128, "checksum_ok": false,
+///     "all_words_in_wordlist": false, "duplicate_words": [], "unknown_words": ["abandn"],
+///     "language": "english", "warnings": []
+///   }
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_assess_mnemonic(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct AssessRequest {
+        mnemonic: String,
+    }
+
+    let request: AssessRequest = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    success_response(wallet::assess_mnemonic(&request.mnemonic))
+}
+
+/// Import a WIF private key
+/// 
+/// Bitcoin mainnet, Litecoin mainnet and testnet keys are accepted. A WIF
+/// doesn't record which script it was used with, so the P2PKH, P2SH-P2WPKH
+/// and P2WPKH addresses are all returned; `address` is the P2WPKH one for
+/// compressed keys and P2PKH for uncompressed ones.
+/// 
+/// # Input
+/// ```json
+/// { "wif": "Kw..." }
+/// ```
+/// 
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "network": "bitcoin", "compressed": true, "private_key": "...", "address": "bc1q...",
+///     "p2pkh_address": "1...", "p2sh_p2wpkh_address": "3...", "p2wpkh_address": "bc1q..."
+///   }
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_import_wif(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct ImportWifRequest {
+        wif: String,
+    }
+
+    let request: ImportWifRequest = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match wallet::import_wif(&request.wif) {
+        Ok(key) => success_response(key),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Import a raw EVM private key
+/// 
+/// # Input
+/// ```json
+/// { "private_key": "0x..." }
+/// ```
+/// 
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": { "private_key": "...", "public_key": "...", "checksummed_address": "0x..." }
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_import_evm_key(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct ImportEvmKeyRequest {
+        private_key: String,
+    }
+
+    let request: ImportEvmKeyRequest = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match wallet::import_evm_key(&request.private_key) {
+        Ok(key) => success_response(key),
+        Err(e) => error_response(e),
+    }
 }
 
 /// Derive address from private key
@@ -378,6 +683,9 @@ pub extern "C" fn hawala_prepare_transaction(input: *const c_char) -> *mut c_cha
         Chain::Xrp | Chain::XrpTestnet => {
             crate::tx::prepare_xrp_transaction(&request)
         }
+        Chain::Tezos => {
+            crate::tx::prepare_tezos_transaction(&request)
+        }
         Chain::Monero => {
             error_response(HawalaError::new(ErrorCode::NotImplemented, "Monero transactions not yet supported"))
         }
@@ -393,6 +701,19 @@ pub extern "C" fn hawala_prepare_transaction(input: *const c_char) -> *mut c_cha
 }
 
 /// Sign a prepared transaction
+///
+/// Bitcoin requests may include `"utxos": [{ "txid", "vout", "value" }]` to
+/// spend exactly those outputs; when omitted they are fetched for the sender.
+///
+/// To spend UTXOs of different script types (P2WPKH, P2SH-P2WPKH, P2TR)
+/// together, pass explicit inputs and outputs instead:
+/// ```json
+/// {
+///   "chain": "bitcoin",
+///   "inputs": [{ "txid": "...", "vout": 0, "value": 40000, "script_pubkey": "0014...", "private_key": "K..." }],
+///   "outputs": [{ "address": "bc1q...", "value": 38000 }]
+/// }
+/// ```
 #[unsafe(no_mangle)]
 pub extern "C" fn hawala_sign_transaction(input: *const c_char) -> *mut c_char {
     let json_str = match parse_input(input) {
@@ -409,14 +730,32 @@ pub extern "C" fn hawala_sign_transaction(input: *const c_char) -> *mut c_char {
             .ok_or_else(|| HawalaError::invalid_input("Missing chain"))?;
         
         match chain_str.to_lowercase().as_str() {
+            "bitcoin" | "bitcoin_testnet" if v["inputs"].is_array() => {
+                let params = crate::tx::BitcoinMultiInputSignParams {
+                    chain: if chain_str.contains("testnet") { Chain::BitcoinTestnet } else { Chain::Bitcoin },
+                    inputs: serde_json::from_value(v["inputs"].clone())
+                        .map_err(|e| HawalaError::invalid_input(format!("Invalid inputs: {}", e)))?,
+                    outputs: serde_json::from_value(v["outputs"].clone())
+                        .map_err(|e| HawalaError::invalid_input(format!("Invalid outputs: {}", e)))?,
+                };
+                crate::tx::sign_bitcoin_multi_input(&params)
+            }
             "bitcoin" | "bitcoin_testnet" => {
+                // Without UTXOs the signer fetches them for the sender address
+                let utxos = match &v["utxos"] {
+                    serde_json::Value::Null => None,
+                    utxos => Some(
+                        serde_json::from_value::<Vec<crate::tx::UtxoInput>>(utxos.clone())
+                            .map_err(|e| HawalaError::invalid_input(format!("Invalid utxos: {}", e)))?,
+                    ),
+                };
                 let params = crate::tx::BitcoinSignParams {
                     chain: if chain_str.contains("testnet") { Chain::BitcoinTestnet } else { Chain::Bitcoin },
                     recipient: v["recipient"].as_str().unwrap_or_default().to_string(),
                     amount_sats: v["amount_sats"].as_u64().unwrap_or(0),
                     fee_rate_sats_per_vbyte: v["fee_rate"].as_u64().unwrap_or(1),
                     sender_wif: v["sender_wif"].as_str().unwrap_or_default().to_string(),
-                    utxos: None, // Would parse from v["utxos"] if provided
+                    utxos,
                 };
                 crate::tx::sign_bitcoin_transaction(&params)
             }
@@ -438,7 +777,10 @@ pub extern "C" fn hawala_sign_transaction(input: *const c_char) -> *mut c_char {
             "solana" | "solana_devnet" => {
                 let params = crate::tx::SolanaSignParams {
                     recipient: v["recipient"].as_str().unwrap_or_default().to_string(),
-                    amount_sol: v["amount_sol"].as_f64().unwrap_or(0.0),
+                    // JSON numbers print as "1e-9" and lose exactness, so only strings are accepted
+                    amount_sol: v["amount_sol"].as_str()
+                        .ok_or_else(|| HawalaError::invalid_input("amount_sol must be a decimal string, e.g. \"0.5\""))?
+                        .to_string(),
                     recent_blockhash: v["recent_blockhash"].as_str().unwrap_or_default().to_string(),
                     sender_base58: v["sender_key"].as_str().unwrap_or_default().to_string(),
                 };
@@ -591,12 +933,67 @@ pub extern "C" fn hawala_cancel_transaction(input: *const c_char) -> *mut c_char
     }
 }
 
+/// Build and sign a CPFP child for a stuck Bitcoin transaction
+///
+/// # Input
+/// ```json
+/// {
+///   "parent_txid": "abc...",
+///   "parent_raw_tx": "0200...",
+///   "parent_fee": 141,
+///   "output_index": 1,
+///   "private_key_wif": "K...",
+///   "target_fee_rate": 25.0,
+///   "destination": "bc1q..."
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_build_cpfp(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct CpfpRequest {
+        parent_txid: String,
+        parent_raw_tx: String,
+        parent_fee: u64,
+        output_index: u32,
+        private_key_wif: String,
+        target_fee_rate: f64,
+        destination: String,
+    }
+
+    let request: CpfpRequest = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    // vsize is recomputed from the raw transaction
+    let parent = crate::cpfp::StuckTransaction::new(&request.parent_txid, &request.parent_raw_tx, 0, request.parent_fee);
+
+    match crate::tx::build_cpfp_child(
+        &parent,
+        request.output_index,
+        &request.private_key_wif,
+        request.target_fee_rate,
+        &request.destination,
+    ) {
+        Ok(child) => success_response(child),
+        Err(e) => error_response(e),
+    }
+}
+
 // =============================================================================
 // Fee Operations
 // =============================================================================
 
 /// Get fee estimates for a chain
 /// 
+/// Bitcoin rates are mempool.space's recommended tiers (fastest, half hour,
+/// hour, economy, minimum), reused for 60 seconds.
+/// 
 /// # Input
 /// ```json
 /// { "chain": "bitcoin" }
@@ -892,8 +1289,17 @@ pub extern "C" fn hawala_get_confirmations(input: *const c_char) -> *mut c_char
 /// 
 /// # Input
 /// ```json
-/// { "txid": "abc...", "chain": "bitcoin" }
+/// {
+///   "txid": "abc...",
+///   "chain": "bitcoin",
+///   "inputs": [{ "txid": "def...", "vout": 0 }]
+/// }
 /// ```
+/// `inputs` (optional) are the outpoints the transaction spends; with them a
+/// fee-bumped transaction is reported as `{ "replaced": { "by": "<txid>" } }`
+/// instead of staying `"pending"`. On EVM chains, `from` and `nonce` do the
+/// same, reporting `{ "replaced": { "by": null } }` once another transaction
+/// has used the nonce.
 #[unsafe(no_mangle)]
 pub extern "C" fn hawala_get_tx_status(input: *const c_char) -> *mut c_char {
     let json_str = match parse_input(input) {
@@ -901,10 +1307,20 @@ pub extern "C" fn hawala_get_tx_status(input: *const c_char) -> *mut c_char {
         Err(ptr) => return ptr,
     };
 
+    #[derive(serde::Deserialize)]
+    struct Outpoint {
+        txid: String,
+        vout: u32,
+    }
+
     #[derive(serde::Deserialize)]
     struct StatusRequest {
         txid: String,
         chain: Chain,
+        #[serde(default)]
+        inputs: Vec<Outpoint>,
+        from: Option<String>,
+        nonce: Option<u64>,
     }
 
     let request: StatusRequest = match serde_json::from_str(json_str) {
@@ -912,7 +1328,15 @@ pub extern "C" fn hawala_get_tx_status(input: *const c_char) -> *mut c_char {
         Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
     };
 
-    match crate::tx::get_transaction_status(&request.txid, request.chain) {
+    let spends = match (request.from, request.nonce) {
+        (Some(from), Some(nonce)) if request.chain.is_evm() => Some(crate::tx::TxConflictKey::Nonce { from, nonce }),
+        _ if !request.inputs.is_empty() => Some(crate::tx::TxConflictKey::Inputs {
+            outpoints: request.inputs.into_iter().map(|o| (o.txid, o.vout)).collect(),
+            fee: None,
+        }),
+        _ => None,
+    };
+    match crate::tx::get_transaction_status(&request.txid, request.chain, spends.as_ref()) {
         Ok(status) => success_response(status),
         Err(e) => error_response(e),
     }
@@ -981,6 +1405,16 @@ pub extern "C" fn hawala_fetch_balances(input: *const c_char) -> *mut c_char {
 /// ```json
 /// { "address": "0x...", "chain": "ethereum" }
 /// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "valid": false,
+///   "normalized": null,
+///   "network_hint": "bitcoin-testnet",
+///   "cross_chain_warning": "Testnet address used on mainnet"
+/// }
+/// ```
 #[unsafe(no_mangle)]
 pub extern "C" fn hawala_validate_address(input: *const c_char) -> *mut c_char {
     let json_str = match parse_input(input) {
@@ -998,6 +1432,8 @@ pub extern "C" fn hawala_validate_address(input: *const c_char) -> *mut c_char {
     struct ValidateAddressResponse {
         valid: bool,
         normalized: Option<String>,
+        network_hint: Option<Chain>,
+        cross_chain_warning: Option<String>,
     }
 
     let request: ValidateAddressRequest = match serde_json::from_str(json_str) {
@@ -1005,8 +1441,15 @@ pub extern "C" fn hawala_validate_address(input: *const c_char) -> *mut c_char {
         Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
     };
 
-    let (valid, normalized) = crate::wallet::validate_address(&request.address, request.chain);
-    success_response(ValidateAddressResponse { valid, normalized })
+    // The same validator drives the network check, so `valid` and the hint agree
+    let validation = crate::wallet::validate_address_detailed(&request.address, request.chain);
+    let network = crate::wallet::check_address_network(&request.address, request.chain);
+    success_response(ValidateAddressResponse {
+        valid: validation.is_valid,
+        normalized: validation.normalized,
+        network_hint: network.network_hint,
+        cross_chain_warning: network.cross_chain_warning,
+    })
 }
 
 // =============================================================================
@@ -1377,7 +1820,104 @@ pub extern "C" fn hawala_detect_nonce_gaps(input: *const c_char) -> *mut c_char
     }
 }
 
-// =============================================================================
+/// Release a reserved or pending nonce (transaction cancelled or dropped)
+/// 
+/// # Input
+/// ```json
+/// { "address": "0x...", "chain_id": 1, "nonce": 42 }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_release_nonce(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct ReleaseRequest {
+        address: String,
+        chain_id: u64,
+        nonce: u64,
+    }
+
+    let request: ReleaseRequest = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::wallet::nonce::release_nonce(&request.address, request.chain_id, request.nonce) {
+        Ok(was_tracked) => success_response(serde_json::json!({
+            "released": request.nonce,
+            "was_tracked": was_tracked
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Reconcile local nonce state with the node's pending transaction count
+/// 
+/// # Input
+/// ```json
+/// { "address": "0x...", "chain_id": 1, "onchain_nonce": 42 }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_resync_nonce(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct ResyncRequest {
+        address: String,
+        chain_id: u64,
+        onchain_nonce: u64,
+    }
+
+    let request: ResyncRequest = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::wallet::nonce::resync_from_chain(&request.address, request.chain_id, request.onchain_nonce) {
+        Ok(state) => success_response(state),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Persist nonce state to a JSON file
+/// 
+/// Call once at startup, before any other nonce function, so reservations
+/// and pending nonces survive app restarts.
+/// 
+/// # Input
+/// ```json
+/// { "path": "/path/to/app-support/nonces.json" }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_set_nonce_storage_path(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct StorageRequest {
+        path: String,
+    }
+
+    let request: StorageRequest = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::wallet::nonce::set_nonce_storage_path(&request.path) {
+        Ok(_) => success_response(serde_json::json!({"path": request.path})),
+        Err(e) => error_response(e),
+    }
+}
+
+// =============================================================================
 // Legacy FFI Compatibility
 // =============================================================================
 // These functions maintain compatibility with existing Swift code
@@ -2919,6 +3459,82 @@ pub extern "C" fn hawala_eip7702_sign_authorization(input: *const c_char) -> *mu
     }))
 }
 
+/// Sign an EIP-7702 revocation
+/// 
+/// Authorizes delegation to the zero address, which clears any existing
+/// delegation on the account.
+/// 
+/// # Input
+/// ```json
+/// {
+///   "chainId": 1,
+///   "nonce": 0,
+///   "privateKey": "0x..." // 32 bytes hex
+/// }
+/// ```
+/// 
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "chainId": 1,
+///   "address": "0x0000000000000000000000000000000000000000",
+///   "nonce": 0,
+///   "yParity": 0,
+///   "r": "0x...",
+///   "s": "0x...",
+///   "isRevocation": true
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_eip7702_revoke_authorization(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        #[serde(rename = "chainId")]
+        chain_id: u64,
+        nonce: u64,
+        #[serde(rename = "privateKey")]
+        private_key: String,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("Invalid JSON: {}", e))),
+    };
+
+    // Parse private key
+    let key_hex = request.private_key.trim_start_matches("0x");
+    let key_bytes = match hex::decode(key_hex) {
+        Ok(k) if k.len() == 32 => zeroize::Zeroizing::new(k),
+        Ok(_) => return error_response(HawalaError::invalid_input("Private key must be 32 bytes")),
+        Err(e) => return error_response(HawalaError::invalid_input(format!("Invalid private key: {}", e))),
+    };
+
+    let signed = match crate::eip7702::authorization::build_revocation(
+        request.chain_id,
+        request.nonce,
+        &key_bytes,
+    ) {
+        Ok(s) => s,
+        Err(e) => return error_response(HawalaError::crypto_error(format!("Revocation signing failed: {}", e))),
+    };
+
+    success_response(serde_json::json!({
+        "chainId": signed.chain_id,
+        "address": format!("0x{}", hex::encode(signed.address)),
+        "nonce": signed.nonce,
+        "yParity": signed.y_parity,
+        "r": format!("0x{}", hex::encode(signed.r)),
+        "s": format!("0x{}", hex::encode(signed.s)),
+        "isRevocation": crate::eip7702::authorization::is_revocation(&signed)
+    }))
+}
+
 /// Sign an EIP-7702 transaction
 /// 
 /// Creates and signs a complete EIP-7702 transaction (type 0x04).
@@ -3021,46 +3637,41 @@ pub extern "C" fn hawala_eip7702_sign_transaction(input: *const c_char) -> *mut
     }
 
     // Parse authorization list
-    for auth_item in &request.authorization_list {
-        let addr_hex = auth_item.address.trim_start_matches("0x");
-        let addr_bytes = match hex::decode(addr_hex) {
-            Ok(b) if b.len() == 20 => {
-                let mut arr = [0u8; 20];
-                arr.copy_from_slice(&b);
-                arr
-            }
-            _ => continue,
-        };
-
-        let r_hex = auth_item.r.trim_start_matches("0x");
-        let r_bytes = match hex::decode(r_hex) {
-            Ok(b) if b.len() == 32 => {
-                let mut arr = [0u8; 32];
-                arr.copy_from_slice(&b);
-                arr
-            }
-            _ => continue,
+    let mut auths = Vec::with_capacity(request.authorization_list.len());
+    for (i, auth_item) in request.authorization_list.iter().enumerate() {
+        let parse_fixed = |value: &str, len: usize| -> Option<Vec<u8>> {
+            hex::decode(value.trim_start_matches("0x")).ok().filter(|b| b.len() == len)
         };
 
-        let s_hex = auth_item.s.trim_start_matches("0x");
-        let s_bytes = match hex::decode(s_hex) {
-            Ok(b) if b.len() == 32 => {
-                let mut arr = [0u8; 32];
-                arr.copy_from_slice(&b);
-                arr
+        let (addr_bytes, r_bytes, s_bytes) = match (
+            parse_fixed(&auth_item.address, 20),
+            parse_fixed(&auth_item.r, 32),
+            parse_fixed(&auth_item.s, 32),
+        ) {
+            (Some(a), Some(r), Some(s)) => {
+                let mut addr = [0u8; 20];
+                let mut r_arr = [0u8; 32];
+                let mut s_arr = [0u8; 32];
+                addr.copy_from_slice(&a);
+                r_arr.copy_from_slice(&r);
+                s_arr.copy_from_slice(&s);
+                (addr, r_arr, s_arr)
             }
-            _ => continue,
+            _ => return error_response(HawalaError::invalid_input(format!("Malformed authorization at index {}", i))),
         };
 
-        let auth = crate::eip7702::Authorization::with_signature(
+        auths.push(crate::eip7702::Authorization::with_signature(
             auth_item.chain_id,
             addr_bytes,
             auth_item.nonce,
             auth_item.y_parity,
             r_bytes,
             s_bytes,
-        );
-        tx.authorization_list.push(auth);
+        ));
+    }
+
+    if let Err(e) = crate::eip7702::transaction::add_authorizations(&mut tx, auths) {
+        return error_response(HawalaError::invalid_input(e.to_string()));
     }
 
     // Parse private key
@@ -3273,6 +3884,68 @@ pub extern "C" fn hawala_get_bitcoin_sighashes(input: *const c_char) -> *mut c_c
     }
 }
 
+/// Generate sighashes for every input of a BIP-174 PSBT
+/// 
+/// # Input
+/// ```json
+/// { "psbt": "cHNidP8B..." }
+/// ```
+/// `psbt` is base64 (as exported by Sparrow, Core, hardware wallets) or hex.
+/// 
+/// # Output
+/// Same shape as `hawala_get_bitcoin_sighashes`.
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_get_psbt_sighashes(input: *const c_char) -> *mut c_char {
+    let input_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        psbt: String,
+    }
+
+    let request: Request = match serde_json::from_str(input_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("JSON parse error: {}", e))),
+    };
+
+    let psbt_bytes = match decode_psbt_string(&request.psbt) {
+        Ok(b) => b,
+        Err(e) => return error_response(e),
+    };
+
+    match crate::signing::preimage::sighashes_from_psbt(&psbt_bytes) {
+        Ok(hashes) => {
+            let result: Vec<_> = hashes.iter().map(|h| serde_json::json!({
+                "hash": h.hash_hex(),
+                "signer_id": h.signer_id,
+                "input_index": h.input_index,
+                "description": h.description,
+                "algorithm": format!("{:?}", h.algorithm)
+            })).collect();
+            success_response(serde_json::json!({ "hashes": result }))
+        }
+        Err(e) => error_response(HawalaError::crypto_error(format!("Sighash error: {}", e))),
+    }
+}
+
+/// Decode a PSBT given as base64 or hex
+fn decode_psbt_string(psbt: &str) -> Result<Vec<u8>, HawalaError> {
+    use base64::Engine;
+
+    let trimmed = psbt.trim();
+    // Hex PSBTs start with the magic bytes "psbt\xff"
+    if trimmed.starts_with("70736274ff") {
+        return hex::decode(trimmed)
+            .map_err(|e| HawalaError::invalid_input(format!("Invalid PSBT hex: {}", e)));
+    }
+    base64::engine::general_purpose::STANDARD
+        .decode(trimmed)
+        .map_err(|e| HawalaError::invalid_input(format!("Invalid PSBT base64: {}", e)))
+}
+
 /// Generate signing hash for Ethereum transaction
 /// 
 /// # Input
@@ -3376,6 +4049,35 @@ pub extern "C" fn hawala_get_solana_message_hash(input: *const c_char) -> *mut c
     }
 }
 
+/// Generate the signing hash for a NEAR transaction
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_get_near_signing_hash(input: *const c_char) -> *mut c_char {
+    let input_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        transaction: crate::signing::preimage::near::UnsignedNearTransaction,
+    }
+
+    let request: Request = match serde_json::from_str(input_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("JSON parse error: {}", e))),
+    };
+
+    match crate::signing::preimage::get_near_signing_hash(&request.transaction) {
+        Ok(hash) => success_response(serde_json::json!({
+            "hash": hash.hash_hex(),
+            "signer_id": hash.signer_id,
+            "description": hash.description,
+            "algorithm": format!("{:?}", hash.algorithm)
+        })),
+        Err(e) => error_response(HawalaError::crypto_error(format!("Hash error: {}", e))),
+    }
+}
+
 /// Compile a Bitcoin transaction with external signatures
 #[unsafe(no_mangle)]
 pub extern "C" fn hawala_compile_bitcoin_transaction(input: *const c_char) -> *mut c_char {
@@ -3406,9 +4108,21 @@ pub extern "C" fn hawala_compile_bitcoin_transaction(input: *const c_char) -> *m
     }
 }
 
-/// Compile an Ethereum transaction with external signature
+/// Finalize a PSBT with external signatures and extract the signed transaction
+/// 
+/// # Input
+/// ```json
+/// {
+///   "psbt": "cHNidP8B...",
+///   "signatures": [{ "signature": [...], "public_key": [...], "input_index": 0 }]
+/// }
+/// ```
+/// Supports P2WPKH, P2SH-P2WPKH and P2TR key-path inputs.
+/// 
+/// # Output
+/// Same shape as `hawala_compile_bitcoin_transaction`.
 #[unsafe(no_mangle)]
-pub extern "C" fn hawala_compile_ethereum_transaction(input: *const c_char) -> *mut c_char {
+pub extern "C" fn hawala_finalize_psbt(input: *const c_char) -> *mut c_char {
     let input_str = match parse_input(input) {
         Ok(s) => s,
         Err(e) => return e,
@@ -3416,8 +4130,8 @@ pub extern "C" fn hawala_compile_ethereum_transaction(input: *const c_char) -> *
 
     #[derive(serde::Deserialize)]
     struct Request {
-        transaction: crate::signing::preimage::ethereum::UnsignedEthereumTransaction,
-        signature: crate::signing::preimage::ExternalSignature,
+        psbt: String,
+        signatures: Vec<crate::signing::preimage::ExternalSignature>,
     }
 
     let request: Request = match serde_json::from_str(input_str) {
@@ -3425,19 +4139,25 @@ pub extern "C" fn hawala_compile_ethereum_transaction(input: *const c_char) -> *
         Err(e) => return error_response(HawalaError::invalid_input(format!("JSON parse error: {}", e))),
     };
 
-    match crate::signing::compile_ethereum_transaction(&request.transaction, &request.signature) {
+    let psbt_bytes = match decode_psbt_string(&request.psbt) {
+        Ok(b) => b,
+        Err(e) => return error_response(e),
+    };
+
+    match crate::signing::finalize_psbt(&psbt_bytes, &request.signatures) {
         Ok(compiled) => success_response(serde_json::json!({
             "raw_tx": format!("0x{}", hex::encode(&compiled.raw_tx)),
-            "tx_hash": format!("0x{}", hex::encode(compiled.tx_hash)),
-            "from": format!("0x{}", hex::encode(compiled.from))
+            "txid": format!("0x{}", hex::encode(compiled.txid)),
+            "wtxid": compiled.wtxid.map(|w| format!("0x{}", hex::encode(w))),
+            "vsize": compiled.vsize
         })),
-        Err(e) => error_response(HawalaError::crypto_error(format!("Compile error: {}", e))),
+        Err(e) => error_response(HawalaError::crypto_error(format!("Finalize error: {}", e))),
     }
 }
 
-/// Compile a Cosmos transaction with external signature
+/// Compile an Ethereum transaction with external signature
 #[unsafe(no_mangle)]
-pub extern "C" fn hawala_compile_cosmos_transaction(input: *const c_char) -> *mut c_char {
+pub extern "C" fn hawala_compile_ethereum_transaction(input: *const c_char) -> *mut c_char {
     let input_str = match parse_input(input) {
         Ok(s) => s,
         Err(e) => return e,
@@ -3445,7 +4165,7 @@ pub extern "C" fn hawala_compile_cosmos_transaction(input: *const c_char) -> *mu
 
     #[derive(serde::Deserialize)]
     struct Request {
-        transaction: crate::signing::preimage::cosmos::UnsignedCosmosTransaction,
+        transaction: crate::signing::preimage::ethereum::UnsignedEthereumTransaction,
         signature: crate::signing::preimage::ExternalSignature,
     }
 
@@ -3454,10 +4174,39 @@ pub extern "C" fn hawala_compile_cosmos_transaction(input: *const c_char) -> *mu
         Err(e) => return error_response(HawalaError::invalid_input(format!("JSON parse error: {}", e))),
     };
 
-    match crate::signing::compile_cosmos_transaction(&request.transaction, &request.signature) {
+    match crate::signing::compile_ethereum_transaction(&request.transaction, &request.signature) {
         Ok(compiled) => success_response(serde_json::json!({
             "raw_tx": format!("0x{}", hex::encode(&compiled.raw_tx)),
-            "tx_hash": format!("0x{}", hex::encode(compiled.tx_hash))
+            "tx_hash": format!("0x{}", hex::encode(compiled.tx_hash)),
+            "from": format!("0x{}", hex::encode(compiled.from))
+        })),
+        Err(e) => error_response(HawalaError::crypto_error(format!("Compile error: {}", e))),
+    }
+}
+
+/// Compile a Cosmos transaction with external signature
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_compile_cosmos_transaction(input: *const c_char) -> *mut c_char {
+    let input_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        transaction: crate::signing::preimage::cosmos::UnsignedCosmosTransaction,
+        signature: crate::signing::preimage::ExternalSignature,
+    }
+
+    let request: Request = match serde_json::from_str(input_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("JSON parse error: {}", e))),
+    };
+
+    match crate::signing::compile_cosmos_transaction(&request.transaction, &request.signature) {
+        Ok(compiled) => success_response(serde_json::json!({
+            "raw_tx": format!("0x{}", hex::encode(&compiled.raw_tx)),
+            "tx_hash": format!("0x{}", hex::encode(compiled.tx_hash))
         })),
         Err(e) => error_response(HawalaError::crypto_error(format!("Compile error: {}", e))),
     }
@@ -3491,6 +4240,34 @@ pub extern "C" fn hawala_compile_solana_transaction(input: *const c_char) -> *mu
     }
 }
 
+/// Compile a NEAR transaction with an external ed25519 signature
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_compile_near_transaction(input: *const c_char) -> *mut c_char {
+    let input_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        transaction: crate::signing::preimage::near::UnsignedNearTransaction,
+        signature: crate::signing::preimage::ExternalSignature,
+    }
+
+    let request: Request = match serde_json::from_str(input_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("JSON parse error: {}", e))),
+    };
+
+    match crate::signing::compile_near_transaction(&request.transaction, &request.signature) {
+        Ok(compiled) => success_response(serde_json::json!({
+            "raw_tx": format!("0x{}", hex::encode(&compiled.raw_tx)),
+            "tx_hash": bs58::encode(compiled.tx_hash).into_string()
+        })),
+        Err(e) => error_response(HawalaError::crypto_error(format!("Compile error: {}", e))),
+    }
+}
+
 // =============================================================================
 // BIP-340 Schnorr Signatures (Section 6: Bitcoin Taproot)
 // =============================================================================
@@ -3966,7 +4743,7 @@ pub extern "C" fn hawala_taproot_merkle_root(input: *const c_char) -> *mut c_cha
 /// # Input
 /// ```json
 /// {
-///   "curve": "secp256k1" | "ed25519" | "sr25519" | "secp256r1",
+///   "curve": "secp256k1" | "ed25519" | "sr25519" | "secp256r1" | "x25519",
 ///   "seed": "0x..." // 32-byte seed (hex)
 /// }
 /// ```
@@ -4021,7 +4798,7 @@ pub extern "C" fn hawala_curve_generate_keypair(input: *const c_char) -> *mut c_
             "public_key": format!("0x{}", hex::encode(&public_key)),
             "curve": curve_type.name()
         })),
-        Err(e) => error_response(HawalaError::crypto_error(format!("Keypair generation failed: {}", e))),
+        Err(e) => error_response(HawalaError::from(e)),
     }
 }
 
@@ -4030,7 +4807,7 @@ pub extern "C" fn hawala_curve_generate_keypair(input: *const c_char) -> *mut c_
 /// # Input
 /// ```json
 /// {
-///   "curve": "secp256k1" | "ed25519" | "sr25519" | "secp256r1",
+///   "curve": "secp256k1" | "ed25519" | "sr25519" | "secp256r1" | "x25519",
 ///   "private_key": "0x..." // hex-encoded private key
 /// }
 /// ```
@@ -4079,7 +4856,7 @@ pub extern "C" fn hawala_curve_public_key(input: *const c_char) -> *mut c_char {
             "public_key": format!("0x{}", hex::encode(&public_key)),
             "curve": curve_type.name()
         })),
-        Err(e) => error_response(HawalaError::crypto_error(format!("Public key derivation failed: {}", e))),
+        Err(e) => error_response(HawalaError::from(e)),
     }
 }
 
@@ -4147,7 +4924,7 @@ pub extern "C" fn hawala_curve_sign(input: *const c_char) -> *mut c_char {
             "public_key": format!("0x{}", hex::encode(&public_key)),
             "curve": curve_type.name()
         })),
-        Err(e) => error_response(HawalaError::crypto_error(format!("Signing failed: {}", e))),
+        Err(e) => error_response(HawalaError::from(e)),
     }
 }
 
@@ -4221,7 +4998,7 @@ pub extern "C" fn hawala_curve_verify(input: *const c_char) -> *mut c_char {
             "valid": valid,
             "curve": curve_type.name()
         })),
-        Err(e) => error_response(HawalaError::crypto_error(format!("Verification failed: {}", e))),
+        Err(e) => error_response(HawalaError::from(e)),
     }
 }
 
@@ -4230,7 +5007,7 @@ pub extern "C" fn hawala_curve_verify(input: *const c_char) -> *mut c_char {
 /// # Input
 /// ```json
 /// {
-///   "curve": "secp256k1" | "ed25519" | "sr25519" | "secp256r1"
+///   "curve": "secp256k1" | "ed25519" | "sr25519" | "secp256r1" | "x25519"
 /// }
 /// ```
 ///
@@ -4278,6 +5055,135 @@ pub extern "C" fn hawala_curve_info(input: *const c_char) -> *mut c_char {
     }))
 }
 
+/// Produce an sr25519 VRF output and proof (Substrate signing context)
+///
+/// # Input
+/// ```json
+/// {
+///   "private_key": "0x...", // 32-byte mini secret or 64-byte secret key
+///   "input": "0x..."        // hex-encoded VRF input
+/// }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "output": "0x...", // 32-byte VRF pre-output
+///     "proof": "0x...",  // 64-byte VRF proof
+///     "public_key": "0x..."
+///   }
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_sr25519_vrf_sign(input: *const c_char) -> *mut c_char {
+    use crate::crypto::curves::{EllipticCurve, Sr25519Curve};
+
+    let input_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        private_key: String,
+        input: String,
+    }
+
+    let request: Request = match serde_json::from_str(input_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("JSON parse error: {}", e))),
+    };
+
+    let pk_str = request.private_key.strip_prefix("0x").unwrap_or(&request.private_key);
+    let private_key = match hex::decode(pk_str) {
+        Ok(b) => b,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("Invalid private key hex: {}", e))),
+    };
+
+    let vrf_input_str = request.input.strip_prefix("0x").unwrap_or(&request.input);
+    let vrf_input = match hex::decode(vrf_input_str) {
+        Ok(b) => b,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("Invalid input hex: {}", e))),
+    };
+
+    let public_key = match Sr25519Curve::public_key_from_private(&private_key) {
+        Ok(pk) => pk,
+        Err(e) => return error_response(HawalaError::crypto_error(format!("Public key derivation failed: {}", e))),
+    };
+
+    match Sr25519Curve::vrf_sign(&private_key, &vrf_input) {
+        Ok((output, proof)) => success_response(serde_json::json!({
+            "output": format!("0x{}", hex::encode(output.0)),
+            "proof": format!("0x{}", hex::encode(proof.0)),
+            "public_key": format!("0x{}", hex::encode(public_key))
+        })),
+        Err(e) => error_response(HawalaError::crypto_error(format!("VRF signing failed: {}", e))),
+    }
+}
+
+/// Verify an sr25519 VRF output and proof
+///
+/// # Input
+/// ```json
+/// {
+///   "public_key": "0x...",
+///   "input": "0x...",
+///   "output": "0x...",
+///   "proof": "0x..."
+/// }
+/// ```
+///
+/// # Output
+/// ```json
+/// { "success": true, "data": { "valid": true } }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_sr25519_vrf_verify(input: *const c_char) -> *mut c_char {
+    use crate::crypto::curves::Sr25519Curve;
+
+    let input_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        public_key: String,
+        input: String,
+        output: String,
+        proof: String,
+    }
+
+    let request: Request = match serde_json::from_str(input_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("JSON parse error: {}", e))),
+    };
+
+    let decode = |field: &str, value: &str| {
+        hex::decode(value.strip_prefix("0x").unwrap_or(value))
+            .map_err(|e| HawalaError::invalid_input(format!("Invalid {} hex: {}", field, e)))
+    };
+
+    let (public_key, vrf_input, output, proof) = match (
+        decode("public key", &request.public_key),
+        decode("input", &request.input),
+        decode("output", &request.output),
+        decode("proof", &request.proof),
+    ) {
+        (Ok(pk), Ok(i), Ok(o), Ok(p)) => (pk, i, o, p),
+        (Err(e), _, _, _) | (_, Err(e), _, _) | (_, _, Err(e), _) | (_, _, _, Err(e)) => {
+            return error_response(e)
+        }
+    };
+
+    match Sr25519Curve::vrf_verify(&public_key, &vrf_input, &output, &proof) {
+        Ok(valid) => success_response(serde_json::json!({ "valid": valid })),
+        Err(e) => error_response(HawalaError::crypto_error(format!("VRF verification failed: {}", e))),
+    }
+}
+
 // =============================================================================
 // QR Code Encoding/Decoding for Air-Gapped Signing
 // =============================================================================
@@ -4440,6 +5346,9 @@ pub extern "C" fn hawala_qr_encode_simple(input: *const c_char) -> *mut c_char {
 ///   }
 /// }
 /// ```
+///
+/// A part of a multi-part UR returns `"complete": false` with `progress`,
+/// `estimated_remaining_frames`, `frames_per_second_hint` and `eta_seconds`.
 #[unsafe(no_mangle)]
 pub extern "C" fn hawala_qr_decode_ur(input: *const c_char) -> *mut c_char {
     let input_str = match parse_input(input) {
@@ -4480,9 +5389,14 @@ pub extern "C" fn hawala_qr_decode_ur(input: *const c_char) -> *mut c_char {
                             Err(e) => error_response(HawalaError::parse_error(format!("Failed to extract result: {}", e))),
                         }
                     } else {
+                        let remaining = decoder.estimated_remaining_frames();
+                        let fps = decoder.frames_per_second_hint();
                         success_response(serde_json::json!({
                             "complete": false,
                             "progress": decoder.progress(),
+                            "estimated_remaining_frames": remaining,
+                            "frames_per_second_hint": fps,
+                            "eta_seconds": remaining.zip(fps).map(|(frames, fps)| frames as f32 / fps),
                             "message": "Submit more parts to complete"
                         }))
                     }
@@ -4609,17 +5523,21 @@ pub extern "C" fn hawala_derive_key(input: *const c_char) -> *mut c_char {
         Ok(b) => b,
         Err(e) => return error_response(HawalaError::invalid_input(format!("Invalid seed hex: {}", e))),
     };
+    let path = match crate::wallet::DerivationPath::parse(&request.path) {
+        Ok(p) => p,
+        Err(e) => return error_response(e),
+    };
 
     // Use existing wallet derivation logic based on curve
     match request.curve.to_lowercase().as_str() {
         "secp256k1" => {
             // BIP-32 derivation
-            match derive_secp256k1_key(&seed, &request.path) {
+            match derive_secp256k1_key(&seed, &path) {
                 Ok((private_key, public_key, chain_code)) => success_response(serde_json::json!({
                     "private_key": format!("0x{}", hex::encode(&private_key)),
                     "public_key": format!("0x{}", hex::encode(&public_key)),
                     "chain_code": format!("0x{}", hex::encode(&chain_code)),
-                    "path": request.path,
+                    "path": path.to_string(),
                     "curve": "secp256k1"
                 })),
                 Err(e) => error_response(HawalaError::crypto_error(format!("Derivation failed: {}", e))),
@@ -4627,15 +5545,16 @@ pub extern "C" fn hawala_derive_key(input: *const c_char) -> *mut c_char {
         }
         "ed25519" => {
             // SLIP-0010 derivation
-            match derive_ed25519_key(&seed, &request.path) {
+            match crate::wallet::derive_ed25519_key(&seed, &path) {
                 Ok((private_key, public_key, chain_code)) => success_response(serde_json::json!({
-                    "private_key": format!("0x{}", hex::encode(&private_key)),
-                    "public_key": format!("0x{}", hex::encode(&public_key)),
-                    "chain_code": format!("0x{}", hex::encode(&chain_code)),
-                    "path": request.path,
+                    "private_key": format!("0x{}", hex::encode(private_key)),
+                    "public_key": format!("0x{}", hex::encode(public_key)),
+                    "chain_code": format!("0x{}", hex::encode(chain_code)),
+                    "path": path.to_string(),
                     "curve": "ed25519"
                 })),
-                Err(e) => error_response(HawalaError::crypto_error(format!("Derivation failed: {}", e))),
+                // Already reads "Key derivation failed: ..."
+                Err(e) => error_response(HawalaError::crypto_error(e.to_string())),
             }
         }
         _ => error_response(HawalaError::invalid_input(format!("Unsupported curve for derivation: {}", request.curve))),
@@ -4646,7 +5565,7 @@ pub extern "C" fn hawala_derive_key(input: *const c_char) -> *mut c_char {
 type DerivedKeyResult = Result<(Vec<u8>, Vec<u8>, Vec<u8>), String>;
 
 // Helper for BIP-32 secp256k1 derivation
-fn derive_secp256k1_key(seed: &[u8], path: &str) -> DerivedKeyResult {
+fn derive_secp256k1_key(seed: &[u8], path: &crate::wallet::DerivationPath) -> DerivedKeyResult {
     use hmac::{Hmac, Mac};
     use sha2::Sha512;
     
@@ -4661,48 +5580,35 @@ fn derive_secp256k1_key(seed: &[u8], path: &str) -> DerivedKeyResult {
     let mut key = result[..32].to_vec();
     let mut chain_code = result[32..].to_vec();
     
-    // Parse and apply path
-    let path = path.trim_start_matches("m/");
-    if !path.is_empty() {
-        for component in path.split('/') {
-            let (index, hardened) = if component.ends_with('\'') || component.ends_with('h') {
-                let idx: u32 = component.trim_end_matches(['\'', 'h'])
-                    .parse()
-                    .map_err(|_| format!("Invalid path component: {}", component))?;
-                (idx | 0x80000000, true)
-            } else {
-                let idx: u32 = component.parse()
-                    .map_err(|_| format!("Invalid path component: {}", component))?;
-                (idx, false)
-            };
-            
-            let mut mac = HmacSha512::new_from_slice(&chain_code)
-                .map_err(|e| format!("HMAC error: {}", e))?;
-            
-            if hardened {
-                mac.update(&[0u8]);
-                mac.update(&key);
-            } else {
-                // Compute public key for non-hardened derivation
-                let secp = secp256k1::Secp256k1::new();
-                let secret_key = secp256k1::SecretKey::from_slice(&key)
-                    .map_err(|e| format!("Invalid key: {}", e))?;
-                let public_key = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
-                mac.update(&public_key.serialize());
-            }
-            
-            mac.update(&index.to_be_bytes());
-            let result = mac.finalize().into_bytes();
-            
-            // Add to parent key
-            let mut key_int = secp256k1::SecretKey::from_slice(&key)
+    // Apply path
+    for component in &path.components {
+        let (index, hardened) = (component.full_index(), component.hardened);
+        let mut mac = HmacSha512::new_from_slice(&chain_code)
+            .map_err(|e| format!("HMAC error: {}", e))?;
+        
+        if hardened {
+            mac.update(&[0u8]);
+            mac.update(&key);
+        } else {
+            // Compute public key for non-hardened derivation
+            let secp = secp256k1::Secp256k1::new();
+            let secret_key = secp256k1::SecretKey::from_slice(&key)
                 .map_err(|e| format!("Invalid key: {}", e))?;
-            key_int = key_int.add_tweak(&secp256k1::Scalar::from_be_bytes(result[..32].try_into().unwrap()).unwrap())
-                .map_err(|e| format!("Key tweak failed: {}", e))?;
-            
-            key = key_int.secret_bytes().to_vec();
-            chain_code = result[32..].to_vec();
+            let public_key = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+            mac.update(&public_key.serialize());
         }
+        
+        mac.update(&index.to_be_bytes());
+        let result = mac.finalize().into_bytes();
+        
+        // Add to parent key
+        let mut key_int = secp256k1::SecretKey::from_slice(&key)
+            .map_err(|e| format!("Invalid key: {}", e))?;
+        key_int = key_int.add_tweak(&secp256k1::Scalar::from_be_bytes(result[..32].try_into().unwrap()).unwrap())
+            .map_err(|e| format!("Key tweak failed: {}", e))?;
+        
+        key = key_int.secret_bytes().to_vec();
+        chain_code = result[32..].to_vec();
     }
     
     // Compute public key
@@ -4714,58 +5620,6 @@ fn derive_secp256k1_key(seed: &[u8], path: &str) -> DerivedKeyResult {
     Ok((key, public_key.serialize().to_vec(), chain_code))
 }
 
-// Helper for SLIP-0010 ed25519 derivation
-fn derive_ed25519_key(seed: &[u8], path: &str) -> DerivedKeyResult {
-    use hmac::{Hmac, Mac};
-    use sha2::Sha512;
-    
-    type HmacSha512 = Hmac<Sha512>;
-    
-    // Master key derivation (SLIP-0010 uses "ed25519 seed" for Ed25519)
-    let mut mac = HmacSha512::new_from_slice(b"ed25519 seed")
-        .map_err(|e| format!("HMAC error: {}", e))?;
-    mac.update(seed);
-    let result = mac.finalize().into_bytes();
-    
-    let mut key = result[..32].to_vec();
-    let mut chain_code = result[32..].to_vec();
-    
-    // Parse and apply path (Ed25519 only supports hardened derivation)
-    let path = path.trim_start_matches("m/");
-    if !path.is_empty() {
-        for component in path.split('/') {
-            let index: u32 = if component.ends_with('\'') || component.ends_with('h') {
-                let idx: u32 = component.trim_end_matches(['\'', 'h'])
-                    .parse()
-                    .map_err(|_| format!("Invalid path component: {}", component))?;
-                idx | 0x80000000
-            } else {
-                // Ed25519 SLIP-0010 requires all hardened
-                let idx: u32 = component.parse()
-                    .map_err(|_| format!("Invalid path component: {}", component))?;
-                idx | 0x80000000
-            };
-            
-            let mut mac = HmacSha512::new_from_slice(&chain_code)
-                .map_err(|e| format!("HMAC error: {}", e))?;
-            mac.update(&[0u8]);
-            mac.update(&key);
-            mac.update(&index.to_be_bytes());
-            let result = mac.finalize().into_bytes();
-            
-            key = result[..32].to_vec();
-            chain_code = result[32..].to_vec();
-        }
-    }
-    
-    // Compute public key
-    use ed25519_dalek::{SigningKey, VerifyingKey};
-    let signing_key = SigningKey::from_bytes(&key.clone().try_into().map_err(|_| "Invalid key length")?);
-    let verifying_key: VerifyingKey = (&signing_key).into();
-    
-    Ok((key, verifying_key.to_bytes().to_vec(), chain_code))
-}
-
 // =============================================================================
 // DEX Aggregator Operations
 // =============================================================================
@@ -5328,9 +6182,14 @@ pub extern "C" fn hawala_ibc_get_supported_chains(_input: *const c_char) -> *mut
 ///   "privateKey": "0x...",
 ///   "memo": "optional",
 ///   "accountNumber": 12345,
-///   "sequence": 0
+///   "sequence": 0,
+///   "gasLimit": 150000,  // optional, skips simulation (offline signing)
+///   "gasAdjustment": 1.3  // optional multiplier on simulated gas
 /// }
 /// ```
+///
+/// Without `gasLimit` the transaction is simulated on the source chain and
+/// the fee is the adjusted gas times the chain's minimum gas price.
 #[unsafe(no_mangle)]
 pub extern "C" fn hawala_ibc_sign_transfer(input: *const c_char) -> *mut c_char {
     let json_str = match parse_input(input) {
@@ -5354,6 +6213,8 @@ pub extern "C" fn hawala_ibc_sign_transfer(input: *const c_char) -> *mut c_char
         memo: Option<String>,
         account_number: u64,
         sequence: u64,
+        gas_limit: Option<u64>,
+        gas_adjustment: Option<f64>,
     }
     
     let request: SignRequest = match serde_json::from_str(json_str) {
@@ -5400,64 +6261,102 @@ pub extern "C" fn hawala_ibc_sign_transfer(input: *const c_char) -> *mut c_char
         )),
     };
     
-    // Sign the message using Cosmos signing
-    let sign_doc = serde_json::json!({
-        "chain_id": source_chain.chain_id(),
-        "account_number": request.account_number.to_string(),
-        "sequence": request.sequence.to_string(),
-        "fee": {
-            "amount": [{"denom": source_chain.native_denom(), "amount": "5000"}],
-            "gas": "200000"
-        },
-        "msgs": [{
-            "@type": "/ibc.applications.transfer.v1.MsgTransfer",
-            "source_port": msg.source_port,
-            "source_channel": msg.source_channel,
-            "token": {
-                "denom": msg.token.denom,
-                "amount": msg.token.amount
-            },
-            "sender": msg.sender,
-            "receiver": msg.receiver,
-            "timeout_height": {
-                "revision_number": "0",
-                "revision_height": "0"
-            },
-            "timeout_timestamp": msg.timeout_timestamp.to_string(),
-            "memo": msg.memo
-        }],
-        "memo": ""
-    });
+    use crate::crypto::curves::secp256k1::Secp256k1Curve;
+    use crate::crypto::curves::EllipticCurve;
+    use crate::signing::preimage::cosmos::{
+        build_direct_sign_doc, encode_msg_transfer, encode_tx_raw, CosmosCoin, CosmosFee,
+        CosmosMessage, CosmosSignMode, CosmosSignerInfo, UnsignedCosmosTransaction,
+    };
     
-    // Hash and sign the document
-    let sign_bytes = serde_json::to_vec(&sign_doc).unwrap_or_default();
+    let public_key = match Secp256k1Curve::public_key_from_private(&private_key) {
+        Ok(p) => p,
+        Err(e) => return error_response(HawalaError::crypto_error(format!("Failed to get pubkey: {}", e))),
+    };
     
-    use sha2::{Sha256, Digest};
-    let mut hasher = Sha256::new();
-    hasher.update(&sign_bytes);
-    let hash = hasher.finalize();
+    // Build a SIGN_MODE_DIRECT protobuf sign doc
+    let token = CosmosCoin { denom: msg.token.denom.clone(), amount: msg.token.amount.clone() };
+    let mut tx = UnsignedCosmosTransaction {
+        chain_id: source_chain.chain_id().to_string(),
+        messages: vec![CosmosMessage {
+            type_url: "/ibc.applications.transfer.v1.MsgTransfer".to_string(),
+            value: encode_msg_transfer(
+                &msg.source_port,
+                &msg.source_channel,
+                &token,
+                &msg.sender,
+                &msg.receiver,
+                msg.timeout_timestamp,
+                &msg.memo,
+            ),
+            json_value: None,
+        }],
+        fee: CosmosFee {
+            amount: Vec::new(),
+            gas: 0,
+            payer: None,
+            granter: None,
+        },
+        memo: String::new(),
+        timeout_height: 0,
+        sign_mode: CosmosSignMode::Direct,
+        signer: CosmosSignerInfo {
+            address: request.sender.clone(),
+            account_number: request.account_number,
+            sequence: request.sequence,
+            public_key: Some(public_key.to_vec()),
+            derivation_path: None,
+        },
+    };
     
-    // Sign with secp256k1
-    use crate::crypto::curves::secp256k1::Secp256k1Curve;
-    use crate::crypto::curves::EllipticCurve;
+    // Size the fee: an explicit gas limit, or simulate with an empty signature
+    use crate::ibc::client::{fee_for_gas, IBCClient, DEFAULT_GAS_ADJUSTMENT};
+    let client = IBCClient::new();
+    let (gas_used, adjustment) = match request.gas_limit {
+        Some(gas_limit) => (gas_limit, 1.0),
+        None => {
+            let sim_doc = match build_direct_sign_doc(&tx) {
+                Ok(d) => d,
+                Err(e) => return error_response(HawalaError::crypto_error(format!("Failed to build sign doc: {}", e))),
+            };
+            let sim_bytes = encode_tx_raw(&sim_doc.body_bytes, &sim_doc.auth_info_bytes, &[Vec::new()]);
+            let rt = match tokio::runtime::Runtime::new() {
+                Ok(rt) => rt,
+                Err(e) => return error_response(HawalaError::internal(format!("Runtime error: {}", e))),
+            };
+            match rt.block_on(client.simulate_tx(source_chain, &sim_bytes)) {
+                Ok(gas_used) => (gas_used, request.gas_adjustment.unwrap_or(DEFAULT_GAS_ADJUSTMENT)),
+                Err(e) => return error_response(HawalaError::network_error(format!("{}; pass gasLimit to sign offline", e))),
+            }
+        }
+    };
+    let (gas_limit, fee_amount) = match fee_for_gas(gas_used, &client.get_gas_price(source_chain), adjustment) {
+        Ok(fee) => fee,
+        Err(e) => return error_response(HawalaError::invalid_input(e)),
+    };
+    tx.fee.gas = gas_limit;
+    tx.fee.amount = vec![CosmosCoin { denom: source_chain.native_denom().to_string(), amount: fee_amount.to_string() }];
+    
+    let sign_doc = match build_direct_sign_doc(&tx) {
+        Ok(d) => d,
+        Err(e) => return error_response(HawalaError::crypto_error(format!("Failed to build sign doc: {}", e))),
+    };
     
-    match Secp256k1Curve::sign(&private_key, hash.as_ref()) {
+    match Secp256k1Curve::sign(&private_key, &sign_doc.hash) {
         Ok(signature) => {
-            // Get public key
-            let pubkey = match Secp256k1Curve::public_key_from_private(&private_key) {
-                Ok(p) => hex::encode(p),
-                Err(e) => return error_response(HawalaError::crypto_error(format!("Failed to get pubkey: {}", e))),
-            };
+            let tx_raw = encode_tx_raw(&sign_doc.body_bytes, &sign_doc.auth_info_bytes, &[signature.to_vec()]);
             
             success_response(serde_json::json!({
                 "signedTx": {
-                    "bodyBytes": hex::encode(&sign_bytes),
-                    "authInfoBytes": "",
+                    "bodyBytes": hex::encode(&sign_doc.body_bytes),
+                    "authInfoBytes": hex::encode(&sign_doc.auth_info_bytes),
                     "signatures": [hex::encode(signature)]
                 },
+                "txBytes": hex::encode(&tx_raw),
                 "signature": hex::encode(signature),
-                "publicKey": pubkey,
-                "signDoc": sign_doc
+                "publicKey": hex::encode(public_key),
+                "signDocHash": hex::encode(sign_doc.hash),
+                "gasLimit": gas_limit,
+                "feeAmount": fee_amount.to_string()
             }))
         }
         Err(e) => error_response(HawalaError::crypto_error(format!("Signing failed: {:?}", e))),
@@ -6395,6 +7294,44 @@ pub extern "C" fn hawala_create_eip681_link(input: *const c_char) -> *mut c_char
     }
 }
 
+// =============================================================================
+// Lightning
+// =============================================================================
+
+/// Parse a BOLT12 offer
+/// 
+/// # Input
+/// ```json
+/// {
+///   "offer": "lno1..."
+/// }
+/// ```
+/// 
+/// # Output
+/// The decoded offer: description, amount, currency, issuer, node_id, paths, ...
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_lightning_parse_offer(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        offer: String,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::lightning::parse_offer(&request.offer) {
+        Ok(offer) => success_response(offer),
+        Err(e) => error_response(HawalaError::invalid_input(e.to_string())),
+    }
+}
+
 // =============================================================================
 // Phase 3: Transaction Notes
 // =============================================================================
@@ -6497,34 +7434,112 @@ pub extern "C" fn hawala_export_notes(input: *const c_char) -> *mut c_char {
 }
 
 // =============================================================================
-// Phase 3: Fiat Off-Ramp
+// Fiat On-Ramp
 // =============================================================================
 
-/// Get off-ramp quote for selling crypto
+/// Rank on-ramp quotes by crypto received for a fiat amount
+/// 
+/// `responses` holds each provider's raw quote response, keyed by provider
+/// (`moonpay`, `transak`, `ramp`, `banxa`). `reference_rate` (fiat per crypto)
+/// is optional and used to measure spread. When `payment_method` is given,
+/// providers that don't offer it are listed under `errors`.
 /// 
 /// # Input
 /// ```json
 /// {
-///   "provider": "moonpay",
-///   "crypto_symbol": "ETH",
-///   "crypto_amount": 1.0,
+///   "fiat_amount": 100.0,
 ///   "fiat_currency": "USD",
-///   "country": "US"
+///   "crypto_currency": "ETH",
+///   "wallet_address": "0x...",
+///   "payment_method": "apple_pay",
+///   "reference_rate": 2000.0,
+///   "responses": { "moonpay": { ... }, "banxa": { ... } }
 /// }
 /// ```
 #[unsafe(no_mangle)]
-pub extern "C" fn hawala_offramp_quote(input: *const c_char) -> *mut c_char {
+pub extern "C" fn hawala_onramp_compare(input: *const c_char) -> *mut c_char {
     let json_str = match parse_input(input) {
         Ok(s) => s,
         Err(ptr) => return ptr,
     };
 
-    let request: crate::offramp::OffRampQuoteRequest = match serde_json::from_str(json_str) {
-        Ok(r) => r,
-        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
-    };
-
-    let service = crate::offramp::OffRampService::new();
+    #[derive(serde::Deserialize)]
+    struct Request {
+        fiat_amount: f64,
+        fiat_currency: String,
+        crypto_currency: String,
+        wallet_address: String,
+        #[serde(default)]
+        network: Option<String>,
+        #[serde(default)]
+        payment_method: Option<crate::onramp::PaymentMethod>,
+        #[serde(default)]
+        reference_rate: Option<f64>,
+        responses: std::collections::HashMap<crate::onramp::OnRampProvider, serde_json::Value>,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let mut onramp_request = crate::onramp::OnRampRequest::new(
+        request.fiat_amount,
+        &request.fiat_currency,
+        &request.crypto_currency,
+        &request.wallet_address,
+    );
+    if let Some(ref network) = request.network {
+        onramp_request = onramp_request.with_network(network);
+    }
+    if let Some(method) = request.payment_method {
+        onramp_request = onramp_request.with_payment_method(method);
+    }
+
+    let responses: Vec<_> = request.responses.iter()
+        .map(|(provider, value)| (*provider, value.to_string()))
+        .collect();
+    let comparison = crate::onramp::compare_quotes(&onramp_request, &responses, request.reference_rate);
+
+    let errors: Vec<_> = comparison.errors.iter().map(|(provider, e)| serde_json::json!({
+        "provider": provider,
+        "error": e.to_string()
+    })).collect();
+    success_response(serde_json::json!({
+        "quotes": comparison.ranked,
+        "errors": errors
+    }))
+}
+
+// =============================================================================
+// Phase 3: Fiat Off-Ramp
+// =============================================================================
+
+/// Get off-ramp quote for selling crypto
+/// 
+/// # Input
+/// ```json
+/// {
+///   "provider": "moonpay",
+///   "crypto_symbol": "ETH",
+///   "crypto_amount": 1.0,
+///   "fiat_currency": "USD",
+///   "country": "US"
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_offramp_quote(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    let request: crate::offramp::OffRampQuoteRequest = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let service = crate::offramp::OffRampService::new();
     match service.get_quote(&request) {
         Ok(quote) => success_response(quote),
         Err(e) => error_response(e),
@@ -6842,10 +7857,13 @@ pub unsafe extern "C" fn hawala_build_user_operation(input: *const c_char) -> *m
 /// ```json
 /// {
 ///   "user_op": { ... },
-///   "chain": "ethereum"
+///   "chain": "ethereum",
+///   "version": "v0.6"
 /// }
 /// ```
 ///
+/// `version` selects the EntryPoint (`v0.6` or `v0.7`, default `v0.7`).
+///
 /// # Safety
 ///
 /// - `input` must be a valid pointer to a null-terminated C string.
@@ -6861,6 +7879,8 @@ pub unsafe extern "C" fn hawala_get_user_op_hash(input: *const c_char) -> *mut c
     struct Request {
         user_op: crate::erc4337::UserOperation,
         chain: String,
+        #[serde(default)]
+        version: crate::erc4337::UserOpVersion,
     }
 
     let request: Request = match serde_json::from_str(json_str) {
@@ -6873,10 +7893,16 @@ pub unsafe extern "C" fn hawala_get_user_op_hash(input: *const c_char) -> *mut c
         Err(e) => return error_response(e),
     };
 
-    match request.user_op.get_hash(chain.entry_point(), chain.chain_id()) {
+    let entry_point = match request.version {
+        crate::erc4337::UserOpVersion::V06 => chain.entry_point_v06(),
+        crate::erc4337::UserOpVersion::V07 => chain.entry_point(),
+    };
+
+    match request.user_op.get_hash_for_version(request.version, entry_point, chain.chain_id()) {
         Ok(hash) => success_response(serde_json::json!({
             "hash": format!("0x{}", hex::encode(hash)),
-            "entry_point": chain.entry_point(),
+            "entry_point": entry_point,
+            "version": request.version,
             "chain_id": chain.chain_id()
         })),
         Err(e) => error_response(e),
@@ -7076,3 +8102,1643 @@ pub extern "C" fn hawala_erc4337_chains() -> *mut c_char {
 
     success_response(serde_json::json!({ "chains": chains }))
 }
+
+/// Compute the Safe (Gnosis Safe) transaction hash signed by owners
+///
+/// # Input
+/// ```json
+/// {
+///   "safe_address": "0x...",
+///   "chain_id": 1,
+///   "transaction": {
+///     "to": "0x...",
+///     "value": "1000000000000000000",
+///     "data": "0x",
+///     "operation": 0,
+///     "nonce": 0
+///   }
+/// }
+/// ```
+///
+/// `transaction` uses the Safe SDK field names; gas and refund fields
+/// default to zero.
+///
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "safe_tx_hash": "0x...",
+///     "typed_data": { ... }
+///   }
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_safe_tx_hash(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        safe_address: String,
+        chain_id: u64,
+        transaction: crate::erc4337::SafeTransaction,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::erc4337::compute_safe_tx_hash(&request.safe_address, request.chain_id, &request.transaction) {
+        Ok(hash) => success_response(serde_json::json!({
+            "safe_tx_hash": format!("0x{}", hex::encode(hash)),
+            "typed_data": request.transaction.typed_data(&request.safe_address, request.chain_id)
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Resolve an ENS name to an address
+///
+/// # Input
+/// ```json
+/// { "name": "vitalik.eth" }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": { "name": "vitalik.eth", "address": "0x..." }
+/// }
+/// ```
+///
+/// `address` is null when the name has no resolver or address record.
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_ens_resolve(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        name: String,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::api::ens::resolve(&request.name) {
+        Ok(address) => success_response(serde_json::json!({
+            "name": request.name,
+            "address": address
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Look up the primary ENS name of an address
+///
+/// # Input
+/// ```json
+/// { "address": "0x..." }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": { "address": "0x...", "name": "vitalik.eth" }
+/// }
+/// ```
+///
+/// `name` is null when no reverse record is set or it does not resolve back
+/// to the address.
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_ens_reverse(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        address: String,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::api::ens::reverse_lookup(&request.address) {
+        Ok(name) => success_response(serde_json::json!({
+            "address": request.address,
+            "name": name
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Confirm a receive address by re-deriving it from the wallet
+///
+/// # Input
+/// ```json
+/// {
+///   "mnemonic": "word1 word2 ...",
+///   "passphrase": "",
+///   "xpub": null,
+///   "path": "m/84'/0'/0'/0/0",
+///   "chain": "bitcoin",
+///   "address": "bc1q..."
+/// }
+/// ```
+///
+/// Give exactly one of `mnemonic` (absolute `path`) or an account `xpub`
+/// (relative, unhardened `path` such as `0/0`).
+///
+/// # Output
+/// ```json
+/// { "success": true, "data": { "matches": true } }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_verify_receive_address(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        #[serde(default)]
+        mnemonic: Option<String>,
+        #[serde(default)]
+        passphrase: String,
+        #[serde(default)]
+        xpub: Option<String>,
+        path: String,
+        chain: Chain,
+        address: String,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let result = match (&request.mnemonic, &request.xpub) {
+        (Some(phrase), None) => {
+            let mnemonic = match bip39::Mnemonic::parse(phrase) {
+                Ok(m) => m,
+                Err(e) => return error_response(HawalaError::new(ErrorCode::InvalidMnemonic, format!("Invalid mnemonic: {}", e))),
+            };
+            let seed = zeroize::Zeroizing::new(mnemonic.to_seed(&request.passphrase));
+            wallet::verify_address(wallet::ScanSource::Seed(seed.as_ref()), &request.path, request.chain, &request.address)
+        }
+        (None, Some(xpub)) => {
+            wallet::verify_address(wallet::ScanSource::Xpub(xpub), &request.path, request.chain, &request.address)
+        }
+        _ => return error_response(HawalaError::invalid_input("Provide exactly one of mnemonic or xpub")),
+    };
+
+    match result {
+        Ok(matches) => success_response(serde_json::json!({ "matches": matches })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Decode a raw transaction for review before signing or broadcasting
+///
+/// # Input
+/// ```json
+/// { "chain": "ethereum", "raw_tx": "0x02f8..." }
+/// ```
+///
+/// EVM and Bitcoin transactions are hex; Solana transactions are base58 or
+/// base64.
+///
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "chain": "ethereum",
+///     "tx_type": "eip1559",
+///     "txid": "0x...",
+///     "chain_id": 1,
+///     "nonce": 7,
+///     "outputs": [{ "address": "0x...", "value": "1000000000000000" }],
+///     "inputs": [],
+///     "fee": "630000000000000",
+///     "method": "transfer(address,uint256)",
+///     "method_args": ["0x...", "100000000"],
+///     "authorizations": []
+///   }
+/// }
+/// ```
+///
+/// For `eip7702` transactions `authorizations` lists each delegation as
+/// `{ "chain_id", "delegate", "nonce", "signer" }`.
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_decode_transaction(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        chain: Chain,
+        raw_tx: String,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::tx::decode_transaction(request.chain, &request.raw_tx) {
+        Ok(decoded) => success_response(decoded),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Point Bitcoin or Litecoin balance and UTXO lookups at an Electrum server
+///
+/// # Input
+/// ```json
+/// { "chain": "bitcoin", "host": "electrum.example.org", "port": 50002, "tls": true }
+/// ```
+///
+/// Omit `host` to go back to the HTTP explorers. The server is tried first;
+/// if it cannot be reached the explorers are used.
+///
+/// # Output
+/// ```json
+/// { "success": true, "data": { "chain": "bitcoin", "server": { "host": "...", "port": 50002, "tls": true } } }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_set_electrum_server(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        chain: Chain,
+        #[serde(default)]
+        host: Option<String>,
+        #[serde(default)]
+        port: Option<u16>,
+        #[serde(default)]
+        tls: bool,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let server = match (request.host, request.port) {
+        (Some(host), Some(port)) => Some(crate::api::electrum::ElectrumServer { host, port, tls: request.tls }),
+        (Some(_), None) => return error_response(HawalaError::invalid_input("port is required with host")),
+        (None, _) => None,
+    };
+
+    match crate::api::electrum::set_electrum_server(request.chain, server.clone()) {
+        Ok(()) => success_response(serde_json::json!({ "chain": request.chain, "server": server })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Override the RPC, bundler, explorer or paymaster URL used for a chain
+///
+/// # Input
+/// ```json
+/// { "chain": "ethereum", "rpc_url": "https://node.example.org", "bundler_url": "", "explorer_url": null, "paymaster_url": null }
+/// ```
+///
+/// Omitted fields are left unchanged; an empty string restores the built-in
+/// default. URLs are validated like custom RPC endpoints (HTTPS unless local).
+///
+/// # Output
+/// ```json
+/// { "success": true, "data": { "chain": "ethereum", "rpc_url": "https://node.example.org", "bundler_url": null, "explorer_url": null, "paymaster_url": null } }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_set_network_config(input: *const c_char) -> *mut c_char {
+    use crate::utils::network_config::{get_network_config, ServiceKind};
+
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        chain: Chain,
+        #[serde(default, alias = "rpcUrl")]
+        rpc_url: Option<String>,
+        #[serde(default, alias = "bundlerUrl")]
+        bundler_url: Option<String>,
+        #[serde(default, alias = "explorerUrl")]
+        explorer_url: Option<String>,
+        #[serde(default, alias = "paymasterUrl")]
+        paymaster_url: Option<String>,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let config = get_network_config();
+    let updates = [
+        (ServiceKind::Rpc, &request.rpc_url),
+        (ServiceKind::Bundler, &request.bundler_url),
+        (ServiceKind::Explorer, &request.explorer_url),
+        (ServiceKind::Paymaster, &request.paymaster_url),
+    ];
+    for (kind, url) in updates {
+        let Some(url) = url else { continue };
+        let url = Some(url.trim()).filter(|u| !u.is_empty());
+        if let Err(e) = config.set_url_override(request.chain, kind, url) {
+            return error_response(e);
+        }
+    }
+
+    success_response(serde_json::json!({
+        "chain": request.chain,
+        "rpc_url": config.url_override(request.chain, ServiceKind::Rpc),
+        "bundler_url": config.url_override(request.chain, ServiceKind::Bundler),
+        "explorer_url": config.url_override(request.chain, ServiceKind::Explorer),
+        "paymaster_url": config.url_override(request.chain, ServiceKind::Paymaster),
+    }))
+}
+
+/// Export account xpubs as a `crypto-account` UR for pairing with watch-only
+/// wallets (Sparrow, Keystone companion apps)
+///
+/// # Input
+/// ```json
+/// { "mnemonic": "abandon ...", "passphrase": "", "paths": ["m/84'/0'/0'", "m/86'/0'/0'"] }
+/// ```
+/// or, when the xpubs are already known:
+/// ```json
+/// { "master_fingerprint": "73c5da0a", "accounts": [{ "path": "m/84'/0'/0'", "xpub": "zpub..." }] }
+/// ```
+///
+/// Paths must use BIP purpose 44', 49', 84' or 86'. With a mnemonic,
+/// `paths` defaults to the BIP-84 and BIP-86 accounts.
+///
+/// # Output
+/// ```json
+/// { "success": true, "data": { "frames": ["ur:crypto-account/..."], "frame_count": 1, "master_fingerprint": "73c5da0a" } }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_qr_export_account(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Account {
+        path: String,
+        xpub: String,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        #[serde(default)]
+        mnemonic: Option<String>,
+        #[serde(default)]
+        passphrase: String,
+        #[serde(default)]
+        paths: Option<Vec<String>>,
+        #[serde(default)]
+        master_fingerprint: Option<String>,
+        #[serde(default)]
+        accounts: Vec<Account>,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let (fingerprint, accounts) = match (&request.mnemonic, &request.master_fingerprint) {
+        (Some(phrase), None) => {
+            let mnemonic = match bip39::Mnemonic::parse(phrase) {
+                Ok(m) => m,
+                Err(e) => return error_response(HawalaError::new(ErrorCode::InvalidMnemonic, format!("Invalid mnemonic: {}", e))),
+            };
+            let seed = zeroize::Zeroizing::new(mnemonic.to_seed(&request.passphrase));
+            let paths = request
+                .paths
+                .clone()
+                .unwrap_or_else(|| vec!["m/84'/0'/0'".to_string(), "m/86'/0'/0'".to_string()]);
+            match wallet::account_xpubs(seed.as_ref(), &paths) {
+                Ok(result) => result,
+                Err(e) => return error_response(e),
+            }
+        }
+        (None, Some(fingerprint)) => {
+            let decoded = hex::decode(fingerprint.trim().trim_start_matches("0x")).ok();
+            let fingerprint = match decoded.and_then(|bytes| <[u8; 4]>::try_from(bytes).ok()) {
+                Some(f) => f,
+                None => return error_response(HawalaError::invalid_input("master_fingerprint must be 4 bytes of hex")),
+            };
+            let accounts = request.accounts.iter().map(|a| (a.path.clone(), a.xpub.clone())).collect();
+            (fingerprint, accounts)
+        }
+        _ => return error_response(HawalaError::invalid_input("Provide exactly one of mnemonic or master_fingerprint")),
+    };
+
+    let xpubs: Vec<(&str, &str)> = accounts.iter().map(|(path, xpub)| (path.as_str(), xpub.as_str())).collect();
+    match crate::qr::ur::encode_crypto_account(fingerprint, &xpubs) {
+        Ok(frames) => success_response(serde_json::json!({
+            "frame_count": frames.len(),
+            "frames": frames,
+            "master_fingerprint": hex::encode(fingerprint),
+        })),
+        Err(e) => error_response(HawalaError::invalid_input(format!("Account export failed: {}", e))),
+    }
+}
+
+/// Decode an EVM event log (e.g. from a transaction receipt) by its ABI
+///
+/// # Input
+/// ```json
+/// {
+///   "abi": [{ "type": "event", "name": "Transfer", "inputs": [...] }],
+///   "event": "Transfer",
+///   "topics": ["0xddf252ad...", "0x000...from", "0x000...to"],
+///   "data": "0x00000000000000000000000000000000000000000000000000000000000f4240"
+/// }
+/// ```
+///
+/// `abi` may be a full contract ABI or a single event item. Without
+/// `event`, the event is found by `topics[0]`. Indexed string, bytes, array
+/// and tuple parameters are returned as their 32-byte hash with `hashed: true`.
+///
+/// # Output
+/// ```json
+/// { "success": true, "data": { "name": "Transfer", "signature": "Transfer(address,address,uint256)",
+///   "params": [{ "name": "value", "type": "uint256", "indexed": false, "hashed": false, "value": "1000000" }] } }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_abi_decode_log(input: *const c_char) -> *mut c_char {
+    use crate::abi::{decode_log, ContractAbi};
+
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        abi: serde_json::Value,
+        #[serde(default)]
+        event: Option<String>,
+        topics: Vec<String>,
+        #[serde(default)]
+        data: String,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let abi_items = match request.abi {
+        item @ serde_json::Value::Object(_) => serde_json::Value::Array(vec![item]),
+        items => items,
+    };
+    let abi = match ContractAbi::from_json_value(abi_items) {
+        Ok(abi) => abi,
+        Err(e) => return error_response(HawalaError::invalid_input(e.to_string())),
+    };
+
+    let mut topics = Vec::with_capacity(request.topics.len());
+    for topic in &request.topics {
+        let decoded = hex::decode(topic.trim().trim_start_matches("0x")).ok();
+        match decoded.and_then(|bytes| <[u8; 32]>::try_from(bytes).ok()) {
+            Some(t) => topics.push(t),
+            None => return error_response(HawalaError::invalid_input(format!("Topic must be 32 bytes of hex: {}", topic))),
+        }
+    }
+    let data = match hex::decode(request.data.trim().trim_start_matches("0x")) {
+        Ok(d) => d,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("Invalid data hex: {}", e))),
+    };
+
+    let event = match (&request.event, topics.first()) {
+        (Some(name), _) => abi.event(name),
+        (None, Some(topic0)) => abi.event_by_topic(topic0),
+        (None, None) => None,
+    };
+    let event = match event {
+        Some(e) => e,
+        None => return error_response(HawalaError::invalid_input("No matching event in ABI")),
+    };
+
+    match decode_log(&topics, &data, event) {
+        Ok(log) => {
+            let params: Vec<serde_json::Value> = log
+                .params
+                .iter()
+                .map(|p| {
+                    serde_json::json!({
+                        "name": p.name,
+                        "type": p.param_type.canonical_type(),
+                        "indexed": p.indexed,
+                        "hashed": p.hashed,
+                        "value": p.value.to_json(),
+                    })
+                })
+                .collect();
+            success_response(serde_json::json!({
+                "name": log.name,
+                "signature": log.signature,
+                "params": params,
+            }))
+        }
+        Err(e) => error_response(HawalaError::invalid_input(format!("Log decoding failed: {}", e))),
+    }
+}
+
+// =============================================================================
+// Schnorr Adaptor Signatures (atomic swaps)
+// =============================================================================
+
+/// Decode a hex field (optional 0x prefix), checking its length when given
+fn decode_adaptor_hex(field: &str, value: &str, len: Option<usize>) -> Result<Vec<u8>, HawalaError> {
+    let bytes = hex::decode(value.strip_prefix("0x").unwrap_or(value))
+        .map_err(|e| HawalaError::invalid_input(format!("Invalid {} hex: {}", field, e)))?;
+    match len {
+        Some(len) if bytes.len() != len => Err(HawalaError::invalid_input(format!(
+            "{} must be {} bytes, got {}",
+            field,
+            len,
+            bytes.len()
+        ))),
+        _ => Ok(bytes),
+    }
+}
+
+fn decode_adaptor_message(value: &str) -> Result<[u8; 32], HawalaError> {
+    let bytes = decode_adaptor_hex("Message", value, Some(32))?;
+    let mut message = [0u8; 32];
+    message.copy_from_slice(&bytes);
+    Ok(message)
+}
+
+/// Create a Schnorr adaptor signature encrypted to an adaptor point
+///
+/// Either `adaptor_point` or `adaptor_secret` must be given.
+///
+/// # Input
+/// ```json
+/// {
+///   "message": "0x...",  // 32-byte message hash (hex)
+///   "private_key": "0x...",  // 32-byte private key (hex)
+///   "adaptor_point": "0x...",  // 33-byte compressed point T (hex)
+///   "adaptor_secret": "0x..."  // optional 32-byte secret t, T = t·G (hex)
+/// }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "adaptor_signature": "0x...",  // 65-byte adaptor signature (hex)
+///     "adaptor_point": "0x...",
+///     "public_key": "0x..."  // 32-byte x-only public key (hex)
+///   }
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_schnorr_adaptor_sign(input: *const c_char) -> *mut c_char {
+    use crate::crypto::schnorr::{adaptor, schnorr_public_key};
+
+    let input_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        message: String,
+        private_key: String,
+        adaptor_point: Option<String>,
+        adaptor_secret: Option<String>,
+    }
+
+    let request: Request = match serde_json::from_str(input_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("JSON parse error: {}", e))),
+    };
+
+    let message = match decode_adaptor_message(&request.message) {
+        Ok(m) => m,
+        Err(e) => return error_response(e),
+    };
+    let private_key = match decode_adaptor_hex("Private key", &request.private_key, Some(32)) {
+        Ok(k) => zeroize::Zeroizing::new(k),
+        Err(e) => return error_response(e),
+    };
+
+    let point = match (&request.adaptor_point, &request.adaptor_secret) {
+        (Some(point), _) => match decode_adaptor_hex("Adaptor point", point, None) {
+            Ok(p) => p,
+            Err(e) => return error_response(e),
+        },
+        (None, Some(secret)) => {
+            let secret = match decode_adaptor_hex("Adaptor secret", secret, Some(32)) {
+                Ok(s) => zeroize::Zeroizing::new(s),
+                Err(e) => return error_response(e),
+            };
+            match adaptor::adaptor_point(&secret) {
+                Ok(p) => p.to_vec(),
+                Err(e) => return error_response(HawalaError::invalid_input(format!("Invalid adaptor secret: {}", e))),
+            }
+        }
+        (None, None) => return error_response(HawalaError::invalid_input("adaptor_point or adaptor_secret is required")),
+    };
+
+    let signature = match adaptor::encrypt_sign(&message, &private_key, &point) {
+        Ok(sig) => sig,
+        Err(e) => return error_response(HawalaError::crypto_error(format!("Adaptor signing failed: {}", e))),
+    };
+    let public_key = match schnorr_public_key(&private_key) {
+        Ok(pk) => pk,
+        Err(e) => return error_response(HawalaError::crypto_error(format!("Failed to derive public key: {}", e))),
+    };
+
+    success_response(serde_json::json!({
+        "adaptor_signature": format!("0x{}", signature.to_hex()),
+        "adaptor_point": format!("0x{}", hex::encode(&point)),
+        "public_key": format!("0x{}", public_key.to_hex())
+    }))
+}
+
+/// Verify a Schnorr adaptor signature against its adaptor point
+///
+/// # Input
+/// ```json
+/// {
+///   "message": "0x...",  // 32-byte message hash (hex)
+///   "public_key": "0x...",  // 32-byte x-only public key (hex)
+///   "adaptor_point": "0x...",  // 33-byte compressed point T (hex)
+///   "adaptor_signature": "0x..."  // 65-byte adaptor signature (hex)
+/// }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "valid": true
+///   }
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_schnorr_adaptor_verify(input: *const c_char) -> *mut c_char {
+    use crate::crypto::schnorr::{adaptor, XOnlyPubKey};
+
+    let input_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        message: String,
+        public_key: String,
+        adaptor_point: String,
+        adaptor_signature: String,
+    }
+
+    let request: Request = match serde_json::from_str(input_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("JSON parse error: {}", e))),
+    };
+
+    let message = match decode_adaptor_message(&request.message) {
+        Ok(m) => m,
+        Err(e) => return error_response(e),
+    };
+    let public_key = match decode_adaptor_hex("Public key", &request.public_key, None)
+        .and_then(|b| XOnlyPubKey::from_slice(&b).map_err(|e| HawalaError::invalid_input(format!("Invalid public key: {}", e))))
+    {
+        Ok(pk) => pk,
+        Err(e) => return error_response(e),
+    };
+    let point = match decode_adaptor_hex("Adaptor point", &request.adaptor_point, None) {
+        Ok(p) => p,
+        Err(e) => return error_response(e),
+    };
+    let signature = match decode_adaptor_hex("Adaptor signature", &request.adaptor_signature, None)
+        .and_then(|b| adaptor::AdaptorSignature::from_slice(&b).map_err(|e| HawalaError::invalid_input(format!("Invalid adaptor signature: {}", e))))
+    {
+        Ok(sig) => sig,
+        Err(e) => return error_response(e),
+    };
+
+    match adaptor::verify_adaptor(&message, &public_key, &point, &signature) {
+        Ok(valid) => success_response(serde_json::json!({
+            "valid": valid
+        })),
+        Err(e) => error_response(HawalaError::invalid_input(format!("Adaptor verification failed: {}", e))),
+    }
+}
+
+/// Complete an adaptor signature into a BIP-340 signature with the secret
+///
+/// # Input
+/// ```json
+/// {
+///   "adaptor_signature": "0x...",  // 65-byte adaptor signature (hex)
+///   "adaptor_secret": "0x..."  // 32-byte secret t (hex)
+/// }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "signature": "0x..."  // 64-byte Schnorr signature (hex)
+///   }
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_schnorr_adaptor_decrypt(input: *const c_char) -> *mut c_char {
+    use crate::crypto::schnorr::adaptor;
+
+    let input_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        adaptor_signature: String,
+        adaptor_secret: String,
+    }
+
+    let request: Request = match serde_json::from_str(input_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("JSON parse error: {}", e))),
+    };
+
+    let signature = match decode_adaptor_hex("Adaptor signature", &request.adaptor_signature, None)
+        .and_then(|b| adaptor::AdaptorSignature::from_slice(&b).map_err(|e| HawalaError::invalid_input(format!("Invalid adaptor signature: {}", e))))
+    {
+        Ok(sig) => sig,
+        Err(e) => return error_response(e),
+    };
+    let secret = match decode_adaptor_hex("Adaptor secret", &request.adaptor_secret, Some(32)) {
+        Ok(s) => zeroize::Zeroizing::new(s),
+        Err(e) => return error_response(e),
+    };
+
+    match adaptor::decrypt(&signature, &secret) {
+        Ok(sig) => success_response(serde_json::json!({
+            "signature": format!("0x{}", sig.to_hex())
+        })),
+        Err(e) => error_response(HawalaError::crypto_error(format!("Adaptor decryption failed: {}", e))),
+    }
+}
+
+/// Recover the adaptor secret from an adaptor signature and the published
+/// BIP-340 signature
+///
+/// # Input
+/// ```json
+/// {
+///   "adaptor_signature": "0x...",  // 65-byte adaptor signature (hex)
+///   "signature": "0x..."  // 64-byte completed Schnorr signature (hex)
+/// }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "adaptor_secret": "0x..."  // 32-byte secret t (hex)
+///   }
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_schnorr_adaptor_recover(input: *const c_char) -> *mut c_char {
+    use crate::crypto::schnorr::{adaptor, SchnorrSig};
+
+    let input_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        adaptor_signature: String,
+        signature: String,
+    }
+
+    let request: Request = match serde_json::from_str(input_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("JSON parse error: {}", e))),
+    };
+
+    let adaptor_sig = match decode_adaptor_hex("Adaptor signature", &request.adaptor_signature, None)
+        .and_then(|b| adaptor::AdaptorSignature::from_slice(&b).map_err(|e| HawalaError::invalid_input(format!("Invalid adaptor signature: {}", e))))
+    {
+        Ok(sig) => sig,
+        Err(e) => return error_response(e),
+    };
+    let final_sig = match decode_adaptor_hex("Signature", &request.signature, None)
+        .and_then(|b| SchnorrSig::from_slice(&b).map_err(|e| HawalaError::invalid_input(format!("Invalid signature: {}", e))))
+    {
+        Ok(sig) => sig,
+        Err(e) => return error_response(e),
+    };
+
+    match adaptor::recover_secret(&adaptor_sig, &final_sig) {
+        Ok(secret) => success_response(serde_json::json!({
+            "adaptor_secret": format!("0x{}", hex::encode(secret))
+        })),
+        Err(e) => error_response(HawalaError::invalid_input(format!("Secret recovery failed: {}", e))),
+    }
+}
+
+/// Build, sign and broadcast a transaction in one call
+///
+/// Reserves the EVM nonce (fetched from the network when `nonce` is omitted)
+/// or the UTXOs being spent, and releases them if signing or broadcasting
+/// fails.
+///
+/// # Input
+/// See `TransactionRequest` in types.rs (`dry_run` is rejected)
+///
+/// # Output
+/// ```json
+/// { "chain": "ethereum", "txid": "0x...", "raw_tx": "0x...", "nonce": 7, "explorer_url": "..." }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_send_transaction(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    let request: TransactionRequest = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::tx::send(&request) {
+        Ok(sent) => success_response(sent),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Parse a scanned payment URI (bitcoin:, litecoin:, monero:, ethereum:)
+///
+/// # Input
+/// ```json
+/// { "uri": "ethereum:0xA0b8...@1/transfer?address=0x...&uint256=1e6" }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "chain": "ethereum",
+///   "address": "0x...",
+///   "amount": "1000000",
+///   "label": null,
+///   "message": null,
+///   "extra": { "chain_id": "1", "contract": "0xA0b8...", "function": "transfer" }
+/// }
+/// ```
+/// `amount` is in whole coins for BIP-21 and Monero URIs and in base units
+/// (wei, token units) for EIP-681.
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_parse_payment_uri(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        uri: String,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::payments::parse_payment_uri(&request.uri) {
+        Ok(parsed) => success_response(parsed),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Build a payment URI for a receive request (BIP-21, monero:, EIP-681)
+///
+/// # Input
+/// ```json
+/// {
+///   "chain": "polygon",
+///   "address": "0x...",
+///   "amount": "1000000",
+///   "token": "0x3c49...",
+///   "label": null,
+///   "message": null
+/// }
+/// ```
+///
+/// # Output
+/// ```json
+/// { "uri": "ethereum:0x3c49...@137/transfer?address=0x...&uint256=1000000" }
+/// ```
+/// `amount` uses the same units as `hawala_parse_payment_uri`.
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_generate_payment_uri(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    let request: crate::payments::PaymentUriRequest = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::payments::generate_payment_uri(&request) {
+        Ok(uri) => success_response(serde_json::json!({ "uri": uri })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Check a URL or domain for phishing, including punycode look-alikes
+///
+/// # Input
+/// ```json
+/// { "url": "https://xn--uniswp-7nf.org/#/swap" }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "host": "uniswаp.org",
+///   "ascii_host": "xn--uniswp-7nf.org",
+///   "is_flagged": true,
+///   "flag_type": "homograph",
+///   "risk_level": "critical",
+///   "details": "...",
+///   "impersonating": "uniswap.org",
+///   "should_block": true
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_check_phishing(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        url: String,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    success_response(crate::security::phishing::check_domain(&request.url))
+}
+
+/// Parse a WalletConnect v2 session request into the wallet's request types
+///
+/// Supports `eth_sendTransaction`, `personal_sign`, `eth_signTypedData_v4`
+/// and `solana_signTransaction`. `chainId` is the session request's CAIP-2
+/// chain; it is required unless the request itself names the chain.
+///
+/// # Input
+/// ```json
+/// {
+///   "method": "personal_sign",
+///   "params": ["0x48656c6c6f", "0x9858EfFD232B4033E47d90003D41EC34EcaEda94"],
+///   "chainId": "eip155:1"
+/// }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "method": "personal_sign",
+///   "signer": "0x9858EfFD232B4033E47d90003D41EC34EcaEda94",
+///   "chain": "ethereum",
+///   "payload": { "type": "personal_message", "message_hex": "0x48656c6c6f", "text": "Hello" }
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_parse_wc_request(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        method: String,
+        #[serde(default)]
+        params: serde_json::Value,
+        #[serde(default, rename = "chainId")]
+        chain_id: Option<String>,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::walletconnect::parse_request(&request.method, request.params, request.chain_id.as_deref()) {
+        Ok(parsed) => success_response(parsed),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Recover the BIP-39 entropy behind a mnemonic phrase
+///
+/// # Input
+/// ```json
+/// { "mnemonic": "word1 word2 ..." }
+/// ```
+///
+/// # Output
+/// ```json
+/// { "entropy": "00000000000000000000000000000000", "language": "english" }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_mnemonic_to_entropy(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        mnemonic: String,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match wallet::mnemonic_to_entropy(&request.mnemonic) {
+        Ok(entropy) => success_response(serde_json::json!({
+            "entropy": hex::encode(entropy),
+            "language": wallet::detect_mnemonic_language(&request.mnemonic),
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Encode BIP-39 entropy (16-32 bytes, hex) as a mnemonic phrase
+///
+/// # Input
+/// ```json
+/// { "entropy": "00000000000000000000000000000000", "language": "english" }
+/// ```
+/// `language` is optional (default english).
+///
+/// # Output
+/// ```json
+/// { "mnemonic": "abandon abandon ... about" }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_entropy_to_mnemonic(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        entropy: String,
+        #[serde(default)]
+        language: wallet::MnemonicLanguage,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let entropy = match crate::utils::parse_hex_bytes(&request.entropy) {
+        Ok(bytes) => zeroize::Zeroizing::new(bytes),
+        Err(e) => return error_response(e),
+    };
+
+    match wallet::entropy_to_mnemonic_in(&entropy, request.language) {
+        Ok(mnemonic) => success_response(serde_json::json!({ "mnemonic": mnemonic })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Derive addresses from an output descriptor (watch-only import)
+///
+/// Supports `pkh`, `wpkh`, `sh(wpkh(...))` and key-path `tr` descriptors over
+/// an extended public key. The `#checksum` suffix is verified when present.
+///
+/// # Input
+/// ```json
+/// {
+///   "descriptor": "wpkh([73c5da0a/84'/0'/0']xpub.../0/*)#wc3n3van",
+///   "index": 0,
+///   "count": 1
+/// }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "descriptor": "wpkh([73c5da0a/84'/0'/0']xpub.../0/*)#wc3n3van",
+///   "kind": "wpkh",
+///   "ranged": true,
+///   "addresses": [{ "index": 0, "address": "bc1q..." }]
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_derive_descriptor(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        descriptor: String,
+        #[serde(default)]
+        index: u32,
+        #[serde(default = "default_count")]
+        count: u32,
+    }
+
+    fn default_count() -> u32 {
+        1
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    if request.count == 0 || request.count > 1000 {
+        return error_response(HawalaError::invalid_input("count must be between 1 and 1000"));
+    }
+
+    let descriptor = match wallet::descriptor::parse(&request.descriptor) {
+        Ok(d) => d,
+        Err(e) => return error_response(e),
+    };
+
+    let mut addresses = Vec::with_capacity(request.count as usize);
+    for offset in 0..request.count {
+        let Some(index) = request.index.checked_add(offset) else {
+            return error_response(HawalaError::invalid_input("index out of range"));
+        };
+        match descriptor.address_at(index) {
+            Ok(address) => addresses.push(serde_json::json!({ "index": index, "address": address.to_string() })),
+            Err(e) => return error_response(e),
+        }
+    }
+
+    success_response(serde_json::json!({
+        "descriptor": descriptor.to_string(),
+        "kind": descriptor.kind,
+        "ranged": descriptor.ranged,
+        "addresses": addresses,
+    }))
+}
+
+/// Check which RPC/explorer providers for a chain are reachable
+///
+/// Each provider gets a block-tip query with a short timeout.
+///
+/// # Input
+/// ```json
+/// { "chain": "ethereum" }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "chain": "ethereum",
+///   "providers": [
+///     { "url": "https://eth.llamarpc.com", "kind": "rpc", "up": true, "latency_ms": 120, "block_height": 19000000, "error": null }
+///   ]
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_provider_health(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        chain: Chain,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    success_response(serde_json::json!({
+        "chain": request.chain,
+        "providers": crate::api::health_check(request.chain),
+    }))
+}
+
+/// Derive a run of receive addresses from a seed in one call
+///
+/// The master and account keys are computed once, so this is much cheaper
+/// than calling `hawala_derive_key` per index. Solana addresses are derived
+/// at `m/44'/501'/index'/0'` and need `account` 0.
+///
+/// # Input
+/// ```json
+/// {
+///   "seed": "0x...",  // 64-byte seed from mnemonic
+///   "chain": "bitcoin",
+///   "account": 0,  // optional, default 0
+///   "start": 0,  // optional, default 0
+///   "count": 20
+/// }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "addresses": [
+///     { "index": 0, "address": "bc1q...", "path": "m/84'/0'/0'/0/0", "public_key": "02..." }
+///   ]
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_derive_addresses(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        seed: String,
+        chain: Chain,
+        #[serde(default)]
+        account: u32,
+        #[serde(default)]
+        start: u32,
+        count: u32,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let seed_str = request.seed.strip_prefix("0x").unwrap_or(&request.seed);
+    let seed = match hex::decode(seed_str) {
+        Ok(b) => b,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("Invalid seed hex: {}", e))),
+    };
+
+    match wallet::derive_addresses(&seed, request.chain, request.account, request.start, request.count) {
+        Ok(addresses) => success_response(serde_json::json!({ "addresses": addresses })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Encrypt a private key with a passphrase (BIP-38, non-EC-multiply)
+///
+/// `private_key` is 32-byte hex or WIF. With WIF, `compressed` defaults to
+/// the WIF's own flag; with hex it defaults to true.
+///
+/// # Input
+/// ```json
+/// { "private_key": "cbf4b9f7...", "passphrase": "TestingOneTwoThree", "compressed": false }
+/// ```
+///
+/// # Output
+/// ```json
+/// { "encrypted": "6PRVWUbkzzsbcVac2qwfssoUJAN1Xhrg6bNk8J7Nzm5H7kxEbn2Nh2ZoGg", "address": "1Jq6MksXQVWzrznvZzxkV6oY57oWXD9TXB" }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_bip38_encrypt(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        private_key: String,
+        passphrase: String,
+        compressed: Option<bool>,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let (secret, compressed) = match bitcoin::PrivateKey::from_wif(request.private_key.trim()) {
+        Ok(key) => (key.inner, request.compressed.unwrap_or(key.compressed)),
+        // Don't echo the key back in the error
+        Err(_) => match hex::decode(request.private_key.trim().trim_start_matches("0x"))
+            .ok()
+            .and_then(|bytes| bitcoin::secp256k1::SecretKey::from_slice(&bytes).ok())
+        {
+            Some(secret) => (secret, request.compressed.unwrap_or(true)),
+            None => return error_response(HawalaError::invalid_input("Invalid private key: expected 32-byte hex or WIF")),
+        },
+    };
+
+    let encrypted = match wallet::bip38::encrypt(&secret.secret_bytes(), &request.passphrase, compressed) {
+        Ok(s) => s,
+        Err(e) => return error_response(e),
+    };
+    let key = if compressed {
+        bitcoin::PrivateKey::new(secret, bitcoin::Network::Bitcoin)
+    } else {
+        bitcoin::PrivateKey::new_uncompressed(secret, bitcoin::Network::Bitcoin)
+    };
+
+    success_response(serde_json::json!({
+        "encrypted": encrypted,
+        "address": wallet::bip38::bip38_address(&key),
+    }))
+}
+
+/// Decrypt a BIP-38 (`6P...`) private key
+///
+/// # Input
+/// ```json
+/// { "encrypted": "6PRVWUbkzzsbcVac2qwfssoUJAN1Xhrg6bNk8J7Nzm5H7kxEbn2Nh2ZoGg", "passphrase": "TestingOneTwoThree" }
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "private_key": "cbf4b9f7...",
+///   "wif": "5KN7MzqK5wt2TP1fQCYyHBtDrXdJuXbUzm4A9rKAteGu3Qi5CVR",
+///   "compressed": false,
+///   "address": "1Jq6MksXQVWzrznvZzxkV6oY57oWXD9TXB"
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_bip38_decrypt(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        encrypted: String,
+        passphrase: String,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match wallet::bip38::decrypt(&request.encrypted, &request.passphrase) {
+        Ok(key) => success_response(serde_json::json!({
+            "private_key": hex::encode(key.inner.secret_bytes()),
+            "wif": key.to_wif(),
+            "compressed": key.compressed,
+            "address": wallet::bip38::bip38_address(&key),
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// List every SPL token held by an address (Solana)
+///
+/// # Input
+/// ```json
+/// { "address": "...", "chain": "solana" }  // chain optional, default "solana"
+/// ```
+///
+/// # Output
+/// ```json
+/// {
+///   "tokens": [
+///     { "mint": "EPjF...", "token_accounts": ["..."], "balance_raw": "12500000", "decimals": 6, "balance": "12.5", "symbol": "USDC", "name": "USD Coin" }
+///   ]
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_fetch_all_spl(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        address: String,
+        chain: Option<Chain>,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let chain = request.chain.unwrap_or(Chain::Solana);
+    if !matches!(chain, Chain::Solana | Chain::SolanaDevnet) {
+        return error_response(HawalaError::invalid_input(format!("SPL tokens are Solana-only, got {:?}", chain)));
+    }
+
+    match crate::balances::fetch_all_spl_balances(&request.address, chain) {
+        Ok(tokens) => success_response(serde_json::json!({ "tokens": tokens })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Fetch ERC-20 token name, symbol and decimals (cached after first fetch)
+///
+/// # Input
+/// ```json
+/// { "contract": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "chain": "ethereum" }
+/// ```
+///
+/// # Output
+/// ```json
+/// { "chain": "ethereum", "contract": "0xA0b8...", "name": "USD Coin", "symbol": "USDC", "decimals": 6 }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_token_metadata(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        contract: String,
+        chain: Chain,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    match crate::balances::fetch_token_metadata(&request.contract, request.chain) {
+        Ok(metadata) => success_response(metadata),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Build an unsigned EVM contract call from an ABI, function name and JSON args
+///
+/// The returned `transaction` is the input `hawala_get_ethereum_signing_hash`
+/// and `hawala_compile_ethereum_transaction` take.
+///
+/// # Input
+/// ```json
+/// {
+///   "abi": [...],  // JSON ABI
+///   "function": "transfer",  // or "transfer(address,uint256)" for overloads
+///   "args": ["0x9858EfFD232B4033E47d90003D41EC34EcaEda94", "1000000"],
+///   "to": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+///   "value": "0",  // optional, wei
+///   "chain": "ethereum",
+///   "nonce": 7,
+///   "gas_limit": 65000,
+///   "max_fee_per_gas": "30000000000",  // or "gas_price" for legacy
+///   "max_priority_fee_per_gas": "1000000000"
+/// }
+/// ```
+///
+/// # Output
+/// ```json
+/// { "function": "transfer(address,uint256)", "calldata": "0xa9059cbb...", "transaction": { ... } }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_build_contract_call(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        abi: serde_json::Value,
+        function: String,
+        #[serde(default)]
+        args: Vec<serde_json::Value>,
+        to: String,
+        #[serde(default)]
+        value: Option<String>,
+        #[serde(flatten)]
+        params: crate::tx::ContractCallParams,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let abi = match crate::abi::ContractAbi::from_json_value(request.abi) {
+        Ok(abi) => abi,
+        Err(e) => return error_response(HawalaError::invalid_input(e.to_string())),
+    };
+
+    match crate::tx::build_contract_call(
+        &abi,
+        &request.function,
+        &request.args,
+        &request.to,
+        request.value.as_deref().unwrap_or("0"),
+        &request.params,
+    ) {
+        Ok(transaction) => success_response(serde_json::json!({
+            "function": transaction.data.get(..4)
+                .and_then(|selector| <[u8; 4]>::try_from(selector).ok())
+                .and_then(|selector| abi.function_by_selector(&selector))
+                .map(|f| f.signature()),
+            "calldata": format!("0x{}", hex::encode(&transaction.data)),
+            "transaction": transaction,
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Hash bytes with one of the crate's hash functions
+///
+/// # Input
+/// ```json
+/// {
+///   "algorithm": "sha256",  // sha256, sha256d, keccak256, blake2b256, ripemd160, hash160
+///   "data_hex": "0x616263"
+/// }
+/// ```
+///
+/// # Output
+/// ```json
+/// { "algorithm": "sha256", "digest": "ba7816bf..." }
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn hawala_hash(input: *const c_char) -> *mut c_char {
+    let json_str = match parse_input(input) {
+        Ok(s) => s,
+        Err(ptr) => return ptr,
+    };
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        algorithm: crate::utils::crypto::HashAlgorithm,
+        data_hex: String,
+    }
+
+    let request: Request = match serde_json::from_str(json_str) {
+        Ok(r) => r,
+        Err(e) => return error_response(HawalaError::parse_error(format!("Invalid JSON: {}", e))),
+    };
+
+    let data = match hex::decode(request.data_hex.trim_start_matches("0x")) {
+        Ok(data) => data,
+        Err(e) => return error_response(HawalaError::invalid_input(format!("Invalid data_hex: {}", e))),
+    };
+
+    success_response(serde_json::json!({
+        "algorithm": request.algorithm,
+        "digest": hex::encode(crate::utils::crypto::hash(request.algorithm, &data)),
+    }))
+}
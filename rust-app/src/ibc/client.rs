@@ -4,8 +4,12 @@
 
 use super::types::*;
 use super::channels::ChannelRegistry;
+use base64::{Engine as _, engine::general_purpose::STANDARD};
 use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Multiplier on simulated gas, as with the Cosmos SDK's `--gas-adjustment`
+pub const DEFAULT_GAS_ADJUSTMENT: f64 = 1.3;
 
 /// IBC Client for chain interactions
 pub struct IBCClient {
@@ -127,6 +131,52 @@ impl IBCClient {
         })
     }
 
+    /// Simulate a transaction and return the gas it used
+    ///
+    /// `tx_bytes` is an encoded `TxRaw`. Signatures may be empty: simulation
+    /// skips signature checks but needs the signer's public key.
+    /// Calls `POST /cosmos/tx/v1beta1/simulate`.
+    pub async fn simulate_tx(
+        &self,
+        chain: IBCChain,
+        tx_bytes: &[u8],
+    ) -> Result<u64, IBCError> {
+        let client = self.get_client(chain)?;
+        let url = format!("{}/cosmos/tx/v1beta1/simulate", client.rest_endpoint.trim_end_matches('/'));
+        let failed = |error: String| IBCError::SimulationFailed { chain, error };
+        
+        let response = reqwest::Client::new()
+            .post(&url)
+            .timeout(Duration::from_secs(15))
+            .json(&serde_json::json!({ "tx_bytes": STANDARD.encode(tx_bytes) }))
+            .send()
+            .await
+            .map_err(|e| failed(e.to_string()))?;
+        let body = response.text().await.map_err(|e| failed(e.to_string()))?;
+        
+        parse_simulate_response(&body).map_err(failed)
+    }
+
+    /// Fee estimate from simulated gas at the chain's minimum gas price
+    pub fn fee_from_simulation(
+        &self,
+        chain: IBCChain,
+        gas_used: u64,
+        gas_adjustment: f64,
+    ) -> Result<IBCFeeEstimate, IBCError> {
+        let gas_price = self.get_gas_price(chain);
+        let (gas_limit, fee_amount) = fee_for_gas(gas_used, &gas_price, gas_adjustment)
+            .map_err(|error| IBCError::SimulationFailed { chain, error })?;
+        
+        Ok(IBCFeeEstimate {
+            gas_limit,
+            gas_price,
+            fee_amount: fee_amount.to_string(),
+            fee_denom: chain.native_denom().to_string(),
+            fee_usd: None,
+        })
+    }
+
     /// Broadcast a signed transaction
     pub async fn broadcast_tx(
         &self,
@@ -256,8 +306,8 @@ impl IBCClient {
         Ok(())
     }
 
-    /// Get gas price for a chain
-    fn get_gas_price(&self, chain: IBCChain) -> String {
+    /// Minimum gas price for a chain, in native denom per unit of gas
+    pub fn get_gas_price(&self, chain: IBCChain) -> String {
         match chain {
             IBCChain::CosmosHub => "0.025".to_string(),
             IBCChain::Osmosis => "0.025".to_string(),
@@ -358,6 +408,52 @@ pub struct PacketAcknowledgement {
     pub success: bool,
 }
 
+/// Extract `gas_info.gas_used` from a simulate response
+///
+/// Error responses (`{"code": .., "message": ..}`) return the node's message.
+pub fn parse_simulate_response(body: &str) -> Result<u64, String> {
+    let json: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| format!("Invalid simulate response: {}", e))?;
+    
+    if let Some(message) = json.get("message").and_then(|m| m.as_str()) {
+        return Err(message.to_string());
+    }
+    
+    // Cosmos REST encodes uint64 as a string
+    let gas_used = json.pointer("/gas_info/gas_used")
+        .ok_or_else(|| "Simulate response has no gas_info.gas_used".to_string())?;
+    match gas_used {
+        serde_json::Value::String(s) => s.parse().map_err(|_| format!("Invalid gas_used: {}", s)),
+        serde_json::Value::Number(n) => n.as_u64().ok_or_else(|| format!("Invalid gas_used: {}", n)),
+        other => Err(format!("Invalid gas_used: {}", other)),
+    }
+}
+
+/// Gas limit `ceil(gas_used × gas_adjustment)` and fee
+/// `ceil(gas_limit × gas_price)`, with `gas_price` a decimal string
+pub fn fee_for_gas(gas_used: u64, gas_price: &str, gas_adjustment: f64) -> Result<(u64, u128), String> {
+    if !gas_adjustment.is_finite() || gas_adjustment < 1.0 {
+        return Err(format!("Gas adjustment must be at least 1.0, got {}", gas_adjustment));
+    }
+    let gas_limit = (gas_used as f64 * gas_adjustment).ceil() as u64;
+    
+    // Exact decimal arithmetic: price = numerator / 10^decimals
+    let (whole, fraction) = gas_price.split_once('.').unwrap_or((gas_price, ""));
+    let digits = format!("{}{}", whole, fraction);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) || fraction.len() > 18 {
+        return Err(format!("Invalid gas price: {}", gas_price));
+    }
+    let numerator: u128 = digits.parse().map_err(|_| format!("Invalid gas price: {}", gas_price))?;
+    let denominator = 10u128.pow(fraction.len() as u32);
+    
+    let fee = u128::from(gas_limit)
+        .checked_mul(numerator)
+        .map(|product| product.div_ceil(denominator))
+        .ok_or_else(|| "Fee overflows".to_string())?;
+    
+    Ok((gas_limit, fee))
+}
+
 /// Get current timestamp in seconds
 fn current_timestamp() -> u64 {
     SystemTime::now()
@@ -448,6 +544,37 @@ mod tests {
         assert!(fee.gas_limit >= 200_000);
     }
 
+    #[test]
+    fn test_simulate_response_to_fee() {
+        let body = r#"{
+            "gas_info": { "gas_wanted": "0", "gas_used": "98765" },
+            "result": { "data": "", "log": "", "events": [] }
+        }"#;
+        let gas_used = parse_simulate_response(body).unwrap();
+        assert_eq!(gas_used, 98_765);
+
+        // 98765 × 1.3 = 128394.5 → 128395 gas; × 0.025 = 3209.875 → 3210 uatom
+        let client = IBCClient::new();
+        let fee = client.fee_from_simulation(IBCChain::CosmosHub, gas_used, DEFAULT_GAS_ADJUSTMENT).unwrap();
+        assert_eq!(fee.gas_limit, 128_395);
+        assert_eq!(fee.fee_amount, "3210");
+        assert_eq!(fee.fee_denom, "uatom");
+
+        // Whole-number prices (18-decimal chains)
+        assert_eq!(fee_for_gas(100_000, "20000000000", 1.0).unwrap(), (100_000, 2_000_000_000_000_000));
+    }
+
+    #[test]
+    fn test_simulate_response_errors() {
+        let error = r#"{"code": 13, "message": "insufficient fees; got: 0uatom", "details": []}"#;
+        assert_eq!(parse_simulate_response(error).unwrap_err(), "insufficient fees; got: 0uatom");
+        assert!(parse_simulate_response(r#"{"gas_info": {}}"#).is_err());
+        assert!(parse_simulate_response("not json").is_err());
+
+        assert!(fee_for_gas(1, "abc", 1.3).is_err());
+        assert!(fee_for_gas(1, "0.025", 0.5).is_err());
+    }
+
     #[test]
     fn test_broadcast_result() {
         let success = BroadcastResult {
@@ -164,6 +164,10 @@ mod integration_tests {
                 length: 500,
                 max: 256,
             },
+            IBCError::SimulationFailed {
+                chain: IBCChain::Osmosis,
+                error: "out of gas".to_string(),
+            },
         ];
         
         for error in errors {
@@ -506,6 +506,10 @@ pub enum IBCError {
         length: usize,
         max: usize,
     },
+    SimulationFailed {
+        chain: IBCChain,
+        error: String,
+    },
 }
 
 impl std::fmt::Display for IBCError {
@@ -535,6 +539,9 @@ impl std::fmt::Display for IBCError {
             IBCError::MemoTooLong { length, max } => {
                 write!(f, "Memo too long: {} bytes (max {})", length, max)
             }
+            IBCError::SimulationFailed { chain, error } => {
+                write!(f, "Simulation on {} failed: {}", chain.display_name(), error)
+            }
         }
     }
 }
@@ -144,6 +144,9 @@ pub mod crypto;
 // QR code support for air-gapped signing
 pub mod qr;
 
+// WalletConnect v2 session request parsing
+pub mod walletconnect;
+
 // Re-export key types for convenience
 pub use error::{HawalaError, HawalaResult, ErrorCode};
 pub use types::*;
@@ -151,7 +154,10 @@ pub use types::*;
 // Re-export wallet functions
 pub use wallet::{
     create_new_wallet,
+    create_new_wallet_on,
+    create_new_wallet_with_strength,
     generate_keys_from_seed,
+    generate_selected_keys,
     restore_from_mnemonic,
     validate_mnemonic as wallet_validate_mnemonic,
 };
@@ -203,6 +209,7 @@ pub use ethereum_wallet::prepare_ethereum_transaction;
 pub use solana_wallet::prepare_solana_transaction;
 pub use monero_wallet::prepare_monero_transaction;
 pub use xrp_wallet::prepare_xrp_transaction;
+pub use tezos_wallet::prepare_tezos_transaction;
 pub use litecoin_wallet::prepare_litecoin_transaction;
 pub use taproot_wallet::{prepare_taproot_transaction, prepare_taproot_transaction_from_wif, derive_taproot_address};
 pub use history_legacy::fetch_bitcoin_history;
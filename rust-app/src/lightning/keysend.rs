@@ -0,0 +1,216 @@
+//! Keysend (spontaneous) payments
+//!
+//! The sender picks the preimage and delivers it to the recipient in the
+//! final hop's onion payload, so no invoice is needed. Custom records ride
+//! along in the same TLV stream (e.g. podcasting 2.0 boostagrams).
+
+use std::collections::BTreeMap;
+
+use sha2::{Digest, Sha256};
+
+use super::types::*;
+
+/// TLV type carrying the keysend preimage
+pub const KEYSEND_RECORD_TYPE: u64 = 5482373484;
+
+/// Custom records must use types at or above this value
+pub const CUSTOM_RECORD_MIN_TYPE: u64 = 65536;
+
+/// Final CLTV delta used by LND and CLN for keysend
+pub const KEYSEND_FINAL_CLTV_DELTA: u32 = 40;
+
+// Final hop payload TLV types (BOLT4)
+const AMT_TO_FORWARD: u64 = 2;
+const OUTGOING_CLTV_VALUE: u64 = 4;
+
+/// Keysend payment ready for routing
+#[derive(Debug, Clone)]
+pub struct KeysendPayment {
+    /// Destination node public key (33 bytes, hex encoded)
+    pub destination: String,
+    /// Amount to deliver
+    pub amount_msat: MilliSatoshi,
+    /// SHA256 of the preimage (hex encoded)
+    pub payment_hash: String,
+    /// Preimage (hex encoded)
+    pub preimage: String,
+    /// CLTV delta for the final hop
+    pub final_cltv_delta: u32,
+    /// Records for the destination, including the keysend record
+    /// (LND `dest_custom_records` / CLN `extratlvs`)
+    pub dest_custom_records: BTreeMap<u64, Vec<u8>>,
+}
+
+impl KeysendPayment {
+    /// TLV stream for the final hop's onion payload
+    ///
+    /// `current_height` sets the absolute `outgoing_cltv_value`. The stream
+    /// is returned without its BigSize length prefix.
+    pub fn final_hop_payload(&self, current_height: u32) -> Vec<u8> {
+        let mut payload = Vec::new();
+        write_record(&mut payload, AMT_TO_FORWARD, &truncated(self.amount_msat.as_msat()));
+        write_record(
+            &mut payload,
+            OUTGOING_CLTV_VALUE,
+            &truncated(u64::from(current_height) + u64::from(self.final_cltv_delta)),
+        );
+        // BTreeMap iterates in ascending type order, as TLV streams require
+        for (tlv_type, value) in &self.dest_custom_records {
+            write_record(&mut payload, *tlv_type, value);
+        }
+        payload
+    }
+}
+
+/// Build a keysend payment to `dest_pubkey`
+///
+/// `custom_records` may not use the keysend type or types below 65536.
+pub fn build_keysend(
+    dest_pubkey: &str,
+    amount_msat: MilliSatoshi,
+    preimage: [u8; 32],
+    custom_records: &BTreeMap<u64, Vec<u8>>,
+) -> Result<KeysendPayment, LightningError> {
+    let pubkey = hex::decode(dest_pubkey)
+        .map_err(|_| LightningError::InvalidKeysend("Destination is not hex".to_string()))?;
+    if pubkey.len() != 33 || secp256k1::PublicKey::from_slice(&pubkey).is_err() {
+        return Err(LightningError::InvalidKeysend("Invalid destination public key".to_string()));
+    }
+    if amount_msat.as_msat() == 0 {
+        return Err(LightningError::InvalidKeysend("Amount must be positive".to_string()));
+    }
+
+    let mut records = BTreeMap::new();
+    for (tlv_type, value) in custom_records {
+        if *tlv_type < CUSTOM_RECORD_MIN_TYPE || *tlv_type == KEYSEND_RECORD_TYPE {
+            return Err(LightningError::InvalidKeysend(format!("Reserved record type {}", tlv_type)));
+        }
+        records.insert(*tlv_type, value.clone());
+    }
+    records.insert(KEYSEND_RECORD_TYPE, preimage.to_vec());
+
+    Ok(KeysendPayment {
+        destination: hex::encode(pubkey),
+        amount_msat,
+        payment_hash: hex::encode(payment_hash(&preimage)),
+        preimage: hex::encode(preimage),
+        final_cltv_delta: KEYSEND_FINAL_CLTV_DELTA,
+        dest_custom_records: records,
+    })
+}
+
+/// Random 32-byte preimage
+pub fn generate_preimage() -> [u8; 32] {
+    rand::random()
+}
+
+/// Payment hash for a preimage
+pub fn payment_hash(preimage: &[u8; 32]) -> [u8; 32] {
+    Sha256::digest(preimage).into()
+}
+
+fn write_record(out: &mut Vec<u8>, tlv_type: u64, value: &[u8]) {
+    write_bigsize(out, tlv_type);
+    write_bigsize(out, value.len() as u64);
+    out.extend_from_slice(value);
+}
+
+/// BOLT1 BigSize
+fn write_bigsize(out: &mut Vec<u8>, value: u64) {
+    match value {
+        0..=0xfc => out.push(value as u8),
+        0xfd..=0xffff => {
+            out.push(0xfd);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(0xfe);
+            out.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(0xff);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+/// Big-endian integer without leading zero bytes (tu64/tu32)
+fn truncated(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    bytes[start..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEST: &str = "02eec7245d6b7d2ccb30380bfbe2a3648cd7a942653f5aa340edcea1f283686619";
+
+    #[test]
+    fn test_payment_hash_from_preimage() {
+        let payment = build_keysend(DEST, MilliSatoshi::from_sat(1), [0u8; 32], &BTreeMap::new()).unwrap();
+
+        assert_eq!(payment.preimage, "00".repeat(32));
+        assert_eq!(payment.payment_hash, "66687aadf862bd776c8fc18b8e9f8e20089714856ee233b3902a591d0d5f2925");
+        assert_eq!(payment.dest_custom_records.get(&KEYSEND_RECORD_TYPE), Some(&vec![0u8; 32]));
+
+        let preimage = generate_preimage();
+        let payment = build_keysend(DEST, MilliSatoshi::from_sat(1), preimage, &BTreeMap::new()).unwrap();
+        assert_eq!(payment.payment_hash, hex::encode(payment_hash(&preimage)));
+    }
+
+    #[test]
+    fn test_final_hop_payload_encoding() {
+        let mut custom = BTreeMap::new();
+        custom.insert(7629169, br#"{"action":"boost"}"#.to_vec());
+
+        let payment = build_keysend(DEST, MilliSatoshi(1000), [1u8; 32], &custom).unwrap();
+        let payload = payment.final_hop_payload(800_000);
+
+        let mut expected = hex::decode("020203e8").unwrap();
+        // 800000 + 40 = 0x0c3528
+        expected.extend(hex::decode("04030c3528").unwrap());
+        // Boostagram record, 4-byte BigSize type
+        expected.extend(hex::decode("fe0074697112").unwrap());
+        expected.extend(br#"{"action":"boost"}"#);
+        // Keysend record, 8-byte BigSize type
+        expected.extend(hex::decode("ff0000000146c6616c20").unwrap());
+        expected.extend([1u8; 32]);
+
+        assert_eq!(payload, expected);
+    }
+
+    #[test]
+    fn test_invalid_keysend() {
+        let mut reserved = BTreeMap::new();
+        reserved.insert(KEYSEND_RECORD_TYPE, vec![0u8; 32]);
+        assert!(build_keysend(DEST, MilliSatoshi(1000), [0u8; 32], &reserved).is_err());
+
+        let mut low = BTreeMap::new();
+        low.insert(100, vec![1]);
+        assert!(build_keysend(DEST, MilliSatoshi(1000), [0u8; 32], &low).is_err());
+
+        assert!(build_keysend("02abcd", MilliSatoshi(1000), [0u8; 32], &BTreeMap::new()).is_err());
+        assert!(build_keysend(DEST, MilliSatoshi(0), [0u8; 32], &BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_bigsize_boundaries() {
+        for (value, encoded) in [
+            (0u64, "00"),
+            (0xfc, "fc"),
+            (0xfd, "fd00fd"),
+            (0xffff, "fdffff"),
+            (0x10000, "fe00010000"),
+            (0xffffffff, "feffffffff"),
+            (0x100000000, "ff0000000100000000"),
+        ] {
+            let mut out = Vec::new();
+            write_bigsize(&mut out, value);
+            assert_eq!(hex::encode(out), encoded);
+        }
+        assert!(truncated(0).is_empty());
+        assert_eq!(truncated(0x0100), vec![0x01, 0x00]);
+    }
+}
@@ -1,9 +1,12 @@
 //! Lightning Network module
 //!
-//! Provides BOLT11 invoice parsing and LNUrl support for Lightning payments.
+//! Provides BOLT11 invoice parsing, BOLT12 offer parsing, keysend payments
+//! and LNUrl support for Lightning payments.
 
 pub mod types;
 pub mod invoice;
+pub mod offer;
+pub mod keysend;
 pub mod lnurl;
 
 #[cfg(test)]
@@ -11,4 +14,6 @@ pub mod tests;
 
 pub use types::*;
 pub use invoice::*;
+pub use offer::*;
+pub use keysend::*;
 pub use lnurl::*;
@@ -0,0 +1,390 @@
+//! BOLT12 offer parsing
+//!
+//! Offers are bech32-encoded TLV streams with the `lno` prefix. Unlike
+//! BOLT11 they carry no checksum and may be split with `+` for display.
+
+use super::types::*;
+
+/// Human-readable part of an offer string
+const OFFER_HRP: &str = "lno";
+
+/// Bech32 alphabet
+const CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+// Offer TLV types
+const OFFER_CHAINS: u64 = 2;
+const OFFER_METADATA: u64 = 4;
+const OFFER_CURRENCY: u64 = 6;
+const OFFER_AMOUNT: u64 = 8;
+const OFFER_DESCRIPTION: u64 = 10;
+const OFFER_FEATURES: u64 = 12;
+const OFFER_ABSOLUTE_EXPIRY: u64 = 14;
+const OFFER_PATHS: u64 = 16;
+const OFFER_ISSUER: u64 = 18;
+const OFFER_QUANTITY_MAX: u64 = 20;
+const OFFER_ISSUER_ID: u64 = 22;
+
+/// Genesis block hashes as used in `chain_hash` (internal byte order)
+const CHAIN_HASHES: [(&str, LightningNetwork); 4] = [
+    ("6fe28c0ab6f1b372c1a6a246ae63f74f931e8365e15a089c68d6190000000000", LightningNetwork::Mainnet),
+    ("43497fd7f826957108f4a30fd9cec3aeba79972084e90ead01ea330900000000", LightningNetwork::Testnet),
+    ("f61eee3b63a380a477a063af32b2bbc97c9ff9f01f2c4225e973988108000000", LightningNetwork::Signet),
+    ("06226e46111a0b59caaf126043eb5bbf28c34f3a5e332a1fc7b2b73cf188910f", LightningNetwork::Regtest),
+];
+
+fn invalid(msg: impl Into<String>) -> LightningError {
+    LightningError::InvalidOffer(msg.into())
+}
+
+/// Parse a BOLT12 offer string
+pub fn parse_offer(offer: &str) -> Result<Offer, LightningError> {
+    // Rejoin strings split with '+' (optionally followed by whitespace)
+    let mut joined = String::new();
+    for (i, part) in offer.trim().split('+').enumerate() {
+        let part = if i > 0 { part.trim_start() } else { part };
+        if part.is_empty() {
+            return Err(invalid("Empty segment around '+'"));
+        }
+        joined.push_str(part);
+    }
+
+    if joined.chars().any(|c| c.is_ascii_uppercase()) && joined.chars().any(|c| c.is_ascii_lowercase()) {
+        return Err(invalid("Mixed case"));
+    }
+    let joined = joined.to_lowercase();
+
+    let (hrp, data) = joined.rsplit_once('1').ok_or_else(|| invalid("Missing separator '1'"))?;
+    if hrp != OFFER_HRP {
+        return Err(invalid(format!("Expected '{}' prefix, got '{}'", OFFER_HRP, hrp)));
+    }
+
+    let values = data.chars()
+        .map(|c| CHARSET.find(c).map(|v| v as u8).ok_or_else(|| invalid(format!("Invalid character '{}'", c))))
+        .collect::<Result<Vec<u8>, _>>()?;
+    let bytes = bech32::convert_bits(&values, 5, 8, false)
+        .map_err(|e| invalid(format!("Invalid bech32 data: {}", e)))?;
+
+    let mut parsed = parse_offer_tlv(&bytes)?;
+    parsed.raw = joined;
+    Ok(parsed)
+}
+
+/// Parse the TLV stream of an offer
+fn parse_offer_tlv(bytes: &[u8]) -> Result<Offer, LightningError> {
+    let mut offer = Offer {
+        raw: String::new(),
+        chains: Vec::new(),
+        network: None,
+        metadata: None,
+        currency: None,
+        amount: None,
+        description: None,
+        features: None,
+        absolute_expiry: None,
+        paths: Vec::new(),
+        issuer: None,
+        quantity_max: None,
+        node_id: None,
+    };
+
+    let mut stream = Reader::new(bytes);
+    let mut last_type = None;
+    while !stream.is_empty() {
+        let tlv_type = stream.bigsize()?;
+        if last_type.is_some_and(|last| tlv_type <= last) {
+            return Err(invalid("TLV types not strictly increasing"));
+        }
+        last_type = Some(tlv_type);
+
+        let length = usize::try_from(stream.bigsize()?).map_err(|_| invalid("TLV length too large"))?;
+        let value = stream.take(length)?;
+
+        // Offers may only contain offer fields (1-79) and experimental ones
+        if !(1..=79).contains(&tlv_type) && !(1_000_000_000..=1_999_999_999).contains(&tlv_type) {
+            return Err(invalid(format!("Unexpected TLV type {}", tlv_type)));
+        }
+
+        match tlv_type {
+            OFFER_CHAINS => {
+                if value.is_empty() || value.len() % 32 != 0 {
+                    return Err(invalid("Invalid offer_chains"));
+                }
+                offer.chains = value.chunks(32).map(hex::encode).collect();
+            }
+            OFFER_METADATA => offer.metadata = Some(hex::encode(value)),
+            OFFER_CURRENCY => offer.currency = Some(utf8(value, "offer_currency")?),
+            OFFER_AMOUNT => offer.amount = Some(tu64(value)?),
+            OFFER_DESCRIPTION => offer.description = Some(utf8(value, "offer_description")?),
+            OFFER_FEATURES => offer.features = Some(hex::encode(value)),
+            OFFER_ABSOLUTE_EXPIRY => offer.absolute_expiry = Some(tu64(value)?),
+            OFFER_PATHS => offer.paths = parse_paths(value)?,
+            OFFER_ISSUER => offer.issuer = Some(utf8(value, "offer_issuer")?),
+            OFFER_QUANTITY_MAX => offer.quantity_max = Some(tu64(value)?),
+            OFFER_ISSUER_ID => offer.node_id = Some(point(value)?),
+            // It's okay to be odd
+            t if t % 2 == 1 => {}
+            t => return Err(invalid(format!("Unknown even TLV type {}", t))),
+        }
+    }
+
+    if offer.amount.is_some() && offer.description.is_none() {
+        return Err(invalid("offer_amount without offer_description"));
+    }
+    if offer.currency.is_some() && offer.amount.is_none() {
+        return Err(invalid("offer_currency without offer_amount"));
+    }
+    if offer.node_id.is_none() && offer.paths.is_empty() {
+        return Err(invalid("Missing offer_issuer_id and offer_paths"));
+    }
+
+    offer.network = if offer.chains.is_empty() {
+        Some(LightningNetwork::Mainnet)
+    } else {
+        let networks: Vec<_> = offer.chains.iter().map(|c| network_for_chain(c)).collect();
+        match networks.first() {
+            Some(Some(first)) if networks.iter().all(|n| *n == Some(*first)) => Some(*first),
+            _ => None,
+        }
+    };
+
+    Ok(offer)
+}
+
+/// Network for a hex chain hash
+fn network_for_chain(chain_hash: &str) -> Option<LightningNetwork> {
+    CHAIN_HASHES.iter()
+        .find(|(hash, _)| *hash == chain_hash)
+        .map(|(_, network)| *network)
+}
+
+fn parse_paths(value: &[u8]) -> Result<Vec<BlindedPath>, LightningError> {
+    let mut reader = Reader::new(value);
+    let mut paths = Vec::new();
+
+    while !reader.is_empty() {
+        let introduction_node = match reader.peek()? {
+            0 | 1 => {
+                let direction = reader.take(1)?[0];
+                let scid = u64::from_be_bytes(reader.array::<8>()?);
+                IntroductionNode::ShortChannelId {
+                    short_channel_id: format!("{}x{}x{}", scid >> 40, (scid >> 16) & 0xff_ffff, scid & 0xffff),
+                    direction,
+                }
+            }
+            _ => IntroductionNode::NodeId(point(reader.take(33)?)?),
+        };
+        let blinding_point = point(reader.take(33)?)?;
+
+        let num_hops = reader.take(1)?[0];
+        if num_hops == 0 {
+            return Err(invalid("Blinded path without hops"));
+        }
+
+        let mut hops = Vec::with_capacity(num_hops as usize);
+        for _ in 0..num_hops {
+            let blinded_node_id = point(reader.take(33)?)?;
+            let length = u16::from_be_bytes(reader.array::<2>()?) as usize;
+            hops.push(BlindedHop {
+                blinded_node_id,
+                encrypted_data: hex::encode(reader.take(length)?),
+            });
+        }
+
+        paths.push(BlindedPath { introduction_node, blinding_point, hops });
+    }
+
+    if paths.is_empty() {
+        return Err(invalid("Empty offer_paths"));
+    }
+    Ok(paths)
+}
+
+/// Truncated big-endian u64 (no leading zeros)
+fn tu64(value: &[u8]) -> Result<u64, LightningError> {
+    if value.len() > 8 || value.first() == Some(&0) {
+        return Err(invalid("Invalid tu64"));
+    }
+    Ok(value.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64))
+}
+
+fn utf8(value: &[u8], field: &str) -> Result<String, LightningError> {
+    String::from_utf8(value.to_vec()).map_err(|_| invalid(format!("{} is not valid UTF-8", field)))
+}
+
+/// Compressed secp256k1 public key, hex encoded
+fn point(value: &[u8]) -> Result<String, LightningError> {
+    if value.len() != 33 || secp256k1::PublicKey::from_slice(value).is_err() {
+        return Err(invalid("Invalid public key"));
+    }
+    Ok(hex::encode(value))
+}
+
+/// Cursor over a byte slice
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn peek(&self) -> Result<u8, LightningError> {
+        self.bytes.first().copied().ok_or_else(|| invalid("Unexpected end of data"))
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], LightningError> {
+        if self.bytes.len() < n {
+            return Err(invalid("Unexpected end of data"));
+        }
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], LightningError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    /// BOLT1 BigSize, minimally encoded
+    fn bigsize(&mut self) -> Result<u64, LightningError> {
+        let (value, min) = match self.take(1)?[0] {
+            0xfd => (u16::from_be_bytes(self.array()?) as u64, 0xfd),
+            0xfe => (u32::from_be_bytes(self.array()?) as u64, 0x1_0000),
+            0xff => (u64::from_be_bytes(self.array()?), 0x1_0000_0000),
+            b => return Ok(b as u64),
+        };
+        if value < min {
+            return Err(invalid("Non-minimal BigSize"));
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUER_ID: &str = "02eec7245d6b7d2ccb30380bfbe2a3648cd7a942653f5aa340edcea1f283686619";
+
+    /// Bech32-encode a TLV stream as an offer
+    fn encode_offer(bytes: &[u8]) -> String {
+        let values = bech32::convert_bits(bytes, 8, 5, true).unwrap();
+        let data: String = values.iter().map(|v| CHARSET.as_bytes()[*v as usize] as char).collect();
+        format!("{}1{}", OFFER_HRP, data)
+    }
+
+    fn tlv(tlv_type: u8, value: &[u8]) -> Vec<u8> {
+        let mut out = vec![tlv_type, value.len() as u8];
+        out.extend_from_slice(value);
+        out
+    }
+
+    #[test]
+    fn test_parse_spec_offer_with_description() {
+        // "with description (but no amount)" from bolt12/offers-test.json
+        let offer = parse_offer("lno1pgx9getnwss8vetrw3hhyuckyypwa3eyt44h6txtxquqh7lz5djge4afgfjn7k4rgrkuag0jsd5xvxg").unwrap();
+
+        assert_eq!(offer.description.as_deref(), Some("Test vectors"));
+        assert_eq!(offer.node_id.as_deref(), Some(ISSUER_ID));
+        assert_eq!(offer.network, Some(LightningNetwork::Mainnet));
+        assert!(offer.is_zero_amount());
+        assert!(offer.paths.is_empty());
+        assert!(!offer.is_expired());
+    }
+
+    #[test]
+    fn test_split_and_uppercase_offer() {
+        let offer = parse_offer("LNO1PGX9GETNWSS8VETRW3HHYUCKYYPWA3EYT44H6TXTXQUQH7LZ5DJGE4AFGF+\n  JN7K4RGRKUAG0JSD5XVXG").unwrap();
+        assert_eq!(offer.description.as_deref(), Some("Test vectors"));
+
+        assert!(parse_offer("lno1pgx9getnwss8vetrw3hhyuckyypwa3eyt44h6txtxquqh7lz5djge4afgf+").is_err());
+        assert!(parse_offer("LNO1pgx9getnwss8vetrw3hhyuckyypwa3eyt44h6txtxquqh7lz5djge4afgfjn7k4rgrkuag0jsd5xvxg").is_err());
+        assert!(parse_offer("lnbc1pgx9getnwss8vetrw3hhyuckyypwa3eyt44h6txtxquqh7lz5djge4afgfjn7k4rgrkuag0jsd5xvxg").is_err());
+    }
+
+    #[test]
+    fn test_parse_full_offer() {
+        let issuer_id = hex::decode(ISSUER_ID).unwrap();
+        let testnet = hex::decode(CHAIN_HASHES[1].0).unwrap();
+
+        // Path: scid intro node, path key, one hop with 3 bytes of data
+        let mut path = vec![0x01];
+        path.extend_from_slice(&((700_000u64 << 40) | (12 << 16) | 1).to_be_bytes());
+        path.extend_from_slice(&issuer_id);
+        path.push(1);
+        path.extend_from_slice(&issuer_id);
+        path.extend_from_slice(&[0x00, 0x03, 0xaa, 0xbb, 0xcc]);
+
+        let mut bytes = Vec::new();
+        bytes.extend(tlv(2, &testnet));
+        bytes.extend(tlv(6, b"USD"));
+        bytes.extend(tlv(8, &[0x03, 0xe8]));
+        bytes.extend(tlv(10, b"Coffee"));
+        bytes.extend(tlv(14, &[0x65, 0x00, 0x00, 0x00]));
+        bytes.extend(tlv(16, &path));
+        bytes.extend(tlv(18, b"Hawala Cafe"));
+        bytes.extend(tlv(20, &[0x05]));
+        bytes.extend(tlv(33, b"ignored"));
+
+        let offer = parse_offer(&encode_offer(&bytes)).unwrap();
+
+        assert_eq!(offer.network, Some(LightningNetwork::Testnet));
+        assert_eq!(offer.currency.as_deref(), Some("USD"));
+        assert_eq!(offer.amount, Some(1000));
+        assert_eq!(offer.amount_msat(), None);
+        assert_eq!(offer.description.as_deref(), Some("Coffee"));
+        assert_eq!(offer.issuer.as_deref(), Some("Hawala Cafe"));
+        assert_eq!(offer.absolute_expiry, Some(0x6500_0000));
+        assert_eq!(offer.quantity_max, Some(5));
+        assert_eq!(offer.node_id, None);
+
+        assert_eq!(offer.paths.len(), 1);
+        assert_eq!(
+            offer.paths[0].introduction_node,
+            IntroductionNode::ShortChannelId { short_channel_id: "700000x12x1".into(), direction: 1 }
+        );
+        assert_eq!(offer.paths[0].hops.len(), 1);
+        assert_eq!(offer.paths[0].hops[0].encrypted_data, "aabbcc");
+    }
+
+    #[test]
+    fn test_invalid_offers() {
+        let issuer_id = tlv(22, &hex::decode(ISSUER_ID).unwrap());
+
+        // Amount without description
+        let mut bytes = tlv(8, &[0x64]);
+        bytes.extend(&issuer_id);
+        assert!(parse_offer(&encode_offer(&bytes)).is_err());
+
+        // Neither issuer id nor paths
+        assert!(parse_offer(&encode_offer(&tlv(10, b"x"))).is_err());
+
+        // Unknown even type
+        let mut bytes = issuer_id.clone();
+        bytes.extend(tlv(24, b"x"));
+        assert!(parse_offer(&encode_offer(&bytes)).is_err());
+
+        // Out-of-order types
+        let mut bytes = issuer_id.clone();
+        bytes.extend(tlv(10, b"x"));
+        assert!(parse_offer(&encode_offer(&bytes)).is_err());
+
+        // Non-minimal tu64
+        let mut bytes = tlv(8, &[0x00, 0x64]);
+        bytes.extend(tlv(10, b"x"));
+        bytes.extend(&issuer_id);
+        assert!(parse_offer(&encode_offer(&bytes)).is_err());
+
+        // Invoice request field
+        let mut bytes = issuer_id;
+        bytes.extend(tlv(82, b"x"));
+        assert!(parse_offer(&encode_offer(&bytes)).is_err());
+    }
+}
@@ -149,6 +149,92 @@ pub struct RouteHint {
     pub cltv_expiry_delta: u16,
 }
 
+/// Parsed BOLT12 offer (`lno...`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Offer {
+    /// Raw offer string
+    pub raw: String,
+    /// Chains the offer is valid for (hex chain hashes; empty means Bitcoin mainnet)
+    pub chains: Vec<String>,
+    /// Network, if every chain is a known Bitcoin network
+    pub network: Option<LightningNetwork>,
+    /// Issuer metadata (hex encoded)
+    pub metadata: Option<String>,
+    /// ISO 4217 currency code; amounts are in its minor unit when set
+    pub currency: Option<String>,
+    /// Amount per item (millisatoshis unless `currency` is set)
+    pub amount: Option<u64>,
+    /// Description
+    pub description: Option<String>,
+    /// Feature bits (hex encoded)
+    pub features: Option<String>,
+    /// Expiry (Unix seconds)
+    pub absolute_expiry: Option<u64>,
+    /// Blinded paths to the issuer
+    pub paths: Vec<BlindedPath>,
+    /// Issuer name
+    pub issuer: Option<String>,
+    /// Maximum quantity per invoice (0 = unlimited)
+    pub quantity_max: Option<u64>,
+    /// Issuer node public key (`offer_issuer_id`, 33 bytes, hex encoded)
+    pub node_id: Option<String>,
+}
+
+impl Offer {
+    /// Check if offer is expired
+    pub fn is_expired(&self) -> bool {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        self.absolute_expiry.is_some_and(|expiry| now > expiry)
+    }
+
+    /// Amount in millisatoshis, if denominated in bitcoin
+    pub fn amount_msat(&self) -> Option<MilliSatoshi> {
+        match self.currency {
+            Some(_) => None,
+            None => self.amount.map(MilliSatoshi),
+        }
+    }
+
+    /// Check if payer chooses the amount
+    pub fn is_zero_amount(&self) -> bool {
+        self.amount.is_none()
+    }
+}
+
+/// Blinded route to an offer issuer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlindedPath {
+    /// Unblinded entry point of the path
+    pub introduction_node: IntroductionNode,
+    /// First path key (33 bytes, hex encoded)
+    pub blinding_point: String,
+    /// Blinded hops, ending at the recipient
+    pub hops: Vec<BlindedHop>,
+}
+
+/// Introduction node of a blinded path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntroductionNode {
+    /// Node public key (hex encoded)
+    NodeId(String),
+    /// Channel and direction identifying the node
+    ShortChannelId { short_channel_id: String, direction: u8 },
+}
+
+/// Hop within a blinded path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlindedHop {
+    /// Blinded node ID (33 bytes, hex encoded)
+    pub blinded_node_id: String,
+    /// Encrypted recipient data (hex encoded)
+    pub encrypted_data: String,
+}
+
 /// LNUrl response types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "tag")]
@@ -312,6 +398,8 @@ pub struct LightningPayment {
 pub enum LightningError {
     InvalidInvoice(String),
     InvoiceExpired,
+    InvalidOffer(String),
+    InvalidKeysend(String),
     InvalidLnUrl(String),
     NetworkMismatch { expected: String, got: String },
     AmountMismatch,
@@ -324,6 +412,8 @@ impl std::fmt::Display for LightningError {
         match self {
             LightningError::InvalidInvoice(msg) => write!(f, "Invalid invoice: {}", msg),
             LightningError::InvoiceExpired => write!(f, "Invoice has expired"),
+            LightningError::InvalidOffer(msg) => write!(f, "Invalid offer: {}", msg),
+            LightningError::InvalidKeysend(msg) => write!(f, "Invalid keysend payment: {}", msg),
             LightningError::InvalidLnUrl(msg) => write!(f, "Invalid LNUrl: {}", msg),
             LightningError::NetworkMismatch { expected, got } => {
                 write!(f, "Network mismatch: expected {}, got {}", expected, got)
@@ -79,6 +79,77 @@ pub fn fetch_litecoin_utxos(address: &str) -> Result<Vec<LitecoinUtxo>, Box<dyn
     Ok(utxos)
 }
 
+/// Change at or below this value is left to the miner (slightly higher than BTC)
+pub const LITECOIN_CHANGE_DUST_LIMIT: u64 = 5460;
+
+/// Inputs, size and fee chosen for a Litecoin P2WPKH spend
+#[derive(Debug)]
+pub struct LitecoinSpendPlan {
+    pub inputs: Vec<LitecoinUtxo>,
+    /// Estimated vsize (recipient + change outputs)
+    pub vsize: u64,
+    /// Total fee, including any dust change left to the miner
+    pub fee: u64,
+    /// Change output value (0 when there is no change output)
+    pub change: u64,
+}
+
+/// Select inputs (largest first) and size the fee for sending `amount_lits`
+pub fn plan_litecoin_spend(
+    utxos: Vec<LitecoinUtxo>,
+    amount_lits: u64,
+    fee_rate_sats_per_vbyte: u64,
+) -> Result<LitecoinSpendPlan, Box<dyn Error>> {
+    // Largest first for better coin selection
+    let mut sorted_utxos = utxos;
+    sorted_utxos.sort_by_key(|u| std::cmp::Reverse(u.value));
+    
+    let mut inputs: Vec<LitecoinUtxo> = Vec::new();
+    let mut total_input_value: u64 = 0;
+    let target_value = amount_lits;
+    
+    for utxo in sorted_utxos {
+        debug_log!("UTXO: txid={}, vout={}, value={} lits", utxo.transaction_hash, utxo.index, utxo.value);
+        total_input_value += utxo.value;
+        inputs.push(utxo);
+        if total_input_value >= target_value {
+            break;
+        }
+    }
+    
+    debug_log!("Selected {} inputs with total {} lits (1 lit = 1 satoshi)", inputs.len(), total_input_value);
+    
+    if total_input_value < target_value {
+        return Err(format!("Insufficient funds: have {} lits, need {} lits", total_input_value, target_value).into());
+    }
+    
+    // Estimate fee
+    // P2WPKH Input: ~68 vbytes, P2WPKH Output: ~31 vbytes
+    let estimated_size = 10 + (inputs.len() as u64 * 68) + (2 * 31);
+    let fee = estimated_size * fee_rate_sats_per_vbyte;
+    
+    if total_input_value < target_value + fee {
+        return Err(format!(
+            "Insufficient funds for amount + fee. Have {}, need {}",
+            total_input_value, target_value + fee
+        ).into());
+    }
+
+    let remainder = total_input_value - target_value - fee;
+    let (fee, change) = if remainder > LITECOIN_CHANGE_DUST_LIMIT {
+        (fee, remainder)
+    } else {
+        (fee + remainder, 0)
+    };
+
+    Ok(LitecoinSpendPlan {
+        inputs,
+        vsize: estimated_size,
+        fee,
+        change,
+    })
+}
+
 /// Prepare and sign a Litecoin transaction
 /// 
 /// Litecoin uses the same transaction format as Bitcoin, but with different:
@@ -125,43 +196,13 @@ pub fn prepare_litecoin_transaction(
         return Err("No UTXOs available for this address".into());
     }
     
-    // 2. Select Inputs (largest first for better coin selection)
-    let mut sorted_utxos = utxos;
-    sorted_utxos.sort_by(|a, b| b.value.cmp(&a.value));
-    
-    let mut inputs: Vec<LitecoinUtxo> = Vec::new();
-    let mut total_input_value: u64 = 0;
+    // 2-3. Select inputs and estimate fee
+    let plan = plan_litecoin_spend(utxos, amount_lits, fee_rate_sats_per_vbyte)?;
+    let inputs = plan.inputs;
     let target_value = amount_lits;
     
-    for utxo in sorted_utxos {
-        debug_log!("UTXO: txid={}, vout={}, value={} lits", utxo.transaction_hash, utxo.index, utxo.value);
-        total_input_value += utxo.value;
-        inputs.push(utxo);
-        if total_input_value >= target_value {
-            break;
-        }
-    }
-    
-    debug_log!("Selected {} inputs with total {} lits (1 lit = 1 satoshi)", inputs.len(), total_input_value);
-    
-    if total_input_value < target_value {
-        return Err(format!("Insufficient funds: have {} lits, need {} lits", total_input_value, target_value).into());
-    }
-    
-    // 3. Estimate Fee
-    // P2WPKH Input: ~68 vbytes, P2WPKH Output: ~31 vbytes
-    let estimated_size = 10 + (inputs.len() as u64 * 68) + (2 * 31);
-    let fee = estimated_size * fee_rate_sats_per_vbyte;
-    
-    if total_input_value < target_value + fee {
-        return Err(format!(
-            "Insufficient funds for amount + fee. Have {}, need {}",
-            total_input_value, target_value + fee
-        ).into());
-    }
-    
     // 4. Build Transaction
-    let change_amount = total_input_value - target_value - fee;
+    let change_amount = plan.change;
     
     // Decode recipient address (Litecoin bech32)
     let recipient_script = decode_litecoin_address(recipient)?;
@@ -186,8 +227,8 @@ pub fn prepare_litecoin_transaction(
         value: Amount::from_sat(target_value),
         script_pubkey: recipient_script,
     });
-    // Change output (if above dust)
-    if change_amount > 5460 {  // Litecoin dust limit (slightly higher than BTC)
+    // Change output (dust change is left to the fee)
+    if change_amount > 0 {
         tx_outputs.push(TxOut {
             value: Amount::from_sat(change_amount),
             script_pubkey: sender_script,
@@ -243,37 +284,21 @@ pub fn prepare_litecoin_transaction(
 /// Decode a Litecoin WIF private key (0xB0 prefix for mainnet)
 fn decode_litecoin_wif(wif: &str) -> Result<(SecretKey, bool), Box<dyn Error>> {
     // Base58Check decode
-    let decoded = bs58::decode(wif).into_vec()?;
-    
-    if decoded.len() < 5 {
-        return Err("Invalid WIF length".into());
-    }
-    
-    // Verify checksum
-    let payload = &decoded[..decoded.len() - 4];
-    let checksum = &decoded[decoded.len() - 4..];
-    
-    use bitcoin::hashes::sha256;
-    let hash1 = sha256::Hash::hash(payload);
-    let hash2 = sha256::Hash::hash(hash1.as_byte_array());
-    let computed_checksum = &hash2.as_byte_array()[..4];
-    
-    if checksum != computed_checksum {
-        return Err("Invalid WIF checksum".into());
-    }
+    let (prefix, payload) = crate::utils::crypto::base58check_decode(wif, 1)
+        .map_err(|e| format!("Invalid WIF: {}", e.message))?;
     
     // Check prefix (0xB0 for Litecoin mainnet, 0xEF for testnet)
-    let prefix = payload[0];
+    let prefix = prefix[0];
     if prefix != 0xB0 && prefix != 0xEF {
         return Err(format!("Invalid Litecoin WIF prefix: 0x{:02X}", prefix).into());
     }
     
     // Extract secret key
-    let compressed = payload.len() == 34 && payload[33] == 0x01;
+    let compressed = payload.len() == 33 && payload[32] == 0x01;
     let key_bytes = if compressed {
-        &payload[1..33]
+        &payload[..32]
     } else {
-        &payload[1..]
+        &payload[..]
     };
     
     let secret_key = SecretKey::from_slice(key_bytes)?;
@@ -281,7 +306,7 @@ fn decode_litecoin_wif(wif: &str) -> Result<(SecretKey, bool), Box<dyn Error>> {
 }
 
 /// Decode a Litecoin bech32 address to script pubkey
-fn decode_litecoin_address(address: &str) -> Result<ScriptBuf, Box<dyn Error>> {
+pub(crate) fn decode_litecoin_address(address: &str) -> Result<ScriptBuf, Box<dyn Error>> {
     use bech32::{self, FromBase32};
     
     let address_lower = address.to_lowercase();
@@ -313,14 +338,10 @@ fn decode_litecoin_address(address: &str) -> Result<ScriptBuf, Box<dyn Error>> {
         }
     } else if address.starts_with("L") || address.starts_with("M") || address.starts_with("m") || address.starts_with("n") {
         // Legacy P2PKH or P2SH address (base58)
-        let decoded = bs58::decode(address).into_vec()?;
-        
-        if decoded.len() < 5 {
-            return Err("Invalid legacy address length".into());
-        }
-        
-        let version = decoded[0];
-        let hash = &decoded[1..decoded.len() - 4];
+        let (version, hash) = crate::utils::crypto::base58check_decode(address, 1)
+            .map_err(|e| format!("Invalid Litecoin address: {}", e.message))?;
+        let version = version[0];
+        let hash = hash.as_slice();
         
         // Litecoin: P2PKH prefix 0x30 (mainnet), P2SH prefix 0x32 (mainnet), 0x3A (M-address)
         // Testnet: P2PKH 0x6F, P2SH 0xC4
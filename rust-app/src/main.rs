@@ -1,6 +1,12 @@
 use clap::{Parser, Subcommand};
-use rust_app::{AllKeys, create_new_wallet, generate_keys_from_seed};
+use rust_app::{Chain, KeyNetwork, PartialKeys, generate_keys_from_seed, generate_selected_keys};
+use rust_app::qr::QrMatrix;
+use rust_app::wallet::{generate_mnemonic, DEFAULT_MNEMONIC_STRENGTH};
+use serde::{Deserialize, Serialize};
 use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use bip39::Mnemonic;
 
 #[derive(Parser)]
@@ -18,9 +24,34 @@ enum Commands {
         /// Optional mnemonic phrase to recover from
         #[arg(long)]
         mnemonic: Option<String>,
+        /// Optional BIP39 passphrase ("25th word"); visible in shell history and
+        /// process listings, prefer --passphrase-stdin
+        #[arg(long, conflicts_with = "passphrase_stdin")]
+        passphrase: Option<String>,
+        /// Read the BIP39 passphrase from stdin (prompts without echo on a terminal)
+        #[arg(long)]
+        passphrase_stdin: bool,
         /// Output as JSON
         #[arg(long)]
         json: bool,
+        /// Network to generate keys for (mainnet or testnet)
+        #[arg(long, default_value = "mainnet")]
+        network: KeyNetwork,
+        /// Only generate these chains (comma-separated, e.g. btc,eth,sol); default all
+        #[arg(long, value_delimiter = ',')]
+        chains: Vec<Chain>,
+        /// Write the JSON output to this file (mode 0600) instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Overwrite the --out file if it already exists
+        #[arg(long, requires = "out")]
+        force: bool,
+        /// Also show each address as a QR code (addresses only, never keys)
+        #[arg(long, conflicts_with = "json")]
+        qr: bool,
+        /// Only show the QR code for this chain's address (e.g. btc)
+        #[arg(long, requires = "qr")]
+        qr_chain: Option<Chain>,
     },
     /// Sign a Bitcoin transaction
     SignBtc {
@@ -34,6 +65,9 @@ enum Commands {
         sender_wif: String,
         #[arg(long)]
         utxos: Option<String>, // JSON string of UTXOs
+        /// Also show the result as a QR code
+        #[arg(long)]
+        qr: bool,
     },
     /// Sign an Ethereum transaction
     SignEth {
@@ -57,28 +91,39 @@ enum Commands {
         max_priority_fee_per_gas: Option<String>,
         #[arg(long, default_value = "")]
         data: String,
+        /// Also show the result as a QR code
+        #[arg(long)]
+        qr: bool,
     },
     /// Sign a Solana transaction
     SignSol {
         #[arg(long)]
         recipient: String,
+        /// Amount in SOL, e.g. 0.000000001
         #[arg(long)]
-        amount_sol: f64,
+        amount_sol: String,
         #[arg(long)]
         recent_blockhash: String,
         #[arg(long)]
         sender_base58: String,
+        /// Also show the result as a QR code
+        #[arg(long)]
+        qr: bool,
     },
     /// Sign a Monero transaction (Validation Only)
     SignXmr {
         #[arg(long)]
         recipient: String,
+        /// Amount in XMR, e.g. 0.5
         #[arg(long)]
-        amount_xmr: f64,
+        amount_xmr: String,
         #[arg(long)]
         sender_spend_hex: String,
         #[arg(long)]
         sender_view_hex: String,
+        /// Also show the result as a QR code
+        #[arg(long)]
+        qr: bool,
     },
     /// Sign an XRP transaction
     SignXrp {
@@ -92,6 +137,9 @@ enum Commands {
         sequence: u32,
         #[arg(long)]
         destination_tag: Option<u32>,
+        /// Also show the result as a QR code
+        #[arg(long)]
+        qr: bool,
     },
     /// Sign a Litecoin transaction
     SignLtc {
@@ -107,170 +155,597 @@ enum Commands {
         sender_address: String,
         #[arg(long)]
         utxos: Option<String>, // JSON string of UTXOs
+        /// Also show the result as a QR code
+        #[arg(long)]
+        qr: bool,
+    },
+    /// Sign a batch of transactions from a JSON file (offline)
+    ///
+    /// The file holds an array of objects with a "chain" (btc, eth, sol, xmr,
+    /// xrp, ltc) and that chain's sign-* arguments; "utxos" is an array.
+    /// Failed entries are reported in the output and the exit status is
+    /// non-zero if any failed.
+    SignBatch {
+        /// JSON file with the signing requests
+        #[arg(long)]
+        file: PathBuf,
+        /// Write the results to this file (mode 0600) instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Overwrite the --out file if it already exists
+        #[arg(long, requires = "out")]
+        force: bool,
     },
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
 
-    match &cli.command {
-        Commands::GenKeys { mnemonic, json } => {
-            handle_gen_keys(mnemonic.as_deref(), *json)?;
+    match cli.command {
+        Commands::GenKeys { mnemonic, passphrase, passphrase_stdin, json, network, chains, out, force, qr, qr_chain } => {
+            let output = match &out {
+                Some(path) => KeyOutput::File { path, force },
+                None if json => KeyOutput::Json,
+                None => KeyOutput::Text,
+            };
+            let passphrase = match passphrase {
+                Some(passphrase) => passphrase,
+                None if passphrase_stdin => read_passphrase_stdin()?,
+                None => String::new(),
+            };
+            let qr = qr.then_some(qr_chain);
+            handle_gen_keys(mnemonic.as_deref(), &passphrase, network, &chains, output, qr)?;
         }
-        Commands::SignBtc { recipient, amount_sats, fee_rate, sender_wif, utxos } => {
-             let manual_utxos = if let Some(json) = utxos {
-                 Some(serde_json::from_str::<Vec<rust_app::bitcoin_wallet::Utxo>>(json)?)
-             } else {
-                 None
-             };
-             let tx_hex = rust_app::bitcoin_wallet::prepare_transaction(recipient, *amount_sats, *fee_rate, sender_wif, manual_utxos)?;
-             println!("{}", tx_hex);
+        Commands::SignBtc { recipient, amount_sats, fee_rate, sender_wif, utxos, qr } => {
+            let utxos = utxos.as_deref().map(serde_json::from_str).transpose()?;
+            let request = SignRequest::Btc { recipient, amount_sats, fee_rate, sender_wif, utxos };
+            print_signed(&request.sign()?, qr)?;
         }
-        Commands::SignEth { recipient, amount_wei, chain_id, sender_key, nonce, gas_limit, gas_price, max_fee_per_gas, max_priority_fee_per_gas, data } => {
-             let rt = tokio::runtime::Runtime::new()?;
-             let tx_hex = rt.block_on(rust_app::ethereum_wallet::prepare_ethereum_transaction(
-                 recipient, amount_wei, *chain_id, sender_key, *nonce, *gas_limit, gas_price.clone(), max_fee_per_gas.clone(), max_priority_fee_per_gas.clone(), data
-             ))?;
-             println!("{}", tx_hex);
+        Commands::SignEth { recipient, amount_wei, chain_id, sender_key, nonce, gas_limit, gas_price, max_fee_per_gas, max_priority_fee_per_gas, data, qr } => {
+            let request = SignRequest::Eth {
+                recipient, amount_wei, chain_id, sender_key, nonce, gas_limit, gas_price, max_fee_per_gas, max_priority_fee_per_gas, data
+            };
+            print_signed(&request.sign()?, qr)?;
         }
-        Commands::SignSol { recipient, amount_sol, recent_blockhash, sender_base58 } => {
-            let tx_base58 = rust_app::solana_wallet::prepare_solana_transaction(
-                recipient, *amount_sol, recent_blockhash, sender_base58
-            )?;
-            println!("{}", tx_base58);
+        Commands::SignSol { recipient, amount_sol, recent_blockhash, sender_base58, qr } => {
+            let request = SignRequest::Sol { recipient, amount_sol, recent_blockhash, sender_base58 };
+            print_signed(&request.sign()?, qr)?;
         }
-        Commands::SignXmr { recipient, amount_xmr, sender_spend_hex, sender_view_hex } => {
-            let tx_hex = rust_app::monero_wallet::prepare_monero_transaction(
-                recipient, *amount_xmr, sender_spend_hex, sender_view_hex
-            )?;
-            println!("{}", tx_hex);
+        Commands::SignXmr { recipient, amount_xmr, sender_spend_hex, sender_view_hex, qr } => {
+            let request = SignRequest::Xmr { recipient, amount_xmr, sender_spend_hex, sender_view_hex };
+            print_signed(&request.sign()?, qr)?;
         }
-        Commands::SignXrp { recipient, amount_drops, sender_seed_hex, sequence, destination_tag } => {
-            let tx_hex = rust_app::xrp_wallet::prepare_xrp_transaction(
-                recipient, *amount_drops, sender_seed_hex, *sequence, *destination_tag
-            )?;
-            println!("{}", tx_hex);
+        Commands::SignXrp { recipient, amount_drops, sender_seed_hex, sequence, destination_tag, qr } => {
+            let request = SignRequest::Xrp { recipient, amount_drops, sender_seed_hex, sequence, destination_tag };
+            print_signed(&request.sign()?, qr)?;
         }
-        Commands::SignLtc { recipient, amount_lits, fee_rate, sender_wif, sender_address, utxos } => {
-            let manual_utxos = if let Some(json) = utxos {
-                Some(serde_json::from_str::<Vec<rust_app::litecoin_wallet::LitecoinUtxo>>(json)?)
-            } else {
-                None
-            };
-            let tx_hex = rust_app::litecoin_wallet::prepare_litecoin_transaction(
-                recipient, *amount_lits, *fee_rate, sender_wif, sender_address, manual_utxos
-            )?;
-            println!("{}", tx_hex);
+        Commands::SignLtc { recipient, amount_lits, fee_rate, sender_wif, sender_address, utxos, qr } => {
+            let utxos = utxos.as_deref().map(serde_json::from_str).transpose()?;
+            let request = SignRequest::Ltc { recipient, amount_lits, fee_rate, sender_wif, sender_address, utxos };
+            print_signed(&request.sign()?, qr)?;
+        }
+        Commands::SignBatch { file, out, force } => {
+            handle_sign_batch(&file, out.as_deref(), force)?;
         }
     }
 
     Ok(())
 }
 
-fn handle_gen_keys(mnemonic_arg: Option<&str>, json: bool) -> Result<(), Box<dyn Error>> {
-    let (mnemonic_str, keys) = if let Some(phrase) = mnemonic_arg {
-        let mnemonic = Mnemonic::parse(phrase)?;
-        let seed = mnemonic.to_seed("");
-        let keys = generate_keys_from_seed(&seed)?;
-        (phrase.to_string(), keys)
-    } else {
-        create_new_wallet()?
+/// One transaction to sign
+///
+/// Fields match the `sign-*` arguments. In `sign-batch` files the variant is
+/// chosen by the "chain" field and `utxos` is an array, not a JSON string.
+#[derive(Deserialize)]
+#[serde(tag = "chain", rename_all = "lowercase")]
+enum SignRequest {
+    #[serde(alias = "bitcoin")]
+    Btc {
+        recipient: String,
+        amount_sats: u64,
+        fee_rate: u64,
+        sender_wif: String,
+        utxos: Option<Vec<rust_app::bitcoin_wallet::Utxo>>,
+    },
+    #[serde(alias = "ethereum")]
+    Eth {
+        recipient: String,
+        amount_wei: String,
+        chain_id: u64,
+        sender_key: String,
+        nonce: u64,
+        gas_limit: u64,
+        gas_price: Option<String>,
+        max_fee_per_gas: Option<String>,
+        max_priority_fee_per_gas: Option<String>,
+        #[serde(default)]
+        data: String,
+    },
+    #[serde(alias = "solana")]
+    Sol {
+        recipient: String,
+        amount_sol: String,
+        recent_blockhash: String,
+        sender_base58: String,
+    },
+    #[serde(alias = "monero")]
+    Xmr {
+        recipient: String,
+        amount_xmr: String,
+        sender_spend_hex: String,
+        sender_view_hex: String,
+    },
+    #[serde(alias = "ripple")]
+    Xrp {
+        recipient: String,
+        amount_drops: u64,
+        sender_seed_hex: String,
+        sequence: u32,
+        destination_tag: Option<u32>,
+    },
+    #[serde(alias = "litecoin")]
+    Ltc {
+        recipient: String,
+        amount_lits: u64,
+        fee_rate: u64,
+        sender_wif: String,
+        sender_address: String,
+        utxos: Option<Vec<rust_app::litecoin_wallet::LitecoinUtxo>>,
+    },
+}
+
+impl SignRequest {
+    /// Sign with the chain's wallet module, returning the encoded transaction
+    fn sign(self) -> Result<String, Box<dyn Error>> {
+        match self {
+            SignRequest::Btc { recipient, amount_sats, fee_rate, sender_wif, utxos } => {
+                rust_app::bitcoin_wallet::prepare_transaction(&recipient, amount_sats, fee_rate, &sender_wif, utxos)
+            }
+            SignRequest::Eth { recipient, amount_wei, chain_id, sender_key, nonce, gas_limit, gas_price, max_fee_per_gas, max_priority_fee_per_gas, data } => {
+                let rt = tokio::runtime::Runtime::new()?;
+                rt.block_on(rust_app::ethereum_wallet::prepare_ethereum_transaction(
+                    &recipient, &amount_wei, chain_id, &sender_key, nonce, gas_limit, gas_price, max_fee_per_gas, max_priority_fee_per_gas, &data
+                ))
+            }
+            SignRequest::Sol { recipient, amount_sol, recent_blockhash, sender_base58 } => {
+                rust_app::solana_wallet::prepare_solana_transaction(
+                    &recipient, &amount_sol, &recent_blockhash, &sender_base58
+                )
+            }
+            SignRequest::Xmr { recipient, amount_xmr, sender_spend_hex, sender_view_hex } => {
+                rust_app::monero_wallet::prepare_monero_transaction(
+                    &recipient, &amount_xmr, &sender_spend_hex, &sender_view_hex
+                )
+            }
+            SignRequest::Xrp { recipient, amount_drops, sender_seed_hex, sequence, destination_tag } => {
+                rust_app::xrp_wallet::prepare_xrp_transaction(
+                    &recipient, amount_drops, &sender_seed_hex, sequence, destination_tag
+                )
+            }
+            SignRequest::Ltc { recipient, amount_lits, fee_rate, sender_wif, sender_address, utxos } => {
+                rust_app::litecoin_wallet::prepare_litecoin_transaction(
+                    &recipient, amount_lits, fee_rate, &sender_wif, &sender_address, utxos
+                )
+            }
+        }
+    }
+}
+
+fn print_signed(signed_tx: &str, qr: bool) -> Result<(), Box<dyn Error>> {
+    println!("{}", signed_tx);
+    if qr {
+        print_qr("Signed transaction", signed_tx)?;
+    }
+    Ok(())
+}
+
+/// Outcome of one `sign-batch` entry
+#[derive(Serialize)]
+struct BatchResult {
+    /// Position in the input array
+    index: usize,
+    /// The entry's "chain" field, if it had one
+    chain: Option<String>,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signed_tx: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn handle_sign_batch(file: &Path, out: Option<&Path>, force: bool) -> Result<(), Box<dyn Error>> {
+    let contents = std::fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&contents)
+        .map_err(|e| format!("{} is not a JSON array: {}", file.display(), e))?;
+
+    // Each entry is parsed and signed on its own so one bad request does not
+    // stop the rest
+    let results: Vec<BatchResult> = entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let chain = entry.get("chain").and_then(|c| c.as_str()).map(str::to_string);
+            let signed = serde_json::from_value::<SignRequest>(entry)
+                .map_err(|e| format!("Invalid request: {}", e))
+                .and_then(|request| request.sign().map_err(|e| e.to_string()));
+            match signed {
+                Ok(signed_tx) => BatchResult { index, chain, success: true, signed_tx: Some(signed_tx), error: None },
+                Err(error) => BatchResult { index, chain, success: false, signed_tx: None, error: Some(error) },
+            }
+        })
+        .collect();
+
+    let document = serde_json::to_string_pretty(&results)?;
+    match out {
+        Some(path) => {
+            write_secret_file(path, format!("{}\n", document).as_bytes(), force)?;
+            eprintln!("Wrote {} results to {}", results.len(), path.display());
+        }
+        None => println!("{}", document),
+    }
+
+    let failed = results.iter().filter(|r| !r.success).count();
+    if failed > 0 {
+        return Err(format!("{} of {} requests failed", failed, results.len()).into());
+    }
+    Ok(())
+}
+
+/// Where `gen-keys` sends its output
+enum KeyOutput<'a> {
+    Text,
+    Json,
+    File { path: &'a Path, force: bool },
+}
+
+/// Read one line from stdin as the BIP39 passphrase
+///
+/// On a terminal the prompt goes to stderr and (on unix) echo is turned off
+/// while typing. Only the line ending is stripped; surrounding spaces are part of
+/// the passphrase.
+fn read_passphrase_stdin() -> Result<String, Box<dyn Error>> {
+    use std::io::{BufRead, IsTerminal};
+
+    let stdin = std::io::stdin();
+    let interactive = stdin.is_terminal();
+    if interactive {
+        eprint!("BIP39 passphrase: ");
+        std::io::stderr().flush()?;
+    }
+
+    let mut line = String::new();
+    {
+        #[cfg(unix)]
+        let _echo = if interactive { EchoGuard::disable() } else { None };
+        stdin.lock().read_line(&mut line)?;
+    }
+    if interactive {
+        eprintln!();
+    }
+
+    let passphrase = line.strip_suffix('\n').unwrap_or(&line);
+    Ok(passphrase.strip_suffix('\r').unwrap_or(passphrase).to_string())
+}
+
+/// Turns terminal echo off on stdin until dropped
+#[cfg(unix)]
+struct EchoGuard(libc::termios);
+
+#[cfg(unix)]
+impl EchoGuard {
+    fn disable() -> Option<Self> {
+        // SAFETY: termios is plain data and tcgetattr fills it in on success
+        unsafe {
+            let mut original: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return None;
+            }
+            let mut silent = original;
+            silent.c_lflag &= !libc::ECHO;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &silent) != 0 {
+                return None;
+            }
+            Some(EchoGuard(original))
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for EchoGuard {
+    fn drop(&mut self) {
+        // SAFETY: restores the attributes read in disable()
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.0);
+        }
+    }
+}
+
+fn handle_gen_keys(
+    mnemonic_arg: Option<&str>,
+    passphrase: &str,
+    network: KeyNetwork,
+    chains: &[Chain],
+    output: KeyOutput<'_>,
+    qr: Option<Option<Chain>>,
+) -> Result<(), Box<dyn Error>> {
+    let mnemonic = match mnemonic_arg {
+        Some(phrase) => Mnemonic::parse(phrase)?,
+        None => generate_mnemonic(DEFAULT_MNEMONIC_STRENGTH)?,
     };
+    let mnemonic_str = mnemonic.to_string();
+    let seed = mnemonic.to_seed(passphrase);
 
-    if json {
-        println!("{{ \"mnemonic\": \"{}\", \"keys\": {} }}", mnemonic_str, serde_json::to_string(&keys)?);
+    // With no --chains, keep emitting the full AllKeys document
+    let (keys_json, keys) = if chains.is_empty() {
+        let keys = generate_keys_from_seed(&seed, network)?;
+        (serde_json::to_string(&keys)?, PartialKeys::from(keys))
     } else {
-        println!("=== Mnemonic ===");
-        println!("{}", mnemonic_str);
-        println!();
-        print_human_readable(&keys);
+        let keys = generate_selected_keys(&seed, network, chains)?;
+        (serde_json::to_string(&keys)?, keys)
+    };
+
+    let document = format!("{{ \"mnemonic\": \"{}\", \"keys\": {} }}", mnemonic_str, keys_json);
+    match output {
+        KeyOutput::Json => println!("{}", document),
+        KeyOutput::Text => {
+            println!("=== Mnemonic ===");
+            println!("{}", mnemonic_str);
+            println!();
+            print_human_readable(&keys);
+            // Chains without a hand-written section only appear when asked for
+            if !chains.is_empty() {
+                print_other_keys(&keys)?;
+            }
+        }
+        KeyOutput::File { path, force } => {
+            write_secret_file(path, format!("{}\n", document).as_bytes(), force)?;
+            eprintln!("Wrote keys to {}", path.display());
+        }
+    }
+
+    if let Some(qr_chain) = qr {
+        let sections: Vec<_> = address_sections(&keys)
+            .into_iter()
+            .filter(|(chain, _, _)| match qr_chain {
+                Some(selected) => selected == *chain,
+                None => true,
+            })
+            .collect();
+        if let (Some(chain), true) = (qr_chain, sections.is_empty()) {
+            return Err(format!("No {:?} address was generated", chain).into());
+        }
+        for (_, label, address) in sections {
+            print_qr(&format!("{} address", label), address)?;
+        }
     }
     Ok(())
 }
 
-fn print_human_readable(keys: &AllKeys) {
-    println!("=== Bitcoin (P2WPKH) ===");
-    println!("Private key (hex): {}", keys.bitcoin.private_hex);
-    println!("Private key (WIF): {}", keys.bitcoin.private_wif);
-    println!(
-        "Public key (compressed hex): {}",
-        keys.bitcoin.public_compressed_hex
-    );
-    println!("Bech32 address (P2WPKH): {}", keys.bitcoin.address);
-    println!();
+/// Addresses shown by `print_human_readable`, with their chain and heading
+fn address_sections(keys: &PartialKeys) -> Vec<(Chain, &'static str, &str)> {
+    let mut sections = Vec::new();
+    if let Some(k) = &keys.bitcoin {
+        sections.push((Chain::Bitcoin, "Bitcoin", k.address.as_str()));
+    }
+    if let Some(k) = &keys.bitcoin_testnet {
+        sections.push((Chain::BitcoinTestnet, "Bitcoin Testnet", k.address.as_str()));
+    }
+    if let Some(k) = &keys.litecoin {
+        sections.push((Chain::Litecoin, "Litecoin", k.address.as_str()));
+    }
+    if let Some(k) = &keys.monero {
+        sections.push((Chain::Monero, "Monero", k.address.as_str()));
+    }
+    if let Some(k) = &keys.solana {
+        sections.push((Chain::Solana, "Solana", k.public_key_base58.as_str()));
+    }
+    if let Some(k) = &keys.ethereum {
+        sections.push((Chain::Ethereum, "Ethereum", k.address.as_str()));
+    }
+    if let Some(k) = &keys.ethereum_sepolia {
+        sections.push((Chain::EthereumSepolia, "Ethereum Sepolia", k.address.as_str()));
+    }
+    if let Some(k) = &keys.bnb {
+        sections.push((Chain::Bnb, "BNB Smart Chain", k.address.as_str()));
+    }
+    if let Some(k) = &keys.xrp {
+        sections.push((Chain::Xrp, "XRP", k.classic_address.as_str()));
+    }
+    if let Some(k) = &keys.stellar {
+        sections.push((Chain::Stellar, "Stellar", k.address.as_str()));
+    }
+    sections
+}
 
-    println!("=== Bitcoin Testnet (P2WPKH) ===");
-    println!("Private key (hex): {}", keys.bitcoin_testnet.private_hex);
-    println!("Private key (WIF): {}", keys.bitcoin_testnet.private_wif);
-    println!(
-        "Public key (compressed hex): {}",
-        keys.bitcoin_testnet.public_compressed_hex
-    );
-    println!(
-        "Bech32 address (P2WPKH): {}",
-        keys.bitcoin_testnet.address
-    );
+/// Print `data` as a terminal QR code under a heading
+fn print_qr(label: &str, data: &str) -> Result<(), Box<dyn Error>> {
+    let matrix = QrMatrix::encode(data)?;
     println!();
+    println!("=== {} (QR) ===", label);
+    print!("{}", matrix.render_unicode());
+    Ok(())
+}
 
-    println!("=== Litecoin (P2WPKH) ===");
-    println!("Private key (hex): {}", keys.litecoin.private_hex);
-    println!("Private key (WIF): {}", keys.litecoin.private_wif);
-    println!(
-        "Public key (compressed hex): {}",
-        keys.litecoin.public_compressed_hex
-    );
-    println!("Bech32 address (P2WPKH): {}", keys.litecoin.address);
-    println!();
+/// Atomically write secret material to `path`, readable only by the owner
+///
+/// The data goes to a temporary file (created with mode 0600) in the same
+/// directory, which is then renamed over `path` with `force`, or hard-linked
+/// into place without it so an existing file is never clobbered.
+fn write_secret_file(path: &Path, contents: &[u8], force: bool) -> std::io::Result<()> {
+    if !force && path.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists; pass --force to overwrite", path.display()),
+        ));
+    }
 
-    println!("=== Monero ===");
-    println!("Private spend key (hex): {}", keys.monero.private_spend_hex);
-    println!("Private view key (hex): {}", keys.monero.private_view_hex);
-    println!("Public spend key (hex): {}", keys.monero.public_spend_hex);
-    println!("Public view key (hex): {}", keys.monero.public_view_hex);
-    println!("Primary address: {}", keys.monero.address);
-    println!();
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = PathBuf::from(tmp_path);
 
-    println!("=== Solana ===");
-    println!("Private seed (hex): {}", keys.solana.private_seed_hex);
-    println!("Private key (base58): {}", keys.solana.private_key_base58);
-    println!(
-        "Public key / address (base58): {}",
-        keys.solana.public_key_base58
-    );
-    println!();
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
 
-    println!("=== Ethereum ===");
-    println!("Private key (hex): {}", keys.ethereum.private_hex);
-    println!(
-        "Public key (uncompressed hex): {}",
-        keys.ethereum.public_uncompressed_hex
-    );
-    println!("Checksummed address: {}", keys.ethereum.address);
-    println!();
+    let result = options.open(&tmp_path).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()?;
+        if force {
+            std::fs::rename(&tmp_path, path)
+        } else {
+            // Fails with AlreadyExists if the file appeared since the check above
+            std::fs::hard_link(&tmp_path, path)
+        }
+    });
 
-    println!("=== Ethereum Sepolia ===");
-    println!("Private key (hex): {}", keys.ethereum_sepolia.private_hex);
-    println!(
-        "Public key (uncompressed hex): {}",
-        keys.ethereum_sepolia.public_uncompressed_hex
-    );
-    println!("Checksummed address: {}", keys.ethereum_sepolia.address);
-    println!();
+    // Already renamed on success with --force; otherwise clean up
+    let _ = std::fs::remove_file(&tmp_path);
+    result
+}
 
-    println!("=== BNB Smart Chain ===");
-    println!("Private key (hex): {}", keys.bnb.private_hex);
-    println!(
-        "Public key (uncompressed hex): {}",
-        keys.bnb.public_uncompressed_hex
-    );
-    println!("Checksummed address: {}", keys.bnb.address);
-    println!();
+fn print_human_readable(keys: &PartialKeys) {
+    if let Some(bitcoin) = &keys.bitcoin {
+        println!("=== Bitcoin (P2WPKH) ===");
+        println!("Private key (hex): {}", bitcoin.private_hex);
+        println!("Private key (WIF): {}", bitcoin.private_wif);
+        println!(
+            "Public key (compressed hex): {}",
+            bitcoin.public_compressed_hex
+        );
+        println!("Bech32 address (P2WPKH): {}", bitcoin.address);
+        println!();
+    }
+
+    if let Some(bitcoin_testnet) = &keys.bitcoin_testnet {
+        println!("=== Bitcoin Testnet (P2WPKH) ===");
+        println!("Private key (hex): {}", bitcoin_testnet.private_hex);
+        println!("Private key (WIF): {}", bitcoin_testnet.private_wif);
+        println!(
+            "Public key (compressed hex): {}",
+            bitcoin_testnet.public_compressed_hex
+        );
+        println!(
+            "Bech32 address (P2WPKH): {}",
+            bitcoin_testnet.address
+        );
+        println!();
+    }
+
+    if let Some(litecoin) = &keys.litecoin {
+        println!("=== Litecoin (P2WPKH) ===");
+        println!("Private key (hex): {}", litecoin.private_hex);
+        println!("Private key (WIF): {}", litecoin.private_wif);
+        println!(
+            "Public key (compressed hex): {}",
+            litecoin.public_compressed_hex
+        );
+        println!("Bech32 address (P2WPKH): {}", litecoin.address);
+        println!();
+    }
+
+    if let Some(monero) = &keys.monero {
+        println!("=== Monero ===");
+        println!("Private spend key (hex): {}", monero.private_spend_hex);
+        println!("Private view key (hex): {}", monero.private_view_hex);
+        println!("Public spend key (hex): {}", monero.public_spend_hex);
+        println!("Public view key (hex): {}", monero.public_view_hex);
+        println!("Primary address: {}", monero.address);
+        println!();
+    }
+
+    if let Some(solana) = &keys.solana {
+        println!("=== Solana ===");
+        println!("Private seed (hex): {}", solana.private_seed_hex);
+        println!("Private key (base58): {}", solana.private_key_base58);
+        println!(
+            "Public key / address (base58): {}",
+            solana.public_key_base58
+        );
+        println!();
+    }
+
+    if let Some(ethereum) = &keys.ethereum {
+        println!("=== Ethereum ===");
+        println!("Private key (hex): {}", ethereum.private_hex);
+        println!(
+            "Public key (uncompressed hex): {}",
+            ethereum.public_uncompressed_hex
+        );
+        println!("Checksummed address: {}", ethereum.address);
+        println!();
+    }
 
-    println!("=== XRP ===");
-    println!("Private key (hex): {}", keys.xrp.private_hex);
-    println!(
-        "Public key (compressed hex): {}",
-        keys.xrp.public_compressed_hex
-    );
-    println!("Classic address: {}", keys.xrp.classic_address);
+    if let Some(ethereum_sepolia) = &keys.ethereum_sepolia {
+        println!("=== Ethereum Sepolia ===");
+        println!("Private key (hex): {}", ethereum_sepolia.private_hex);
+        println!(
+            "Public key (uncompressed hex): {}",
+            ethereum_sepolia.public_uncompressed_hex
+        );
+        println!("Checksummed address: {}", ethereum_sepolia.address);
+        println!();
+    }
+
+    if let Some(bnb) = &keys.bnb {
+        println!("=== BNB Smart Chain ===");
+        println!("Private key (hex): {}", bnb.private_hex);
+        println!(
+            "Public key (uncompressed hex): {}",
+            bnb.public_uncompressed_hex
+        );
+        println!("Checksummed address: {}", bnb.address);
+        println!();
+    }
+
+    if let Some(xrp) = &keys.xrp {
+        println!("=== XRP ===");
+        println!("Private key (hex): {}", xrp.private_hex);
+        println!(
+            "Public key (compressed hex): {}",
+            xrp.public_compressed_hex
+        );
+        println!("Classic address: {}", xrp.classic_address);
+        println!();
+    }
+
+    if let Some(stellar) = &keys.stellar {
+        println!("=== Stellar ===");
+        println!("Private key (hex): {}", stellar.private_hex);
+        println!("Secret seed (S...): {}", stellar.secret_key);
+        println!("Public key (hex): {}", stellar.public_hex);
+        println!("Account ID (G...): {}", stellar.address);
+        println!();
+    }
+}
+
+/// Key sets `print_human_readable` has a section for
+const FORMATTED_KEY_SETS: &[&str] = &[
+    "bitcoin", "bitcoin_testnet", "litecoin", "monero", "solana",
+    "ethereum", "ethereum_sepolia", "bnb", "xrp", "stellar",
+];
+
+/// Print every other key set in `keys` field by field
+fn print_other_keys(keys: &PartialKeys) -> Result<(), Box<dyn Error>> {
+    let serde_json::Value::Object(sets) = serde_json::to_value(keys)? else {
+        return Ok(());
+    };
+    for (name, set) in sets.iter().filter(|(name, _)| !FORMATTED_KEY_SETS.contains(&name.as_str())) {
+        let heading = name.parse::<Chain>().map(|chain| format!("{:?}", chain)).unwrap_or_else(|_| name.clone());
+        println!("=== {} ===", heading);
+        if let serde_json::Value::Object(fields) = set {
+            for (field, value) in fields {
+                match value {
+                    serde_json::Value::String(text) => println!("{}: {}", field, text),
+                    other => println!("{}: {}", field, other),
+                }
+            }
+        }
+        println!();
+    }
+    Ok(())
 }
@@ -0,0 +1,300 @@
+//! Banxa integration
+//!
+//! Checkout URL: https://{partner}.banxa.com
+//! API: https://{partner}.banxa.com/api
+
+use super::types::*;
+
+/// Banxa production and sandbox domains (prefixed with the partner subdomain)
+pub const BANXA_DOMAIN: &str = "banxa.com";
+pub const BANXA_SANDBOX_DOMAIN: &str = "banxa-sandbox.com";
+
+/// Banxa on-ramp client
+#[derive(Debug, Clone)]
+pub struct BanxaClient {
+    /// Partner subdomain (e.g. "hawala" for hawala.banxa.com)
+    pub partner: String,
+    /// API key
+    pub api_key: String,
+    /// Base URL for checkout and API
+    pub base_url: String,
+    /// Sandbox mode
+    pub sandbox: bool,
+}
+
+impl BanxaClient {
+    /// Create new Banxa client
+    pub fn new(partner: String, api_key: String) -> Self {
+        let base_url = format!("https://{}.{}", partner, BANXA_DOMAIN);
+        Self {
+            partner,
+            api_key,
+            base_url,
+            sandbox: false,
+        }
+    }
+
+    /// Create sandbox client for testing
+    pub fn sandbox(partner: String, api_key: String) -> Self {
+        let base_url = format!("https://{}.{}", partner, BANXA_SANDBOX_DOMAIN);
+        Self {
+            partner,
+            api_key,
+            base_url,
+            sandbox: true,
+        }
+    }
+
+    /// Build checkout URL for buy flow
+    pub fn build_widget_url(&self, request: &OnRampRequest) -> String {
+        let mut url = format!(
+            "{}/?coinType={}&fiatType={}&fiatAmount={}",
+            self.base_url,
+            request.crypto_currency.to_uppercase(),
+            request.fiat_currency.to_uppercase(),
+            request.fiat_amount
+        );
+
+        // Add wallet address
+        url.push_str(&format!("&walletAddress={}", request.wallet_address));
+
+        url.push_str(&format!("&blockchain={}", self.format_blockchain(&request.crypto_currency, request.network.as_deref())));
+
+        // Add email if specified
+        if let Some(ref email) = request.email {
+            url.push_str(&format!("&email={}", email));
+        }
+
+        url
+    }
+
+    /// Format blockchain code for Banxa (e.g., BTC, ETH, MATIC)
+    fn format_blockchain(&self, crypto: &str, network: Option<&str>) -> String {
+        match network {
+            Some("polygon") | Some("matic") => "MATIC".to_string(),
+            Some("arbitrum") => "ARB".to_string(),
+            Some("optimism") => "OPTIMISM".to_string(),
+            Some("avalanche") | Some("avax") => "AVAX-C".to_string(),
+            Some("bsc") | Some("binance") => "BSC".to_string(),
+            Some("solana") => "SOL".to_string(),
+            Some("ethereum") => "ETH".to_string(),
+            _ => match crypto.to_uppercase().as_str() {
+                "BTC" => "BTC".to_string(),
+                "SOL" => "SOL".to_string(),
+                "MATIC" => "MATIC".to_string(),
+                "AVAX" => "AVAX-C".to_string(),
+                _ => "ETH".to_string(), // Default to Ethereum
+            },
+        }
+    }
+
+    /// Build quote URL
+    /// GET /api/prices
+    pub fn quote_url(&self, request: &OnRampRequest) -> String {
+        format!(
+            "{}/api/prices?source={}&target={}&source_amount={}&blockchain={}",
+            self.base_url,
+            request.fiat_currency.to_uppercase(),
+            request.crypto_currency.to_uppercase(),
+            request.fiat_amount,
+            self.format_blockchain(&request.crypto_currency, request.network.as_deref())
+        )
+    }
+
+    /// Build supported cryptocurrencies URL
+    /// GET /api/coins/buy
+    pub fn coins_url(&self) -> String {
+        format!("{}/api/coins/buy", self.base_url)
+    }
+
+    /// Parse quote response
+    ///
+    /// Banxa returns one price per payment method; the one delivering the
+    /// most crypto is used.
+    pub fn parse_quote(&self, json: &str, request: &OnRampRequest) -> Result<OnRampQuote, OnRampError> {
+        let parsed: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| OnRampError::QuoteFailed(e.to_string()))?;
+
+        // Check for error
+        if let Some(error) = parsed.get("errors") {
+            return Err(OnRampError::QuoteFailed(
+                error.get("title").and_then(|m| m.as_str()).unwrap_or("Unknown error").to_string()
+            ));
+        }
+
+        // Banxa encodes amounts as strings
+        let number = |value: &serde_json::Value, key: &str| -> f64 {
+            match value.get(key) {
+                Some(serde_json::Value::String(s)) => s.parse().unwrap_or(0.0),
+                Some(v) => v.as_f64().unwrap_or(0.0),
+                None => 0.0,
+            }
+        };
+
+        let prices = parsed.get("data")
+            .and_then(|d| d.get("prices"))
+            .and_then(|p| p.as_array())
+            .ok_or_else(|| OnRampError::QuoteFailed("No prices returned".to_string()))?;
+
+        let best = prices.iter()
+            .max_by(|a, b| {
+                number(a, "coin_amount")
+                    .partial_cmp(&number(b, "coin_amount"))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .ok_or_else(|| OnRampError::QuoteFailed("No prices returned".to_string()))?;
+
+        let crypto_amount = number(best, "coin_amount");
+        let fee_amount = number(best, "fee_amount");
+        let network_fee = number(best, "network_fee");
+
+        Ok(OnRampQuote {
+            provider: OnRampProvider::Banxa,
+            fiat_amount: request.fiat_amount,
+            fiat_currency: request.fiat_currency.clone(),
+            crypto_amount,
+            crypto_currency: request.crypto_currency.clone(),
+            network_fee,
+            provider_fee: fee_amount,
+            total_fees: fee_amount + network_fee,
+            exchange_rate: if crypto_amount > 0.0 { request.fiat_amount / crypto_amount } else { 0.0 },
+            payment_methods: vec![
+                PaymentMethod::CreditCard,
+                PaymentMethod::DebitCard,
+                PaymentMethod::ApplePay,
+                PaymentMethod::GooglePay,
+                PaymentMethod::Sepa,
+                PaymentMethod::BankTransfer,
+            ],
+            kyc_required: true,
+            kyc_threshold: None,
+            expires_at: None,
+            quote_id: None,
+        })
+    }
+
+    /// Get supported cryptocurrencies
+    pub fn supported_cryptos() -> Vec<SupportedCrypto> {
+        vec![
+            SupportedCrypto {
+                symbol: "BTC".into(),
+                name: "Bitcoin".into(),
+                networks: vec!["bitcoin".into()],
+                min_amount: Some(0.0001),
+                max_amount: None,
+            },
+            SupportedCrypto {
+                symbol: "ETH".into(),
+                name: "Ethereum".into(),
+                networks: vec!["ethereum".into(), "arbitrum".into(), "optimism".into()],
+                min_amount: Some(0.001),
+                max_amount: None,
+            },
+            SupportedCrypto {
+                symbol: "USDC".into(),
+                name: "USD Coin".into(),
+                networks: vec!["ethereum".into(), "polygon".into(), "solana".into()],
+                min_amount: Some(1.0),
+                max_amount: None,
+            },
+            SupportedCrypto {
+                symbol: "USDT".into(),
+                name: "Tether".into(),
+                networks: vec!["ethereum".into(), "polygon".into(), "bsc".into()],
+                min_amount: Some(1.0),
+                max_amount: None,
+            },
+            SupportedCrypto {
+                symbol: "SOL".into(),
+                name: "Solana".into(),
+                networks: vec!["solana".into()],
+                min_amount: Some(0.01),
+                max_amount: None,
+            },
+        ]
+    }
+
+    /// Get fee estimate
+    pub fn estimated_fee_percent() -> f64 {
+        3.0 // Banxa typically charges ~3% including spread
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_widget_url() {
+        let client = BanxaClient::new("hawala".into(), "key".into());
+        let request = OnRampRequest::new(100.0, "USD", "BTC", "bc1qtest...");
+
+        let url = client.build_widget_url(&request);
+
+        assert!(url.starts_with("https://hawala.banxa.com/"));
+        assert!(url.contains("coinType=BTC"));
+        assert!(url.contains("fiatType=USD"));
+        assert!(url.contains("fiatAmount=100"));
+        assert!(url.contains("walletAddress=bc1qtest"));
+        assert!(url.contains("blockchain=BTC"));
+    }
+
+    #[test]
+    fn test_sandbox_mode() {
+        let client = BanxaClient::sandbox("hawala".into(), "key".into());
+
+        assert!(client.sandbox);
+        assert!(client.base_url.contains("banxa-sandbox.com"));
+    }
+
+    #[test]
+    fn test_quote_url() {
+        let client = BanxaClient::new("hawala".into(), "key".into());
+        let request = OnRampRequest::new(250.0, "EUR", "USDC", "0x...")
+            .with_network("polygon");
+
+        let url = client.quote_url(&request);
+
+        assert!(url.contains("/api/prices"));
+        assert!(url.contains("source=EUR"));
+        assert!(url.contains("target=USDC"));
+        assert!(url.contains("source_amount=250"));
+        assert!(url.contains("blockchain=MATIC"));
+    }
+
+    #[test]
+    fn test_parse_quote_picks_best_payment_method() {
+        let client = BanxaClient::new("hawala".into(), "key".into());
+        let request = OnRampRequest::new(100.0, "USD", "ETH", "0x...");
+
+        let json = r#"{
+            "data": {
+                "spot_price": "2000.00",
+                "prices": [
+                    {"payment_method_id": 6037, "coin_amount": "0.04800", "fee_amount": "2.99", "network_fee": "1.01"},
+                    {"payment_method_id": 6040, "coin_amount": "0.04900", "fee_amount": "1.50", "network_fee": "0.50"}
+                ]
+            }
+        }"#;
+
+        let quote = client.parse_quote(json, &request).unwrap();
+
+        assert_eq!(quote.provider, OnRampProvider::Banxa);
+        assert!((quote.crypto_amount - 0.049).abs() < 1e-9);
+        assert_eq!(quote.provider_fee, 1.50);
+        assert!((quote.total_fees - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let client = BanxaClient::new("hawala".into(), "key".into());
+        let request = OnRampRequest::new(100.0, "USD", "ETH", "0x...");
+
+        let json = r#"{"errors": {"status": 400, "title": "Amount below minimum"}}"#;
+
+        match client.parse_quote(json, &request) {
+            Err(OnRampError::QuoteFailed(msg)) => assert!(msg.contains("minimum")),
+            _ => panic!("Expected QuoteFailed error"),
+        }
+    }
+}
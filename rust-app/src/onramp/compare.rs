@@ -0,0 +1,132 @@
+//! Best-price routing across on-ramp providers
+//!
+//! The app fetches each provider's `quote_url`; the raw responses are parsed
+//! here and ranked by crypto received.
+
+use super::banxa::BanxaClient;
+use super::moonpay::MoonPayClient;
+use super::ramp::RampClient;
+use super::transak::TransakClient;
+use super::types::*;
+
+/// Parse a raw quote response from any provider
+pub fn parse_provider_quote(
+    provider: OnRampProvider,
+    json: &str,
+    request: &OnRampRequest,
+) -> Result<OnRampQuote, OnRampError> {
+    // Parsing doesn't depend on credentials
+    match provider {
+        OnRampProvider::MoonPay => MoonPayClient::new(String::new()).parse_quote(json, request),
+        OnRampProvider::Transak => TransakClient::new(String::new()).parse_quote(json, request),
+        OnRampProvider::Ramp => RampClient::new(String::new()).parse_quote(json, request),
+        OnRampProvider::Banxa => BanxaClient::new(String::new(), String::new()).parse_quote(json, request),
+    }
+}
+
+/// Result of comparing provider quotes
+#[derive(Debug, Clone)]
+pub struct OnRampComparison {
+    /// Successfully parsed quotes, best first
+    pub ranked: Vec<RankedQuote>,
+    /// Providers whose responses could not be used
+    pub errors: Vec<(OnRampProvider, OnRampError)>,
+}
+
+/// Parse every provider response and rank the quotes by crypto received
+///
+/// When the request names a payment method, providers that don't offer it
+/// are reported as errors rather than ranked.
+pub fn compare_quotes(
+    request: &OnRampRequest,
+    responses: &[(OnRampProvider, String)],
+    reference_rate: Option<f64>,
+) -> OnRampComparison {
+    let mut quotes = OnRampQuotes::new(request.clone());
+    let mut errors = Vec::new();
+
+    for (provider, json) in responses {
+        match parse_provider_quote(*provider, json, request) {
+            Ok(quote) if request.payment_method.is_some_and(|m| !quote.supports(m)) => {
+                errors.push((*provider, OnRampError::QuoteFailed("Payment method not supported".to_string())))
+            }
+            Ok(quote) if quote.crypto_amount > 0.0 => quotes.quotes.push(quote),
+            Ok(_) => errors.push((*provider, OnRampError::QuoteFailed("Quote returned no crypto".to_string()))),
+            Err(e) => errors.push((*provider, e)),
+        }
+    }
+
+    OnRampComparison {
+        ranked: quotes.ranked(reference_rate),
+        errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranking_by_amount_received() {
+        let request = OnRampRequest::new(100.0, "USD", "ETH", "0x...");
+        let responses = vec![
+            // 4.99 fee, 0.0475 ETH
+            (OnRampProvider::MoonPay, r#"{"quoteCurrencyAmount": 0.0475, "feeAmount": 4.99}"#.to_string()),
+            // 1.50 + 0.50 fees, 0.0490 ETH
+            (OnRampProvider::Banxa, r#"{"data": {"prices": [{"coin_amount": "0.0490", "fee_amount": "1.50", "network_fee": "0.50"}]}}"#.to_string()),
+            // 3.00 fee, 0.0480 ETH
+            (OnRampProvider::Transak, r#"{"response": {"cryptoAmount": 0.0480, "totalFee": 3.00}}"#.to_string()),
+        ];
+
+        let comparison = compare_quotes(&request, &responses, Some(2000.0));
+        assert!(comparison.errors.is_empty());
+
+        let order: Vec<_> = comparison.ranked.iter().map(|r| r.quote.provider).collect();
+        assert_eq!(order, vec![OnRampProvider::Banxa, OnRampProvider::Transak, OnRampProvider::MoonPay]);
+        assert_eq!(comparison.ranked.iter().map(|r| r.rank).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        // Banxa: 100 - 0.049 * 2000 = 2.00 all-in, all of it explicit fees
+        let banxa = &comparison.ranked[0].fees;
+        assert!((banxa.total_cost - 2.0).abs() < 1e-9);
+        assert!(banxa.spread.abs() < 1e-9);
+
+        // MoonPay: 100 - 0.0475 * 2000 = 5.00 all-in, 0.01 of it spread
+        let moonpay = &comparison.ranked[2].fees;
+        assert!((moonpay.total_cost - 5.0).abs() < 1e-9);
+        assert!((moonpay.spread - 0.01).abs() < 1e-9);
+        assert!((moonpay.cost_percentage - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_failed_responses_reported() {
+        let request = OnRampRequest::new(100.0, "USD", "ETH", "0x...");
+        let responses = vec![
+            (OnRampProvider::MoonPay, r#"{"message": "Unsupported currency"}"#.to_string()),
+            (OnRampProvider::Ramp, r#"{"cryptoAmount": "48000000000000000", "asset": {"decimals": 18}, "baseRampFee": 2.0}"#.to_string()),
+            (OnRampProvider::Transak, "not json".to_string()),
+        ];
+
+        let comparison = compare_quotes(&request, &responses, None);
+
+        assert_eq!(comparison.ranked.len(), 1);
+        assert_eq!(comparison.ranked[0].quote.provider, OnRampProvider::Ramp);
+        assert_eq!(comparison.errors.len(), 2);
+    }
+
+    #[test]
+    fn test_payment_method_filter() {
+        let request = OnRampRequest::new(100.0, "EUR", "ETH", "0x...")
+            .with_payment_method(PaymentMethod::Sepa);
+        let responses = vec![
+            (OnRampProvider::MoonPay, r#"{"quoteCurrencyAmount": 0.0490, "feeAmount": 2.00, "paymentMethod": "apple_pay"}"#.to_string()),
+            (OnRampProvider::Transak, r#"{"response": {"cryptoAmount": 0.0480, "totalFee": 3.00, "paymentMethod": "sepa_bank_transfer"}}"#.to_string()),
+        ];
+
+        let comparison = compare_quotes(&request, &responses, None);
+
+        assert_eq!(comparison.ranked.len(), 1);
+        assert_eq!(comparison.ranked[0].quote.provider, OnRampProvider::Transak);
+        assert_eq!(comparison.errors.len(), 1);
+        assert_eq!(comparison.errors[0].0, OnRampProvider::MoonPay);
+    }
+}
@@ -4,12 +4,15 @@
 //! - MoonPay
 //! - Transak
 //! - Ramp Network
-//! - Quote comparison
+//! - Banxa
+//! - Quote comparison and best-price ranking
 
 pub mod types;
 pub mod moonpay;
 pub mod transak;
 pub mod ramp;
+pub mod banxa;
+pub mod compare;
 #[cfg(test)]
 pub mod tests;
 
@@ -17,3 +20,5 @@ pub use types::*;
 pub use moonpay::*;
 pub use transak::*;
 pub use ramp::*;
+pub use banxa::*;
+pub use compare::*;
@@ -67,14 +67,20 @@ impl MoonPayClient {
     /// Build quote URL
     /// GET /v3/currencies/{crypto}/buy_quote
     pub fn quote_url(&self, request: &OnRampRequest) -> String {
-        format!(
+        let mut url = format!(
             "{}/v3/currencies/{}/buy_quote?apiKey={}&baseCurrencyCode={}&baseCurrencyAmount={}&areFeesIncluded=true",
             MOONPAY_API_BASE,
             request.crypto_currency.to_lowercase(),
             self.api_key,
             request.fiat_currency.to_lowercase(),
             request.fiat_amount
-        )
+        );
+        
+        if let Some(method) = request.payment_method {
+            url.push_str(&format!("&paymentMethod={}", method.provider_id()));
+        }
+        
+        url
     }
     
     /// Build supported currencies URL
@@ -117,6 +123,15 @@ impl MoonPayClient {
         
         let total_fees = fee_amount + extra_fee;
         
+        let payment_methods = parse_payment_methods(parsed.get("paymentMethod"))
+            .unwrap_or_else(|| vec![
+                PaymentMethod::CreditCard,
+                PaymentMethod::DebitCard,
+                PaymentMethod::ApplePay,
+                PaymentMethod::GooglePay,
+                PaymentMethod::BankTransfer,
+            ]);
+        
         Ok(OnRampQuote {
             provider: OnRampProvider::MoonPay,
             fiat_amount: request.fiat_amount,
@@ -127,13 +142,9 @@ impl MoonPayClient {
             provider_fee: fee_amount,
             total_fees,
             exchange_rate: if quote_amount > 0.0 { request.fiat_amount / quote_amount } else { 0.0 },
-            payment_methods: vec![
-                PaymentMethod::CreditCard,
-                PaymentMethod::DebitCard,
-                PaymentMethod::ApplePay,
-                PaymentMethod::GooglePay,
-                PaymentMethod::BankTransfer,
-            ],
+            payment_methods,
+            kyc_required: true,
+            kyc_threshold: None,
             expires_at: None,
             quote_id: None,
         })
@@ -252,6 +263,41 @@ mod tests {
         assert!((quote.total_fees - 4.49).abs() < 0.001);
     }
     
+    #[test]
+    fn test_parse_quote_payment_method_and_kyc() {
+        let client = MoonPayClient::new("pk_test_123".into());
+        let json = r#"{
+            "baseCurrencyCode": "usd",
+            "baseCurrencyAmount": 100,
+            "quoteCurrencyCode": "eth",
+            "quoteCurrencyAmount": 0.045,
+            "quoteCurrencyPrice": 2122.45,
+            "paymentMethod": "apple_pay",
+            "feeAmount": 3.99,
+            "extraFeeAmount": 0,
+            "extraFeePercentage": 0,
+            "networkFeeAmount": 0.001,
+            "totalAmount": 103.99
+        }"#;
+        
+        let request = OnRampRequest::new(100.0, "USD", "ETH", "0x...");
+        let quote = client.parse_quote(json, &request).unwrap();
+        
+        assert_eq!(quote.payment_methods, vec![PaymentMethod::ApplePay]);
+        // The quote carries no KYC information
+        assert!(quote.kyc_required);
+        assert_eq!(quote.kyc_threshold, None);
+    }
+    
+    #[test]
+    fn test_quote_url_payment_method() {
+        let client = MoonPayClient::new("pk_test_123".into());
+        let request = OnRampRequest::new(100.0, "EUR", "ETH", "0x...")
+            .with_payment_method(PaymentMethod::Sepa);
+        
+        assert!(client.quote_url(&request).contains("paymentMethod=sepa_bank_transfer"));
+    }
+    
     #[test]
     fn test_supported_cryptos() {
         let cryptos = MoonPayClient::supported_cryptos();
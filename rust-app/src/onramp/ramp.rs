@@ -151,6 +151,16 @@ impl RampClient {
         
         let total_fees = base_fee + network_fee;
         
+        let payment_methods = parse_payment_methods(parsed.get("paymentMethodType"))
+            .unwrap_or_else(|| vec![
+                PaymentMethod::CreditCard,
+                PaymentMethod::DebitCard,
+                PaymentMethod::ApplePay,
+                PaymentMethod::BankTransfer,
+                PaymentMethod::Sepa,
+                PaymentMethod::iDEAL,
+            ]);
+        
         Ok(OnRampQuote {
             provider: OnRampProvider::Ramp,
             fiat_amount: request.fiat_amount,
@@ -161,14 +171,9 @@ impl RampClient {
             provider_fee: base_fee,
             total_fees,
             exchange_rate: if crypto_amount > 0.0 { request.fiat_amount / crypto_amount } else { 0.0 },
-            payment_methods: vec![
-                PaymentMethod::CreditCard,
-                PaymentMethod::DebitCard,
-                PaymentMethod::ApplePay,
-                PaymentMethod::BankTransfer,
-                PaymentMethod::Sepa,
-                PaymentMethod::iDEAL,
-            ],
+            payment_methods,
+            kyc_required: true,
+            kyc_threshold: None,
             expires_at: None,
             quote_id: None,
         })
@@ -45,6 +45,8 @@ mod integration_tests {
             total_fees: 24.99,
             exchange_rate: 47619.05,
             payment_methods: vec![PaymentMethod::CreditCard],
+            kyc_required: true,
+            kyc_threshold: None,
             expires_at: None,
             quote_id: Some("mp-123".into()),
         });
@@ -60,6 +62,8 @@ mod integration_tests {
             total_fees: 27.50,
             exchange_rate: 49019.61,
             payment_methods: vec![PaymentMethod::CreditCard, PaymentMethod::Sepa],
+            kyc_required: true,
+            kyc_threshold: None,
             expires_at: None,
             quote_id: Some("tr-456".into()),
         });
@@ -75,6 +79,8 @@ mod integration_tests {
             total_fees: 14.99,
             exchange_rate: 46296.30,
             payment_methods: vec![PaymentMethod::CreditCard, PaymentMethod::BankTransfer],
+            kyc_required: true,
+            kyc_threshold: None,
             expires_at: None,
             quote_id: None,
         });
@@ -97,10 +103,11 @@ mod integration_tests {
     fn test_all_providers() {
         let providers = OnRampProvider::all();
         
-        assert_eq!(providers.len(), 3);
+        assert_eq!(providers.len(), 4);
         assert!(providers.contains(&OnRampProvider::MoonPay));
         assert!(providers.contains(&OnRampProvider::Transak));
         assert!(providers.contains(&OnRampProvider::Ramp));
+        assert!(providers.contains(&OnRampProvider::Banxa));
     }
     
     #[test]
@@ -175,6 +182,8 @@ mod integration_tests {
             total_fees: 5.00,
             exchange_rate: 2222.22,
             payment_methods: vec![],
+            kyc_required: true,
+            kyc_threshold: None,
             expires_at: None,
             quote_id: None,
         };
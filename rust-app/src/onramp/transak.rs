@@ -77,12 +77,13 @@ impl TransakClient {
     /// GET /api/v1/pricing/public/quotes
     pub fn quote_url(&self, request: &OnRampRequest) -> String {
         format!(
-            "{}/api/v1/pricing/public/quotes?partnerApiKey={}&cryptoCurrency={}&fiatCurrency={}&fiatAmount={}&isBuyOrSell=BUY&paymentMethod=credit_debit_card",
+            "{}/api/v1/pricing/public/quotes?partnerApiKey={}&cryptoCurrency={}&fiatCurrency={}&fiatAmount={}&isBuyOrSell=BUY&paymentMethod={}",
             TRANSAK_API_BASE,
             self.api_key,
             request.crypto_currency.to_uppercase(),
             request.fiat_currency.to_uppercase(),
-            request.fiat_amount
+            request.fiat_amount,
+            request.payment_method.map_or("credit_debit_card", |m| m.provider_id())
         )
     }
     
@@ -124,6 +125,16 @@ impl TransakClient {
             .and_then(|v| v.as_f64())
             .unwrap_or(0.0);
         
+        let payment_methods = parse_payment_methods(response.get("paymentMethod"))
+            .unwrap_or_else(|| vec![
+                PaymentMethod::CreditCard,
+                PaymentMethod::DebitCard,
+                PaymentMethod::ApplePay,
+                PaymentMethod::GooglePay,
+                PaymentMethod::BankTransfer,
+                PaymentMethod::Sepa,
+            ]);
+        
         Ok(OnRampQuote {
             provider: OnRampProvider::Transak,
             fiat_amount: request.fiat_amount,
@@ -134,14 +145,9 @@ impl TransakClient {
             provider_fee: fee_amount - network_fee,
             total_fees: fee_amount,
             exchange_rate: if crypto_amount > 0.0 { request.fiat_amount / crypto_amount } else { 0.0 },
-            payment_methods: vec![
-                PaymentMethod::CreditCard,
-                PaymentMethod::DebitCard,
-                PaymentMethod::ApplePay,
-                PaymentMethod::GooglePay,
-                PaymentMethod::BankTransfer,
-                PaymentMethod::Sepa,
-            ],
+            payment_methods,
+            kyc_required: true,
+            kyc_threshold: None,
             expires_at: None,
             quote_id: response.get("quoteId").and_then(|v| v.as_str()).map(|s| s.to_string()),
         })
@@ -280,6 +286,46 @@ mod tests {
         assert_eq!(quote.quote_id, Some("quote-123".into()));
     }
     
+    #[test]
+    fn test_parse_quote_payment_method_and_kyc() {
+        let client = TransakClient::new("api_key_123".into());
+        let request = OnRampRequest::new(100.0, "EUR", "ETH", "0x...");
+        
+        let json = r#"{
+            "response": {
+                "quoteId": "quote-456",
+                "conversionPrice": 0.00048,
+                "fiatCurrency": "EUR",
+                "cryptoCurrency": "ETH",
+                "paymentMethod": "sepa_bank_transfer",
+                "fiatAmount": 100,
+                "cryptoAmount": 0.048,
+                "isBuyOrSell": "BUY",
+                "network": "ethereum",
+                "totalFee": 2.10
+            }
+        }"#;
+        
+        let quote = client.parse_quote(json, &request).unwrap();
+        
+        assert_eq!(quote.payment_methods, vec![PaymentMethod::Sepa]);
+        assert!(quote.supports(PaymentMethod::Sepa));
+        assert!(!quote.supports(PaymentMethod::ApplePay));
+        // The quote carries no KYC information
+        assert!(quote.kyc_required);
+        assert_eq!(quote.kyc_threshold, None);
+    }
+    
+    #[test]
+    fn test_quote_url_payment_method() {
+        let client = TransakClient::new("api_key_123".into());
+        let request = OnRampRequest::new(100.0, "USD", "ETH", "0x...");
+        assert!(client.quote_url(&request).contains("paymentMethod=credit_debit_card"));
+        
+        let request = request.with_payment_method(PaymentMethod::GooglePay);
+        assert!(client.quote_url(&request).contains("paymentMethod=google_pay"));
+    }
+    
     #[test]
     fn test_supported_cryptos() {
         let cryptos = TransakClient::supported_cryptos();
@@ -1,13 +1,16 @@
 //! On-ramp types and data structures
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Supported on-ramp providers
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OnRampProvider {
     MoonPay,
     Transak,
     Ramp,
+    Banxa,
 }
 
 impl OnRampProvider {
@@ -16,6 +19,7 @@ impl OnRampProvider {
             OnRampProvider::MoonPay => "MoonPay",
             OnRampProvider::Transak => "Transak",
             OnRampProvider::Ramp => "Ramp Network",
+            OnRampProvider::Banxa => "Banxa",
         }
     }
     
@@ -24,11 +28,12 @@ impl OnRampProvider {
             OnRampProvider::MoonPay => "https://www.moonpay.com",
             OnRampProvider::Transak => "https://transak.com",
             OnRampProvider::Ramp => "https://ramp.network",
+            OnRampProvider::Banxa => "https://banxa.com",
         }
     }
     
     pub fn all() -> Vec<OnRampProvider> {
-        vec![OnRampProvider::MoonPay, OnRampProvider::Transak, OnRampProvider::Ramp]
+        vec![OnRampProvider::MoonPay, OnRampProvider::Transak, OnRampProvider::Ramp, OnRampProvider::Banxa]
     }
 }
 
@@ -39,7 +44,8 @@ impl fmt::Display for OnRampProvider {
 }
 
 /// Payment method types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 #[allow(non_camel_case_types)]
 pub enum PaymentMethod {
     CreditCard,
@@ -48,9 +54,12 @@ pub enum PaymentMethod {
     GooglePay,
     BankTransfer,
     Sepa,
+    #[serde(rename = "ach")]
     ACH,
     Wire,
+    #[serde(rename = "pix")]
     PIX,
+    #[serde(rename = "ideal")]
     iDEAL,
 }
 
@@ -86,6 +95,63 @@ impl PaymentMethod {
     }
 }
 
+impl PaymentMethod {
+    /// Map a provider payment method identifier (e.g. `credit_debit_card`)
+    pub fn from_provider_id(id: &str) -> Vec<PaymentMethod> {
+        match id.to_lowercase().as_str() {
+            "credit_debit_card" | "card" | "card_payment" => vec![PaymentMethod::CreditCard, PaymentMethod::DebitCard],
+            "credit_card" => vec![PaymentMethod::CreditCard],
+            "debit_card" => vec![PaymentMethod::DebitCard],
+            "apple_pay" => vec![PaymentMethod::ApplePay],
+            "google_pay" => vec![PaymentMethod::GooglePay],
+            "sepa_bank_transfer" | "sepa" => vec![PaymentMethod::Sepa],
+            "gbp_bank_transfer" | "bank_transfer" | "manual_bank_transfer" => vec![PaymentMethod::BankTransfer],
+            "ach_bank_transfer" | "ach" => vec![PaymentMethod::ACH],
+            "wire_transfer" | "pm_us_wire_bank_transfer" => vec![PaymentMethod::Wire],
+            "pix_instant_payment" | "pm_pix" | "pix" => vec![PaymentMethod::PIX],
+            "ideal" => vec![PaymentMethod::iDEAL],
+            _ => Vec::new(),
+        }
+    }
+    
+    /// Provider identifier used in quote requests
+    pub fn provider_id(&self) -> &'static str {
+        match self {
+            PaymentMethod::CreditCard | PaymentMethod::DebitCard => "credit_debit_card",
+            PaymentMethod::ApplePay => "apple_pay",
+            PaymentMethod::GooglePay => "google_pay",
+            PaymentMethod::BankTransfer => "gbp_bank_transfer",
+            PaymentMethod::Sepa => "sepa_bank_transfer",
+            PaymentMethod::ACH => "ach_bank_transfer",
+            PaymentMethod::Wire => "wire_transfer",
+            PaymentMethod::PIX => "pix_instant_payment",
+            PaymentMethod::iDEAL => "ideal",
+        }
+    }
+}
+
+/// Payment methods from a provider field holding one identifier or a list of them
+///
+/// Returns `None` when the field is absent or holds nothing recognizable, so
+/// callers can fall back to the provider's default list.
+pub(crate) fn parse_payment_methods(value: Option<&serde_json::Value>) -> Option<Vec<PaymentMethod>> {
+    let ids: Vec<&str> = match value? {
+        serde_json::Value::String(id) => vec![id.as_str()],
+        serde_json::Value::Array(items) => items.iter()
+            .filter_map(|item| item.as_str().or_else(|| item.get("id").and_then(|id| id.as_str())))
+            .collect(),
+        _ => return None,
+    };
+    
+    let mut methods = Vec::new();
+    for method in ids.into_iter().flat_map(PaymentMethod::from_provider_id) {
+        if !methods.contains(&method) {
+            methods.push(method);
+        }
+    }
+    if methods.is_empty() { None } else { Some(methods) }
+}
+
 impl fmt::Display for PaymentMethod {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.name())
@@ -93,7 +159,7 @@ impl fmt::Display for PaymentMethod {
 }
 
 /// On-ramp quote from a provider
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct OnRampQuote {
     /// Provider offering this quote
     pub provider: OnRampProvider,
@@ -115,6 +181,13 @@ pub struct OnRampQuote {
     pub exchange_rate: f64,
     /// Supported payment methods
     pub payment_methods: Vec<PaymentMethod>,
+    /// Whether identity verification is required at this fiat amount
+    ///
+    /// None of the providers' quote APIs report KYC requirements, so quotes
+    /// parsed from them always set this and leave `kyc_threshold` empty.
+    pub kyc_required: bool,
+    /// Fiat amount above which KYC is required (`None` = always required)
+    pub kyc_threshold: Option<f64>,
     /// Quote expiry time (unix timestamp)
     pub expires_at: Option<u64>,
     /// Quote ID for reference
@@ -137,6 +210,11 @@ impl OnRampQuote {
         }
         (self.total_fees / self.fiat_amount) * 100.0
     }
+    
+    /// Whether this quote can be paid with `method`
+    pub fn supports(&self, method: PaymentMethod) -> bool {
+        self.payment_methods.contains(&method)
+    }
 }
 
 /// Request for on-ramp quote
@@ -156,6 +234,8 @@ pub struct OnRampRequest {
     pub country_code: Option<String>,
     /// User's email (for some providers)
     pub email: Option<String>,
+    /// Preferred payment method
+    pub payment_method: Option<PaymentMethod>,
 }
 
 impl OnRampRequest {
@@ -168,6 +248,7 @@ impl OnRampRequest {
             network: None,
             country_code: None,
             email: None,
+            payment_method: None,
         }
     }
     
@@ -185,6 +266,11 @@ impl OnRampRequest {
         self.email = Some(email.to_string());
         self
     }
+    
+    pub fn with_payment_method(mut self, method: PaymentMethod) -> Self {
+        self.payment_method = Some(method);
+        self
+    }
 }
 
 /// Aggregated quotes from multiple providers
@@ -218,12 +304,95 @@ impl OnRampQuotes {
         })
     }
     
+    /// Quotes that can be paid with `method`
+    pub fn with_payment_method(&self, method: PaymentMethod) -> Vec<&OnRampQuote> {
+        self.quotes.iter().filter(|q| q.supports(method)).collect()
+    }
+    
     /// Sort quotes by effective rate (best first)
     pub fn sorted_by_rate(&self) -> Vec<&OnRampQuote> {
         let mut sorted: Vec<_> = self.quotes.iter().collect();
         sorted.sort_by(|a, b| a.effective_rate().partial_cmp(&b.effective_rate()).unwrap());
         sorted
     }
+    
+    /// Rank quotes by crypto received for the requested fiat amount (best first)
+    ///
+    /// Spread is measured against `reference_rate` (fiat per crypto, e.g. the
+    /// mid-market price). Without one, the best fee-adjusted rate among the
+    /// quotes is used, so the tightest quote shows zero spread.
+    pub fn ranked(&self, reference_rate: Option<f64>) -> Vec<RankedQuote> {
+        let reference_rate = reference_rate.or_else(|| {
+            self.quotes.iter()
+                .filter(|q| q.crypto_amount > 0.0)
+                .map(|q| (q.fiat_amount - q.total_fees) / q.crypto_amount)
+                .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        });
+
+        let mut ranked: Vec<RankedQuote> = self.quotes.iter().map(|quote| {
+            // Normalize to the requested fiat input in case a provider quoted a different amount
+            let amount_received = if quote.fiat_amount > 0.0 {
+                quote.crypto_amount * self.request.fiat_amount / quote.fiat_amount
+            } else {
+                0.0
+            };
+            let total_cost = match reference_rate {
+                Some(rate) => (self.request.fiat_amount - amount_received * rate).max(0.0),
+                None => quote.total_fees,
+            };
+
+            RankedQuote {
+                rank: 0,
+                quote: quote.clone(),
+                amount_received,
+                fees: FeeBreakdown {
+                    provider_fee: quote.provider_fee,
+                    network_fee: quote.network_fee,
+                    spread: (total_cost - quote.total_fees).max(0.0),
+                    total_cost,
+                    cost_percentage: if self.request.fiat_amount > 0.0 {
+                        total_cost / self.request.fiat_amount * 100.0
+                    } else {
+                        0.0
+                    },
+                },
+            }
+        }).collect();
+
+        ranked.sort_by(|a, b| {
+            b.amount_received.partial_cmp(&a.amount_received).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        for (index, entry) in ranked.iter_mut().enumerate() {
+            entry.rank = index + 1;
+        }
+        ranked
+    }
+}
+
+/// Quote with its position in a comparison and an all-in cost breakdown
+#[derive(Debug, Clone, Serialize)]
+pub struct RankedQuote {
+    /// 1-based rank (1 = most crypto received)
+    pub rank: usize,
+    pub quote: OnRampQuote,
+    /// Crypto received for the requested fiat amount
+    pub amount_received: f64,
+    pub fees: FeeBreakdown,
+}
+
+/// Fiat cost breakdown for a quote
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeBreakdown {
+    /// Provider fee in fiat
+    pub provider_fee: f64,
+    /// Network fee as reported by the provider
+    pub network_fee: f64,
+    /// Cost hidden in the exchange rate, in fiat
+    pub spread: f64,
+    /// Fiat spent minus the reference value of the crypto received
+    pub total_cost: f64,
+    /// Total cost as a percentage of the fiat amount
+    pub cost_percentage: f64,
 }
 
 /// Supported fiat currencies
@@ -403,8 +572,10 @@ mod tests {
         assert_eq!(OnRampProvider::Transak.name(), "Transak");
         assert_eq!(OnRampProvider::Ramp.name(), "Ramp Network");
         
+        assert_eq!(OnRampProvider::Banxa.name(), "Banxa");
+        
         let all = OnRampProvider::all();
-        assert_eq!(all.len(), 3);
+        assert_eq!(all.len(), 4);
     }
     
     #[test]
@@ -414,6 +585,21 @@ mod tests {
         assert_eq!(PaymentMethod::Sepa.name(), "SEPA");
     }
     
+    #[test]
+    fn test_parse_provider_payment_methods() {
+        let single = serde_json::json!("sepa_bank_transfer");
+        assert_eq!(parse_payment_methods(Some(&single)), Some(vec![PaymentMethod::Sepa]));
+        
+        let list = serde_json::json!(["credit_debit_card", {"id": "apple_pay"}, "unknown"]);
+        assert_eq!(
+            parse_payment_methods(Some(&list)),
+            Some(vec![PaymentMethod::CreditCard, PaymentMethod::DebitCard, PaymentMethod::ApplePay])
+        );
+        
+        assert_eq!(parse_payment_methods(Some(&serde_json::json!("unknown"))), None);
+        assert_eq!(parse_payment_methods(None), None);
+    }
+    
     #[test]
     fn test_quote_calculations() {
         let quote = OnRampQuote {
@@ -427,6 +613,8 @@ mod tests {
             total_fees: 4.99,
             exchange_rate: 50000.0,
             payment_methods: vec![PaymentMethod::CreditCard, PaymentMethod::ApplePay],
+            kyc_required: true,
+            kyc_threshold: None,
             expires_at: None,
             quote_id: None,
         };
@@ -465,6 +653,8 @@ mod tests {
             total_fees: 5.99,
             exchange_rate: 2000.0,
             payment_methods: vec![PaymentMethod::CreditCard],
+            kyc_required: true,
+            kyc_threshold: None,
             expires_at: None,
             quote_id: None,
         });
@@ -480,6 +670,8 @@ mod tests {
             total_fees: 4.99,
             exchange_rate: 1920.0,
             payment_methods: vec![PaymentMethod::CreditCard],
+            kyc_required: true,
+            kyc_threshold: None,
             expires_at: None,
             quote_id: None,
         });
@@ -489,6 +681,12 @@ mod tests {
         
         let lowest_fee = quotes.lowest_fee_quote().unwrap();
         assert_eq!(lowest_fee.provider, OnRampProvider::Transak);
+        
+        quotes.quotes[0].payment_methods.push(PaymentMethod::ApplePay);
+        let apple_pay = quotes.with_payment_method(PaymentMethod::ApplePay);
+        assert_eq!(apple_pay.len(), 1);
+        assert_eq!(apple_pay[0].provider, OnRampProvider::MoonPay);
+        assert!(quotes.with_payment_method(PaymentMethod::PIX).is_empty());
     }
     
     #[test]
@@ -1,10 +1,11 @@
 //! Ordinals and BRC-20 module
 //!
-//! Provides support for Bitcoin Ordinals inscriptions and BRC-20 tokens.
+//! Provides support for Bitcoin Ordinals inscriptions, BRC-20 tokens and Runes.
 
 pub mod types;
 pub mod indexer;
 pub mod parser;
+pub mod runes;
 
 #[cfg(test)]
 pub mod tests;
@@ -12,3 +13,4 @@ pub mod tests;
 pub use types::*;
 pub use indexer::*;
 pub use parser::*;
+pub use runes::*;
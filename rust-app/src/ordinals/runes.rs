@@ -0,0 +1,836 @@
+//! Runes protocol support
+//!
+//! Runestones live in an `OP_RETURN OP_13 <pushes>` output. The concatenated
+//! pushes form a sequence of LEB128 integers: tag/value pairs, optionally
+//! followed by the `Body` tag and delta-encoded edicts in groups of four.
+//! Malformed runestones are cenotaphs, which burn every rune in the inputs.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::{Amount, ScriptBuf, TxOut};
+use serde::{Deserialize, Serialize};
+
+use super::types::OrdinalsError;
+
+const OP_RETURN: u8 = 0x6a;
+const OP_13: u8 = 0x5d;
+const OP_PUSHDATA1: u8 = 0x4c;
+const OP_PUSHDATA2: u8 = 0x4d;
+const OP_PUSHDATA4: u8 = 0x4e;
+
+/// Largest single data push allowed by standardness rules
+const MAX_PUSH_SIZE: usize = 520;
+
+/// Maximum divisibility of a rune
+pub const MAX_DIVISIBILITY: u8 = 38;
+
+/// Spacer bits are only valid between the 28 possible rune letters
+pub const MAX_SPACERS: u32 = 0b0000_0111_1111_1111_1111_1111_1111_1111;
+
+/// Spacer character used when displaying rune names
+pub const SPACER: char = '•';
+
+/// Default postage for rune-carrying outputs (sats)
+pub const DEFAULT_RUNE_POSTAGE: u64 = 546;
+
+// Message tags; unknown even tags make a cenotaph
+const TAG_BODY: u128 = 0;
+const TAG_FLAGS: u128 = 2;
+const TAG_RUNE: u128 = 4;
+const TAG_PREMINE: u128 = 6;
+const TAG_CAP: u128 = 8;
+const TAG_AMOUNT: u128 = 10;
+const TAG_HEIGHT_START: u128 = 12;
+const TAG_HEIGHT_END: u128 = 14;
+const TAG_OFFSET_START: u128 = 16;
+const TAG_OFFSET_END: u128 = 18;
+const TAG_MINT: u128 = 20;
+const TAG_POINTER: u128 = 22;
+const TAG_DIVISIBILITY: u128 = 1;
+const TAG_SPACERS: u128 = 3;
+const TAG_SYMBOL: u128 = 5;
+
+const FLAG_ETCHING: u128 = 1 << 0;
+const FLAG_TERMS: u128 = 1 << 1;
+const FLAG_TURBO: u128 = 1 << 2;
+
+/// Rune identifier: the block height and transaction index of its etching
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RuneId {
+    pub block: u64,
+    pub tx: u32,
+}
+
+impl RuneId {
+    pub fn new(block: u64, tx: u32) -> Self {
+        Self { block, tx }
+    }
+
+    // `block:tx`, or `None` for `0:tx` with a nonzero `tx`, which names no rune
+    fn checked(block: u64, tx: u32) -> Option<RuneId> {
+        (block > 0 || tx == 0).then_some(RuneId { block, tx })
+    }
+
+    /// Apply an edict delta to this ID
+    fn next(&self, block_delta: u128, tx_delta: u128) -> Option<RuneId> {
+        let block = self.block.checked_add(u64::try_from(block_delta).ok()?)?;
+        let tx = if block_delta == 0 {
+            self.tx.checked_add(u32::try_from(tx_delta).ok()?)?
+        } else {
+            u32::try_from(tx_delta).ok()?
+        };
+        RuneId::checked(block, tx)
+    }
+
+    /// Delta from this ID to a later one (`next` must sort after `self`)
+    fn delta(&self, next: RuneId) -> (u128, u128) {
+        let block = next.block - self.block;
+        let tx = if block == 0 { next.tx - self.tx } else { next.tx };
+        (block.into(), tx.into())
+    }
+}
+
+impl fmt::Display for RuneId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.block, self.tx)
+    }
+}
+
+impl FromStr for RuneId {
+    type Err = OrdinalsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || OrdinalsError::InvalidRune(format!("Invalid rune ID: {}", s));
+        let (block, tx) = s.split_once(':').ok_or_else(invalid)?;
+        Ok(RuneId {
+            block: block.parse().map_err(|_| invalid())?,
+            tx: tx.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Rune name, stored as its modified base-26 integer (A = 0, Z = 25, AA = 26)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Rune(pub u128);
+
+impl Rune {
+    /// Parse a name that may contain spacers (e.g. `UNCOMMON•GOODS`)
+    ///
+    /// Returns the rune and its spacer bitfield. `.` is accepted as a spacer.
+    pub fn from_spaced(name: &str) -> Result<(Rune, u32), OrdinalsError> {
+        let invalid = || OrdinalsError::InvalidRune(format!("Invalid spaced rune: {}", name));
+        let mut letters = String::new();
+        let mut spacers = 0u32;
+
+        for c in name.chars() {
+            if c == SPACER || c == '.' {
+                // A spacer must follow a letter and can't be doubled
+                let position = letters.len().checked_sub(1).ok_or_else(invalid)?;
+                let bit = 1u32.checked_shl(position as u32).ok_or_else(invalid)?;
+                if spacers & bit != 0 {
+                    return Err(invalid());
+                }
+                spacers |= bit;
+            } else {
+                letters.push(c);
+            }
+        }
+
+        // Trailing spacer
+        if spacers != 0 && 32 - spacers.leading_zeros() >= letters.len() as u32 {
+            return Err(invalid());
+        }
+
+        Ok((letters.parse()?, spacers))
+    }
+
+    /// Format the name with spacers inserted
+    pub fn spaced(&self, spacers: u32) -> String {
+        let name = self.to_string();
+        let mut spaced = String::with_capacity(name.len() * 2);
+        for (i, c) in name.chars().enumerate() {
+            spaced.push(c);
+            if i + 1 < name.len() && i < 32 && spacers & (1 << i) != 0 {
+                spaced.push(SPACER);
+            }
+        }
+        spaced
+    }
+}
+
+impl fmt::Display for Rune {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // u128::MAX + 1 overflows the loop below
+        if self.0 == u128::MAX {
+            return write!(f, "BCGDENLQRQWDSLRUGSNLBTMFIJAV");
+        }
+
+        let mut n = self.0 + 1;
+        let mut symbol = Vec::new();
+        while n > 0 {
+            symbol.push(b'A' + ((n - 1) % 26) as u8);
+            n = (n - 1) / 26;
+        }
+        symbol.reverse();
+        write!(f, "{}", String::from_utf8_lossy(&symbol))
+    }
+}
+
+impl FromStr for Rune {
+    type Err = OrdinalsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || OrdinalsError::InvalidRune(format!("Invalid rune name: {}", s));
+        if s.is_empty() {
+            return Err(invalid());
+        }
+
+        let mut x = 0u128;
+        for (i, c) in s.chars().enumerate() {
+            if i > 0 {
+                x = x.checked_add(1).ok_or_else(invalid)?;
+            }
+            x = x.checked_mul(26).ok_or_else(invalid)?;
+            match c {
+                'A'..='Z' => x = x.checked_add(c as u128 - 'A' as u128).ok_or_else(invalid)?,
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(Rune(x))
+    }
+}
+
+/// Transfer of `amount` units of a rune to an output
+///
+/// `output` equal to the transaction's output count splits the amount
+/// across all non-`OP_RETURN` outputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Edict {
+    pub id: RuneId,
+    pub amount: u128,
+    pub output: u32,
+}
+
+/// Open mint terms of an etching
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Terms {
+    /// Units created per mint
+    pub amount: Option<u128>,
+    /// Maximum number of mints
+    pub cap: Option<u128>,
+    /// Absolute block height window (start, end)
+    pub height: (Option<u64>, Option<u64>),
+    /// Window relative to the etching block (start, end)
+    pub offset: (Option<u64>, Option<u64>),
+}
+
+/// Creation of a new rune
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Etching {
+    pub divisibility: Option<u8>,
+    pub premine: Option<u128>,
+    /// Name; a reserved name is assigned when absent
+    pub rune: Option<Rune>,
+    pub spacers: Option<u32>,
+    pub symbol: Option<char>,
+    pub terms: Option<Terms>,
+    /// Opt in to future protocol changes
+    pub turbo: bool,
+}
+
+impl Etching {
+    /// Maximum supply (premine plus all mints), `None` on overflow
+    pub fn supply(&self) -> Option<u128> {
+        let premine = self.premine.unwrap_or_default();
+        let cap = self.terms.and_then(|t| t.cap).unwrap_or_default();
+        let amount = self.terms.and_then(|t| t.amount).unwrap_or_default();
+        premine.checked_add(cap.checked_mul(amount)?)
+    }
+
+    /// Display name with spacers (e.g. `UNCOMMON•GOODS`)
+    pub fn spaced_name(&self) -> Option<String> {
+        self.rune.map(|rune| rune.spaced(self.spacers.unwrap_or_default()))
+    }
+}
+
+/// A decoded runestone
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Runestone {
+    pub edicts: Vec<Edict>,
+    pub etching: Option<Etching>,
+    pub mint: Option<RuneId>,
+    /// Output receiving unallocated runes (defaults to the first non-`OP_RETURN` output)
+    pub pointer: Option<u32>,
+    /// Malformed runestone: edicts and pointer are ignored and input runes are burned
+    pub cenotaph: bool,
+}
+
+impl Runestone {
+    fn cenotaph() -> Self {
+        Runestone { cenotaph: true, ..Default::default() }
+    }
+
+    /// Build the `OP_RETURN` script carrying this runestone
+    pub fn encipher(&self) -> Vec<u8> {
+        let mut payload = Vec::new();
+
+        if let Some(etching) = self.etching {
+            let mut flags = FLAG_ETCHING;
+            if etching.terms.is_some() {
+                flags |= FLAG_TERMS;
+            }
+            if etching.turbo {
+                flags |= FLAG_TURBO;
+            }
+            encode_field(&mut payload, TAG_FLAGS, Some(flags));
+            encode_field(&mut payload, TAG_RUNE, etching.rune.map(|r| r.0));
+            encode_field(&mut payload, TAG_DIVISIBILITY, etching.divisibility.map(u128::from));
+            encode_field(&mut payload, TAG_SPACERS, etching.spacers.map(u128::from));
+            encode_field(&mut payload, TAG_SYMBOL, etching.symbol.map(|c| u128::from(u32::from(c))));
+            encode_field(&mut payload, TAG_PREMINE, etching.premine);
+
+            if let Some(terms) = etching.terms {
+                encode_field(&mut payload, TAG_AMOUNT, terms.amount);
+                encode_field(&mut payload, TAG_CAP, terms.cap);
+                encode_field(&mut payload, TAG_HEIGHT_START, terms.height.0.map(u128::from));
+                encode_field(&mut payload, TAG_HEIGHT_END, terms.height.1.map(u128::from));
+                encode_field(&mut payload, TAG_OFFSET_START, terms.offset.0.map(u128::from));
+                encode_field(&mut payload, TAG_OFFSET_END, terms.offset.1.map(u128::from));
+            }
+        }
+
+        if let Some(id) = self.mint {
+            encode_field(&mut payload, TAG_MINT, Some(id.block.into()));
+            encode_field(&mut payload, TAG_MINT, Some(id.tx.into()));
+        }
+
+        encode_field(&mut payload, TAG_POINTER, self.pointer.map(u128::from));
+
+        if !self.edicts.is_empty() {
+            encode_varint(TAG_BODY, &mut payload);
+
+            let mut edicts = self.edicts.clone();
+            edicts.sort_by_key(|edict| edict.id);
+
+            let mut previous = RuneId::default();
+            for edict in edicts {
+                let (block, tx) = previous.delta(edict.id);
+                encode_varint(block, &mut payload);
+                encode_varint(tx, &mut payload);
+                encode_varint(edict.amount, &mut payload);
+                encode_varint(edict.output.into(), &mut payload);
+                previous = edict.id;
+            }
+        }
+
+        let mut script = vec![OP_RETURN, OP_13];
+        for chunk in payload.chunks(MAX_PUSH_SIZE) {
+            push_data(&mut script, chunk);
+        }
+        script
+    }
+}
+
+/// Decode a runestone from an output script
+///
+/// Returns `None` if the script is not a runestone (`OP_RETURN OP_13 ...`).
+/// Malformed runestones decode as cenotaphs. Edict outputs are not checked
+/// against the transaction's output count.
+pub fn decode_runestone(script: &[u8]) -> Option<Runestone> {
+    if script.len() < 2 || script[0] != OP_RETURN || script[1] != OP_13 {
+        return None;
+    }
+
+    let Some(payload) = read_pushes(&script[2..]) else {
+        return Some(Runestone::cenotaph());
+    };
+
+    let mut integers = Vec::new();
+    let mut i = 0;
+    while i < payload.len() {
+        match decode_varint(&payload[i..]) {
+            Some((value, length)) => {
+                integers.push(value);
+                i += length;
+            }
+            None => return Some(Runestone::cenotaph()),
+        }
+    }
+
+    let mut flawed = false;
+    let mut edicts = Vec::new();
+    let mut fields: Vec<(u128, Vec<u128>)> = Vec::new();
+
+    let mut i = 0;
+    while i < integers.len() {
+        let tag = integers[i];
+        if tag == TAG_BODY {
+            let mut id = RuneId::default();
+            for chunk in integers[i + 1..].chunks(4) {
+                if chunk.len() != 4 {
+                    flawed = true;
+                    break;
+                }
+                let (Some(next), Ok(output)) = (id.next(chunk[0], chunk[1]), u32::try_from(chunk[3])) else {
+                    flawed = true;
+                    break;
+                };
+                edicts.push(Edict { id: next, amount: chunk[2], output });
+                id = next;
+            }
+            break;
+        }
+
+        let Some(&value) = integers.get(i + 1) else {
+            flawed = true;
+            break;
+        };
+        match fields.iter_mut().find(|(t, _)| *t == tag) {
+            Some((_, values)) => values.push(value),
+            None => fields.push((tag, vec![value])),
+        }
+        i += 2;
+    }
+
+    let mut flags = take_field(&mut fields, TAG_FLAGS, 1, |v| Some(v[0])).unwrap_or_default();
+
+    let etching = if flags & FLAG_ETCHING != 0 {
+        flags &= !FLAG_ETCHING;
+        let has_terms = flags & FLAG_TERMS != 0;
+        flags &= !FLAG_TERMS;
+        let turbo = flags & FLAG_TURBO != 0;
+        flags &= !FLAG_TURBO;
+
+        Some(Etching {
+            divisibility: take_field(&mut fields, TAG_DIVISIBILITY, 1, |v| {
+                u8::try_from(v[0]).ok().filter(|d| *d <= MAX_DIVISIBILITY)
+            }),
+            premine: take_field(&mut fields, TAG_PREMINE, 1, |v| Some(v[0])),
+            rune: take_field(&mut fields, TAG_RUNE, 1, |v| Some(Rune(v[0]))),
+            spacers: take_field(&mut fields, TAG_SPACERS, 1, |v| {
+                u32::try_from(v[0]).ok().filter(|s| *s <= MAX_SPACERS)
+            }),
+            symbol: take_field(&mut fields, TAG_SYMBOL, 1, |v| char::from_u32(u32::try_from(v[0]).ok()?)),
+            terms: has_terms.then(|| Terms {
+                amount: take_field(&mut fields, TAG_AMOUNT, 1, |v| Some(v[0])),
+                cap: take_field(&mut fields, TAG_CAP, 1, |v| Some(v[0])),
+                height: (
+                    take_field(&mut fields, TAG_HEIGHT_START, 1, |v| u64::try_from(v[0]).ok()),
+                    take_field(&mut fields, TAG_HEIGHT_END, 1, |v| u64::try_from(v[0]).ok()),
+                ),
+                offset: (
+                    take_field(&mut fields, TAG_OFFSET_START, 1, |v| u64::try_from(v[0]).ok()),
+                    take_field(&mut fields, TAG_OFFSET_END, 1, |v| u64::try_from(v[0]).ok()),
+                ),
+            }),
+            turbo,
+        })
+    } else {
+        None
+    };
+
+    let mint = take_field(&mut fields, TAG_MINT, 2, |v| {
+        RuneId::checked(u64::try_from(v[0]).ok()?, u32::try_from(v[1]).ok()?)
+    });
+    let pointer = take_field(&mut fields, TAG_POINTER, 1, |v| u32::try_from(v[0]).ok());
+
+    if etching.is_some_and(|e| e.supply().is_none()) {
+        flawed = true;
+    }
+    if flags != 0 {
+        flawed = true;
+    }
+    if fields.iter().any(|(tag, _)| tag & 1 == 0) {
+        flawed = true;
+    }
+
+    if flawed {
+        // Only the etched name and mint survive a cenotaph
+        return Some(Runestone {
+            etching: etching.map(|e| Etching { rune: e.rune, ..Default::default() }),
+            mint,
+            ..Runestone::cenotaph()
+        });
+    }
+
+    Some(Runestone { edicts, etching, mint, pointer, cenotaph: false })
+}
+
+/// Build the `OP_RETURN` script for a transfer and/or etching
+pub fn encode_runestone(edicts: &[Edict], etching: Option<Etching>) -> Vec<u8> {
+    Runestone {
+        edicts: edicts.to_vec(),
+        etching,
+        ..Default::default()
+    }
+    .encipher()
+}
+
+/// Outputs for a rune transfer
+#[derive(Debug, Clone)]
+pub struct RuneTransfer {
+    /// Recipients, then rune change (if any), then the runestone
+    pub outputs: Vec<TxOut>,
+    pub runestone: Runestone,
+    /// Index of the rune change output
+    pub change_index: Option<u32>,
+    /// Rune units left in the change output
+    pub change_amount: u128,
+}
+
+/// Rune transfer builder
+///
+/// Produces the rune-carrying outputs of a transaction. Funding inputs,
+/// fee and BTC change are left to the transaction builder, which must
+/// append them after these outputs.
+pub struct RuneTransferBuilder {
+    rune_id: RuneId,
+    available: u128,
+    recipients: Vec<(ScriptBuf, u128)>,
+    change_script: Option<ScriptBuf>,
+    postage: u64,
+}
+
+impl RuneTransferBuilder {
+    /// `available` is the rune balance held by the inputs being spent
+    pub fn new(rune_id: RuneId, available: u128) -> Self {
+        Self {
+            rune_id,
+            available,
+            recipients: Vec::new(),
+            change_script: None,
+            postage: DEFAULT_RUNE_POSTAGE,
+        }
+    }
+
+    /// Send `amount` units to `script_pubkey`
+    pub fn add_recipient(mut self, script_pubkey: ScriptBuf, amount: u128) -> Self {
+        self.recipients.push((script_pubkey, amount));
+        self
+    }
+
+    /// Output receiving the remaining rune balance
+    pub fn change_script(mut self, script_pubkey: ScriptBuf) -> Self {
+        self.change_script = Some(script_pubkey);
+        self
+    }
+
+    /// Sats attached to each rune-carrying output
+    pub fn postage(mut self, sats: u64) -> Self {
+        self.postage = sats;
+        self
+    }
+
+    pub fn build(&self) -> Result<RuneTransfer, OrdinalsError> {
+        if self.recipients.is_empty() {
+            return Err(OrdinalsError::InvalidRune("No recipients".to_string()));
+        }
+        if self.recipients.iter().any(|(_, amount)| *amount == 0) {
+            return Err(OrdinalsError::InvalidRune("Transfer amount must be positive".to_string()));
+        }
+
+        let total = self.recipients.iter()
+            .try_fold(0u128, |sum, (_, amount)| sum.checked_add(*amount))
+            .ok_or_else(|| OrdinalsError::InvalidRune("Transfer amount overflow".to_string()))?;
+        let change_amount = self.available.checked_sub(total).ok_or_else(|| {
+            OrdinalsError::InvalidRune(format!("Insufficient rune balance: {} < {}", self.available, total))
+        })?;
+
+        let mut outputs = Vec::new();
+        let mut edicts = Vec::new();
+        for (script_pubkey, amount) in &self.recipients {
+            edicts.push(Edict {
+                id: self.rune_id,
+                amount: *amount,
+                output: outputs.len() as u32,
+            });
+            outputs.push(TxOut {
+                value: Amount::from_sat(self.postage),
+                script_pubkey: script_pubkey.clone(),
+            });
+        }
+
+        // Without an explicit pointer, leftovers go to the first recipient
+        let change_index = if change_amount > 0 {
+            let script_pubkey = self.change_script.clone().ok_or_else(|| {
+                OrdinalsError::InvalidRune("Change script required for remaining runes".to_string())
+            })?;
+            outputs.push(TxOut {
+                value: Amount::from_sat(self.postage),
+                script_pubkey,
+            });
+            Some(outputs.len() as u32 - 1)
+        } else {
+            None
+        };
+
+        let runestone = Runestone {
+            edicts,
+            pointer: change_index,
+            ..Default::default()
+        };
+        outputs.push(TxOut {
+            value: Amount::ZERO,
+            script_pubkey: ScriptBuf::from_bytes(runestone.encipher()),
+        });
+
+        Ok(RuneTransfer { outputs, runestone, change_index, change_amount })
+    }
+}
+
+fn encode_field(payload: &mut Vec<u8>, tag: u128, value: Option<u128>) {
+    if let Some(value) = value {
+        encode_varint(tag, payload);
+        encode_varint(value, payload);
+    }
+}
+
+/// Remove the first `count` values of `tag` if `parse` accepts them
+fn take_field<T>(
+    fields: &mut Vec<(u128, Vec<u128>)>,
+    tag: u128,
+    count: usize,
+    parse: impl FnOnce(&[u128]) -> Option<T>,
+) -> Option<T> {
+    let index = fields.iter().position(|(t, _)| *t == tag)?;
+    let values = &mut fields[index].1;
+    if values.len() < count {
+        return None;
+    }
+    let value = parse(&values[..count])?;
+    values.drain(..count);
+    if values.is_empty() {
+        fields.remove(index);
+    }
+    Some(value)
+}
+
+/// LEB128-encode a u128
+fn encode_varint(mut n: u128, out: &mut Vec<u8>) {
+    while n >> 7 > 0 {
+        out.push((n & 0x7f) as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+/// Decode a LEB128 u128, returning the value and bytes consumed
+fn decode_varint(buffer: &[u8]) -> Option<(u128, usize)> {
+    let mut n = 0u128;
+    for (i, &byte) in buffer.iter().enumerate() {
+        // 19 bytes hold 133 bits; the last may only use its low 2
+        if i > 18 {
+            return None;
+        }
+        let value = u128::from(byte & 0x7f);
+        if i == 18 && value & 0b0111_1100 != 0 {
+            return None;
+        }
+        n |= value << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((n, i + 1));
+        }
+    }
+    None
+}
+
+/// Append a minimal data push
+fn push_data(script: &mut Vec<u8>, data: &[u8]) {
+    match data.len() {
+        len @ 0..=0x4b => script.push(len as u8),
+        len @ 0x4c..=0xff => {
+            script.push(OP_PUSHDATA1);
+            script.push(len as u8);
+        }
+        len => {
+            script.push(OP_PUSHDATA2);
+            script.extend_from_slice(&(len as u16).to_le_bytes());
+        }
+    }
+    script.extend_from_slice(data);
+}
+
+/// Concatenate data pushes; `None` on any other opcode or truncation
+fn read_pushes(mut script: &[u8]) -> Option<Vec<u8>> {
+    let mut payload = Vec::new();
+    while let Some((&opcode, rest)) = script.split_first() {
+        let (len, rest) = match opcode {
+            0x00..=0x4b => (opcode as usize, rest),
+            OP_PUSHDATA1 => (*rest.first()? as usize, rest.get(1..)?),
+            OP_PUSHDATA2 => (u16::from_le_bytes(rest.get(..2)?.try_into().ok()?) as usize, rest.get(2..)?),
+            OP_PUSHDATA4 => (u32::from_le_bytes(rest.get(..4)?.try_into().ok()?) as usize, rest.get(4..)?),
+            _ => return None,
+        };
+        payload.extend_from_slice(rest.get(..len)?);
+        script = &rest[len..];
+    }
+    Some(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_mainnet_mint() {
+        // Mint of UNCOMMON•GOODS (1:0), the most common runestone after launch
+        let script = hex::decode("6a5d0414011400").unwrap();
+        let runestone = decode_runestone(&script).unwrap();
+
+        assert!(!runestone.cenotaph);
+        assert_eq!(runestone.mint, Some(RuneId::new(1, 0)));
+        assert!(runestone.edicts.is_empty());
+        assert!(runestone.etching.is_none());
+        assert_eq!(runestone.encipher(), script);
+    }
+
+    #[test]
+    fn test_decode_transfer() {
+        // Pointer 1, edict: 1000 units of 840000:3 to output 0
+        let script = hex::decode("6a5d0a160100c0a23303e80700").unwrap();
+        let runestone = decode_runestone(&script).unwrap();
+
+        assert_eq!(runestone.pointer, Some(1));
+        assert_eq!(runestone.edicts, vec![Edict { id: RuneId::new(840000, 3), amount: 1000, output: 0 }]);
+        assert_eq!(runestone.encipher(), script);
+    }
+
+    #[test]
+    fn test_etching_round_trip() {
+        let (rune, spacers) = Rune::from_spaced("HAWALA•TEST•RUNE").unwrap();
+        let etching = Etching {
+            divisibility: Some(2),
+            premine: Some(1_000_000),
+            rune: Some(rune),
+            spacers: Some(spacers),
+            symbol: Some('ℏ'),
+            terms: Some(Terms {
+                amount: Some(100),
+                cap: Some(1_000_000),
+                height: (Some(900_000), None),
+                offset: (None, Some(10_000)),
+            }),
+            turbo: true,
+        };
+
+        let script = encode_runestone(&[], Some(etching));
+        let runestone = decode_runestone(&script).unwrap();
+
+        assert!(!runestone.cenotaph);
+        assert_eq!(runestone.etching, Some(etching));
+        assert_eq!(runestone.etching.unwrap().spaced_name().unwrap(), "HAWALA•TEST•RUNE");
+        assert_eq!(etching.supply(), Some(101_000_000));
+    }
+
+    #[test]
+    fn test_edicts_delta_encoded_in_order() {
+        let edicts = [
+            Edict { id: RuneId::new(840000, 10), amount: 5, output: 1 },
+            Edict { id: RuneId::new(840000, 3), amount: 7, output: 0 },
+            Edict { id: RuneId::new(840001, 2), amount: 9, output: 2 },
+        ];
+
+        let runestone = decode_runestone(&encode_runestone(&edicts, None)).unwrap();
+
+        let mut expected = edicts.to_vec();
+        expected.sort_by_key(|e| e.id);
+        assert_eq!(runestone.edicts, expected);
+    }
+
+    #[test]
+    fn test_not_a_runestone() {
+        assert!(decode_runestone(&[]).is_none());
+        assert!(decode_runestone(&hex::decode("6a0401020304").unwrap()).is_none());
+        assert!(decode_runestone(&hex::decode("0014751e76e8199196d454941c45d1b3a323f1433bd6").unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_cenotaphs() {
+        // Non-push opcode (OP_VERIFY)
+        assert!(decode_runestone(&hex::decode("6a5d69").unwrap()).unwrap().cenotaph);
+        // Unterminated varint
+        assert!(decode_runestone(&hex::decode("6a5d0180").unwrap()).unwrap().cenotaph);
+        // Unknown even tag 24
+        assert!(decode_runestone(&hex::decode("6a5d021801").unwrap()).unwrap().cenotaph);
+        // Truncated edict
+        assert!(decode_runestone(&hex::decode("6a5d03000101").unwrap()).unwrap().cenotaph);
+        // Unknown odd tags are ignored
+        assert!(!decode_runestone(&hex::decode("6a5d021901").unwrap()).unwrap().cenotaph);
+    }
+
+    #[test]
+    fn test_block_zero_rune_id_is_cenotaph() {
+        // Edict for 0:1
+        assert!(decode_runestone(&hex::decode("6a5d050000010500").unwrap()).unwrap().cenotaph);
+        // Mint of 0:1 stays an unconsumed even tag
+        let runestone = decode_runestone(&hex::decode("6a5d0414001401").unwrap()).unwrap();
+        assert!(runestone.cenotaph);
+        assert_eq!(runestone.mint, None);
+        // 0:0 is the rune etched by this transaction
+        let runestone = decode_runestone(&hex::decode("6a5d050000000500").unwrap()).unwrap();
+        assert!(!runestone.cenotaph);
+        assert_eq!(runestone.edicts, vec![Edict { id: RuneId::new(0, 0), amount: 5, output: 0 }]);
+    }
+
+    #[test]
+    fn test_rune_names() {
+        assert_eq!(Rune(0).to_string(), "A");
+        assert_eq!(Rune(25).to_string(), "Z");
+        assert_eq!(Rune(26).to_string(), "AA");
+        assert_eq!("UNCOMMONGOODS".parse::<Rune>().unwrap(), Rune(2055900680524219742));
+        assert_eq!(Rune(u128::MAX).to_string().parse::<Rune>().unwrap(), Rune(u128::MAX));
+
+        let (rune, spacers) = Rune::from_spaced("UNCOMMON•GOODS").unwrap();
+        assert_eq!(spacers, 0b1000_0000);
+        assert_eq!(rune.spaced(spacers), "UNCOMMON•GOODS");
+
+        assert!(Rune::from_spaced("•ABC").is_err());
+        assert!(Rune::from_spaced("ABC•").is_err());
+        assert!("abc".parse::<Rune>().is_err());
+    }
+
+    #[test]
+    fn test_varint_round_trip() {
+        for n in [0u128, 1, 127, 128, 840000, u64::MAX as u128, u128::MAX] {
+            let mut buffer = Vec::new();
+            encode_varint(n, &mut buffer);
+            assert_eq!(decode_varint(&buffer), Some((n, buffer.len())));
+        }
+        // Overlong encodings are rejected
+        assert!(decode_varint(&[0xff; 19]).is_none());
+    }
+
+    #[test]
+    fn test_transfer_builder() {
+        let rune_id: RuneId = "840000:3".parse().unwrap();
+        let recipient = ScriptBuf::from_bytes(vec![0x00, 0x14, 0x11, 0x22]);
+        let change = ScriptBuf::from_bytes(vec![0x00, 0x14, 0x33, 0x44]);
+
+        let transfer = RuneTransferBuilder::new(rune_id, 1500)
+            .add_recipient(recipient.clone(), 1000)
+            .change_script(change.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(transfer.outputs.len(), 3);
+        assert_eq!(transfer.outputs[0].script_pubkey, recipient);
+        assert_eq!(transfer.outputs[0].value, Amount::from_sat(DEFAULT_RUNE_POSTAGE));
+        assert_eq!(transfer.outputs[1].script_pubkey, change);
+        assert_eq!(transfer.change_index, Some(1));
+        assert_eq!(transfer.change_amount, 500);
+        assert_eq!(transfer.outputs[2].value, Amount::ZERO);
+        assert_eq!(transfer.outputs[2].script_pubkey.as_bytes(), hex::decode("6a5d0a160100c0a23303e80700").unwrap());
+
+        // Exact amount needs no change output
+        let exact = RuneTransferBuilder::new(rune_id, 1000)
+            .add_recipient(recipient.clone(), 1000)
+            .build()
+            .unwrap();
+        assert_eq!(exact.outputs.len(), 2);
+        assert_eq!(exact.runestone.pointer, None);
+
+        assert!(RuneTransferBuilder::new(rune_id, 999).add_recipient(recipient.clone(), 1000).build().is_err());
+        assert!(RuneTransferBuilder::new(rune_id, 1500).add_recipient(recipient, 1000).build().is_err());
+    }
+}
@@ -322,6 +322,7 @@ pub enum OrdinalsError {
     InvalidBrc20(String),
     ApiError(String),
     ParseError(String),
+    InvalidRune(String),
 }
 
 impl std::fmt::Display for OrdinalsError {
@@ -336,6 +337,7 @@ impl std::fmt::Display for OrdinalsError {
             OrdinalsError::InvalidBrc20(msg) => write!(f, "Invalid BRC-20: {}", msg),
             OrdinalsError::ApiError(msg) => write!(f, "API error: {}", msg),
             OrdinalsError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            OrdinalsError::InvalidRune(msg) => write!(f, "Invalid rune: {}", msg),
         }
     }
 }
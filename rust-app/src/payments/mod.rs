@@ -1,7 +1,10 @@
 //! Payment Request Links Module
 //!
-//! Create and parse shareable payment request links.
+//! Create and parse shareable payment request links, and read the payment
+//! URIs (BIP-21, EIP-681, monero:) found in QR codes.
 
 pub mod links;
+pub mod uri;
 
 pub use links::*;
+pub use uri::*;
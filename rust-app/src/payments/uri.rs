@@ -0,0 +1,510 @@
+//! Payment URIs
+//!
+//! Parses the `scheme:address?params` URIs found in payment QR codes into one
+//! normalized shape:
+//!
+//! - `bitcoin:` / `litecoin:` (BIP-21): `amount`, `label`, `message`
+//! - `monero:`: `tx_amount`, `recipient_name`, `tx_description`
+//! - `ethereum:` (EIP-681): `[pay-]target[@chain_id][/function]?params`,
+//!   either a native transfer (`value`) or an ERC-20 `transfer`
+//!
+//! Parameters without a normalized field are kept, percent-decoded, in `extra`.
+//! [`generate_payment_uri`] writes the same formats for receive requests.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{HawalaError, HawalaResult};
+use crate::types::Chain;
+use crate::wallet::validate_chain_address;
+
+/// A payment request read from a URI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PaymentUri {
+    pub chain: Chain,
+    /// Who gets paid; for ERC-20 transfers the recipient, not the token contract
+    pub address: String,
+    /// Decimal amount in whole coins (BTC, LTC, XMR); for EIP-681 an integer
+    /// in base units (wei, or the token's smallest unit)
+    pub amount: Option<String>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    /// Other parameters; EIP-681 adds `chain_id`, and `contract` / `function`
+    /// for contract calls
+    pub extra: BTreeMap<String, String>,
+}
+
+impl PaymentUri {
+    fn new(chain: Chain, address: String) -> Self {
+        Self {
+            chain,
+            address,
+            amount: None,
+            label: None,
+            message: None,
+            extra: BTreeMap::new(),
+        }
+    }
+}
+
+/// What to ask for in a generated payment URI
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaymentUriRequest {
+    pub chain: Chain,
+    pub address: String,
+    /// Same units as [`PaymentUri::amount`]: whole coins, or base units on EVM
+    #[serde(default)]
+    pub amount: Option<String>,
+    /// ERC-20 contract; the URI becomes an EIP-681 `transfer` call
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Not part of EIP-681, so dropped for EVM chains
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Build a BIP-21, Monero or EIP-681 payment URI
+///
+/// Addresses are validated and written as given. EVM chains other than
+/// Ethereum mainnet get an `@chain_id` suffix.
+pub fn generate_payment_uri(request: &PaymentUriRequest) -> HawalaResult<String> {
+    let chain = request.chain;
+    if !validate_chain_address(&request.address, chain).0 {
+        return Err(HawalaError::invalid_input(format!("Invalid {:?} address: {}", chain, request.address)));
+    }
+    if request.token.is_some() && !chain.is_evm() {
+        return Err(HawalaError::invalid_input(format!("Token transfers are not supported for {:?}", chain)));
+    }
+
+    let mut params = Vec::new();
+    let text_params = |label_key: &str, message_key: &str| {
+        let encode = |s: &String| urlencoding::encode(s).into_owned();
+        let mut text = Vec::new();
+        if let Some(label) = &request.label {
+            text.push(format!("{}={}", label_key, encode(label)));
+        }
+        if let Some(message) = &request.message {
+            text.push(format!("{}={}", message_key, encode(message)));
+        }
+        text
+    };
+
+    let base = match chain {
+        Chain::Bitcoin | Chain::BitcoinTestnet | Chain::Litecoin | Chain::Monero => {
+            let (scheme, amount_key, label_key, message_key) = match chain {
+                Chain::Monero => ("monero", "tx_amount", "recipient_name", "tx_description"),
+                Chain::Litecoin => ("litecoin", "amount", "label", "message"),
+                _ => ("bitcoin", "amount", "label", "message"),
+            };
+            if let Some(amount) = &request.amount {
+                params.push(format!("{}={}", amount_key, decimal_amount(amount)?));
+            }
+            params.extend(text_params(label_key, message_key));
+            format!("{}:{}", scheme, request.address)
+        }
+        chain if chain.is_evm() => {
+            let chain_id = chain.chain_id()
+                .ok_or_else(|| HawalaError::invalid_input(format!("No chain id for {:?}", chain)))?;
+            let suffix = if chain_id == 1 { String::new() } else { format!("@{}", chain_id) };
+            let amount = request.amount.as_deref().map(eip681_integer).transpose()?;
+            match &request.token {
+                Some(token) => {
+                    if !validate_chain_address(token, chain).0 {
+                        return Err(HawalaError::invalid_input(format!("Invalid token contract: {}", token)));
+                    }
+                    params.push(format!("address={}", request.address));
+                    if let Some(amount) = amount {
+                        params.push(format!("uint256={}", amount));
+                    }
+                    format!("ethereum:{}{}/transfer", token, suffix)
+                }
+                None => {
+                    if let Some(amount) = amount {
+                        params.push(format!("value={}", amount));
+                    }
+                    format!("ethereum:{}{}", request.address, suffix)
+                }
+            }
+        }
+        other => {
+            return Err(HawalaError::invalid_input(format!("Payment URIs are not supported for {:?}", other)));
+        }
+    };
+
+    Ok(if params.is_empty() { base } else { format!("{}?{}", base, params.join("&")) })
+}
+
+/// Parse a `bitcoin:`, `litecoin:`, `monero:` or `ethereum:` payment URI
+pub fn parse_payment_uri(uri: &str) -> HawalaResult<PaymentUri> {
+    let uri = uri.trim();
+    let (scheme, rest) = uri.split_once(':')
+        .ok_or_else(|| HawalaError::invalid_input("Not a payment URI"))?;
+    // Some wallets write scheme://address
+    let rest = rest.strip_prefix("//").unwrap_or(rest);
+    let (target, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let params = parse_query(query)?;
+
+    match scheme.to_ascii_lowercase().as_str() {
+        "bitcoin" => parse_bip21(&[Chain::Bitcoin, Chain::BitcoinTestnet], target, params),
+        "litecoin" => parse_bip21(&[Chain::Litecoin], target, params),
+        "monero" => parse_monero(target, params),
+        "ethereum" => parse_eip681(target, params),
+        other => Err(HawalaError::invalid_input(format!("Unsupported payment URI scheme: {}", other))),
+    }
+}
+
+/// BIP-21: the first of `chains` the address is valid on
+fn parse_bip21(chains: &[Chain], address: &str, params: Vec<(String, String)>) -> HawalaResult<PaymentUri> {
+    // QR codes often carry bech32 in upper case, which is more compact
+    let lower = address.to_ascii_lowercase();
+    let address = if ["bc1", "tb1", "ltc1", "tltc1"].iter().any(|hrp| lower.starts_with(hrp)) {
+        lower
+    } else {
+        address.to_string()
+    };
+    let chain = chains.iter().copied()
+        .find(|chain| validate_chain_address(&address, *chain).0)
+        .ok_or_else(|| HawalaError::invalid_input(format!("Invalid {:?} address: {}", chains[0], address)))?;
+
+    let mut uri = PaymentUri::new(chain, address);
+    for (key, value) in params {
+        match key.as_str() {
+            "amount" => uri.amount = Some(decimal_amount(&value)?),
+            "label" => uri.label = Some(value),
+            "message" => uri.message = Some(value),
+            // BIP-21: unknown required parameters make the URI unusable
+            k if k.starts_with("req-") => {
+                return Err(HawalaError::invalid_input(format!("Unsupported required parameter: {}", k)));
+            }
+            _ => {
+                uri.extra.insert(key, value);
+            }
+        }
+    }
+    Ok(uri)
+}
+
+fn parse_monero(address: &str, params: Vec<(String, String)>) -> HawalaResult<PaymentUri> {
+    if !validate_chain_address(address, Chain::Monero).0 {
+        return Err(HawalaError::invalid_input(format!("Invalid Monero address: {}", address)));
+    }
+
+    let mut uri = PaymentUri::new(Chain::Monero, address.to_string());
+    for (key, value) in params {
+        match key.as_str() {
+            "tx_amount" => uri.amount = Some(decimal_amount(&value)?),
+            "recipient_name" => uri.label = Some(value),
+            "tx_description" => uri.message = Some(value),
+            _ => {
+                uri.extra.insert(key, value);
+            }
+        }
+    }
+    Ok(uri)
+}
+
+/// EIP-681; the chain defaults to Ethereum mainnet
+fn parse_eip681(target: &str, params: Vec<(String, String)>) -> HawalaResult<PaymentUri> {
+    let target = target.strip_prefix("pay-").unwrap_or(target);
+    let (target, function) = match target.split_once('/') {
+        Some((target, function)) => (target, Some(function)),
+        None => (target, None),
+    };
+    let (target, chain_id) = match target.split_once('@') {
+        Some((target, id)) => (
+            target,
+            id.parse::<u64>().map_err(|_| HawalaError::invalid_input(format!("Invalid chain id: {}", id)))?,
+        ),
+        None => (target, 1),
+    };
+    let chain = Chain::from_chain_id(chain_id)
+        .ok_or_else(|| HawalaError::invalid_input(format!("Unsupported chain id: {}", chain_id)))?;
+    let target = evm_address(target)?;
+
+    let mut params: BTreeMap<String, String> = params.into_iter().collect();
+    let mut uri = match function {
+        None => {
+            let mut uri = PaymentUri::new(chain, target);
+            uri.amount = params.remove("value").map(|v| eip681_integer(&v)).transpose()?;
+            uri
+        }
+        Some("transfer") => {
+            let recipient = params.remove("address")
+                .ok_or_else(|| HawalaError::invalid_input("ERC-20 transfer is missing the recipient address"))?;
+            let mut uri = PaymentUri::new(chain, evm_address(&recipient)?);
+            uri.amount = params.remove("uint256").map(|v| eip681_integer(&v)).transpose()?;
+            uri.extra.insert("contract".to_string(), target);
+            uri.extra.insert("function".to_string(), "transfer".to_string());
+            uri
+        }
+        // Any other call: the arguments stay in `extra` for the caller to show
+        Some(function) => {
+            let mut uri = PaymentUri::new(chain, target.clone());
+            uri.extra.insert("contract".to_string(), target);
+            uri.extra.insert("function".to_string(), function.to_string());
+            uri
+        }
+    };
+    uri.label = params.remove("label");
+    uri.message = params.remove("message");
+    uri.extra.insert("chain_id".to_string(), chain_id.to_string());
+    uri.extra.extend(params);
+    Ok(uri)
+}
+
+/// Checksummed 0x address, or an ENS name as given
+fn evm_address(address: &str) -> HawalaResult<String> {
+    if !address.starts_with("0x") && address.contains('.') {
+        return Ok(address.to_string());
+    }
+    match validate_chain_address(address, Chain::Ethereum) {
+        (true, Some(checksummed)) => Ok(checksummed),
+        _ => Err(HawalaError::invalid_input(format!("Invalid address: {}", address))),
+    }
+}
+
+/// Plain decimal amount ("1.5"); BIP-21 allows no sign or exponent
+fn decimal_amount(value: &str) -> HawalaResult<String> {
+    let (int, frac) = value.split_once('.').unwrap_or((value, ""));
+    let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if int.is_empty() && frac.is_empty() || !digits(int) || !digits(frac) {
+        return Err(HawalaError::invalid_input(format!("Invalid amount: {}", value)));
+    }
+    Ok(value.to_string())
+}
+
+/// EIP-681 number such as "2.014e18" as an integer string
+fn eip681_integer(value: &str) -> HawalaResult<String> {
+    let invalid = || HawalaError::invalid_input(format!("Invalid amount: {}", value));
+
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<usize>().map_err(|_| invalid())?),
+        None => (value, 0),
+    };
+    // Nothing a uint256 can hold needs more
+    if exponent > 78 {
+        return Err(invalid());
+    }
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if int.is_empty() && frac.is_empty() || !digits(int) || !digits(frac) {
+        return Err(invalid());
+    }
+
+    let frac = frac.trim_end_matches('0');
+    if frac.len() > exponent {
+        return Err(HawalaError::invalid_input(format!("Amount is not a whole number of base units: {}", value)));
+    }
+    let number = format!("{}{}{}", int, frac, "0".repeat(exponent - frac.len()));
+    let number = number.trim_start_matches('0');
+    Ok(if number.is_empty() { "0".to_string() } else { number.to_string() })
+}
+
+/// Percent-decoded `key=value` pairs, in order
+fn parse_query(query: &str) -> HawalaResult<Vec<(String, String)>> {
+    let decode = |s: &str| {
+        urlencoding::decode(s)
+            .map(|d| d.into_owned())
+            .map_err(|_| HawalaError::invalid_input(format!("Invalid percent-encoding: {}", s)))
+    };
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            Ok((decode(key)?, decode(value)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_bitcoin_uri() {
+        let uri = parse_payment_uri(
+            "bitcoin:BC1QAR0SRRR7XFKVY5L643LYDNW9RE59GTZZWF5MDQ?amount=0.0015&label=Luke-Jr&message=Donation%20for%20project%20xyz&lightning=lnbc1",
+        )
+        .unwrap();
+        assert_eq!(uri.chain, Chain::Bitcoin);
+        assert_eq!(uri.address, "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
+        assert_eq!(uri.amount.as_deref(), Some("0.0015"));
+        assert_eq!(uri.label.as_deref(), Some("Luke-Jr"));
+        assert_eq!(uri.message.as_deref(), Some("Donation for project xyz"));
+        assert_eq!(uri.extra.get("lightning").map(String::as_str), Some("lnbc1"));
+
+        let testnet = parse_payment_uri("bitcoin:tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx").unwrap();
+        assert_eq!(testnet.chain, Chain::BitcoinTestnet);
+        assert_eq!(testnet.amount, None);
+
+        assert!(parse_payment_uri("bitcoin:bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq?req-somethingyoudontunderstand=50").is_err());
+        assert!(parse_payment_uri("bitcoin:bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq?amount=1e3").is_err());
+        assert!(parse_payment_uri("bitcoin:notanaddress").is_err());
+    }
+
+    #[test]
+    fn test_litecoin_uri() {
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let uri = parse_payment_uri(&format!("litecoin:{}?amount=2.5&message=rent", keys.litecoin.address)).unwrap();
+        assert_eq!(uri.chain, Chain::Litecoin);
+        assert_eq!(uri.address, keys.litecoin.address);
+        assert_eq!(uri.amount.as_deref(), Some("2.5"));
+        assert_eq!(uri.message.as_deref(), Some("rent"));
+
+        // A Bitcoin address is not a Litecoin one
+        assert!(parse_payment_uri("litecoin:bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq").is_err());
+    }
+
+    #[test]
+    fn test_monero_uri() {
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let uri = parse_payment_uri(&format!(
+            "monero:{}?tx_amount=1.5&recipient_name=Alice&tx_description=Lunch%20%26%20coffee&tx_payment_id=abcd",
+            keys.monero.address
+        ))
+        .unwrap();
+        assert_eq!(uri.chain, Chain::Monero);
+        assert_eq!(uri.address, keys.monero.address);
+        assert_eq!(uri.amount.as_deref(), Some("1.5"));
+        assert_eq!(uri.label.as_deref(), Some("Alice"));
+        assert_eq!(uri.message.as_deref(), Some("Lunch & coffee"));
+        assert_eq!(uri.extra.get("tx_payment_id").map(String::as_str), Some("abcd"));
+
+        assert!(parse_payment_uri("monero:4notanaddress?tx_amount=1").is_err());
+    }
+
+    #[test]
+    fn test_eip681_native_transfer() {
+        let uri = parse_payment_uri("ethereum:0xd8da6bf26964af9d7eed9e03e53415d37aa96045?value=2.014e18").unwrap();
+        assert_eq!(uri.chain, Chain::Ethereum);
+        assert_eq!(uri.address, "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        assert_eq!(uri.amount.as_deref(), Some("2014000000000000000"));
+        assert_eq!(uri.extra.get("chain_id").map(String::as_str), Some("1"));
+
+        let polygon = parse_payment_uri("ethereum:pay-0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045@137?value=1000").unwrap();
+        assert_eq!(polygon.chain, Chain::Polygon);
+        assert_eq!(polygon.amount.as_deref(), Some("1000"));
+
+        // Not a whole number of wei, unknown chain
+        assert!(parse_payment_uri("ethereum:0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045?value=1.5").is_err());
+        assert!(parse_payment_uri("ethereum:0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045@999999").is_err());
+    }
+
+    #[test]
+    fn test_eip681_erc20_transfer() {
+        let uri = parse_payment_uri(
+            "ethereum:0x2791bca1f2de4661ed88a30c99a7a9449aa84174@137/transfer?address=0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045&uint256=1e6",
+        )
+        .unwrap();
+        assert_eq!(uri.chain, Chain::Polygon);
+        assert_eq!(uri.address, "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        assert_eq!(uri.amount.as_deref(), Some("1000000"));
+        assert_eq!(
+            uri.extra.get("contract").map(String::as_str),
+            Some("0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174")
+        );
+        assert_eq!(uri.extra.get("function").map(String::as_str), Some("transfer"));
+        assert_eq!(uri.extra.get("chain_id").map(String::as_str), Some("137"));
+
+        assert!(parse_payment_uri("ethereum:0x2791bca1f2de4661ed88a30c99a7a9449aa84174/transfer?uint256=1").is_err());
+    }
+
+    #[test]
+    fn test_unknown_scheme() {
+        assert!(parse_payment_uri("dogecoin:DH5yaieqoZN36fDVciNyRueRGvGLR3mr7L").is_err());
+        assert!(parse_payment_uri("no scheme here").is_err());
+    }
+
+    fn uri_request(chain: Chain, address: &str) -> PaymentUriRequest {
+        PaymentUriRequest {
+            chain,
+            address: address.to_string(),
+            amount: None,
+            token: None,
+            label: None,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn test_generate_bip21_uri() {
+        // BIP-21 example query strings (the spec's address fails its checksum)
+        let mut request = uri_request(Chain::Bitcoin, "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2");
+        assert_eq!(generate_payment_uri(&request).unwrap(), "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2");
+
+        request.amount = Some("20.3".to_string());
+        request.label = Some("Luke-Jr".to_string());
+        assert_eq!(
+            generate_payment_uri(&request).unwrap(),
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?amount=20.3&label=Luke-Jr"
+        );
+
+        request.amount = Some("50".to_string());
+        request.message = Some("Donation for project xyz".to_string());
+        let uri = generate_payment_uri(&request).unwrap();
+        assert_eq!(
+            uri,
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?amount=50&label=Luke-Jr&message=Donation%20for%20project%20xyz"
+        );
+
+        // Reads back to the same request
+        let parsed = parse_payment_uri(&uri).unwrap();
+        assert_eq!(parsed.amount.as_deref(), Some("50"));
+        assert_eq!(parsed.message.as_deref(), Some("Donation for project xyz"));
+
+        request.amount = Some("-1".to_string());
+        assert!(generate_payment_uri(&request).is_err());
+        assert!(generate_payment_uri(&uri_request(Chain::Bitcoin, "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")).is_err());
+    }
+
+    #[test]
+    fn test_generate_eip681_uri() {
+        // EIP-681 examples
+        let mut request = uri_request(Chain::Ethereum, "0x8e23ee67d1332ad560396262c48ffbb01f93d052");
+        request.token = Some("0x89205a3a3b2a69de6dbf7f01ed13b2108b2c43e7".to_string());
+        request.amount = Some("1".to_string());
+        assert_eq!(
+            generate_payment_uri(&request).unwrap(),
+            "ethereum:0x89205a3a3b2a69de6dbf7f01ed13b2108b2c43e7/transfer?address=0x8e23ee67d1332ad560396262c48ffbb01f93d052&uint256=1"
+        );
+
+        let mut native = uri_request(Chain::Ethereum, "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359");
+        native.amount = Some("2.014e18".to_string());
+        assert_eq!(
+            generate_payment_uri(&native).unwrap(),
+            "ethereum:0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359?value=2014000000000000000"
+        );
+
+        // Other EVM chains carry their chain id, and round-trip
+        request.chain = Chain::Polygon;
+        let uri = generate_payment_uri(&request).unwrap();
+        assert!(uri.starts_with("ethereum:0x89205a3a3b2a69de6dbf7f01ed13b2108b2c43e7@137/transfer?"));
+        let parsed = parse_payment_uri(&uri).unwrap();
+        assert_eq!(parsed.chain, Chain::Polygon);
+        assert!(parsed.address.eq_ignore_ascii_case("0x8e23ee67d1332ad560396262c48ffbb01f93d052"));
+        assert_eq!(parsed.amount.as_deref(), Some("1"));
+
+        // Tokens only exist on EVM chains
+        let mut btc = uri_request(Chain::Bitcoin, "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2");
+        btc.token = Some("0x89205a3a3b2a69de6dbf7f01ed13b2108b2c43e7".to_string());
+        assert!(generate_payment_uri(&btc).is_err());
+    }
+
+    #[test]
+    fn test_eip681_integer() {
+        assert_eq!(eip681_integer("0").unwrap(), "0");
+        assert_eq!(eip681_integer("007").unwrap(), "7");
+        assert_eq!(eip681_integer("1.50e2").unwrap(), "150");
+        assert_eq!(eip681_integer("2E3").unwrap(), "2000");
+        assert!(eip681_integer("1.25e1").is_err());
+        assert!(eip681_integer("-1").is_err());
+        assert!(eip681_integer("1e").is_err());
+        assert!(eip681_integer(".").is_err());
+    }
+}
@@ -0,0 +1,225 @@
+//! Minimal CBOR (RFC 8949) Encoding
+//!
+//! Covers the subset used by the BC-UR registry types: unsigned integers,
+//! byte and text strings, arrays, maps, tags and booleans. Indefinite-length
+//! items and floats are rejected when decoding.
+
+use super::{QrError, QrResult};
+
+/// Nesting limit when decoding untrusted input
+const MAX_DEPTH: usize = 32;
+
+/// A CBOR data item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cbor {
+    /// Major type 0
+    Unsigned(u64),
+    /// Major type 2
+    Bytes(Vec<u8>),
+    /// Major type 3
+    Text(String),
+    /// Major type 4
+    Array(Vec<Cbor>),
+    /// Major type 5, entries in encoding order
+    Map(Vec<(Cbor, Cbor)>),
+    /// Major type 6
+    Tag(u64, Box<Cbor>),
+    /// Simple values `false` / `true`
+    Bool(bool),
+}
+
+impl Cbor {
+    /// Wrap `value` in a semantic tag
+    pub fn tag(tag: u64, value: Cbor) -> Self {
+        Cbor::Tag(tag, Box::new(value))
+    }
+
+    /// Look up an unsigned integer key in a map
+    pub fn get(&self, key: u64) -> Option<&Cbor> {
+        match self {
+            Cbor::Map(entries) => entries
+                .iter()
+                .find(|(k, _)| *k == Cbor::Unsigned(key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Serialize to bytes
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write(&mut out);
+        out
+    }
+
+    /// Parse a single data item spanning all of `data`
+    pub fn decode(data: &[u8]) -> QrResult<Self> {
+        let mut pos = 0;
+        let item = read_item(data, &mut pos, 0)?;
+        if pos != data.len() {
+            return Err(QrError::CborError(format!("{} trailing bytes", data.len() - pos)));
+        }
+        Ok(item)
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            Cbor::Unsigned(n) => write_head(out, 0, *n),
+            Cbor::Bytes(bytes) => {
+                write_head(out, 2, bytes.len() as u64);
+                out.extend_from_slice(bytes);
+            }
+            Cbor::Text(text) => {
+                write_head(out, 3, text.len() as u64);
+                out.extend_from_slice(text.as_bytes());
+            }
+            Cbor::Array(items) => {
+                write_head(out, 4, items.len() as u64);
+                for item in items {
+                    item.write(out);
+                }
+            }
+            Cbor::Map(entries) => {
+                write_head(out, 5, entries.len() as u64);
+                for (key, value) in entries {
+                    key.write(out);
+                    value.write(out);
+                }
+            }
+            Cbor::Tag(tag, value) => {
+                write_head(out, 6, *tag);
+                value.write(out);
+            }
+            Cbor::Bool(value) => out.push(if *value { 0xf5 } else { 0xf4 }),
+        }
+    }
+}
+
+/// Initial byte plus the shortest argument encoding
+fn write_head(out: &mut Vec<u8>, major: u8, n: u64) {
+    let major = major << 5;
+    match n {
+        0..=23 => out.push(major | n as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, n as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(n as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(n as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+    }
+}
+
+fn read_item(data: &[u8], pos: &mut usize, depth: usize) -> QrResult<Cbor> {
+    if depth > MAX_DEPTH {
+        return Err(QrError::CborError("Nesting too deep".to_string()));
+    }
+
+    let (major, n) = read_head(data, pos)?;
+    match major {
+        0 => Ok(Cbor::Unsigned(n)),
+        2 => Ok(Cbor::Bytes(take(data, pos, n)?.to_vec())),
+        3 => String::from_utf8(take(data, pos, n)?.to_vec())
+            .map(Cbor::Text)
+            .map_err(|_| QrError::CborError("Invalid UTF-8 in text string".to_string())),
+        4 => {
+            let mut items = Vec::new();
+            for _ in 0..n {
+                items.push(read_item(data, pos, depth + 1)?);
+            }
+            Ok(Cbor::Array(items))
+        }
+        5 => {
+            let mut entries = Vec::new();
+            for _ in 0..n {
+                let key = read_item(data, pos, depth + 1)?;
+                let value = read_item(data, pos, depth + 1)?;
+                entries.push((key, value));
+            }
+            Ok(Cbor::Map(entries))
+        }
+        6 => Ok(Cbor::tag(n, read_item(data, pos, depth + 1)?)),
+        7 if n == 20 => Ok(Cbor::Bool(false)),
+        7 if n == 21 => Ok(Cbor::Bool(true)),
+        _ => Err(QrError::CborError(format!("Unsupported item (major type {})", major))),
+    }
+}
+
+fn read_head(data: &[u8], pos: &mut usize) -> QrResult<(u8, u64)> {
+    let initial = take(data, pos, 1)?[0];
+    let major = initial >> 5;
+    let n = match initial & 0x1f {
+        info @ 0..=23 => info as u64,
+        24 => take(data, pos, 1)?[0] as u64,
+        25 => u16::from_be_bytes(take(data, pos, 2)?.try_into().unwrap()) as u64,
+        26 => u32::from_be_bytes(take(data, pos, 4)?.try_into().unwrap()) as u64,
+        27 => u64::from_be_bytes(take(data, pos, 8)?.try_into().unwrap()),
+        _ => return Err(QrError::CborError("Indefinite or reserved length".to_string())),
+    };
+    Ok((major, n))
+}
+
+fn take<'a>(data: &'a [u8], pos: &mut usize, len: u64) -> QrResult<&'a [u8]> {
+    let end = usize::try_from(len)
+        .ok()
+        .and_then(|len| pos.checked_add(len))
+        .filter(|end| *end <= data.len())
+        .ok_or_else(|| QrError::CborError("Unexpected end of data".to_string()))?;
+    let slice = &data[*pos..end];
+    *pos = end;
+    Ok(slice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_rfc_examples() {
+        assert_eq!(Cbor::Unsigned(10).encode(), vec![0x0a]);
+        assert_eq!(Cbor::Unsigned(500).encode(), vec![0x19, 0x01, 0xf4]);
+        assert_eq!(Cbor::Unsigned(1_000_000).encode(), vec![0x1a, 0x00, 0x0f, 0x42, 0x40]);
+        assert_eq!(Cbor::Text("IETF".to_string()).encode(), vec![0x64, 0x49, 0x45, 0x54, 0x46]);
+        assert_eq!(
+            Cbor::tag(1, Cbor::Unsigned(1_363_896_240)).encode(),
+            vec![0xc1, 0x1a, 0x51, 0x4b, 0x67, 0xb0]
+        );
+        assert_eq!(
+            Cbor::Map(vec![
+                (Cbor::Unsigned(1), Cbor::Unsigned(2)),
+                (Cbor::Unsigned(3), Cbor::Array(vec![Cbor::Bool(true)])),
+            ])
+            .encode(),
+            vec![0xa2, 0x01, 0x02, 0x03, 0x81, 0xf5]
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let item = Cbor::Map(vec![
+            (Cbor::Unsigned(1), Cbor::Unsigned(u64::MAX)),
+            (Cbor::Unsigned(2), Cbor::tag(303, Cbor::Bytes(vec![0xab; 40]))),
+            (Cbor::Unsigned(3), Cbor::Array(vec![Cbor::Bool(false), Cbor::Text("x".to_string())])),
+        ]);
+        let decoded = Cbor::decode(&item.encode()).unwrap();
+        assert_eq!(decoded, item);
+        assert_eq!(decoded.get(1), Some(&Cbor::Unsigned(u64::MAX)));
+        assert_eq!(decoded.get(4), None);
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed() {
+        assert!(Cbor::decode(&[]).is_err());
+        assert!(Cbor::decode(&[0x19, 0x01]).is_err()); // Truncated argument
+        assert!(Cbor::decode(&[0x44, 0x01]).is_err()); // Truncated byte string
+        assert!(Cbor::decode(&[0x9f, 0xff]).is_err()); // Indefinite array
+        assert!(Cbor::decode(&[0x01, 0x02]).is_err()); // Trailing data
+        assert!(Cbor::decode(&[0x81; 64]).is_err()); // Nesting
+    }
+}
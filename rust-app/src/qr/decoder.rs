@@ -186,6 +186,8 @@ impl QrDecoder {
             Ok(ScanResult::Fountain {
                 progress: decoder.progress(),
                 can_decode: decoder.can_decode(),
+                estimated_remaining_frames: decoder.estimated_remaining_frames(),
+                frames_per_second_hint: decoder.frames_per_second_hint(),
             })
         }
     }
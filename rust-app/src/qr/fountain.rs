@@ -19,6 +19,16 @@ use super::{QrError, QrResult};
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 
+/// Frames needed per missing fragment with this degree distribution
+const FOUNTAIN_OVERHEAD: f32 = 1.5;
+
+/// Animation rates a phone camera decodes reliably
+const MIN_SCAN_FPS: f32 = 4.0;
+const MAX_SCAN_FPS: f32 = 10.0;
+
+/// Pace frames so the remainder takes about this long to show
+const TARGET_SCAN_SECONDS: f32 = 3.0;
+
 /// A single fountain-encoded part
 #[derive(Debug, Clone)]
 pub struct FountainPart {
@@ -41,8 +51,16 @@ pub struct FountainEncoder {
 }
 
 impl FountainEncoder {
-    /// Create a new fountain encoder
+    /// Create a new fountain encoder with a random part sequence
     pub fn new(message: &[u8], fragment_size: usize) -> Self {
+        Self::with_seed(message, fragment_size, rand::thread_rng().gen())
+    }
+    
+    /// Create a fountain encoder whose part sequence is fixed by `seed`
+    ///
+    /// The same message, fragment size and seed always produce the same
+    /// parts, which makes frame sequences reproducible in tests.
+    pub fn with_seed(message: &[u8], fragment_size: usize, seed: u64) -> Self {
         let fragment_count = (message.len() + fragment_size - 1) / fragment_size;
         
         // Split message into fragments, padding last fragment if needed
@@ -56,14 +74,11 @@ impl FountainEncoder {
             fragments.push(fragment);
         }
         
-        // Generate random seed
-        let rng_seed: u64 = rand::thread_rng().gen();
-        
         Self {
             fragments,
             fragment_size,
             message_len: message.len(),
-            rng_seed,
+            rng_seed: seed,
         }
     }
     
@@ -157,6 +172,8 @@ pub struct FountainDecoder {
     recovered: Vec<Option<Vec<u8>>>,
     /// Whether decoding is complete
     complete: bool,
+    /// Running estimate of frames still needed (see `estimated_remaining_frames`)
+    remaining_estimate: usize,
 }
 
 impl FountainDecoder {
@@ -171,6 +188,7 @@ impl FountainDecoder {
             parts: Vec::new(),
             recovered: vec![None; fragment_count],
             complete: false,
+            remaining_estimate: Self::frames_for(fragment_count),
         }
     }
     
@@ -202,6 +220,7 @@ impl FountainDecoder {
         if self.recovered.iter().all(|r| r.is_some()) {
             self.complete = true;
         }
+        self.update_remaining_estimate();
         
         Ok(())
     }
@@ -268,6 +287,35 @@ impl FountainDecoder {
         recovered as f32 / self.fragment_count as f32
     }
     
+    /// Estimate how many more frames are needed to finish
+    ///
+    /// Each missing fragment costs [`FOUNTAIN_OVERHEAD`] frames on average;
+    /// buffered mixed parts already count toward that. Never increases as
+    /// parts arrive, and is 0 once complete.
+    pub fn estimated_remaining_frames(&self) -> usize {
+        self.remaining_estimate
+    }
+    
+    /// Lower the remaining-frames estimate after a part is processed
+    fn update_remaining_estimate(&mut self) {
+        if self.complete {
+            self.remaining_estimate = 0;
+            return;
+        }
+        let missing = self.recovered.iter().filter(|r| r.is_none()).count();
+        let needed = Self::frames_for(missing).saturating_sub(self.parts.len()).max(1);
+        self.remaining_estimate = self.remaining_estimate.min(needed);
+    }
+    
+    fn frames_for(missing_fragments: usize) -> usize {
+        (missing_fragments as f32 * FOUNTAIN_OVERHEAD).ceil() as usize
+    }
+
+    /// Suggested animation rate for the remaining frames
+    pub fn frames_per_second_hint(&self) -> f32 {
+        frames_per_second_for(self.estimated_remaining_frames())
+    }
+
     /// Get the decoded result
     pub fn result(&self) -> QrResult<Vec<u8>> {
         if !self.complete {
@@ -312,6 +360,12 @@ pub struct DecoderStats {
     pub progress: f32,
 }
 
+/// Frame rate that shows `remaining` frames in about
+/// [`TARGET_SCAN_SECONDS`], within what cameras can keep up with
+pub(crate) fn frames_per_second_for(remaining: usize) -> f32 {
+    (remaining as f32 / TARGET_SCAN_SECONDS).clamp(MIN_SCAN_FPS, MAX_SCAN_FPS)
+}
+
 /// XOR bytes in place
 fn xor_bytes(target: &mut [u8], source: &[u8]) {
     for (t, s) in target.iter_mut().zip(source.iter()) {
@@ -381,6 +435,29 @@ mod tests {
         assert_eq!(result, message.to_vec());
     }
     
+    #[test]
+    fn test_seeded_encoder_is_reproducible() {
+        let message = b"Seeded fountain encoders must produce identical frame sequences";
+        let first = FountainEncoder::with_seed(message, 8, 42);
+        let second = FountainEncoder::with_seed(message, 8, 42);
+        let other = FountainEncoder::with_seed(message, 8, 43);
+        
+        let parts: Vec<FountainPart> = (0..200).map(|seq| first.next_part(seq)).collect();
+        for (seq, part) in parts.iter().enumerate() {
+            let again = second.next_part(seq);
+            assert_eq!(part.indexes, again.indexes);
+            assert_eq!(part.data, again.data);
+        }
+        assert!((0..200).any(|seq| other.next_part(seq).indexes != parts[seq].indexes));
+        
+        let mut decoder = FountainDecoder::new(first.fragment_count(), first.message_len());
+        for part in parts {
+            decoder.receive_part(part).unwrap();
+        }
+        assert!(decoder.is_complete());
+        assert_eq!(decoder.result().unwrap(), message.to_vec());
+    }
+    
     #[test]
     fn test_decoder_stats() {
         let encoder = FountainEncoder::new(b"Test", 2);
@@ -395,6 +472,33 @@ mod tests {
         assert!(!stats.is_complete);
     }
     
+    #[test]
+    fn test_remaining_frames_estimate() {
+        let message: Vec<u8> = (0..200u8).collect();
+        let encoder = FountainEncoder::new(&message, 10);
+        let mut decoder = FountainDecoder::new(encoder.fragment_count(), encoder.message_len());
+        
+        let mut previous = decoder.estimated_remaining_frames();
+        assert_eq!(previous, 30);
+        assert_eq!(decoder.frames_per_second_hint(), 10.0);
+        
+        for seq in 0..500 {
+            decoder.receive_part(encoder.next_part(seq)).unwrap();
+            let remaining = decoder.estimated_remaining_frames();
+            assert!(remaining <= previous, "estimate rose from {} to {}", previous, remaining);
+            previous = remaining;
+            
+            if decoder.is_complete() {
+                break;
+            }
+            assert!(remaining >= 1);
+        }
+        
+        assert!(decoder.is_complete());
+        assert_eq!(decoder.estimated_remaining_frames(), 0);
+        assert_eq!(decoder.frames_per_second_hint(), MIN_SCAN_FPS);
+    }
+    
     #[test]
     fn test_xor_bytes() {
         let mut a = vec![0xFF, 0x00, 0xAA];
@@ -9,6 +9,7 @@
 //! - CBOR encoding for efficient binary data
 //! - CRC32 checksums for data integrity
 //! - Multi-part message assembly
+//! - Terminal rendering of static QR codes
 //!
 //! # Standards
 //! - BC-UR: Blockchain Commons Uniform Resources
@@ -37,11 +38,14 @@ pub mod decoder;
 pub mod fountain;
 pub mod ur;
 pub mod types;
+pub mod render;
+pub mod cbor;
 
 pub use encoder::QrEncoder;
 pub use decoder::QrDecoder;
 pub use fountain::{FountainEncoder, FountainDecoder};
 pub use ur::{UrEncoder, UrDecoder, UrType};
+pub use render::QrMatrix;
 pub use types::*;
 
 use serde::{Deserialize, Serialize};
@@ -0,0 +1,141 @@
+//! Terminal QR Rendering
+//!
+//! Renders a single static QR code as text, two modules per character row
+//! using Unicode half blocks, so addresses and signed payloads can be scanned
+//! straight off an offline machine's screen.
+
+use qrcode::{Color, EcLevel, QrCode};
+
+use super::{ErrorCorrectionLevel, QrError, QrResult};
+
+/// Quiet zone (light border) required around the symbol, in modules
+pub const QUIET_ZONE: usize = 4;
+
+/// Module grid of an encoded QR code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrMatrix {
+    width: usize,
+    /// Row-major, `true` for dark modules
+    modules: Vec<bool>,
+}
+
+impl QrMatrix {
+    /// Encode `data` at error correction level M
+    pub fn encode(data: &str) -> QrResult<Self> {
+        Self::encode_with_level(data, ErrorCorrectionLevel::M)
+    }
+
+    /// Encode `data`, picking the smallest version that fits
+    pub fn encode_with_level(data: &str, level: ErrorCorrectionLevel) -> QrResult<Self> {
+        if data.len() > level.max_bytes() {
+            return Err(QrError::PayloadTooLarge(data.len(), level.max_bytes()));
+        }
+
+        let ec_level = match level {
+            ErrorCorrectionLevel::L => EcLevel::L,
+            ErrorCorrectionLevel::M => EcLevel::M,
+            ErrorCorrectionLevel::Q => EcLevel::Q,
+            ErrorCorrectionLevel::H => EcLevel::H,
+        };
+        let code = QrCode::with_error_correction_level(data.as_bytes(), ec_level)
+            .map_err(|e| QrError::InvalidData(e.to_string()))?;
+
+        Ok(Self {
+            width: code.width(),
+            modules: code.to_colors().into_iter().map(|c| c == Color::Dark).collect(),
+        })
+    }
+
+    /// Modules per side (21 for version 1, plus 4 per version)
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// QR version (1-40)
+    pub fn version(&self) -> usize {
+        (self.width - 17) / 4
+    }
+
+    /// Whether the module at column `x`, row `y` is dark
+    ///
+    /// Coordinates outside the symbol are light (the quiet zone).
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.width && self.modules[y * self.width + x]
+    }
+
+    /// Render for a terminal, including the quiet zone
+    ///
+    /// Like `qrencode -t UTF8`, light modules are drawn as blocks, so the
+    /// code reads correctly as light-on-dark on a dark terminal background.
+    /// Each line covers two module rows; lines are `width + 8` characters.
+    pub fn render_unicode(&self) -> String {
+        let size = self.width + 2 * QUIET_ZONE;
+        let light = |x: usize, y: usize| {
+            // Shift into symbol coordinates; wrapping puts the leading quiet
+            // zone out of range, where is_dark is false
+            !self.is_dark(x.wrapping_sub(QUIET_ZONE), y.wrapping_sub(QUIET_ZONE))
+        };
+
+        let mut out = String::with_capacity((size + 1) * size.div_ceil(2) * 3);
+        for y in (0..size).step_by(2) {
+            for x in 0..size {
+                let top = light(x, y);
+                let bottom = y + 1 < size && light(x, y + 1);
+                out.push(match (top, bottom) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu";
+
+    #[test]
+    fn test_address_dimensions() {
+        // 42 bytes fills version 3 at level M exactly
+        let matrix = QrMatrix::encode(ADDRESS).unwrap();
+        assert_eq!(matrix.width(), 29);
+        assert_eq!(matrix.version(), 3);
+
+        let rendered = matrix.render_unicode();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), (29 + 2 * QUIET_ZONE).div_ceil(2));
+        assert!(lines.iter().all(|line| line.chars().count() == 29 + 2 * QUIET_ZONE));
+    }
+
+    #[test]
+    fn test_finder_patterns_and_quiet_zone() {
+        let matrix = QrMatrix::encode(ADDRESS).unwrap();
+        let w = matrix.width();
+
+        // Finder pattern corners are dark, separators light
+        for (x, y) in [(0, 0), (w - 1, 0), (0, w - 1), (6, 6), (w - 7, 6)] {
+            assert!(matrix.is_dark(x, y), "({}, {})", x, y);
+        }
+        assert!(!matrix.is_dark(7, 7));
+        assert!(!matrix.is_dark(w, 0));
+
+        // Top quiet zone renders as solid light blocks
+        let first = matrix.render_unicode().lines().next().unwrap().to_string();
+        assert!(first.chars().all(|c| c == '█'));
+    }
+
+    #[test]
+    fn test_too_large() {
+        let data = "a".repeat(crate::qr::MAX_QR_BYTES_H + 1);
+        assert!(matches!(
+            QrMatrix::encode_with_level(&data, ErrorCorrectionLevel::H),
+            Err(QrError::PayloadTooLarge(_, _))
+        ));
+    }
+}
@@ -158,6 +158,10 @@ pub enum ScanResult {
         progress: f32,
         /// Whether decoding is possible
         can_decode: bool,
+        /// Estimated frames still needed
+        estimated_remaining_frames: usize,
+        /// Suggested animation rate for the remaining frames
+        frames_per_second_hint: f32,
     },
 }
 
@@ -14,6 +14,7 @@
 //! - crypto-seed: BIP39 seed
 //! - bytes: Raw bytes
 
+use super::cbor::Cbor;
 use super::{QrError, QrResult};
 use serde::{Deserialize, Serialize};
 
@@ -261,6 +262,18 @@ impl UrDecoder {
         }
     }
     
+    /// Parts still missing, or `None` before the part count is known
+    ///
+    /// UR parts here are sequential, so every missing part is one frame.
+    pub fn estimated_remaining_frames(&self) -> Option<usize> {
+        self.total_parts.map(|total| total.saturating_sub(self.parts.len()))
+    }
+    
+    /// Suggested animation rate for the remaining frames
+    pub fn frames_per_second_hint(&self) -> Option<f32> {
+        self.estimated_remaining_frames().map(super::fountain::frames_per_second_for)
+    }
+    
     /// Parse a UR string
     fn parse_ur(ur: &str) -> QrResult<ParsedUr> {
         let ur = ur.trim();
@@ -314,6 +327,137 @@ impl Default for UrDecoder {
     }
 }
 
+// =============================================================================
+// crypto-account (BCR-2020-015)
+// =============================================================================
+
+/// CBOR tags from the BC-UR registry (BCR-2020-006)
+const TAG_CRYPTO_HDKEY: u64 = 303;
+const TAG_CRYPTO_KEYPATH: u64 = 304;
+const TAG_CRYPTO_COININFO: u64 = 305;
+const TAG_CRYPTO_OUTPUT: u64 = 308;
+const TAG_SCRIPT_HASH: u64 = 400;
+const TAG_PUBKEY_HASH: u64 = 403;
+const TAG_WITNESS_PUBKEY_HASH: u64 = 404;
+const TAG_TAPROOT: u64 = 409;
+
+/// Encode account xpubs as `crypto-account` UR frames for watch-only pairing
+///
+/// Each entry is `(derivation path, extended public key)`, e.g.
+/// `("m/84'/0'/0'", "zpub...")`. The output descriptor follows the BIP
+/// purpose of the path: 44' pkh, 49' sh(wpkh), 84' wpkh, 86' tr.
+/// SLIP-132 prefixes (ypub, zpub, ...) are accepted.
+pub fn encode_crypto_account(master_fingerprint: [u8; 4], xpubs: &[(&str, &str)]) -> QrResult<Vec<String>> {
+    let cbor = crypto_account_cbor(master_fingerprint, xpubs)?;
+    UrEncoder::new(UrType::CryptoAccount, &cbor).encode()
+}
+
+/// The `crypto-account` CBOR payload behind [`encode_crypto_account`]
+pub fn crypto_account_cbor(master_fingerprint: [u8; 4], xpubs: &[(&str, &str)]) -> QrResult<Vec<u8>> {
+    if xpubs.is_empty() {
+        return Err(QrError::InvalidData("No accounts to export".to_string()));
+    }
+
+    let descriptors = xpubs
+        .iter()
+        .map(|(path, xpub)| output_descriptor(master_fingerprint, path, xpub))
+        .collect::<QrResult<Vec<_>>>()?;
+
+    let account = Cbor::Map(vec![
+        (Cbor::Unsigned(1), Cbor::Unsigned(u32::from_be_bytes(master_fingerprint) as u64)),
+        (Cbor::Unsigned(2), Cbor::Array(descriptors)),
+    ]);
+    Ok(account.encode())
+}
+
+/// `crypto-output` wrapping the account key in its script expression
+fn output_descriptor(master_fingerprint: [u8; 4], path: &str, xpub: &str) -> QrResult<Cbor> {
+    let components = crate::wallet::DerivationPath::parse(path)
+        .map_err(|e| QrError::InvalidData(e.message))?
+        .components;
+    let key = decode_xpub(xpub)?;
+    if key[4] as usize != components.len() {
+        return Err(QrError::InvalidData(format!(
+            "Key depth {} does not match path {}",
+            key[4], path
+        )));
+    }
+
+    let hardened = |i: usize| components.get(i).filter(|c| c.hardened).map(|c| c.index);
+
+    let mut keypath = Vec::with_capacity(components.len() * 2);
+    for child in &components {
+        keypath.push(Cbor::Unsigned(child.index as u64));
+        keypath.push(Cbor::Bool(child.hardened));
+    }
+    let origin = Cbor::Map(vec![
+        (Cbor::Unsigned(1), Cbor::Array(keypath)),
+        (Cbor::Unsigned(2), Cbor::Unsigned(u32::from_be_bytes(master_fingerprint) as u64)),
+        (Cbor::Unsigned(3), Cbor::Unsigned(components.len() as u64)),
+    ]);
+
+    // Coin info defaults to bitcoin mainnet; coin type 1' is bitcoin testnet
+    let coin_info = match hardened(1) {
+        Some(0) => vec![],
+        Some(1) => vec![(Cbor::Unsigned(2), Cbor::Unsigned(1))],
+        Some(coin_type) => vec![(Cbor::Unsigned(1), Cbor::Unsigned(coin_type as u64))],
+        None => return Err(QrError::InvalidData(format!("Path {} has no hardened coin type", path))),
+    };
+
+    let mut hdkey = vec![
+        (Cbor::Unsigned(3), Cbor::Bytes(key[45..78].to_vec())),
+        (Cbor::Unsigned(4), Cbor::Bytes(key[13..45].to_vec())),
+    ];
+    if !coin_info.is_empty() {
+        hdkey.push((Cbor::Unsigned(5), Cbor::tag(TAG_CRYPTO_COININFO, Cbor::Map(coin_info))));
+    }
+    hdkey.push((Cbor::Unsigned(6), Cbor::tag(TAG_CRYPTO_KEYPATH, origin)));
+    hdkey.push((
+        Cbor::Unsigned(8),
+        Cbor::Unsigned(u32::from_be_bytes([key[5], key[6], key[7], key[8]]) as u64),
+    ));
+    let hdkey = Cbor::tag(TAG_CRYPTO_HDKEY, Cbor::Map(hdkey));
+
+    let script = match hardened(0) {
+        Some(44) => Cbor::tag(TAG_PUBKEY_HASH, hdkey),
+        Some(49) => Cbor::tag(TAG_SCRIPT_HASH, Cbor::tag(TAG_WITNESS_PUBKEY_HASH, hdkey)),
+        Some(84) => Cbor::tag(TAG_WITNESS_PUBKEY_HASH, hdkey),
+        Some(86) => Cbor::tag(TAG_TAPROOT, hdkey),
+        _ => return Err(QrError::InvalidData(format!("Unsupported account path {}", path))),
+    };
+    Ok(Cbor::tag(TAG_CRYPTO_OUTPUT, script))
+}
+
+/// BIP32 serialization of an extended public key, with SLIP-132 version
+/// bytes mapped back to xpub/tpub
+fn decode_xpub(xpub: &str) -> QrResult<[u8; 78]> {
+    const XPUB: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+    const TPUB: [u8; 4] = [0x04, 0x35, 0x87, 0xcf];
+
+    let mut data = bitcoin::base58::decode_check(xpub.trim())
+        .map_err(|e| QrError::InvalidData(format!("Invalid extended public key: {}", e)))?;
+    if data.len() != 78 {
+        return Err(QrError::InvalidData("Invalid extended public key length".to_string()));
+    }
+
+    let version = match &data[..4] {
+        // xpub, ypub, zpub
+        [0x04, 0x88, 0xb2, 0x1e] | [0x04, 0x9d, 0x7c, 0xb2] | [0x04, 0xb2, 0x47, 0x46] => XPUB,
+        // tpub, upub, vpub
+        [0x04, 0x35, 0x87, 0xcf] | [0x04, 0x4a, 0x52, 0x62] | [0x04, 0x5f, 0x1c, 0xf6] => TPUB,
+        _ => return Err(QrError::InvalidData("Not an extended public key".to_string())),
+    };
+    data[..4].copy_from_slice(&version);
+
+    // Rejects keys that are not valid curve points
+    bitcoin::bip32::Xpub::decode(&data)
+        .map_err(|e| QrError::InvalidData(format!("Invalid extended public key: {}", e)))?;
+
+    let mut key = [0u8; 78];
+    key.copy_from_slice(&data);
+    Ok(key)
+}
+
 /// Parsed UR components
 struct ParsedUr {
     ur_type: String,
@@ -584,21 +728,148 @@ mod tests {
         
         let mut decoder = UrDecoder::new();
         let mut complete = false;
+        assert_eq!(decoder.estimated_remaining_frames(), None);
         
-        for part in &parts {
+        for (i, part) in parts.iter().enumerate() {
             complete = decoder.receive(part).unwrap();
+            assert_eq!(decoder.estimated_remaining_frames(), Some(parts.len() - i - 1));
             if complete {
                 break;
             }
         }
         
         assert!(complete);
+        assert_eq!(decoder.frames_per_second_hint(), Some(4.0));
         
         let (ur_type, decoded) = decoder.result().unwrap();
         assert_eq!(ur_type, UrType::CryptoPsbt);
         assert_eq!(decoded, data);
     }
     
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    /// Master fingerprint and account xpub for `path` under [`MNEMONIC`]
+    fn account_xpub(path: &str) -> ([u8; 4], bitcoin::bip32::Xpub) {
+        use bitcoin::bip32::{DerivationPath, Xpriv, Xpub};
+        use bitcoin::secp256k1::Secp256k1;
+        use std::str::FromStr;
+
+        let secp = Secp256k1::new();
+        let seed = bip39::Mnemonic::parse(MNEMONIC).unwrap().to_seed("");
+        let master = Xpriv::new_master(bitcoin::Network::Bitcoin, &seed).unwrap();
+        let account = master.derive_priv(&secp, &DerivationPath::from_str(path).unwrap()).unwrap();
+        let fingerprint = hex::decode(master.fingerprint(&secp).to_string()).unwrap();
+        (fingerprint.try_into().unwrap(), Xpub::from_priv(&secp, &account))
+    }
+
+    fn map_keys(item: &Cbor) -> Vec<u64> {
+        match item {
+            Cbor::Map(entries) => entries
+                .iter()
+                .map(|(k, _)| match k {
+                    Cbor::Unsigned(n) => *n,
+                    other => panic!("non-integer key {:?}", other),
+                })
+                .collect(),
+            other => panic!("expected map, got {:?}", other),
+        }
+    }
+
+    fn untag(item: &Cbor, tag: u64) -> &Cbor {
+        match item {
+            Cbor::Tag(t, inner) if *t == tag => inner,
+            other => panic!("expected tag {}, got {:?}", tag, other),
+        }
+    }
+
+    #[test]
+    fn test_crypto_account_cbor() {
+        let (fingerprint, xpub) = account_xpub("m/84'/0'/0'");
+        assert_eq!(hex::encode(fingerprint), "73c5da0a");
+
+        let cbor = crypto_account_cbor(fingerprint, &[("m/84'/0'/0'", &xpub.to_string())]).unwrap();
+        let account = Cbor::decode(&cbor).unwrap();
+        assert_eq!(map_keys(&account), vec![1, 2]);
+        assert_eq!(account.get(1), Some(&Cbor::Unsigned(0x73c5da0a)));
+
+        let Some(Cbor::Array(descriptors)) = account.get(2) else { panic!("missing descriptors") };
+        assert_eq!(descriptors.len(), 1);
+        let hdkey = untag(untag(untag(&descriptors[0], 308), 404), 303);
+        assert_eq!(map_keys(hdkey), vec![3, 4, 6, 8]);
+        assert_eq!(hdkey.get(3), Some(&Cbor::Bytes(xpub.public_key.serialize().to_vec())));
+        let serialized = xpub.encode();
+        assert_eq!(hdkey.get(4), Some(&Cbor::Bytes(serialized[13..45].to_vec())));
+        assert_eq!(
+            hdkey.get(8),
+            Some(&Cbor::Unsigned(u32::from_be_bytes(serialized[5..9].try_into().unwrap()) as u64))
+        );
+
+        let origin = untag(hdkey.get(6).unwrap(), 304);
+        assert_eq!(map_keys(origin), vec![1, 2, 3]);
+        assert_eq!(
+            origin.get(1),
+            Some(&Cbor::Array(vec![
+                Cbor::Unsigned(84), Cbor::Bool(true),
+                Cbor::Unsigned(0), Cbor::Bool(true),
+                Cbor::Unsigned(0), Cbor::Bool(true),
+            ]))
+        );
+        assert_eq!(origin.get(2), Some(&Cbor::Unsigned(0x73c5da0a)));
+        assert_eq!(origin.get(3), Some(&Cbor::Unsigned(3)));
+    }
+
+    #[test]
+    fn test_crypto_account_script_types() {
+        let (fingerprint, legacy) = account_xpub("m/44'/0'/0'");
+        let (_, nested) = account_xpub("m/49'/0'/0'");
+        let (_, testnet) = account_xpub("m/84'/1'/0'");
+
+        // The testnet account is passed with its SLIP-132 vpub prefix
+        let mut vpub = testnet.encode();
+        vpub[..4].copy_from_slice(&[0x04, 0x5f, 0x1c, 0xf6]);
+        let vpub = bitcoin::base58::encode_check(&vpub);
+
+        let cbor = crypto_account_cbor(fingerprint, &[
+            ("m/44'/0'/0'", &legacy.to_string()),
+            ("m/49'/0'/0'", &nested.to_string()),
+            ("m/84'/1'/0'", &vpub),
+        ]).unwrap();
+        let account = Cbor::decode(&cbor).unwrap();
+        let Some(Cbor::Array(descriptors)) = account.get(2) else { panic!("missing descriptors") };
+
+        untag(untag(untag(&descriptors[0], 308), 403), 303);
+        untag(untag(untag(untag(&descriptors[1], 308), 400), 404), 303);
+        let testnet_key = untag(untag(untag(&descriptors[2], 308), 404), 303);
+        assert_eq!(map_keys(testnet_key), vec![3, 4, 5, 6, 8]);
+        assert_eq!(untag(testnet_key.get(5).unwrap(), 305).get(2), Some(&Cbor::Unsigned(1)));
+        assert_eq!(testnet_key.get(3), Some(&Cbor::Bytes(testnet.public_key.serialize().to_vec())));
+    }
+
+    #[test]
+    fn test_crypto_account_ur() {
+        let (fingerprint, xpub) = account_xpub("m/86'/0'/0'");
+        let frames = encode_crypto_account(fingerprint, &[("m/86'/0'/0'", &xpub.to_string())]).unwrap();
+        assert!(frames[0].starts_with("ur:crypto-account/"));
+
+        let mut decoder = UrDecoder::with_expected_type(UrType::CryptoAccount);
+        for frame in &frames {
+            decoder.receive(frame).unwrap();
+        }
+        let (_, cbor) = decoder.result().unwrap();
+        assert_eq!(cbor, crypto_account_cbor(fingerprint, &[("m/86'/0'/0'", &xpub.to_string())]).unwrap());
+    }
+
+    #[test]
+    fn test_crypto_account_rejects_bad_input() {
+        let (fingerprint, xpub) = account_xpub("m/84'/0'/0'");
+        let xpub = xpub.to_string();
+
+        assert!(crypto_account_cbor(fingerprint, &[]).is_err());
+        assert!(crypto_account_cbor(fingerprint, &[("m/84'/0'", &xpub)]).is_err()); // Depth mismatch
+        assert!(crypto_account_cbor(fingerprint, &[("m/45'/0'/0'", &xpub)]).is_err()); // Unknown purpose
+        assert!(crypto_account_cbor(fingerprint, &[("m/84'/0'/0'", "xpub123")]).is_err());
+    }
+
     #[test]
     fn test_parse_ur() {
         // Single part
@@ -367,7 +367,7 @@ impl ApprovalManager {
                     .map_err(|e| HawalaError::parse_error(format!("Failed to parse response: {}", e)))?;
                 
                 if let Some(logs) = json["result"].as_array() {
-                    return self.parse_approval_logs(logs, Chain::from_chain_id(chain_id));
+                    return self.parse_approval_logs(logs, Chain::from_chain_id(chain_id).unwrap_or(Chain::Ethereum));
                 }
             }
             Err(e) => {
@@ -461,27 +461,6 @@ impl Default for ApprovalManager {
     }
 }
 
-// =============================================================================
-// Chain Helper
-// =============================================================================
-
-impl Chain {
-    /// Get chain from chain ID
-    pub fn from_chain_id(chain_id: u64) -> Self {
-        match chain_id {
-            1 => Chain::Ethereum,
-            56 => Chain::Bnb,
-            137 => Chain::Polygon,
-            42161 => Chain::Arbitrum,
-            10 => Chain::Optimism,
-            8453 => Chain::Base,
-            43114 => Chain::Avalanche,
-            11155111 => Chain::EthereumSepolia,
-            _ => Chain::Ethereum,
-        }
-    }
-}
-
 // =============================================================================
 // Tests
 // =============================================================================
@@ -5,6 +5,10 @@
 //! - Phishing domains
 //! - Sanctioned addresses (OFAC)
 //! - Honeypot tokens
+//! - Unicode look-alikes (homographs) of well-known domains
+//!
+//! [`check_domain`] and [`update_blocklist`] work on a shared detector that
+//! starts from the bundled lists.
 //!
 //! Inspired by MetaMask Snaps, Rabby
 
@@ -13,6 +17,38 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::RwLock;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Phishing domains shipped with the app; fetched lists are merged on top
+/// with [`update_blocklist`]
+const BUNDLED_DOMAIN_BLOCKLIST: &[&str] = &[
+    "myetherwalet.com",
+    "myetherwallet.com.ru",
+    "rnyetherwallet.com",
+    "metamask-io.com",
+    "uniswap-airdrop.org",
+    "opensea-nft.io",
+];
+
+/// Non-Latin letters that render like Latin ones, mapped to the letter they
+/// imitate (accents and full-width forms are handled by NFKD)
+const CONFUSABLES: &[(char, char)] = &[
+    // Cyrillic
+    ('а', 'a'), ('с', 'c'), ('ԁ', 'd'), ('е', 'e'), ('һ', 'h'), ('і', 'i'),
+    ('ј', 'j'), ('ӏ', 'l'), ('о', 'o'), ('р', 'p'), ('ԛ', 'q'), ('ѕ', 's'),
+    ('ԝ', 'w'), ('х', 'x'), ('у', 'y'),
+    // Greek
+    ('α', 'a'), ('ε', 'e'), ('ι', 'i'), ('κ', 'k'), ('ν', 'v'), ('ο', 'o'),
+    ('ρ', 'p'), ('υ', 'u'), ('χ', 'x'),
+    // Armenian and Latin extensions
+    ('օ', 'o'), ('ս', 'u'), ('ı', 'i'), ('ɑ', 'a'), ('ɡ', 'g'), ('ȷ', 'j'),
+];
+
+lazy_static::lazy_static! {
+    /// Detector behind [`check_domain`] and [`update_blocklist`]
+    static ref SHARED_DETECTOR: PhishingDetector = PhishingDetector::new();
+}
 
 // =============================================================================
 // Types
@@ -92,6 +128,8 @@ pub enum DomainFlagType {
     Suspicious,
     /// Community reported
     CommunityReport,
+    /// Unicode look-alike of a legitimate domain
+    Homograph,
 }
 
 /// Risk level
@@ -105,6 +143,27 @@ pub enum PhishingRiskLevel {
     Critical,
 }
 
+/// Result of [`check_domain`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhishingVerdict {
+    /// Host that was checked, lowercased and in Unicode form
+    pub host: String,
+    /// ASCII (punycode) form of `host`
+    pub ascii_host: String,
+    /// Whether the domain is flagged
+    pub is_flagged: bool,
+    /// Type of flag (if flagged)
+    pub flag_type: Option<DomainFlagType>,
+    /// Risk level
+    pub risk_level: PhishingRiskLevel,
+    /// Details about the flag
+    pub details: Option<String>,
+    /// Legitimate domain it may be impersonating
+    pub impersonating: Option<String>,
+    /// Whether connection should be blocked
+    pub should_block: bool,
+}
+
 /// Blocklist entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlocklistEntry {
@@ -193,10 +252,12 @@ impl PhishingDetector {
             ("kraken.com", "Kraken"),
         ];
         
+        let domain_blocklist = BUNDLED_DOMAIN_BLOCKLIST.iter().map(|d| d.to_string()).collect();
+        
         Self {
             address_blocklist: RwLock::new(HashSet::new()),
             sanctioned_addresses: RwLock::new(Self::default_sanctioned()),
-            domain_blocklist: RwLock::new(HashSet::new()),
+            domain_blocklist: RwLock::new(domain_blocklist),
             trusted_domains: RwLock::new(trusted_domains),
             legitimate_domains,
             last_update: RwLock::new(Self::current_timestamp()),
@@ -324,6 +385,108 @@ impl PhishingDetector {
         }
     }
 
+    /// Check the host of a URL (or a bare domain)
+    ///
+    /// The host is normalized to lowercase ASCII and Unicode forms. Subdomains
+    /// of blocklisted and trusted domains count as listed, and a Unicode host
+    /// whose letters imitate a well-known domain is blocked as a homograph.
+    pub fn check_url(&self, url: &str) -> PhishingVerdict {
+        let Some((host, ascii_host)) = normalize_host(url) else {
+            return PhishingVerdict {
+                host: url.trim().to_lowercase(),
+                ascii_host: String::new(),
+                is_flagged: true,
+                flag_type: Some(DomainFlagType::Suspicious),
+                risk_level: PhishingRiskLevel::Medium,
+                details: Some("Not a valid domain name".to_string()),
+                impersonating: None,
+                should_block: false,
+            };
+        };
+        
+        let listed = |list: &RwLock<HashSet<String>>| {
+            list.read()
+                .map(|list| list.iter().any(|d| domain_matches(&ascii_host, d) || domain_matches(&host, d)))
+                .unwrap_or(false)
+        };
+        
+        let result = if listed(&self.domain_blocklist) {
+            DomainCheckResult {
+                domain: ascii_host.clone(),
+                is_flagged: true,
+                flag_type: Some(DomainFlagType::Phishing),
+                risk_level: PhishingRiskLevel::Critical,
+                details: Some("This domain has been reported as a phishing site.".to_string()),
+                impersonating: None,
+                should_block: true,
+            }
+        } else if listed(&self.trusted_domains) {
+            DomainCheckResult {
+                domain: ascii_host.clone(),
+                is_flagged: false,
+                flag_type: None,
+                risk_level: PhishingRiskLevel::Safe,
+                details: Some("Verified legitimate domain".to_string()),
+                impersonating: None,
+                should_block: false,
+            }
+        } else if let Some(target) = self.imitated_domain(&host) {
+            DomainCheckResult {
+                domain: ascii_host.clone(),
+                is_flagged: true,
+                flag_type: Some(DomainFlagType::Homograph),
+                risk_level: PhishingRiskLevel::Critical,
+                details: Some(format!("This domain uses look-alike characters to imitate {}", target)),
+                impersonating: Some(target),
+                should_block: true,
+            }
+        } else if has_mixed_script_label(&host) {
+            DomainCheckResult {
+                domain: ascii_host.clone(),
+                is_flagged: true,
+                flag_type: Some(DomainFlagType::Suspicious),
+                risk_level: PhishingRiskLevel::High,
+                details: Some("This domain mixes Latin and non-Latin letters.".to_string()),
+                impersonating: None,
+                should_block: false,
+            }
+        } else {
+            self.check_domain(&ascii_host)
+        };
+        
+        PhishingVerdict {
+            host,
+            ascii_host,
+            is_flagged: result.is_flagged,
+            flag_type: result.flag_type,
+            risk_level: result.risk_level,
+            details: result.details,
+            impersonating: result.impersonating,
+            should_block: result.should_block,
+        }
+    }
+
+    /// Merge fetched domain entries into the blocklist, returning how many
+    /// were new
+    ///
+    /// Entries whose value isn't a domain name (addresses, IPs) are skipped.
+    pub fn merge_blocklist(&self, entries: &[BlocklistEntry]) -> usize {
+        let mut added = 0;
+        if let Ok(mut blocklist) = self.domain_blocklist.write() {
+            for entry in entries {
+                if let Some((_, ascii_host)) = normalize_host(&entry.value) {
+                    if blocklist.insert(ascii_host) {
+                        added += 1;
+                    }
+                }
+            }
+        }
+        if let Ok(mut last_update) = self.last_update.write() {
+            *last_update = Self::current_timestamp();
+        }
+        added
+    }
+
     /// Add addresses to the blocklist
     pub fn add_to_blocklist(&self, addresses: &[String]) {
         if let Ok(mut blocklist) = self.address_blocklist.write() {
@@ -402,6 +565,27 @@ impl PhishingDetector {
         addresses.into_iter().map(|s| s.to_string()).collect()
     }
 
+    /// Well-known domain that a Unicode `host` imitates, if any
+    fn imitated_domain(&self, host: &str) -> Option<String> {
+        if host.is_ascii() {
+            return None;
+        }
+        let skeleton = skeleton(host);
+        let labels: Vec<&str> = skeleton.split('.').collect();
+        
+        for (legit, _) in &self.legitimate_domains {
+            let brand = legit.split('.').next().unwrap_or(*legit);
+            if domain_matches(&skeleton, legit) || labels.contains(&brand) {
+                return Some(legit.to_string());
+            }
+        }
+        self.trusted_domains.read().ok()?
+            .iter()
+            .filter(|d| domain_matches(&skeleton, d))
+            .max_by_key(|d| d.len())
+            .cloned()
+    }
+
     fn check_impersonation(&self, domain: &str) -> Option<(&'static str, &'static str)> {
         for (legit, name) in &self.legitimate_domains {
             // Check if domain is similar but not the same
@@ -512,6 +696,71 @@ impl Default for PhishingDetector {
     }
 }
 
+// =============================================================================
+// Shared Detector
+// =============================================================================
+
+/// Check a URL or domain against the shared detector
+pub fn check_domain(url: &str) -> PhishingVerdict {
+    SHARED_DETECTOR.check_url(url)
+}
+
+/// Merge a fetched blocklist into the shared detector, returning how many
+/// domains were new
+pub fn update_blocklist(entries: &[BlocklistEntry]) -> usize {
+    SHARED_DETECTOR.merge_blocklist(entries)
+}
+
+/// Lowercase Unicode and ASCII forms of a URL's host; `None` unless the host
+/// is a domain name
+fn normalize_host(input: &str) -> Option<(String, String)> {
+    let input = input.trim();
+    let url = if input.contains("://") {
+        url::Url::parse(input)
+    } else {
+        url::Url::parse(&format!("https://{}", input))
+    }
+    .ok()?;
+    
+    let url::Host::Domain(ascii) = url.host()? else {
+        return None;
+    };
+    let ascii = ascii.trim_end_matches('.').to_lowercase();
+    let ascii = ascii.strip_prefix("www.").unwrap_or(&ascii).to_string();
+    if !ascii.contains('.') {
+        return None;
+    }
+    let unicode = url::quirks::domain_to_unicode(&ascii);
+    let unicode = if unicode.is_empty() { ascii.clone() } else { unicode };
+    Some((unicode, ascii))
+}
+
+/// `host` is `domain` or one of its subdomains
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain || host.strip_suffix(domain).is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// `host` with accents stripped and look-alike letters replaced by the Latin
+/// letters they imitate
+fn skeleton(host: &str) -> String {
+    host.nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .map(|c| {
+            CONFUSABLES.iter()
+                .find(|(confusable, _)| *confusable == c)
+                .map_or(c, |(_, latin)| *latin)
+        })
+        .collect()
+}
+
+/// A single label mixing ASCII letters with non-ASCII ones, as in `pаypal`
+fn has_mixed_script_label(host: &str) -> bool {
+    host.split('.').any(|label| {
+        label.chars().any(|c| c.is_ascii_alphabetic())
+            && label.chars().any(|c| c.is_alphabetic() && !c.is_ascii())
+    })
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -587,6 +836,60 @@ mod tests {
         assert_eq!(result.flag_type, Some(FlagType::Scammer));
     }
 
+    #[test]
+    fn test_check_domain_punycode_homograph() {
+        // "uniswаp.org" with a Cyrillic "а"
+        let result = check_domain("https://xn--uniswp-7nf.org/#/swap");
+        assert_eq!(result.host, "uniswаp.org");
+        assert_eq!(result.ascii_host, "xn--uniswp-7nf.org");
+        assert!(result.is_flagged);
+        assert_eq!(result.flag_type, Some(DomainFlagType::Homograph));
+        assert_eq!(result.impersonating.as_deref(), Some("uniswap.org"));
+        assert!(result.should_block);
+        
+        // Same domain typed in Unicode, and a look-alike subdomain
+        let result = check_domain("uniswаp.org");
+        assert_eq!(result.ascii_host, "xn--uniswp-7nf.org");
+        assert_eq!(result.flag_type, Some(DomainFlagType::Homograph));
+        let result = check_domain("xn--metamsk-6fg.io");
+        assert_eq!(result.impersonating.as_deref(), Some("metamask.io"));
+    }
+
+    #[test]
+    fn test_check_domain_legitimate() {
+        for url in ["https://app.uniswap.org/#/swap", "UNISWAP.ORG", "www.etherscan.io/tx/0x1", "docs.uniswap.org"] {
+            let result = check_domain(url);
+            assert!(!result.is_flagged, "{} was flagged", url);
+            assert_eq!(result.risk_level, PhishingRiskLevel::Safe);
+            assert!(!result.should_block);
+        }
+        
+        // Not a domain at all
+        assert!(check_domain("not a url").is_flagged);
+    }
+
+    #[test]
+    fn test_bundled_and_merged_blocklist() {
+        let detector = PhishingDetector::new();
+        let result = detector.check_url("https://rnyetherwallet.com/");
+        assert_eq!(result.flag_type, Some(DomainFlagType::Phishing));
+        assert!(result.should_block);
+        
+        let entry = |value: &str| BlocklistEntry {
+            value: value.to_string(),
+            flag_type: "phishing".to_string(),
+            source: "test".to_string(),
+            added_at: 0,
+            report_count: 1,
+        };
+        let entries = [entry("https://Fake-Wallet-Sync.example/login"), entry("rnyetherwallet.com"), entry("0xbadaddress")];
+        assert_eq!(detector.merge_blocklist(&entries), 1);
+        
+        // Subdomains of a listed domain are listed too
+        let result = detector.check_url("claim.fake-wallet-sync.example");
+        assert_eq!(result.flag_type, Some(DomainFlagType::Phishing));
+    }
+
     #[test]
     fn test_case_insensitive() {
         let detector = PhishingDetector::new();
@@ -219,16 +219,20 @@ pub fn zeroize_string(mut s: String) {
 
 /// Secure comparison (constant-time)
 /// Returns true if slices are equal
+///
+/// Only the lengths are compared in variable time. Use this (or
+/// `subtle::ConstantTimeEq`) wherever either side is secret-derived:
+///
+/// - `verification::VerificationManager::verify_challenge` (challenge ids,
+///   which embed the nonce)
+/// - `utils::crypto` Base58Check checksums (WIF private keys)
+/// - `shamir` share checksums
+/// - `wallet::derivation` claimed-key checks
 pub fn secure_compare(a: &[u8], b: &[u8]) -> bool {
-    if a.len() != b.len() {
-        return false;
-    }
+    use subtle::ConstantTimeEq;
 
-    let mut result = 0u8;
-    for (x, y) in a.iter().zip(b.iter()) {
-        result |= x ^ y;
-    }
-    result == 0
+    // ct_eq is false for different lengths without inspecting the contents
+    a.ct_eq(b).into()
 }
 
 /// Secure comparison for strings
@@ -183,7 +183,7 @@ pub fn recover_seed(shares: &[RecoveryShare]) -> HawalaResult<String> {
         let share_bytes = base64_decode(&share.data)?;
         let expected_checksum = calculate_checksum(&share_bytes);
         
-        if share.checksum != expected_checksum {
+        if !super::secure_memory::secure_compare_str(&share.checksum, &expected_checksum) {
             return Err(HawalaError::invalid_input(format!(
                 "Share {} has invalid checksum - may be corrupted",
                 share.id
@@ -245,7 +245,7 @@ pub fn validate_share(share: &RecoveryShare) -> ShareValidation {
     match base64_decode(&share.data) {
         Ok(bytes) => {
             let expected_checksum = calculate_checksum(&bytes);
-            if share.checksum != expected_checksum {
+            if !super::secure_memory::secure_compare_str(&share.checksum, &expected_checksum) {
                 ShareValidation {
                     valid: false,
                     share_id: share.id,
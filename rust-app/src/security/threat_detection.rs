@@ -6,6 +6,14 @@
 //! - Velocity checks
 //! - Geographic anomalies
 //! - Known malicious address database
+//!
+//! # Concurrency
+//!
+//! `ThreatDetector` is `Send + Sync` and is shared process-wide through
+//! [`get_threat_detector`]. The blacklist, whitelist and history each sit
+//! behind their own `RwLock`, no method holds more than one of them at a time,
+//! and every write is a single critical section, so concurrent
+//! `blacklist_address`/`record_transaction` calls are never lost.
 
 use crate::error::{read_lock, write_lock};
 use crate::types::Chain;
@@ -159,15 +167,8 @@ impl ThreatDetector {
 
         // Check address poisoning
         if self.config.check_address_similarity {
-            if let Some(similar) = self.find_similar_address(recipient, known_addresses) {
-                threats.push(ThreatIndicator {
-                    threat_type: ThreatType::AddressPoisoning,
-                    severity: RiskLevel::High,
-                    description: format!(
-                        "Address is similar to known address - possible poisoning attack"
-                    ),
-                    evidence: Some(format!("Similar to: {}", similar)),
-                });
+            if let Some(threat) = self.assess_address_similarity(recipient, known_addresses) {
+                threats.push(threat);
                 recommendations.push("Verify the address character by character".to_string());
             }
         }
@@ -310,6 +311,37 @@ impl ThreatDetector {
         }
     }
 
+    /// Check `recipient` against previously-used addresses for poisoning
+    ///
+    /// Poisoners generate vanity addresses whose first and last characters
+    /// match an address the victim has paid before, since that is all most
+    /// wallets and explorers display. A recipient that shares the prefix and
+    /// suffix of a known address but differs in the middle is flagged, as is
+    /// one that is merely very similar overall. Exact matches are never flagged.
+    pub fn assess_address_similarity(&self, recipient: &str, known_addresses: &[String]) -> Option<ThreatIndicator> {
+        let normalized = normalize_address(recipient);
+        if known_addresses.iter().any(|known| normalize_address(known) == normalized) {
+            return None;
+        }
+
+        if let Some(known) = find_prefix_suffix_match(&normalized, known_addresses) {
+            return Some(ThreatIndicator {
+                threat_type: ThreatType::AddressPoisoning,
+                severity: RiskLevel::High,
+                description: "Address matches the start and end of a previously used address - possible poisoning attack"
+                    .to_string(),
+                evidence: Some(format!("Look-alike of: {}", known)),
+            });
+        }
+
+        self.find_similar_address(recipient, known_addresses).map(|similar| ThreatIndicator {
+            threat_type: ThreatType::AddressPoisoning,
+            severity: RiskLevel::High,
+            description: "Address is similar to known address - possible poisoning attack".to_string(),
+            evidence: Some(format!("Similar to: {}", similar)),
+        })
+    }
+
     /// Find similar address (for poisoning detection)
     fn find_similar_address(&self, address: &str, known: &[String]) -> Option<String> {
         let normalized = normalize_address(address);
@@ -441,6 +473,49 @@ fn normalize_address(address: &str) -> String {
     address.trim().to_lowercase()
 }
 
+/// Characters compared at each end of an address body for look-alikes
+const POISONING_PREFIX_CHARS: usize = 4;
+const POISONING_SUFFIX_CHARS: usize = 4;
+
+/// Bech32 HRPs whose separator and witness version are skipped
+const BECH32_HRPS: &[&str] = &["bc", "tb", "bcrt", "ltc", "tltc"];
+
+/// Strip the part of an address that every address of its type shares
+/// (`0x`, or a bech32 HRP, separator and witness version)
+fn address_body(address: &str) -> &str {
+    if let Some(body) = address.strip_prefix("0x") {
+        return body;
+    }
+    if let Some(sep) = address.rfind('1') {
+        // `get` because a multi-byte character may follow the separator
+        if let Some(body) = address.get(sep + 2..).filter(|body| !body.is_empty()) {
+            if BECH32_HRPS.contains(&&address[..sep]) {
+                return body;
+            }
+        }
+    }
+    address
+}
+
+/// Find a known address with the same leading and trailing characters as
+/// `normalized` but a different middle
+fn find_prefix_suffix_match(normalized: &str, known: &[String]) -> Option<String> {
+    let body = address_body(normalized);
+    if !body.is_ascii() || body.len() <= POISONING_PREFIX_CHARS + POISONING_SUFFIX_CHARS {
+        return None;
+    }
+
+    known.iter().find(|known_addr| {
+        let known_normalized = normalize_address(known_addr);
+        let known_body = address_body(&known_normalized);
+        known_body.is_ascii()
+            && known_body.len() > POISONING_PREFIX_CHARS + POISONING_SUFFIX_CHARS
+            && known_body != body
+            && known_body[..POISONING_PREFIX_CHARS] == body[..POISONING_PREFIX_CHARS]
+            && known_body[known_body.len() - POISONING_SUFFIX_CHARS..] == body[body.len() - POISONING_SUFFIX_CHARS..]
+    }).cloned()
+}
+
 /// Calculate similarity between two addresses (0-100)
 fn calculate_similarity(a: &str, b: &str) -> u8 {
     if a.len() != b.len() {
@@ -607,4 +682,95 @@ mod tests {
 
         assert!(assessment.threats.iter().any(|t| t.threat_type == ThreatType::AddressPoisoning));
     }
+
+    #[test]
+    fn test_prefix_suffix_look_alike_flagged() {
+        let detector = ThreatDetector::new();
+        let known = vec!["0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string()];
+
+        // Same first and last four characters, different middle
+        let poisoned = "0xd8dA1c2b3a4958e7f6d5c4b3a2918f7e6d5c6045";
+        assert!(calculate_similarity(&normalize_address(poisoned), &normalize_address(&known[0])) < 80);
+
+        let threat = detector.assess_address_similarity(poisoned, &known).unwrap();
+        assert_eq!(threat.threat_type, ThreatType::AddressPoisoning);
+        assert_eq!(threat.severity, RiskLevel::High);
+        assert!(threat.evidence.unwrap().contains(&known[0]));
+
+        let assessment = detector.assess_transaction("wallet1", poisoned, 1000, Chain::Ethereum, &known);
+        assert!(assessment.threats.iter().any(|t| t.threat_type == ThreatType::AddressPoisoning));
+
+        // Bech32 look-alike: the shared "bc1q" does not count towards the prefix
+        let known = vec!["bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string()];
+        let poisoned = "bc1qar0s9x8c7v6d5n4m3k2j7h0g9f8d7s6a5p5mdq";
+        assert!(detector.assess_address_similarity(poisoned, &known).is_some());
+
+        // A multi-byte character after the separator is not sliced through
+        assert_eq!(address_body("bc1é0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"), "bc1é0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
+        detector.assess_address_similarity("bc1éar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", &known);
+    }
+
+    #[test]
+    fn test_new_address_not_flagged() {
+        let detector = ThreatDetector::new();
+        let known = vec!["0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string()];
+
+        assert!(detector.assess_address_similarity("0x742d35Cc6634C0532925a3b844Bc454e4438f44e", &known).is_none());
+        // Paying a known address again is not poisoning
+        assert!(detector.assess_address_similarity("0xD8DA6BF26964AF9D7EED9E03E53415D37AA96045", &known).is_none());
+
+        let assessment = detector.assess_transaction(
+            "wallet1",
+            "0x742d35Cc6634C0532925a3b844Bc454e4438f44e",
+            1000,
+            Chain::Ethereum,
+            &known
+        );
+        assert!(!assessment.threats.iter().any(|t| t.threat_type == ThreatType::AddressPoisoning));
+
+        // Different bech32 addresses share "bc1q" but nothing else
+        let known = vec!["bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string()];
+        assert!(detector.assess_address_similarity("bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh", &known).is_none());
+    }
+
+    #[test]
+    fn test_concurrent_blacklist_and_assessment() {
+        use std::sync::Arc;
+
+        let detector = Arc::new(ThreatDetector::new());
+        let handles: Vec<_> = (0..8).map(|t| {
+            let detector = Arc::clone(&detector);
+            std::thread::spawn(move || {
+                for i in 0..250 {
+                    let address = format!("0xbad{}_{}", t, i);
+                    detector.blacklist_address(&address);
+                    let assessment = detector.assess_transaction(
+                        "wallet1",
+                        &address,
+                        1000,
+                        Chain::Ethereum,
+                        &[]
+                    );
+                    assert!(!assessment.allow_transaction);
+                    detector.record_transaction("wallet1", &address, 1000, Chain::Ethereum);
+                }
+            })
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        for t in 0..8 {
+            for i in 0..250 {
+                assert!(detector.is_blacklisted(&format!("0xBAD{}_{}", t, i)));
+            }
+        }
+        assert_eq!(read_lock(&detector.tx_history).unwrap().len(), 1000);
+    }
+
+    #[test]
+    fn test_threat_detector_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ThreatDetector>();
+    }
 }
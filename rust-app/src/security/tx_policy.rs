@@ -6,12 +6,22 @@
 //! - Time-based restrictions
 //! - Chain-specific rules
 //! - Approval workflows
+//!
+//! # Concurrency
+//!
+//! `PolicyManager` is `Send + Sync` and is shared process-wide through
+//! [`get_policy_manager`]. Each piece of state sits behind its own `RwLock`;
+//! locks are always taken in the order global policy → policies → spending
+//! history and never re-entered, so concurrent callers cannot deadlock.
+//! Individual updates are atomic, but `check_transaction` followed by
+//! `record_transaction` is not: use [`PolicyManager::check_and_record_transaction`]
+//! when several threads may spend from the same wallet.
 
 use crate::error::{read_lock, write_lock, HawalaError, HawalaResult};
 use crate::types::Chain;
 use chrono::{Datelike, Timelike};
 use std::collections::{HashMap, HashSet};
-use std::sync::RwLock;
+use std::sync::{Mutex, RwLock};
 use std::time::{Duration, Instant, SystemTime};
 
 /// Transaction policy manager
@@ -22,6 +32,8 @@ pub struct PolicyManager {
     spending_history: RwLock<HashMap<String, Vec<SpendRecord>>>,
     /// Global policies (apply to all wallets)
     global_policy: RwLock<GlobalPolicy>,
+    /// Serializes check-and-record so concurrent spends can't overshoot a limit
+    spend_gate: Mutex<()>,
 }
 
 /// Wallet-specific policy
@@ -153,6 +165,7 @@ impl PolicyManager {
             policies: RwLock::new(HashMap::new()),
             spending_history: RwLock::new(HashMap::new()),
             global_policy: RwLock::new(GlobalPolicy::default()),
+            spend_gate: Mutex::new(()),
         }
     }
 
@@ -200,12 +213,12 @@ impl PolicyManager {
 
             if let Some(global_limit) = global.global_daily_limit {
                 let today_total = self.get_daily_spending(wallet_id);
-                if today_total + amount > global_limit {
+                if today_total.saturating_add(amount) > global_limit {
                     violations.push(PolicyViolation {
                         violation_type: ViolationType::GlobalLimitExceeded,
                         message: "Global daily spending limit exceeded".to_string(),
                         limit: Some(global_limit),
-                        actual: Some(today_total + amount),
+                        actual: Some(today_total.saturating_add(amount)),
                     });
                 }
             }
@@ -245,12 +258,12 @@ impl PolicyManager {
                 // Daily limit
                 if let Some(limit) = policy.daily_limit {
                     let today_total = self.get_daily_spending(wallet_id);
-                    if today_total + amount > limit {
+                    if today_total.saturating_add(amount) > limit {
                         violations.push(PolicyViolation {
                             violation_type: ViolationType::DailyLimitExceeded,
                             message: "Daily spending limit exceeded".to_string(),
                             limit: Some(limit),
-                            actual: Some(today_total + amount),
+                            actual: Some(today_total.saturating_add(amount)),
                         });
                     }
                 }
@@ -258,12 +271,12 @@ impl PolicyManager {
                 // Weekly limit
                 if let Some(limit) = policy.weekly_limit {
                     let week_total = self.get_weekly_spending(wallet_id);
-                    if week_total + amount > limit {
+                    if week_total.saturating_add(amount) > limit {
                         violations.push(PolicyViolation {
                             violation_type: ViolationType::WeeklyLimitExceeded,
                             message: "Weekly spending limit exceeded".to_string(),
                             limit: Some(limit),
-                            actual: Some(week_total + amount),
+                            actual: Some(week_total.saturating_add(amount)),
                         });
                     }
                 }
@@ -271,12 +284,12 @@ impl PolicyManager {
                 // Monthly limit
                 if let Some(limit) = policy.monthly_limit {
                     let month_total = self.get_monthly_spending(wallet_id);
-                    if month_total + amount > limit {
+                    if month_total.saturating_add(amount) > limit {
                         violations.push(PolicyViolation {
                             violation_type: ViolationType::MonthlyLimitExceeded,
                             message: "Monthly spending limit exceeded".to_string(),
                             limit: Some(limit),
-                            actual: Some(month_total + amount),
+                            actual: Some(month_total.saturating_add(amount)),
                         });
                     }
                 }
@@ -382,18 +395,8 @@ impl PolicyManager {
             }
         }
 
-        // Calculate remaining limits
-        let policies = match read_lock(&self.policies) {
-            Ok(p) => p,
-            Err(_) => return PolicyCheckResult {
-                allowed: violations.is_empty(),
-                violations,
-                warnings,
-                requires_approval,
-                remaining_daily_limit: None,
-                remaining_weekly_limit: None,
-            },
-        };
+        // Calculate remaining limits (reusing the guard above - re-acquiring a
+        // read lock on the same thread can deadlock behind a queued writer)
         let remaining_daily = policies.get(wallet_id)
             .and_then(|p| p.daily_limit)
             .map(|limit| limit.saturating_sub(self.get_daily_spending(wallet_id)));
@@ -412,6 +415,26 @@ impl PolicyManager {
         }
     }
 
+    /// Check a transaction and, if it is allowed outright, record it in one step
+    ///
+    /// Concurrent callers are serialized, so two transactions can never both
+    /// pass a spending limit that only one of them fits under. Transactions
+    /// that are rejected or still require approval are not recorded.
+    pub fn check_and_record_transaction(
+        &self,
+        wallet_id: &str,
+        recipient: &str,
+        amount: u128,
+        chain: Chain,
+    ) -> PolicyCheckResult {
+        let _gate = self.spend_gate.lock().unwrap_or_else(|e| e.into_inner());
+        let result = self.check_transaction(wallet_id, recipient, amount, chain);
+        if result.allowed && !result.requires_approval {
+            self.record_transaction(wallet_id, recipient, amount, chain);
+        }
+        result
+    }
+
     /// Record a completed transaction
     pub fn record_transaction(
         &self,
@@ -638,9 +661,11 @@ mod tests {
     fn test_daily_limit() {
         let manager = PolicyManager::new();
         
-        let mut policy = WalletPolicy::default();
-        policy.enabled = true;
-        policy.daily_limit = Some(1000);
+        let policy = WalletPolicy {
+            enabled: true,
+            daily_limit: Some(1000),
+            ..Default::default()
+        };
         manager.set_policy("wallet1", policy);
 
         // Record some spending
@@ -730,4 +755,78 @@ mod tests {
         assert_eq!(result.remaining_daily_limit, Some(700));
         assert_eq!(result.remaining_weekly_limit, Some(4700));
     }
+
+    #[test]
+    fn test_check_and_record_never_overshoots_limit() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let manager = Arc::new(PolicyManager::new());
+        let policy = WalletPolicy {
+            enabled: true,
+            daily_limit: Some(1000),
+            ..Default::default()
+        };
+        manager.set_policy("wallet1", policy);
+
+        let approved = Arc::new(AtomicUsize::new(0));
+        let handles: Vec<_> = (0..16).map(|_| {
+            let manager = Arc::clone(&manager);
+            let approved = Arc::clone(&approved);
+            std::thread::spawn(move || {
+                for _ in 0..50 {
+                    let result = manager.check_and_record_transaction("wallet1", "recipient", 10, Chain::Ethereum);
+                    if result.allowed {
+                        approved.fetch_add(1, Ordering::SeqCst);
+                    }
+                }
+            })
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(approved.load(Ordering::SeqCst), 100);
+        assert_eq!(manager.get_daily_spending("wallet1"), 1000);
+    }
+
+    #[test]
+    fn test_concurrent_policy_updates_and_checks() {
+        use std::sync::Arc;
+
+        let manager = Arc::new(PolicyManager::new());
+        let policy = WalletPolicy {
+            daily_limit: Some(1_000_000),
+            ..Default::default()
+        };
+        manager.set_policy("wallet1", policy);
+
+        let handles: Vec<_> = (0..8).map(|t| {
+            let manager = Arc::clone(&manager);
+            std::thread::spawn(move || {
+                for i in 0..200 {
+                    let address = format!("0xblocked{}_{}", t, i);
+                    manager.block_address("wallet1", &address).unwrap();
+                    manager.set_policy_enabled("wallet1", true).unwrap();
+                    manager.check_transaction("wallet1", &address, u128::MAX, Chain::Ethereum);
+                    manager.record_transaction("wallet1", &address, 1, Chain::Ethereum);
+                }
+            })
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let policy = manager.get_policy("wallet1").unwrap();
+        assert_eq!(policy.blocked_addresses.len(), 8 * 200);
+        assert_eq!(manager.get_daily_spending("wallet1"), 8 * 200);
+        let result = manager.check_transaction("wallet1", "0xblocked3_17", 1, Chain::Ethereum);
+        assert!(result.violations.iter().any(|v| v.violation_type == ViolationType::AddressBlocked));
+    }
+
+    #[test]
+    fn test_policy_manager_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PolicyManager>();
+    }
 }
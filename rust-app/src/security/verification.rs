@@ -5,6 +5,19 @@
 //! - Message signing (EIP-191/EIP-712 style)
 //! - Challenge-response authentication
 //! - Signature verification
+//!
+//! # Concurrency
+//!
+//! `VerificationManager` is `Send + Sync` and is shared process-wide through
+//! [`get_verification_manager`]. A challenge is checked and marked used under
+//! a single write lock, so racing `verify_challenge` calls for the same
+//! challenge succeed at most once.
+//!
+//! # Timing
+//!
+//! Challenge ids embed the first 64 bits of the nonce, so `verify_challenge`
+//! matches them with a constant-time scan instead of a `HashMap` key lookup.
+//! Addresses are public and compared normally.
 
 use crate::error::{read_lock, write_lock, HawalaError, HawalaResult};
 use serde::{Deserialize, Serialize};
@@ -202,9 +215,8 @@ impl VerificationManager {
 
     /// Verify a challenge response
     /// 
-    /// Note: This is a simplified verification that checks the signature format
-    /// and challenge validity. In production, you'd integrate with actual
-    /// crypto libraries for signature verification.
+    /// `signature` must be an EIP-191 `personal_sign` of the challenge
+    /// message by the expected signer; the signer is recovered from it.
     pub fn verify_challenge(
         &self,
         challenge_id: &str,
@@ -213,7 +225,7 @@ impl VerificationManager {
     ) -> HawalaResult<VerificationResult> {
         let mut pending = write_lock(&self.pending_challenges)?;
         
-        let challenge = pending.get_mut(challenge_id)
+        let challenge = find_challenge(&mut pending, challenge_id)
             .ok_or_else(|| HawalaError::auth_error("Challenge not found"))?;
 
         let now = current_timestamp();
@@ -250,15 +262,23 @@ impl VerificationManager {
             });
         }
 
-        // Verify signature format (basic validation)
-        let sig_valid = validate_signature_format(signature);
+        // Recover the signer of the challenge message
+        let error = if !validate_signature_format(signature) {
+            Some("Invalid signature format")
+        } else {
+            match recover_personal_signer(&challenge.message, signature) {
+                Some(recovered) if addresses_match(&recovered, &challenge.expected_signer) => None,
+                Some(_) => Some("Signature was not made by the expected signer"),
+                None => Some("Invalid signature"),
+            }
+        };
         
         challenge.used = true;
 
         let result = VerificationResult {
-            valid: sig_valid,
+            valid: error.is_none(),
             signer: Some(claimed_signer.to_string()),
-            error: if sig_valid { None } else { Some("Invalid signature format".to_string()) },
+            error: error.map(str::to_string),
             verified_at: now,
         };
 
@@ -401,6 +421,28 @@ fn validate_signature_format(signature: &str) -> bool {
     len >= 128 && len <= 146
 }
 
+/// Address that made an EIP-191 `personal_sign` signature over `message`
+fn recover_personal_signer(message: &str, signature: &str) -> Option<String> {
+    let bytes = hex::decode(signature.strip_prefix("0x").unwrap_or(signature)).ok()?;
+    crate::message_signer::ethereum::recover_address(message.as_bytes(), &bytes).ok()
+}
+
+/// Find a pending challenge by id, comparing every id in constant time
+fn find_challenge<'a>(
+    pending: &'a mut HashMap<String, Challenge>,
+    challenge_id: &str,
+) -> Option<&'a mut Challenge> {
+    use subtle::ConstantTimeEq;
+
+    let mut found = None;
+    for (id, challenge) in pending.iter_mut() {
+        if bool::from(id.as_bytes().ct_eq(challenge_id.as_bytes())) {
+            found = Some(challenge);
+        }
+    }
+    found
+}
+
 /// Compare addresses (case-insensitive for Ethereum)
 fn addresses_match(a: &str, b: &str) -> bool {
     let a = a.strip_prefix("0x").unwrap_or(a);
@@ -469,6 +511,17 @@ pub fn get_verification_manager() -> &'static VerificationManager {
 mod tests {
     use super::*;
 
+    /// EIP-155 example key and its address
+    const TEST_KEY: [u8; 32] = [0x46; 32];
+    const TEST_SIGNER: &str = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+
+    /// personal_sign of the challenge message by the test key
+    fn sign_challenge(challenge: &Challenge) -> String {
+        crate::message_signer::ethereum::personal_sign(challenge.message.as_bytes(), &TEST_KEY)
+            .unwrap()
+            .signature
+    }
+
     #[test]
     fn test_create_challenge() {
         let manager = VerificationManager::new();
@@ -517,26 +570,17 @@ mod tests {
         let manager = VerificationManager::new();
         
         let challenge = manager.create_challenge(
-            "0x1234567890abcdef1234567890abcdef12345678",
+            TEST_SIGNER,
             None
         ).unwrap();
 
-        // Valid-looking signature (65 bytes = 130 hex chars)
-        let fake_sig = "0x".to_string() + &"ab".repeat(65);
+        let signature = sign_challenge(&challenge);
 
         // First verification
-        let result1 = manager.verify_challenge(
-            &challenge.id,
-            &fake_sig,
-            "0x1234567890abcdef1234567890abcdef12345678"
-        ).unwrap();
+        let result1 = manager.verify_challenge(&challenge.id, &signature, TEST_SIGNER).unwrap();
 
         // Second attempt should fail
-        let result2 = manager.verify_challenge(
-            &challenge.id,
-            &fake_sig,
-            "0x1234567890abcdef1234567890abcdef12345678"
-        ).unwrap();
+        let result2 = manager.verify_challenge(&challenge.id, &signature, TEST_SIGNER).unwrap();
 
         assert!(result1.valid);
         assert!(!result2.valid);
@@ -564,6 +608,22 @@ mod tests {
         assert!(result.error.as_ref().map(|e| e.contains("does not match")).unwrap_or(false));
     }
 
+    #[test]
+    fn test_find_challenge_exact_match_only() {
+        let manager = VerificationManager::new();
+        let challenge = manager.create_challenge(TEST_SIGNER, None).unwrap();
+        let signature = sign_challenge(&challenge);
+
+        // Prefixes and near-misses of the id are not found
+        let prefix = &challenge.id[..challenge.id.len() - 1];
+        assert!(manager.verify_challenge(prefix, &signature, TEST_SIGNER).is_err());
+        let mut near = challenge.id.clone();
+        near.push('0');
+        assert!(manager.verify_challenge(&near, &signature, TEST_SIGNER).is_err());
+
+        assert!(manager.verify_challenge(&challenge.id, &signature, TEST_SIGNER).unwrap().valid);
+    }
+
     #[test]
     fn test_addresses_match() {
         // Case insensitive
@@ -671,4 +731,53 @@ mod tests {
         );
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_concurrent_verification_succeeds_once() {
+        use std::sync::Arc;
+
+        let manager = Arc::new(VerificationManager::new());
+        let challenge = manager.create_challenge(TEST_SIGNER, None).unwrap();
+        let signature = sign_challenge(&challenge);
+
+        let handles: Vec<_> = (0..16).map(|_| {
+            let manager = Arc::clone(&manager);
+            let id = challenge.id.clone();
+            let sig = signature.clone();
+            std::thread::spawn(move || manager.verify_challenge(&id, &sig, TEST_SIGNER).unwrap().valid)
+        }).collect();
+        let successes = handles.into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|valid| *valid)
+            .count();
+
+        assert_eq!(successes, 1);
+    }
+
+    #[test]
+    fn test_forged_signature_rejected() {
+        let manager = VerificationManager::new();
+
+        // Well-formed but not a signature of the message
+        let challenge = manager.create_challenge(TEST_SIGNER, None).unwrap();
+        let forged = "0x".to_string() + &"ab".repeat(64) + "1b";
+        let result = manager.verify_challenge(&challenge.id, &forged, TEST_SIGNER).unwrap();
+        assert!(!result.valid);
+
+        // A real signature by a different key
+        let challenge = manager.create_challenge(TEST_SIGNER, None).unwrap();
+        let other = crate::message_signer::ethereum::personal_sign(challenge.message.as_bytes(), &[0x11; 32])
+            .unwrap()
+            .signature;
+        let result = manager.verify_challenge(&challenge.id, &other, TEST_SIGNER).unwrap();
+        assert!(!result.valid);
+        assert!(result.error.as_ref().map(|e| e.contains("expected signer")).unwrap_or(false));
+
+        // The test key's signature of a different message
+        let challenge = manager.create_challenge(TEST_SIGNER, None).unwrap();
+        let replayed = crate::message_signer::ethereum::personal_sign(b"some other message", &TEST_KEY)
+            .unwrap()
+            .signature;
+        assert!(!manager.verify_challenge(&challenge.id, &replayed, TEST_SIGNER).unwrap().valid);
+    }
 }
@@ -28,6 +28,29 @@ pub mod hex32 {
     }
 }
 
+/// Serialize/deserialize [u8; 32] as base58 string
+pub mod base58_32 {
+    use super::*;
+
+    pub fn serialize<S>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&bs58::encode(bytes).into_string())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 32], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let bytes = bs58::decode(&s).into_vec().map_err(serde::de::Error::custom)?;
+        bytes
+            .try_into()
+            .map_err(|_| serde::de::Error::custom("expected 32 bytes"))
+    }
+}
+
 /// Serialize/deserialize [u8; 64] as hex string
 pub mod hex64 {
     use super::*;
@@ -8,6 +8,7 @@ use crate::signing::preimage::{
     ethereum::{UnsignedEthereumTransaction, EthereumTxType},
     cosmos::UnsignedCosmosTransaction,
     solana::UnsignedSolanaTransaction,
+    near::{UnsignedNearTransaction, NEAR_ED25519_KEY_TYPE},
 };
 use serde::{Deserialize, Serialize};
 
@@ -54,7 +55,20 @@ pub struct CompiledSolanaTransaction {
     pub signature: [u8; 64],
 }
 
+/// Compiled NEAR transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompiledNearTransaction {
+    /// Borsh-encoded `SignedTransaction`
+    pub raw_tx: Vec<u8>,
+    /// Transaction hash (SHA-256 of the unsigned transaction)
+    pub tx_hash: [u8; 32],
+}
+
 /// Compile a Bitcoin transaction with external signatures
+///
+/// Signatures are matched to inputs by `input_index` (falling back to their
+/// position) and each one is verified against the input's sighash and
+/// script before anything is serialized.
 pub fn compile_bitcoin_transaction(
     tx: &UnsignedBitcoinTransaction,
     signatures: &[ExternalSignature],
@@ -68,6 +82,8 @@ pub fn compile_bitcoin_transaction(
             signatures.len()
         )));
     }
+    let signatures = signatures_by_input(signatures, tx.inputs.len())?;
+    verify_bitcoin_signatures(tx, &signatures)?;
     
     // Determine if we need SegWit serialization
     let has_witness = tx.inputs.iter().any(|i| 
@@ -98,7 +114,7 @@ pub fn compile_bitcoin_transaction(
         raw_tx.extend_from_slice(&input.vout.to_le_bytes());
         
         // scriptSig
-        let sig = &signatures[i];
+        let sig = signatures[i];
         let script_sig = build_script_sig(input, sig)?;
         write_var_int(script_sig.len() as u64, &mut raw_tx);
         raw_tx.extend_from_slice(&script_sig);
@@ -139,7 +155,7 @@ pub fn compile_bitcoin_transaction(
             txid_inner.reverse();
             tx_for_hash.extend_from_slice(&txid_inner);
             tx_for_hash.extend_from_slice(&input.vout.to_le_bytes());
-            let sig = &signatures[i];
+            let sig = signatures[i];
             let script_sig = build_script_sig(input, sig)?;
             write_var_int(script_sig.len() as u64, &mut tx_for_hash);
             tx_for_hash.extend_from_slice(&script_sig);
@@ -286,20 +302,302 @@ fn build_witness(
     }
 }
 
+/// Insert external signatures into a PSBT, finalize every input and extract
+/// the network-serialized transaction.
+///
+/// Signatures are matched to inputs by `input_index`, falling back to their
+/// position in the slice. ECDSA signatures may be DER (with or without the
+/// trailing sighash byte) or 64-byte compact; Taproot key-path signatures are
+/// 64-byte BIP-340 signatures, or 65 bytes with an explicit sighash type.
+/// Each signature is verified against the input's sighash before it is used.
+pub fn finalize_psbt(
+    psbt_bytes: &[u8],
+    signatures: &[ExternalSignature],
+) -> PreImageResult<CompiledBitcoinTransaction> {
+    use crate::signing::preimage::bitcoin::{psbt_input_type, psbt_prevouts, sighashes_from_psbt};
+    use bitcoin::hashes::Hash;
+    use bitcoin::psbt::Psbt;
+    use bitcoin::secp256k1::{Message, Secp256k1, XOnlyPublicKey};
+    use bitcoin::{CompressedPublicKey, ScriptBuf, Witness};
+    
+    let mut psbt = Psbt::deserialize(psbt_bytes)
+        .map_err(|e| PreImageError::InvalidTransaction(format!("Invalid PSBT: {}", e)))?;
+    let prevouts = psbt_prevouts(&psbt)?;
+    let sighashes = sighashes_from_psbt(psbt_bytes)?;
+    let secp = Secp256k1::verification_only();
+    
+    let by_input = signatures_by_input(signatures, psbt.inputs.len())?;
+    
+    for (index, input) in psbt.inputs.iter_mut().enumerate() {
+        let sig = by_input[index];
+        
+        let prevout = &prevouts[index];
+        let input_type = psbt_input_type(input, &prevout.script_pubkey)?;
+        let msg = Message::from_digest(sighashes[index].hash);
+        
+        match input_type {
+            BitcoinInputType::P2WPKH | BitcoinInputType::P2SH_P2WPKH => {
+                let public_key = CompressedPublicKey::from_slice(&sig.public_key)
+                    .map_err(|e| PreImageError::InvalidSignature(format!("Invalid public key: {}", e)))?;
+                let program = match input_type {
+                    BitcoinInputType::P2SH_P2WPKH => input.redeem_script.clone()
+                        .ok_or_else(|| PreImageError::MissingField(format!("redeem_script for input {}", index)))?,
+                    _ => prevout.script_pubkey.clone(),
+                };
+                if ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash()) != program {
+                    return Err(PreImageError::PublicKeyMismatch);
+                }
+                
+                let sighash_type = match input.sighash_type {
+                    Some(t) => t.ecdsa_hash_ty()
+                        .map_err(|e| PreImageError::EncodingError(e.to_string()))?,
+                    None => bitcoin::sighash::EcdsaSighashType::All,
+                };
+                let mut signature = parse_ecdsa_signature(&sig.signature, sighash_type)?;
+                signature.normalize_s();
+                secp.verify_ecdsa(&msg, &signature, &public_key.0)
+                    .map_err(|_| PreImageError::InvalidSignature(format!(
+                        "Signature does not verify for input {}",
+                        index
+                    )))?;
+                
+                let signature = bitcoin::ecdsa::Signature { signature, sighash_type };
+                if let Some(redeem_script) = input.redeem_script.take() {
+                    let push = bitcoin::script::PushBytesBuf::try_from(redeem_script.into_bytes())
+                        .map_err(|e| PreImageError::EncodingError(e.to_string()))?;
+                    input.final_script_sig = Some(
+                        bitcoin::script::Builder::new().push_slice(push).into_script()
+                    );
+                }
+                input.final_script_witness = Some(Witness::p2wpkh(&signature, &public_key.0));
+            }
+            BitcoinInputType::P2TR_KeyPath => {
+                let signature = bitcoin::taproot::Signature::from_slice(&sig.signature)
+                    .map_err(|e| PreImageError::InvalidSignature(e.to_string()))?;
+                let expected_type = match input.sighash_type {
+                    Some(t) => t.taproot_hash_ty()
+                        .map_err(|e| PreImageError::EncodingError(e.to_string()))?,
+                    None => bitcoin::sighash::TapSighashType::Default,
+                };
+                if signature.sighash_type != expected_type {
+                    return Err(PreImageError::InvalidSignature(format!(
+                        "Input {} expects sighash type {}",
+                        index, expected_type
+                    )));
+                }
+                
+                let output_key = XOnlyPublicKey::from_slice(&prevout.script_pubkey.as_bytes()[2..])
+                    .map_err(|e| PreImageError::InvalidTransaction(e.to_string()))?;
+                secp.verify_schnorr(&signature.signature, &msg, &output_key)
+                    .map_err(|_| PreImageError::InvalidSignature(format!(
+                        "Signature does not verify for input {}",
+                        index
+                    )))?;
+                
+                input.final_script_witness = Some(Witness::p2tr_key_spend(&signature));
+            }
+            other => {
+                return Err(PreImageError::UnsupportedType(format!(
+                    "Cannot finalize {:?} input {}",
+                    other, index
+                )));
+            }
+        }
+        
+        // BIP-174: the finalizer clears everything but the UTXO and final fields
+        input.partial_sigs.clear();
+        input.sighash_type = None;
+        input.redeem_script = None;
+        input.witness_script = None;
+        input.bip32_derivation.clear();
+        input.tap_key_sig = None;
+        input.tap_key_origins.clear();
+        input.tap_internal_key = None;
+        input.tap_merkle_root = None;
+    }
+    
+    let tx = psbt.extract_tx()
+        .map_err(|e| PreImageError::InvalidTransaction(e.to_string()))?;
+    let raw_tx = bitcoin::consensus::encode::serialize(&tx);
+    
+    let mut txid = tx.compute_txid().to_byte_array();
+    txid.reverse(); // txid is displayed reversed
+    let wtxid = if tx.input.iter().any(|i| !i.witness.is_empty()) {
+        let mut hash = tx.compute_wtxid().to_byte_array();
+        hash.reverse();
+        Some(hash)
+    } else {
+        None
+    };
+    
+    Ok(CompiledBitcoinTransaction {
+        raw_tx,
+        txid,
+        wtxid,
+        vsize: tx.vsize(),
+    })
+}
+
+/// Order signatures by `input_index`, falling back to their position in the
+/// slice, and require exactly one per input
+fn signatures_by_input(
+    signatures: &[ExternalSignature],
+    input_count: usize,
+) -> PreImageResult<Vec<&ExternalSignature>> {
+    let mut by_input: Vec<Option<&ExternalSignature>> = vec![None; input_count];
+    for (position, sig) in signatures.iter().enumerate() {
+        let index = sig.input_index.unwrap_or(position);
+        let slot = by_input.get_mut(index).ok_or(PreImageError::InvalidInputIndex(index))?;
+        if slot.is_some() {
+            return Err(PreImageError::InvalidSignature(format!(
+                "Duplicate signature for input {}",
+                index
+            )));
+        }
+        *slot = Some(sig);
+    }
+    
+    by_input
+        .into_iter()
+        .enumerate()
+        .map(|(index, sig)| sig.ok_or_else(|| PreImageError::MissingField(format!("signature for input {}", index))))
+        .collect()
+}
+
+/// Check each signature against its input's sighash and script before a
+/// transaction is assembled from them
+///
+/// ECDSA inputs take a DER signature (SIGHASH_ALL) from `public_key`, which
+/// must be the key the input's script commits to. Taproot key-path inputs
+/// take a 64-byte BIP-340 signature from the output key in the scriptPubKey.
+fn verify_bitcoin_signatures(
+    tx: &UnsignedBitcoinTransaction,
+    signatures: &[&ExternalSignature],
+) -> PreImageResult<()> {
+    use crate::signing::preimage::bitcoin::{get_bitcoin_sighashes, BitcoinSigHashType};
+    use bitcoin::secp256k1::{ecdsa, schnorr, Message, PublicKey, Secp256k1, XOnlyPublicKey};
+    use bitcoin::{CompressedPublicKey, ScriptBuf};
+    
+    let sighashes = get_bitcoin_sighashes(tx, BitcoinSigHashType::TaprootDefault)?;
+    let secp = Secp256k1::verification_only();
+    let does_not_verify = |index: usize| PreImageError::InvalidSignature(format!(
+        "Signature does not verify for input {}",
+        index
+    ));
+    
+    for (index, (input, sig)) in tx.inputs.iter().zip(signatures).enumerate() {
+        let msg = Message::from_digest(sighashes[index].hash);
+        
+        match input.input_type {
+            BitcoinInputType::P2TR_KeyPath => {
+                let output_key = Some(input.script_code.as_slice())
+                    .filter(|script| script.len() == 34 && script[..2] == [0x51, 0x20])
+                    .and_then(|script| XOnlyPublicKey::from_slice(&script[2..]).ok())
+                    .ok_or_else(|| PreImageError::InvalidTransaction(format!(
+                        "Input {} script_code is not a P2TR scriptPubKey",
+                        index
+                    )))?;
+                if !sig.public_key.is_empty() && sig.public_key != output_key.serialize() {
+                    return Err(PreImageError::PublicKeyMismatch);
+                }
+                let signature = schnorr::Signature::from_slice(&sig.signature)
+                    .map_err(|e| PreImageError::InvalidSignature(e.to_string()))?;
+                secp.verify_schnorr(&signature, &msg, &output_key)
+                    .map_err(|_| does_not_verify(index))?;
+            }
+            BitcoinInputType::P2TR_ScriptPath => {
+                return Err(PreImageError::UnsupportedType(format!(
+                    "Cannot compile Taproot script-path input {}",
+                    index
+                )));
+            }
+            _ => {
+                let public_key = PublicKey::from_slice(&sig.public_key)
+                    .map_err(|e| PreImageError::InvalidSignature(format!("Invalid public key for input {}: {}", index, e)))?;
+                
+                let commits_to_key = match input.input_type {
+                    // Keeps the key's compressed / uncompressed form
+                    BitcoinInputType::P2PKH => bitcoin::PublicKey::from_slice(&sig.public_key)
+                        .map(|key| input.script_code == ScriptBuf::new_p2pkh(&key.pubkey_hash()).into_bytes())
+                        .unwrap_or(false),
+                    // SegWit requires the compressed form
+                    BitcoinInputType::P2WPKH | BitcoinInputType::P2SH_P2WPKH => CompressedPublicKey::from_slice(&sig.public_key)
+                        .map(|key| input.script_code == ScriptBuf::new_p2wpkh(&key.wpubkey_hash()).into_bytes())
+                        .unwrap_or(false),
+                    // Redeem / witness scripts push the key itself
+                    _ => {
+                        let key = sig.public_key.as_slice();
+                        input.script_code.windows(key.len()).any(|window| window == key)
+                    }
+                };
+                if !commits_to_key {
+                    return Err(PreImageError::PublicKeyMismatch);
+                }
+                
+                let signature = ecdsa::Signature::from_der(&sig.signature)
+                    .map_err(|e| PreImageError::InvalidSignature(format!("Input {} needs a DER signature: {}", index, e)))?;
+                secp.verify_ecdsa(&msg, &signature, &public_key)
+                    .map_err(|_| does_not_verify(index))?;
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// Parse an ECDSA signature given as DER, DER plus sighash byte, or compact r || s
+fn parse_ecdsa_signature(
+    bytes: &[u8],
+    sighash_type: bitcoin::sighash::EcdsaSighashType,
+) -> PreImageResult<bitcoin::secp256k1::ecdsa::Signature> {
+    use bitcoin::secp256k1::ecdsa::Signature;
+    
+    if let Ok(signature) = Signature::from_der(bytes) {
+        return Ok(signature);
+    }
+    if bytes.len() == 64 || bytes.len() == 65 {
+        // Compact r || s, optionally followed by a recovery byte
+        return Signature::from_compact(&bytes[..64])
+            .map_err(|e| PreImageError::InvalidSignature(e.to_string()));
+    }
+    
+    let (sighash_byte, der) = bytes.split_last()
+        .ok_or_else(|| PreImageError::InvalidSignature("Empty signature".to_string()))?;
+    if u32::from(*sighash_byte) != sighash_type.to_u32() {
+        return Err(PreImageError::InvalidSignature(format!(
+            "Signature sighash byte 0x{:02x} does not match {}",
+            sighash_byte, sighash_type
+        )));
+    }
+    Signature::from_der(der).map_err(|e| PreImageError::InvalidSignature(e.to_string()))
+}
+
 /// Compile an Ethereum transaction with external signature
+///
+/// The sender is recovered from the signature; when `public_key` is set it
+/// must be the recovered key.
 pub fn compile_ethereum_transaction(
     tx: &UnsignedEthereumTransaction,
     signature: &ExternalSignature,
 ) -> PreImageResult<CompiledEthereumTransaction> {
     use tiny_keccak::{Hasher, Keccak};
     
-    let recovery_id = signature.recovery_id
-        .ok_or_else(|| PreImageError::MissingField("recovery_id".to_string()))?;
+    // Accept both 0/1 and 27/28 style recovery IDs
+    let recovery_id = match signature.recovery_id {
+        Some(v @ (0 | 1)) => v,
+        Some(v @ (27 | 28)) => v - 27,
+        Some(v) => return Err(PreImageError::InvalidSignature(format!("Invalid recovery_id: {}", v))),
+        None => return Err(PreImageError::MissingField("recovery_id".to_string())),
+    };
     
     // Extract r, s from signature
     if signature.signature.len() < 64 {
         return Err(PreImageError::InvalidSignature("Signature too short".to_string()));
     }
+    
+    // The signature must recover to the claimed key before anything is encoded
+    let from = recover_sender(tx, &signature.signature, recovery_id, &signature.public_key)?;
+    
     let r = &signature.signature[0..32];
     let s = &signature.signature[32..64];
     
@@ -316,9 +614,6 @@ pub fn compile_ethereum_transaction(
     hasher.update(&raw_tx);
     hasher.finalize(&mut tx_hash);
     
-    // Recover sender address from signature
-    let from = recover_sender(tx, &signature.signature, recovery_id)?;
-    
     Ok(CompiledEthereumTransaction {
         raw_tx,
         tx_hash,
@@ -348,7 +643,7 @@ fn compile_legacy_tx(tx: &UnsignedEthereumTransaction, r: &[u8], s: &[u8], v: u8
 
 fn compile_eip2930_tx(tx: &UnsignedEthereumTransaction, r: &[u8], s: &[u8], v: u8) -> PreImageResult<Vec<u8>> {
     let gas_price = tx.gas_price.ok_or_else(|| PreImageError::MissingField("gas_price".to_string()))?;
-    let access_list = tx.access_list.as_ref().ok_or_else(|| PreImageError::MissingField("access_list".to_string()))?;
+    let access_list = tx.access_list.as_ref().cloned().unwrap_or_default();
     
     let mut items = Vec::new();
     items.push(rlp_encode_u64(tx.chain_id));
@@ -358,7 +653,7 @@ fn compile_eip2930_tx(tx: &UnsignedEthereumTransaction, r: &[u8], s: &[u8], v: u
     items.push(rlp_encode_address(tx.to));
     items.push(rlp_encode_u128(tx.value));
     items.push(rlp_encode_bytes(&tx.data));
-    items.push(rlp_encode_access_list(access_list));
+    items.push(rlp_encode_access_list(&access_list));
     items.push(rlp_encode_u64(v as u64));
     items.push(rlp_encode_bytes(r));
     items.push(rlp_encode_bytes(s));
@@ -422,12 +717,17 @@ fn compile_eip7702_tx(tx: &UnsignedEthereumTransaction, r: &[u8], s: &[u8], v: u
 }
 
 /// Compile a Cosmos transaction with external signature
+///
+/// The signature is verified against the sign doc hash and the signer's
+/// public key (from the transaction, or the signature if it has none).
 pub fn compile_cosmos_transaction(
     tx: &UnsignedCosmosTransaction,
     signature: &ExternalSignature,
 ) -> PreImageResult<CompiledCosmosTransaction> {
     use sha2::{Sha256, Digest};
     
+    verify_cosmos_signature(tx, signature)?;
+    
     // Build TxRaw protobuf: { body_bytes, auth_info_bytes, signatures }
     let mut tx_raw = Vec::new();
     
@@ -461,7 +761,38 @@ pub fn compile_cosmos_transaction(
     })
 }
 
+/// Check a compact secp256k1 signature against the sign doc hash and the
+/// signer's public key
+fn verify_cosmos_signature(
+    tx: &UnsignedCosmosTransaction,
+    signature: &ExternalSignature,
+) -> PreImageResult<()> {
+    use crate::signing::preimage::get_cosmos_sign_doc_hash;
+    use secp256k1::{ecdsa, Message, PublicKey, Secp256k1};
+    
+    let key_bytes = match (&tx.signer.public_key, signature.public_key.is_empty()) {
+        (Some(expected), false) if *expected != signature.public_key => {
+            return Err(PreImageError::PublicKeyMismatch);
+        }
+        (Some(expected), _) => expected.as_slice(),
+        (None, false) => signature.public_key.as_slice(),
+        (None, true) => return Err(PreImageError::MissingField("public_key".to_string())),
+    };
+    let public_key = PublicKey::from_slice(key_bytes)
+        .map_err(|e| PreImageError::InvalidSignature(format!("Invalid public key: {}", e)))?;
+    
+    let compact = ecdsa::Signature::from_compact(&signature.signature)
+        .map_err(|e| PreImageError::InvalidSignature(format!("Cosmos signatures must be 64-byte r || s: {}", e)))?;
+    let hash = get_cosmos_sign_doc_hash(tx)?.hash;
+    Secp256k1::verification_only()
+        .verify_ecdsa(&Message::from_digest(hash), &compact, &public_key)
+        .map_err(|_| PreImageError::InvalidSignature("Signature does not match the sign doc".to_string()))
+}
+
 /// Compile a Solana transaction with external signatures
+///
+/// Each signer's signature must be an ed25519 signature over the serialized
+/// message from that signer's key.
 pub fn compile_solana_transaction(
     tx: &UnsignedSolanaTransaction,
     signatures: &[ExternalSignature],
@@ -474,21 +805,35 @@ pub fn compile_solana_transaction(
         )));
     }
     
+    let signatures = signatures_by_input(signatures, tx.signers.len())?;
+    let message = serialize_solana_message(tx)?;
+    
+    // Each signer signs the serialized message itself
+    for (index, (signer, sig)) in tx.signers.iter().zip(&signatures).enumerate() {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+        
+        if !sig.public_key.is_empty() && sig.public_key != signer.pubkey {
+            return Err(PreImageError::PublicKeyMismatch);
+        }
+        let sig_bytes: [u8; 64] = sig.signature.as_slice().try_into()
+            .map_err(|_| PreImageError::InvalidSignature("Solana signatures must be 64 bytes".to_string()))?;
+        let verifying_key = VerifyingKey::from_bytes(&signer.pubkey)
+            .map_err(|e| PreImageError::InvalidTransaction(format!("Invalid signer {}: {}", index, e)))?;
+        verifying_key.verify(&message, &Signature::from_bytes(&sig_bytes))
+            .map_err(|_| PreImageError::InvalidSignature(format!("Signature does not verify for signer {}", index)))?;
+    }
+    
     let mut raw_tx = Vec::new();
     
     // Number of signatures
     write_compact_u16(signatures.len() as u16, &mut raw_tx);
     
     // Signatures (64 bytes each)
-    for sig in signatures {
-        if sig.signature.len() != 64 {
-            return Err(PreImageError::InvalidSignature("Solana signatures must be 64 bytes".to_string()));
-        }
+    for sig in &signatures {
         raw_tx.extend_from_slice(&sig.signature);
     }
     
     // Message
-    let message = serialize_solana_message(tx)?;
     raw_tx.extend_from_slice(&message);
     
     // First signature is the transaction ID
@@ -501,6 +846,39 @@ pub fn compile_solana_transaction(
     })
 }
 
+/// Compile a NEAR transaction with an external ed25519 signature
+///
+/// The signature is checked against the transaction's public key before the
+/// Borsh `SignedTransaction` (transaction followed by the signature) is built.
+pub fn compile_near_transaction(
+    tx: &UnsignedNearTransaction,
+    signature: &ExternalSignature,
+) -> PreImageResult<CompiledNearTransaction> {
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+    use crate::signing::preimage::near::{get_near_signing_hash, serialize_near_transaction};
+
+    if !signature.public_key.is_empty() && signature.public_key != tx.public_key {
+        return Err(PreImageError::PublicKeyMismatch);
+    }
+    let sig_bytes: [u8; 64] = signature.signature.as_slice().try_into()
+        .map_err(|_| PreImageError::InvalidSignature("NEAR signatures must be 64 bytes".to_string()))?;
+
+    let tx_hash = get_near_signing_hash(tx)?.hash;
+    let verifying_key = VerifyingKey::from_bytes(&tx.public_key)
+        .map_err(|e| PreImageError::InvalidTransaction(format!("Invalid public key: {}", e)))?;
+    verifying_key.verify(&tx_hash, &Signature::from_bytes(&sig_bytes))
+        .map_err(|_| PreImageError::InvalidSignature("Signature does not match the transaction".to_string()))?;
+
+    let mut raw_tx = serialize_near_transaction(tx)?;
+    raw_tx.push(NEAR_ED25519_KEY_TYPE);
+    raw_tx.extend_from_slice(&sig_bytes);
+
+    Ok(CompiledNearTransaction {
+        raw_tx,
+        tx_hash,
+    })
+}
+
 // Helper functions
 
 fn write_var_int(value: u64, buf: &mut Vec<u8>) {
@@ -680,18 +1058,36 @@ fn rlp_encode_auth_list(list: &[crate::signing::preimage::ethereum::Eip7702Auth]
 }
 
 fn recover_sender(
-    _tx: &UnsignedEthereumTransaction,
-    _signature: &[u8],
-    _recovery_id: u8,
+    tx: &UnsignedEthereumTransaction,
+    signature: &[u8],
+    recovery_id: u8,
+    claimed_public_key: &[u8],
 ) -> PreImageResult<[u8; 20]> {
-    // In a full implementation, this would use secp256k1 ECDSA recovery
-    // For now, return a placeholder
-    // The actual recovery would:
-    // 1. Compute the signing hash
-    // 2. Use ecrecover with (hash, v, r, s)
-    // 3. Take keccak256 of uncompressed public key
-    // 4. Return last 20 bytes
-    Ok([0u8; 20])
+    use crate::signing::preimage::get_ethereum_signing_hash;
+    use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+    use secp256k1::{Message, PublicKey, Secp256k1};
+    
+    let hash = get_ethereum_signing_hash(tx)?.hash;
+    let recovery_id = RecoveryId::from_i32(recovery_id as i32)
+        .map_err(|e| PreImageError::InvalidSignature(e.to_string()))?;
+    let recoverable = RecoverableSignature::from_compact(&signature[..64], recovery_id)
+        .map_err(|e| PreImageError::InvalidSignature(e.to_string()))?;
+    let public_key = Secp256k1::verification_only()
+        .recover_ecdsa(&Message::from_digest(hash), &recoverable)
+        .map_err(|_| PreImageError::InvalidSignature("Signature does not recover a public key".to_string()))?;
+    
+    if !claimed_public_key.is_empty() {
+        let claimed = PublicKey::from_slice(claimed_public_key)
+            .map_err(|e| PreImageError::InvalidSignature(format!("Invalid public key: {}", e)))?;
+        if claimed != public_key {
+            return Err(PreImageError::PublicKeyMismatch);
+        }
+    }
+    
+    let address_hash = crate::utils::crypto::keccak256(&public_key.serialize_uncompressed()[1..]);
+    let mut from = [0u8; 20];
+    from.copy_from_slice(&address_hash[12..]);
+    Ok(from)
 }
 
 fn encode_cosmos_body(tx: &UnsignedCosmosTransaction) -> PreImageResult<Vec<u8>> {
@@ -812,6 +1208,7 @@ mod tests {
     use super::*;
     use crate::signing::preimage::bitcoin::{BitcoinInput, BitcoinOutput, BitcoinInputType};
     use crate::signing::preimage::ethereum::EthereumTxType;
+    use crate::signing::test_fixtures::p2wpkh_psbt;
     
     #[test]
     fn test_compile_simple_bitcoin() {
@@ -833,15 +1230,94 @@ mod tests {
             locktime: 0,
         };
         
-        let sig = ExternalSignature::new(
-            vec![0x30; 35], // Mock DER signature
-            vec![0x02; 33], // Mock compressed pubkey
-        );
+        let mut tx = tx;
+        let (secret, public_key) = test_key(0x11);
+        tx.inputs[0].script_code = p2pkh_script(&public_key);
+        let sig = sign_bitcoin_input(&tx, 0, &secret);
         
         let result = compile_bitcoin_transaction(&tx, &[sig]);
         assert!(result.is_ok());
     }
     
+    fn test_key(byte: u8) -> (bitcoin::secp256k1::SecretKey, bitcoin::secp256k1::PublicKey) {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let secret = bitcoin::secp256k1::SecretKey::from_slice(&[byte; 32]).unwrap();
+        (secret, secret.public_key(&secp))
+    }
+    
+    fn p2pkh_script(public_key: &bitcoin::secp256k1::PublicKey) -> Vec<u8> {
+        bitcoin::ScriptBuf::new_p2pkh(&bitcoin::PublicKey::new(*public_key).pubkey_hash()).into_bytes()
+    }
+    
+    /// DER signature over input `index`'s SIGHASH_ALL hash, tagged with that index
+    fn sign_bitcoin_input(
+        tx: &UnsignedBitcoinTransaction,
+        index: usize,
+        secret: &bitcoin::secp256k1::SecretKey,
+    ) -> ExternalSignature {
+        use crate::signing::preimage::bitcoin::{get_bitcoin_sighashes, BitcoinSigHashType};
+        use bitcoin::secp256k1::{Message, Secp256k1};
+        
+        let secp = Secp256k1::new();
+        let hash = get_bitcoin_sighashes(tx, BitcoinSigHashType::All).unwrap()[index].hash;
+        let signature = secp.sign_ecdsa(&Message::from_digest(hash), secret);
+        ExternalSignature::new(signature.serialize_der().to_vec(), secret.public_key(&secp).serialize().to_vec())
+            .with_input_index(index)
+    }
+    
+    fn two_input_p2wpkh_tx() -> (UnsignedBitcoinTransaction, [bitcoin::secp256k1::SecretKey; 2]) {
+        let keys = [test_key(0x21), test_key(0x22)];
+        let inputs = keys.iter().enumerate().map(|(i, (_, public_key))| BitcoinInput {
+            txid: [i as u8 + 1; 32],
+            vout: i as u32,
+            script_code: bitcoin::ScriptBuf::new_p2wpkh(
+                &bitcoin::CompressedPublicKey(*public_key).wpubkey_hash()
+            ).into_bytes(),
+            value: 50_000,
+            sequence: 0xfffffffd,
+            derivation_path: None,
+            input_type: BitcoinInputType::P2WPKH,
+        }).collect();
+        let tx = UnsignedBitcoinTransaction {
+            version: 2,
+            inputs,
+            outputs: vec![BitcoinOutput { value: 90_000, script_pubkey: [&[0x00, 0x14][..], &[0xaa; 20][..]].concat() }],
+            locktime: 0,
+        };
+        (tx, [keys[0].0, keys[1].0])
+    }
+    
+    #[test]
+    fn test_compile_bitcoin_verifies_signatures() {
+        let (tx, [first, second]) = two_input_p2wpkh_tx();
+        let sig0 = sign_bitcoin_input(&tx, 0, &first);
+        let sig1 = sign_bitcoin_input(&tx, 1, &second);
+        
+        // Matched by input_index, so order in the slice doesn't matter
+        let compiled = compile_bitcoin_transaction(&tx, &[sig1.clone(), sig0.clone()]).unwrap();
+        assert_eq!(compiled.raw_tx, compile_bitcoin_transaction(&tx, &[sig0.clone(), sig1.clone()]).unwrap().raw_tx);
+        
+        // Signed by a key the input doesn't pay to
+        let wrong_key = sign_bitcoin_input(&tx, 0, &second);
+        assert!(matches!(
+            compile_bitcoin_transaction(&tx, &[wrong_key, sig1.clone()]),
+            Err(PreImageError::PublicKeyMismatch)
+        ));
+        
+        // Right key, but the signature was made for the other input's sighash
+        let misplaced = sign_bitcoin_input(&tx, 1, &first).with_input_index(0);
+        assert!(matches!(
+            compile_bitcoin_transaction(&tx, &[misplaced, sig1.clone()]),
+            Err(PreImageError::InvalidSignature(_))
+        ));
+        
+        // Index that doesn't exist
+        assert!(matches!(
+            compile_bitcoin_transaction(&tx, &[sig0.with_input_index(2), sig1]),
+            Err(PreImageError::InvalidInputIndex(2))
+        ));
+    }
+    
     #[test]
     fn test_compile_ethereum_legacy() {
         let tx = UnsignedEthereumTransaction {
@@ -860,13 +1336,28 @@ mod tests {
             derivation_path: None,
         };
         
+        let (secret, public_key) = test_key(0x33);
+        let hash = crate::signing::preimage::get_ethereum_signing_hash(&tx).unwrap().hash;
+        let (recovery_id, compact) = secp256k1::Secp256k1::new()
+            .sign_ecdsa_recoverable(&secp256k1::Message::from_digest(hash), &secret)
+            .serialize_compact();
         let sig = ExternalSignature::new(
-            vec![0; 64],
-            vec![0x04; 65],
-        ).with_recovery_id(0);
+            compact.to_vec(),
+            public_key.serialize_uncompressed().to_vec(),
+        ).with_recovery_id(recovery_id.to_i32() as u8);
         
-        let result = compile_ethereum_transaction(&tx, &sig);
-        assert!(result.is_ok());
+        let compiled = compile_ethereum_transaction(&tx, &sig).unwrap();
+        let address_hash = crate::utils::crypto::keccak256(&public_key.serialize_uncompressed()[1..]);
+        assert_eq!(compiled.from[..], address_hash[12..]);
+        
+        // 27/28-style recovery IDs give the same transaction
+        let legacy_v = sig.clone().with_recovery_id(recovery_id.to_i32() as u8 + 27);
+        assert_eq!(compile_ethereum_transaction(&tx, &legacy_v).unwrap().raw_tx, compiled.raw_tx);
+        
+        let (_, other_key) = test_key(0x34);
+        let mut wrong_key = sig;
+        wrong_key.public_key = other_key.serialize().to_vec();
+        assert!(matches!(compile_ethereum_transaction(&tx, &wrong_key), Err(PreImageError::PublicKeyMismatch)));
     }
     
     #[test]
@@ -890,4 +1381,270 @@ mod tests {
         write_var_int(253, &mut buf);
         assert_eq!(buf, vec![0xfd, 253, 0]);
     }
+
+    
+    // Signature and final transaction for `P2WPKH_PSBT_BASE64`
+    const P2WPKH_PUBKEY: &str = "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c";
+    const P2WPKH_SIGNATURE: &str = "a53d927553a0ac10def89292102a7d83469403519a6dabb8d6d657a126d1d8dc462f7a2e4727483cce59d8885904891ceb2db093905cd1f909110722af8dd0a3";
+    const P2WPKH_FINAL_TX: &str = "02000000000101f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f10000000000fdffffff0250c30000000000001600149c90f934ea51fa0f6504177043e0908da692998388c20000000000001600143e34985dca6fddc9fb369940e4c7d8e2873f529c02483045022100a53d927553a0ac10def89292102a7d83469403519a6dabb8d6d657a126d1d8dc0220462f7a2e4727483cce59d8885904891ceb2db093905cd1f909110722af8dd0a301210330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c00000000";
+    
+    fn single_input_psbt(script_pubkey: bitcoin::ScriptBuf, redeem_script: Option<bitcoin::ScriptBuf>) -> Vec<u8> {
+        use bitcoin::hashes::Hash;
+        use bitcoin::{absolute, transaction, Amount, OutPoint, Sequence, Transaction, TxIn, TxOut, Txid, Witness};
+        
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(Txid::from_byte_array([0xf1; 32]), 0),
+                script_sig: bitcoin::ScriptBuf::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::new(),
+            }],
+            output: vec![TxOut { value: Amount::from_sat(90_000), script_pubkey: script_pubkey.clone() }],
+        };
+        let mut psbt = bitcoin::psbt::Psbt::from_unsigned_tx(tx).unwrap();
+        psbt.inputs[0].witness_utxo = Some(TxOut { value: Amount::from_sat(100_000), script_pubkey });
+        psbt.inputs[0].redeem_script = redeem_script;
+        psbt.serialize()
+    }
+    
+    #[test]
+    fn test_finalize_psbt_p2wpkh() {
+        let sig = ExternalSignature::new(
+            hex::decode(P2WPKH_SIGNATURE).unwrap(),
+            hex::decode(P2WPKH_PUBKEY).unwrap(),
+        ).with_input_index(0);
+        
+        let compiled = finalize_psbt(&p2wpkh_psbt(), &[sig]).unwrap();
+        assert_eq!(hex::encode(&compiled.raw_tx), P2WPKH_FINAL_TX);
+        assert_eq!(
+            hex::encode(compiled.txid),
+            "79dd71fb87b3079c0e9b9e8b286f787fe300aed03117e35ba2304cbe1eb88027"
+        );
+        assert!(compiled.wtxid.is_some());
+        assert_eq!(compiled.vsize, 141);
+    }
+    
+    #[test]
+    fn test_finalize_psbt_accepts_der_signature() {
+        let compact = hex::decode(P2WPKH_SIGNATURE).unwrap();
+        let der = bitcoin::secp256k1::ecdsa::Signature::from_compact(&compact).unwrap()
+            .serialize_der()
+            .to_vec();
+        let mut der_with_sighash = der.clone();
+        der_with_sighash.push(0x01);
+        
+        for signature in [der, der_with_sighash] {
+            let sig = ExternalSignature::new(signature, hex::decode(P2WPKH_PUBKEY).unwrap());
+            let compiled = finalize_psbt(&p2wpkh_psbt(), &[sig]).unwrap();
+            assert_eq!(hex::encode(&compiled.raw_tx), P2WPKH_FINAL_TX);
+        }
+    }
+    
+    #[test]
+    fn test_finalize_psbt_rejects_bad_signatures() {
+        let pubkey = hex::decode(P2WPKH_PUBKEY).unwrap();
+        let mut tampered = hex::decode(P2WPKH_SIGNATURE).unwrap();
+        tampered[10] ^= 0x01;
+        let result = finalize_psbt(&p2wpkh_psbt(), &[ExternalSignature::new(tampered, pubkey.clone())]);
+        assert!(matches!(result, Err(PreImageError::InvalidSignature(_))));
+        
+        let mut other_key = pubkey.clone();
+        other_key[0] = 0x02;
+        let sig = ExternalSignature::new(hex::decode(P2WPKH_SIGNATURE).unwrap(), other_key);
+        assert!(matches!(finalize_psbt(&p2wpkh_psbt(), &[sig]), Err(PreImageError::PublicKeyMismatch)));
+        
+        assert!(matches!(finalize_psbt(&p2wpkh_psbt(), &[]), Err(PreImageError::MissingField(_))));
+        
+        let sig = ExternalSignature::new(hex::decode(P2WPKH_SIGNATURE).unwrap(), pubkey).with_input_index(1);
+        assert!(matches!(finalize_psbt(&p2wpkh_psbt(), &[sig]), Err(PreImageError::InvalidInputIndex(1))));
+    }
+    
+    #[test]
+    fn test_finalize_psbt_p2sh_p2wpkh() {
+        use bitcoin::secp256k1::{Message, Secp256k1, SecretKey};
+        use bitcoin::{CompressedPublicKey, ScriptBuf};
+        
+        let secp = Secp256k1::new();
+        let secret = SecretKey::from_slice(&[0x11; 32]).unwrap();
+        let public_key = CompressedPublicKey(secret.public_key(&secp));
+        let redeem_script = ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash());
+        let psbt = single_input_psbt(ScriptBuf::new_p2sh(&redeem_script.script_hash()), Some(redeem_script.clone()));
+        
+        let hash = crate::signing::preimage::bitcoin::sighashes_from_psbt(&psbt).unwrap()[0].hash;
+        let signature = secp.sign_ecdsa(&Message::from_digest(hash), &secret);
+        let sig = ExternalSignature::new(signature.serialize_compact().to_vec(), public_key.to_bytes().to_vec());
+        
+        let compiled = finalize_psbt(&psbt, &[sig]).unwrap();
+        let tx: bitcoin::Transaction = bitcoin::consensus::deserialize(&compiled.raw_tx).unwrap();
+        let input = &tx.input[0];
+        
+        let mut expected_script_sig = vec![redeem_script.len() as u8];
+        expected_script_sig.extend_from_slice(redeem_script.as_bytes());
+        assert_eq!(input.script_sig.as_bytes(), expected_script_sig.as_slice());
+        assert_eq!(input.witness.len(), 2);
+        assert_eq!(input.witness.nth(0).unwrap().last(), Some(&0x01));
+        assert_eq!(input.witness.nth(1).unwrap(), public_key.to_bytes().as_slice());
+    }
+    
+    #[test]
+    fn test_finalize_psbt_taproot_key_path() {
+        use bitcoin::key::{Keypair, TapTweak};
+        use bitcoin::secp256k1::{Message, Secp256k1};
+        use bitcoin::ScriptBuf;
+        
+        let secp = Secp256k1::new();
+        let keypair = Keypair::from_seckey_slice(&secp, &[0x22; 32]).unwrap();
+        let (internal_key, _) = keypair.x_only_public_key();
+        let psbt = single_input_psbt(ScriptBuf::new_p2tr(&secp, internal_key, None), None);
+        
+        let hash = crate::signing::preimage::bitcoin::sighashes_from_psbt(&psbt).unwrap()[0].hash;
+        let tweaked = keypair.tap_tweak(&secp, None).to_keypair();
+        let signature = secp.sign_schnorr_no_aux_rand(&Message::from_digest(hash), &tweaked);
+        let sig = ExternalSignature::new(signature.serialize().to_vec(), Vec::new());
+        
+        let compiled = finalize_psbt(&psbt, &[sig]).unwrap();
+        let tx: bitcoin::Transaction = bitcoin::consensus::deserialize(&compiled.raw_tx).unwrap();
+        let input = &tx.input[0];
+        
+        assert!(input.script_sig.is_empty());
+        assert_eq!(input.witness.len(), 1);
+        assert_eq!(input.witness.nth(0).unwrap(), &signature.serialize()[..]);
+        
+        // A signature from the untweaked internal key must not verify
+        let untweaked = secp.sign_schnorr_no_aux_rand(&Message::from_digest(hash), &keypair);
+        let sig = ExternalSignature::new(untweaked.serialize().to_vec(), Vec::new());
+        assert!(matches!(finalize_psbt(&psbt, &[sig]), Err(PreImageError::InvalidSignature(_))));
+    }
+    
+    #[test]
+    fn test_compile_near_transfer() {
+        use crate::signing::preimage::near::{get_near_signing_hash, serialize_near_transaction, NearAction};
+        use ed25519_dalek::{Signer, SigningKey};
+        
+        let key = SigningKey::from_bytes(&[0x11; 32]);
+        let tx = UnsignedNearTransaction {
+            signer_id: "alice.near".to_string(),
+            public_key: key.verifying_key().to_bytes(),
+            nonce: 1,
+            receiver_id: "bob.near".to_string(),
+            block_hash: [0xcd; 32],
+            actions: vec![NearAction::Transfer { deposit: "1000000000000000000000000".to_string() }],
+        };
+        
+        let hash = get_near_signing_hash(&tx).unwrap().hash;
+        let signature = key.sign(&hash).to_bytes();
+        let sig = ExternalSignature::new(signature.to_vec(), tx.public_key.to_vec());
+        
+        let compiled = compile_near_transaction(&tx, &sig).unwrap();
+        let unsigned = serialize_near_transaction(&tx).unwrap();
+        assert_eq!(compiled.raw_tx[..unsigned.len()], unsigned[..]);
+        assert_eq!(compiled.raw_tx[unsigned.len()], 0x00);
+        assert_eq!(compiled.raw_tx[unsigned.len() + 1..], signature[..]);
+        assert_eq!(compiled.tx_hash, hash);
+        
+        // Signature over something else
+        let wrong = ExternalSignature::new(key.sign(&[0u8; 32]).to_bytes().to_vec(), Vec::new());
+        assert!(matches!(compile_near_transaction(&tx, &wrong), Err(PreImageError::InvalidSignature(_))));
+        
+        let other_key = ExternalSignature::new(signature.to_vec(), vec![0x22; 32]);
+        assert!(matches!(compile_near_transaction(&tx, &other_key), Err(PreImageError::PublicKeyMismatch)));
+    }
+    
+    #[test]
+    fn test_compile_cosmos_verifies_signature() {
+        use crate::signing::preimage::cosmos::{
+            CosmosCoin, CosmosFee, CosmosMessage, CosmosSignMode, CosmosSignerInfo,
+        };
+        use crate::signing::preimage::get_cosmos_sign_doc_hash;
+        use bitcoin::secp256k1::{Message, Secp256k1};
+        
+        let (secret, public_key) = test_key(0x31);
+        let tx = UnsignedCosmosTransaction {
+            chain_id: "cosmoshub-4".to_string(),
+            messages: vec![CosmosMessage {
+                type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                value: vec![1, 2, 3, 4],
+                json_value: None,
+            }],
+            fee: CosmosFee {
+                amount: vec![CosmosCoin { denom: "uatom".to_string(), amount: "5000".to_string() }],
+                gas: 200_000,
+                payer: None,
+                granter: None,
+            },
+            memo: String::new(),
+            timeout_height: 0,
+            sign_mode: CosmosSignMode::Direct,
+            signer: CosmosSignerInfo {
+                address: "cosmos1signer".to_string(),
+                account_number: 12345,
+                sequence: 42,
+                public_key: Some(public_key.serialize().to_vec()),
+                derivation_path: None,
+            },
+        };
+        
+        let secp = Secp256k1::new();
+        let sign = |digest: [u8; 32]| {
+            secp.sign_ecdsa(&Message::from_digest(digest), &secret).serialize_compact().to_vec()
+        };
+        let signature = sign(get_cosmos_sign_doc_hash(&tx).unwrap().hash);
+        
+        let compiled = compile_cosmos_transaction(&tx, &ExternalSignature::new(signature.clone(), Vec::new())).unwrap();
+        assert!(compiled.raw_tx.ends_with(&signature));
+        
+        // Signature from a key other than the signer's
+        let (_, other_public_key) = test_key(0x32);
+        let other_key = ExternalSignature::new(signature, other_public_key.serialize().to_vec());
+        assert!(matches!(compile_cosmos_transaction(&tx, &other_key), Err(PreImageError::PublicKeyMismatch)));
+        
+        // Signer's key, but over something other than the sign doc
+        let wrong = ExternalSignature::new(sign([0u8; 32]), public_key.serialize().to_vec());
+        assert!(matches!(compile_cosmos_transaction(&tx, &wrong), Err(PreImageError::InvalidSignature(_))));
+    }
+    
+    #[test]
+    fn test_compile_solana_verifies_signature() {
+        use crate::signing::preimage::solana::{
+            SolanaAccountMeta, SolanaInstruction, SolanaSignerInfo, SolanaVersion,
+        };
+        use ed25519_dalek::{Signer, SigningKey};
+        
+        let key = SigningKey::from_bytes(&[0x41; 32]);
+        let fee_payer = key.verifying_key().to_bytes();
+        let tx = UnsignedSolanaTransaction {
+            version: SolanaVersion::Legacy,
+            recent_blockhash: [0xab; 32],
+            fee_payer,
+            instructions: vec![SolanaInstruction {
+                program_id: [0u8; 32],
+                accounts: vec![
+                    SolanaAccountMeta { pubkey: fee_payer, is_signer: true, is_writable: true },
+                    SolanaAccountMeta { pubkey: [0x03; 32], is_signer: false, is_writable: true },
+                ],
+                data: vec![0x02, 0x00, 0x00, 0x00],
+            }],
+            address_lookup_tables: None,
+            signers: vec![SolanaSignerInfo { pubkey: fee_payer, derivation_path: None }],
+        };
+        
+        let message = serialize_solana_message(&tx).unwrap();
+        let signature = key.sign(&message).to_bytes();
+        let sig = ExternalSignature::new(signature.to_vec(), fee_payer.to_vec());
+        
+        let compiled = compile_solana_transaction(&tx, &[sig]).unwrap();
+        assert_eq!(compiled.raw_tx[0], 1);
+        assert_eq!(compiled.raw_tx[1..65], signature[..]);
+        assert_eq!(compiled.raw_tx[65..], message[..]);
+        assert_eq!(compiled.signature, signature);
+        
+        let other_key = ExternalSignature::new(signature.to_vec(), vec![0x22; 32]);
+        assert!(matches!(compile_solana_transaction(&tx, &[other_key]), Err(PreImageError::PublicKeyMismatch)));
+        
+        // Signature over something else
+        let wrong = ExternalSignature::new(key.sign(&[0u8; 32]).to_bytes().to_vec(), Vec::new());
+        assert!(matches!(compile_solana_transaction(&tx, &[wrong]), Err(PreImageError::InvalidSignature(_))));
+    }
 }
@@ -10,9 +10,12 @@
 //! - Ethereum (Legacy, EIP-2930, EIP-1559, EIP-7702)
 //! - Cosmos (Amino, Protobuf/Direct)
 //! - Solana (Legacy, Versioned)
+//! - NEAR (Transfer, FunctionCall)
 
 pub mod preimage;
 pub mod compiler;
+#[cfg(test)]
+mod test_fixtures;
 
 pub use preimage::*;
 pub use compiler::*;
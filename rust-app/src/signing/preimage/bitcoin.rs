@@ -5,6 +5,9 @@
 
 use super::{PreImageHash, PreImageError, PreImageResult, SigningAlgorithm};
 use bitcoin::hashes::{sha256d, Hash};
+use bitcoin::psbt::{self, Psbt};
+use bitcoin::sighash::{EcdsaSighashType, Prevouts, SighashCache, TapSighashType};
+use bitcoin::{Script, TxOut};
 use serde::{Deserialize, Serialize};
 
 /// Bitcoin sighash types
@@ -56,7 +59,8 @@ pub struct BitcoinInput {
     pub txid: [u8; 32],
     /// Output index in previous transaction
     pub vout: u32,
-    /// Script code for signing (scriptPubKey or redeemScript)
+    /// Script code for signing: the scriptPubKey for P2PKH, P2WPKH and P2TR,
+    /// the redeem script for P2SH and P2SH-P2WPKH, the witness script for P2WSH
     pub script_code: Vec<u8>,
     /// Value in satoshis (required for SegWit)
     pub value: u64,
@@ -68,6 +72,35 @@ pub struct BitcoinInput {
     pub input_type: BitcoinInputType,
 }
 
+impl BitcoinInput {
+    /// scriptPubKey of the output this input spends
+    pub fn script_pubkey(&self) -> Vec<u8> {
+        use bitcoin::hashes::{hash160, sha256};
+        
+        match self.input_type {
+            BitcoinInputType::P2SH | BitcoinInputType::P2SH_P2WPKH => {
+                let hash = hash160::Hash::hash(&self.script_code);
+                [&[0xa9, 0x14][..], &hash.as_byte_array()[..], &[0x87][..]].concat()
+            }
+            BitcoinInputType::P2WSH => {
+                let hash = sha256::Hash::hash(&self.script_code);
+                [&[0x00, 0x20][..], &hash.as_byte_array()[..]].concat()
+            }
+            _ => self.script_code.clone(),
+        }
+    }
+    
+    /// BIP-143 scriptCode; a P2WPKH program `0x0014 <hash>` signs as
+    /// `OP_DUP OP_HASH160 <hash> OP_EQUALVERIFY OP_CHECKSIG`
+    fn segwit_script_code(&self) -> Vec<u8> {
+        if Script::from_bytes(&self.script_code).is_p2wpkh() {
+            [&[0x76, 0xa9, 0x14][..], &self.script_code[2..], &[0x88, 0xac][..]].concat()
+        } else {
+            self.script_code.clone()
+        }
+    }
+}
+
 /// Type of Bitcoin input
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(non_camel_case_types)]  // Bitcoin naming conventions (P2SH-P2WPKH, P2TR, etc.)
@@ -121,6 +154,10 @@ pub struct UnsignedBitcoinTransaction {
 }
 
 /// Get sighashes for all inputs in a Bitcoin transaction
+/// 
+/// Each input is hashed by its own type, so one transaction can mix legacy,
+/// SegWit and Taproot inputs. `TaprootDefault` has no ECDSA encoding and
+/// means ALL for the non-Taproot inputs.
 pub fn get_bitcoin_sighashes(
     tx: &UnsignedBitcoinTransaction,
     sighash_type: BitcoinSigHashType,
@@ -128,6 +165,10 @@ pub fn get_bitcoin_sighashes(
     let mut hashes = Vec::with_capacity(tx.inputs.len());
     
     for (index, input) in tx.inputs.iter().enumerate() {
+        let sighash_type = match sighash_type {
+            BitcoinSigHashType::TaprootDefault if !input.input_type.is_taproot() => BitcoinSigHashType::All,
+            other => other,
+        };
         let hash = if input.input_type.is_taproot() {
             get_taproot_sighash(tx, index, sighash_type)?
         } else if input.input_type.is_segwit() {
@@ -160,6 +201,147 @@ pub fn get_bitcoin_sighashes(
     Ok(hashes)
 }
 
+/// Get sighashes for all inputs of a BIP-174 PSBT
+/// 
+/// Each input's script type is taken from its witness or non-witness UTXO
+/// (plus redeem/witness script where needed) and its sighash type from
+/// `PSBT_IN_SIGHASH_TYPE`, defaulting to ALL (or DEFAULT for Taproot).
+pub fn sighashes_from_psbt(psbt_bytes: &[u8]) -> PreImageResult<Vec<PreImageHash>> {
+    let psbt = Psbt::deserialize(psbt_bytes)
+        .map_err(|e| PreImageError::InvalidTransaction(format!("Invalid PSBT: {}", e)))?;
+    let prevouts = psbt_prevouts(&psbt)?;
+    let mut cache = SighashCache::new(&psbt.unsigned_tx);
+    let mut hashes = Vec::with_capacity(psbt.inputs.len());
+    
+    for (index, input) in psbt.inputs.iter().enumerate() {
+        let prevout = &prevouts[index];
+        let input_type = psbt_input_type(input, &prevout.script_pubkey)?;
+        let encoding_err = |e: &dyn std::fmt::Display| PreImageError::EncodingError(e.to_string());
+        
+        let hash = if input_type.is_taproot() {
+            let sighash_type = match input.sighash_type {
+                Some(t) => t.taproot_hash_ty().map_err(|e| encoding_err(&e))?,
+                None => TapSighashType::Default,
+            };
+            cache
+                .taproot_key_spend_signature_hash(index, &Prevouts::All(&prevouts), sighash_type)
+                .map_err(|e| encoding_err(&e))?
+                .to_byte_array()
+        } else {
+            let sighash_type = match input.sighash_type {
+                Some(t) => t.ecdsa_hash_ty().map_err(|e| encoding_err(&e))?,
+                None => EcdsaSighashType::All,
+            };
+            let redeem_script = || {
+                input.redeem_script.as_ref()
+                    .ok_or_else(|| PreImageError::MissingField(format!("redeem_script for input {}", index)))
+            };
+            
+            match input_type {
+                BitcoinInputType::P2WPKH => cache
+                    .p2wpkh_signature_hash(index, &prevout.script_pubkey, prevout.value, sighash_type)
+                    .map_err(|e| encoding_err(&e))?
+                    .to_byte_array(),
+                BitcoinInputType::P2SH_P2WPKH => cache
+                    .p2wpkh_signature_hash(index, redeem_script()?, prevout.value, sighash_type)
+                    .map_err(|e| encoding_err(&e))?
+                    .to_byte_array(),
+                BitcoinInputType::P2WSH => {
+                    let witness_script = input.witness_script.as_ref()
+                        .ok_or_else(|| PreImageError::MissingField(format!("witness_script for input {}", index)))?;
+                    cache
+                        .p2wsh_signature_hash(index, witness_script, prevout.value, sighash_type)
+                        .map_err(|e| encoding_err(&e))?
+                        .to_byte_array()
+                }
+                BitcoinInputType::P2SH => cache
+                    .legacy_signature_hash(index, redeem_script()?, sighash_type.to_u32())
+                    .map_err(|e| encoding_err(&e))?
+                    .to_byte_array(),
+                _ => cache
+                    .legacy_signature_hash(index, &prevout.script_pubkey, sighash_type.to_u32())
+                    .map_err(|e| encoding_err(&e))?
+                    .to_byte_array(),
+            }
+        };
+        
+        let algorithm = if input_type.is_taproot() {
+            SigningAlgorithm::Secp256k1Schnorr
+        } else {
+            SigningAlgorithm::Secp256k1Ecdsa
+        };
+        
+        let signer_id = psbt_input_key_path(input)
+            .unwrap_or_else(|| format!("input_{}", index));
+        
+        hashes.push(PreImageHash::new(hash, signer_id, algorithm)
+            .with_input_index(index)
+            .with_description(format!(
+                "Bitcoin {:?} input {} ({} sats)",
+                input_type,
+                index,
+                prevout.value.to_sat()
+            )));
+    }
+    
+    Ok(hashes)
+}
+
+/// Spent outputs for every PSBT input, from its witness or non-witness UTXO
+pub(crate) fn psbt_prevouts(psbt: &Psbt) -> PreImageResult<Vec<TxOut>> {
+    psbt.inputs.iter().zip(&psbt.unsigned_tx.input).enumerate().map(|(index, (input, txin))| {
+        if let Some(utxo) = &input.witness_utxo {
+            return Ok(utxo.clone());
+        }
+        
+        let prev_tx = input.non_witness_utxo.as_ref()
+            .ok_or_else(|| PreImageError::MissingField(format!("utxo for input {}", index)))?;
+        if prev_tx.compute_txid() != txin.previous_output.txid {
+            return Err(PreImageError::InvalidTransaction(format!(
+                "non_witness_utxo for input {} does not match its outpoint",
+                index
+            )));
+        }
+        prev_tx.output.get(txin.previous_output.vout as usize)
+            .cloned()
+            .ok_or(PreImageError::InvalidInputIndex(index))
+    }).collect()
+}
+
+/// Classify a PSBT input by the script it spends
+pub(crate) fn psbt_input_type(input: &psbt::Input, script_pubkey: &Script) -> PreImageResult<BitcoinInputType> {
+    if script_pubkey.is_p2wpkh() {
+        Ok(BitcoinInputType::P2WPKH)
+    } else if script_pubkey.is_p2wsh() {
+        Ok(BitcoinInputType::P2WSH)
+    } else if script_pubkey.is_p2tr() {
+        Ok(BitcoinInputType::P2TR_KeyPath)
+    } else if script_pubkey.is_p2pkh() {
+        Ok(BitcoinInputType::P2PKH)
+    } else if script_pubkey.is_p2sh() {
+        match &input.redeem_script {
+            Some(redeem) if redeem.is_p2wpkh() => Ok(BitcoinInputType::P2SH_P2WPKH),
+            Some(_) => Ok(BitcoinInputType::P2SH),
+            None => Err(PreImageError::MissingField("redeem_script".to_string())),
+        }
+    } else {
+        Err(PreImageError::UnsupportedType(format!("script {}", script_pubkey.to_hex_string())))
+    }
+}
+
+/// First BIP-32 path recorded for an input's key, as "m/84'/0'/0'/0/0"
+fn psbt_input_key_path(input: &psbt::Input) -> Option<String> {
+    let path = input.bip32_derivation.values().map(|(_, path)| path)
+        .chain(input.tap_key_origins.values().map(|(_, (_, path))| path))
+        .next()?;
+    
+    let mut formatted = String::from("m");
+    for child in path {
+        formatted.push_str(&format!("/{}", child));
+    }
+    Some(formatted)
+}
+
 /// Calculate legacy (pre-SegWit) sighash
 fn get_legacy_sighash(
     tx: &UnsignedBitcoinTransaction,
@@ -300,14 +482,15 @@ fn get_segwit_sighash(
     serialized.extend_from_slice(&input.vout.to_le_bytes());
     
     // 5. scriptCode
-    let script_len = input.script_code.len();
+    let script_code = input.segwit_script_code();
+    let script_len = script_code.len();
     if script_len < 0xfd {
         serialized.push(script_len as u8);
     } else {
         serialized.push(0xfd);
         serialized.extend_from_slice(&(script_len as u16).to_le_bytes());
     }
-    serialized.extend_from_slice(&input.script_code);
+    serialized.extend_from_slice(&script_code);
     
     // 6. value
     serialized.extend_from_slice(&input.value.to_le_bytes());
@@ -422,12 +605,13 @@ fn get_taproot_sighash(
         serialized.extend_from_slice(&sha_amounts);
     }
     
-    // sha_scriptpubkeys
+    // sha_scriptpubkeys (of every input, whatever its type)
     if !anyone_can_pay {
         let mut scripts = Vec::new();
         for inp in &tx.inputs {
-            scripts.push(inp.script_code.len() as u8);
-            scripts.extend_from_slice(&inp.script_code);
+            let script_pubkey = inp.script_pubkey();
+            scripts.push(script_pubkey.len() as u8);
+            scripts.extend_from_slice(&script_pubkey);
         }
         let sha_scripts = sha256::Hash::hash(&scripts).to_byte_array();
         serialized.extend_from_slice(&sha_scripts);
@@ -469,8 +653,9 @@ fn get_taproot_sighash(
         serialized.extend_from_slice(&txid);
         serialized.extend_from_slice(&input.vout.to_le_bytes());
         serialized.extend_from_slice(&input.value.to_le_bytes());
-        serialized.push(input.script_code.len() as u8);
-        serialized.extend_from_slice(&input.script_code);
+        let script_pubkey = input.script_pubkey();
+        serialized.push(script_pubkey.len() as u8);
+        serialized.extend_from_slice(&script_pubkey);
         serialized.extend_from_slice(&input.sequence.to_le_bytes());
     }
     
@@ -491,6 +676,7 @@ fn get_taproot_sighash(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::signing::test_fixtures::p2wpkh_psbt;
     
     fn sample_input() -> BitcoinInput {
         BitcoinInput {
@@ -593,6 +779,53 @@ mod tests {
         assert!(result.is_err());
     }
     
+    #[test]
+    fn test_sighashes_from_psbt_p2wpkh() {
+        let hashes = sighashes_from_psbt(&p2wpkh_psbt()).unwrap();
+        
+        assert_eq!(hashes.len(), 1);
+        assert_eq!(hashes[0].input_index, Some(0));
+        assert_eq!(hashes[0].algorithm, SigningAlgorithm::Secp256k1Ecdsa);
+        assert_eq!(hashes[0].signer_id, "m/84'/0'/0'/0/0");
+        assert_eq!(
+            hex::encode(hashes[0].hash),
+            "d277020e3ca3333427f6ac121028366160196b8668a016dfa1200e2fc52f0bff"
+        );
+        assert!(hashes[0].description.contains("P2WPKH"));
+    }
+    
+    #[test]
+    fn test_sighashes_from_psbt_bip143_p2sh_p2wpkh() {
+        use bitcoin::consensus::deserialize;
+        use bitcoin::{Amount, ScriptBuf, Transaction};
+
+        // BIP-143 "P2SH-P2WPKH" example wrapped in a PSBT
+        let unsigned_tx: Transaction = deserialize(&hex::decode(
+            "0100000001db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a54770100000000feffffff02b8b4eb0b000000001976a914a457b684d7f0d539a46a45bbc043f35b59d0d96388ac0008af2f000000001976a914fd270b1ee6abcaea97fea7ad0402e8bd8ad6d77c88ac92040000"
+        ).unwrap()).unwrap();
+        let redeem_script = ScriptBuf::from_bytes(hex::decode("001479091972186c449eb1ded22b78e40d009bdf0089").unwrap());
+
+        let mut psbt = Psbt::from_unsigned_tx(unsigned_tx).unwrap();
+        psbt.inputs[0].witness_utxo = Some(TxOut {
+            value: Amount::from_sat(1_000_000_000),
+            script_pubkey: ScriptBuf::new_p2sh(&redeem_script.script_hash()),
+        });
+        psbt.inputs[0].redeem_script = Some(redeem_script);
+
+        let hashes = sighashes_from_psbt(&psbt.serialize()).unwrap();
+        assert_eq!(hashes.len(), 1);
+        assert_eq!(
+            hex::encode(hashes[0].hash),
+            "64f3b0f4dd2bb3aa1ce8566d220cc74dda9df97d8490cc81d89d735c92e59fb6"
+        );
+        assert!(hashes[0].description.contains("P2SH_P2WPKH"));
+    }
+
+    #[test]
+    fn test_sighashes_from_psbt_rejects_garbage() {
+        assert!(sighashes_from_psbt(b"not a psbt").is_err());
+    }
+    
     #[test]
     fn test_sighash_type_parsing() {
         assert_eq!(BitcoinSigHashType::from_byte(0x01), Some(BitcoinSigHashType::All));
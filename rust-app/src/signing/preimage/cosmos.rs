@@ -157,55 +157,103 @@ fn get_amino_sign_doc_hash(tx: &UnsignedCosmosTransaction) -> PreImageResult<[u8
     Ok(hash)
 }
 
-/// Generate Direct (Protobuf) sign doc hash
-fn get_direct_sign_doc_hash(tx: &UnsignedCosmosTransaction) -> PreImageResult<[u8; 32]> {
-    // Build SignDoc protobuf
-    // SignDoc = { body_bytes, auth_info_bytes, chain_id, account_number }
-    
-    // Build TxBody
+/// Protobuf-encoded SIGN_MODE_DIRECT sign document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectSignDoc {
+    /// Encoded `cosmos.tx.v1beta1.TxBody`
+    pub body_bytes: Vec<u8>,
+    /// Encoded `cosmos.tx.v1beta1.AuthInfo`
+    pub auth_info_bytes: Vec<u8>,
+    /// Encoded `cosmos.tx.v1beta1.SignDoc`
+    pub sign_doc_bytes: Vec<u8>,
+    /// SHA-256 of `sign_doc_bytes`; this is what gets signed
+    pub hash: [u8; 32],
+}
+
+/// Build the SIGN_MODE_DIRECT `SignDoc` for a transaction
+///
+/// `SignDoc = { body_bytes, auth_info_bytes, chain_id, account_number }`.
+/// Default (zero/empty) scalar fields are omitted, matching the canonical
+/// protobuf encoding produced by the Cosmos SDK and cosmjs.
+pub fn build_direct_sign_doc(tx: &UnsignedCosmosTransaction) -> PreImageResult<DirectSignDoc> {
     let body_bytes = encode_tx_body(tx)?;
-    
-    // Build AuthInfo
     let auth_info_bytes = encode_auth_info(tx)?;
     
-    // Encode SignDoc
     let mut sign_doc_bytes = Vec::new();
     
     // Field 1: body_bytes (bytes)
-    if !body_bytes.is_empty() {
-        sign_doc_bytes.push(0x0a); // field 1, wire type 2 (length-delimited)
-        encode_varint(body_bytes.len() as u64, &mut sign_doc_bytes);
-        sign_doc_bytes.extend_from_slice(&body_bytes);
-    }
+    encode_bytes_field(1, &body_bytes, &mut sign_doc_bytes);
     
     // Field 2: auth_info_bytes (bytes)
-    if !auth_info_bytes.is_empty() {
-        sign_doc_bytes.push(0x12); // field 2, wire type 2
-        encode_varint(auth_info_bytes.len() as u64, &mut sign_doc_bytes);
-        sign_doc_bytes.extend_from_slice(&auth_info_bytes);
-    }
+    encode_bytes_field(2, &auth_info_bytes, &mut sign_doc_bytes);
     
     // Field 3: chain_id (string)
-    if !tx.chain_id.is_empty() {
-        sign_doc_bytes.push(0x1a); // field 3, wire type 2
-        encode_varint(tx.chain_id.len() as u64, &mut sign_doc_bytes);
-        sign_doc_bytes.extend_from_slice(tx.chain_id.as_bytes());
-    }
+    encode_bytes_field(3, tx.chain_id.as_bytes(), &mut sign_doc_bytes);
     
     // Field 4: account_number (uint64)
-    if tx.signer.account_number > 0 {
-        sign_doc_bytes.push(0x20); // field 4, wire type 0 (varint)
-        encode_varint(tx.signer.account_number, &mut sign_doc_bytes);
-    }
+    encode_uint_field(4, tx.signer.account_number, &mut sign_doc_bytes);
     
-    // SHA256 hash
-    let mut hasher = Sha256::new();
-    hasher.update(&sign_doc_bytes);
-    let result = hasher.finalize();
+    let hash: [u8; 32] = Sha256::digest(&sign_doc_bytes).into();
     
-    let mut hash = [0u8; 32];
-    hash.copy_from_slice(&result);
-    Ok(hash)
+    Ok(DirectSignDoc {
+        body_bytes,
+        auth_info_bytes,
+        sign_doc_bytes,
+        hash,
+    })
+}
+
+/// Encode a `cosmos.tx.v1beta1.TxRaw` ready for broadcast
+pub fn encode_tx_raw(body_bytes: &[u8], auth_info_bytes: &[u8], signatures: &[Vec<u8>]) -> Vec<u8> {
+    let mut raw = Vec::new();
+    encode_bytes_field(1, body_bytes, &mut raw);
+    encode_bytes_field(2, auth_info_bytes, &mut raw);
+    for sig in signatures {
+        // Repeated bytes are always written, even when empty
+        raw.push(0x1a);
+        encode_varint(sig.len() as u64, &mut raw);
+        raw.extend_from_slice(sig);
+    }
+    raw
+}
+
+/// Encode a `cosmos.bank.v1beta1.MsgSend`
+pub fn encode_msg_send(from_address: &str, to_address: &str, amount: &[CosmosCoin]) -> Vec<u8> {
+    let mut msg = Vec::new();
+    encode_bytes_field(1, from_address.as_bytes(), &mut msg);
+    encode_bytes_field(2, to_address.as_bytes(), &mut msg);
+    for coin in amount {
+        encode_message_field(3, &encode_coin(coin), &mut msg);
+    }
+    msg
+}
+
+/// Encode an `ibc.applications.transfer.v1.MsgTransfer` with a timestamp-only timeout
+pub fn encode_msg_transfer(
+    source_port: &str,
+    source_channel: &str,
+    token: &CosmosCoin,
+    sender: &str,
+    receiver: &str,
+    timeout_timestamp: u64,
+    memo: &str,
+) -> Vec<u8> {
+    let mut msg = Vec::new();
+    encode_bytes_field(1, source_port.as_bytes(), &mut msg);
+    encode_bytes_field(2, source_channel.as_bytes(), &mut msg);
+    encode_message_field(3, &encode_coin(token), &mut msg);
+    encode_bytes_field(4, sender.as_bytes(), &mut msg);
+    encode_bytes_field(5, receiver.as_bytes(), &mut msg);
+    // Field 6: timeout_height is non-nullable and always present (empty = no height timeout)
+    encode_message_field(6, &[], &mut msg);
+    encode_uint_field(7, timeout_timestamp, &mut msg);
+    encode_bytes_field(8, memo.as_bytes(), &mut msg);
+    msg
+}
+
+/// Generate Direct (Protobuf) sign doc hash
+fn get_direct_sign_doc_hash(tx: &UnsignedCosmosTransaction) -> PreImageResult<[u8; 32]> {
+    Ok(build_direct_sign_doc(tx)?.hash)
 }
 
 /// Generate Textual sign doc hash (ADR-050)
@@ -326,8 +374,7 @@ fn encode_signer_info(tx: &UnsignedCosmosTransaction) -> PreImageResult<Vec<u8>>
     signer_info.extend_from_slice(&mode_info);
     
     // Field 3: sequence (uint64)
-    signer_info.push(0x18);
-    encode_varint(tx.signer.sequence, &mut signer_info);
+    encode_uint_field(3, tx.signer.sequence, &mut signer_info);
     
     Ok(signer_info)
 }
@@ -361,29 +408,53 @@ fn encode_fee(tx: &UnsignedCosmosTransaction) -> PreImageResult<Vec<u8>> {
     
     // Field 1: amount (repeated Coin)
     for coin in &tx.fee.amount {
-        fee.push(0x0a);
-        
-        let mut coin_proto = Vec::new();
-        // denom
-        coin_proto.push(0x0a);
-        encode_varint(coin.denom.len() as u64, &mut coin_proto);
-        coin_proto.extend_from_slice(coin.denom.as_bytes());
-        // amount
-        coin_proto.push(0x12);
-        encode_varint(coin.amount.len() as u64, &mut coin_proto);
-        coin_proto.extend_from_slice(coin.amount.as_bytes());
-        
-        encode_varint(coin_proto.len() as u64, &mut fee);
-        fee.extend_from_slice(&coin_proto);
+        encode_message_field(1, &encode_coin(coin), &mut fee);
     }
     
     // Field 2: gas_limit (uint64)
-    fee.push(0x10);
-    encode_varint(tx.fee.gas, &mut fee);
+    encode_uint_field(2, tx.fee.gas, &mut fee);
+    
+    // Field 3: payer (string), Field 4: granter (string)
+    if let Some(payer) = &tx.fee.payer {
+        encode_bytes_field(3, payer.as_bytes(), &mut fee);
+    }
+    if let Some(granter) = &tx.fee.granter {
+        encode_bytes_field(4, granter.as_bytes(), &mut fee);
+    }
     
     Ok(fee)
 }
 
+/// Encode Coin protobuf
+fn encode_coin(coin: &CosmosCoin) -> Vec<u8> {
+    let mut coin_proto = Vec::new();
+    encode_bytes_field(1, coin.denom.as_bytes(), &mut coin_proto);
+    encode_bytes_field(2, coin.amount.as_bytes(), &mut coin_proto);
+    coin_proto
+}
+
+/// Encode a length-delimited scalar field, omitted when empty (proto3 default)
+fn encode_bytes_field(field: u32, data: &[u8], buf: &mut Vec<u8>) {
+    if !data.is_empty() {
+        encode_message_field(field, data, buf);
+    }
+}
+
+/// Encode an embedded message field (always written, even when empty)
+fn encode_message_field(field: u32, data: &[u8], buf: &mut Vec<u8>) {
+    encode_varint(((field << 3) | 2) as u64, buf);
+    encode_varint(data.len() as u64, buf);
+    buf.extend_from_slice(data);
+}
+
+/// Encode a uint64 varint field, omitted when zero (proto3 default)
+fn encode_uint_field(field: u32, value: u64, buf: &mut Vec<u8>) {
+    if value != 0 {
+        encode_varint((field << 3) as u64, buf);
+        encode_varint(value, buf);
+    }
+}
+
 /// Encode varint (protobuf base 128 varint)
 fn encode_varint(mut value: u64, buf: &mut Vec<u8>) {
     loop {
@@ -568,6 +639,94 @@ mod tests {
         assert_eq!(buf, vec![0xac, 0x02]);
     }
     
+    /// `testVectors[0]` from cosmjs `packages/proto-signing/src/testutils.spec.ts`:
+    /// the faucet account sends 1234567ucosm with a 2000ucosm / 200000 gas fee,
+    /// signed at account 1, sequence 0 on "simd-testing". The expected hex is that
+    /// vector's `bodyBytes` and `signBytes`, as checked by `makeSignBytes` in
+    /// `signing.spec.ts`
+    fn reference_msg_send_tx() -> UnsignedCosmosTransaction {
+        let pubkey = hex::decode("034f04181eeba35391b858633a765c4a0c189697b40d216354d50890d350c70290").unwrap();
+        UnsignedCosmosTransaction {
+            chain_id: "simd-testing".to_string(),
+            messages: vec![CosmosMessage {
+                type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                value: encode_msg_send(
+                    "cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs6",
+                    "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu",
+                    &[CosmosCoin { denom: "ucosm".to_string(), amount: "1234567".to_string() }],
+                ),
+                json_value: None,
+            }],
+            fee: CosmosFee {
+                amount: vec![CosmosCoin { denom: "ucosm".to_string(), amount: "2000".to_string() }],
+                gas: 200000,
+                payer: None,
+                granter: None,
+            },
+            memo: String::new(),
+            timeout_height: 0,
+            sign_mode: CosmosSignMode::Direct,
+            signer: CosmosSignerInfo {
+                address: "cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs6".to_string(),
+                account_number: 1,
+                sequence: 0,
+                public_key: Some(pubkey),
+                derivation_path: None,
+            },
+        }
+    }
+    
+    #[test]
+    fn test_direct_sign_doc_matches_reference() {
+        let doc = build_direct_sign_doc(&reference_msg_send_tx()).unwrap();
+        
+        assert_eq!(
+            hex::encode(&doc.body_bytes),
+            "0a90010a1c2f636f736d6f732e62616e6b2e763162657461312e4d736753656e6412700a2d636f736d6f73\
+             31706b707472653766646b6c366766727a6c65736a6a766878686c63337234676d6d6b38727336122d636f\
+             736d6f7331717970717870713971637273737a673270767871367273307a716733797963356c7a76377875\
+             1a100a0575636f736d120731323334353637"
+        );
+        assert_eq!(
+            hex::encode(&doc.auth_info_bytes),
+            "0a4e0a460a1f2f636f736d6f732e63727970746f2e736563703235366b312e5075624b657912230a21034f\
+             04181eeba35391b858633a765c4a0c189697b40d216354d50890d350c7029012040a02080112130a0d0a05\
+             75636f736d12043230303010c09a0c"
+        );
+        // SignDoc wraps both, then chain_id = "simd-testing" and account_number = 1
+        let mut sign_doc = hex::decode("0a9301").unwrap();
+        sign_doc.extend_from_slice(&doc.body_bytes);
+        sign_doc.extend_from_slice(&hex::decode("1265").unwrap());
+        sign_doc.extend_from_slice(&doc.auth_info_bytes);
+        sign_doc.extend_from_slice(&hex::decode("1a0c73696d642d74657374696e672001").unwrap());
+        assert_eq!(doc.sign_doc_bytes, sign_doc);
+        assert_eq!(
+            hex::encode(doc.hash),
+            "5d5967f13a4ff2045594ece00ad7bef5c61b149a559b15fd948af00fb93b31d3"
+        );
+        assert_eq!(get_cosmos_sign_doc_hash(&reference_msg_send_tx()).unwrap().hash, doc.hash);
+    }
+    
+    #[test]
+    fn test_fee_payer_and_granter_encoded() {
+        let mut tx = reference_msg_send_tx();
+        let without = build_direct_sign_doc(&tx).unwrap();
+        
+        tx.fee.granter = Some("cosmos1granter".to_string());
+        let with = build_direct_sign_doc(&tx).unwrap();
+        
+        assert_ne!(without.auth_info_bytes, with.auth_info_bytes);
+        assert_eq!(without.body_bytes, with.body_bytes);
+    }
+    
+    #[test]
+    fn test_tx_raw_layout() {
+        let raw = encode_tx_raw(&[0x01], &[0x02], &[vec![0x03; 64]]);
+        assert_eq!(&raw[..6], &[0x0a, 0x01, 0x01, 0x12, 0x01, 0x02]);
+        assert_eq!(&raw[6..8], &[0x1a, 64]);
+        assert_eq!(raw.len(), 8 + 64);
+    }
+    
     #[test]
     fn test_multiple_messages() {
         let mut tx = sample_cosmos_tx();
@@ -135,8 +135,8 @@ fn get_legacy_hash(tx: &UnsignedEthereumTransaction) -> PreImageResult<[u8; 32]>
 fn get_eip2930_hash(tx: &UnsignedEthereumTransaction) -> PreImageResult<[u8; 32]> {
     let gas_price = tx.gas_price
         .ok_or_else(|| PreImageError::MissingField("gas_price".to_string()))?;
-    let access_list = tx.access_list.as_ref()
-        .ok_or_else(|| PreImageError::MissingField("access_list".to_string()))?;
+    // An empty access list is valid for type 0x01
+    let access_list = tx.access_list.as_ref().cloned().unwrap_or_default();
     
     // RLP encode: 0x01 || RLP([chainId, nonce, gasPrice, gasLimit, to, value, data, accessList])
     let mut items = Vec::new();
@@ -148,7 +148,7 @@ fn get_eip2930_hash(tx: &UnsignedEthereumTransaction) -> PreImageResult<[u8; 32]
     items.push(rlp_encode_address(tx.to));
     items.push(rlp_encode_u128(tx.value));
     items.push(rlp_encode_bytes(&tx.data));
-    items.push(rlp_encode_access_list(access_list));
+    items.push(rlp_encode_access_list(&access_list));
     
     let rlp_data = rlp_encode_list(&items);
     
@@ -420,6 +420,52 @@ mod tests {
         assert!(result.is_ok());
     }
     
+    #[test]
+    fn test_eip2930_reference_hash() {
+        let mut key_3 = [0u8; 32];
+        key_3[31] = 3;
+        let mut key_7 = [0u8; 32];
+        key_7[31] = 7;
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hex::decode("de0b295669a9fd93d5f28d9ec85e40f4cb697bae").unwrap());
+        
+        let tx = UnsignedEthereumTransaction {
+            tx_type: EthereumTxType::AccessList,
+            chain_id: 1,
+            nonce: 9,
+            gas_price: Some(20_000_000_000),
+            max_priority_fee_per_gas: None,
+            max_fee_per_gas: None,
+            gas_limit: 30000,
+            to: Some([0x35; 20]),
+            value: 1_000_000_000_000_000_000,
+            data: vec![],
+            access_list: Some(vec![AccessListEntry {
+                address,
+                storage_keys: vec![key_3, key_7],
+            }]),
+            authorization_list: None,
+            derivation_path: None,
+        };
+        
+        let hash = get_ethereum_signing_hash(&tx).unwrap();
+        assert_eq!(
+            hex::encode(hash.hash),
+            "01a2edaf0965be86f04c30baa9dfbf917a80a9847d81780e184ff03eb2b296b7"
+        );
+    }
+    
+    #[test]
+    fn test_eip2930_empty_access_list() {
+        let mut tx = sample_legacy_tx();
+        tx.tx_type = EthereumTxType::AccessList;
+        
+        let missing = get_ethereum_signing_hash(&tx).unwrap();
+        tx.access_list = Some(vec![]);
+        let empty = get_ethereum_signing_hash(&tx).unwrap();
+        assert_eq!(missing.hash, empty.hash);
+    }
+    
     #[test]
     fn test_eip7702_hash() {
         let mut tx = sample_eip1559_tx();
@@ -6,6 +6,7 @@ pub mod bitcoin;
 pub mod ethereum;
 pub mod cosmos;
 pub mod solana;
+pub mod near;
 
 use serde::{Deserialize, Serialize};
 
@@ -149,8 +150,10 @@ pub type PreImageResult<T> = Result<T, PreImageError>;
 // Re-export chain-specific functions
 pub use bitcoin::{
     get_bitcoin_sighashes,
+    sighashes_from_psbt,
     BitcoinSigHashType,
 };
 pub use ethereum::get_ethereum_signing_hash;
-pub use cosmos::get_cosmos_sign_doc_hash;
+pub use cosmos::{get_cosmos_sign_doc_hash, build_direct_sign_doc, DirectSignDoc};
 pub use solana::get_solana_message_hash;
+pub use near::get_near_signing_hash;
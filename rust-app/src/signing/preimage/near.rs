@@ -0,0 +1,268 @@
+//! NEAR Pre-Image Hashing
+//!
+//! Borsh-serializes NEAR transactions and generates the SHA-256 hash that the
+//! ed25519 key signs.
+
+use super::{PreImageHash, PreImageError, PreImageResult, SigningAlgorithm};
+use serde::{Deserialize, Serialize};
+use sha2::{Sha256, Digest};
+
+/// Key type tag of an ed25519 `PublicKey` / `Signature`
+pub const NEAR_ED25519_KEY_TYPE: u8 = 0;
+
+/// NEAR transaction action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NearAction {
+    /// Call a contract method
+    FunctionCall {
+        method_name: String,
+        /// Method arguments (usually JSON bytes)
+        args: Vec<u8>,
+        /// Attached gas
+        gas: u64,
+        /// Attached deposit in yoctoNEAR (decimal string)
+        deposit: String,
+    },
+    /// Send NEAR
+    Transfer {
+        /// Amount in yoctoNEAR (decimal string)
+        deposit: String,
+    },
+}
+
+impl NearAction {
+    /// Borsh enum index of the action
+    fn index(&self) -> u8 {
+        match self {
+            NearAction::FunctionCall { .. } => 2,
+            NearAction::Transfer { .. } => 3,
+        }
+    }
+}
+
+/// Unsigned NEAR transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsignedNearTransaction {
+    /// Account ID of the signer (e.g. `alice.near` or an implicit hex account)
+    pub signer_id: String,
+    /// Ed25519 public key of the access key used to sign, as `ed25519:<base58>`
+    #[serde(with = "near_public_key")]
+    pub public_key: [u8; 32],
+    /// Access key nonce (current nonce plus one)
+    pub nonce: u64,
+    /// Account ID receiving the actions
+    pub receiver_id: String,
+    /// Recent block hash, base58 as returned by the RPC
+    #[serde(with = "crate::serde_bytes::base58_32")]
+    pub block_hash: [u8; 32],
+    /// Actions, executed in order
+    pub actions: Vec<NearAction>,
+}
+
+/// Get the signing hash for a NEAR transaction
+///
+/// This is SHA-256 of the Borsh-serialized transaction, which is also the
+/// transaction hash.
+pub fn get_near_signing_hash(tx: &UnsignedNearTransaction) -> PreImageResult<PreImageHash> {
+    let serialized = serialize_near_transaction(tx)?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(&serialized);
+    let result = hasher.finalize();
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&result);
+
+    let description = format!(
+        "NEAR tx: {} -> {}, {} action(s), nonce {}",
+        tx.signer_id,
+        tx.receiver_id,
+        tx.actions.len(),
+        tx.nonce
+    );
+
+    Ok(PreImageHash::new(hash, tx.signer_id.clone(), SigningAlgorithm::Ed25519)
+        .with_description(description))
+}
+
+/// Borsh-serialize a NEAR transaction
+pub fn serialize_near_transaction(tx: &UnsignedNearTransaction) -> PreImageResult<Vec<u8>> {
+    if tx.actions.is_empty() {
+        return Err(PreImageError::MissingField("actions".to_string()));
+    }
+
+    let mut out = Vec::new();
+    write_string(&tx.signer_id, &mut out);
+    out.push(NEAR_ED25519_KEY_TYPE);
+    out.extend_from_slice(&tx.public_key);
+    out.extend_from_slice(&tx.nonce.to_le_bytes());
+    write_string(&tx.receiver_id, &mut out);
+    out.extend_from_slice(&tx.block_hash);
+
+    out.extend_from_slice(&(tx.actions.len() as u32).to_le_bytes());
+    for action in &tx.actions {
+        out.push(action.index());
+        match action {
+            NearAction::FunctionCall { method_name, args, gas, deposit } => {
+                write_string(method_name, &mut out);
+                out.extend_from_slice(&(args.len() as u32).to_le_bytes());
+                out.extend_from_slice(args);
+                out.extend_from_slice(&gas.to_le_bytes());
+                out.extend_from_slice(&parse_yocto(deposit)?.to_le_bytes());
+            }
+            NearAction::Transfer { deposit } => {
+                out.extend_from_slice(&parse_yocto(deposit)?.to_le_bytes());
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Borsh string: u32 length followed by UTF-8 bytes
+fn write_string(value: &str, out: &mut Vec<u8>) {
+    out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// NEAR's `ed25519:<base58>` public key string; the prefix is optional on input
+mod near_public_key {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(key: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("ed25519:{}", bs58::encode(key).into_string()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 32], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let encoded = match s.split_once(':') {
+            Some(("ed25519", encoded)) => encoded,
+            Some((key_type, _)) => {
+                return Err(serde::de::Error::custom(format!("Unsupported key type: {}", key_type)));
+            }
+            None => &s,
+        };
+        let bytes = bs58::decode(encoded).into_vec().map_err(serde::de::Error::custom)?;
+        bytes
+            .try_into()
+            .map_err(|_| serde::de::Error::custom("expected a 32-byte ed25519 key"))
+    }
+}
+
+fn parse_yocto(deposit: &str) -> PreImageResult<u128> {
+    deposit.parse()
+        .map_err(|_| PreImageError::InvalidTransaction(format!("Invalid yoctoNEAR amount: {}", deposit)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_NEAR: &str = "1000000000000000000000000";
+
+    fn sample_tx() -> UnsignedNearTransaction {
+        let mut public_key = [0u8; 32];
+        for (i, byte) in public_key.iter_mut().enumerate() {
+            *byte = i as u8 + 1;
+        }
+        UnsignedNearTransaction {
+            signer_id: "alice.near".to_string(),
+            public_key,
+            nonce: 5,
+            receiver_id: "bob.near".to_string(),
+            block_hash: [0xab; 32],
+            actions: vec![NearAction::Transfer { deposit: ONE_NEAR.to_string() }],
+        }
+    }
+
+    #[test]
+    fn test_serialize_transfer() {
+        let serialized = serialize_near_transaction(&sample_tx()).unwrap();
+        assert_eq!(
+            hex::encode(&serialized),
+            concat!(
+                "0a000000616c6963652e6e656172",
+                "00", "0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20",
+                "0500000000000000",
+                "08000000626f622e6e656172",
+                "abababababababababababababababababababababababababababababababab",
+                "01000000", "03", "000000a1edccce1bc2d3000000000000",
+            )
+        );
+
+        let hash = get_near_signing_hash(&sample_tx()).unwrap();
+        assert_eq!(hex::encode(hash.hash), "247406b4eb523d58b3156558848b6a299091c5071a4e7b57000334da26984949");
+        assert_eq!(hash.algorithm, SigningAlgorithm::Ed25519);
+        assert_eq!(hash.signer_id, "alice.near");
+    }
+
+    #[test]
+    fn test_serialize_matches_near_api_js() {
+        // Transfer from near-api-js' serialization tests
+        let tx: UnsignedNearTransaction = serde_json::from_value(serde_json::json!({
+            "signer_id": "test.near",
+            "public_key": "ed25519:Anu7LYDfpLtkP7E16LT9imXF694BdQaa9ufVkQiwTQxC",
+            "nonce": 1,
+            "receiver_id": "whatever.near",
+            "block_hash": "244ZQ9cgj3CQ6bWBdytfrJMuMQ1jdXLFGnr4HhvtCTnM",
+            "actions": [{ "type": "transfer", "deposit": "1" }],
+        })).unwrap();
+
+        assert_eq!(
+            hex::encode(serialize_near_transaction(&tx).unwrap()),
+            "09000000746573742e6e65617200917b3d268d4b58f7fec1b150bd68d69be3ee5d4cc39855e341538465bb77860d01000000000000000d00000077686174657665722e6e6561720fa473fd26901df296be6adc4cc4df34d040efa2435224b6986910e630c2fef6010000000301000000000000000000000000000000"
+        );
+
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["public_key"], "ed25519:Anu7LYDfpLtkP7E16LT9imXF694BdQaa9ufVkQiwTQxC");
+        assert_eq!(json["block_hash"], "244ZQ9cgj3CQ6bWBdytfrJMuMQ1jdXLFGnr4HhvtCTnM");
+    }
+
+    #[test]
+    fn test_public_key_format() {
+        let parse = |key: &str| serde_json::from_value::<UnsignedNearTransaction>(serde_json::json!({
+            "signer_id": "test.near",
+            "public_key": key,
+            "nonce": 1,
+            "receiver_id": "whatever.near",
+            "block_hash": "244ZQ9cgj3CQ6bWBdytfrJMuMQ1jdXLFGnr4HhvtCTnM",
+            "actions": [{ "type": "transfer", "deposit": "1" }],
+        }));
+
+        assert!(parse("Anu7LYDfpLtkP7E16LT9imXF694BdQaa9ufVkQiwTQxC").is_ok());
+        assert!(parse("secp256k1:Anu7LYDfpLtkP7E16LT9imXF694BdQaa9ufVkQiwTQxC").is_err());
+        assert!(parse("ed25519:3").is_err());
+    }
+
+    #[test]
+    fn test_serialize_function_call() {
+        let mut tx = sample_tx();
+        tx.actions = vec![NearAction::FunctionCall {
+            method_name: "ft_transfer".to_string(),
+            args: b"{}".to_vec(),
+            gas: 30_000_000_000_000,
+            deposit: "1".to_string(),
+        }];
+        let serialized = hex::encode(serialize_near_transaction(&tx).unwrap());
+        assert!(serialized.ends_with(
+            "01000000020b00000066745f7472616e73666572020000007b7d00e057eb481b000001000000000000000000000000000000"
+        ));
+    }
+
+    #[test]
+    fn test_rejects_invalid_transaction() {
+        let mut tx = sample_tx();
+        tx.actions = vec![NearAction::Transfer { deposit: "1.5".to_string() }];
+        assert!(serialize_near_transaction(&tx).is_err());
+
+        tx.actions.clear();
+        assert!(get_near_signing_hash(&tx).is_err());
+    }
+}
@@ -0,0 +1,11 @@
+//! Shared Signing Test Fixtures
+
+/// Single-input P2WPKH PSBT spending m/84'/0'/0'/0/0 of the "abandon ... about"
+/// test mnemonic, with witness UTXO and BIP-32 derivation
+pub(crate) const P2WPKH_PSBT_BASE64: &str = "cHNidP8BAHECAAAAAfHx8fHx8fHx8fHx8fHx8fHx8fHx8fHx8fHx8fHx8fHxAAAAAAD9////AlDDAAAAAAAAFgAUnJD5NOpR+g9lBBdwQ+CQjaaSmYOIwgAAAAAAABYAFD40mF3Kb93J+zaZQOTH2OKHP1KcAAAAAAABAR+ghgEAAAAAABYAFMDOvNbD08qMddxexi6+VTMO+RDiIgYDMNVP0N1CCm5fjTYk9fNILK41D3nV8HU79b7vnC2RrzwYc8XaClQAAIAAAACAAAAAgAAAAAAAAAAAAAAA";
+
+/// [`P2WPKH_PSBT_BASE64`] decoded
+pub(crate) fn p2wpkh_psbt() -> Vec<u8> {
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+    STANDARD.decode(P2WPKH_PSBT_BASE64).unwrap()
+}
@@ -9,9 +9,14 @@ use solana_sdk::{
 use std::error::Error;
 use std::str::FromStr;
 
+use crate::wallet::parse_decimal_amount;
+
+/// Lamports per SOL as a power of ten
+pub const SOL_DECIMALS: u8 = 9;
+
 pub fn prepare_solana_transaction(
     recipient: &str,
-    amount_sol: f64,
+    amount_sol: &str,
     recent_blockhash: &str,
     sender_base58: &str,
 ) -> Result<String, Box<dyn Error>> {
@@ -20,8 +25,9 @@ pub fn prepare_solana_transaction(
     let recipient_pubkey = Pubkey::from_str(recipient)?;
     let blockhash = solana_sdk::hash::Hash::from_str(recent_blockhash)?;
 
-    // 2. Convert Amount (SOL -> Lamports)
-    let lamports = (amount_sol * 1_000_000_000.0) as u64;
+    // 2. Convert Amount (SOL -> Lamports), exactly
+    let lamports = u64::try_from(parse_decimal_amount(amount_sol, SOL_DECIMALS)?)
+        .map_err(|_| format!("Amount {} SOL exceeds the lamport range", amount_sol))?;
 
     // 3. Create Instruction
     let instruction = system_instruction::transfer(
@@ -41,3 +47,34 @@ pub fn prepare_solana_transaction(
     let serialized = bincode::serialize(&tx)?;
     Ok(bs58::encode(serialized).into_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_lamport_amount_is_exact() {
+        let sender = Keypair::new();
+        let recipient = Keypair::new().pubkey().to_string();
+        let blockhash = solana_sdk::hash::Hash::default().to_string();
+
+        let encoded = prepare_solana_transaction(&recipient, "0.000000001", &blockhash, &sender.to_base58_string())
+            .unwrap();
+        let tx: Transaction = bincode::deserialize(&bs58::decode(encoded).into_vec().unwrap()).unwrap();
+
+        // SystemInstruction::Transfer = u32 tag 2 followed by u64 lamports
+        let data = &tx.message.instructions[0].data;
+        assert_eq!(&data[..4], &2u32.to_le_bytes());
+        assert_eq!(&data[4..], &1u64.to_le_bytes());
+    }
+
+    #[test]
+    fn test_over_precise_amount_is_rejected() {
+        let sender = Keypair::new();
+        let recipient = Keypair::new().pubkey().to_string();
+        let blockhash = solana_sdk::hash::Hash::default().to_string();
+
+        assert!(prepare_solana_transaction(&recipient, "1e-9", &blockhash, &sender.to_base58_string()).is_err());
+        assert!(prepare_solana_transaction(&recipient, "0.0000000001", &blockhash, &sender.to_base58_string()).is_err());
+    }
+}
@@ -32,8 +32,12 @@ pub fn derive_stellar_keys(seed: &[u8]) -> Result<StellarKeys, String> {
         .try_into()
         .map_err(|_| "Failed to extract private key bytes")?;
 
-    // Create signing key
-    let signing_key = SigningKey::from_bytes(&private_bytes);
+    generate_stellar_keys(&SigningKey::from_bytes(&private_bytes))
+}
+
+/// Build Stellar keys (G... account id, S... secret seed) from an ed25519 signing key
+pub fn generate_stellar_keys(signing_key: &SigningKey) -> Result<StellarKeys, String> {
+    let private_bytes = signing_key.to_bytes();
     let verifying_key = signing_key.verifying_key();
 
     // Private key hex
@@ -129,4 +133,26 @@ mod tests {
         assert!(!keys.private_hex.is_empty());
         assert!(!keys.public_hex.is_empty());
     }
+
+    #[test]
+    fn test_generate_stellar_keys_known_vector() {
+        // SEP-0005 test 1, m/44'/148'/0'
+        let seed: [u8; 32] = hex::decode("4d691bc19b44a1383b1a0a130aaca3e05c3c1a371dbe45930ef9b761f7a74691")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let keys = generate_stellar_keys(&SigningKey::from_bytes(&seed)).unwrap();
+
+        assert_eq!(keys.address, "GDRXE2BQUC3AZNPVFSCEZ76NJ3WWL25FYFK6RGZGIEKWE4SOOHSUJUJ6");
+        assert_eq!(keys.secret_key, "SBGWSG6BTNCKCOB3DIFBGCVMUPQFYPA2G4O34RMTB343OYPXU5DJDVMN");
+    }
+
+    #[test]
+    fn test_generate_stellar_keys_zero_seed() {
+        let keys = generate_stellar_keys(&SigningKey::from_bytes(&[0u8; 32])).unwrap();
+
+        assert_eq!(keys.address, "GA5WUJ54Z23KILLCUOUNAKTPBVZWKMQVO4O6EQ5GHLAERIMLLHNCSKYH");
+        assert_eq!(keys.secret_key, "SAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSU2");
+    }
 }
@@ -171,6 +171,20 @@ impl SuiKeyPair {
     }
 }
 
+/// Build Sui keys from an ed25519 signing key
+///
+/// address = blake2b_256(0x00 || public_key)
+pub fn generate_sui_keys(signing_key: &SigningKey) -> crate::types::SuiKeys {
+    let public_key = signing_key.verifying_key().to_bytes();
+    let address = SuiAddress::from_public_key(&public_key);
+
+    crate::types::SuiKeys {
+        private_hex: hex::encode(signing_key.to_bytes()),
+        public_hex: hex::encode(public_key),
+        address: address.to_hex(),
+    }
+}
+
 /// Sui transaction types
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum SuiTransactionKind {
@@ -525,6 +539,21 @@ pub fn get_address_from_seed(seed: &[u8; 64], account: u32) -> HawalaResult<Stri
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_generate_sui_keys_known_vector() {
+        // Sui keytool vector ("film crazy soon ..." at m/44'/784'/0'/0'/0')
+        let seed: [u8; 32] = hex::decode("dd09307a43ba6dc186b5709e4ca51f4fc71911c143f7ceffcf8c60e6b03bc8fa")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let keys = generate_sui_keys(&SigningKey::from_bytes(&seed));
+
+        assert_eq!(keys.public_hex, "22647feeef363060bd4205a1671a15f10a1236765918b1b5f638d82f33cfc469");
+        assert_eq!(keys.address, "0xa2d14fad60c56049ecf75246a481934691214ce413e6a8ae2fe6834c173a6133");
+        assert_eq!(keys.address, SuiKeyPair::from_seed(&seed).unwrap().address.to_hex());
+    }
+
     #[test]
     fn test_address_derivation() {
         let public_key = [1u8; 32];
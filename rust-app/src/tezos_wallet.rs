@@ -112,6 +112,179 @@ fn encode_tezos_address(public_key: &VerifyingKey) -> Result<String, String> {
     Ok(tezos_base58check_encode(&prefix, &hash))
 }
 
+// =============================================================================
+// Operations
+// =============================================================================
+
+/// Base58 prefixes of Tezos hashes and signatures
+const TZ1_PREFIX: [u8; 3] = [6, 161, 159];
+const TZ2_PREFIX: [u8; 3] = [6, 161, 161];
+const TZ3_PREFIX: [u8; 3] = [6, 161, 164];
+const KT1_PREFIX: [u8; 3] = [2, 90, 121];
+const BLOCK_HASH_PREFIX: [u8; 2] = [1, 52];
+const OPERATION_HASH_PREFIX: [u8; 2] = [5, 116];
+const EDSIG_PREFIX: [u8; 5] = [9, 245, 205, 134, 18];
+const EDSK_SEED_PREFIX: [u8; 4] = [13, 15, 58, 7];
+const EDSK_FULL_PREFIX: [u8; 4] = [43, 246, 78, 7];
+
+/// Operation tag of `transaction`
+const TRANSACTION_TAG: u8 = 0x6c;
+/// Watermark prepended to operation bytes before signing
+const GENERIC_OPERATION_WATERMARK: u8 = 0x03;
+
+/// A `transaction` operation sending tez
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TezosTransfer {
+    /// Block hash the operation is anchored to (`B...`)
+    pub branch: String,
+    pub source: String,
+    /// Implicit (`tz1/2/3...`) or originated (`KT1...`) account
+    pub destination: String,
+    /// Fee in mutez
+    pub fee: u64,
+    /// Source account's counter plus one
+    pub counter: u64,
+    pub gas_limit: u64,
+    pub storage_limit: u64,
+    /// Amount in mutez
+    pub amount: u64,
+}
+
+/// A forged, signed operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedTezosOperation {
+    /// Operation bytes followed by the signature, hex (the body for
+    /// `/injection/operation`)
+    pub signed_hex: String,
+    /// `edsig...`
+    pub signature: String,
+    /// `o...`
+    pub operation_hash: String,
+}
+
+/// Forge a transfer to its binary encoding: branch, then the
+/// `transaction` content with zarith-encoded numbers and no parameters
+pub fn forge_transfer(transfer: &TezosTransfer) -> Result<Vec<u8>, String> {
+    let mut out = tezos_base58check_decode(&transfer.branch, &BLOCK_HASH_PREFIX, 32)
+        .map_err(|e| format!("Invalid branch: {}", e))?;
+
+    out.push(TRANSACTION_TAG);
+    out.extend(forge_public_key_hash(&transfer.source).map_err(|e| format!("Invalid source: {}", e))?);
+    for n in [transfer.fee, transfer.counter, transfer.gas_limit, transfer.storage_limit, transfer.amount] {
+        forge_zarith(n, &mut out);
+    }
+    out.extend(forge_contract(&transfer.destination).map_err(|e| format!("Invalid destination: {}", e))?);
+    // No parameters
+    out.push(0x00);
+    Ok(out)
+}
+
+/// Sign forged operation bytes with an ed25519 key
+///
+/// The signature covers Blake2b-256 of the 0x03 watermark and the bytes.
+pub fn sign_operation(forged: &[u8], private_key: &[u8; 32]) -> SignedTezosOperation {
+    use ed25519_dalek::Signer;
+
+    let mut watermarked = Vec::with_capacity(forged.len() + 1);
+    watermarked.push(GENERIC_OPERATION_WATERMARK);
+    watermarked.extend_from_slice(forged);
+
+    let signature = SigningKey::from_bytes(private_key).sign(&blake2b_256(&watermarked)).to_bytes();
+
+    let mut signed = forged.to_vec();
+    signed.extend_from_slice(&signature);
+
+    SignedTezosOperation {
+        signed_hex: hex::encode(&signed),
+        signature: tezos_base58check_encode(&EDSIG_PREFIX, &signature),
+        operation_hash: tezos_base58check_encode(&OPERATION_HASH_PREFIX, &blake2b_256(&signed)),
+    }
+}
+
+/// Forge and sign a transfer; `private_key` must belong to the tz1 source
+pub fn prepare_tezos_transaction(transfer: &TezosTransfer, private_key: &str) -> Result<SignedTezosOperation, String> {
+    let secret = parse_tezos_private_key(private_key)?;
+    let address = encode_tezos_address(&SigningKey::from_bytes(&secret).verifying_key())?;
+    if address != transfer.source {
+        return Err(format!("Private key is for {}, not the source {}", address, transfer.source));
+    }
+
+    let forged = forge_transfer(transfer)?;
+    Ok(sign_operation(&forged, &secret))
+}
+
+/// Ed25519 seed from 32-byte hex or an `edsk...` key
+fn parse_tezos_private_key(key: &str) -> Result<[u8; 32], String> {
+    let key = key.trim();
+    let seed = if key.starts_with("edsk") {
+        let (prefix, len) = if key.len() > 60 { (EDSK_FULL_PREFIX, 64) } else { (EDSK_SEED_PREFIX, 32) };
+        tezos_base58check_decode(key, &prefix, len)?
+    } else {
+        hex::decode(key.trim_start_matches("0x")).map_err(|_| "Invalid private key hex".to_string())?
+    };
+    seed.get(..32)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| "Private key must be 32 bytes".to_string())
+}
+
+/// Decode base58check `encoded`, checking its prefix and payload length
+fn tezos_base58check_decode(encoded: &str, prefix: &[u8], len: usize) -> Result<Vec<u8>, String> {
+    let decoded = bitcoin::base58::decode_check(encoded).map_err(|e| e.to_string())?;
+    match decoded.strip_prefix(prefix) {
+        Some(payload) if payload.len() == len => Ok(payload.to_vec()),
+        _ => Err(format!("Unexpected prefix or length in {}", encoded)),
+    }
+}
+
+/// `public_key_hash` encoding: curve tag then the 20-byte hash
+fn forge_public_key_hash(address: &str) -> Result<Vec<u8>, String> {
+    let (tag, prefix) = match address.get(..3) {
+        Some("tz1") => (0x00, TZ1_PREFIX),
+        Some("tz2") => (0x01, TZ2_PREFIX),
+        Some("tz3") => (0x02, TZ3_PREFIX),
+        _ => return Err(format!("Not an implicit account: {}", address)),
+    };
+    let mut out = vec![tag];
+    out.extend(tezos_base58check_decode(address, &prefix, 20)?);
+    Ok(out)
+}
+
+/// `contract_id` encoding: 0x00 + public key hash for implicit accounts,
+/// 0x01 + hash + padding byte for originated ones
+fn forge_contract(address: &str) -> Result<Vec<u8>, String> {
+    if address.starts_with("KT1") {
+        let mut out = vec![0x01];
+        out.extend(tezos_base58check_decode(address, &KT1_PREFIX, 20)?);
+        out.push(0x00);
+        return Ok(out);
+    }
+    let mut out = vec![0x00];
+    out.extend(forge_public_key_hash(address)?);
+    Ok(out)
+}
+
+/// Zarith (unsigned LEB128) natural number
+fn forge_zarith(mut n: u64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn blake2b_256(data: &[u8]) -> [u8; 32] {
+    use blake2::{Blake2b, Digest};
+    use blake2::digest::consts::U32;
+
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +310,79 @@ mod tests {
         assert!(keys.secret_key.starts_with("edsk"), "Tezos secret key should start with edsk, got: {}", keys.secret_key);
         assert!(!keys.private_hex.is_empty());
     }
+
+    fn reference_transfer() -> TezosTransfer {
+        TezosTransfer {
+            branch: "BMZYTcuD2WXFq3XGTAuAccCfFpytJpv7eZHYYDe1pGXFybAFWrw".to_string(),
+            source: "tz1MsZxMSJdiUV9hVs4UKAMrXtksDvxWAZe2".to_string(),
+            destination: "tz1Qr9uevaimfiPS6X1otehsKrwvZjX7bsyL".to_string(),
+            fee: 1420,
+            counter: 8_123_456,
+            gas_limit: 1527,
+            storage_limit: 257,
+            amount: 2_500_000,
+        }
+    }
+
+    #[test]
+    fn test_forge_transfer() {
+        let forged = forge_transfer(&reference_transfer()).unwrap();
+        // branch | 6c | source | fee | counter | gas | storage | amount | destination | no params
+        //
+        // Each field matches the encoders in Octez's `tezos_data_encoding` and
+        // `tezos-smart-rollup-encoding` crates (block hash, public key hash,
+        // `N` numbers, contract id). No taquito output was available to compare
+        // the whole operation against.
+        assert_eq!(
+            hex::encode(forged),
+            concat!(
+                "f38c764c8aa00b6578f4254a4dc6d9b50f88fa926e270ea7859bd1b707cd8662",
+                "6c",
+                "00187fc17d77ec0e3c6ec1cf6374b04d9594c9659a",
+                "8c0b",
+                "c0e8ef03",
+                "f70b",
+                "8102",
+                "a0cb9801",
+                "00003923a4e9144201ffcc3d4ac891d14a14df438c52",
+                "00",
+            )
+        );
+
+        let mut to_contract = reference_transfer();
+        to_contract.destination = "KT1DXdud7dg1H3sWBhY69LT47iTx5PuYrwE9".to_string();
+        let forged = hex::encode(forge_transfer(&to_contract).unwrap());
+        assert!(forged.ends_with("013640fd205707f71256f40427a7da3f51601286cb0000"));
+
+        let mut bad = reference_transfer();
+        bad.branch = bad.source.clone();
+        assert!(forge_transfer(&bad).is_err());
+    }
+
+    #[test]
+    fn test_zarith() {
+        for (n, expected) in [(0u64, "00"), (127, "7f"), (128, "8001"), (300, "ac02")] {
+            let mut out = Vec::new();
+            forge_zarith(n, &mut out);
+            assert_eq!(hex::encode(out), expected);
+        }
+    }
+
+    #[test]
+    fn test_sign_transfer() {
+        let key = "0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20";
+        let signed = prepare_tezos_transaction(&reference_transfer(), key).unwrap();
+
+        assert_eq!(
+            signed.signature,
+            "edsigtxbH4JfZi6wcmxiCGb5gk1jgNBoDWvEpGgJquzjkYSaJZPM7afwj9WcWyNGUfomZSVH1FHRp2m2pnSc4L5ZJmnd4GM1SVF"
+        );
+        assert!(signed.signed_hex.ends_with(
+            "bd71cf95db9c3edb8c1445c3602307a4d2019ffefb0acf117fa91f6cc443abd48c56091a1f38c120df1807d32157b82bd09e94b6f6cb0114b55dce06c1d59300"
+        ));
+        assert_eq!(signed.operation_hash, "opMsDMoadfRN7HpCZqL2GVDS7nrT2L2YyFGm4FRBzsKPJFUBM3E");
+
+        // A key that doesn't own the source is refused
+        assert!(prepare_tezos_transaction(&reference_transfer(), &"07".repeat(32)).is_err());
+    }
 }
@@ -202,14 +202,19 @@ pub fn broadcast_ethereum(raw_tx: &str, testnet: bool) -> HawalaResult<Broadcast
 }
 
 /// Broadcast to any EVM chain by chain ID
+///
+/// The transaction must be EIP-155 bound to `chain_id`; anything else is
+/// rejected before it reaches a node.
 pub fn broadcast_evm(raw_tx: &str, chain_id: u64) -> HawalaResult<BroadcastResult> {
+    super::replay_protection::validate_evm(raw_tx, chain_id)?;
+    
     let endpoints = get_rpc_endpoints(chain_id);
     let mut last_error = HawalaError::broadcast_failed("All endpoints failed");
     
     for endpoint in &endpoints {
         match broadcast_evm_single(raw_tx, endpoint) {
             Ok(txid) => {
-                let chain = chain_from_id(chain_id);
+                let chain = Chain::from_chain_id(chain_id).unwrap_or(Chain::Ethereum);
                 let (explorer_base, _) = get_explorer_info(chain_id);
                 
                 return Ok(BroadcastResult {
@@ -601,20 +606,6 @@ fn get_rpc_endpoints(chain_id: u64) -> Vec<&'static str> {
     }
 }
 
-fn chain_from_id(chain_id: u64) -> Chain {
-    match chain_id {
-        1 => Chain::Ethereum,
-        11155111 => Chain::EthereumSepolia,
-        56 => Chain::Bnb,
-        137 => Chain::Polygon,
-        42161 => Chain::Arbitrum,
-        10 => Chain::Optimism,
-        8453 => Chain::Base,
-        43114 => Chain::Avalanche,
-        _ => Chain::Ethereum,
-    }
-}
-
 fn get_explorer_info(chain_id: u64) -> (&'static str, &'static str) {
     match chain_id {
         1 => ("https://etherscan.io/tx/", "ethereum"),
@@ -639,11 +630,12 @@ mod tests {
         assert!(!endpoints.is_empty());
         assert!(endpoints[0].contains("eth"));
     }
-    
+
     #[test]
-    fn test_chain_from_id() {
-        assert!(matches!(chain_from_id(1), Chain::Ethereum));
-        assert!(matches!(chain_from_id(56), Chain::Bnb));
-        assert!(matches!(chain_from_id(137), Chain::Polygon));
+    fn test_broadcast_evm_rejects_other_chain() {
+        // EIP-155 example transaction, signed for chain id 1
+        let raw_tx = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+        let err = broadcast_evm(raw_tx, 137).unwrap_err();
+        assert_eq!(err.code, ErrorCode::ChainIdMismatch);
     }
 }
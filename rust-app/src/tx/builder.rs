@@ -2,8 +2,18 @@
 //!
 //! Constructs unsigned transactions for all supported chains.
 
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::abi::{AbiEncoder, AbiFunction, AbiValue, ContractAbi};
 use crate::error::{HawalaError, HawalaResult, ErrorCode};
+use crate::fees::units::{Lamports, SatPerVb, Wei};
+use crate::signing::preimage::ethereum::{EthereumTxType, UnsignedEthereumTransaction};
 use crate::types::*;
+use crate::wallet::parse_decimal_amount;
+use ethers_core::types::transaction::eip2930::{AccessList, AccessListItem as EthersAccessListItem};
 
 /// Build a Bitcoin transaction
 pub fn build_bitcoin_transaction(request: &TransactionRequest) -> HawalaResult<SignedTransaction> {
@@ -13,60 +23,412 @@ pub fn build_bitcoin_transaction(request: &TransactionRequest) -> HawalaResult<S
     let utxos = request.utxos.as_ref()
         .ok_or_else(|| HawalaError::invalid_input("UTXOs required for Bitcoin transaction"))?;
     
-    let fee_rate = request.fee_rate
+    let SatPerVb(fee_rate) = request.fee_rate
         .ok_or_else(|| HawalaError::invalid_input("Fee rate required for Bitcoin transaction"))?;
     
-    // Convert our Utxo type to bitcoin_wallet::Utxo
-    let btc_utxos: Vec<crate::bitcoin_wallet::Utxo> = utxos.iter().map(|u| {
-        crate::bitcoin_wallet::Utxo {
-            txid: u.txid.clone(),
-            vout: u.vout,
-            status: crate::bitcoin_wallet::UtxoStatus {
-                confirmed: u.confirmed,
-                block_height: u.block_height,
-                block_hash: None,
-                block_time: None,
-            },
-            value: u.value,
-        }
-    }).collect();
+    check_bitcoin_fee(request, utxos, fee_rate)?;
     
-    // Parse amount - handle both sats and BTC format
-    let amount_sats: u64 = if request.amount.contains('.') {
-        // Parse as BTC
-        let btc: f64 = request.amount.parse()
-            .map_err(|_| HawalaError::invalid_input("Invalid amount format"))?;
-        (btc * 100_000_000.0) as u64
+    let btc_utxos = to_bitcoin_utxos(utxos);
+    
+    // A sweep has no change output
+    let (prepared, change) = if request.send_max {
+        let prepared = crate::bitcoin_wallet::prepare_sweep_transaction(
+            &request.to,
+            fee_rate,
+            &request.private_key,
+            Some(btc_utxos),
+        );
+        (prepared, None)
     } else {
-        request.amount.parse()
-            .map_err(|_| HawalaError::invalid_input("Invalid amount format"))?
+        let amount_sats = parse_utxo_amount(&request.amount)?;
+        let change = resolve_bitcoin_change(request)?;
+        let prepared = crate::bitcoin_wallet::prepare_transaction_with_change(
+            &request.to,
+            amount_sats,
+            fee_rate,
+            &request.private_key,
+            Some(btc_utxos),
+            change.as_ref().map(|c| c.address.as_str()),
+        );
+        (prepared, change)
     };
     
-    match crate::bitcoin_wallet::prepare_transaction(
-        &request.to,
-        amount_sats,
-        fee_rate,
-        &request.private_key,
-        Some(btc_utxos),
-    ) {
+    match prepared {
         Ok(raw_tx) => {
             // Extract txid from the signed transaction
             let txid = calculate_btc_txid(&raw_tx);
+            // Change below the dust limit is dropped from the transaction
+            let change = change.filter(|c| has_output_to(&raw_tx, &c.address));
             
             Ok(SignedTransaction {
                 chain: request.chain,
                 raw_tx,
                 txid,
-                estimated_fee: Some(format!("{} sats/vB", fee_rate)),
+                estimated_fee: Some(SatPerVb(fee_rate).to_string()),
                 size_bytes: None,
+                change,
             })
         }
         Err(e) => Err(HawalaError::new(ErrorCode::CryptoError, e.to_string())),
     }
 }
 
+/// Pick the change destination: explicit address, else the address at
+/// `change_index` on the change chain of `account_xpub`, else none (change
+/// returns to the sender)
+fn resolve_bitcoin_change(request: &TransactionRequest) -> HawalaResult<Option<ChangeOutput>> {
+    if let Some(address) = &request.change_address {
+        return Ok(Some(ChangeOutput {
+            address: address.clone(),
+            path: String::new(),
+        }));
+    }
+    
+    let Some(xpub) = &request.account_xpub else {
+        return Ok(None);
+    };
+    
+    // The builder does no network I/O, so the caller supplies the next
+    // unused index (e.g. `next_change_index` from `scan_addresses`)
+    let change_index = request.change_index
+        .ok_or_else(|| HawalaError::invalid_input("change_index required with account_xpub"))?;
+    
+    crate::wallet::derive_change_address(
+        crate::wallet::ScanSource::Xpub(xpub),
+        request.chain,
+        change_index,
+    )
+    .map(Some)
+}
+
+/// Preview a Bitcoin transaction: the same input selection and fee as
+/// `build_bitcoin_transaction`, without signing
+pub fn preview_bitcoin_transaction(request: &TransactionRequest) -> HawalaResult<TransactionPreview> {
+    let utxos = request.utxos.as_ref()
+        .ok_or_else(|| HawalaError::invalid_input("UTXOs required for Bitcoin transaction"))?;
+    
+    let SatPerVb(fee_rate) = request.fee_rate
+        .ok_or_else(|| HawalaError::invalid_input("Fee rate required for Bitcoin transaction"))?;
+    
+    let (amount_sats, plan, change) = if request.send_max {
+        let (amount_sats, plan) = crate::bitcoin_wallet::plan_sweep(to_bitcoin_utxos(utxos), fee_rate)
+            .map_err(|e| HawalaError::new(ErrorCode::CryptoError, e.to_string()))?;
+        (amount_sats, plan, None)
+    } else {
+        let amount_sats = parse_utxo_amount(&request.amount)?;
+        // Manual UTXOs are always spent in full, as in the signed build
+        let plan = crate::bitcoin_wallet::plan_spend(to_bitcoin_utxos(utxos), amount_sats, fee_rate, true)
+            .map_err(|e| HawalaError::new(ErrorCode::CryptoError, e.to_string()))?;
+        (amount_sats, plan, resolve_bitcoin_change(request)?)
+    };
+    
+    Ok(TransactionPreview {
+        chain: request.chain,
+        fee: plan.fee.to_string(),
+        size_or_gas: Some(plan.vsize),
+        change: plan.change.to_string(),
+        change_output: change.filter(|_| plan.change > 0),
+        total: (amount_sats + plan.fee).to_string(),
+        inputs_used: plan.inputs.iter().map(|u| format!("{}:{}", u.txid, u.vout)).collect(),
+    })
+}
+
+/// Default absolute fee cap for Bitcoin, matching Bitcoin Core's `-maxtxfee`
+const BITCOIN_MAX_FEE_SATS: u128 = 10_000_000;
+
+// Check the fee of the same input selection as the preview against the fee
+// guard. Selection failures are left for the builder to report
+fn check_bitcoin_fee(request: &TransactionRequest, utxos: &[Utxo], fee_rate: u64) -> HawalaResult<()> {
+    if request.allow_high_fee {
+        return Ok(());
+    }
+    
+    let planned = if request.send_max {
+        crate::bitcoin_wallet::plan_sweep(to_bitcoin_utxos(utxos), fee_rate).ok()
+    } else {
+        let amount_sats = parse_utxo_amount(&request.amount)?;
+        crate::bitcoin_wallet::plan_spend(to_bitcoin_utxos(utxos), amount_sats, fee_rate, true)
+            .ok()
+            .map(|plan| (amount_sats, plan))
+    };
+    
+    match planned {
+        Some((amount_sats, plan)) => check_fee_guard(
+            request,
+            u128::from(plan.fee),
+            u128::from(amount_sats),
+            Some(BITCOIN_MAX_FEE_SATS),
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Reject a fee above the request's fee guard unless `allow_high_fee` is set
+///
+/// `fee` and `amount` are in base units. The error carries the computed fee
+/// in `details`.
+fn check_fee_guard(
+    request: &TransactionRequest,
+    fee: u128,
+    amount: u128,
+    default_cap: Option<u128>,
+) -> HawalaResult<()> {
+    if request.allow_high_fee {
+        return Ok(());
+    }
+    
+    let guard = request.fee_guard.clone().unwrap_or_default();
+    let cap = match &guard.max_fee {
+        Some(max_fee) => Some(parse_wei(max_fee)?),
+        None => default_cap,
+    };
+    
+    if let Some(cap) = cap {
+        if fee > cap {
+            return Err(HawalaError::new(
+                ErrorCode::FeeTooHigh,
+                format!("Fee of {} exceeds the cap of {}; set allow_high_fee to send anyway", fee, cap),
+            ).with_details(fee.to_string()));
+        }
+    }
+    
+    let limit = amount.saturating_mul(u128::from(guard.max_fee_percent));
+    if amount > 0 && fee.saturating_mul(100) > limit {
+        return Err(HawalaError::new(
+            ErrorCode::FeeTooHigh,
+            format!(
+                "Fee of {} is more than {}% of the {} being sent; set allow_high_fee to send anyway",
+                fee, guard.max_fee_percent, amount
+            ),
+        ).with_details(fee.to_string()));
+    }
+    
+    Ok(())
+}
+
+fn to_bitcoin_utxos(utxos: &[Utxo]) -> Vec<crate::bitcoin_wallet::Utxo> {
+    utxos.iter().map(|u| {
+        crate::bitcoin_wallet::Utxo {
+            txid: u.txid.clone(),
+            vout: u.vout,
+            status: crate::bitcoin_wallet::UtxoStatus {
+                confirmed: u.confirmed,
+                block_height: u.block_height,
+                block_hash: None,
+                block_time: None,
+            },
+            value: u.value,
+        }
+    }).collect()
+}
+
+fn to_litecoin_utxos(utxos: &[Utxo]) -> Vec<crate::litecoin_wallet::LitecoinUtxo> {
+    utxos.iter().map(|u| {
+        crate::litecoin_wallet::LitecoinUtxo {
+            transaction_hash: u.txid.clone(),
+            index: u.vout,
+            value: u.value,
+            script_hex: None,
+            block_id: u.block_height.map(|h| h as i64),
+        }
+    }).collect()
+}
+
+/// Inputs a Bitcoin or Litecoin build would spend out of `available`: all
+/// of them for `send_max`, else the shortest prefix covering amount and fee
+pub(super) fn select_utxo_inputs(request: &TransactionRequest, available: &[Utxo]) -> HawalaResult<Vec<Utxo>> {
+    if request.send_max {
+        return Ok(available.to_vec());
+    }
+    
+    let SatPerVb(fee_rate) = request.fee_rate
+        .ok_or_else(|| HawalaError::invalid_input(format!("Fee rate required for {:?} transaction", request.chain)))?;
+    let amount = parse_utxo_amount(&request.amount)?;
+    
+    (1..=available.len())
+        .map(|count| &available[..count])
+        .find(|inputs| crate::bitcoin_wallet::plan_spend(to_bitcoin_utxos(inputs), amount, fee_rate, true).is_ok())
+        .map(<[Utxo]>::to_vec)
+        .ok_or_else(|| HawalaError::insufficient_funds("Not enough spendable UTXOs for amount and fee"))
+}
+
+// Parse a UTXO-chain amount given in base units or, with a decimal point, in coins
+fn parse_utxo_amount(amount: &str) -> HawalaResult<u64> {
+    if amount.contains('.') {
+        let coins: f64 = amount.parse()
+            .map_err(|_| HawalaError::invalid_input("Invalid amount format"))?;
+        Ok((coins * 100_000_000.0) as u64)
+    } else {
+        amount.parse()
+            .map_err(|_| HawalaError::invalid_input("Invalid amount format"))
+    }
+}
+
+// Check whether a raw transaction pays to the given address
+fn has_output_to(tx_hex: &str, address: &str) -> bool {
+    use bitcoin::consensus::encode::deserialize;
+    
+    let script = match bitcoin::Address::from_str(address) {
+        Ok(a) => a.assume_checked().script_pubkey(),
+        Err(_) => return false,
+    };
+    let tx: bitcoin::Transaction = match hex::decode(tx_hex).ok().and_then(|b| deserialize(&b).ok()) {
+        Some(tx) => tx,
+        None => return false,
+    };
+    tx.output.iter().any(|o| o.script_pubkey == script)
+}
+
+/// Signed CPFP child and the fee rate of the parent + child package
+#[derive(Debug, Clone, Serialize)]
+pub struct CpfpChild {
+    pub transaction: SignedTransaction,
+    pub parent_txid: String,
+    pub parent_vsize: u64,
+    pub child_vsize: u64,
+    pub child_fee: u64,
+    pub package_fee_rate: f64,
+}
+
+/// Build and sign a CPFP child spending our output of a stuck parent
+///
+/// The parent output at `parent_output_index` must be P2WPKH for
+/// `spend_wif`. The child sends it to `destination` minus a fee sized by
+/// `cpfp::CpfpCalculator` so the package reaches `target_fee_rate`
+/// (sat/vB). The child is sized with a worst-case signature, so the real
+/// package rate can only come out at or above the target.
+pub fn build_cpfp_child(
+    parent: &crate::cpfp::StuckTransaction,
+    parent_output_index: u32,
+    spend_wif: &str,
+    target_fee_rate: f64,
+    destination: &str,
+) -> HawalaResult<CpfpChild> {
+    use bitcoin::consensus::encode::{deserialize, serialize};
+    use bitcoin::hashes::Hash;
+    use bitcoin::secp256k1::{Message, Secp256k1};
+    use bitcoin::sighash::{EcdsaSighashType, SighashCache};
+    use bitcoin::{
+        Address, Amount, Network, NetworkKind, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness,
+        absolute::LockTime, transaction::Version,
+    };
+    use crate::cpfp::{AddressType, CpfpCalculator, DustLimits};
+    
+    let parent_tx: Transaction = hex::decode(parent.raw_tx.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| deserialize(&bytes).ok())
+        .ok_or_else(|| HawalaError::invalid_input("Invalid parent transaction hex"))?;
+    let parent_txid = parent_tx.compute_txid();
+    if !parent.txid.is_empty() && parent.txid != parent_txid.to_string() {
+        return Err(HawalaError::invalid_input("Parent txid does not match the raw transaction"));
+    }
+    let parent_output = parent_tx.output.get(parent_output_index as usize)
+        .ok_or_else(|| HawalaError::invalid_input(format!("Parent has no output {}", parent_output_index)))?;
+    
+    let secp = Secp256k1::new();
+    let private_key = bitcoin::PrivateKey::from_wif(spend_wif)
+        .map_err(|e| HawalaError::invalid_input(format!("Invalid WIF: {}", e)))?;
+    let network = match private_key.network {
+        NetworkKind::Main => Network::Bitcoin,
+        NetworkKind::Test => Network::Testnet,
+    };
+    let public_key = bitcoin::key::CompressedPublicKey::try_from(private_key.public_key(&secp))
+        .map_err(|_| HawalaError::invalid_input("P2WPKH requires a compressed key"))?;
+    let own_script = Address::p2wpkh(&public_key, network).script_pubkey();
+    if parent_output.script_pubkey != own_script {
+        return Err(HawalaError::invalid_input(format!(
+            "Parent output {} is not a P2WPKH output of this key",
+            parent_output_index
+        )));
+    }
+    
+    let destination_address = Address::from_str(destination)
+        .map_err(|e| HawalaError::invalid_input(format!("Invalid destination: {}", e)))?
+        .require_network(network)
+        .map_err(|e| HawalaError::invalid_input(format!("Invalid destination: {}", e)))?;
+    let dust_limit = DustLimits::for_type(AddressType::from_address(destination).unwrap_or(AddressType::P2PKH));
+    
+    let mut child = Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint::new(parent_txid, parent_output_index),
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            // Placeholder witness with a maximum-size DER signature for sizing
+            witness: Witness::from_slice(&[vec![0u8; 73], public_key.to_bytes().to_vec()]),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: destination_address.script_pubkey(),
+        }],
+    };
+    
+    let parent_vsize = parent_tx.vsize() as u64;
+    let child_vsize = child.vsize() as u64;
+    let viability = CpfpCalculator::is_cpfp_viable(
+        parent_vsize,
+        parent.fee,
+        child_vsize,
+        parent_output.value.to_sat(),
+        target_fee_rate,
+        dust_limit,
+    )
+    .map_err(|e| HawalaError::insufficient_funds(e.to_string()))?;
+    child.output[0].value = Amount::from_sat(viability.output_amount);
+    
+    let sighash = SighashCache::new(&child)
+        .p2wpkh_signature_hash(0, &own_script, parent_output.value, EcdsaSighashType::All)
+        .map_err(|e| HawalaError::crypto_error(format!("Sighash failed: {}", e)))?;
+    let msg = Message::from_digest_slice(sighash.as_byte_array())
+        .map_err(|e| HawalaError::crypto_error(e.to_string()))?;
+    let signature = secp.sign_ecdsa(&msg, &private_key.inner);
+    
+    let mut sig_vec = signature.serialize_der().to_vec();
+    sig_vec.push(EcdsaSighashType::All as u8);
+    child.input[0].witness = Witness::from_slice(&[sig_vec, public_key.to_bytes().to_vec()]);
+    
+    let raw_tx = hex::encode(serialize(&child));
+    let child_vsize = child.vsize() as u64;
+    let child_fee = viability.required_child_fee;
+    
+    Ok(CpfpChild {
+        transaction: SignedTransaction {
+            chain: if network == Network::Bitcoin { Chain::Bitcoin } else { Chain::BitcoinTestnet },
+            txid: child.compute_txid().to_string(),
+            raw_tx,
+            estimated_fee: Some(format!("{} sats", child_fee)),
+            size_bytes: Some(child_vsize as u32),
+            change: None,
+        },
+        parent_txid: parent_txid.to_string(),
+        parent_vsize,
+        child_vsize,
+        child_fee,
+        package_fee_rate: CpfpCalculator::calculate_package_fee_rate(parent_vsize, parent.fee, child_vsize, child_fee),
+    })
+}
+
+/// Margin applied to `eth_estimateGas` results when `auto_gas` is set, in percent
+const AUTO_GAS_MARGIN_PERCENT: u64 = 120;
+
+/// Gas limit of a plain value transfer
+const PLAIN_TRANSFER_GAS: u64 = 21_000;
+
 /// Build an EVM transaction (Ethereum, BSC, Polygon, etc.)
 pub fn build_evm_transaction(request: &TransactionRequest) -> HawalaResult<SignedTransaction> {
+    build_evm_transaction_with(request, crate::fees::estimate_gas_limit)
+}
+
+/// [`build_evm_transaction`] with a caller-supplied gas estimator
+/// (`chain_id, from, to, value, data`), used when `auto_gas` is set
+pub fn build_evm_transaction_with(
+    request: &TransactionRequest,
+    estimate_gas: impl Fn(u64, &str, &str, &str, &str) -> HawalaResult<GasEstimateResult>,
+) -> HawalaResult<SignedTransaction> {
+    let resolved = evm_with_auto_gas(request, estimate_gas)?;
+    let request: &TransactionRequest = &resolved;
+    
     let chain_id = request.chain.chain_id()
         .ok_or_else(|| HawalaError::invalid_input("Invalid EVM chain"))?;
     
@@ -77,22 +439,32 @@ pub fn build_evm_transaction(request: &TransactionRequest) -> HawalaResult<Signe
         .ok_or_else(|| HawalaError::invalid_input("Gas limit required for EVM transaction"))?;
     
     let data = request.data.clone().unwrap_or_else(|| "0x".to_string());
+    let amount = if request.send_max {
+        evm_sweep_value(request)?.to_string()
+    } else {
+        request.amount.clone()
+    };
+    
+    if !request.allow_high_fee {
+        check_fee_guard(request, evm_max_fee(request)?, parse_wei(&amount)?, None)?;
+    }
     
     // Use tokio runtime for async ethereum_wallet
     let rt = tokio::runtime::Runtime::new()
         .map_err(|e| HawalaError::internal(format!("Runtime error: {}", e)))?;
     
-    match rt.block_on(crate::ethereum_wallet::prepare_ethereum_transaction(
+    match rt.block_on(crate::ethereum_wallet::prepare_ethereum_transaction_with_access_list(
         &request.to,
-        &request.amount,
+        &amount,
         chain_id,
         &request.private_key,
         nonce,
         gas_limit,
-        request.gas_price.clone(),
-        request.max_fee_per_gas.clone(),
-        request.max_priority_fee_per_gas.clone(),
+        request.gas_price.map(|wei| wei.0.to_string()),
+        request.max_fee_per_gas.map(|wei| wei.0.to_string()),
+        request.max_priority_fee_per_gas.map(|wei| wei.0.to_string()),
         &data,
+        evm_access_list(request)?,
     )) {
         Ok(raw_tx) => {
             let txid = calculate_eth_txid(&raw_tx);
@@ -101,42 +473,283 @@ pub fn build_evm_transaction(request: &TransactionRequest) -> HawalaResult<Signe
                 chain: request.chain,
                 raw_tx,
                 txid,
-                estimated_fee: None, // Depends on gas * gasPrice
+                estimated_fee: evm_max_fee(request).ok().map(|fee| Wei(fee).to_string()),
                 size_bytes: None,
+                change: None,
             })
         }
         Err(e) => Err(HawalaError::new(ErrorCode::CryptoError, e.to_string())),
     }
 }
 
+/// Preview an EVM transaction: the maximum fee (gas limit × max fee per gas,
+/// or × gas price for legacy transactions), without signing
+pub fn preview_evm_transaction(request: &TransactionRequest) -> HawalaResult<TransactionPreview> {
+    preview_evm_transaction_with(request, crate::fees::estimate_gas_limit)
+}
+
+/// [`preview_evm_transaction`] with a caller-supplied gas estimator
+pub fn preview_evm_transaction_with(
+    request: &TransactionRequest,
+    estimate_gas: impl Fn(u64, &str, &str, &str, &str) -> HawalaResult<GasEstimateResult>,
+) -> HawalaResult<TransactionPreview> {
+    let resolved = evm_with_auto_gas(request, estimate_gas)?;
+    let request: &TransactionRequest = &resolved;
+    
+    request.chain.chain_id()
+        .ok_or_else(|| HawalaError::invalid_input("Invalid EVM chain"))?;
+    
+    let gas_limit = request.gas_limit
+        .ok_or_else(|| HawalaError::invalid_input("Gas limit required for EVM transaction"))?;
+    
+    let fee = evm_max_fee(request)?;
+    let value = if request.send_max {
+        evm_sweep_value(request)?
+    } else {
+        parse_wei(&request.amount)?
+    };
+    let total = value.checked_add(fee)
+        .ok_or_else(|| HawalaError::invalid_input("Amount plus fee overflows"))?;
+    
+    Ok(TransactionPreview {
+        chain: request.chain,
+        fee: fee.to_string(),
+        size_or_gas: Some(gas_limit),
+        change: "0".to_string(),
+        change_output: None,
+        total: total.to_string(),
+        inputs_used: Vec::new(),
+    })
+}
+
+// Fill in the gas limit from the estimator when `auto_gas` is set. A plain
+// transfer falls back to 21000 if estimation fails; a contract call errors,
+// since any guess risks an out-of-gas failure
+fn evm_with_auto_gas(
+    request: &TransactionRequest,
+    estimate_gas: impl Fn(u64, &str, &str, &str, &str) -> HawalaResult<GasEstimateResult>,
+) -> HawalaResult<Cow<'_, TransactionRequest>> {
+    if !request.auto_gas {
+        return Ok(Cow::Borrowed(request));
+    }
+    
+    let chain_id = request.chain.chain_id()
+        .ok_or_else(|| HawalaError::invalid_input("Invalid EVM chain"))?;
+    let data = request.data.as_deref().unwrap_or("0x");
+    let plain_transfer = data.trim_start_matches("0x").is_empty();
+    
+    // A send_max value depends on the gas limit, so estimate it as zero-value
+    let value = if request.send_max { 0 } else { parse_wei(&request.amount)? };
+    
+    let gas_limit = match estimate_gas(chain_id, &request.from, &request.to, &format!("0x{:x}", value), data) {
+        Ok(estimate) if estimate.is_estimated => estimate.estimated_gas
+            .checked_mul(AUTO_GAS_MARGIN_PERCENT)
+            .map(|gas| gas.div_ceil(100))
+            .ok_or_else(|| HawalaError::invalid_input("Gas estimate overflows"))?,
+        _ if plain_transfer => PLAIN_TRANSFER_GAS,
+        Ok(estimate) => {
+            return Err(HawalaError::network_error(format!(
+                "Gas estimation failed: {}",
+                estimate.error_message.unwrap_or_else(|| "no estimate".to_string())
+            )))
+        }
+        Err(e) => return Err(e),
+    };
+    
+    let mut resolved = request.clone();
+    resolved.gas_limit = Some(gas_limit);
+    Ok(Cow::Owned(resolved))
+}
+
+// Maximum fee an EVM transaction can pay, in wei
+fn evm_max_fee(request: &TransactionRequest) -> HawalaResult<u128> {
+    let gas_limit = request.gas_limit
+        .ok_or_else(|| HawalaError::invalid_input("Gas limit required for EVM transaction"))?;
+    
+    // EIP-1559 when max_fee_per_gas is set, as in ethereum_wallet
+    let price = request.max_fee_per_gas
+        .or(request.gas_price)
+        .ok_or_else(|| HawalaError::invalid_input("Missing gas_price for legacy transaction"))?;
+    
+    Ok(price.for_gas(gas_limit)?.0)
+}
+
+// Parse the request's EIP-2930 access list into the ethers form
+fn evm_access_list(request: &TransactionRequest) -> HawalaResult<Option<AccessList>> {
+    use ethers_core::types::{Address, H256};
+    
+    let Some(items) = &request.access_list else {
+        return Ok(None);
+    };
+    
+    let items = items.iter()
+        .map(|item| {
+            let address = Address::from_str(&item.address)
+                .map_err(|_| HawalaError::invalid_input(format!("Invalid access list address: {}", item.address)))?;
+            let storage_keys = item.storage_keys.iter()
+                .map(|key| H256::from_str(key)
+                    .map_err(|_| HawalaError::invalid_input(format!("Invalid access list storage key: {}", key))))
+                .collect::<HawalaResult<Vec<_>>>()?;
+            Ok(EthersAccessListItem { address, storage_keys })
+        })
+        .collect::<HawalaResult<Vec<_>>>()?;
+    
+    Ok(Some(AccessList(items)))
+}
+
+// Value for an EVM send_max: the balance less the maximum fee, so the
+// transaction is valid at any base fee up to max_fee_per_gas
+fn evm_sweep_value(request: &TransactionRequest) -> HawalaResult<u128> {
+    let balance = request.balance.as_deref()
+        .ok_or_else(|| HawalaError::invalid_input("Balance required for send_max on EVM chains"))?;
+    let balance = parse_wei(balance)?;
+    let fee = evm_max_fee(request)?;
+    
+    match balance.checked_sub(fee) {
+        Some(value) if value > 0 => Ok(value),
+        _ => Err(HawalaError::insufficient_funds(format!(
+            "Balance of {} wei does not cover the maximum fee of {} wei",
+            balance, fee
+        ))),
+    }
+}
+
+// Reject send_max on chains whose builders do not support it
+fn reject_send_max(request: &TransactionRequest) -> HawalaResult<()> {
+    if request.send_max {
+        return Err(HawalaError::invalid_input(format!(
+            "send_max is not supported for {:?}",
+            request.chain
+        )));
+    }
+    Ok(())
+}
+
+// Parse a wei quantity given as decimal or 0x-prefixed hex
+fn parse_wei(value: &str) -> HawalaResult<u128> {
+    Wei::parse(value).map(|wei| wei.0)
+}
+
+/// Nonce and fee fields of a contract call; EIP-1559 when
+/// `max_fee_per_gas` is set, legacy with `gas_price` otherwise
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ContractCallParams {
+    pub chain: Chain,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    #[serde(default)]
+    pub gas_price: Option<Wei>,
+    #[serde(default)]
+    pub max_fee_per_gas: Option<Wei>,
+    #[serde(default)]
+    pub max_priority_fee_per_gas: Option<Wei>,
+}
+
+/// Build an unsigned call to `function_name` on contract `to`
+///
+/// `function_name` is a bare name, or a full signature such as
+/// `"transfer(address,uint256)"` to pick between overloads. `args` are JSON
+/// values parsed against the function's parameter types (see
+/// [`AbiValue::from_json`]). `value` is in wei. The result can be hashed
+/// with `get_ethereum_signing_hash` and signed externally.
+pub fn build_contract_call(
+    abi: &ContractAbi,
+    function_name: &str,
+    args: &[serde_json::Value],
+    to: &str,
+    value: &str,
+    params: &ContractCallParams,
+) -> HawalaResult<UnsignedEthereumTransaction> {
+    let chain_id = params.chain.chain_id()
+        .ok_or_else(|| HawalaError::invalid_input("Invalid EVM chain"))?;
+    let to = match AbiValue::address_from_str(to) {
+        Ok(AbiValue::Address(address)) => address,
+        _ => return Err(HawalaError::invalid_input(format!("Invalid contract address: {}", to))),
+    };
+
+    let function = resolve_contract_function(abi, function_name, args.len())?;
+    let values = function.inputs.iter()
+        .zip(args)
+        .map(|(param, arg)| AbiValue::from_json(arg, &param.param_type).map_err(|e| {
+            HawalaError::invalid_input(format!("Argument '{}' of {}: {}", param.name, function.signature(), e))
+        }))
+        .collect::<HawalaResult<Vec<_>>>()?;
+    let data = AbiEncoder::encode_function_call(function, &values)
+        .map_err(|e| HawalaError::invalid_input(format!("Failed to encode {}: {}", function.signature(), e)))?;
+
+    let (tx_type, gas_price, max_fee_per_gas, max_priority_fee_per_gas) = match (params.max_fee_per_gas, params.gas_price) {
+        (Some(max_fee), _) => {
+            let priority = params.max_priority_fee_per_gas.unwrap_or_default();
+            (EthereumTxType::FeeMarket, None, Some(max_fee.0), Some(priority.0))
+        }
+        (None, Some(gas_price)) => (EthereumTxType::Legacy, Some(gas_price.0), None, None),
+        (None, None) => return Err(HawalaError::invalid_input("Missing gas_price for legacy transaction")),
+    };
+
+    Ok(UnsignedEthereumTransaction {
+        tx_type,
+        chain_id,
+        nonce: params.nonce,
+        gas_price,
+        max_priority_fee_per_gas,
+        max_fee_per_gas,
+        gas_limit: params.gas_limit,
+        to: Some(to),
+        value: parse_wei(value)?,
+        data,
+        access_list: None,
+        authorization_list: None,
+        derivation_path: None,
+    })
+}
+
+// Find the function a contract call names: by exact signature, or by name
+// and argument count when that is unambiguous
+fn resolve_contract_function<'a>(
+    abi: &'a ContractAbi,
+    function_name: &str,
+    arg_count: usize,
+) -> HawalaResult<&'a AbiFunction> {
+    if function_name.contains('(') {
+        let signature: String = function_name.chars().filter(|c| !c.is_whitespace()).collect();
+        return abi.functions.iter()
+            .find(|f| f.signature() == signature)
+            .ok_or_else(|| HawalaError::invalid_input(format!("Function not in ABI: {}", function_name)));
+    }
+
+    let candidates = abi.functions_by_name(function_name);
+    if candidates.is_empty() {
+        return Err(HawalaError::invalid_input(format!("Function not in ABI: {}", function_name)));
+    }
+    let matching: Vec<&AbiFunction> = candidates.iter().copied().filter(|f| f.inputs.len() == arg_count).collect();
+    match matching.as_slice() {
+        [function] => Ok(*function),
+        [] => Err(HawalaError::invalid_input(format!(
+            "{} takes {} argument(s), got {}",
+            function_name,
+            candidates[0].inputs.len(),
+            arg_count
+        ))),
+        overloads => Err(HawalaError::invalid_input(format!(
+            "{} is overloaded; call it by signature: {}",
+            function_name,
+            overloads.iter().map(|f| f.signature()).collect::<Vec<_>>().join(", ")
+        ))),
+    }
+}
+
 /// Build a Litecoin transaction
 pub fn build_litecoin_transaction(request: &TransactionRequest) -> HawalaResult<SignedTransaction> {
+    reject_send_max(request)?;
+    
     let utxos = request.utxos.as_ref()
         .ok_or_else(|| HawalaError::invalid_input("UTXOs required for Litecoin transaction"))?;
     
-    let fee_rate = request.fee_rate
+    let SatPerVb(fee_rate) = request.fee_rate
         .ok_or_else(|| HawalaError::invalid_input("Fee rate required for Litecoin transaction"))?;
     
-    // Convert UTXOs to LitecoinUtxo format
-    let ltc_utxos: Vec<crate::litecoin_wallet::LitecoinUtxo> = utxos.iter().map(|u| {
-        crate::litecoin_wallet::LitecoinUtxo {
-            transaction_hash: u.txid.clone(),
-            index: u.vout,
-            value: u.value,
-            script_hex: None,
-            block_id: u.block_height.map(|h| h as i64),
-        }
-    }).collect();
-    
-    // Parse amount
-    let amount_lits: u64 = if request.amount.contains('.') {
-        let ltc: f64 = request.amount.parse()
-            .map_err(|_| HawalaError::invalid_input("Invalid amount format"))?;
-        (ltc * 100_000_000.0) as u64
-    } else {
-        request.amount.parse()
-            .map_err(|_| HawalaError::invalid_input("Invalid amount format"))?
-    };
+    let ltc_utxos = to_litecoin_utxos(utxos);
+    let amount_lits = parse_utxo_amount(&request.amount)?;
     
     // Get sender address from request
     let sender_address: &str = &request.from;
@@ -158,12 +771,43 @@ pub fn build_litecoin_transaction(request: &TransactionRequest) -> HawalaResult<
                 txid,
                 estimated_fee: Some(format!("{} lits/vB", fee_rate)),
                 size_bytes: None,
+                change: None,
             })
         }
         Err(e) => Err(HawalaError::new(ErrorCode::CryptoError, e.to_string())),
     }
 }
 
+/// Preview a Litecoin transaction: the same input selection and fee as
+/// `build_litecoin_transaction`, without signing
+pub fn preview_litecoin_transaction(request: &TransactionRequest) -> HawalaResult<TransactionPreview> {
+    reject_send_max(request)?;
+    
+    let utxos = request.utxos.as_ref()
+        .ok_or_else(|| HawalaError::invalid_input("UTXOs required for Litecoin transaction"))?;
+    
+    let SatPerVb(fee_rate) = request.fee_rate
+        .ok_or_else(|| HawalaError::invalid_input("Fee rate required for Litecoin transaction"))?;
+    
+    let amount_lits = parse_utxo_amount(&request.amount)?;
+    let plan = crate::litecoin_wallet::plan_litecoin_spend(to_litecoin_utxos(utxos), amount_lits, fee_rate)
+        .map_err(|e| HawalaError::new(ErrorCode::CryptoError, e.to_string()))?;
+    
+    Ok(TransactionPreview {
+        chain: request.chain,
+        fee: plan.fee.to_string(),
+        size_or_gas: Some(plan.vsize),
+        change: plan.change.to_string(),
+        // Litecoin change returns to the sender
+        change_output: (plan.change > 0).then(|| ChangeOutput {
+            address: request.from.clone(),
+            path: String::new(),
+        }),
+        total: (amount_lits + plan.fee).to_string(),
+        inputs_used: plan.inputs.iter().map(|u| format!("{}:{}", u.transaction_hash, u.index)).collect(),
+    })
+}
+
 // Helper to calculate Bitcoin-style txid
 fn calculate_btc_txid(tx_hex: &str) -> String {
     use bitcoin::hashes::{Hash, sha256d};
@@ -196,18 +840,19 @@ fn calculate_eth_txid(tx_hex: &str) -> String {
     format!("0x{}", hex::encode(hash))
 }
 
+/// Fee for a single-signature Solana transaction
+pub const SOLANA_SIGNATURE_FEE_LAMPORTS: u64 = 5000;
+
 /// Build a Solana transaction
 pub fn build_solana_transaction(request: &TransactionRequest) -> HawalaResult<SignedTransaction> {
-    // Parse amount as SOL
-    let amount_sol: f64 = request.amount.parse()
-        .map_err(|_| HawalaError::invalid_input("Invalid SOL amount"))?;
+    reject_send_max(request)?;
     
     let recent_blockhash = request.recent_blockhash.as_ref()
         .ok_or_else(|| HawalaError::invalid_input("Recent blockhash required for Solana transaction"))?;
     
     match crate::solana_wallet::prepare_solana_transaction(
         &request.to,
-        amount_sol,
+        &request.amount,
         recent_blockhash,
         &request.private_key,
     ) {
@@ -216,16 +861,37 @@ pub fn build_solana_transaction(request: &TransactionRequest) -> HawalaResult<Si
                 chain: request.chain,
                 raw_tx, // Base58 encoded
                 txid: "pending".to_string(), // Signature returned on broadcast
-                estimated_fee: Some("5000 lamports".to_string()),
+                estimated_fee: Some(Lamports(SOLANA_SIGNATURE_FEE_LAMPORTS).to_string()),
                 size_bytes: None,
+                change: None,
             })
         }
         Err(e) => Err(HawalaError::new(ErrorCode::CryptoError, e.to_string())),
     }
 }
 
+/// Preview a Solana transfer: one signature's fee, without signing
+pub fn preview_solana_transaction(request: &TransactionRequest) -> HawalaResult<TransactionPreview> {
+    reject_send_max(request)?;
+    
+    // Same conversion as solana_wallet
+    let lamports = parse_decimal_amount(&request.amount, crate::solana_wallet::SOL_DECIMALS)?;
+    
+    Ok(TransactionPreview {
+        chain: request.chain,
+        fee: SOLANA_SIGNATURE_FEE_LAMPORTS.to_string(),
+        size_or_gas: None,
+        change: "0".to_string(),
+        change_output: None,
+        total: (lamports + SOLANA_SIGNATURE_FEE_LAMPORTS as u128).to_string(),
+        inputs_used: Vec::new(),
+    })
+}
+
 /// Build an XRP transaction
 pub fn build_xrp_transaction(request: &TransactionRequest) -> HawalaResult<SignedTransaction> {
+    reject_send_max(request)?;
+    
     // Parse amount as drops (1 XRP = 1,000,000 drops)
     let amount_drops: u64 = if request.amount.contains('.') {
         let xrp: f64 = request.amount.parse()
@@ -253,8 +919,734 @@ pub fn build_xrp_transaction(request: &TransactionRequest) -> HawalaResult<Signe
                 txid: "pending".to_string(), // Hash returned on broadcast
                 estimated_fee: Some("12 drops".to_string()),
                 size_bytes: None,
+                change: None,
             })
         }
         Err(e) => Err(HawalaError::new(ErrorCode::CryptoError, e.to_string())),
     }
 }
+
+/// Default fee, gas and storage for a plain Tezos transfer. Storage covers
+/// allocating the destination if it has never held tez
+const TEZOS_DEFAULT_FEE_MUTEZ: u64 = 1420;
+const TEZOS_DEFAULT_GAS_LIMIT: u64 = 1527;
+const TEZOS_DEFAULT_STORAGE_LIMIT: u64 = 257;
+
+/// Build a Tezos transaction
+///
+/// Forges a `transaction` operation and signs it with the source's ed25519
+/// key. `raw_tx` is the hex to inject; `txid` is the operation hash.
+pub fn build_tezos_transaction(request: &TransactionRequest) -> HawalaResult<SignedTransaction> {
+    reject_send_max(request)?;
+
+    // A decimal amount is XTZ, an integer is mutez (1 XTZ = 1,000,000 mutez)
+    let amount_mutez: u64 = if request.amount.contains('.') {
+        parse_decimal_amount(&request.amount, 6)?
+            .try_into()
+            .map_err(|_| HawalaError::invalid_input("XTZ amount too large"))?
+    } else {
+        request.amount.parse()
+            .map_err(|_| HawalaError::invalid_input("Invalid mutez amount"))?
+    };
+
+    let transfer = crate::tezos_wallet::TezosTransfer {
+        branch: request.branch.clone()
+            .ok_or_else(|| HawalaError::invalid_input("Branch (block hash) required for Tezos transaction"))?,
+        source: request.from.clone(),
+        destination: request.to.clone(),
+        fee: request.fee_mutez.unwrap_or(TEZOS_DEFAULT_FEE_MUTEZ),
+        counter: request.counter
+            .ok_or_else(|| HawalaError::invalid_input("Counter required for Tezos transaction"))?,
+        gas_limit: request.gas_limit.unwrap_or(TEZOS_DEFAULT_GAS_LIMIT),
+        storage_limit: request.storage_limit.unwrap_or(TEZOS_DEFAULT_STORAGE_LIMIT),
+        amount: amount_mutez,
+    };
+
+    let signed = crate::tezos_wallet::prepare_tezos_transaction(&transfer, &request.private_key)
+        .map_err(|e| HawalaError::new(ErrorCode::CryptoError, e))?;
+
+    Ok(SignedTransaction {
+        chain: request.chain,
+        raw_tx: signed.signed_hex,
+        txid: signed.operation_hash,
+        estimated_fee: Some(format!("{} mutez", transfer.fee)),
+        size_bytes: None,
+        change: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::bip32::{DerivationPath, Xpriv, Xpub};
+    use bitcoin::secp256k1::Secp256k1;
+    use bitcoin::Network;
+
+    use crate::tx::test_fixtures::{bitcoin_request, MNEMONIC};
+
+    fn request_with(account_xpub: Option<String>, change_address: Option<String>) -> TransactionRequest {
+        let mut request = bitcoin_request();
+        request.account_xpub = account_xpub;
+        request.change_address = change_address;
+        request.change_index = Some(3);
+        request
+    }
+
+    fn account_xpub() -> String {
+        let seed = bip39::Mnemonic::parse(MNEMONIC).unwrap().to_seed("");
+        let secp = Secp256k1::new();
+        let master = Xpriv::new_master(Network::Bitcoin, &seed).unwrap();
+        let account = master
+            .derive_priv(&secp, &DerivationPath::from_str("m/84'/0'/0'").unwrap())
+            .unwrap();
+        Xpub::from_priv(&secp, &account).to_string()
+    }
+
+    #[test]
+    fn test_change_goes_to_change_chain() {
+        let tx = build_bitcoin_transaction(&request_with(Some(account_xpub()), None)).unwrap();
+        let change = tx.change.expect("change output");
+
+        let seed = bip39::Mnemonic::parse(MNEMONIC).unwrap().to_seed("");
+        let expected = crate::wallet::derive_change_address(
+            crate::wallet::ScanSource::Seed(&seed),
+            Chain::Bitcoin,
+            3,
+        )
+        .unwrap();
+
+        assert_eq!(change.path, "m/84'/0'/0'/1/3");
+        assert_eq!(change, expected);
+        assert!(has_output_to(&tx.raw_tx, &change.address));
+
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        assert_ne!(change.address, keys.bitcoin.address);
+        assert!(!has_output_to(&tx.raw_tx, &keys.bitcoin.address));
+
+        // An xpub without a change index is rejected rather than guessed
+        let mut request = request_with(Some(account_xpub()), None);
+        request.change_index = None;
+        let err = build_bitcoin_transaction(&request).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+    }
+
+    #[test]
+    fn test_explicit_change_address() {
+        let explicit = "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el".to_string();
+        let tx = build_bitcoin_transaction(&request_with(Some(account_xpub()), Some(explicit.clone()))).unwrap();
+
+        let change = tx.change.expect("change output");
+        assert_eq!(change.address, explicit);
+        assert!(change.path.is_empty());
+    }
+
+    /// Parent paying 100,000 sats to the test wallet's P2WPKH address
+    fn stuck_parent(fee: u64) -> crate::cpfp::StuckTransaction {
+        use bitcoin::consensus::encode::serialize;
+        use bitcoin::{absolute::LockTime, transaction::Version, Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid, Witness};
+
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let own = bitcoin::Address::from_str(&keys.bitcoin.address).unwrap().assume_checked();
+        let parent = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(Txid::from_str(&"b".repeat(64)).unwrap(), 1),
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::from_slice(&[vec![0x30; 72], vec![0x02; 33]]),
+            }],
+            output: vec![
+                TxOut {
+                    value: Amount::from_sat(250_000),
+                    script_pubkey: bitcoin::Address::from_str("bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g")
+                        .unwrap()
+                        .assume_checked()
+                        .script_pubkey(),
+                },
+                TxOut { value: Amount::from_sat(100_000), script_pubkey: own.script_pubkey() },
+            ],
+        };
+
+        crate::cpfp::StuckTransaction::new(
+            &parent.compute_txid().to_string(),
+            &hex::encode(serialize(&parent)),
+            parent.vsize() as u64,
+            fee,
+        )
+    }
+
+    #[test]
+    fn test_cpfp_child_reaches_package_rate() {
+        use bitcoin::consensus::encode::deserialize;
+
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let parent = stuck_parent(141);
+        let destination = "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el";
+
+        let result = build_cpfp_child(&parent, 1, &keys.bitcoin.private_wif, 25.0, destination).unwrap();
+
+        let child: bitcoin::Transaction = deserialize(&hex::decode(&result.transaction.raw_tx).unwrap()).unwrap();
+        assert_eq!(child.input.len(), 1);
+        assert_eq!(child.input[0].previous_output.txid.to_string(), parent.txid);
+        assert_eq!(child.input[0].previous_output.vout, 1);
+        assert!(has_output_to(&result.transaction.raw_tx, destination));
+
+        // Fee and package rate measured on the signed child
+        let child_fee = 100_000 - child.output[0].value.to_sat();
+        let actual_vsize = child.vsize() as u64;
+        assert_eq!(child_fee, result.child_fee);
+        assert_eq!(actual_vsize, result.child_vsize);
+        let package_rate = (parent.fee + child_fee) as f64 / (parent.vsize + actual_vsize) as f64;
+        assert!(package_rate >= 25.0, "package rate {} below target", package_rate);
+        assert!(package_rate < 25.5, "package rate {} overpays", package_rate);
+        assert!(result.package_fee_rate >= 25.0);
+    }
+
+    #[test]
+    fn test_cpfp_child_rejects_foreign_output_and_dust() {
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let parent = stuck_parent(141);
+        let destination = "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el";
+
+        // Output 0 pays someone else
+        let err = build_cpfp_child(&parent, 0, &keys.bitcoin.private_wif, 25.0, destination).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+
+        // 100,000 sats cannot lift the package to 1,000 sat/vB
+        let err = build_cpfp_child(&parent, 1, &keys.bitcoin.private_wif, 1_000.0, destination).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InsufficientFunds);
+
+        assert!(build_cpfp_child(&parent, 5, &keys.bitcoin.private_wif, 25.0, destination).is_err());
+    }
+
+    // Fee actually paid by a signed UTXO transaction spending `input_value`
+    fn paid_fee(raw_tx: &str, input_value: u64) -> u64 {
+        let tx: bitcoin::Transaction =
+            bitcoin::consensus::encode::deserialize(&hex::decode(raw_tx).unwrap()).unwrap();
+        input_value - tx.output.iter().map(|o| o.value.to_sat()).sum::<u64>()
+    }
+
+    #[test]
+    fn test_bitcoin_preview_matches_build() {
+        let request = request_with(Some(account_xpub()), None);
+
+        let preview = preview_bitcoin_transaction(&request).unwrap();
+        let built = build_bitcoin_transaction(&request).unwrap();
+
+        assert_eq!(preview.fee, paid_fee(&built.raw_tx, 100_000).to_string());
+        assert_eq!(preview.change_output, built.change);
+        assert_eq!(preview.inputs_used, vec![format!("{}:0", "a".repeat(64))]);
+        let fee: u64 = preview.fee.parse().unwrap();
+        let change: u64 = preview.change.parse().unwrap();
+        assert_eq!(preview.total, (50_000 + fee).to_string());
+        assert_eq!(50_000 + fee + change, 100_000);
+    }
+
+    #[test]
+    fn test_bitcoin_preview_dust_change_goes_to_fee() {
+        let mut request = request_with(None, None);
+        // 100,000 - 99,400 - 280 (140 vB at 2 sat/vB) leaves 320 sats of dust change
+        request.amount = "99400".to_string();
+
+        let preview = preview_bitcoin_transaction(&request).unwrap();
+        let built = build_bitcoin_transaction(&request).unwrap();
+
+        assert_eq!(preview.change, "0");
+        assert!(preview.change_output.is_none());
+        assert_eq!(preview.fee, "600");
+        assert_eq!(preview.fee, paid_fee(&built.raw_tx, 100_000).to_string());
+    }
+
+    #[test]
+    fn test_litecoin_preview_matches_build() {
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let mut request = request_with(None, None);
+        request.chain = Chain::Litecoin;
+        request.from = keys.litecoin.address.clone();
+        request.to = keys.litecoin.address.clone();
+        request.private_key = keys.litecoin.private_wif.clone();
+
+        let preview = preview_litecoin_transaction(&request).unwrap();
+        let built = build_litecoin_transaction(&request).unwrap();
+
+        assert_eq!(preview.fee, paid_fee(&built.raw_tx, 100_000).to_string());
+        assert_eq!(preview.size_or_gas, Some(140));
+    }
+
+    #[test]
+    fn test_evm_preview_matches_build() {
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let mut request = request_with(None, None);
+        request.chain = Chain::Ethereum;
+        request.from = keys.ethereum.address.clone();
+        request.to = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string();
+        request.amount = "1000000000000000".to_string();
+        request.private_key = keys.ethereum.private_hex.clone();
+        request.utxos = None;
+        request.nonce = Some(0);
+        request.gas_limit = Some(21_000);
+        request.max_fee_per_gas = Some(Wei(30_000_000_000));
+        request.max_priority_fee_per_gas = Some(Wei(1_000_000_000));
+        request.allow_high_fee = true;
+
+        let preview = preview_evm_transaction(&request).unwrap();
+        let built = build_evm_transaction(&request).unwrap();
+
+        assert_eq!(preview.fee, "630000000000000");
+        assert_eq!(built.estimated_fee, Some(format!("{} wei", preview.fee)));
+        assert_eq!(preview.size_or_gas, Some(21_000));
+        assert_eq!(preview.total, "1630000000000000");
+    }
+
+    #[test]
+    fn test_bitcoin_send_max_spends_everything() {
+        let mut request = request_with(Some(account_xpub()), None);
+        request.send_max = true;
+        request.amount = "0".to_string();
+        let mut second = request.utxos.as_ref().unwrap()[0].clone();
+        second.txid = "b".repeat(64);
+        second.value = 50_000;
+        request.utxos.as_mut().unwrap().push(second);
+
+        let preview = preview_bitcoin_transaction(&request).unwrap();
+        let built = build_bitcoin_transaction(&request).unwrap();
+
+        // 10 + 2 × 68 + 31 = 177 vB at 2 sat/vB
+        assert_eq!(preview.fee, "354");
+        assert_eq!(preview.change, "0");
+        assert!(preview.change_output.is_none());
+        assert_eq!(preview.total, "150000");
+        assert_eq!(preview.inputs_used.len(), 2);
+
+        let tx: bitcoin::Transaction =
+            bitcoin::consensus::encode::deserialize(&hex::decode(&built.raw_tx).unwrap()).unwrap();
+        assert_eq!(tx.input.len(), 2);
+        assert_eq!(tx.output.len(), 1);
+        assert_eq!(tx.output[0].value.to_sat(), 150_000 - 354);
+        assert!(has_output_to(&built.raw_tx, &request.to));
+        assert!(built.change.is_none());
+    }
+
+    #[test]
+    fn test_bitcoin_send_max_below_fee() {
+        let mut request = request_with(None, None);
+        request.send_max = true;
+        request.utxos.as_mut().unwrap()[0].value = 200;
+
+        let err = build_bitcoin_transaction(&request).unwrap_err();
+        assert!(err.message.contains("does not cover"), "{}", err.message);
+        assert!(preview_bitcoin_transaction(&request).is_err());
+    }
+
+    fn evm_request() -> TransactionRequest {
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let mut request = request_with(None, None);
+        request.chain = Chain::Ethereum;
+        request.from = keys.ethereum.address.clone();
+        request.to = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string();
+        request.private_key = keys.ethereum.private_hex.clone();
+        request.utxos = None;
+        request.nonce = Some(0);
+        request.gas_limit = Some(21_000);
+        request.max_fee_per_gas = Some(Wei(30_000_000_000));
+        request.max_priority_fee_per_gas = Some(Wei(1_000_000_000));
+        // Fixture amounts are tiny next to a 30 gwei fee
+        request.allow_high_fee = true;
+        request
+    }
+
+    #[test]
+    fn test_evm_send_max_leaves_nothing() {
+        let mut request = evm_request();
+        request.send_max = true;
+        request.amount = "0".to_string();
+        request.balance = Some("1000000000000000".to_string());
+
+        // 1e15 - 21,000 × 30 gwei
+        let preview = preview_evm_transaction(&request).unwrap();
+        assert_eq!(preview.fee, "630000000000000");
+        assert_eq!(preview.total, "1000000000000000");
+
+        // Signs exactly the same transaction as sending the remainder explicitly
+        let built = build_evm_transaction(&request).unwrap();
+        let mut explicit = evm_request();
+        explicit.amount = "370000000000000".to_string();
+        assert_eq!(built.raw_tx, build_evm_transaction(&explicit).unwrap().raw_tx);
+    }
+
+    #[test]
+    fn test_evm_send_max_errors() {
+        let mut request = evm_request();
+        request.send_max = true;
+
+        // No balance given
+        assert_eq!(build_evm_transaction(&request).unwrap_err().code, ErrorCode::InvalidInput);
+
+        // Balance below the maximum fee
+        request.balance = Some("629999999999999".to_string());
+        let err = preview_evm_transaction(&request).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InsufficientFunds);
+        assert!(build_evm_transaction(&request).is_err());
+
+        // Balance exactly equal to the fee leaves nothing to send
+        request.balance = Some("630000000000000".to_string());
+        assert!(build_evm_transaction(&request).is_err());
+    }
+
+    #[test]
+    fn test_evm_access_list_transaction() {
+        use crate::signing::preimage::ethereum::{AccessListEntry, EthereumTxType, UnsignedEthereumTransaction};
+        use crate::signing::get_ethereum_signing_hash;
+        use ethers_core::types::{Signature, H256, U256};
+
+        let contract = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+        let slot = format!("0x{:064x}", 3);
+        let mut request = evm_request();
+        request.amount = "1000".to_string();
+        request.max_fee_per_gas = None;
+        request.max_priority_fee_per_gas = None;
+        request.gas_price = Some(Wei(20_000_000_000));
+        request.gas_limit = Some(30_000);
+        request.access_list = Some(vec![AccessListItem {
+            address: contract.to_string(),
+            storage_keys: vec![slot],
+        }]);
+
+        let built = build_evm_transaction(&request).unwrap();
+        assert!(built.raw_tx.starts_with("0x01"), "{}", built.raw_tx);
+
+        let decoded = crate::tx::decode_transaction(Chain::Ethereum, &built.raw_tx).unwrap();
+        assert_eq!(decoded.tx_type, "eip2930");
+        assert_eq!(decoded.chain_id, Some(1));
+        assert_eq!(decoded.txid, built.txid);
+
+        // The signature must be over the preimage module's type 0x01 hash
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hex::decode(&contract[2..]).unwrap());
+        let mut key = [0u8; 32];
+        key[31] = 3;
+        let mut to = [0u8; 20];
+        to.copy_from_slice(&hex::decode(&request.to[2..]).unwrap());
+        let unsigned = UnsignedEthereumTransaction {
+            tx_type: EthereumTxType::AccessList,
+            chain_id: 1,
+            nonce: 0,
+            gas_price: Some(20_000_000_000),
+            max_priority_fee_per_gas: None,
+            max_fee_per_gas: None,
+            gas_limit: 30_000,
+            to: Some(to),
+            value: 1000,
+            data: vec![],
+            access_list: Some(vec![AccessListEntry { address, storage_keys: vec![key] }]),
+            authorization_list: None,
+            derivation_path: None,
+        };
+        let hash = get_ethereum_signing_hash(&unsigned).unwrap().hash;
+
+        let raw = hex::decode(&built.raw_tx[2..]).unwrap();
+        let fields = crate::tx::replay_protection::rlp_list_fields(&raw[1..]).unwrap();
+        assert_eq!(fields.len(), 11);
+        let signature = Signature {
+            r: U256::from_big_endian(fields[9]),
+            s: U256::from_big_endian(fields[10]),
+            v: crate::tx::replay_protection::rlp_scalar(fields[8]).unwrap(),
+        };
+        let signer = signature.recover(H256(hash)).unwrap();
+        assert!(format!("{:?}", signer).eq_ignore_ascii_case(&request.from));
+    }
+
+    #[test]
+    fn test_evm_access_list_rejects_bad_entries() {
+        let mut request = evm_request();
+        request.access_list = Some(vec![AccessListItem {
+            address: "0x1234".to_string(),
+            storage_keys: vec![],
+        }]);
+        assert_eq!(build_evm_transaction(&request).unwrap_err().code, ErrorCode::InvalidInput);
+
+        request.access_list = Some(vec![AccessListItem {
+            address: "0xdAC17F958D2ee523a2206206994597C13D831ec7".to_string(),
+            storage_keys: vec!["0x03".to_string()],
+        }]);
+        assert_eq!(build_evm_transaction(&request).unwrap_err().code, ErrorCode::InvalidInput);
+    }
+
+    fn estimated(gas: u64) -> GasEstimateResult {
+        GasEstimateResult { estimated_gas: gas, recommended_gas: gas, is_estimated: true, error_message: None }
+    }
+
+    fn estimation_failed(_: u64, _: &str, _: &str, _: &str, _: &str) -> HawalaResult<GasEstimateResult> {
+        Ok(GasEstimateResult {
+            estimated_gas: 21_000,
+            recommended_gas: 25_200,
+            is_estimated: false,
+            error_message: Some("execution reverted".to_string()),
+        })
+    }
+
+    #[test]
+    fn test_evm_auto_gas_contract_call() {
+        let token = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+        let mut request = evm_request();
+        request.to = token.to_string();
+        request.amount = "0".to_string();
+        request.gas_limit = None;
+        request.auto_gas = true;
+        request.data = Some(format!("0xa9059cbb{:0>64}{:064x}", "d8da6bf26964af9d7eed9e03e53415d37aa96045", 1_000_000));
+
+        let estimate = |chain_id: u64, from: &str, to: &str, value: &str, data: &str| {
+            assert_eq!(chain_id, 1);
+            assert_eq!(from, request.from);
+            assert_eq!(to, token);
+            assert_eq!(value, "0x0");
+            assert!(data.starts_with("0xa9059cbb"));
+            Ok(estimated(51_234))
+        };
+
+        // 51234 × 1.2, rounded up
+        let preview = preview_evm_transaction_with(&request, estimate).unwrap();
+        assert_eq!(preview.size_or_gas, Some(61_481));
+        let built = build_evm_transaction_with(&request, estimate).unwrap();
+        assert_eq!(built.estimated_fee, Some(format!("{} wei", 61_481u128 * 30_000_000_000)));
+
+        let mut explicit = request.clone();
+        explicit.auto_gas = false;
+        explicit.gas_limit = Some(61_481);
+        assert_eq!(built.raw_tx, build_evm_transaction(&explicit).unwrap().raw_tx);
+
+        // No fallback guess for a contract call
+        assert!(build_evm_transaction_with(&request, estimation_failed).is_err());
+    }
+
+    #[test]
+    fn test_evm_auto_gas_plain_transfer_fallback() {
+        let mut request = evm_request();
+        request.amount = "1000".to_string();
+        request.gas_limit = None;
+        request.auto_gas = true;
+
+        let preview = preview_evm_transaction_with(&request, estimation_failed).unwrap();
+        assert_eq!(preview.size_or_gas, Some(21_000));
+
+        let offline = |_: u64, _: &str, _: &str, _: &str, _: &str| -> HawalaResult<GasEstimateResult> {
+            Err(HawalaError::network_error("offline"))
+        };
+        assert_eq!(preview_evm_transaction_with(&request, offline).unwrap().size_or_gas, Some(21_000));
+
+        // Without auto_gas the limit is still required
+        request.auto_gas = false;
+        assert!(preview_evm_transaction_with(&request, |_: u64, _: &str, _: &str, _: &str, _: &str| Ok(estimated(1))).is_err());
+    }
+
+    #[test]
+    fn test_send_max_rejected_on_other_chains() {
+        let mut request = request_with(None, None);
+        request.chain = Chain::Litecoin;
+        request.send_max = true;
+        assert!(build_litecoin_transaction(&request).is_err());
+        assert!(preview_litecoin_transaction(&request).is_err());
+    }
+
+    #[test]
+    fn test_bitcoin_fee_guard() {
+        // 50,000 sats at 2 sat/vB passes
+        let request = request_with(None, None);
+        assert!(build_bitcoin_transaction(&request).is_ok());
+
+        // A fat-fingered 200 sat/vB pays ~28,000 sats on 50,000
+        let mut request = request_with(None, None);
+        request.fee_rate = Some(SatPerVb(200));
+        let err = build_bitcoin_transaction(&request).unwrap_err();
+        assert_eq!(err.code, ErrorCode::FeeTooHigh);
+        let fee: u64 = preview_bitcoin_transaction(&request).unwrap().fee.parse().unwrap();
+        assert_eq!(err.details, Some(fee.to_string()));
+
+        request.allow_high_fee = true;
+        assert!(build_bitcoin_transaction(&request).is_ok());
+
+        // Absolute cap
+        let mut request = request_with(None, None);
+        request.fee_guard = Some(FeeGuard { max_fee_percent: 50, max_fee: Some("100".to_string()) });
+        assert_eq!(build_bitcoin_transaction(&request).unwrap_err().code, ErrorCode::FeeTooHigh);
+    }
+
+    #[test]
+    fn test_evm_fee_guard() {
+        // 0.01 ETH with a 0.00063 ETH maximum fee passes
+        let mut request = evm_request();
+        request.allow_high_fee = false;
+        request.amount = "10000000000000000".to_string();
+        assert!(build_evm_transaction(&request).is_ok());
+
+        // 10x the gas price makes the fee 63% of the amount
+        request.max_fee_per_gas = Some(Wei(300_000_000_000));
+        let err = build_evm_transaction(&request).unwrap_err();
+        assert_eq!(err.code, ErrorCode::FeeTooHigh);
+        assert_eq!(err.details.as_deref(), Some("6300000000000000"));
+
+        request.fee_guard = Some(FeeGuard { max_fee_percent: 100, max_fee: None });
+        assert!(build_evm_transaction(&request).is_ok());
+
+        request.fee_guard = Some(FeeGuard { max_fee_percent: 100, max_fee: Some("1000000000000000".to_string()) });
+        assert_eq!(build_evm_transaction(&request).unwrap_err().code, ErrorCode::FeeTooHigh);
+
+        // Token transfers send no value, so only the cap applies
+        let mut request = evm_request();
+        request.allow_high_fee = false;
+        request.amount = "0".to_string();
+        request.data = Some(format!("0xa9059cbb{:0>64}{:064x}", "d8da6bf26964af9d7eed9e03e53415d37aa96045", 1_000_000));
+        assert!(build_evm_transaction(&request).is_ok());
+    }
+
+    #[test]
+    fn test_solana_preview_matches_build() {
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let mut request = request_with(None, None);
+        request.chain = Chain::Solana;
+        request.to = keys.solana.public_key_base58.clone();
+        request.amount = "0.5".to_string();
+        request.private_key = keys.solana.private_key_base58.clone();
+        request.recent_blockhash = Some("11111111111111111111111111111111".to_string());
+
+        let preview = preview_solana_transaction(&request).unwrap();
+        let built = build_solana_transaction(&request).unwrap();
+
+        assert_eq!(built.estimated_fee, Some(format!("{} lamports", preview.fee)));
+        assert_eq!(preview.total, "500005000");
+    }
+
+    #[test]
+    fn test_dry_run_defaults_off() {
+        let request: TransactionRequest = serde_json::from_str(
+            r#"{"chain":"bitcoin","from":"a","to":"b","amount":"1","private_key":"k"}"#,
+        ).unwrap();
+        assert!(!request.dry_run);
+    }
+
+    #[test]
+    fn test_no_change_source_keeps_legacy_behavior() {
+        let tx = build_bitcoin_transaction(&request_with(None, None)).unwrap();
+        assert!(tx.change.is_none());
+
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        assert!(has_output_to(&tx.raw_tx, &keys.bitcoin.address));
+    }
+
+    #[test]
+    fn test_build_contract_call_transfer() {
+        let abi = crate::abi::KnownAbis::erc20();
+        let params = ContractCallParams {
+            chain: Chain::Ethereum,
+            nonce: 7,
+            gas_limit: 65_000,
+            gas_price: None,
+            max_fee_per_gas: Some(Wei(30_000_000_000)),
+            max_priority_fee_per_gas: Some(Wei(1_000_000_000)),
+        };
+        let args = [
+            serde_json::json!("0x9858EfFD232B4033E47d90003D41EC34EcaEda94"),
+            serde_json::json!("1000000"),
+        ];
+
+        let tx = build_contract_call(
+            &abi,
+            "transfer",
+            &args,
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "0",
+            &params,
+        ).unwrap();
+
+        assert_eq!(
+            hex::encode(&tx.data),
+            "a9059cbb\
+             0000000000000000000000009858effd232b4033e47d90003d41ec34ecaeda94\
+             00000000000000000000000000000000000000000000000000000000000f4240"
+        );
+        assert_eq!(tx.tx_type, EthereumTxType::FeeMarket);
+        assert_eq!(tx.chain_id, 1);
+        assert_eq!(tx.nonce, 7);
+        assert_eq!(tx.max_fee_per_gas, Some(30_000_000_000));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(1_000_000_000));
+        assert_eq!(tx.value, 0);
+
+        // A JSON number works too, and the signature form picks the same function
+        let by_signature = build_contract_call(
+            &abi,
+            "transfer(address, uint256)",
+            &[args[0].clone(), serde_json::json!(1_000_000)],
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "0",
+            &params,
+        ).unwrap();
+        assert_eq!(by_signature.data, tx.data);
+
+        // Over FFI the fee fields are wei amounts, as decimal or hex strings
+        let from_json: ContractCallParams = serde_json::from_value(serde_json::json!({
+            "chain": "ethereum",
+            "nonce": 7,
+            "gas_limit": 65000,
+            "max_fee_per_gas": "30000000000",
+            "max_priority_fee_per_gas": "0x3b9aca00"
+        })).unwrap();
+        assert_eq!(from_json.max_fee_per_gas, params.max_fee_per_gas);
+        assert_eq!(from_json.max_priority_fee_per_gas, params.max_priority_fee_per_gas);
+        assert_eq!(from_json.gas_price, None);
+        assert!(serde_json::from_value::<ContractCallParams>(serde_json::json!({
+            "chain": "ethereum",
+            "nonce": 7,
+            "gas_limit": 65000,
+            "gas_price": "30 gwei"
+        })).is_err());
+    }
+
+    #[test]
+    fn test_build_contract_call_rejects_bad_args() {
+        let abi = crate::abi::KnownAbis::erc20();
+        let params = ContractCallParams {
+            chain: Chain::Ethereum,
+            nonce: 0,
+            gas_limit: 65_000,
+            gas_price: Some(Wei(1)),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        };
+        let token = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        let recipient = serde_json::json!("0x9858EfFD232B4033E47d90003D41EC34EcaEda94");
+
+        let legacy = build_contract_call(&abi, "transfer", &[recipient.clone(), serde_json::json!("1")], token, "0", &params).unwrap();
+        assert_eq!(legacy.tx_type, EthereumTxType::Legacy);
+
+        assert!(build_contract_call(&abi, "transfer", std::slice::from_ref(&recipient), token, "0", &params).is_err());
+        assert!(build_contract_call(&abi, "transfer", &[recipient.clone(), serde_json::json!("-1")], token, "0", &params).is_err());
+        assert!(build_contract_call(&abi, "transfer", &[serde_json::json!("0x1234"), serde_json::json!("1")], token, "0", &params).is_err());
+        assert!(build_contract_call(&abi, "mint", &[], token, "0", &params).is_err());
+    }
+
+    #[test]
+    fn test_build_tezos_transaction() {
+        let mut request = request_with(None, None);
+        request.chain = Chain::Tezos;
+        request.from = "tz1MsZxMSJdiUV9hVs4UKAMrXtksDvxWAZe2".to_string();
+        request.to = "tz1Qr9uevaimfiPS6X1otehsKrwvZjX7bsyL".to_string();
+        request.private_key = "0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20".to_string();
+        request.amount = "2.5".to_string();
+        request.utxos = None;
+        request.branch = Some("BMZYTcuD2WXFq3XGTAuAccCfFpytJpv7eZHYYDe1pGXFybAFWrw".to_string());
+        request.counter = Some(8_123_456);
+
+        let signed = build_tezos_transaction(&request).unwrap();
+        assert_eq!(signed.txid, "opMsDMoadfRN7HpCZqL2GVDS7nrT2L2YyFGm4FRBzsKPJFUBM3E");
+        assert!(signed.raw_tx.starts_with("f38c764c8aa00b6578f4254a4dc6d9b50f88fa926e270ea7859bd1b707cd86626c00"));
+        assert_eq!(signed.estimated_fee.as_deref(), Some("1420 mutez"));
+
+        // Same amount in mutez
+        request.amount = "2500000".to_string();
+        assert_eq!(build_tezos_transaction(&request).unwrap().raw_tx, signed.raw_tx);
+
+        request.counter = None;
+        assert!(build_tezos_transaction(&request).is_err());
+    }
+}
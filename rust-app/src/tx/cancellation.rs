@@ -5,6 +5,7 @@
 //! - Ethereum/EVM: Nonce replacement - send 0 value to self with same nonce, higher gas
 
 use crate::error::{HawalaError, HawalaResult};
+use crate::fees::units::{sat_per_vb_to_total, wei_to_gwei, SatPerVb, Wei, WEI_PER_GWEI};
 use crate::types::*;
 use crate::tx::{signer, broadcaster};
 use crate::tx::signer::{SignParams, BitcoinSignParams, LitecoinSignParams, EthereumSignParams, UtxoInput};
@@ -115,7 +116,7 @@ pub fn cancel_bitcoin_rbf(request: &BitcoinCancelRequest) -> HawalaResult<Cancel
     
     // Estimate transaction size (1-in-1-out P2WPKH ~110 vB, +68 vB per additional input)
     let estimated_vsize = 110 + (request.utxos.len().saturating_sub(1) * 68);
-    let new_fee = request.new_fee_rate * estimated_vsize as u64;
+    let new_fee = sat_per_vb_to_total(SatPerVb(request.new_fee_rate), estimated_vsize as u64)?.0;
     
     // Calculate output value
     let output_value = total_input.saturating_sub(new_fee);
@@ -271,7 +272,8 @@ pub fn cancel_evm_nonce(request: &EvmCancelRequest) -> HawalaResult<Cancellation
         )));
     }
     
-    let chain = chain_from_id(request.chain_id)?;
+    let chain = Chain::from_chain_id(request.chain_id)
+        .ok_or_else(|| HawalaError::invalid_input(format!("Unknown chain ID: {}", request.chain_id)))?;
     let use_eip1559 = supports_eip1559(request.chain_id);
     
     // Build EthereumSignParams
@@ -314,7 +316,7 @@ pub fn cancel_evm_nonce(request: &EvmCancelRequest) -> HawalaResult<Cancellation
         original_txid: request.original_txid.clone(),
         replacement_txid: Some(broadcast_result.txid),
         method: CancellationMethod::NonceReplace,
-        new_fee_rate: (new_gas / 1_000_000_000) as u64, // Convert to Gwei
+        new_fee_rate: (new_gas / WEI_PER_GWEI) as u64, // Convert to Gwei
         message: format!(
             "Transaction cancelled. Nonce {} consumed.",
             request.nonce
@@ -340,7 +342,8 @@ pub fn speed_up_evm(request: &EvmSpeedUpRequest) -> HawalaResult<CancellationRes
         )));
     }
     
-    let chain = chain_from_id(request.chain_id)?;
+    let chain = Chain::from_chain_id(request.chain_id)
+        .ok_or_else(|| HawalaError::invalid_input(format!("Unknown chain ID: {}", request.chain_id)))?;
     let use_eip1559 = supports_eip1559(request.chain_id);
     
     // Determine gas limit based on data
@@ -389,10 +392,10 @@ pub fn speed_up_evm(request: &EvmSpeedUpRequest) -> HawalaResult<CancellationRes
         original_txid: request.original_txid.clone(),
         replacement_txid: Some(broadcast_result.txid),
         method: CancellationMethod::NonceSpeedUp,
-        new_fee_rate: (new_gas / 1_000_000_000) as u64,
+        new_fee_rate: (new_gas / WEI_PER_GWEI) as u64,
         message: format!(
             "Transaction sped up with {} Gwei gas",
-            new_gas / 1_000_000_000
+            wei_to_gwei(Wei(new_gas))
         ),
     })
 }
@@ -466,21 +469,6 @@ fn calculate_priority_fee(max_fee: u128, chain_id: u64) -> u128 {
     calculated.max(2_500_000_000) // At least 2.5 Gwei
 }
 
-/// Convert chain ID to Chain enum
-fn chain_from_id(chain_id: u64) -> HawalaResult<Chain> {
-    match chain_id {
-        1 => Ok(Chain::Ethereum),
-        11155111 => Ok(Chain::EthereumSepolia),
-        56 => Ok(Chain::Bnb),
-        137 => Ok(Chain::Polygon),
-        42161 => Ok(Chain::Arbitrum),
-        10 => Ok(Chain::Optimism),
-        8453 => Ok(Chain::Base),
-        43114 => Ok(Chain::Avalanche),
-        _ => Err(HawalaError::invalid_input(format!("Unknown chain ID: {}", chain_id))),
-    }
-}
-
 /// Format satoshis for display
 fn format_satoshis(sats: u64) -> String {
     let btc = sats as f64 / 100_000_000.0;
@@ -0,0 +1,496 @@
+//! Transaction Decoding
+//!
+//! Summarizes a raw transaction (e.g. one handed over by a dApp) before it is
+//! signed or broadcast: who gets paid, how much, the fee where it can be
+//! known from the transaction alone, and the contract method for known EVM
+//! selectors. EIP-7702 transactions also list their authorizations, so the
+//! UI can warn before an account's code is delegated.
+
+use crate::abi::KnownSelectors;
+use crate::eip7702::{recover_authorization_signer, Authorization};
+use crate::error::{HawalaError, HawalaResult};
+use crate::types::Chain;
+use crate::utils::{keccak256, to_checksum_address};
+use ethers_core::types::U256;
+use serde::Serialize;
+
+use super::replay_protection::{rlp_list_fields, rlp_scalar, rlp_sequence};
+use super::SOLANA_SIGNATURE_FEE_LAMPORTS;
+
+/// Human-readable summary of a raw transaction
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedTransaction {
+    pub chain: Chain,
+    /// EVM: legacy, eip2930, eip1559 or eip7702; Bitcoin: legacy or segwit; Solana: legacy or v0
+    pub tx_type: String,
+    /// Transaction hash (EVM), txid (Bitcoin) or first signature (Solana)
+    pub txid: String,
+    /// EVM chain id (None for pre-EIP-155 legacy transactions)
+    pub chain_id: Option<u64>,
+    pub nonce: Option<u64>,
+    /// Value transfers, in base units (wei, sats, lamports)
+    pub outputs: Vec<DecodedOutput>,
+    /// Spent outpoints as "txid:vout" (Bitcoin)
+    pub inputs: Vec<String>,
+    /// Maximum fee in base units; None when it needs the spent outputs (Bitcoin)
+    pub fee: Option<String>,
+    /// Contract call signature for known selectors, e.g. "transfer(address,uint256)"
+    pub method: Option<String>,
+    /// Decoded arguments of `method` (addresses checksummed, integers in decimal)
+    pub method_args: Vec<String>,
+    /// EIP-7702 authorizations (empty for every other type)
+    pub authorizations: Vec<DecodedAuthorization>,
+}
+
+/// A single payment in a decoded transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecodedOutput {
+    /// Recipient; None for contract creation or non-standard scripts
+    pub address: Option<String>,
+    pub value: String,
+}
+
+/// An EIP-7702 authorization: `signer` runs `delegate`'s code from now on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecodedAuthorization {
+    /// Chain the authorization is valid on (0 = any chain)
+    pub chain_id: u64,
+    /// Contract the account delegates to; the zero address clears delegation
+    pub delegate: String,
+    pub nonce: u64,
+    /// Account being delegated; None if the signature doesn't recover
+    pub signer: Option<String>,
+}
+
+/// Decode a raw transaction for display
+///
+/// EVM and Bitcoin transactions are hex (with or without 0x); Solana
+/// transactions are base58 or base64.
+pub fn decode_transaction(chain: Chain, raw: &str) -> HawalaResult<DecodedTransaction> {
+    let raw = raw.trim();
+    match chain {
+        c if c.is_evm() => decode_evm(chain, raw),
+        Chain::Bitcoin | Chain::BitcoinTestnet => decode_bitcoin(chain, raw),
+        Chain::Solana | Chain::SolanaDevnet => decode_solana(chain, raw),
+        other => Err(HawalaError::not_implemented(format!(
+            "Transaction decoding not supported for {:?}",
+            other
+        ))),
+    }
+}
+
+fn decode_evm(chain: Chain, raw: &str) -> HawalaResult<DecodedTransaction> {
+    let bytes = crate::utils::parse_hex_bytes(raw)?;
+    let first = *bytes.first().ok_or_else(|| HawalaError::invalid_input("Empty transaction"))?;
+
+    // Field positions of nonce, gas price (max fee for EIP-1559), gas, to, value, data
+    let (tx_type, fields, chain_id_at, positions) = match first {
+        0x01 => ("eip2930", rlp_list_fields(&bytes[1..])?, Some(0), [1, 2, 3, 4, 5, 6]),
+        0x02 => ("eip1559", rlp_list_fields(&bytes[1..])?, Some(0), [1, 3, 4, 5, 6, 7]),
+        0x04 => ("eip7702", rlp_list_fields(&bytes[1..])?, Some(0), [1, 3, 4, 5, 6, 7]),
+        b if b >= 0xc0 => ("legacy", rlp_list_fields(&bytes)?, None, [0, 1, 2, 3, 4, 5]),
+        other => return Err(HawalaError::invalid_input(format!("Unknown transaction type: {}", other))),
+    };
+    let field = |at: usize| {
+        fields.get(at).copied().ok_or_else(|| HawalaError::invalid_input("Transaction is missing fields"))
+    };
+    let [nonce, price, gas, to, value, data] = positions;
+    let (nonce, price, gas, to, value, data) =
+        (field(nonce)?, field(price)?, field(gas)?, field(to)?, field(value)?, field(data)?);
+
+    let chain_id = match chain_id_at {
+        Some(at) => Some(rlp_scalar(field(at)?)?),
+        // Legacy: v = chain_id * 2 + 35 + recovery_id when signed under EIP-155
+        None => match fields.get(6) {
+            Some(v) => Some(rlp_scalar(v)?).filter(|v| *v >= 35).map(|v| (v - 35) / 2),
+            None => None,
+        },
+    };
+
+    let to = match to.len() {
+        0 => None,
+        20 => Some(to_checksum_address(to)),
+        _ => return Err(HawalaError::invalid_input("Invalid recipient length")),
+    };
+    let value = rlp_u256(value)?;
+    let authorizations = match first {
+        // After the access list
+        0x04 => decode_authorizations(field(9)?)?,
+        _ => Vec::new(),
+    };
+    let fee = rlp_u256(price)?
+        .checked_mul(rlp_u256(gas)?)
+        .ok_or_else(|| HawalaError::invalid_input("Fee overflows"))?;
+
+    let selector = data.get(..4).and_then(|s| <[u8; 4]>::try_from(s).ok());
+    let (method, method_args) = match (&to, selector) {
+        (None, _) => (Some("contract creation".to_string()), Vec::new()),
+        (Some(_), Some(selector)) => match KnownSelectors::identify(&selector) {
+            Some(signature) => (Some(signature.to_string()), decode_static_args(signature, &data[4..])),
+            None => (Some(format!("unknown (0x{})", hex::encode(selector))), Vec::new()),
+        },
+        (Some(_), None) => (None, Vec::new()),
+    };
+
+    Ok(DecodedTransaction {
+        chain,
+        tx_type: tx_type.to_string(),
+        txid: format!("0x{}", hex::encode(keccak256(&bytes))),
+        chain_id,
+        nonce: Some(rlp_scalar(nonce)?),
+        outputs: vec![DecodedOutput { address: to, value: value.to_string() }],
+        inputs: Vec::new(),
+        fee: Some(fee.to_string()),
+        method,
+        method_args,
+        authorizations,
+    })
+}
+
+/// Decode an EIP-7702 authorization list, recovering each signer
+fn decode_authorizations(list: &[u8]) -> HawalaResult<Vec<DecodedAuthorization>> {
+    let malformed = || HawalaError::invalid_input("Malformed authorization");
+
+    rlp_sequence(list)?
+        .into_iter()
+        .map(|entry| {
+            let fields = rlp_sequence(entry)?;
+            let &[chain_id, address, nonce, y_parity, r, s] = fields.as_slice() else {
+                return Err(malformed());
+            };
+            let address: [u8; 20] = address.try_into().map_err(|_| malformed())?;
+            if r.len() > 32 || s.len() > 32 {
+                return Err(malformed());
+            }
+
+            let mut auth = Authorization::new(rlp_scalar(chain_id)?, address, rlp_scalar(nonce)?);
+            auth.y_parity = u8::try_from(rlp_scalar(y_parity)?).map_err(|_| malformed())?;
+            auth.r[32 - r.len()..].copy_from_slice(r);
+            auth.s[32 - s.len()..].copy_from_slice(s);
+
+            Ok(DecodedAuthorization {
+                chain_id: auth.chain_id,
+                delegate: to_checksum_address(&auth.address),
+                nonce: auth.nonce,
+                signer: recover_authorization_signer(&auth).ok().map(|a| to_checksum_address(&a)),
+            })
+        })
+        .collect()
+}
+
+/// RLP integer of up to 256 bits
+fn rlp_u256(bytes: &[u8]) -> HawalaResult<U256> {
+    if bytes.len() > 32 {
+        return Err(HawalaError::invalid_input("RLP integer too large"));
+    }
+    Ok(U256::from_big_endian(bytes))
+}
+
+/// Decode the address/uint256/bool arguments of a known signature; empty if
+/// the signature has other types or the data is short
+fn decode_static_args(signature: &str, args: &[u8]) -> Vec<String> {
+    let params = signature
+        .split_once('(')
+        .and_then(|(_, rest)| rest.strip_suffix(')'))
+        .unwrap_or_default();
+    if params.is_empty() {
+        return Vec::new();
+    }
+
+    let mut decoded = Vec::new();
+    for (i, param) in params.split(',').enumerate() {
+        let Some(word) = args.get(i * 32..(i + 1) * 32) else {
+            return Vec::new();
+        };
+        decoded.push(match param {
+            "address" => to_checksum_address(&word[12..]),
+            "uint256" => U256::from_big_endian(word).to_string(),
+            "bool" => (word[31] != 0).to_string(),
+            _ => return Vec::new(),
+        });
+    }
+    decoded
+}
+
+fn decode_bitcoin(chain: Chain, raw: &str) -> HawalaResult<DecodedTransaction> {
+    let bytes = crate::utils::parse_hex_bytes(raw)?;
+    let tx: bitcoin::Transaction = bitcoin::consensus::encode::deserialize(&bytes)
+        .map_err(|e| HawalaError::invalid_input(format!("Invalid Bitcoin transaction: {}", e)))?;
+    let network = if chain == Chain::Bitcoin { bitcoin::Network::Bitcoin } else { bitcoin::Network::Testnet };
+
+    let segwit = tx.input.iter().any(|input| !input.witness.is_empty());
+    let outputs = tx
+        .output
+        .iter()
+        .map(|output| DecodedOutput {
+            address: bitcoin::Address::from_script(&output.script_pubkey, network)
+                .ok()
+                .map(|a| a.to_string()),
+            value: output.value.to_sat().to_string(),
+        })
+        .collect();
+
+    Ok(DecodedTransaction {
+        chain,
+        tx_type: if segwit { "segwit" } else { "legacy" }.to_string(),
+        txid: tx.compute_txid().to_string(),
+        chain_id: None,
+        nonce: None,
+        outputs,
+        inputs: tx
+            .input
+            .iter()
+            .map(|input| format!("{}:{}", input.previous_output.txid, input.previous_output.vout))
+            .collect(),
+        fee: None,
+        method: None,
+        method_args: Vec::new(),
+        authorizations: Vec::new(),
+    })
+}
+
+#[allow(deprecated)]
+fn decode_solana(chain: Chain, raw: &str) -> HawalaResult<DecodedTransaction> {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    use solana_sdk::message::VersionedMessage;
+    use solana_sdk::system_instruction::SystemInstruction;
+    use solana_sdk::transaction::VersionedTransaction;
+
+    // The wallet produces base58; RPC and dApps commonly use base64
+    let tx: VersionedTransaction = bs58::decode(raw)
+        .into_vec()
+        .ok()
+        .and_then(|bytes| bincode::deserialize(&bytes).ok())
+        .or_else(|| STANDARD.decode(raw).ok().and_then(|bytes| bincode::deserialize(&bytes).ok()))
+        .ok_or_else(|| HawalaError::invalid_input("Invalid Solana transaction (expected base58 or base64)"))?;
+
+    let keys = tx.message.static_account_keys();
+    let system_program = solana_sdk::system_program::id();
+    let outputs = tx
+        .message
+        .instructions()
+        .iter()
+        .filter(|ix| keys.get(ix.program_id_index as usize) == Some(&system_program))
+        .filter_map(|ix| match bincode::deserialize::<SystemInstruction>(&ix.data).ok()? {
+            SystemInstruction::Transfer { lamports } => Some(DecodedOutput {
+                // Recipients in address lookup tables are not resolved
+                address: ix.accounts.get(1).and_then(|i| keys.get(*i as usize)).map(|k| k.to_string()),
+                value: lamports.to_string(),
+            }),
+            _ => None,
+        })
+        .collect();
+
+    let signatures = u64::from(tx.message.header().num_required_signatures);
+    Ok(DecodedTransaction {
+        chain,
+        tx_type: match tx.message {
+            VersionedMessage::Legacy(_) => "legacy",
+            VersionedMessage::V0(_) => "v0",
+        }
+        .to_string(),
+        txid: tx.signatures.first().map(|s| s.to_string()).unwrap_or_default(),
+        chain_id: None,
+        nonce: None,
+        outputs,
+        inputs: Vec::new(),
+        fee: Some((signatures * SOLANA_SIGNATURE_FEE_LAMPORTS).to_string()),
+        method: None,
+        method_args: Vec::new(),
+        authorizations: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx::{build_bitcoin_transaction, build_evm_transaction};
+    use crate::fees::units::{SatPerVb, Wei};
+    use crate::types::{TransactionRequest, Utxo};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn request(chain: Chain) -> TransactionRequest {
+        serde_json::from_value(serde_json::json!({
+            "chain": chain,
+            "from": "",
+            "to": "",
+            "amount": "0",
+            "private_key": "",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_eip1559_transfer() {
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let mut tx = request(Chain::Ethereum);
+        tx.to = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string();
+        tx.amount = "1000000000000000".to_string();
+        tx.private_key = keys.ethereum.private_hex.clone();
+        tx.nonce = Some(7);
+        tx.gas_limit = Some(21_000);
+        tx.max_fee_per_gas = Some(Wei(30_000_000_000));
+        tx.max_priority_fee_per_gas = Some(Wei(1_000_000_000));
+        tx.allow_high_fee = true;
+        let signed = build_evm_transaction(&tx).unwrap();
+
+        let decoded = decode_transaction(Chain::Ethereum, &signed.raw_tx).unwrap();
+        assert_eq!(decoded.tx_type, "eip1559");
+        assert_eq!(decoded.txid, signed.txid);
+        assert_eq!(decoded.chain_id, Some(1));
+        assert_eq!(decoded.nonce, Some(7));
+        assert_eq!(
+            decoded.outputs,
+            vec![DecodedOutput {
+                address: Some("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string()),
+                value: "1000000000000000".to_string(),
+            }]
+        );
+        assert_eq!(decoded.fee.as_deref(), Some("630000000000000"));
+        assert_eq!(decoded.method, None);
+    }
+
+    #[test]
+    fn test_decode_eip2930_transfer() {
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let mut tx = request(Chain::Ethereum);
+        tx.to = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string();
+        tx.amount = "1000000000000000".to_string();
+        tx.private_key = keys.ethereum.private_hex.clone();
+        tx.nonce = Some(2);
+        tx.gas_limit = Some(30_000);
+        tx.gas_price = Some(Wei(20_000_000_000));
+        tx.access_list = Some(vec![crate::types::AccessListItem {
+            address: "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174".to_string(),
+            storage_keys: vec![format!("0x{}", "00".repeat(32))],
+        }]);
+        tx.allow_high_fee = true;
+        let signed = build_evm_transaction(&tx).unwrap();
+
+        let decoded = decode_transaction(Chain::Ethereum, &signed.raw_tx).unwrap();
+        assert_eq!(decoded.tx_type, "eip2930");
+        assert_eq!(decoded.txid, signed.txid);
+        assert_eq!(decoded.chain_id, Some(1));
+        assert_eq!(decoded.nonce, Some(2));
+        assert_eq!(decoded.outputs[0].value, "1000000000000000");
+        assert_eq!(decoded.fee.as_deref(), Some("600000000000000"));
+        assert!(decoded.authorizations.is_empty());
+    }
+
+    #[test]
+    fn test_decode_eip7702_authorizations() {
+        use crate::eip7702::{serialize_for_broadcast, sign_authorization, sign_eip7702_transaction, Eip7702Transaction};
+
+        // Private key 1 delegates to 0xdead...00ef
+        let mut key = [0u8; 32];
+        key[31] = 1;
+        let mut delegate = [0u8; 20];
+        delegate[..2].copy_from_slice(&[0xde, 0xad]);
+        delegate[19] = 0xef;
+
+        let tx = Eip7702Transaction::new(1)
+            .with_nonce(5)
+            .with_max_fee(30_000_000_000)
+            .with_max_priority_fee(1_000_000_000)
+            .with_gas_limit(100_000)
+            .with_to(delegate)
+            .add_authorization(sign_authorization(1, delegate, 6, &key).unwrap());
+        let raw = hex::encode(serialize_for_broadcast(&sign_eip7702_transaction(&tx, &key).unwrap()));
+
+        let decoded = decode_transaction(Chain::Ethereum, &raw).unwrap();
+        assert_eq!(decoded.tx_type, "eip7702");
+        assert_eq!(decoded.chain_id, Some(1));
+        assert_eq!(decoded.nonce, Some(5));
+        assert_eq!(decoded.fee.as_deref(), Some("3000000000000000"));
+        assert_eq!(
+            decoded.authorizations,
+            vec![DecodedAuthorization {
+                chain_id: 1,
+                delegate: to_checksum_address(&delegate),
+                nonce: 6,
+                signer: Some("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf".to_string()),
+            }]
+        );
+
+        // Truncated transaction
+        let bytes = hex::decode(&raw).unwrap();
+        assert!(decode_transaction(Chain::Ethereum, &hex::encode(&bytes[..bytes.len() - 70])).is_err());
+    }
+
+    #[test]
+    fn test_decode_erc20_transfer_method() {
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let mut tx = request(Chain::Polygon);
+        tx.to = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174".to_string();
+        tx.private_key = keys.ethereum.private_hex.clone();
+        tx.nonce = Some(0);
+        tx.gas_limit = Some(65_000);
+        tx.gas_price = Some(Wei(50_000_000_000));
+        // transfer(0xd8dA...6045, 100 USDC)
+        tx.data = Some(format!(
+            "0xa9059cbb{:0>64}{:0>64}",
+            "d8da6bf26964af9d7eed9e03e53415d37aa96045", "5f5e100"
+        ));
+        let signed = build_evm_transaction(&tx).unwrap();
+
+        let decoded = decode_transaction(Chain::Polygon, &signed.raw_tx).unwrap();
+        assert_eq!(decoded.tx_type, "legacy");
+        assert_eq!(decoded.chain_id, Some(137));
+        assert_eq!(decoded.outputs[0].value, "0");
+        assert_eq!(decoded.method.as_deref(), Some("transfer(address,uint256)"));
+        assert_eq!(
+            decoded.method_args,
+            vec!["0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string(), "100000000".to_string()]
+        );
+        assert_eq!(decoded.fee.as_deref(), Some("3250000000000000"));
+    }
+
+    #[test]
+    fn test_decode_p2wpkh_bitcoin() {
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let mut tx = request(Chain::Bitcoin);
+        tx.to = "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g".to_string();
+        tx.amount = "50000".to_string();
+        tx.private_key = keys.bitcoin.private_wif.clone();
+        tx.fee_rate = Some(SatPerVb(2));
+        tx.utxos = Some(vec![Utxo {
+            txid: "a".repeat(64),
+            vout: 1,
+            value: 100_000,
+            script_pubkey: None,
+            confirmed: true,
+            block_height: None,
+        }]);
+        let signed = build_bitcoin_transaction(&tx).unwrap();
+
+        let decoded = decode_transaction(Chain::Bitcoin, &signed.raw_tx).unwrap();
+        assert_eq!(decoded.tx_type, "segwit");
+        assert_eq!(decoded.txid.len(), 64);
+        assert_eq!(decoded.inputs, vec![format!("{}:1", "a".repeat(64))]);
+        assert_eq!(
+            decoded.outputs,
+            vec![
+                DecodedOutput {
+                    address: Some("bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g".to_string()),
+                    value: "50000".to_string(),
+                },
+                // 10 + 68 + 62 = 140 vB at 2 sat/vB
+                DecodedOutput {
+                    address: Some(keys.bitcoin.address.clone()),
+                    value: "49720".to_string(),
+                },
+            ]
+        );
+        assert_eq!(decoded.fee, None);
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage() {
+        assert!(decode_transaction(Chain::Ethereum, "0x").is_err());
+        assert!(decode_transaction(Chain::Ethereum, "0x05c0").is_err());
+        assert!(decode_transaction(Chain::Bitcoin, "00").is_err());
+        assert!(decode_transaction(Chain::Solana, "not a transaction").is_err());
+        assert!(decode_transaction(Chain::Xrp, "00").is_err());
+    }
+}
@@ -1,20 +1,27 @@
 //! Transaction Module
 //!
-//! Handles transaction building, signing, broadcasting, and tracking.
+//! Handles transaction building, signing, broadcasting, and tracking, plus a
+//! one-call [`send`] that does all three.
 
 mod builder;
 mod signer;
 mod broadcaster;
 mod cancellation;
+mod decoder;
 mod replay_protection;
 mod tracker;
+mod send;
+#[cfg(test)]
+mod test_fixtures;
 
 pub use builder::*;
 pub use signer::*;
 pub use broadcaster::*;
 pub use cancellation::*;
+pub use decoder::*;
 pub use replay_protection::*;
 pub use tracker::*;
+pub use send::*;
 
 use std::os::raw::c_char;
 use crate::error::HawalaError;
@@ -34,26 +41,39 @@ fn ffi_response<T: serde::Serialize>(result: Result<T, HawalaError>) -> *mut c_c
     }
 }
 
-/// Prepare a Bitcoin transaction
+/// Prepare a Bitcoin transaction (or preview it when `dry_run` is set)
 pub fn prepare_bitcoin_transaction(request: &TransactionRequest) -> *mut c_char {
+    if request.dry_run {
+        return ffi_response(builder::preview_bitcoin_transaction(request));
+    }
     let result = builder::build_bitcoin_transaction(request);
     ffi_response(result)
 }
 
-/// Prepare an EVM transaction (Ethereum, BSC, Polygon, etc.)
+/// Prepare an EVM transaction (Ethereum, BSC, Polygon, etc.), or preview it
+/// when `dry_run` is set
 pub fn prepare_evm_transaction(request: &TransactionRequest) -> *mut c_char {
+    if request.dry_run {
+        return ffi_response(builder::preview_evm_transaction(request));
+    }
     let result = builder::build_evm_transaction(request);
     ffi_response(result)
 }
 
-/// Prepare a Litecoin transaction
+/// Prepare a Litecoin transaction (or preview it when `dry_run` is set)
 pub fn prepare_litecoin_transaction(request: &TransactionRequest) -> *mut c_char {
+    if request.dry_run {
+        return ffi_response(builder::preview_litecoin_transaction(request));
+    }
     let result = builder::build_litecoin_transaction(request);
     ffi_response(result)
 }
 
-/// Prepare a Solana transaction
+/// Prepare a Solana transaction (or preview it when `dry_run` is set)
 pub fn prepare_solana_transaction(request: &TransactionRequest) -> *mut c_char {
+    if request.dry_run {
+        return ffi_response(builder::preview_solana_transaction(request));
+    }
     let result = builder::build_solana_transaction(request);
     ffi_response(result)
 }
@@ -64,3 +84,9 @@ pub fn prepare_xrp_transaction(request: &TransactionRequest) -> *mut c_char {
     ffi_response(result)
 }
 
+/// Prepare a Tezos transaction
+pub fn prepare_tezos_transaction(request: &TransactionRequest) -> *mut c_char {
+    let result = builder::build_tezos_transaction(request);
+    ffi_response(result)
+}
+
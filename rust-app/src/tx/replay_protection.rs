@@ -3,7 +3,7 @@
 //! Prevents transaction replay attacks across chains and ensures
 //! proper chain ID verification for EVM transactions.
 
-use crate::error::{HawalaError, HawalaResult};
+use crate::error::{ErrorCode, HawalaError, HawalaResult};
 use crate::types::Chain;
 use std::collections::HashSet;
 use std::sync::{LazyLock, Mutex};
@@ -110,34 +110,139 @@ pub fn was_transaction_broadcast(tx_hash: &str) -> HawalaResult<bool> {
 
 /// Verify EIP-155 replay protection in transaction
 /// EIP-155 adds chain_id to the signature, preventing replay on other chains
-pub fn verify_eip155_protection(raw_tx: &str, _expected_chain_id: u64) -> HawalaResult<()> {
-    // Decode the raw transaction
+pub fn verify_eip155_protection(raw_tx: &str, expected_chain_id: u64) -> HawalaResult<()> {
+    validate_evm(raw_tx, expected_chain_id).map(|_| ())
+}
+
+/// Check that a signed EVM transaction is bound to `expected_chain_id`
+///
+/// Typed (EIP-2930/EIP-1559/EIP-4844/EIP-7702) transactions carry the chain
+/// id as their first field; legacy ones encode it in `v = chain_id * 2 + 35 + recovery_id`.
+/// Pre-EIP-155 legacy transactions (`v` of 27/28) and chain id 0 are
+/// rejected as replayable on any chain, and a different chain id fails with
+/// `ErrorCode::ChainIdMismatch`. Returns the transaction's chain id.
+pub fn validate_evm(raw_tx: &str, expected_chain_id: u64) -> HawalaResult<u64> {
     let tx_bytes = crate::utils::parse_hex_bytes(raw_tx)?;
+    let tx_type = *tx_bytes.first()
+        .ok_or_else(|| HawalaError::invalid_input("Empty transaction"))?;
     
-    if tx_bytes.is_empty() {
-        return Err(HawalaError::invalid_input("Empty transaction"));
-    }
-    
-    // Check transaction type
-    let tx_type = tx_bytes[0];
-    
-    match tx_type {
-        // EIP-2930 (type 1) or EIP-1559 (type 2) - chain_id is embedded
-        0x01 | 0x02 => {
-            // These types inherently include chain_id, so they're protected
-            Ok(())
+    let chain_id = match tx_type {
+        0x01..=0x04 => {
+            let mut fields = rlp_list_fields(&tx_bytes[1..])?;
+            // Blob transactions in network form wrap the signed transaction
+            // as the first item: [tx_payload_body, blobs, commitments, proofs]
+            if tx_type == 0x03 && tx_bytes.get(1..).and_then(first_item_is_list).unwrap_or(false) {
+                fields = rlp_sequence(fields[0])?;
+            }
+            rlp_scalar(fields.first().copied().unwrap_or_default())?
         }
-        // Legacy transaction
         _ if tx_type >= 0xc0 => {
-            // Legacy transactions use EIP-155: v = chain_id * 2 + 35 + recovery_id
-            // We can verify the chain_id from v value
-            // For now, trust that ethers-rs signs correctly
-            Ok(())
+            let fields = rlp_list_fields(&tx_bytes)?;
+            if fields.len() != 9 {
+                return Err(HawalaError::new(
+                    ErrorCode::InvalidTransaction,
+                    format!("Legacy transaction has {} fields, expected 9", fields.len()),
+                ));
+            }
+            match rlp_scalar(fields[6])? {
+                27 | 28 => {
+                    return Err(HawalaError::new(
+                        ErrorCode::InvalidTransaction,
+                        "Legacy transaction without EIP-155 chain id can be replayed on any EVM chain",
+                    ))
+                }
+                v if v >= 35 => (v - 35) / 2,
+                v => {
+                    return Err(HawalaError::new(
+                        ErrorCode::InvalidTransaction,
+                        format!("Invalid legacy signature v value: {}", v),
+                    ))
+                }
+            }
         }
         _ => {
-            Err(HawalaError::invalid_input(format!("Unknown transaction type: {}", tx_type)))
+            return Err(HawalaError::invalid_input(format!("Unknown transaction type: {}", tx_type)));
+        }
+    };
+    
+    if chain_id == 0 {
+        return Err(HawalaError::new(
+            ErrorCode::InvalidTransaction,
+            "Transaction has chain id 0 and can be replayed on any EVM chain",
+        ));
+    }
+    if chain_id != expected_chain_id {
+        return Err(HawalaError::new(
+            ErrorCode::ChainIdMismatch,
+            format!("Transaction is for chain id {}, expected {}", chain_id, expected_chain_id),
+        ));
+    }
+    
+    Ok(chain_id)
+}
+
+/// Split an RLP list spanning all of `data` into its item payloads
+pub(super) fn rlp_list_fields(data: &[u8]) -> HawalaResult<Vec<&[u8]>> {
+    let malformed = || HawalaError::new(ErrorCode::InvalidTransaction, "Malformed RLP transaction");
+    
+    let (is_list, payload, consumed) = rlp_item(data).ok_or_else(malformed)?;
+    if !is_list || consumed != data.len() {
+        return Err(malformed());
+    }
+    rlp_sequence(payload)
+}
+
+/// Split the payload of an RLP list into its items' payloads
+pub(super) fn rlp_sequence(mut payload: &[u8]) -> HawalaResult<Vec<&[u8]>> {
+    let malformed = || HawalaError::new(ErrorCode::InvalidTransaction, "Malformed RLP transaction");
+    
+    let mut fields = Vec::new();
+    while !payload.is_empty() {
+        let (_, item, consumed) = rlp_item(payload).ok_or_else(malformed)?;
+        fields.push(item);
+        payload = &payload[consumed..];
+    }
+    Ok(fields)
+}
+
+/// Whether the first item inside the RLP list `data` is itself a list
+fn first_item_is_list(data: &[u8]) -> Option<bool> {
+    let (_, payload, _) = rlp_item(data)?;
+    rlp_item(payload).map(|(is_list, _, _)| is_list)
+}
+
+/// Decode one RLP item header: (is_list, payload, total length)
+fn rlp_item(data: &[u8]) -> Option<(bool, &[u8], usize)> {
+    let prefix = *data.first()?;
+    let (is_list, header_len, payload_len) = match prefix {
+        0x00..=0x7f => return Some((false, &data[..1], 1)),
+        0x80..=0xb7 => (false, 1, (prefix - 0x80) as usize),
+        0xb8..=0xbf => (false, 1 + (prefix - 0xb7) as usize, 0),
+        0xc0..=0xf7 => (true, 1, (prefix - 0xc0) as usize),
+        0xf8..=0xff => (true, 1 + (prefix - 0xf7) as usize, 0),
+    };
+    
+    // Long form: the length itself follows the prefix, big-endian
+    let payload_len = if header_len > 1 {
+        let len_bytes = data.get(1..header_len)?;
+        if len_bytes.len() > std::mem::size_of::<usize>() {
+            return None;
         }
+        len_bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize)
+    } else {
+        payload_len
+    };
+    
+    let end = header_len.checked_add(payload_len)?;
+    Some((is_list, data.get(header_len..end)?, end))
+}
+
+/// Big-endian RLP integer
+pub(super) fn rlp_scalar(bytes: &[u8]) -> HawalaResult<u64> {
+    if bytes.len() > 8 {
+        return Err(HawalaError::new(ErrorCode::InvalidTransaction, "RLP integer too large"));
     }
+    Ok(bytes.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64))
 }
 
 /// Validate that a signed transaction targets the correct chain
@@ -220,6 +325,77 @@ mod tests {
         assert_eq!(get_mainnet_equivalent(chain_ids::ETHEREUM), None);
     }
 
+    // EIP-155 example: nonce 9, 20 gwei, 1 ETH to 0x3535..., chain id 1 (v = 37)
+    const EIP155_TX: &str = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+    // Same transaction with a different v (signature left as is)
+    fn with_v(v: u8) -> String {
+        if v < 0x80 {
+            EIP155_TX.replacen("8025a0", &format!("80{:02x}a0", v), 1)
+        } else {
+            // One extra byte for the string prefix, so the list grows too
+            EIP155_TX
+                .replacen("f86c", "f86d", 1)
+                .replacen("8025a0", &format!("8081{:02x}a0", v), 1)
+        }
+    }
+
+    #[test]
+    fn test_eip155_legacy_passes() {
+        assert_eq!(validate_evm(EIP155_TX, chain_ids::ETHEREUM).unwrap(), 1);
+        assert!(verify_eip155_protection(EIP155_TX, chain_ids::ETHEREUM).is_ok());
+    }
+
+    #[test]
+    fn test_unprotected_legacy_flagged() {
+        // chain id 0: v = 0 * 2 + 35
+        let err = validate_evm(&with_v(35), chain_ids::ETHEREUM).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidTransaction);
+        assert!(err.message.contains("chain id 0"));
+
+        // Pre-EIP-155 signature
+        let err = validate_evm(&with_v(27), chain_ids::ETHEREUM).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidTransaction);
+        assert!(err.message.contains("EIP-155"));
+    }
+
+    #[test]
+    fn test_chain_id_mismatch() {
+        let err = validate_evm(EIP155_TX, chain_ids::POLYGON).unwrap_err();
+        assert_eq!(err.code, ErrorCode::ChainIdMismatch);
+
+        // BSC: v = 56 * 2 + 35
+        assert_eq!(validate_evm(&with_v(147), chain_ids::BSC).unwrap(), chain_ids::BSC);
+        assert_eq!(validate_evm(&with_v(147), chain_ids::ETHEREUM).unwrap_err().code, ErrorCode::ChainIdMismatch);
+    }
+
+    #[test]
+    fn test_typed_transaction_chain_id() {
+        // EIP-1559 envelope with chain id 1 and empty remaining fields
+        let eip1559 = "0x02cc0180808080808080c0808080";
+        assert_eq!(validate_evm(eip1559, chain_ids::ETHEREUM).unwrap(), 1);
+        assert_eq!(validate_evm(eip1559, chain_ids::BASE).unwrap_err().code, ErrorCode::ChainIdMismatch);
+
+        // Chain id 137 (0x89) needs a length prefix
+        let polygon = "0x02cd818980808080808080c0808080";
+        assert_eq!(validate_evm(polygon, chain_ids::POLYGON).unwrap(), 137);
+
+        // EIP-7702 and EIP-4844 envelopes
+        let eip7702 = "0x04cd0180808080808080c0c0808080";
+        assert_eq!(validate_evm(eip7702, chain_ids::ETHEREUM).unwrap(), 1);
+        assert_eq!(validate_evm(eip7702, chain_ids::BASE).unwrap_err().code, ErrorCode::ChainIdMismatch);
+        let blob = "0x03c701808080808080";
+        assert_eq!(validate_evm(blob, chain_ids::ETHEREUM).unwrap(), 1);
+        // Network form: [tx_payload_body, blobs, commitments, proofs]
+        let blob_network = "0x03cbc701808080808080c0c0c0";
+        assert_eq!(validate_evm(blob_network, chain_ids::ETHEREUM).unwrap(), 1);
+        assert_eq!(validate_evm(blob_network, chain_ids::BASE).unwrap_err().code, ErrorCode::ChainIdMismatch);
+
+        assert!(validate_evm("0x02cd01", chain_ids::ETHEREUM).is_err());
+        assert!(validate_evm("0x05c0", chain_ids::ETHEREUM).is_err());
+        assert!(validate_evm("0x", chain_ids::ETHEREUM).is_err());
+    }
+
     #[test]
     fn test_transaction_recording() {
         let hash = "0x1234567890abcdef";
@@ -0,0 +1,469 @@
+//! Build, Sign and Broadcast
+//!
+//! One-call send flow on top of the chain builders and the broadcaster.
+//! Whatever the transaction consumes is reserved before signing so that two
+//! concurrent sends can't pick the same inputs:
+//!
+//! - EVM: the nonce is reserved in the nonce manager, moved to pending once
+//!   the node accepts the transaction and released if anything fails.
+//! - UTXO chains: the request's outpoints are held for the duration of the
+//!   send; a second send spending any of them is rejected. Without explicit
+//!   `utxos` the sender's UTXOs are fetched, held ones skipped and the inputs
+//!   selected here, so they are reserved before the builder sees them. Once broadcast
+//!   they stay held until a UTXO refresh for the address no longer lists
+//!   them, or [`SPENT_OUTPOINT_TTL`] passes.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{HawalaError, HawalaResult};
+use crate::types::*;
+use crate::wallet::nonce;
+
+use super::broadcaster::broadcast_transaction;
+use super::replay_protection::validate_evm;
+use super::builder::{
+    build_bitcoin_transaction, build_evm_transaction, build_litecoin_transaction,
+    build_solana_transaction, build_tezos_transaction, build_xrp_transaction, select_utxo_inputs,
+};
+
+/// How long a broadcast transaction's inputs stay held if no UTXO refresh
+/// shows them spent (e.g. the transaction was dropped from the mempool)
+pub const SPENT_OUTPOINT_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// An outpoint (`txid:vout`) held by a send
+struct OutpointHold {
+    /// Address the outpoint belongs to
+    address: String,
+    /// When the spending transaction was broadcast; `None` while in flight
+    broadcast_at: Option<Instant>,
+}
+
+/// Outpoints spent by sends that are in flight or recently broadcast
+static HELD_OUTPOINTS: Mutex<BTreeMap<String, OutpointHold>> = Mutex::new(BTreeMap::new());
+
+/// Result of [`send`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendResult {
+    pub chain: Chain,
+    pub txid: String,
+    pub raw_tx: String,
+    /// Nonce used (EVM only)
+    pub nonce: Option<u64>,
+    pub explorer_url: Option<String>,
+}
+
+/// What a send is holding until it either lands or fails
+enum Reservation {
+    Nonce { address: String, chain_id: u64, nonce: u64 },
+    Outpoints(Vec<String>),
+    None,
+}
+
+impl Reservation {
+    /// Take whatever `request` is about to spend, picking the EVM nonce
+    /// from the network when the caller didn't supply one
+    fn acquire(request: &mut TransactionRequest) -> HawalaResult<Self> {
+        if request.chain.is_evm() {
+            let chain_id = request.chain.chain_id()
+                .ok_or_else(|| HawalaError::invalid_input("Invalid EVM chain"))?;
+            let nonce = match request.nonce {
+                Some(n) => {
+                    nonce::reserve_nonce(&request.from, chain_id, n)?;
+                    n
+                }
+                None => nonce::reserve_next_nonce(&request.from, chain_id)?.nonce,
+            };
+            request.nonce = Some(nonce);
+            return Ok(Reservation::Nonce { address: request.from.clone(), chain_id, nonce });
+        }
+
+        let Some(utxos) = &request.utxos else {
+            return Ok(Reservation::None);
+        };
+        let outpoints: Vec<String> = utxos.iter()
+            .map(|u| outpoint_key(&u.txid, u.vout))
+            .collect();
+
+        let mut held = lock_outpoints()?;
+        held.retain(|_, hold| hold.broadcast_at.is_none_or(|at| at.elapsed() < SPENT_OUTPOINT_TTL));
+        if let Some(busy) = outpoints.iter().find(|o| held.contains_key(*o)) {
+            return Err(HawalaError::invalid_input(format!(
+                "UTXO {} is already being spent by another transaction", busy
+            )));
+        }
+        for outpoint in &outpoints {
+            held.insert(outpoint.clone(), OutpointHold {
+                address: request.from.clone(),
+                broadcast_at: None,
+            });
+        }
+        Ok(Reservation::Outpoints(outpoints))
+    }
+
+    /// The transaction was accepted by the network
+    fn commit(self) -> HawalaResult<()> {
+        match self {
+            Reservation::Nonce { address, chain_id, nonce } => {
+                nonce::mark_nonce_pending(&address, chain_id, nonce)
+            }
+            // Spent, but a stale UTXO list still shows them until the
+            // wallet refreshes
+            Reservation::Outpoints(outpoints) => {
+                let mut held = lock_outpoints()?;
+                let now = Instant::now();
+                for outpoint in &outpoints {
+                    if let Some(hold) = held.get_mut(outpoint) {
+                        hold.broadcast_at = Some(now);
+                    }
+                }
+                Ok(())
+            }
+            Reservation::None => Ok(()),
+        }
+    }
+
+    /// Nothing was broadcast, so everything held goes back
+    fn rollback(self) -> HawalaResult<()> {
+        match self {
+            Reservation::Nonce { address, chain_id, nonce } => {
+                nonce::release_nonce(&address, chain_id, nonce).map(|_| ())
+            }
+            Reservation::Outpoints(outpoints) => {
+                let mut held = lock_outpoints()?;
+                for outpoint in &outpoints {
+                    held.remove(outpoint);
+                }
+                Ok(())
+            }
+            Reservation::None => Ok(()),
+        }
+    }
+}
+
+fn lock_outpoints() -> HawalaResult<std::sync::MutexGuard<'static, BTreeMap<String, OutpointHold>>> {
+    HELD_OUTPOINTS.lock().map_err(|_| HawalaError::internal("Lock failed"))
+}
+
+fn outpoint_key(txid: &str, vout: u32) -> String {
+    format!("{}:{}", txid.to_lowercase(), vout)
+}
+
+/// Release broadcast outpoints of `address` that a fresh UTXO fetch no
+/// longer lists
+///
+/// `unspent` is the address's current UTXO set as `(txid, vout)`. Outpoints
+/// of sends still in flight are kept.
+pub fn release_spent_outpoints<'a>(
+    address: &str,
+    unspent: impl IntoIterator<Item = (&'a str, u32)>,
+) -> HawalaResult<()> {
+    let unspent: Vec<String> = unspent.into_iter()
+        .map(|(txid, vout)| outpoint_key(txid, vout))
+        .collect();
+    lock_outpoints()?.retain(|outpoint, hold| {
+        hold.address != address || hold.broadcast_at.is_none() || unspent.contains(outpoint)
+    });
+    Ok(())
+}
+
+/// Whether `chain` spends UTXOs that `send` selects and reserves
+fn selects_utxos(chain: Chain) -> bool {
+    matches!(chain, Chain::Bitcoin | Chain::BitcoinTestnet | Chain::Litecoin)
+}
+
+/// The sender's UTXOs from the network
+fn fetch_utxos(address: &str, chain: Chain) -> HawalaResult<Vec<Utxo>> {
+    Ok(crate::wallet::utxo::fetch_utxos(address, chain)?
+        .into_iter()
+        .map(|u| Utxo {
+            txid: u.txid,
+            vout: u.vout,
+            value: u.value,
+            script_pubkey: None,
+            confirmed: u.confirmations > 0,
+            block_height: None,
+        })
+        .collect())
+}
+
+/// Pick the inputs for a request without `utxos` from the sender's fetched
+/// UTXOs, skipping outpoints other sends are holding
+fn select_fetched_utxos(
+    request: &TransactionRequest,
+    fetch: impl Fn(&str, Chain) -> HawalaResult<Vec<Utxo>>,
+) -> HawalaResult<Vec<Utxo>> {
+    let fetched = fetch(&request.from, request.chain)?;
+    // A fresh list also tells which broadcast inputs are now spent
+    release_spent_outpoints(&request.from, fetched.iter().map(|u| (u.txid.as_str(), u.vout)))?;
+
+    let available: Vec<Utxo> = {
+        let held = lock_outpoints()?;
+        fetched.into_iter()
+            .filter(|u| !held.contains_key(&outpoint_key(&u.txid, u.vout)))
+            .collect()
+    };
+    select_utxo_inputs(request, &available)
+}
+
+/// Sign a transaction with the chain's builder
+fn build_signed(request: &TransactionRequest) -> HawalaResult<SignedTransaction> {
+    match request.chain {
+        Chain::Bitcoin | Chain::BitcoinTestnet => build_bitcoin_transaction(request),
+        Chain::Litecoin => build_litecoin_transaction(request),
+        Chain::Solana | Chain::SolanaDevnet => build_solana_transaction(request),
+        Chain::Xrp | Chain::XrpTestnet => build_xrp_transaction(request),
+        Chain::Tezos => build_tezos_transaction(request),
+        chain if chain.is_evm() => build_evm_transaction(request),
+        chain => Err(HawalaError::not_implemented(format!(
+            "Transactions not yet supported for {:?}", chain
+        ))),
+    }
+}
+
+/// Build, sign and broadcast `request` in one step
+///
+/// On EVM chains a missing `nonce` is fetched from the network, and on
+/// Bitcoin and Litecoin missing `utxos` are fetched and selected. If signing
+/// or broadcasting fails the nonce / UTXOs are released so the next send can
+/// reuse them.
+pub fn send(request: &TransactionRequest) -> HawalaResult<SendResult> {
+    send_with(request, fetch_utxos, broadcast_transaction)
+}
+
+/// [`send`] with a caller-supplied UTXO fetcher (`address, chain`) and
+/// broadcaster (`chain, raw_tx`)
+pub fn send_with(
+    request: &TransactionRequest,
+    fetch: impl Fn(&str, Chain) -> HawalaResult<Vec<Utxo>>,
+    broadcast: impl Fn(Chain, &str) -> HawalaResult<BroadcastResult>,
+) -> HawalaResult<SendResult> {
+    if request.dry_run {
+        return Err(HawalaError::invalid_input("dry_run requests can't be sent"));
+    }
+
+    let mut request = request.clone();
+    if request.utxos.is_none() && selects_utxos(request.chain) {
+        request.utxos = Some(select_fetched_utxos(&request, fetch)?);
+    }
+    let reservation = Reservation::acquire(&mut request)?;
+
+    let outcome = build_signed(&request).and_then(|signed| {
+        if let Some(chain_id) = request.chain.chain_id().filter(|_| request.chain.is_evm()) {
+            validate_evm(&signed.raw_tx, chain_id)?;
+        }
+        let result = broadcast(request.chain, &signed.raw_tx)?;
+        if !result.success {
+            return Err(HawalaError::broadcast_failed(
+                result.error_message.unwrap_or_else(|| "Broadcast rejected".to_string()),
+            ));
+        }
+        Ok((signed, result))
+    });
+
+    match outcome {
+        Ok((signed, result)) => {
+            // The transaction is out; the caller must get the txid even if
+            // the bookkeeping fails, or a retry would send it twice
+            if let Err(e) = reservation.commit() {
+                crate::log_warn!(
+                    "Send",
+                    "Broadcast but reservation not committed",
+                    txid = signed.txid,
+                    error = e.message,
+                );
+            }
+            Ok(SendResult {
+                chain: signed.chain,
+                txid: signed.txid,
+                raw_tx: signed.raw_tx,
+                nonce: request.nonce.filter(|_| request.chain.is_evm()),
+                explorer_url: result.explorer_url,
+            })
+        }
+        Err(e) => {
+            // The send's own error is what the caller needs to see
+            let _ = reservation.rollback();
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fees::units::Wei;
+    use crate::tx::test_fixtures::{bitcoin_request, MNEMONIC};
+
+    fn base_request() -> TransactionRequest {
+        let mut request = bitcoin_request();
+        let utxo = &mut request.utxos.as_mut().unwrap()[0];
+        utxo.txid = "b".repeat(64);
+        utxo.vout = 1;
+        request
+    }
+
+    /// Arbitrum so these tests don't share nonce state with anything else
+    fn evm_request(nonce: u64) -> TransactionRequest {
+        let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+        let mut request = base_request();
+        request.chain = Chain::Arbitrum;
+        request.from = keys.ethereum.address.clone();
+        request.to = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string();
+        request.private_key = keys.ethereum.private_hex.clone();
+        request.amount = "10000000000000000".to_string();
+        request.utxos = None;
+        request.fee_rate = None;
+        request.nonce = Some(nonce);
+        request.gas_limit = Some(21_000);
+        request.max_fee_per_gas = Some(Wei(30_000_000_000));
+        request.max_priority_fee_per_gas = Some(Wei(1_000_000_000));
+        request
+    }
+
+    fn no_fetch(_: &str, _: Chain) -> HawalaResult<Vec<Utxo>> {
+        panic!("request already has its UTXOs")
+    }
+
+    fn accepted(chain: Chain, raw_tx: &str) -> HawalaResult<BroadcastResult> {
+        Ok(BroadcastResult {
+            chain,
+            txid: format!("mock-{}", raw_tx.len()),
+            success: true,
+            error_message: None,
+            explorer_url: Some("https://example.invalid/tx".to_string()),
+        })
+    }
+
+    #[test]
+    fn test_send_evm_marks_nonce_pending() {
+        let request = evm_request(7);
+        let chain_id = request.chain.chain_id().unwrap();
+        let expected = build_evm_transaction(&request).unwrap();
+
+        let result = send_with(&request, no_fetch, accepted).unwrap();
+        assert_eq!(result.txid, expected.txid);
+        assert_eq!(result.raw_tx, expected.raw_tx);
+        assert_eq!(result.nonce, Some(7));
+
+        let state = nonce::get_nonce_state(&request.from, chain_id).unwrap();
+        assert!(state.pending_nonces.contains(&7));
+        assert!(!state.reserved_nonces.contains(&7));
+    }
+
+    #[test]
+    fn test_send_evm_broadcast_failure_releases_nonce() {
+        let request = evm_request(8);
+        let chain_id = request.chain.chain_id().unwrap();
+
+        let err = send_with(&request, no_fetch, |_, _| Err(HawalaError::broadcast_failed("nonce too low")))
+            .unwrap_err();
+        assert_eq!(err.code, crate::error::ErrorCode::BroadcastFailed);
+
+        let state = nonce::get_nonce_state(&request.from, chain_id).unwrap();
+        assert!(!state.pending_nonces.contains(&8));
+        assert!(!state.reserved_nonces.contains(&8));
+
+        // A rejection reported in the result rolls back the same way
+        let request = evm_request(9);
+        let err = send_with(&request, no_fetch, |chain, _| Ok(BroadcastResult {
+            chain,
+            txid: String::new(),
+            success: false,
+            error_message: Some("replacement transaction underpriced".to_string()),
+            explorer_url: None,
+        }))
+        .unwrap_err();
+        assert!(err.message.contains("underpriced"));
+
+        let state = nonce::get_nonce_state(&request.from, chain_id).unwrap();
+        assert!(!state.pending_nonces.contains(&9));
+        assert!(!state.reserved_nonces.contains(&9));
+    }
+
+    #[test]
+    fn test_send_bitcoin_releases_utxos() {
+        let request = base_request();
+        let expected = build_bitcoin_transaction(&request).unwrap();
+
+        // Failed broadcast: the outpoint is free again for the retry
+        assert!(send_with(&request, no_fetch, |_, _| Err(HawalaError::broadcast_failed("mempool full"))).is_err());
+        let result = send_with(&request, no_fetch, accepted).unwrap();
+        assert_eq!(result.txid, expected.txid);
+        assert_eq!(result.nonce, None);
+    }
+
+    #[test]
+    fn test_send_rejects_utxo_in_flight() {
+        let mut request = base_request();
+        request.utxos.as_mut().unwrap()[0].txid = "c".repeat(64);
+
+        // A second send of the same outpoint while the first is broadcasting
+        let result = send_with(&request, no_fetch, |chain, raw_tx| {
+            let inner = send_with(&request, no_fetch, accepted).unwrap_err();
+            assert!(inner.message.contains("already being spent"));
+            accepted(chain, raw_tx)
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_broadcast_utxos_held_until_refresh() {
+        let mut request = base_request();
+        request.utxos.as_mut().unwrap()[0].txid = "d".repeat(64);
+        let outpoint = ("d".repeat(64), 1);
+
+        send_with(&request, no_fetch, accepted).unwrap();
+        // A stale UTXO list would spend the same input again
+        let err = send_with(&request, no_fetch, accepted).unwrap_err();
+        assert!(err.message.contains("already being spent"));
+
+        // Still listed as unspent: keep holding it
+        release_spent_outpoints(&request.from, [(outpoint.0.as_str(), outpoint.1)]).unwrap();
+        assert!(send_with(&request, no_fetch, accepted).is_err());
+
+        // Gone from the address's UTXO set: free to forget
+        release_spent_outpoints(&request.from, Vec::<(&str, u32)>::new()).unwrap();
+        assert!(send_with(&request, no_fetch, accepted).is_ok());
+    }
+
+    #[test]
+    fn test_send_reserves_fetched_utxos() {
+        fn fetch(_: &str, _: Chain) -> HawalaResult<Vec<Utxo>> {
+            let utxo = |c: &str| Utxo { txid: c.repeat(64), ..base_request().utxos.unwrap()[0].clone() };
+            Ok(vec![utxo("e"), utxo("f")])
+        }
+        let mut request = base_request();
+        request.utxos = None;
+
+        // A concurrent send skips the input the first one selected
+        let inner = std::cell::RefCell::new(None);
+        let outer = send_with(&request, fetch, |chain, raw_tx| {
+            *inner.borrow_mut() = Some(send_with(&request, fetch, accepted).unwrap());
+            accepted(chain, raw_tx)
+        })
+        .unwrap();
+        let inner = inner.into_inner().unwrap();
+        assert_ne!(outer.raw_tx, inner.raw_tx);
+        {
+            let held = lock_outpoints().unwrap();
+            assert!(held.contains_key(&outpoint_key(&"e".repeat(64), 1)));
+            assert!(held.contains_key(&outpoint_key(&"f".repeat(64), 1)));
+        }
+
+        // Both inputs are taken until a refresh drops them
+        let err = send_with(&request, fetch, accepted).unwrap_err();
+        assert_eq!(err.code, crate::error::ErrorCode::InsufficientFunds);
+    }
+
+    #[test]
+    fn test_send_rejects_dry_run() {
+        let mut request = base_request();
+        request.dry_run = true;
+        assert!(send_with(&request, no_fetch, accepted).is_err());
+    }
+}
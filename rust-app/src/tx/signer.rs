@@ -47,6 +47,7 @@ pub fn sign_bitcoin_transaction(params: &BitcoinSignParams) -> HawalaResult<Sign
         txid,
         estimated_fee: None, // Could be calculated from tx size
         size_bytes: None,
+        change: None,
     })
 }
 
@@ -64,6 +65,176 @@ fn calculate_bitcoin_txid(raw_hex: &str) -> HawalaResult<String> {
     Ok(tx.compute_txid().to_string())
 }
 
+/// Sign a Bitcoin transaction whose inputs may each be a different script type
+///
+/// The type of every input is read from its UTXO's scriptPubKey and signed
+/// accordingly:
+///
+/// - P2WPKH: BIP-143 sighash, witness `<sig> <pubkey>`
+/// - P2SH-P2WPKH: BIP-143 sighash over the redeem script, which is pushed in
+///   the scriptSig, plus the same witness as P2WPKH
+/// - P2TR (key path): BIP-341 sighash over all prevouts, witness `<schnorr sig>`
+///
+/// Every input carries its own key, so UTXOs from BIP-49/84/86 accounts can
+/// be spent together. Whatever the outputs don't claim is the fee.
+pub fn sign_bitcoin_multi_input(params: &BitcoinMultiInputSignParams) -> HawalaResult<SignedTransaction> {
+    use bitcoin::hashes::Hash;
+    use bitcoin::key::{CompressedPublicKey, Keypair, TapTweak};
+    use bitcoin::secp256k1::{Message, Secp256k1};
+    use bitcoin::sighash::{EcdsaSighashType, Prevouts, SighashCache, TapSighashType};
+    use bitcoin::{
+        absolute::LockTime, transaction::Version, Address, Amount, OutPoint, PrivateKey,
+        ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid, Witness,
+    };
+    use std::str::FromStr;
+
+    /// How an input is spent, with the key material it needs
+    enum Spend {
+        P2wpkh(PrivateKey, CompressedPublicKey),
+        P2shP2wpkh(PrivateKey, CompressedPublicKey, ScriptBuf),
+        P2tr(Keypair),
+    }
+
+    if params.inputs.is_empty() {
+        return Err(HawalaError::invalid_input("No inputs to sign"));
+    }
+    if params.outputs.is_empty() {
+        return Err(HawalaError::invalid_input("No outputs"));
+    }
+    let network = match params.chain {
+        Chain::Bitcoin => bitcoin::Network::Bitcoin,
+        Chain::BitcoinTestnet => bitcoin::Network::Testnet,
+        other => return Err(HawalaError::invalid_input(format!("Not a Bitcoin chain: {:?}", other))),
+    };
+    let secp = Secp256k1::new();
+
+    let mut prevouts = Vec::with_capacity(params.inputs.len());
+    let mut spends = Vec::with_capacity(params.inputs.len());
+    let mut tx_inputs = Vec::with_capacity(params.inputs.len());
+    for (index, input) in params.inputs.iter().enumerate() {
+        let script_pubkey = ScriptBuf::from_hex(input.script_pubkey.trim_start_matches("0x"))
+            .map_err(|e| HawalaError::invalid_input(format!("Input {}: invalid script_pubkey: {}", index, e)))?;
+        let key = PrivateKey::from_wif(&input.private_key)
+            .map_err(|e| HawalaError::invalid_input(format!("Input {}: invalid WIF: {}", index, e)))?;
+        let wrong_key = || HawalaError::invalid_input(format!("Input {}: key does not match script_pubkey", index));
+
+        let mut script_sig = ScriptBuf::new();
+        let spend = if script_pubkey.is_p2tr() {
+            let keypair = Keypair::from_secret_key(&secp, &key.inner);
+            let (internal_key, _) = keypair.x_only_public_key();
+            if ScriptBuf::new_p2tr(&secp, internal_key, None) != script_pubkey {
+                return Err(wrong_key());
+            }
+            Spend::P2tr(keypair)
+        } else {
+            let pubkey = CompressedPublicKey::from_private_key(&secp, &key)
+                .map_err(|_| HawalaError::invalid_input(format!("Input {}: key must be compressed", index)))?;
+            let p2wpkh = ScriptBuf::new_p2wpkh(&pubkey.wpubkey_hash());
+            if script_pubkey.is_p2wpkh() {
+                if p2wpkh != script_pubkey {
+                    return Err(wrong_key());
+                }
+                Spend::P2wpkh(key, pubkey)
+            } else if script_pubkey.is_p2sh() {
+                // Only the nested-SegWit form of P2SH is spendable with a single key
+                if p2wpkh.to_p2sh() != script_pubkey {
+                    return Err(wrong_key());
+                }
+                script_sig = ScriptBuf::builder()
+                    .push_slice(<&bitcoin::script::PushBytes>::try_from(p2wpkh.as_bytes())
+                        .map_err(|_| HawalaError::internal("Redeem script too long"))?)
+                    .into_script();
+                Spend::P2shP2wpkh(key, pubkey, p2wpkh)
+            } else {
+                return Err(HawalaError::invalid_input(format!(
+                    "Input {}: unsupported script type {}",
+                    index,
+                    script_pubkey.to_hex_string()
+                )));
+            }
+        };
+
+        tx_inputs.push(TxIn {
+            previous_output: OutPoint {
+                txid: Txid::from_str(&input.txid)
+                    .map_err(|e| HawalaError::invalid_input(format!("Input {}: invalid txid: {}", index, e)))?,
+                vout: input.vout,
+            },
+            script_sig,
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::default(),
+        });
+        prevouts.push(TxOut { value: Amount::from_sat(input.value), script_pubkey });
+        spends.push(spend);
+    }
+
+    let mut tx_outputs = Vec::with_capacity(params.outputs.len());
+    for output in &params.outputs {
+        let address = Address::from_str(&output.address)
+            .and_then(|a| a.require_network(network))
+            .map_err(|e| HawalaError::invalid_input(format!("Invalid address {}: {}", output.address, e)))?;
+        tx_outputs.push(TxOut { value: Amount::from_sat(output.value), script_pubkey: address.script_pubkey() });
+    }
+
+    let total_in: u64 = params.inputs.iter().map(|i| i.value).sum();
+    let total_out: u64 = params.outputs.iter().map(|o| o.value).sum();
+    let fee = total_in.checked_sub(total_out)
+        .ok_or_else(|| HawalaError::insufficient_funds(format!("Outputs ({}) exceed inputs ({})", total_out, total_in)))?;
+
+    let mut tx = Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: tx_inputs,
+        output: tx_outputs,
+    };
+
+    let signing_err = |e: &dyn std::fmt::Display| HawalaError::signing_failed(e.to_string());
+    let mut cache = SighashCache::new(&mut tx);
+    for (index, spend) in spends.iter().enumerate() {
+        let mut witness = Witness::new();
+        match spend {
+            Spend::P2wpkh(key, pubkey) | Spend::P2shP2wpkh(key, pubkey, _) => {
+                // For nested SegWit the script code comes from the redeem script
+                let script_code = match spend {
+                    Spend::P2shP2wpkh(_, _, redeem) => redeem,
+                    _ => &prevouts[index].script_pubkey,
+                };
+                let sighash = cache
+                    .p2wpkh_signature_hash(index, script_code, prevouts[index].value, EcdsaSighashType::All)
+                    .map_err(|e| signing_err(&e))?;
+                let signature = secp.sign_ecdsa(&Message::from_digest(sighash.to_byte_array()), &key.inner);
+                let mut sig = signature.serialize_der().to_vec();
+                sig.push(EcdsaSighashType::All as u8);
+                witness.push(sig);
+                witness.push(pubkey.to_bytes());
+            }
+            Spend::P2tr(keypair) => {
+                let sighash = cache
+                    .taproot_key_spend_signature_hash(index, &Prevouts::All(&prevouts), TapSighashType::Default)
+                    .map_err(|e| signing_err(&e))?;
+                let tweaked = keypair.tap_tweak(&secp, None);
+                let signature = secp.sign_schnorr_no_aux_rand(
+                    &Message::from_digest(sighash.to_byte_array()),
+                    &tweaked.to_keypair(),
+                );
+                witness.push(signature.serialize());
+            }
+        }
+        *cache.witness_mut(index)
+            .ok_or_else(|| HawalaError::internal(format!("Invalid input index {}", index)))? = witness;
+    }
+
+    let vsize = tx.vsize();
+    Ok(SignedTransaction {
+        chain: params.chain,
+        raw_tx: hex::encode(bitcoin::consensus::encode::serialize(&tx)),
+        txid: tx.compute_txid().to_string(),
+        estimated_fee: Some(fee.to_string()),
+        size_bytes: Some(vsize as u32),
+        change: None,
+    })
+}
+
 // =============================================================================
 // Litecoin Signing
 // =============================================================================
@@ -100,6 +271,7 @@ pub fn sign_litecoin_transaction(params: &LitecoinSignParams) -> HawalaResult<Si
         txid,
         estimated_fee: None,
         size_bytes: None,
+        change: None,
     })
 }
 
@@ -133,11 +305,12 @@ pub fn sign_ethereum_transaction(params: &EthereumSignParams) -> HawalaResult<Si
     let txid = extract_eth_txid(&signed_hex)?;
     
     Ok(SignedTransaction {
-        chain: chain_from_id(params.chain_id),
+        chain: Chain::from_chain_id(params.chain_id).unwrap_or(Chain::Ethereum),
         raw_tx: signed_hex,
         txid,
         estimated_fee: None,
         size_bytes: None,
+        change: None,
     })
 }
 
@@ -164,7 +337,7 @@ fn extract_eth_txid(raw_hex: &str) -> HawalaResult<String> {
 pub fn sign_solana_transaction(params: &SolanaSignParams) -> HawalaResult<SignedTransaction> {
     let signed_base58 = solana_wallet::prepare_solana_transaction(
         &params.recipient,
-        params.amount_sol,
+        &params.amount_sol,
         &params.recent_blockhash,
         &params.sender_base58,
     ).map_err(|e| HawalaError::signing_failed(e.to_string()))?;
@@ -178,6 +351,7 @@ pub fn sign_solana_transaction(params: &SolanaSignParams) -> HawalaResult<Signed
         txid,
         estimated_fee: None,
         size_bytes: None,
+        change: None,
     })
 }
 
@@ -204,6 +378,7 @@ pub fn sign_xrp_transaction(params: &XrpSignParams) -> HawalaResult<SignedTransa
         txid,
         estimated_fee: None,
         size_bytes: None,
+        change: None,
     })
 }
 
@@ -219,6 +394,7 @@ pub enum SignParams {
     Ethereum(EthereumSignParams),
     Solana(SolanaSignParams),
     Xrp(XrpSignParams),
+    BitcoinMultiInput(BitcoinMultiInputSignParams),
 }
 
 /// Bitcoin signing parameters
@@ -232,6 +408,33 @@ pub struct BitcoinSignParams {
     pub utxos: Option<Vec<UtxoInput>>,
 }
 
+/// Parameters for [`sign_bitcoin_multi_input`]
+#[derive(Debug, Clone)]
+pub struct BitcoinMultiInputSignParams {
+    pub chain: Chain, // Bitcoin or BitcoinTestnet
+    pub inputs: Vec<BitcoinSpendInput>,
+    pub outputs: Vec<BitcoinSpendOutput>,
+}
+
+/// A UTXO to spend, with the key that controls it
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct BitcoinSpendInput {
+    pub txid: String,
+    pub vout: u32,
+    pub value: u64,
+    /// Hex scriptPubKey of the output being spent; decides how it is signed
+    pub script_pubkey: String,
+    /// WIF private key for this input
+    pub private_key: String,
+}
+
+/// A payment in a [`BitcoinMultiInputSignParams`] transaction
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct BitcoinSpendOutput {
+    pub address: String,
+    pub value: u64,
+}
+
 /// Litecoin signing parameters
 #[derive(Debug, Clone)]
 pub struct LitecoinSignParams {
@@ -262,7 +465,8 @@ pub struct EthereumSignParams {
 #[derive(Debug, Clone)]
 pub struct SolanaSignParams {
     pub recipient: String,
-    pub amount_sol: f64,
+    /// Decimal SOL string, e.g. "0.5"
+    pub amount_sol: String,
     pub recent_blockhash: String,
     pub sender_base58: String,
 }
@@ -278,7 +482,10 @@ pub struct XrpSignParams {
 }
 
 /// UTXO input for Bitcoin/Litecoin
-#[derive(Debug, Clone)]
+///
+/// Deserializes from `{ "txid", "vout", "value" }`; extra fields such as the
+/// Esplora `status` object are ignored.
+#[derive(Debug, Clone, serde::Deserialize)]
 pub struct UtxoInput {
     pub txid: String,
     pub vout: u32,
@@ -293,24 +500,7 @@ pub fn sign_transaction(params: SignParams) -> HawalaResult<SignedTransaction> {
         SignParams::Ethereum(p) => sign_ethereum_transaction(&p),
         SignParams::Solana(p) => sign_solana_transaction(&p),
         SignParams::Xrp(p) => sign_xrp_transaction(&p),
-    }
-}
-
-// =============================================================================
-// Helper Functions
-// =============================================================================
-
-fn chain_from_id(chain_id: u64) -> Chain {
-    match chain_id {
-        1 => Chain::Ethereum,
-        11155111 => Chain::EthereumSepolia,
-        56 => Chain::Bnb,
-        137 => Chain::Polygon,
-        42161 => Chain::Arbitrum,
-        10 => Chain::Optimism,
-        8453 => Chain::Base,
-        43114 => Chain::Avalanche,
-        _ => Chain::Ethereum,
+        SignParams::BitcoinMultiInput(p) => sign_bitcoin_multi_input(&p),
     }
 }
 
@@ -319,9 +509,178 @@ mod tests {
     use super::*;
     
     #[test]
-    fn test_chain_from_id() {
-        assert!(matches!(chain_from_id(1), Chain::Ethereum));
-        assert!(matches!(chain_from_id(56), Chain::Bnb));
-        assert!(matches!(chain_from_id(137), Chain::Polygon));
+    fn test_sign_bitcoin_mixed_input_types() {
+        use crate::signing::preimage::bitcoin::{
+            get_bitcoin_sighashes, BitcoinInput, BitcoinInputType, BitcoinOutput, BitcoinSigHashType,
+            UnsignedBitcoinTransaction,
+        };
+        use bitcoin::hashes::Hash;
+        use bitcoin::key::{CompressedPublicKey, Keypair, TapTweak};
+        use bitcoin::secp256k1::{ecdsa, schnorr, Message, Secp256k1, SecretKey};
+        use bitcoin::sighash::{EcdsaSighashType, Prevouts, SighashCache, TapSighashType};
+        use bitcoin::{Amount, PrivateKey, ScriptBuf, TxOut};
+
+        let secp = Secp256k1::new();
+        let key = |b: u8| PrivateKey::new(SecretKey::from_slice(&[b; 32]).unwrap(), bitcoin::Network::Bitcoin);
+        let (wpkh_key, nested_key, tr_key) = (key(1), key(2), key(3));
+        let wpkh_pub = CompressedPublicKey::from_private_key(&secp, &wpkh_key).unwrap();
+        let nested_pub = CompressedPublicKey::from_private_key(&secp, &nested_key).unwrap();
+        let tr_pair = Keypair::from_secret_key(&secp, &tr_key.inner);
+
+        let wpkh_spk = ScriptBuf::new_p2wpkh(&wpkh_pub.wpubkey_hash());
+        let redeem = ScriptBuf::new_p2wpkh(&nested_pub.wpubkey_hash());
+        let nested_spk = redeem.to_p2sh();
+        let tr_spk = ScriptBuf::new_p2tr(&secp, tr_pair.x_only_public_key().0, None);
+
+        let input = |txid: &str, value: u64, spk: &ScriptBuf, key: &PrivateKey| BitcoinSpendInput {
+            txid: txid.repeat(32),
+            vout: 0,
+            value,
+            script_pubkey: spk.to_hex_string(),
+            private_key: key.to_wif(),
+        };
+        let params = BitcoinMultiInputSignParams {
+            chain: Chain::Bitcoin,
+            inputs: vec![
+                input("a1", 40_000, &wpkh_spk, &wpkh_key),
+                input("b2", 30_000, &nested_spk, &nested_key),
+                input("c3", 50_000, &tr_spk, &tr_key),
+            ],
+            outputs: vec![BitcoinSpendOutput {
+                address: "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g".to_string(),
+                value: 118_000,
+            }],
+        };
+        let signed = sign_bitcoin_multi_input(&params).unwrap();
+        assert_eq!(signed.estimated_fee.as_deref(), Some("2000"));
+
+        let bytes = hex::decode(&signed.raw_tx).unwrap();
+        let tx: bitcoin::Transaction = bitcoin::consensus::encode::deserialize(&bytes).unwrap();
+        assert_eq!(tx.compute_txid().to_string(), signed.txid);
+
+        // Only the nested input carries a scriptSig: a push of its redeem script
+        assert!(tx.input[0].script_sig.is_empty());
+        assert_eq!(&tx.input[1].script_sig.as_bytes()[1..], redeem.as_bytes());
+        assert!(tx.input[2].script_sig.is_empty());
+
+        let prevouts: Vec<TxOut> = params.inputs.iter().zip([&wpkh_spk, &nested_spk, &tr_spk])
+            .map(|(i, spk)| TxOut { value: Amount::from_sat(i.value), script_pubkey: spk.clone() })
+            .collect();
+        let mut cache = SighashCache::new(&tx);
+        let wpkh_hash = cache.p2wpkh_signature_hash(0, &wpkh_spk, prevouts[0].value, EcdsaSighashType::All).unwrap();
+        let nested_hash = cache.p2wpkh_signature_hash(1, &redeem, prevouts[1].value, EcdsaSighashType::All).unwrap();
+        let tr_hash = cache
+            .taproot_key_spend_signature_hash(2, &Prevouts::All(&prevouts), TapSighashType::Default)
+            .unwrap();
+
+        // ECDSA witnesses: <DER sig || SIGHASH_ALL> <pubkey>
+        for (index, hash, pubkey) in [(0, wpkh_hash, wpkh_pub), (1, nested_hash, nested_pub)] {
+            let witness: Vec<&[u8]> = tx.input[index].witness.iter().collect();
+            assert_eq!(witness.len(), 2);
+            let (sig, sighash_byte) = witness[0].split_at(witness[0].len() - 1);
+            assert_eq!(sighash_byte, [0x01]);
+            assert_eq!(witness[1], pubkey.to_bytes());
+            let sig = ecdsa::Signature::from_der(sig).unwrap();
+            secp.verify_ecdsa(&Message::from_digest(hash.to_byte_array()), &sig, &pubkey.0).unwrap();
+        }
+
+        // Taproot witness: a single 64-byte signature by the tweaked key
+        let witness: Vec<&[u8]> = tx.input[2].witness.iter().collect();
+        assert_eq!(witness.len(), 1);
+        let sig = schnorr::Signature::from_slice(witness[0]).unwrap();
+        let output_key = tr_pair.tap_tweak(&secp, None).to_keypair().x_only_public_key().0;
+        secp.verify_schnorr(&sig, &Message::from_digest(tr_hash.to_byte_array()), &output_key).unwrap();
+
+        // The external-signing preimages agree input by input
+        let unsigned = UnsignedBitcoinTransaction {
+            version: 2,
+            inputs: [
+                (wpkh_spk.clone(), BitcoinInputType::P2WPKH),
+                (redeem.clone(), BitcoinInputType::P2SH_P2WPKH),
+                (tr_spk.clone(), BitcoinInputType::P2TR_KeyPath),
+            ]
+            .into_iter()
+            .zip(&params.inputs)
+            .map(|((script_code, input_type), input)| BitcoinInput {
+                txid: hex::decode(&input.txid).unwrap().try_into().unwrap(),
+                vout: input.vout,
+                script_code: script_code.to_bytes(),
+                value: input.value,
+                sequence: 0xfffffffd,
+                derivation_path: None,
+                input_type,
+            })
+            .collect(),
+            outputs: tx.output.iter()
+                .map(|o| BitcoinOutput { value: o.value.to_sat(), script_pubkey: o.script_pubkey.to_bytes() })
+                .collect(),
+            locktime: 0,
+        };
+        let preimages = get_bitcoin_sighashes(&unsigned, BitcoinSigHashType::TaprootDefault).unwrap();
+        assert_eq!(preimages[0].hash, wpkh_hash.to_byte_array());
+        assert_eq!(preimages[1].hash, nested_hash.to_byte_array());
+        assert_eq!(preimages[2].hash, tr_hash.to_byte_array());
+    }
+
+    #[test]
+    fn test_sign_bitcoin_mixed_input_rejects_wrong_key() {
+        use bitcoin::secp256k1::{Secp256k1, SecretKey};
+
+        let secp = Secp256k1::new();
+        let owner = bitcoin::PrivateKey::new(SecretKey::from_slice(&[1; 32]).unwrap(), bitcoin::Network::Bitcoin);
+        let other = bitcoin::PrivateKey::new(SecretKey::from_slice(&[2; 32]).unwrap(), bitcoin::Network::Bitcoin);
+        let pubkey = bitcoin::key::CompressedPublicKey::from_private_key(&secp, &owner).unwrap();
+
+        let params = BitcoinMultiInputSignParams {
+            chain: Chain::Bitcoin,
+            inputs: vec![BitcoinSpendInput {
+                txid: "a1".repeat(32),
+                vout: 0,
+                value: 10_000,
+                script_pubkey: bitcoin::ScriptBuf::new_p2wpkh(&pubkey.wpubkey_hash()).to_hex_string(),
+                private_key: other.to_wif(),
+            }],
+            outputs: vec![BitcoinSpendOutput {
+                address: "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g".to_string(),
+                value: 9_000,
+            }],
+        };
+        assert!(sign_bitcoin_multi_input(&params).is_err());
+    }
+
+    #[test]
+    fn test_sign_bitcoin_uses_provided_utxos() {
+        let keys = crate::wallet::restore_from_mnemonic(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .unwrap();
+        let sign_with = |utxos: serde_json::Value| {
+            let params = BitcoinSignParams {
+                chain: Chain::Bitcoin,
+                recipient: "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g".to_string(),
+                amount_sats: 50_000,
+                fee_rate_sats_per_vbyte: 2,
+                sender_wif: keys.bitcoin.private_wif.clone(),
+                utxos: Some(serde_json::from_value(utxos).unwrap()),
+            };
+            let signed = sign_bitcoin_transaction(&params).unwrap();
+            let bytes = hex::decode(&signed.raw_tx).unwrap();
+            let tx: bitcoin::Transaction = bitcoin::consensus::encode::deserialize(&bytes).unwrap();
+            tx.input
+                .iter()
+                .map(|i| format!("{}:{}", i.previous_output.txid, i.previous_output.vout))
+                .collect::<Vec<_>>()
+        };
+
+        // Esplora shape, as the CLI and the FFI receive it
+        let first = sign_with(serde_json::json!([
+            { "txid": "a".repeat(64), "vout": 0, "value": 100_000, "status": { "confirmed": true } }
+        ]));
+        assert_eq!(first, vec![format!("{}:0", "a".repeat(64))]);
+
+        let second = sign_with(serde_json::json!([
+            { "txid": "b".repeat(64), "vout": 3, "value": 80_000 }
+        ]));
+        assert_eq!(second, vec![format!("{}:3", "b".repeat(64))]);
     }
 }
@@ -0,0 +1,51 @@
+//! Shared Transaction Test Fixtures
+
+use crate::fees::units::SatPerVb;
+use crate::types::*;
+
+pub(crate) const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+/// A 50 000 sat Bitcoin send from the test mnemonic's first address,
+/// spending one 100 000 sat UTXO at 2 sat/vB
+pub(crate) fn bitcoin_request() -> TransactionRequest {
+    let keys = crate::wallet::restore_from_mnemonic(MNEMONIC).unwrap();
+    TransactionRequest {
+        chain: Chain::Bitcoin,
+        from: keys.bitcoin.address.clone(),
+        to: "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g".to_string(),
+        amount: "50000".to_string(),
+        private_key: keys.bitcoin.private_wif.clone(),
+        utxos: Some(vec![Utxo {
+            txid: "a".repeat(64),
+            vout: 0,
+            value: 100_000,
+            script_pubkey: None,
+            confirmed: true,
+            block_height: Some(800_000),
+        }]),
+        fee_rate: Some(SatPerVb(2)),
+        nonce: None,
+        gas_limit: None,
+        auto_gas: false,
+        gas_price: None,
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        data: None,
+        balance: None,
+        access_list: None,
+        recent_blockhash: None,
+        sequence: None,
+        destination_tag: None,
+        branch: None,
+        counter: None,
+        fee_mutez: None,
+        storage_limit: None,
+        change_address: None,
+        account_xpub: None,
+        change_index: None,
+        dry_run: false,
+        send_max: false,
+        fee_guard: None,
+        allow_high_fee: false,
+    }
+}
@@ -7,15 +7,15 @@ use crate::error::{HawalaError, HawalaResult, ErrorCode};
 use crate::types::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Mutex;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 // =============================================================================
 // Types
 // =============================================================================
 
 /// Transaction status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TxStatus {
     /// Transaction is in mempool, not yet confirmed
@@ -28,6 +28,9 @@ pub enum TxStatus {
     Failed,
     /// Transaction was dropped from mempool
     Dropped,
+    /// Another transaction spending the same inputs or nonce took its place.
+    /// `by` is unknown on EVM chains, where only the consumed nonce is seen
+    Replaced { by: Option<String> },
 }
 
 /// A transaction tracking entry (internal to tracker)
@@ -148,6 +151,40 @@ pub fn get_all_tracked() -> Vec<TxTrackingEntry> {
 
 /// Check transaction status and update tracking
 pub fn check_transaction(txid: &str, chain: Chain) -> HawalaResult<TxTrackingEntry> {
+    check_transaction_with(&NetworkTxSource, txid, chain)
+}
+
+fn check_transaction_with<S: TxStatusSource + ?Sized>(source: &S, txid: &str, chain: Chain) -> HawalaResult<TxTrackingEntry> {
+    let result = source.check(txid, chain)?;
+    Ok(record_status(txid, chain, result.confirmations, result.status, result.block_height))
+}
+
+/// Store the latest status of a transaction in the tracker
+fn record_status(txid: &str, chain: Chain, confirmations: u32, status: TxStatus, block_height: Option<u64>) -> TxTrackingEntry {
+    let now = current_timestamp();
+    let mut tx = TxTrackingEntry {
+        txid: txid.to_string(),
+        chain,
+        confirmations,
+        status,
+        block_height,
+        timestamp: now,
+        last_checked: now,
+    };
+    
+    // Preserve original timestamp if already tracked
+    if let Ok(mut tracked) = TRACKED_TXS.lock() {
+        if let Some(existing) = tracked.get(txid) {
+            tx.timestamp = existing.timestamp;
+        }
+        tracked.insert(txid.to_string(), tx.clone());
+    }
+    
+    tx
+}
+
+/// Query the chain's provider for a transaction
+fn fetch_transaction(txid: &str, chain: Chain) -> HawalaResult<TransactionCheckResult> {
     let result = match chain {
         Chain::Bitcoin | Chain::BitcoinTestnet | Chain::Litecoin => {
             check_bitcoin_transaction(txid, chain)?
@@ -169,27 +206,7 @@ pub fn check_transaction(txid: &str, chain: Chain) -> HawalaResult<TxTrackingEnt
         }
     };
     
-    // Update tracked transaction
-    let now = current_timestamp();
-    let mut tx = TxTrackingEntry {
-        txid: txid.to_string(),
-        chain,
-        confirmations: result.confirmations,
-        status: result.status,
-        block_height: result.block_height,
-        timestamp: now,
-        last_checked: now,
-    };
-    
-    // Preserve original timestamp if already tracked
-    if let Ok(mut tracked) = TRACKED_TXS.lock() {
-        if let Some(existing) = tracked.get(txid) {
-            tx.timestamp = existing.timestamp;
-        }
-        tracked.insert(txid.to_string(), tx.clone());
-    }
-    
-    Ok(tx)
+    Ok(result)
 }
 
 /// Get current confirmations for a transaction
@@ -199,21 +216,61 @@ pub fn get_confirmations(txid: &str, chain: Chain) -> HawalaResult<u32> {
 }
 
 /// Get transaction status
-pub fn get_transaction_status(txid: &str, chain: Chain) -> HawalaResult<TxStatus> {
-    let result = check_transaction(txid, chain)?;
-    Ok(result.status)
+///
+/// `spends` is what the transaction spends: its outpoints on UTXO chains, or
+/// its sender and nonce on EVM chains. A transaction that is still pending
+/// while another transaction has spent the same inputs, or mined the same
+/// nonce, is reported as [`TxStatus::Replaced`] rather than left pending or
+/// dropped. A key that doesn't fit the chain is ignored.
+pub fn get_transaction_status(txid: &str, chain: Chain, spends: Option<&TxConflictKey>) -> HawalaResult<TxStatus> {
+    get_transaction_status_with(&NetworkTxSource, txid, chain, spends)
+}
+
+/// [`get_transaction_status`] with an explicit source
+pub fn get_transaction_status_with<S: TxStatusSource + ?Sized>(
+    source: &S,
+    txid: &str,
+    chain: Chain,
+    spends: Option<&TxConflictKey>,
+) -> HawalaResult<TxStatus> {
+    let entry = check_transaction_with(source, txid, chain)?;
+    if entry.status != TxStatus::Pending {
+        return Ok(entry.status);
+    }
+
+    let key = match spends {
+        // The original's fee is unknown once it leaves the mempool, so any
+        // other spend of its inputs counts
+        Some(TxConflictKey::Inputs { outpoints, .. }) if chain.is_utxo() && !outpoints.is_empty() => {
+            TxConflictKey::Inputs { outpoints: outpoints.clone(), fee: None }
+        }
+        Some(key @ TxConflictKey::Nonce { .. }) if chain.is_evm() => key.clone(),
+        _ => return Ok(entry.status),
+    };
+    match source.find_replacement(txid, chain, &key)? {
+        Some(Replacement { txid: by }) => {
+            let status = TxStatus::Replaced { by };
+            record_status(txid, chain, 0, status.clone(), None);
+            Ok(status)
+        }
+        None => Ok(entry.status),
+    }
 }
 
 // =============================================================================
 // Bitcoin/Litecoin Transaction Checking
 // =============================================================================
 
-fn check_bitcoin_transaction(txid: &str, chain: Chain) -> HawalaResult<TransactionCheckResult> {
-    let base_url = match chain {
+fn esplora_base_url(chain: Chain) -> &'static str {
+    match chain {
         Chain::BitcoinTestnet => "https://mempool.space/testnet/api",
         Chain::Litecoin => "https://litecoinspace.org/api",
         _ => "https://mempool.space/api",
-    };
+    }
+}
+
+fn check_bitcoin_transaction(txid: &str, chain: Chain) -> HawalaResult<TransactionCheckResult> {
+    let base_url = esplora_base_url(chain);
     
     let url = format!("{}/tx/{}", base_url, txid);
     
@@ -626,6 +683,322 @@ fn check_xrp_transaction(txid: &str) -> HawalaResult<TransactionCheckResult> {
     })
 }
 
+// =============================================================================
+// Confirmation Polling
+// =============================================================================
+
+/// Consecutive "not found" polls, after the transaction was seen, before it
+/// is reported as dropped
+const MISSES_BEFORE_DROPPED: u32 = 3;
+
+/// Final outcome of [`poll_until_confirmed`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PollStatus {
+    /// Reached the required confirmations
+    Confirmed,
+    /// Included but failed (reverted for EVM)
+    Failed,
+    /// Disappeared from the mempool without a conflicting transaction
+    Dropped,
+    /// A higher-fee transaction spending the same inputs (or nonce) replaced it
+    Replaced,
+    /// Timeout elapsed first
+    TimedOut,
+}
+
+/// Result of polling a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PollResult {
+    pub txid: String,
+    pub chain: Chain,
+    pub status: PollStatus,
+    /// Confirmations at the last successful check
+    pub confirmations: u32,
+    pub block_height: Option<u64>,
+    /// Replacing transaction, when known (UTXO chains only)
+    pub replaced_by: Option<String>,
+    /// Number of status checks made
+    pub polls: u32,
+}
+
+/// What a transaction spends, used to recognize a replacement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxConflictKey {
+    /// Outpoints spent by a UTXO transaction, and its fee in base units
+    Inputs { outpoints: Vec<(String, u32)>, fee: Option<u64> },
+    /// Sender and nonce of an account-based transaction
+    Nonce { from: String, nonce: u64 },
+}
+
+/// A transaction that replaced the one being polled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    /// Replacement txid, if the provider can identify it
+    pub txid: Option<String>,
+}
+
+/// Provider queried by the poller
+///
+/// Calls are made from a blocking thread, so implementations may block.
+pub trait TxStatusSource: Send + Sync {
+    /// Current status of a transaction
+    fn check(&self, txid: &str, chain: Chain) -> HawalaResult<TransactionCheckResult>;
+    /// Inputs or nonce of a transaction that is still in the mempool
+    fn conflict_key(&self, txid: &str, chain: Chain) -> HawalaResult<Option<TxConflictKey>>;
+    /// A transaction, other than `txid`, that spent `key` for a higher fee
+    fn find_replacement(&self, txid: &str, chain: Chain, key: &TxConflictKey) -> HawalaResult<Option<Replacement>>;
+}
+
+/// [`TxStatusSource`] backed by the public explorers and RPC nodes used by
+/// [`check_transaction`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NetworkTxSource;
+
+impl TxStatusSource for NetworkTxSource {
+    fn check(&self, txid: &str, chain: Chain) -> HawalaResult<TransactionCheckResult> {
+        fetch_transaction(txid, chain)
+    }
+
+    fn conflict_key(&self, txid: &str, chain: Chain) -> HawalaResult<Option<TxConflictKey>> {
+        match chain {
+            Chain::Bitcoin | Chain::BitcoinTestnet | Chain::Litecoin => {
+                let json = esplora_get(chain, &format!("tx/{}", txid))?;
+                let outpoints = json["vin"]
+                    .as_array()
+                    .map(|vin| {
+                        vin.iter()
+                            .filter_map(|input| {
+                                let prev = input["txid"].as_str()?;
+                                let vout = input["vout"].as_u64()?;
+                                Some((prev.to_string(), vout as u32))
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                if outpoints.is_empty() {
+                    return Ok(None);
+                }
+                Ok(Some(TxConflictKey::Inputs { outpoints, fee: json["fee"].as_u64() }))
+            }
+            chain if chain.is_evm() => {
+                let tx = evm_rpc(chain, "eth_getTransactionByHash", serde_json::json!([txid]))?;
+                let from = tx["from"].as_str();
+                let nonce = tx["nonce"].as_str().and_then(parse_hex_u64);
+                match (from, nonce) {
+                    (Some(from), Some(nonce)) => Ok(Some(TxConflictKey::Nonce { from: from.to_string(), nonce })),
+                    _ => Ok(None),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn find_replacement(&self, txid: &str, chain: Chain, key: &TxConflictKey) -> HawalaResult<Option<Replacement>> {
+        match key {
+            TxConflictKey::Inputs { outpoints, fee } => {
+                for (prev, vout) in outpoints {
+                    let outspend = esplora_get(chain, &format!("tx/{}/outspend/{}", prev, vout))?;
+                    let spender = match outspend["txid"].as_str() {
+                        Some(spender) if outspend["spent"].as_bool() == Some(true) && spender != txid => spender,
+                        _ => continue,
+                    };
+                    let replacement_fee = esplora_get(chain, &format!("tx/{}", spender))?["fee"].as_u64();
+                    let higher_fee = match (fee, replacement_fee) {
+                        (Some(original), Some(replacement)) => replacement > *original,
+                        _ => true,
+                    };
+                    if higher_fee {
+                        return Ok(Some(Replacement { txid: Some(spender.to_string()) }));
+                    }
+                }
+                Ok(None)
+            }
+            TxConflictKey::Nonce { from, nonce } => {
+                // The nonce was mined by some other transaction; EVM nodes
+                // cannot look that transaction up by nonce
+                let count = evm_rpc(chain, "eth_getTransactionCount", serde_json::json!([from, "latest"]))?;
+                match count.as_str().and_then(parse_hex_u64) {
+                    Some(count) if count > *nonce => Ok(Some(Replacement { txid: None })),
+                    _ => Ok(None),
+                }
+            }
+        }
+    }
+}
+
+/// Default interval between status checks for a chain
+pub fn poll_interval(chain: Chain) -> Duration {
+    match chain {
+        Chain::Bitcoin | Chain::BitcoinTestnet | Chain::Litecoin => Duration::from_secs(30),
+        Chain::Monero => Duration::from_secs(60),
+        Chain::Solana | Chain::SolanaDevnet | Chain::Xrp | Chain::XrpTestnet => Duration::from_secs(2),
+        _ => Duration::from_secs(5),
+    }
+}
+
+/// Poll a transaction until it has `required` confirmations, fails, is
+/// dropped or replaced, or `timeout` elapses
+///
+/// Solana and XRP report finality rather than a depth, so `required` is
+/// capped at 1 there. Transient provider errors are retried until the
+/// timeout; chains without tracking support return an error.
+pub async fn poll_until_confirmed(
+    txid: &str,
+    chain: Chain,
+    required: u32,
+    timeout: Duration,
+) -> HawalaResult<PollResult> {
+    poll_until_confirmed_with(Arc::new(NetworkTxSource), txid, chain, required, timeout, poll_interval(chain)).await
+}
+
+/// [`poll_until_confirmed`] with an explicit source and interval
+pub async fn poll_until_confirmed_with<S: TxStatusSource + 'static>(
+    source: Arc<S>,
+    txid: &str,
+    chain: Chain,
+    required: u32,
+    timeout: Duration,
+    interval: Duration,
+) -> HawalaResult<PollResult> {
+    let required = match chain {
+        Chain::Solana | Chain::SolanaDevnet | Chain::Xrp | Chain::XrpTestnet => 1,
+        _ => required.max(1),
+    };
+    let deadline = Instant::now() + timeout;
+    
+    let mut result = PollResult {
+        txid: txid.to_string(),
+        chain,
+        status: PollStatus::TimedOut,
+        confirmations: 0,
+        block_height: None,
+        replaced_by: None,
+        polls: 0,
+    };
+    let mut seen = false;
+    let mut misses = 0;
+    let mut conflict_key: Option<TxConflictKey> = None;
+    
+    loop {
+        result.polls += 1;
+        let owned_txid = txid.to_string();
+        match run_blocking(&source, move |s| s.check(&owned_txid, chain)).await {
+            Ok(check) if check.found => {
+                seen = true;
+                misses = 0;
+                result.confirmations = check.confirmations;
+                result.block_height = check.block_height;
+                
+                if check.status == TxStatus::Failed {
+                    result.status = PollStatus::Failed;
+                    return Ok(result);
+                }
+                if check.confirmations >= required {
+                    result.status = PollStatus::Confirmed;
+                    return Ok(result);
+                }
+                if check.confirmations == 0 && conflict_key.is_none() {
+                    let owned_txid = txid.to_string();
+                    conflict_key = run_blocking(&source, move |s| s.conflict_key(&owned_txid, chain))
+                        .await
+                        .ok()
+                        .flatten();
+                }
+            }
+            Ok(_) if seen => {
+                if let Some(key) = conflict_key.clone() {
+                    let owned_txid = txid.to_string();
+                    let replacement = run_blocking(&source, move |s| s.find_replacement(&owned_txid, chain, &key)).await;
+                    if let Ok(Some(replacement)) = replacement {
+                        result.status = PollStatus::Replaced;
+                        result.replaced_by = replacement.txid;
+                        return Ok(result);
+                    }
+                }
+                misses += 1;
+                if misses >= MISSES_BEFORE_DROPPED {
+                    result.status = PollStatus::Dropped;
+                    return Ok(result);
+                }
+            }
+            // Not yet propagated to this provider
+            Ok(_) => {}
+            Err(e) if e.code == ErrorCode::NotImplemented => return Err(e),
+            Err(_) => {}
+        }
+        
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(result);
+        }
+        tokio::time::sleep(interval.min(deadline - now)).await;
+    }
+}
+
+/// Run a source call on the blocking thread pool (the network source uses
+/// blocking HTTP clients)
+async fn run_blocking<S, T, F>(source: &Arc<S>, f: F) -> HawalaResult<T>
+where
+    S: TxStatusSource + 'static,
+    T: Send + 'static,
+    F: FnOnce(&S) -> HawalaResult<T> + Send + 'static,
+{
+    let source = Arc::clone(source);
+    tokio::task::spawn_blocking(move || f(&source))
+        .await
+        .map_err(|e| HawalaError::internal(format!("Status check task failed: {}", e)))?
+}
+
+fn esplora_get(chain: Chain, path: &str) -> HawalaResult<serde_json::Value> {
+    let url = format!("{}/{}", esplora_base_url(chain), path);
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| HawalaError::network_error(format!("Failed to create client: {}", e)))?;
+    
+    client
+        .get(&url)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| HawalaError::network_error(format!("Request failed: {}", e)))?
+        .json()
+        .map_err(|e| HawalaError::parse_error(format!("Failed to parse response: {}", e)))
+}
+
+/// Call a JSON-RPC method on the first EVM endpoint that answers
+fn evm_rpc(chain: Chain, method: &str, params: serde_json::Value) -> HawalaResult<serde_json::Value> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| HawalaError::network_error(format!("Failed to create client: {}", e)))?;
+    let payload = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": params,
+        "id": 1
+    });
+    
+    for endpoint in get_rpc_endpoints(chain.chain_id().unwrap_or(1)) {
+        let response = client
+            .post(endpoint)
+            .json(&payload)
+            .send()
+            .and_then(|r| r.json::<serde_json::Value>());
+        if let Ok(mut json) = response {
+            if json.get("error").is_none() {
+                return Ok(json.get_mut("result").map(serde_json::Value::take).unwrap_or_default());
+            }
+        }
+    }
+    
+    Err(HawalaError::network_error(format!("All RPC endpoints failed for {}", method)))
+}
+
+fn parse_hex_u64(s: &str) -> Option<u64> {
+    u64::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+}
+
 // =============================================================================
 // Helper Functions
 // =============================================================================
@@ -665,4 +1038,229 @@ mod tests {
         assert_eq!(tx.confirmation_progress(), 0.5);
         assert!(!tx.is_confirmed());
     }
+    
+    /// Replays a fixed sequence of checks, repeating the last one
+    struct ScriptedSource {
+        checks: Vec<(bool, u32)>,
+        next: Mutex<usize>,
+        replacement: Option<Replacement>,
+    }
+    
+    impl ScriptedSource {
+        fn new(checks: Vec<(bool, u32)>, replacement: Option<Replacement>) -> Arc<Self> {
+            Arc::new(Self { checks, next: Mutex::new(0), replacement })
+        }
+    }
+    
+    impl TxStatusSource for ScriptedSource {
+        fn check(&self, txid: &str, chain: Chain) -> HawalaResult<TransactionCheckResult> {
+            let mut next = self.next.lock().unwrap();
+            let (found, confirmations) = self.checks[(*next).min(self.checks.len() - 1)];
+            *next += 1;
+            Ok(TransactionCheckResult {
+                txid: txid.to_string(),
+                chain,
+                found,
+                confirmations,
+                status: if confirmations == 0 { TxStatus::Pending } else { TxStatus::Confirming },
+                block_height: if confirmations == 0 { None } else { Some(100) },
+                block_hash: None,
+                fee_paid: None,
+                gas_used: None,
+            })
+        }
+        
+        fn conflict_key(&self, _txid: &str, _chain: Chain) -> HawalaResult<Option<TxConflictKey>> {
+            Ok(Some(TxConflictKey::Inputs { outpoints: vec![("00".repeat(32), 0)], fee: Some(500) }))
+        }
+        
+        fn find_replacement(&self, _txid: &str, _chain: Chain, _key: &TxConflictKey) -> HawalaResult<Option<Replacement>> {
+            Ok(self.replacement.clone())
+        }
+    }
+    
+    async fn poll(source: Arc<ScriptedSource>, required: u32, timeout_ms: u64) -> PollResult {
+        poll_until_confirmed_with(
+            source,
+            "txid",
+            Chain::Bitcoin,
+            required,
+            Duration::from_millis(timeout_ms),
+            Duration::from_millis(1),
+        )
+        .await
+        .unwrap()
+    }
+    
+    #[tokio::test]
+    async fn test_poll_walks_to_confirmed() {
+        let source = ScriptedSource::new(vec![(false, 0), (true, 0), (true, 1), (true, 2), (true, 3)], None);
+        let result = poll(source, 3, 5_000).await;
+        
+        assert_eq!(result.status, PollStatus::Confirmed);
+        assert_eq!(result.confirmations, 3);
+        assert_eq!(result.block_height, Some(100));
+        assert_eq!(result.polls, 5);
+    }
+    
+    #[tokio::test]
+    async fn test_poll_detects_replacement() {
+        let replacement = Replacement { txid: Some("ff".repeat(32)) };
+        let source = ScriptedSource::new(vec![(true, 0), (false, 0)], Some(replacement));
+        let result = poll(source, 1, 5_000).await;
+        
+        assert_eq!(result.status, PollStatus::Replaced);
+        assert_eq!(result.replaced_by, Some("ff".repeat(32)));
+    }
+    
+    #[tokio::test]
+    async fn test_poll_dropped_and_timed_out() {
+        let source = ScriptedSource::new(vec![(true, 0), (false, 0)], None);
+        let result = poll(source, 1, 5_000).await;
+        assert_eq!(result.status, PollStatus::Dropped);
+        assert_eq!(result.polls, 1 + MISSES_BEFORE_DROPPED);
+        
+        // Never propagated: keeps polling until the timeout
+        let source = ScriptedSource::new(vec![(false, 0)], None);
+        let result = poll(source, 1, 20).await;
+        assert_eq!(result.status, PollStatus::TimedOut);
+        assert!(result.polls > 1);
+    }
+    
+    /// Knows one set of transactions and who spends which outpoint
+    struct MempoolSource {
+        /// txid -> (fee, confirmations)
+        txs: HashMap<String, (u64, u32)>,
+        /// outpoint -> spending txid
+        spends: HashMap<(String, u32), String>,
+    }
+    
+    impl TxStatusSource for MempoolSource {
+        fn check(&self, txid: &str, chain: Chain) -> HawalaResult<TransactionCheckResult> {
+            let tx = self.txs.get(txid);
+            let confirmations = tx.map(|(_, c)| *c).unwrap_or(0);
+            Ok(TransactionCheckResult {
+                txid: txid.to_string(),
+                chain,
+                found: tx.is_some(),
+                confirmations,
+                status: if confirmations == 0 { TxStatus::Pending } else { TxStatus::Confirming },
+                block_height: None,
+                block_hash: None,
+                fee_paid: tx.map(|(fee, _)| fee.to_string()),
+                gas_used: None,
+            })
+        }
+        
+        fn conflict_key(&self, _txid: &str, _chain: Chain) -> HawalaResult<Option<TxConflictKey>> {
+            Ok(None)
+        }
+        
+        fn find_replacement(&self, txid: &str, _chain: Chain, key: &TxConflictKey) -> HawalaResult<Option<Replacement>> {
+            let TxConflictKey::Inputs { outpoints, fee } = key else {
+                return Ok(None);
+            };
+            for outpoint in outpoints {
+                let Some(spender) = self.spends.get(outpoint).filter(|s| *s != txid) else {
+                    continue;
+                };
+                let higher_fee = match (fee, self.txs.get(spender)) {
+                    (Some(original), Some((replacement, _))) => replacement > original,
+                    _ => true,
+                };
+                if higher_fee {
+                    return Ok(Some(Replacement { txid: Some(spender.clone()) }));
+                }
+            }
+            Ok(None)
+        }
+    }
+    
+    #[test]
+    fn test_status_reports_rbf_replacement() {
+        let original = "aa".repeat(32);
+        let bump = "bb".repeat(32);
+        let outpoints = vec![("11".repeat(32), 0), ("22".repeat(32), 1)];
+        let inputs = TxConflictKey::Inputs { outpoints: outpoints.clone(), fee: None };
+        
+        // Still in the mempool, spending its own inputs
+        let mut source = MempoolSource {
+            txs: HashMap::from([(original.clone(), (500, 0))]),
+            spends: outpoints.iter().map(|o| (o.clone(), original.clone())).collect(),
+        };
+        let status = get_transaction_status_with(&source, &original, Chain::Bitcoin, Some(&inputs)).unwrap();
+        assert_eq!(status, TxStatus::Pending);
+        
+        // Fee bump: the original vanishes and a same-input, higher-fee
+        // transaction confirms
+        source.txs = HashMap::from([(bump.clone(), (2_000, 1))]);
+        source.spends = outpoints.iter().map(|o| (o.clone(), bump.clone())).collect();
+        let status = get_transaction_status_with(&source, &original, Chain::Bitcoin, Some(&inputs)).unwrap();
+        assert_eq!(status, TxStatus::Replaced { by: Some(bump.clone()) });
+        assert_eq!(get_tracked(&original).unwrap().status, TxStatus::Replaced { by: Some(bump) });
+        assert_eq!(
+            serde_json::to_value(&status).unwrap()["replaced"]["by"],
+            serde_json::json!("bb".repeat(32))
+        );
+        
+        // Without the inputs there is nothing to compare against
+        let status = get_transaction_status_with(&source, &original, Chain::Bitcoin, None).unwrap();
+        assert_eq!(status, TxStatus::Pending);
+        stop_tracking(&original);
+    }
+    
+    /// EVM node where `mined` transactions from the sender have been included
+    struct NonceSource {
+        mined: u64,
+    }
+    
+    impl TxStatusSource for NonceSource {
+        fn check(&self, txid: &str, chain: Chain) -> HawalaResult<TransactionCheckResult> {
+            Ok(TransactionCheckResult {
+                txid: txid.to_string(),
+                chain,
+                found: false,
+                confirmations: 0,
+                status: TxStatus::Pending,
+                block_height: None,
+                block_hash: None,
+                fee_paid: None,
+                gas_used: None,
+            })
+        }
+        
+        fn conflict_key(&self, _txid: &str, _chain: Chain) -> HawalaResult<Option<TxConflictKey>> {
+            Ok(None)
+        }
+        
+        fn find_replacement(&self, _txid: &str, _chain: Chain, key: &TxConflictKey) -> HawalaResult<Option<Replacement>> {
+            match key {
+                TxConflictKey::Nonce { nonce, .. } if self.mined > *nonce => Ok(Some(Replacement { txid: None })),
+                TxConflictKey::Nonce { .. } => Ok(None),
+                TxConflictKey::Inputs { .. } => panic!("outpoints looked up on an EVM chain"),
+            }
+        }
+    }
+    
+    #[test]
+    fn test_status_reports_evm_nonce_replacement() {
+        let original = format!("0x{}", "cc".repeat(32));
+        let nonce = TxConflictKey::Nonce { from: format!("0x{}", "11".repeat(20)), nonce: 7 };
+        
+        let status = get_transaction_status_with(&NonceSource { mined: 7 }, &original, Chain::Ethereum, Some(&nonce)).unwrap();
+        assert_eq!(status, TxStatus::Pending);
+        
+        // Nonce 7 was mined by some other transaction
+        let source = NonceSource { mined: 8 };
+        let status = get_transaction_status_with(&source, &original, Chain::Ethereum, Some(&nonce)).unwrap();
+        assert_eq!(status, TxStatus::Replaced { by: None });
+        
+        // Outpoints mean nothing on an EVM chain, and a nonce nothing on a UTXO chain
+        let inputs = TxConflictKey::Inputs { outpoints: vec![("11".repeat(32), 0)], fee: None };
+        let status = get_transaction_status_with(&source, &original, Chain::Ethereum, Some(&inputs)).unwrap();
+        assert_eq!(status, TxStatus::Pending);
+        let status = get_transaction_status_with(&source, &original, Chain::Bitcoin, Some(&nonce)).unwrap();
+        assert_eq!(status, TxStatus::Pending);
+        stop_tracking(&original);
+    }
 }
@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::fees::units::{SatPerVb, Wei};
+
 // =============================================================================
 // Chain Types
 // =============================================================================
@@ -217,6 +219,18 @@ impl Chain {
         }
     }
 
+    /// EVM chain with the given EIP-155 chain id
+    pub fn from_chain_id(chain_id: u64) -> Option<Chain> {
+        const EVM_CHAINS: [Chain; 25] = [
+            Chain::Ethereum, Chain::EthereumSepolia, Chain::Bnb, Chain::Polygon, Chain::Arbitrum,
+            Chain::Optimism, Chain::Base, Chain::Avalanche, Chain::Fantom, Chain::Cronos,
+            Chain::Gnosis, Chain::Celo, Chain::Moonbeam, Chain::Moonriver, Chain::Aurora,
+            Chain::Metis, Chain::Boba, Chain::ZkSync, Chain::PolygonZkEvm, Chain::Linea,
+            Chain::Scroll, Chain::Mantle, Chain::Blast, Chain::Harmony, Chain::Vechain,
+        ];
+        EVM_CHAINS.into_iter().find(|chain| chain.chain_id() == Some(chain_id))
+    }
+
     pub fn symbol(&self) -> &'static str {
         match self {
             Chain::Bitcoin | Chain::BitcoinTestnet => "BTC",
@@ -487,6 +501,32 @@ impl std::str::FromStr for Chain {
 // Wallet Types
 // =============================================================================
 
+/// Network that generated keys are encoded for
+///
+/// On `Testnet`, Bitcoin and Litecoin use coin type 1 with testnet addresses
+/// and WIF, and Monero uses testnet addresses. EVM, Solana and XRP keys are
+/// the same on both (Sepolia, devnet and the XRP testnet share mainnet
+/// encodings). Other chains are always mainnet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyNetwork {
+    #[default]
+    Mainnet,
+    Testnet,
+}
+
+impl std::str::FromStr for KeyNetwork {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mainnet" => Ok(Self::Mainnet),
+            "testnet" => Ok(Self::Testnet),
+            _ => Err(format!("Unknown network '{}': expected mainnet or testnet", s)),
+        }
+    }
+}
+
 /// Generated keys for all supported chains
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AllKeys {
@@ -548,6 +588,154 @@ pub struct AllKeys {
     pub nervos: NervosKeys,
 }
 
+/// Keys for a subset of chains
+///
+/// Same JSON shape as [`AllKeys`], with chains that were not requested left out.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PartialKeys {
+    // Bitcoin & forks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitcoin: Option<BitcoinKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitcoin_testnet: Option<BitcoinKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub litecoin: Option<LitecoinKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dogecoin: Option<DogecoinKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitcoin_cash: Option<BitcoinCashKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zcash: Option<ZcashKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dash: Option<DashKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ravencoin: Option<RavencoinKeys>,
+    
+    // EVM (shared key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ethereum: Option<EthereumKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ethereum_sepolia: Option<EthereumKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bnb: Option<EvmKeys>,
+    
+    // Solana
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solana: Option<SolanaKeys>,
+    
+    // XRP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub xrp: Option<XrpKeys>,
+    
+    // Privacy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monero: Option<MoneroKeys>,
+    
+    // Cosmos ecosystem (shared secp256k1 key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cosmos: Option<CosmosKeys>,
+    
+    // Substrate-based (shared ed25519 key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub polkadot: Option<PolkadotKeys>,
+    
+    // Move-based
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aptos: Option<AptosKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sui: Option<SuiKeys>,
+    
+    // Other L1s
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cardano: Option<CardanoKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tron: Option<TronKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorand: Option<AlgorandKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stellar: Option<StellarKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub near: Option<NearKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tezos: Option<TezosKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hedera: Option<HederaKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ton: Option<TonKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vechain: Option<VechainKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub harmony: Option<HarmonyKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oasis: Option<OasisKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filecoin: Option<FilecoinKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internet_computer: Option<InternetComputerKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub waves: Option<WavesKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiversx: Option<MultiversXKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flow: Option<FlowKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mina: Option<MinaKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zilliqa: Option<ZilliqaKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eos: Option<EosKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub neo: Option<NeoKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nervos: Option<NervosKeys>,
+}
+
+impl From<AllKeys> for PartialKeys {
+    fn from(keys: AllKeys) -> Self {
+        Self {
+            bitcoin: Some(keys.bitcoin),
+            bitcoin_testnet: Some(keys.bitcoin_testnet),
+            litecoin: Some(keys.litecoin),
+            dogecoin: Some(keys.dogecoin),
+            bitcoin_cash: Some(keys.bitcoin_cash),
+            zcash: Some(keys.zcash),
+            dash: Some(keys.dash),
+            ravencoin: Some(keys.ravencoin),
+            ethereum: Some(keys.ethereum),
+            ethereum_sepolia: Some(keys.ethereum_sepolia),
+            bnb: Some(keys.bnb),
+            solana: Some(keys.solana),
+            xrp: Some(keys.xrp),
+            monero: Some(keys.monero),
+            cosmos: Some(keys.cosmos),
+            polkadot: Some(keys.polkadot),
+            aptos: Some(keys.aptos),
+            sui: Some(keys.sui),
+            cardano: Some(keys.cardano),
+            tron: Some(keys.tron),
+            algorand: Some(keys.algorand),
+            stellar: Some(keys.stellar),
+            near: Some(keys.near),
+            tezos: Some(keys.tezos),
+            hedera: Some(keys.hedera),
+            ton: Some(keys.ton),
+            vechain: Some(keys.vechain),
+            harmony: Some(keys.harmony),
+            oasis: Some(keys.oasis),
+            filecoin: Some(keys.filecoin),
+            internet_computer: Some(keys.internet_computer),
+            waves: Some(keys.waves),
+            multiversx: Some(keys.multiversx),
+            flow: Some(keys.flow),
+            mina: Some(keys.mina),
+            zilliqa: Some(keys.zilliqa),
+            eos: Some(keys.eos),
+            neo: Some(keys.neo),
+            nervos: Some(keys.nervos),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BitcoinKeys {
     pub private_hex: String,
@@ -683,7 +871,12 @@ pub struct CosmosKeys {
 pub struct CardanoKeys {
     pub private_hex: String,
     pub public_hex: String,
+    /// Shelley base address (payment + stake credentials)
     pub address: String,
+    pub stake_public_hex: String,
+    /// Payment-only address, as generated before base addresses
+    pub enterprise_address: String,
+    pub stake_address: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -884,15 +1077,25 @@ pub struct TransactionRequest {
     
     // UTXO chains
     pub utxos: Option<Vec<Utxo>>,
-    pub fee_rate: Option<u64>,
+    pub fee_rate: Option<SatPerVb>,
     
     // EVM chains
     pub nonce: Option<u64>,
     pub gas_limit: Option<u64>,
-    pub gas_price: Option<String>,
-    pub max_fee_per_gas: Option<String>,
-    pub max_priority_fee_per_gas: Option<String>,
+    /// Estimate `gas_limit` with `eth_estimateGas` (plus a 20% margin) instead
+    /// of taking it from the request
+    #[serde(default)]
+    pub auto_gas: bool,
+    pub gas_price: Option<Wei>,
+    pub max_fee_per_gas: Option<Wei>,
+    pub max_priority_fee_per_gas: Option<Wei>,
     pub data: Option<String>,
+    /// Account balance in wei; required for `send_max` on EVM chains
+    #[serde(default)]
+    pub balance: Option<String>,
+    /// EIP-2930 access list; with `gas_price` this builds a type 0x01 transaction
+    #[serde(default, alias = "accessList")]
+    pub access_list: Option<Vec<AccessListItem>>,
     
     // Solana
     pub recent_blockhash: Option<String>,
@@ -900,6 +1103,91 @@ pub struct TransactionRequest {
     // XRP
     pub sequence: Option<u32>,
     pub destination_tag: Option<u32>,
+
+    // Tezos (`amount` is XTZ if it has a decimal point, mutez otherwise;
+    // `fee_mutez` is always mutez; `gas_limit` is shared)
+    /// Block hash (`B...`) the operation is anchored to
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Source account's counter plus one
+    #[serde(default)]
+    pub counter: Option<u64>,
+    #[serde(default)]
+    pub fee_mutez: Option<u64>,
+    #[serde(default)]
+    pub storage_limit: Option<u64>,
+
+    // Change handling (UTXO chains)
+    /// Explicit change address; takes precedence over `account_xpub`
+    #[serde(default)]
+    pub change_address: Option<String>,
+    /// Account-level xpub used to derive the change address
+    #[serde(default)]
+    pub account_xpub: Option<String>,
+    /// Next unused index on the change chain; required with `account_xpub`
+    #[serde(default)]
+    pub change_index: Option<u32>,
+
+    /// Return a `TransactionPreview` instead of signing
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Send the whole balance less fees; `amount` is ignored. Bitcoin spends
+    /// every UTXO with no change output, EVM chains send `balance` minus
+    /// gas limit × max fee per gas
+    #[serde(default)]
+    pub send_max: bool,
+
+    /// Fee limits checked before signing (Bitcoin and EVM chains)
+    #[serde(default)]
+    pub fee_guard: Option<FeeGuard>,
+    /// Sign even when the fee exceeds `fee_guard`
+    #[serde(default)]
+    pub allow_high_fee: bool,
+}
+
+/// Limits that block an accidentally excessive fee
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeGuard {
+    /// Largest fee as a percentage of the amount sent; not applied to
+    /// zero-value sends such as token transfers
+    #[serde(default = "FeeGuard::default_max_fee_percent")]
+    pub max_fee_percent: u32,
+    /// Absolute cap in base units (sats, wei); Bitcoin defaults to 0.1 BTC
+    #[serde(default)]
+    pub max_fee: Option<String>,
+}
+
+impl FeeGuard {
+    fn default_max_fee_percent() -> u32 {
+        50
+    }
+}
+
+impl Default for FeeGuard {
+    fn default() -> Self {
+        Self {
+            max_fee_percent: Self::default_max_fee_percent(),
+            max_fee: None,
+        }
+    }
+}
+
+/// EIP-2930 access list entry: a contract and the storage slots it touches
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+    pub address: String,
+    #[serde(default, alias = "storage_keys")]
+    pub storage_keys: Vec<String>,
+}
+
+/// Change output of a prepared transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeOutput {
+    pub address: String,
+    /// Full derivation path, e.g. "m/84'/0'/0'/1/3" (empty when supplied explicitly)
+    pub path: String,
 }
 
 /// Signed transaction result
@@ -912,6 +1200,29 @@ pub struct SignedTransaction {
     pub estimated_fee: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size_bytes: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change: Option<ChangeOutput>,
+}
+
+/// Fee preview of a transaction request, produced without signing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionPreview {
+    pub chain: Chain,
+    /// Network fee in base units (sats, litoshis, wei, lamports); the
+    /// maximum fee for EVM chains
+    pub fee: String,
+    /// Estimated vsize (UTXO chains) or gas limit (EVM chains)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_or_gas: Option<u64>,
+    /// Change returned to the wallet in base units ("0" when none)
+    pub change: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_output: Option<ChangeOutput>,
+    /// Amount plus fee in base units
+    pub total: String,
+    /// Outpoints ("txid:vout") spent by UTXO chains
+    #[serde(default)]
+    pub inputs_used: Vec<String>,
 }
 
 /// Broadcast result
@@ -955,7 +1266,7 @@ pub struct TrackedTransaction {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeeLevel {
     pub label: String,
-    pub rate: u64,           // sat/vB for BTC, gwei for EVM
+    pub rate: SatPerVb,
     pub estimated_minutes: u32,
 }
 
@@ -969,6 +1280,19 @@ pub struct BitcoinFeeEstimate {
     pub minimum: FeeLevel,
 }
 
+/// Bitcoin fee tiers in sat/vB, as recommended by mempool.space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BitcoinFeeTiers {
+    /// Next block
+    pub fastest: SatPerVb,
+    pub half_hour: SatPerVb,
+    pub hour: SatPerVb,
+    pub economy: SatPerVb,
+    /// Mempool purge floor; below this a transaction may not relay
+    pub minimum: SatPerVb,
+}
+
 /// Litecoin fee estimates
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LitecoinFeeEstimate {
@@ -981,11 +1305,11 @@ pub struct LitecoinFeeEstimate {
 /// Ethereum/EVM fee estimates
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvmFeeEstimate {
-    pub base_fee: String,
-    pub priority_fee_low: String,
-    pub priority_fee_medium: String,
-    pub priority_fee_high: String,
-    pub gas_price_legacy: String,
+    pub base_fee: Wei,
+    pub priority_fee_low: Wei,
+    pub priority_fee_medium: Wei,
+    pub priority_fee_high: Wei,
+    pub gas_price_legacy: Wei,
     pub chain_id: u64,
 }
 
@@ -1159,6 +1483,8 @@ mod tests {
         assert!(!Chain::Bitcoin.is_evm());
         assert!(Chain::Bitcoin.is_utxo());
         assert_eq!(Chain::Ethereum.chain_id(), Some(1));
+        assert_eq!(Chain::from_chain_id(137), Some(Chain::Polygon));
+        assert_eq!(Chain::from_chain_id(999_999), None);
         assert_eq!(Chain::Bitcoin.decimals(), 8);
         assert_eq!(Chain::Ethereum.decimals(), 18);
     }
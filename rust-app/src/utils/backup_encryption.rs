@@ -4,6 +4,23 @@
 //! - AES-256-GCM for authenticated encryption
 //! - Argon2id for key derivation from password
 //! - Random nonces to prevent nonce reuse
+//!
+//! # Format (version 1)
+//!
+//! Backups are self-describing: every parameter needed to re-derive the key
+//! travels with the ciphertext. The JSON form is [`EncryptedBackup`]; the
+//! compact binary form produced by [`EncryptedBackup::to_bytes`] is:
+//!
+//! ```text
+//! magic        4 bytes   "HWBK"
+//! version      1 byte    0x01
+//! memory_cost  4 bytes   big-endian, KiB
+//! time_cost    4 bytes   big-endian
+//! parallelism  4 bytes   big-endian
+//! salt        32 bytes
+//! nonce       12 bytes
+//! ciphertext   N bytes   AES-256-GCM output (data || 16-byte tag)
+//! ```
 
 #![allow(deprecated)] // GenericArray::from_slice deprecated in generic-array 1.x
 
@@ -12,13 +29,43 @@ use aes_gcm::{
     Aes256Gcm, Nonce,
 };
 use rand::RngCore;
+use zeroize::Zeroizing;
 use crate::error::{HawalaError, HawalaResult};
 
+/// Current backup format version
+pub const BACKUP_VERSION: u8 = 1;
+/// Magic prefix of the binary backup encoding
+pub const BACKUP_MAGIC: &[u8; 4] = b"HWBK";
+/// KDF identifier stored in the backup
+pub const KDF_ARGON2ID: &str = "argon2id";
+/// Cipher identifier stored in the backup
+pub const CIPHER_AES_256_GCM: &str = "aes-256-gcm";
+
+const SALT_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const HEADER_LEN: usize = 4 + 1 + 4 * 3 + SALT_LEN + NONCE_LEN;
+
+/// Lower bound on Argon2 memory cost (8 MiB)
+pub const MIN_MEMORY_COST: u32 = 8 * 1024;
+/// Upper bound on Argon2 memory cost (4 GiB), guards against hostile backups
+pub const MAX_MEMORY_COST: u32 = 4 * 1024 * 1024;
+/// Upper bound on Argon2 iterations
+pub const MAX_TIME_COST: u32 = 16;
+/// Upper bound on Argon2 lanes
+pub const MAX_PARALLELISM: u32 = 16;
+
 /// Encrypted backup structure
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct EncryptedBackup {
     /// Version for future compatibility
     pub version: u8,
+    /// Key derivation function identifier
+    #[serde(default = "default_kdf")]
+    pub kdf: String,
+    /// Cipher identifier
+    #[serde(default = "default_cipher")]
+    pub cipher: String,
     /// Salt used for key derivation (32 bytes, base64)
     pub salt: String,
     /// Nonce used for encryption (12 bytes, base64)
@@ -40,6 +87,51 @@ pub struct KdfParams {
     pub parallelism: u32,
 }
 
+fn default_kdf() -> String {
+    KDF_ARGON2ID.to_string()
+}
+
+fn default_cipher() -> String {
+    CIPHER_AES_256_GCM.to_string()
+}
+
+impl KdfParams {
+    /// Create validated KDF parameters
+    pub fn new(memory_cost: u32, time_cost: u32, parallelism: u32) -> HawalaResult<Self> {
+        let params = Self { memory_cost, time_cost, parallelism };
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Check parameters are within the accepted bounds
+    pub fn validate(&self) -> HawalaResult<()> {
+        if self.parallelism == 0 || self.parallelism > MAX_PARALLELISM {
+            return Err(HawalaError::invalid_input(format!(
+                "KDF parallelism must be between 1 and {}",
+                MAX_PARALLELISM
+            )));
+        }
+        if self.time_cost == 0 || self.time_cost > MAX_TIME_COST {
+            return Err(HawalaError::invalid_input(format!(
+                "KDF time cost must be between 1 and {}",
+                MAX_TIME_COST
+            )));
+        }
+        if self.memory_cost < MIN_MEMORY_COST || self.memory_cost > MAX_MEMORY_COST {
+            return Err(HawalaError::invalid_input(format!(
+                "KDF memory cost must be between {} and {} KiB",
+                MIN_MEMORY_COST, MAX_MEMORY_COST
+            )));
+        }
+        if self.memory_cost < 8 * self.parallelism {
+            return Err(HawalaError::invalid_input(
+                "KDF memory cost must be at least 8 KiB per lane"
+            ));
+        }
+        Ok(())
+    }
+}
+
 impl Default for KdfParams {
     fn default() -> Self {
         Self {
@@ -54,6 +146,17 @@ impl Default for KdfParams {
 
 /// Encrypt wallet backup data with password
 pub fn encrypt_backup(plaintext: &[u8], password: &str) -> HawalaResult<EncryptedBackup> {
+    encrypt_backup_with_params(plaintext, password, KdfParams::default())
+}
+
+/// Encrypt wallet backup data with password and explicit Argon2id parameters
+pub fn encrypt_backup_with_params(
+    plaintext: &[u8],
+    password: &str,
+    kdf_params: KdfParams,
+) -> HawalaResult<EncryptedBackup> {
+    kdf_params.validate()?;
+
     if password.len() < 8 {
         return Err(HawalaError::invalid_input(
             "Password must be at least 8 characters"
@@ -61,19 +164,18 @@ pub fn encrypt_backup(plaintext: &[u8], password: &str) -> HawalaResult<Encrypte
     }
     
     // Generate random salt for key derivation
-    let mut salt = [0u8; 32];
+    let mut salt = [0u8; SALT_LEN];
     OsRng.fill_bytes(&mut salt);
     
     // Generate random nonce for encryption
-    let mut nonce_bytes = [0u8; 12];
+    let mut nonce_bytes = [0u8; NONCE_LEN];
     OsRng.fill_bytes(&mut nonce_bytes);
     
     // Derive encryption key from password using Argon2id
-    let kdf_params = KdfParams::default();
     let key = derive_key(password, &salt, &kdf_params)?;
     
     // Encrypt with AES-256-GCM
-    let cipher = Aes256Gcm::new_from_slice(&key)
+    let cipher = Aes256Gcm::new_from_slice(&key[..])
         .map_err(|e| HawalaError::crypto_error(format!("Failed to create cipher: {}", e)))?;
     
     let nonce = Nonce::from_slice(&nonce_bytes);
@@ -82,7 +184,9 @@ pub fn encrypt_backup(plaintext: &[u8], password: &str) -> HawalaResult<Encrypte
         .map_err(|e| HawalaError::crypto_error(format!("Encryption failed: {}", e)))?;
     
     Ok(EncryptedBackup {
-        version: 1,
+        version: BACKUP_VERSION,
+        kdf: KDF_ARGON2ID.to_string(),
+        cipher: CIPHER_AES_256_GCM.to_string(),
         salt: base64_encode(&salt),
         nonce: base64_encode(&nonce_bytes),
         ciphertext: base64_encode(&ciphertext),
@@ -92,31 +196,42 @@ pub fn encrypt_backup(plaintext: &[u8], password: &str) -> HawalaResult<Encrypte
 
 /// Decrypt wallet backup data with password
 pub fn decrypt_backup(backup: &EncryptedBackup, password: &str) -> HawalaResult<Vec<u8>> {
-    if backup.version != 1 {
+    if backup.version != BACKUP_VERSION {
         return Err(HawalaError::invalid_input(format!(
             "Unsupported backup version: {}",
             backup.version
         )));
     }
+    if backup.kdf != KDF_ARGON2ID {
+        return Err(HawalaError::invalid_input(format!("Unsupported KDF: {}", backup.kdf)));
+    }
+    if backup.cipher != CIPHER_AES_256_GCM {
+        return Err(HawalaError::invalid_input(format!("Unsupported cipher: {}", backup.cipher)));
+    }
+    backup.kdf_params.validate()?;
     
     // Decode components
     let salt = base64_decode(&backup.salt)?;
     let nonce_bytes = base64_decode(&backup.nonce)?;
     let ciphertext = base64_decode(&backup.ciphertext)?;
     
-    if salt.len() != 32 {
+    if salt.len() != SALT_LEN {
         return Err(HawalaError::invalid_input("Invalid salt length"));
     }
     
-    if nonce_bytes.len() != 12 {
+    if nonce_bytes.len() != NONCE_LEN {
         return Err(HawalaError::invalid_input("Invalid nonce length"));
     }
     
+    if ciphertext.len() < TAG_LEN {
+        return Err(HawalaError::invalid_input("Ciphertext shorter than authentication tag"));
+    }
+    
     // Derive encryption key from password
     let key = derive_key(password, &salt, &backup.kdf_params)?;
     
     // Decrypt with AES-256-GCM
-    let cipher = Aes256Gcm::new_from_slice(&key)
+    let cipher = Aes256Gcm::new_from_slice(&key[..])
         .map_err(|e| HawalaError::crypto_error(format!("Failed to create cipher: {}", e)))?;
     
     let nonce = Nonce::from_slice(&nonce_bytes);
@@ -130,7 +245,7 @@ pub fn decrypt_backup(backup: &EncryptedBackup, password: &str) -> HawalaResult<
 }
 
 /// Derive encryption key from password using Argon2id
-fn derive_key(password: &str, salt: &[u8], params: &KdfParams) -> HawalaResult<[u8; 32]> {
+fn derive_key(password: &str, salt: &[u8], params: &KdfParams) -> HawalaResult<Zeroizing<[u8; 32]>> {
     use argon2::{Argon2, Algorithm, Version, Params};
     
     let argon2_params = Params::new(
@@ -142,13 +257,79 @@ fn derive_key(password: &str, salt: &[u8], params: &KdfParams) -> HawalaResult<[
     
     let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params);
     
-    let mut key = [0u8; 32];
-    argon2.hash_password_into(password.as_bytes(), salt, &mut key)
+    let mut key = Zeroizing::new([0u8; 32]);
+    argon2.hash_password_into(password.as_bytes(), salt, &mut key[..])
         .map_err(|e| HawalaError::crypto_error(format!("Key derivation failed: {}", e)))?;
     
     Ok(key)
 }
 
+impl EncryptedBackup {
+    /// Serialize to the compact binary format described in the module docs
+    pub fn to_bytes(&self) -> HawalaResult<Vec<u8>> {
+        let salt = base64_decode(&self.salt)?;
+        let nonce = base64_decode(&self.nonce)?;
+        let ciphertext = base64_decode(&self.ciphertext)?;
+
+        if salt.len() != SALT_LEN || nonce.len() != NONCE_LEN {
+            return Err(HawalaError::invalid_input("Invalid salt or nonce length"));
+        }
+
+        let mut out = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+        out.extend_from_slice(BACKUP_MAGIC);
+        out.push(self.version);
+        out.extend_from_slice(&self.kdf_params.memory_cost.to_be_bytes());
+        out.extend_from_slice(&self.kdf_params.time_cost.to_be_bytes());
+        out.extend_from_slice(&self.kdf_params.parallelism.to_be_bytes());
+        out.extend_from_slice(&salt);
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&ciphertext);
+        Ok(out)
+    }
+
+    /// Parse the compact binary format described in the module docs
+    pub fn from_bytes(bytes: &[u8]) -> HawalaResult<Self> {
+        if bytes.len() < HEADER_LEN + TAG_LEN {
+            return Err(HawalaError::invalid_input("Backup blob too short"));
+        }
+        if &bytes[0..4] != BACKUP_MAGIC {
+            return Err(HawalaError::invalid_input("Not a Hawala backup (bad magic)"));
+        }
+
+        let version = bytes[4];
+        if version != BACKUP_VERSION {
+            return Err(HawalaError::invalid_input(format!(
+                "Unsupported backup version: {}",
+                version
+            )));
+        }
+
+        let read_u32 = |offset: usize| {
+            u32::from_be_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+        };
+        let kdf_params = KdfParams {
+            memory_cost: read_u32(5),
+            time_cost: read_u32(9),
+            parallelism: read_u32(13),
+        };
+        kdf_params.validate()?;
+
+        let salt_start = 17;
+        let nonce_start = salt_start + SALT_LEN;
+        let ct_start = nonce_start + NONCE_LEN;
+
+        Ok(Self {
+            version,
+            kdf: KDF_ARGON2ID.to_string(),
+            cipher: CIPHER_AES_256_GCM.to_string(),
+            salt: base64_encode(&bytes[salt_start..nonce_start]),
+            nonce: base64_encode(&bytes[nonce_start..ct_start]),
+            ciphertext: base64_encode(&bytes[ct_start..]),
+            kdf_params,
+        })
+    }
+}
+
 /// Base64 encode bytes
 fn base64_encode(data: &[u8]) -> String {
     use base64::Engine;
@@ -191,6 +372,10 @@ pub fn estimate_memory_usage(backup: &EncryptedBackup) -> usize {
 mod tests {
     use super::*;
 
+    fn fast_params() -> KdfParams {
+        KdfParams::new(MIN_MEMORY_COST, 1, 1).unwrap()
+    }
+
     #[test]
     fn test_encrypt_decrypt_roundtrip() {
         let plaintext = b"abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
@@ -258,4 +443,96 @@ mod tests {
         assert!(verify_backup_password(&encrypted, password));
         assert!(!verify_backup_password(&encrypted, "wrong"));
     }
+
+    #[test]
+    fn test_custom_params_roundtrip() {
+        let plaintext = b"custom params";
+        let password = "test_password_123";
+
+        let encrypted = encrypt_backup_with_params(plaintext, password, fast_params()).unwrap();
+        assert_eq!(encrypted.kdf_params.memory_cost, MIN_MEMORY_COST);
+        assert_eq!(encrypted.kdf, KDF_ARGON2ID);
+        assert_eq!(encrypted.cipher, CIPHER_AES_256_GCM);
+
+        let decrypted = decrypt_backup(&encrypted, password).unwrap();
+        assert_eq!(plaintext.as_slice(), decrypted.as_slice());
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_fails_tag_check() {
+        use base64::Engine;
+        let engine = base64::engine::general_purpose::STANDARD;
+        let password = "test_password_123";
+
+        let mut encrypted = encrypt_backup_with_params(b"seed words", password, fast_params()).unwrap();
+        let mut ct = engine.decode(&encrypted.ciphertext).unwrap();
+        ct[0] ^= 0x01;
+        encrypted.ciphertext = engine.encode(&ct);
+
+        assert!(decrypt_backup(&encrypted, password).is_err());
+    }
+
+    #[test]
+    fn test_tampered_tag_fails() {
+        let password = "test_password_123";
+        let encrypted = encrypt_backup_with_params(b"seed words", password, fast_params()).unwrap();
+
+        let mut bytes = encrypted.to_bytes().unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x80;
+
+        let parsed = EncryptedBackup::from_bytes(&bytes).unwrap();
+        assert!(decrypt_backup(&parsed, password).is_err());
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let plaintext = b"binary format";
+        let password = "test_password_123";
+        let encrypted = encrypt_backup_with_params(plaintext, password, fast_params()).unwrap();
+
+        let bytes = encrypted.to_bytes().unwrap();
+        assert_eq!(&bytes[0..4], BACKUP_MAGIC);
+        assert_eq!(bytes[4], BACKUP_VERSION);
+        assert_eq!(bytes.len(), HEADER_LEN + plaintext.len() + TAG_LEN);
+
+        let parsed = EncryptedBackup::from_bytes(&bytes).unwrap();
+        assert_eq!(decrypt_backup(&parsed, password).unwrap(), plaintext.to_vec());
+    }
+
+    #[test]
+    fn test_bad_magic_rejected() {
+        let encrypted = encrypt_backup_with_params(b"data", "test_password_123", fast_params()).unwrap();
+        let mut bytes = encrypted.to_bytes().unwrap();
+        bytes[0] = b'X';
+        assert!(EncryptedBackup::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_kdf_params_bounds() {
+        assert!(KdfParams::new(1024, 3, 4).is_err());
+        assert!(KdfParams::new(MIN_MEMORY_COST, 0, 1).is_err());
+        assert!(KdfParams::new(MIN_MEMORY_COST, 1, 0).is_err());
+        assert!(KdfParams::new(MAX_MEMORY_COST + 1, 1, 1).is_err());
+        assert!(KdfParams::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_unknown_kdf_rejected() {
+        let mut encrypted = encrypt_backup_with_params(b"data", "test_password_123", fast_params()).unwrap();
+        encrypted.kdf = "scrypt".to_string();
+        assert!(decrypt_backup(&encrypted, "test_password_123").is_err());
+    }
+
+    #[test]
+    fn test_legacy_json_without_identifiers() {
+        let encrypted = encrypt_backup_with_params(b"legacy", "test_password_123", fast_params()).unwrap();
+        let mut value = serde_json::to_value(&encrypted).unwrap();
+        let obj = value.as_object_mut().unwrap();
+        obj.remove("kdf");
+        obj.remove("cipher");
+
+        let decrypted = decrypt_backup_from_json(&value.to_string(), "test_password_123").unwrap();
+        assert_eq!(decrypted, b"legacy".to_vec());
+    }
 }
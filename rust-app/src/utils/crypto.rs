@@ -3,10 +3,14 @@
 //! Helper functions for cryptographic operations that are used
 //! by both the legacy code and the new modules.
 
-use bitcoin::hashes::{Hash, sha256d};
+use bitcoin::hashes::{Hash, hash160, ripemd160, sha256, sha256d};
 use bitcoin::secp256k1::SecretKey;
+use subtle::ConstantTimeEq;
+use serde::{Deserialize, Serialize};
 use tiny_keccak::{Hasher, Keccak};
 
+use crate::error::{HawalaError, HawalaResult};
+
 /// Keccak256 hash (used for Ethereum addresses)
 pub fn keccak256(data: &[u8]) -> [u8; 32] {
     let mut hasher = Keccak::v256();
@@ -16,6 +20,37 @@ pub fn keccak256(data: &[u8]) -> [u8; 32] {
     out
 }
 
+/// Hash function selectable by name through [`hash`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    Sha256,
+    /// SHA-256 applied twice (Bitcoin txids, Base58Check checksums)
+    Sha256d,
+    Keccak256,
+    /// Blake2b with a 32-byte digest
+    Blake2b256,
+    Ripemd160,
+    /// RIPEMD-160 of SHA-256 (Bitcoin P2PKH/P2WPKH key hashes)
+    Hash160,
+}
+
+/// Hash `data` with `algorithm`
+pub fn hash(algorithm: HashAlgorithm, data: &[u8]) -> Vec<u8> {
+    match algorithm {
+        HashAlgorithm::Sha256 => sha256::Hash::hash(data).to_byte_array().to_vec(),
+        HashAlgorithm::Sha256d => sha256d::Hash::hash(data).to_byte_array().to_vec(),
+        HashAlgorithm::Keccak256 => keccak256(data).to_vec(),
+        HashAlgorithm::Blake2b256 => {
+            use blake2::digest::consts::U32;
+            use blake2::{Blake2b, Digest};
+            Blake2b::<U32>::digest(data).to_vec()
+        }
+        HashAlgorithm::Ripemd160 => ripemd160::Hash::hash(data).to_byte_array().to_vec(),
+        HashAlgorithm::Hash160 => hash160::Hash::hash(data).to_byte_array().to_vec(),
+    }
+}
+
 /// Convert raw address bytes to checksummed Ethereum address
 pub fn to_checksum_address(address: &[u8]) -> String {
     let lower = hex::encode(address);
@@ -52,10 +87,66 @@ pub fn encode_litecoin_wif(secret_key: &SecretKey) -> String {
     bs58::encode(payload).into_string()
 }
 
+/// Decode a Base58Check string into `(version, payload)`
+///
+/// Verifies the 4-byte double-SHA256 checksum and splits the first
+/// `expected_version_len` bytes off as the version prefix.
+pub fn base58check_decode(s: &str, expected_version_len: usize) -> HawalaResult<(Vec<u8>, Vec<u8>)> {
+    base58check_decode_with(s, expected_version_len, bs58::Alphabet::BITCOIN)
+}
+
+/// [`base58check_decode`] with a different alphabet (XRP uses
+/// [`bs58::Alphabet::RIPPLE`])
+pub fn base58check_decode_with(
+    s: &str,
+    expected_version_len: usize,
+    alphabet: &bs58::Alphabet,
+) -> HawalaResult<(Vec<u8>, Vec<u8>)> {
+    let data = bs58::decode(s.trim())
+        .with_alphabet(alphabet)
+        .into_vec()
+        .map_err(|e| HawalaError::invalid_input(format!("Invalid base58 encoding: {}", e)))?;
+    split_checksummed(&data, expected_version_len, |body| sha256d::Hash::hash(body)[..4].to_vec())
+}
+
+/// Decode a Monero base58 string into `(version, payload)`
+///
+/// Verifies the 4-byte Keccak-256 checksum. Monero versions are varints; the
+/// standard, integrated and subaddress prefixes on every network fit in one
+/// or two bytes.
+pub fn monero_base58check_decode(s: &str, expected_version_len: usize) -> HawalaResult<(Vec<u8>, Vec<u8>)> {
+    let data = monero_base58_decode(s.trim())?;
+    split_checksummed(&data, expected_version_len, |body| keccak256(body)[..4].to_vec())
+}
+
+/// Split `version || payload || checksum` after checking the checksum
+///
+/// The checksum is compared in constant time: WIF keys go through here, so
+/// the checksum is derived from secret data.
+fn split_checksummed(
+    data: &[u8],
+    version_len: usize,
+    checksum: impl Fn(&[u8]) -> Vec<u8>,
+) -> HawalaResult<(Vec<u8>, Vec<u8>)> {
+    if data.len() < version_len + 4 {
+        return Err(HawalaError::invalid_input("Base58Check data too short"));
+    }
+
+    let (body, expected) = data.split_at(data.len() - 4);
+    if !bool::from(checksum(body).as_slice().ct_eq(expected)) {
+        return Err(HawalaError::invalid_input("Invalid Base58Check checksum"));
+    }
+
+    let (version, payload) = body.split_at(version_len);
+    Ok((version.to_vec(), payload.to_vec()))
+}
+
 // Monero Base58 constants
 const MONERO_BASE58_ALPHABET: &[u8; 58] =
     b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
-const MONERO_BLOCK_ENCODED_LENGTH: [usize; 9] = [0, 2, 3, 5, 7, 8, 9, 10, 11];
+const MONERO_BLOCK_ENCODED_LENGTH: [usize; 9] = [0, 2, 3, 5, 6, 7, 9, 10, 11];
+const MONERO_FULL_BLOCK_SIZE: usize = 8;
+const MONERO_FULL_ENCODED_BLOCK_SIZE: usize = 11;
 
 /// Encode bytes using Monero's custom Base58 encoding
 pub fn monero_base58_encode(data: &[u8]) -> String {
@@ -78,9 +169,11 @@ pub fn monero_base58_encode(data: &[u8]) -> String {
 }
 
 fn encode_monero_block(block: &[u8]) -> String {
+    // Blocks are big-endian numbers written most significant digit first,
+    // left-padded with the zero digit
     let mut value: u64 = 0;
-    for (index, byte) in block.iter().enumerate() {
-        value |= (*byte as u64) << (8 * index);
+    for byte in block {
+        value = (value << 8) | *byte as u64;
     }
 
     let mut chars = Vec::new();
@@ -93,14 +186,41 @@ fn encode_monero_block(block: &[u8]) -> String {
     let target_len = if block.len() < MONERO_BLOCK_ENCODED_LENGTH.len() {
         MONERO_BLOCK_ENCODED_LENGTH[block.len()]
     } else {
-        11
+        MONERO_FULL_ENCODED_BLOCK_SIZE
     };
 
     while chars.len() < target_len {
         chars.push('1');
     }
 
-    chars.into_iter().collect()
+    chars.into_iter().rev().collect()
+}
+
+/// Decode Monero's block-wise Base58 (8-byte blocks as 11 characters)
+pub fn monero_base58_decode(encoded: &str) -> HawalaResult<Vec<u8>> {
+    let invalid = || HawalaError::invalid_input("Invalid Monero base58 encoding");
+    let chars = encoded.as_bytes();
+    let mut out = Vec::with_capacity(chars.len() / MONERO_FULL_ENCODED_BLOCK_SIZE * MONERO_FULL_BLOCK_SIZE + 8);
+
+    for block in chars.chunks(MONERO_FULL_ENCODED_BLOCK_SIZE) {
+        let size = MONERO_BLOCK_ENCODED_LENGTH
+            .iter()
+            .position(|len| *len == block.len())
+            .filter(|size| *size > 0)
+            .ok_or_else(invalid)?;
+
+        let mut value: u128 = 0;
+        for ch in block {
+            let digit = MONERO_BASE58_ALPHABET.iter().position(|c| c == ch).ok_or_else(invalid)?;
+            value = value * 58 + digit as u128;
+        }
+        if value >> (8 * size) != 0 {
+            return Err(invalid());
+        }
+        out.extend_from_slice(&value.to_be_bytes()[16 - size..]);
+    }
+
+    Ok(out)
 }
 
 #[cfg(test)]
@@ -113,6 +233,83 @@ mod tests {
         assert_eq!(hash.len(), 32);
     }
     
+    #[test]
+    fn test_hash_vectors() {
+        // (algorithm, hash of "", hash of "abc")
+        let vectors = [
+            (HashAlgorithm::Sha256,
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+            (HashAlgorithm::Sha256d,
+                "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456",
+                "4f8b42c22dd3729b519ba6f68d2da7cc5b2d606d05daed5ad5128cc03e6c6358"),
+            (HashAlgorithm::Keccak256,
+                "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+                "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"),
+            (HashAlgorithm::Blake2b256,
+                "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8",
+                "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319"),
+            (HashAlgorithm::Ripemd160,
+                "9c1185a5c5e9fc54612808977ee8f548b2258d31",
+                "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"),
+            (HashAlgorithm::Hash160,
+                "b472a266d0bd89c13706a4132ccfb16f7c3b9fcb",
+                "bb1be98c142444d7a56aa3981c3942a978e4dc33"),
+        ];
+        for (algorithm, empty, abc) in vectors {
+            assert_eq!(hex::encode(hash(algorithm, b"")), empty, "{:?}", algorithm);
+            assert_eq!(hex::encode(hash(algorithm, b"abc")), abc, "{:?}", algorithm);
+        }
+
+        let parsed: HashAlgorithm = serde_json::from_str("\"blake2b256\"").unwrap();
+        assert_eq!(parsed, HashAlgorithm::Blake2b256);
+    }
+
+    #[test]
+    fn test_base58check_decode() {
+        let (version, payload) = base58check_decode("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2", 1).unwrap();
+        assert_eq!(version, vec![0x00]);
+        assert_eq!(hex::encode(payload), "77bff20c60e522dfaa3350c39b030a5d004e839a");
+
+        // One character changed
+        let err = base58check_decode("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3", 1).unwrap_err();
+        assert!(err.message.contains("checksum"));
+
+        // Truncated
+        assert!(base58check_decode("1BvBMSEYstWetqTFn5Au4m4GFg7xJa", 1).is_err());
+        assert!(base58check_decode("1BvB", 1).is_err());
+        assert!(base58check_decode("0OIl", 1).is_err());
+    }
+
+    #[test]
+    fn test_base58check_decode_ripple() {
+        let address = "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh";
+        let (version, payload) = base58check_decode_with(address, 1, bs58::Alphabet::RIPPLE).unwrap();
+        assert_eq!(version, vec![0x00]);
+        assert_eq!(payload.len(), 20);
+
+        assert!(base58check_decode_with("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTj", 1, bs58::Alphabet::RIPPLE).is_err());
+        assert!(base58check_decode(address, 1).is_err()); // Wrong alphabet
+    }
+
+    #[test]
+    fn test_monero_base58check_decode() {
+        let address = "4AdUndXHHZ6cfufTMvppY6JwXNouMBzSkbLYfpAV5Usx3skxNgYeYTRj5UzqtReoS44qo9mtmXCqY45DJ852K5Jv2684Rge";
+        let (version, payload) = monero_base58check_decode(address, 1).unwrap();
+        assert_eq!(version, vec![18]);
+        assert_eq!(payload.len(), 64);
+
+        // Encoding the decoded bytes gives the address back
+        let mut data = version.clone();
+        data.extend_from_slice(&payload);
+        data.extend_from_slice(&keccak256(&data)[..4]);
+        assert_eq!(monero_base58_encode(&data), address);
+
+        let corrupted = address.replacen("4Ad", "4Ae", 1);
+        assert!(monero_base58check_decode(&corrupted, 1).is_err());
+        assert!(monero_base58check_decode(&address[..90], 1).is_err());
+    }
+
     #[test]
     fn test_checksum_address() {
         let addr_bytes = hex::decode("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").unwrap();
@@ -5,7 +5,11 @@
 //! - Mnemonics/seed phrases
 //! - Passwords
 //! - Full addresses (partial redaction)
+//!
+//! Field values are redacted by key name; on top of that every emitted line
+//! is scanned for secrets by content (see [`scrub_secrets`]).
 
+use crate::security::secure_memory::redact;
 use std::fmt;
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -88,6 +92,23 @@ impl LogEntry {
         self
     }
 
+    /// Render the entry as a log line (without timestamp), with any
+    /// secrets in the message or field values masked
+    pub fn render(&self) -> String {
+        let fields_str = self.fields
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, scrub_secrets(v)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let message = scrub_secrets(&self.message);
+
+        if fields_str.is_empty() {
+            format!("{} [{}] {}", self.level, self.module, message)
+        } else {
+            format!("{} [{}] {} | {}", self.level, self.module, message, fields_str)
+        }
+    }
+
     /// Log the entry
     pub fn log(self) {
         // Skip debug logs if not enabled
@@ -101,21 +122,112 @@ impl LogEntry {
 
         #[cfg(debug_assertions)]
         {
-            let fields_str = self.fields
-                .iter()
-                .map(|(k, v)| format!("{}={}", k, v))
-                .collect::<Vec<_>>()
-                .join(" ");
-
             let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ");
-            
-            if fields_str.is_empty() {
-                eprintln!("[{}] {} [{}] {}", timestamp, self.level, self.module, self.message);
-            } else {
-                eprintln!("[{}] {} [{}] {} | {}", timestamp, self.level, self.module, self.message, fields_str);
+            eprintln!("[{}] {}", timestamp, self.render());
+        }
+    }
+}
+
+/// Mask secrets anywhere in `text`: hex strings of 64+ digits (private keys,
+/// seeds), WIF keys, extended private keys and runs of 12 or more BIP39 words
+pub fn scrub_secrets(text: &str) -> String {
+    let text = scrub_mnemonics(text);
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text.as_str();
+
+    while let Some(start) = rest.find(|c: char| c.is_ascii_alphanumeric()) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(rest.len());
+        let token = &rest[..end];
+        if looks_like_secret(token) {
+            out.push_str(&redact(token));
+        } else {
+            out.push_str(token);
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn looks_like_secret(token: &str) -> bool {
+    let is_base58 = |s: &str| s.chars().all(|c| c.is_ascii_alphanumeric() && !"0OIl".contains(c));
+    let hex = token.strip_prefix("0x").unwrap_or(token);
+
+    // Private keys and seeds
+    if hex.len() >= 64 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return true;
+    }
+    // WIF: 51 chars uncompressed ('5' mainnet, '9' testnet), 52 compressed
+    let wif = match token.len() {
+        51 => token.starts_with(['5', '9']),
+        52 => token.starts_with(['K', 'L', 'c']),
+        _ => false,
+    };
+    if wif && is_base58(token) {
+        return true;
+    }
+    // BIP32 extended private keys
+    ["xprv", "tprv", "yprv", "zprv", "uprv", "vprv"].iter().any(|p| token.starts_with(p))
+        && token.len() >= 100
+        && is_base58(token)
+}
+
+/// Replace runs of 12 or more whitespace-separated BIP39 words
+fn scrub_mnemonics(text: &str) -> String {
+    const MIN_WORDS: usize = 12;
+    let wordlist = bip39::Language::English.word_list();
+    let is_bip39 = |word: &str| wordlist.binary_search(&word.to_ascii_lowercase().as_str()).is_ok();
+
+    // Byte ranges of alphabetic words
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        if c.is_ascii_alphabetic() {
+            start.get_or_insert(i);
+        } else if let Some(s) = start.take() {
+            words.push((s, i));
+        }
+    }
+    if let Some(s) = start {
+        words.push((s, text.len()));
+    }
+
+    // Byte ranges of mnemonic-looking runs
+    let mut runs: Vec<(usize, usize, usize)> = Vec::new();
+    let mut run: Option<(usize, usize, usize)> = None;
+    for &(s, e) in &words {
+        if !is_bip39(&text[s..e]) {
+            if let Some(r) = run.take().filter(|r| r.2 >= MIN_WORDS) {
+                runs.push(r);
             }
+            continue;
         }
+        run = match run {
+            Some((rs, re, n)) if text[re..s].chars().all(char::is_whitespace) => Some((rs, e, n + 1)),
+            Some(r) => {
+                if r.2 >= MIN_WORDS {
+                    runs.push(r);
+                }
+                Some((s, e, 1))
+            }
+            None => Some((s, e, 1)),
+        };
     }
+    if let Some(r) = run.filter(|r| r.2 >= MIN_WORDS) {
+        runs.push(r);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (s, e, n) in runs {
+        out.push_str(&text[last..s]);
+        out.push_str(&format!("[REDACTED:{}words]", n));
+        last = e;
+    }
+    out.push_str(&text[last..]);
+    out
 }
 
 /// Redact a value if the key suggests it's sensitive
@@ -300,6 +412,20 @@ macro_rules! log_error {
     };
 }
 
+/// Log a formatted message; secrets in the formatted text are masked
+///
+/// `log_redacted!(Warn, "ffi", "rejected key {}", input)`
+#[macro_export]
+macro_rules! log_redacted {
+    ($level:ident, $module:expr, $($arg:tt)+) => {
+        $crate::utils::logging::LogEntry::new(
+            $crate::utils::logging::LogLevel::$level,
+            $module,
+            format!($($arg)+)
+        ).log()
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -365,4 +491,54 @@ mod tests {
         assert!(addr_field.is_some());
         assert!(addr_field.unwrap().1.contains("..."));
     }
+
+    #[test]
+    fn test_scrub_private_keys() {
+        let key = "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35";
+        let line = LogEntry::new(LogLevel::Info, "test", format!("signing with {}", key)).render();
+        assert!(!line.contains(key));
+        assert!(line.contains(&redact(key)));
+
+        let prefixed = format!("key=0x{}", key);
+        assert_eq!(scrub_secrets(&prefixed), format!("key={}", redact(&format!("0x{}", key))));
+
+        // Secrets in unrelated fields are caught by content
+        let entry = LogEntry::new(LogLevel::Info, "test", "request").field("payload", format!("{{\"k\":\"{}\"}}", key));
+        assert!(!entry.render().contains(key));
+
+        // log_redacted! formats and logs without panicking
+        crate::log_redacted!(Info, "test", "signing with {}", key);
+    }
+
+    #[test]
+    fn test_scrub_wif_and_xprv() {
+        for secret in [
+            "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ",
+            "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn",
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+        ] {
+            let scrubbed = scrub_secrets(&format!("import {} now", secret));
+            assert!(!scrubbed.contains(secret), "{}", scrubbed);
+            assert!(scrubbed.starts_with("import ") && scrubbed.ends_with(" now"));
+        }
+    }
+
+    #[test]
+    fn test_scrub_mnemonic() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert_eq!(
+            scrub_secrets(&format!("restoring: {}.", phrase)),
+            "restoring: [REDACTED:12words]."
+        );
+
+        // Fewer than 12 words is left alone
+        let short = "abandon ability able about above absent";
+        assert_eq!(scrub_secrets(short), short);
+    }
+
+    #[test]
+    fn test_scrub_leaves_public_data() {
+        let line = "sent 0.5 ETH to 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045 from bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
+        assert_eq!(scrub_secrets(line), line);
+    }
 }
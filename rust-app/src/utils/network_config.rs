@@ -6,10 +6,13 @@
 //! - Endpoint health checking
 //! - Known provider validation
 //! - Custom endpoint whitelisting
+//! - Per-chain RPC, bundler, explorer and paymaster URL overrides
+//! - Failover ordering of providers by recent success and latency
 
-use crate::error::{HawalaError, HawalaResult};
+use crate::error::{ErrorCode, HawalaError, HawalaResult};
 use crate::types::Chain;
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::RwLock;
 use std::time::{Duration, Instant};
 use url::Url;
@@ -22,6 +25,37 @@ pub struct NetworkConfig {
     whitelisted_domains: RwLock<HashSet<String>>,
     /// Endpoint health status cache
     health_cache: RwLock<HashMap<String, EndpointHealth>>,
+    /// URLs used in place of the built-in defaults
+    url_overrides: RwLock<HashMap<(Chain, ServiceKind), String>>,
+    /// Most recent call outcomes per endpoint, newest last
+    recent_outcomes: RwLock<HashMap<String, VecDeque<CallOutcome>>>,
+}
+
+/// Call outcomes kept per endpoint when ranking providers
+pub const PROVIDER_OUTCOME_WINDOW: usize = 20;
+
+/// Score of an endpoint with no recorded calls: below a reliable provider,
+/// above one that has been failing
+const UNTRIED_PROVIDER_SCORE: i64 = 900;
+
+#[derive(Debug, Clone, Copy)]
+struct CallOutcome {
+    success: bool,
+    latency_ms: Option<u64>,
+}
+
+/// Kind of service a chain URL points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceKind {
+    /// JSON-RPC node
+    Rpc,
+    /// ERC-4337 bundler
+    Bundler,
+    /// Block explorer REST API (Esplora-style for Bitcoin)
+    Explorer,
+    /// ERC-4337 paymaster
+    Paymaster,
 }
 
 /// RPC endpoint configuration
@@ -62,6 +96,8 @@ impl NetworkConfig {
             custom_endpoints: RwLock::new(HashMap::new()),
             whitelisted_domains: RwLock::new(Self::default_whitelist()),
             health_cache: RwLock::new(HashMap::new()),
+            url_overrides: RwLock::new(HashMap::new()),
+            recent_outcomes: RwLock::new(HashMap::new()),
         }
     }
 
@@ -422,6 +458,43 @@ impl NetworkConfig {
         }
     }
 
+    /// Use `url` for a chain's service in place of the built-in default;
+    /// `None` restores the default
+    pub fn set_url_override(&self, chain: Chain, kind: ServiceKind, url: Option<&str>) -> HawalaResult<()> {
+        let url = match url {
+            Some(url) => {
+                let validation = self.validate_endpoint(url, chain);
+                if !validation.is_valid {
+                    return Err(HawalaError::invalid_input(validation.errors.join("; ")));
+                }
+                Some(url.trim_end_matches('/').to_string())
+            }
+            None => None,
+        };
+
+        let mut overrides = self.url_overrides.write()
+            .map_err(|_| HawalaError::internal("URL overrides lock poisoned"))?;
+        match url {
+            Some(url) => overrides.insert((chain, kind), url),
+            None => overrides.remove(&(chain, kind)),
+        };
+        Ok(())
+    }
+
+    /// The override for a chain's service, if one is set
+    pub fn url_override(&self, chain: Chain, kind: ServiceKind) -> Option<String> {
+        let overrides = self.url_overrides.read().ok()?;
+        overrides.get(&(chain, kind)).cloned()
+    }
+
+    /// The override for the EVM chain with `chain_id`, if one is set
+    pub fn evm_url_override(&self, chain_id: u64, kind: ServiceKind) -> Option<String> {
+        let overrides = self.url_overrides.read().ok()?;
+        overrides.iter()
+            .find(|((chain, k), _)| *k == kind && chain.chain_id() == Some(chain_id))
+            .map(|(_, url)| url.clone())
+    }
+
     /// Update health status for an endpoint
     pub fn update_health(&self, url: &str, is_healthy: bool, latency_ms: Option<u64>) {
         let Ok(mut cache) = self.health_cache.write() else { return; };
@@ -443,6 +516,51 @@ impl NetworkConfig {
         } else {
             entry.error_count += 1;
         }
+        drop(cache);
+
+        let Ok(mut outcomes) = self.recent_outcomes.write() else { return; };
+        let window = outcomes.entry(url.to_string()).or_default();
+        if window.len() == PROVIDER_OUTCOME_WINDOW {
+            window.pop_front();
+        }
+        window.push_back(CallOutcome { success: is_healthy, latency_ms });
+    }
+
+    /// Ranking score of an endpoint from its recent outcomes, higher is better
+    ///
+    /// Success rate in thousandths, less up to 100 for average latency
+    /// (1 point per 100 ms). Endpoints with no history score
+    /// `UNTRIED_PROVIDER_SCORE`.
+    pub fn provider_score(&self, url: &str) -> i64 {
+        let Ok(outcomes) = self.recent_outcomes.read() else {
+            return UNTRIED_PROVIDER_SCORE;
+        };
+        let Some(window) = outcomes.get(url).filter(|w| !w.is_empty()) else {
+            return UNTRIED_PROVIDER_SCORE;
+        };
+
+        let successes = window.iter().filter(|o| o.success).count() as i64;
+        let success_rate = successes * 1000 / window.len() as i64;
+
+        let latencies: Vec<u64> = window.iter()
+            .filter(|o| o.success)
+            .filter_map(|o| o.latency_ms)
+            .collect();
+        let latency_penalty = if latencies.is_empty() {
+            0
+        } else {
+            let average = latencies.iter().sum::<u64>() / latencies.len() as u64;
+            (average.min(10_000) / 100) as i64
+        };
+
+        success_rate - latency_penalty
+    }
+
+    /// `urls` ordered best-first by [`provider_score`](Self::provider_score);
+    /// ties keep their original order
+    pub fn rank_urls(&self, mut urls: Vec<String>) -> Vec<String> {
+        urls.sort_by_cached_key(|url| std::cmp::Reverse(self.provider_score(url)));
+        urls
     }
 
     /// Get health status for an endpoint
@@ -485,6 +603,90 @@ pub fn is_trusted_provider(domain: &str) -> bool {
     get_network_config().is_domain_whitelisted(domain)
 }
 
+/// A chain's service URL: the configured override, else `default`
+pub fn service_url(chain: Chain, kind: ServiceKind, default: &str) -> String {
+    get_network_config().url_override(chain, kind).unwrap_or_else(|| default.to_string())
+}
+
+/// Service URLs to try in order: the configured override, then `defaults`
+/// ranked by recent success and latency
+pub fn service_urls(chain: Chain, kind: ServiceKind, defaults: &[&str]) -> Vec<String> {
+    with_override(get_network_config().url_override(chain, kind), defaults)
+}
+
+/// [`service_url`] for an EVM chain identified by chain id
+pub fn evm_service_url(chain_id: u64, kind: ServiceKind, default: &str) -> String {
+    get_network_config().evm_url_override(chain_id, kind).unwrap_or_else(|| default.to_string())
+}
+
+/// [`service_urls`] for an EVM chain identified by chain id
+pub fn evm_service_urls(chain_id: u64, kind: ServiceKind, defaults: &[&str]) -> Vec<String> {
+    with_override(get_network_config().evm_url_override(chain_id, kind), defaults)
+}
+
+// A configured override is the user's choice and always goes first
+fn with_override(url: Option<String>, defaults: &[&str]) -> Vec<String> {
+    let defaults = get_network_config().rank_urls(defaults.iter().map(|d| d.to_string()).collect());
+    url.into_iter().chain(defaults).collect()
+}
+
+/// Call `f` on each of `urls` in turn until one succeeds, recording every
+/// outcome so later calls try the most reliable provider first
+///
+/// Only transport and RPC failures count against a provider and move on to
+/// the next one. Any other error, such as a reverted call, is an answer every
+/// provider would give, so it is returned as is.
+pub fn with_failover<T>(urls: &[String], mut f: impl FnMut(&str) -> HawalaResult<T>) -> HawalaResult<T> {
+    let config = get_network_config();
+    let mut last_error = None;
+
+    for url in urls {
+        let started = Instant::now();
+        match f(url) {
+            Ok(value) => {
+                config.update_health(url, true, Some(started.elapsed().as_millis() as u64));
+                return Ok(value);
+            }
+            Err(e) if is_provider_failure(&e) => {
+                config.update_health(url, false, None);
+                last_error = Some(e);
+            }
+            Err(e) => {
+                config.update_health(url, true, Some(started.elapsed().as_millis() as u64));
+                return Err(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| HawalaError::network_error("No providers configured")))
+}
+
+/// Whether an error means the provider could not be reached or answered
+/// with something other than a result
+fn is_provider_failure(error: &HawalaError) -> bool {
+    matches!(
+        error.code,
+        ErrorCode::NetworkError
+            | ErrorCode::RateLimited
+            | ErrorCode::ProviderUnavailable
+            | ErrorCode::Timeout
+            | ErrorCode::ParseError
+    )
+}
+
+/// Error for a JSON-RPC `error` object returned by an EVM node
+///
+/// Execution reverts are the call's own outcome and map to
+/// `TransactionRejected`; anything else is treated as a provider failure.
+pub fn evm_rpc_error(method: &str, error: &serde_json::Value) -> HawalaError {
+    let message = error["message"].as_str().unwrap_or_default();
+    if error["code"].as_i64() == Some(3) || message.to_lowercase().contains("revert") {
+        HawalaError::new(ErrorCode::TransactionRejected, format!("{} reverted: {}", method, message))
+    } else {
+        HawalaError::network_error(format!("{} failed: {}", method, error))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -546,4 +748,106 @@ mod tests {
         );
         assert!(result.warnings.iter().any(|w| w.contains("Credentials")));
     }
+
+    #[test]
+    fn test_url_override() {
+        let config = NetworkConfig::new();
+        assert_eq!(config.url_override(Chain::Ethereum, ServiceKind::Rpc), None);
+
+        config.set_url_override(Chain::Ethereum, ServiceKind::Rpc, Some("https://node.example.org/")).unwrap();
+        assert_eq!(
+            config.url_override(Chain::Ethereum, ServiceKind::Rpc),
+            Some("https://node.example.org".to_string())
+        );
+        assert_eq!(config.evm_url_override(1, ServiceKind::Rpc), Some("https://node.example.org".to_string()));
+        assert_eq!(config.evm_url_override(137, ServiceKind::Rpc), None);
+        assert_eq!(config.url_override(Chain::Ethereum, ServiceKind::Bundler), None);
+
+        // Remote plain HTTP is still rejected
+        assert!(config.set_url_override(Chain::Ethereum, ServiceKind::Rpc, Some("http://node.example.org")).is_err());
+
+        config.set_url_override(Chain::Ethereum, ServiceKind::Rpc, None).unwrap();
+        assert_eq!(config.url_override(Chain::Ethereum, ServiceKind::Rpc), None);
+    }
+
+    #[test]
+    fn test_with_override() {
+        assert_eq!(with_override(None, &["https://a", "https://b"]), vec!["https://a", "https://b"]);
+        assert_eq!(
+            with_override(Some("https://mine".to_string()), &["https://a"]),
+            vec!["https://mine", "https://a"]
+        );
+    }
+
+    #[test]
+    fn test_failing_primary_is_demoted() {
+        let config = NetworkConfig::new();
+        let urls = vec!["https://primary.example".to_string(), "https://secondary.example".to_string()];
+        assert_eq!(config.rank_urls(urls.clone()), urls);
+
+        for _ in 0..5 {
+            config.update_health("https://primary.example", false, None);
+            config.update_health("https://secondary.example", true, Some(150));
+        }
+        assert_eq!(
+            config.rank_urls(urls.clone()),
+            vec!["https://secondary.example".to_string(), "https://primary.example".to_string()]
+        );
+
+        // Old failures slide out of the window once the primary recovers
+        for _ in 0..PROVIDER_OUTCOME_WINDOW {
+            config.update_health("https://primary.example", true, Some(100));
+        }
+        assert_eq!(config.rank_urls(urls.clone()), urls);
+    }
+
+    #[test]
+    fn test_provider_score_prefers_lower_latency() {
+        let config = NetworkConfig::new();
+        config.update_health("https://slow.example", true, Some(2_000));
+        config.update_health("https://fast.example", true, Some(100));
+        assert!(config.provider_score("https://fast.example") > config.provider_score("https://slow.example"));
+        assert_eq!(config.provider_score("https://untried.example"), UNTRIED_PROVIDER_SCORE);
+    }
+
+    #[test]
+    fn test_with_failover_tries_next_provider() {
+        let urls = vec![
+            "https://failover-a.example".to_string(),
+            "https://failover-b.example".to_string(),
+        ];
+        let result = with_failover(&urls, |url| {
+            if url.contains("-a.") {
+                Err(HawalaError::network_error("down"))
+            } else {
+                Ok(url.to_string())
+            }
+        });
+        assert_eq!(result.unwrap(), "https://failover-b.example");
+        assert!(get_network_config().provider_score("https://failover-a.example") < UNTRIED_PROVIDER_SCORE);
+
+        let err = with_failover(&urls[..1], |_| -> HawalaResult<()> { Err(HawalaError::network_error("down")) });
+        assert_eq!(err.unwrap_err().message, "down");
+    }
+
+    #[test]
+    fn test_with_failover_keeps_provider_on_revert() {
+        let urls = vec![
+            "https://revert-a.example".to_string(),
+            "https://revert-b.example".to_string(),
+        ];
+        let revert = serde_json::json!({ "code": 3, "message": "execution reverted", "data": "0x" });
+        let mut calls = Vec::new();
+        let err = with_failover(&urls, |url| -> HawalaResult<()> {
+            calls.push(url.to_string());
+            Err(evm_rpc_error("eth_call", &revert))
+        }).unwrap_err();
+
+        assert_eq!(err.code, ErrorCode::TransactionRejected);
+        assert_eq!(calls, urls[..1]);
+        assert!(get_network_config().provider_score("https://revert-a.example") > UNTRIED_PROVIDER_SCORE);
+
+        let limited = serde_json::json!({ "code": -32005, "message": "limit exceeded" });
+        assert_eq!(evm_rpc_error("eth_call", &limited).code, ErrorCode::NetworkError);
+    }
 }
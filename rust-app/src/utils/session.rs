@@ -6,12 +6,14 @@
 //! - Secure session token generation
 //! - Session state tracking
 //! - Automatic cleanup
+//! - Time-limited, zeroize-on-drop key sessions
 
 use crate::error::{HawalaError, HawalaResult};
 use rand::{RngCore, rngs::OsRng};
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::sync::{Arc, Condvar, Mutex, PoisonError, RwLock};
 use std::time::{Duration, Instant};
+use zeroize::{Zeroize, Zeroizing};
 
 /// Session manager for wallet security
 pub struct SessionManager {
@@ -446,6 +448,112 @@ pub fn require_valid_session(session_id: &str) -> HawalaResult<()> {
     get_session_manager().record_activity(session_id)
 }
 
+/// Decrypted seed held for a limited time, so several transactions can be
+/// signed without prompting for the password again.
+///
+/// The seed is zeroized when the handle is dropped, or by a timer thread once
+/// `ttl` has elapsed, whichever comes first.
+pub struct KeySession {
+    material: Arc<KeyMaterial>,
+    /// None when `ttl` is too large to represent
+    expires_at: Option<Instant>,
+}
+
+struct KeyMaterial {
+    seed: Mutex<SessionSeed>,
+    wiped: Condvar,
+}
+
+struct SessionSeed {
+    bytes: Zeroizing<Vec<u8>>,
+    wiped: bool,
+}
+
+impl SessionSeed {
+    fn wipe(&mut self) {
+        self.bytes.as_mut_slice().zeroize();
+        self.wiped = true;
+    }
+}
+
+impl KeySession {
+    /// Take ownership of `seed` for at most `ttl`
+    pub fn new(seed: Vec<u8>, ttl: Duration) -> Self {
+        let material = Arc::new(KeyMaterial {
+            seed: Mutex::new(SessionSeed { bytes: Zeroizing::new(seed), wiped: false }),
+            wiped: Condvar::new(),
+        });
+
+        // Wipe at expiry even if the handle is never touched again; exits
+        // early when the handle is dropped. If the thread cannot be spawned,
+        // expiry is still enforced on access.
+        let timer = Arc::clone(&material);
+        let _ = std::thread::Builder::new()
+            .name("key-session-expiry".to_string())
+            .spawn(move || {
+                let seed = timer.seed.lock().unwrap_or_else(PoisonError::into_inner);
+                let (mut seed, _) = timer.wiped
+                    .wait_timeout_while(seed, ttl, |seed| !seed.wiped)
+                    .unwrap_or_else(PoisonError::into_inner);
+                seed.wipe();
+            });
+
+        Self {
+            material,
+            expires_at: Instant::now().checked_add(ttl),
+        }
+    }
+
+    /// Run `f` with the seed; fails once the session has expired or been wiped.
+    /// The seed cannot be wiped while `f` runs.
+    pub fn with_seed<R>(&self, f: impl FnOnce(&[u8]) -> R) -> HawalaResult<R> {
+        let mut seed = self.material.seed.lock()
+            .map_err(|_| HawalaError::internal("Key session lock poisoned"))?;
+
+        if !seed.wiped && self.expires_at.is_some_and(|at| Instant::now() >= at) {
+            seed.wipe();
+        }
+        if seed.wiped {
+            return Err(HawalaError::session_expired("Key session expired"));
+        }
+        Ok(f(&seed.bytes))
+    }
+
+    /// Whether the seed is still available
+    pub fn is_active(&self) -> bool {
+        self.with_seed(|_| ()).is_ok()
+    }
+
+    /// Time left before expiry
+    pub fn time_remaining(&self) -> Duration {
+        match self.expires_at {
+            Some(at) => at.saturating_duration_since(Instant::now()),
+            None => Duration::MAX,
+        }
+    }
+
+    /// Zeroize the seed now, ending the session
+    pub fn wipe(&self) {
+        self.material.seed.lock().unwrap_or_else(PoisonError::into_inner).wipe();
+        self.material.wiped.notify_all();
+    }
+}
+
+impl Drop for KeySession {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+impl std::fmt::Debug for KeySession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeySession")
+            .field("seed", &"[REDACTED]")
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -548,4 +656,58 @@ mod tests {
         let id2 = generate_session_id();
         assert_ne!(id1, id2);
     }
+
+    #[test]
+    fn test_key_session_access_before_expiry() {
+        let session = KeySession::new(vec![7u8; 64], Duration::from_secs(60));
+        assert_eq!(session.with_seed(|seed| seed.to_vec()).unwrap(), vec![7u8; 64]);
+        assert!(session.is_active());
+        assert!(session.time_remaining() > Duration::from_secs(50));
+    }
+
+    #[test]
+    fn test_key_session_refuses_after_expiry() {
+        let session = KeySession::new(vec![7u8; 64], Duration::from_millis(20));
+        std::thread::sleep(Duration::from_millis(200));
+
+        // The timer wiped the seed without any access
+        {
+            let seed = session.material.seed.lock().unwrap();
+            assert!(seed.wiped);
+            assert!(seed.bytes.iter().all(|b| *b == 0));
+        }
+
+        let err = session.with_seed(|seed| seed.len()).unwrap_err();
+        assert_eq!(err.code, crate::error::ErrorCode::SessionExpired);
+        assert!(!session.is_active());
+        assert_eq!(session.time_remaining(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_key_session_expiry_checked_on_access() {
+        // Expired before the timer thread gets to run
+        let session = KeySession::new(vec![7u8; 64], Duration::ZERO);
+        assert!(session.with_seed(|_| ()).is_err());
+    }
+
+    #[test]
+    fn test_key_session_drop_zeroizes() {
+        let session = KeySession::new(vec![0xA5; 64], Duration::from_secs(60));
+        let sentinel = Arc::clone(&session.material);
+        assert!(sentinel.seed.lock().unwrap().bytes.iter().all(|b| *b == 0xA5));
+
+        drop(session);
+
+        let seed = sentinel.seed.lock().unwrap();
+        assert!(seed.wiped);
+        assert_eq!(seed.bytes.len(), 64);
+        assert!(seed.bytes.iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn test_key_session_explicit_wipe() {
+        let session = KeySession::new(vec![1, 2, 3], Duration::from_secs(60));
+        session.wipe();
+        assert!(session.with_seed(|_| ()).is_err());
+    }
 }
@@ -7,34 +7,58 @@
 //! - Detailed error reporting
 
 use crate::error::{HawalaError, HawalaResult};
+use crate::monero_wallet::MoneroAddressKind;
 use crate::types::Chain;
-use bech32::{self, Variant};
-use sha2::{Sha256, Digest};
+use crate::utils::crypto::{base58check_decode, base58check_decode_with, monero_base58check_decode};
+use bech32::{self, FromBase32, Variant};
 use tiny_keccak::{Hasher, Keccak};
 
-/// SHA256 helper function for Base58Check verification
-fn sha256(data: &[u8]) -> [u8; 32] {
-    let mut hasher = Sha256::new();
-    hasher.update(data);
-    hasher.finalize().into()
+/// Decoded segwit address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WitnessAddress {
+    pub hrp: String,
+    pub version: u8,
+    pub program: Vec<u8>,
 }
 
-/// Convert 5-bit values to 8-bit bytes (for bech32 decoding)
-fn convert_bits_5_to_8(data: &[u8]) -> Vec<u8> {
-    let mut result = Vec::new();
-    let mut acc: u32 = 0;
-    let mut bits: u32 = 0;
-    
-    for value in data {
-        acc = (acc << 5) | (*value as u32);
-        bits += 5;
-        while bits >= 8 {
-            bits -= 8;
-            result.push((acc >> bits) as u8);
-        }
+/// Decode a segwit address, enforcing BIP-173/BIP-350 rules
+///
+/// Witness v0 must use the bech32 checksum and v1+ must use bech32m;
+/// a string with the other checksum variant is rejected outright.
+pub fn decode_witness_address(address: &str, hrps: &[&str]) -> Result<WitnessAddress, String> {
+    let (hrp, data, variant) = bech32::decode(address)
+        .map_err(|_| "Invalid bech32 encoding".to_string())?;
+
+    if !hrps.contains(&hrp.as_str()) {
+        return Err("Network mismatch".to_string());
     }
-    
-    result
+    let (version, program_5bit) = match data.split_first() {
+        Some((version, rest)) => (version.to_u8(), rest),
+        None => return Err("Empty witness program".to_string()),
+    };
+    if version > 16 {
+        return Err(format!("Invalid witness version {}", version));
+    }
+
+    let expected_variant = if version == 0 { Variant::Bech32 } else { Variant::Bech32m };
+    if variant != expected_variant {
+        return Err(format!(
+            "Witness v{} requires {} checksum",
+            version,
+            if version == 0 { "bech32" } else { "bech32m" }
+        ));
+    }
+
+    let program = Vec::<u8>::from_base32(program_5bit)
+        .map_err(|_| "Invalid witness program padding".to_string())?;
+    if program.len() < 2 || program.len() > 40 {
+        return Err(format!("Invalid witness program length {}", program.len()));
+    }
+    if version == 0 && program.len() != 20 && program.len() != 32 {
+        return Err(format!("Invalid v0 witness program length {}", program.len()));
+    }
+
+    Ok(WitnessAddress { hrp, version, program })
 }
 
 /// Detailed address validation result
@@ -67,6 +91,7 @@ pub enum AddressType {
     XRP,
     Monero,
     Litecoin,
+    Tron,
     
     Unknown,
 }
@@ -84,6 +109,7 @@ pub fn validate_address_detailed(address: &str, chain: Chain) -> AddressValidati
         Chain::Solana | Chain::SolanaDevnet => validate_solana_detailed(address),
         Chain::Xrp | Chain::XrpTestnet => validate_xrp_detailed(address),
         Chain::Monero => validate_monero_detailed(address),
+        Chain::Tron => validate_tron_detailed(address),
         // EVM-compatible chains
         chain if chain.is_evm() => validate_evm_detailed(address),
         // Default fallback for new chains
@@ -147,86 +173,42 @@ fn validate_bitcoin_detailed(address: &str, testnet: bool) -> AddressValidation
 fn validate_bech32_bitcoin(address: &str, testnet: bool) -> AddressValidation {
     let mut warnings = Vec::new();
     let lower = address.to_lowercase();
-    
-    // Try decoding - returns (hrp, data, variant)
-    if let Ok((hrp, data, variant)) = bech32::decode(&lower) {
-        let expected_hrp = if testnet { "tb" } else { "bc" };
-        
-        if hrp != expected_hrp {
-            return AddressValidation {
-                is_valid: false,
-                normalized: None,
-                address_type: AddressType::Unknown,
-                checksum_valid: true,
-                network_match: false,
-                warnings: vec!["Network mismatch".to_string()],
-            };
-        }
-        
-        // Check witness version
-        if data.is_empty() {
+    let expected_hrp = if testnet { "tb" } else { "bc" };
+
+    let witness = match decode_witness_address(&lower, &[expected_hrp]) {
+        Ok(w) => w,
+        Err(e) => {
+            // A wrong HRP still decoded with a valid checksum
+            let network_mismatch = e == "Network mismatch";
             return AddressValidation {
                 is_valid: false,
                 normalized: None,
                 address_type: AddressType::Unknown,
-                checksum_valid: false,
-                network_match: true,
-                warnings: vec!["Empty witness program".to_string()],
+                checksum_valid: network_mismatch,
+                network_match: !network_mismatch,
+                warnings: vec![e],
             };
         }
-        
-        let witness_version = data[0].to_u8();
-        
-        // Convert 5-bit to 8-bit (witness program bytes)
-        // bech32 crate 0.9 returns Vec<u5>, we need to convert to bytes
-        let program_5bit: Vec<u8> = data[1..].iter().map(|u| u.to_u8()).collect();
-        let program = convert_bits_5_to_8(&program_5bit);
-        
-        // Determine address type based on witness version and program length
-        let address_type = match (witness_version, program.len()) {
-            (0, 20) => AddressType::P2WPKH,  // SegWit v0 pubkey hash
-            (0, 32) => AddressType::P2WSH,   // SegWit v0 script hash
-            (1, 32) => AddressType::P2TR,    // Taproot (SegWit v1)
-            _ => {
-                warnings.push(format!("Unusual witness version {} or program length {}", 
-                    witness_version, program.len()));
-                AddressType::Unknown
-            }
-        };
-        
-        // Verify correct bech32 variant for the witness version
-        let expected_variant = if witness_version == 0 {
-            Variant::Bech32
-        } else {
-            Variant::Bech32m
-        };
-        
-        if variant != expected_variant {
-            warnings.push("Incorrect bech32 variant for witness version".to_string());
-        }
-        
-        // Warn about deprecated address types
-        if address_type == AddressType::P2WPKH {
-            // P2WPKH is fine but Taproot is preferred for new wallets
+    };
+
+    // Determine address type based on witness version and program length
+    let address_type = match (witness.version, witness.program.len()) {
+        (0, 20) => AddressType::P2WPKH,  // SegWit v0 pubkey hash
+        (0, 32) => AddressType::P2WSH,   // SegWit v0 script hash
+        (1, 32) => AddressType::P2TR,    // Taproot (SegWit v1)
+        (version, len) => {
+            warnings.push(format!("Unusual witness version {} or program length {}", version, len));
+            AddressType::Unknown
         }
-        
-        return AddressValidation {
-            is_valid: true,
-            normalized: Some(lower),
-            address_type,
-            checksum_valid: true,
-            network_match: true,
-            warnings,
-        };
-    }
-    
+    };
+
     AddressValidation {
-        is_valid: false,
-        normalized: None,
-        address_type: AddressType::Unknown,
-        checksum_valid: false,
-        network_match: false,
-        warnings: vec!["Invalid bech32 encoding".to_string()],
+        is_valid: true,
+        normalized: Some(lower),
+        address_type,
+        checksum_valid: true,
+        network_match: true,
+        warnings,
     }
 }
 
@@ -234,59 +216,23 @@ fn validate_bech32_bitcoin(address: &str, testnet: bool) -> AddressValidation {
 fn validate_base58_bitcoin(address: &str, testnet: bool) -> AddressValidation {
     let mut warnings = Vec::new();
     
-    // Decode Base58 and verify checksum manually
-    let decoded = match bs58::decode(address).into_vec() {
-        Ok(d) if d.len() >= 5 => {
-            // Verify checksum (last 4 bytes)
-            let (payload, checksum) = d.split_at(d.len() - 4);
-            let hash1 = sha256(payload);
-            let hash2 = sha256(&hash1);
-            if &hash2[..4] != checksum {
-                return AddressValidation {
-                    is_valid: false,
-                    normalized: None,
-                    address_type: AddressType::Unknown,
-                    checksum_valid: false,
-                    network_match: false,
-                    warnings: vec!["Invalid Base58Check checksum".to_string()],
-                };
-            }
-            payload.to_vec()
-        }
-        Ok(_) => {
-            return AddressValidation {
-                is_valid: false,
-                normalized: None,
-                address_type: AddressType::Unknown,
-                checksum_valid: false,
-                network_match: false,
-                warnings: vec!["Address too short".to_string()],
-            };
-        }
-        Err(_) => {
-            return AddressValidation {
-                is_valid: false,
-                normalized: None,
-                address_type: AddressType::Unknown,
-                checksum_valid: false,
-                network_match: false,
-                warnings: vec!["Invalid Base58 encoding".to_string()],
-            };
-        }
+    let (version, hash) = match base58check_decode(address, 1) {
+        Ok(decoded) => decoded,
+        Err(e) => return invalid_base58check(e, false),
     };
     
-    if decoded.is_empty() {
+    if hash.len() != 20 {
         return AddressValidation {
             is_valid: false,
             normalized: None,
             address_type: AddressType::Unknown,
-            checksum_valid: false,
+            checksum_valid: true,
             network_match: false,
-            warnings: vec!["Empty address data".to_string()],
+            warnings: vec![format!("Invalid hash length: expected 20 bytes, got {}", hash.len())],
         };
     }
     
-    let version = decoded[0];
+    let version = version[0];
     
     // Determine address type and network
     let (address_type, is_testnet) = match version {
@@ -390,9 +336,10 @@ fn validate_evm_detailed(address: &str) -> AddressValidation {
         warnings.push("Zero address - this is typically the burn address".to_string());
     }
     
+    // A mixed-case address that fails EIP-55 is likely mistyped
     AddressValidation {
-        is_valid: true,
-        normalized: Some(checksummed),
+        is_valid: checksum_valid,
+        normalized: if checksum_valid { Some(checksummed) } else { None },
         address_type: AddressType::EOA, // Can't distinguish from contract by format
         checksum_valid,
         network_match: true,
@@ -496,11 +443,8 @@ fn validate_xrp_detailed(address: &str) -> AddressValidation {
         };
     }
     
-    match bs58::decode(trimmed)
-        .with_alphabet(bs58::Alphabet::RIPPLE)
-        .into_vec()
-    {
-        Ok(bytes) if bytes.len() >= 21 => AddressValidation {
+    match base58check_decode_with(trimmed, 1, bs58::Alphabet::RIPPLE) {
+        Ok((version, account_id)) if version == [0x00] && account_id.len() == 20 => AddressValidation {
             is_valid: true,
             normalized: Some(trimmed.to_string()),
             address_type: AddressType::XRP,
@@ -508,43 +452,102 @@ fn validate_xrp_detailed(address: &str) -> AddressValidation {
             network_match: true,
             warnings: vec![],
         },
-        _ => AddressValidation {
+        Ok(_) => AddressValidation {
             is_valid: false,
             normalized: None,
             address_type: AddressType::Unknown,
-            checksum_valid: false,
+            checksum_valid: true,
             network_match: true,
-            warnings: vec!["Invalid Ripple Base58 encoding".to_string()],
+            warnings: vec!["Not an XRP account address".to_string()],
+        },
+        Err(e) => invalid_base58check(e, true),
+    }
+}
+
+/// Validate Tron address (Base58Check, version 0x41)
+fn validate_tron_detailed(address: &str) -> AddressValidation {
+    let trimmed = address.trim();
+    
+    match base58check_decode(trimmed, 1) {
+        Ok((version, hash)) if version == [0x41] && hash.len() == 20 => AddressValidation {
+            is_valid: true,
+            normalized: Some(trimmed.to_string()),
+            address_type: AddressType::Tron,
+            checksum_valid: true,
+            network_match: true,
+            warnings: vec![],
         },
+        Ok(_) => AddressValidation {
+            is_valid: false,
+            normalized: None,
+            address_type: AddressType::Unknown,
+            checksum_valid: true,
+            network_match: false,
+            warnings: vec!["Not a Tron address".to_string()],
+        },
+        Err(e) => invalid_base58check(e, true),
+    }
+}
+
+/// Result for an address that failed Base58Check decoding
+fn invalid_base58check(error: HawalaError, network_match: bool) -> AddressValidation {
+    AddressValidation {
+        is_valid: false,
+        normalized: None,
+        address_type: AddressType::Unknown,
+        checksum_valid: false,
+        network_match,
+        warnings: vec![error.message],
     }
 }
 
 /// Validate Monero address
+///
+/// [`Chain::Monero`] is mainnet only: testnet and stagenet addresses decode
+/// but are rejected with `network_match: false`.
 fn validate_monero_detailed(address: &str) -> AddressValidation {
     let trimmed = address.trim();
-    let mut warnings = Vec::new();
     
-    let (is_valid, address_type) = if trimmed.starts_with('4') {
-        if trimmed.len() == 95 {
-            (true, AddressType::Monero)
-        } else if trimmed.len() == 106 {
-            warnings.push("Integrated address - contains payment ID".to_string());
-            (true, AddressType::Monero)
-        } else {
-            (false, AddressType::Unknown)
+    if let Err(e) = monero_base58check_decode(trimmed, 1) {
+        return invalid_base58check(e, true);
+    }
+    
+    let parsed = match crate::monero_wallet::parse_address(trimmed) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            return AddressValidation {
+                is_valid: false,
+                normalized: None,
+                address_type: AddressType::Unknown,
+                checksum_valid: true,
+                network_match: true,
+                warnings: vec![e.message],
+            };
         }
-    } else if trimmed.starts_with('8') && trimmed.len() == 95 {
-        warnings.push("Subaddress - preferred for privacy".to_string());
-        (true, AddressType::Monero)
-    } else {
-        (false, AddressType::Unknown)
     };
     
+    let mut warnings = Vec::new();
+    match parsed.kind {
+        MoneroAddressKind::Standard => {}
+        MoneroAddressKind::Integrated => warnings.push("Integrated address - contains payment ID".to_string()),
+        MoneroAddressKind::Subaddress => warnings.push("Subaddress - preferred for privacy".to_string()),
+    }
+    if parsed.network != "mainnet" {
+        return AddressValidation {
+            is_valid: false,
+            normalized: None,
+            address_type: AddressType::Monero,
+            checksum_valid: true,
+            network_match: false,
+            warnings: vec![format!("Monero {} address", parsed.network)],
+        };
+    }
+    
     AddressValidation {
-        is_valid,
-        normalized: if is_valid { Some(trimmed.to_string()) } else { None },
-        address_type,
-        checksum_valid: is_valid, // Monero addresses have built-in checksum
+        is_valid: true,
+        normalized: Some(trimmed.to_string()),
+        address_type: AddressType::Monero,
+        checksum_valid: true,
         network_match: true,
         warnings,
     }
@@ -555,76 +558,69 @@ fn validate_litecoin_detailed(address: &str) -> AddressValidation {
     let trimmed = address.trim();
     let mut warnings = Vec::new();
     
-    // Bech32 (ltc1)
-    if trimmed.to_lowercase().starts_with("ltc1") {
-        let lower = trimmed.to_lowercase();
-        
-        // Validate bech32 format - returns (hrp, data, variant)
-        if let Ok((hrp, data, _variant)) = bech32::decode(&lower) {
-            if hrp != "ltc" {
-                return AddressValidation {
-                    is_valid: false,
-                    normalized: None,
-                    address_type: AddressType::Unknown,
-                    checksum_valid: false,
-                    network_match: false,
-                    warnings: vec!["Invalid HRP for Litecoin".to_string()],
-                };
-            }
-            
-            if !data.is_empty() {
-                let witness_version = data[0].to_u8();
-                let address_type = match witness_version {
-                    0 => AddressType::P2WPKH,
-                    1 => AddressType::P2TR,
+    // Bech32 (ltc1); tltc1 is decoded only to report the network mismatch
+    let lower = trimmed.to_lowercase();
+    if lower.starts_with("ltc1") || lower.starts_with("tltc1") {
+        return match decode_witness_address(&lower, &["ltc", "tltc"]) {
+            Ok(witness) => {
+                let address_type = match (witness.version, witness.program.len()) {
+                    (0, 20) => AddressType::P2WPKH,
+                    (0, 32) => AddressType::P2WSH,
+                    (1, 32) => AddressType::P2TR,
                     _ => AddressType::Litecoin,
                 };
-                
-                return AddressValidation {
+
+                if witness.hrp == "tltc" {
+                    return AddressValidation {
+                        is_valid: false,
+                        normalized: None,
+                        address_type,
+                        checksum_valid: true,
+                        network_match: false,
+                        warnings: vec!["Litecoin testnet address".to_string()],
+                    };
+                }
+
+                AddressValidation {
                     is_valid: true,
                     normalized: Some(lower),
                     address_type,
                     checksum_valid: true,
                     network_match: true,
                     warnings,
-                };
+                }
             }
-        }
-        
-        return AddressValidation {
-            is_valid: false,
-            normalized: None,
-            address_type: AddressType::Unknown,
-            checksum_valid: false,
-            network_match: true,
-            warnings: vec!["Invalid Bech32 encoding".to_string()],
+            Err(e) => AddressValidation {
+                is_valid: false,
+                normalized: None,
+                address_type: AddressType::Unknown,
+                checksum_valid: false,
+                network_match: true,
+                warnings: vec![e],
+            },
         };
     }
     
     // Legacy (L, M, 3)
     if trimmed.starts_with('L') || trimmed.starts_with('M') || trimmed.starts_with('3') {
         if trimmed.len() >= 26 && trimmed.len() <= 35 {
-            // Manual Base58Check validation
-            if let Ok(decoded) = bs58::decode(trimmed).into_vec() {
-                if decoded.len() >= 5 {
-                    let (payload, checksum) = decoded.split_at(decoded.len() - 4);
-                    let hash1 = sha256(payload);
-                    let hash2 = sha256(&hash1);
-                    if &hash2[..4] == checksum {
-                        if trimmed.starts_with('L') || trimmed.starts_with('M') {
-                            warnings.push("Legacy P2PKH - consider using Bech32 for lower fees".to_string());
-                        }
-                        
-                        return AddressValidation {
-                            is_valid: true,
-                            normalized: Some(trimmed.to_string()),
-                            address_type: AddressType::Litecoin,
-                            checksum_valid: true,
-                            network_match: true,
-                            warnings,
-                        };
+            match base58check_decode(trimmed, 1) {
+                Ok((_, hash)) if hash.len() == 20 => {
+                    if trimmed.starts_with('L') || trimmed.starts_with('M') {
+                        warnings.push("Legacy P2PKH - consider using Bech32 for lower fees".to_string());
                     }
+                    
+                    return AddressValidation {
+                        is_valid: true,
+                        normalized: Some(trimmed.to_string()),
+                        address_type: AddressType::Litecoin,
+                        checksum_valid: true,
+                        network_match: true,
+                        warnings,
+                    };
                 }
+                Ok(_) => {}
+                Err(e) => return invalid_base58check(e, true),
             }
         }
     }
@@ -639,6 +635,90 @@ fn validate_litecoin_detailed(address: &str) -> AddressValidation {
     }
 }
 
+/// Network detected for an address, independent of the chain it was entered for
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkCheck {
+    /// Network the address most likely belongs to
+    pub network_hint: Option<Chain>,
+    /// Set when the address may not be spendable on the requested chain
+    pub cross_chain_warning: Option<String>,
+}
+
+/// Chains whose address formats are distinctive enough to identify
+const NETWORK_CANDIDATES: [Chain; 8] = [
+    Chain::Bitcoin,
+    Chain::BitcoinTestnet,
+    Chain::Litecoin,
+    Chain::Ethereum,
+    Chain::Tron,
+    Chain::Xrp,
+    Chain::Monero,
+    Chain::Solana,
+];
+
+/// Detect addresses that are well formed but probably meant for another network
+///
+/// Catches testnet addresses entered on mainnet (and vice versa), addresses of
+/// a different chain family, and EVM addresses, which are valid on every EVM
+/// chain and so cannot prove which one the recipient expects.
+pub fn check_address_network(address: &str, chain: Chain) -> NetworkCheck {
+    let trimmed = address.trim();
+
+    let validation = validate_address_detailed(trimmed, chain);
+    if validation.is_valid {
+        if chain.is_evm() {
+            return NetworkCheck {
+                network_hint: Some(chain),
+                cross_chain_warning: Some(format!(
+                    "EVM addresses are valid on every EVM chain; confirm the recipient expects {} on this network",
+                    chain.symbol()
+                )),
+            };
+        }
+        // Chains without a dedicated validator only get a length check
+        let known_format = validation.address_type != AddressType::Unknown;
+        return NetworkCheck { network_hint: known_format.then_some(chain), cross_chain_warning: None };
+    }
+
+    let detected = NETWORK_CANDIDATES
+        .iter()
+        .copied()
+        .filter(|candidate| *candidate != chain)
+        .find(|candidate| validate_address_detailed(trimmed, *candidate).is_valid);
+
+    let Some(detected) = detected else {
+        // Decoded with a valid checksum but for a network with no Chain of its
+        // own, such as Litecoin testnet or Monero stagenet
+        if validation.checksum_valid && !validation.network_match {
+            let warning = if chain.is_testnet() {
+                "Mainnet address used on testnet"
+            } else {
+                "Testnet address used on mainnet"
+            };
+            return NetworkCheck { network_hint: None, cross_chain_warning: Some(warning.to_string()) };
+        }
+        return NetworkCheck::default();
+    };
+
+    let warning = match (chain.is_testnet(), detected.is_testnet()) {
+        (false, true) if same_family(chain, detected) => "Testnet address used on mainnet".to_string(),
+        (true, false) if same_family(chain, detected) => "Mainnet address used on testnet".to_string(),
+        _ if detected.is_evm() => format!("EVM address cannot receive {}", chain.symbol()),
+        _ => format!("Address belongs to {}, not {}", detected.symbol(), chain.symbol()),
+    };
+
+    NetworkCheck { network_hint: Some(detected), cross_chain_warning: Some(warning) }
+}
+
+fn same_family(a: Chain, b: Chain) -> bool {
+    matches!(
+        (a, b),
+        (Chain::Bitcoin | Chain::BitcoinTestnet, Chain::Bitcoin | Chain::BitcoinTestnet)
+            | (Chain::Solana | Chain::SolanaDevnet, Chain::Solana | Chain::SolanaDevnet)
+            | (Chain::Xrp | Chain::XrpTestnet, Chain::Xrp | Chain::XrpTestnet)
+    ) || (a.is_evm() && b.is_evm())
+}
+
 /// Quick validation check - returns error if invalid
 pub fn require_valid_address(address: &str, chain: Chain) -> HawalaResult<String> {
     let validation = validate_address_detailed(address, chain);
@@ -673,6 +753,36 @@ mod tests {
         assert_eq!(result.address_type, AddressType::P2TR);
     }
 
+    #[test]
+    fn test_bech32m_required_for_taproot() {
+        // Valid bech32m v1 passes
+        let taproot = "bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297";
+        let witness = decode_witness_address(taproot, &["bc"]).unwrap();
+        assert_eq!(witness.version, 1);
+        assert_eq!(witness.program.len(), 32);
+
+        // Same witness program with a bech32 checksum is rejected
+        let taproot_bech32 = "bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusn5pxqu";
+        assert!(decode_witness_address(taproot_bech32, &["bc"]).is_err());
+        let result = validate_bitcoin_detailed(taproot_bech32, false);
+        assert!(!result.is_valid);
+        assert!(!result.checksum_valid);
+
+        // v0 requires bech32: the bech32m form of a valid bc1q is rejected
+        assert!(validate_bitcoin_detailed("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", false).is_valid);
+        let v0_bech32m = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzm4yhgz";
+        assert!(decode_witness_address(v0_bech32m, &["bc"]).is_err());
+        assert!(!validate_bitcoin_detailed(v0_bech32m, false).is_valid);
+    }
+
+    #[test]
+    fn test_litecoin_bech32_variants() {
+        // BIP-173 P2WPKH program under the ltc HRP, then with a bech32m checksum
+        let v0 = "ltc1qw508d6qejxtdg4y5r3zarvary0c5xw7kgmn4n9";
+        assert!(validate_litecoin_detailed(v0).is_valid);
+        assert!(!validate_litecoin_detailed("ltc1qw508d6qejxtdg4y5r3zarvary0c5xw7ka8rek8").is_valid);
+    }
+
     #[test]
     fn test_bitcoin_legacy_warnings() {
         let result = validate_bitcoin_detailed("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2", false);
@@ -692,6 +802,12 @@ mod tests {
         let result = validate_evm_detailed("0xd8da6bf26964af9d7eed9e03e53415d37aa96045");
         assert!(result.is_valid); // Still valid, just no checksum
         assert!(result.warnings.iter().any(|w| w.contains("no EIP-55 checksum")));
+
+        // Mixed case with one letter's case flipped
+        let result = validate_evm_detailed("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".replace("dA6", "DA6").as_str());
+        assert!(!result.is_valid);
+        assert!(!result.checksum_valid);
+        assert!(result.normalized.is_none());
     }
 
     #[test]
@@ -705,6 +821,42 @@ mod tests {
         assert_eq!(result.address_type, AddressType::P2TR);
     }
 
+    #[test]
+    fn test_base58check_addresses() {
+        let cases = [
+            (Chain::Bitcoin, "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2"),
+            (Chain::Litecoin, "LW98ceYNxYki9e9QxDACLn82TtVEPm4qmy"),
+            (Chain::Xrp, "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh"),
+            (Chain::Tron, "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"),
+        ];
+        for (chain, address) in cases {
+            let result = validate_address_detailed(address, chain);
+            assert!(result.is_valid, "{:?} {}", chain, address);
+            assert!(result.checksum_valid);
+
+            // Last character changed
+            let last = address.chars().last().unwrap();
+            let corrupted = format!("{}{}", &address[..address.len() - 1], if last == 'a' { 'b' } else { 'a' });
+            let result = validate_address_detailed(&corrupted, chain);
+            assert!(!result.is_valid, "{:?} {}", chain, corrupted);
+            assert!(!result.checksum_valid);
+
+            // Truncated
+            assert!(!validate_address_detailed(&address[..address.len() - 5], chain).is_valid);
+        }
+    }
+
+    #[test]
+    fn test_monero_checksum() {
+        let address = "4AdUndXHHZ6cfufTMvppY6JwXNouMBzSkbLYfpAV5Usx3skxNgYeYTRj5UzqtReoS44qo9mtmXCqY45DJ852K5Jv2684Rge";
+        assert!(validate_address_detailed(address, Chain::Monero).is_valid);
+
+        let corrupted = address.replacen("4Ad", "4Ae", 1);
+        let result = validate_address_detailed(&corrupted, Chain::Monero);
+        assert!(!result.is_valid);
+        assert!(!result.checksum_valid);
+    }
+
     #[test]
     fn test_require_valid_address() {
         // Valid
@@ -713,4 +865,61 @@ mod tests {
         // Invalid
         assert!(require_valid_address("invalid", Chain::Ethereum).is_err());
     }
+
+    #[test]
+    fn test_network_mismatch_detection() {
+        // Testnet address entered for mainnet
+        let check = check_address_network("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx", Chain::Bitcoin);
+        assert_eq!(check.network_hint, Some(Chain::BitcoinTestnet));
+        assert_eq!(check.cross_chain_warning.as_deref(), Some("Testnet address used on mainnet"));
+
+        // And the reverse
+        let check = check_address_network("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", Chain::BitcoinTestnet);
+        assert_eq!(check.network_hint, Some(Chain::Bitcoin));
+        assert!(check.cross_chain_warning.is_some());
+
+        // Matching network has no warning
+        let check = check_address_network("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", Chain::Bitcoin);
+        assert_eq!(check.network_hint, Some(Chain::Bitcoin));
+        assert!(check.cross_chain_warning.is_none());
+
+        // EVM addresses are flagged as shared across chains
+        let check = check_address_network("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045", Chain::Polygon);
+        assert_eq!(check.network_hint, Some(Chain::Polygon));
+        assert!(check.cross_chain_warning.is_some());
+
+        // EVM address pasted into a Bitcoin field
+        let check = check_address_network("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045", Chain::Bitcoin);
+        assert_eq!(check.network_hint, Some(Chain::Ethereum));
+
+        // Garbage gives no hint
+        assert_eq!(check_address_network("invalid", Chain::Bitcoin), NetworkCheck::default());
+    }
+
+    #[test]
+    fn test_litecoin_testnet_rejected_on_mainnet() {
+        let tltc = "tltc1qw508d6qejxtdg4y5r3zarvary0c5xw7klfsuq0";
+        let result = validate_address_detailed(tltc, Chain::Litecoin);
+        assert!(!result.is_valid);
+        assert!(result.checksum_valid);
+        assert!(!result.network_match);
+        assert!(!crate::wallet::validate_address(tltc, Chain::Litecoin).0);
+
+        let check = check_address_network(tltc, Chain::Litecoin);
+        assert_eq!(check.network_hint, None);
+        assert_eq!(check.cross_chain_warning.as_deref(), Some("Testnet address used on mainnet"));
+    }
+
+    #[test]
+    fn test_monero_stagenet_rejected_on_mainnet() {
+        let stagenet = "5LYBtHFjYRicfufTMvppY6JwXNouMBzSkbLYfpAV5Usx3skxNgYeYTRj5UzqtReoS44qo9mtmXCqY45DJ852K5Jv2WMGcEvyW3G21nL8Jn";
+        let result = validate_address_detailed(stagenet, Chain::Monero);
+        assert!(!result.is_valid);
+        assert!(!result.network_match);
+        assert!(result.warnings.iter().any(|w| w.contains("stagenet")));
+        assert!(!crate::wallet::validate_address(stagenet, Chain::Monero).0);
+
+        let check = check_address_network(stagenet, Chain::Monero);
+        assert_eq!(check.cross_chain_warning.as_deref(), Some("Testnet address used on mainnet"));
+    }
 }
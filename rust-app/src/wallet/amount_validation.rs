@@ -204,8 +204,51 @@ fn parse_amount(amount: &str, chain: Chain) -> Result<(u128, u8), String> {
     }
 }
 
+/// Parse a decimal amount string (e.g. "1.5") into base units, exactly
+///
+/// Never goes through floating point. Accepts plain digits with at most one
+/// '.', rejects signs, exponents and more than `decimals` fractional digits,
+/// and errors instead of overflowing `u128`.
+pub fn parse_decimal_amount(input: &str, decimals: u8) -> HawalaResult<u128> {
+    let trimmed = input.trim();
+    let invalid = |reason: &str| HawalaError::invalid_input(format!("Invalid amount '{}': {}", input, reason));
+
+    let (integer_str, fractional_str) = match trimmed.split_once('.') {
+        Some((integer, fractional)) => (integer, fractional),
+        None => (trimmed, ""),
+    };
+    if integer_str.is_empty() && fractional_str.is_empty() {
+        return Err(invalid("no digits"));
+    }
+    if !integer_str.bytes().chain(fractional_str.bytes()).all(|b| b.is_ascii_digit()) {
+        return Err(invalid("expected digits with an optional decimal point"));
+    }
+    if fractional_str.len() > decimals as usize {
+        return Err(invalid(&format!("more than {} decimal places", decimals)));
+    }
+
+    let multiplier = 10u128
+        .checked_pow(decimals as u32)
+        .ok_or_else(|| invalid("too many decimals"))?;
+    let padded = format!("{:0<width$}", fractional_str, width = decimals as usize);
+
+    // Leading zeros are fine; each digit is checked so long inputs cannot wrap
+    let digits_to_u128 = |digits: &str| {
+        digits.bytes().try_fold(0u128, |acc, b| {
+            acc.checked_mul(10)?.checked_add((b - b'0') as u128)
+        })
+    };
+    let integer = digits_to_u128(integer_str).ok_or_else(|| invalid("overflow"))?;
+    let fractional = digits_to_u128(&padded).ok_or_else(|| invalid("overflow"))?;
+
+    integer
+        .checked_mul(multiplier)
+        .and_then(|v| v.checked_add(fractional))
+        .ok_or_else(|| invalid("overflow"))
+}
+
 /// Format raw amount to display string
-fn format_amount(raw: u128, decimals: u8) -> String {
+pub(crate) fn format_amount(raw: u128, decimals: u8) -> String {
     let multiplier = 10u128.pow(decimals as u32);
     let integer = raw / multiplier;
     let fractional = raw % multiplier;
@@ -400,6 +443,33 @@ mod tests {
         assert!(validation.warnings.iter().any(|w| w.contains("Large amount")));
     }
 
+    #[test]
+    fn test_parse_decimal_amount_is_exact() {
+        // 1 lamport; as f64 this is 0.000000000999... and truncates to 0
+        assert_eq!(parse_decimal_amount("0.000000001", 9).unwrap(), 1);
+        assert_eq!(parse_decimal_amount("1.1", 9).unwrap(), 1_100_000_000);
+        assert_eq!(parse_decimal_amount(" 12 ", 9).unwrap(), 12_000_000_000);
+        assert_eq!(parse_decimal_amount(".5", 12).unwrap(), 500_000_000_000);
+        assert_eq!(parse_decimal_amount("7.", 2).unwrap(), 700);
+        assert_eq!(parse_decimal_amount("0.300000000000000000", 18).unwrap(), 300_000_000_000_000_000);
+    }
+
+    #[test]
+    fn test_parse_decimal_amount_rejects_bad_input() {
+        // Over-precise: 10 decimals for a 9-decimal asset
+        let err = parse_decimal_amount("0.0000000001", 9).unwrap_err();
+        assert!(err.message.contains("decimal places"), "{}", err.message);
+
+        for input in ["", ".", "-1", "+1", "1e9", "1.2.3", "0x10", "1,5", "NaN"] {
+            assert!(parse_decimal_amount(input, 9).is_err(), "accepted {:?}", input);
+        }
+
+        assert!(parse_decimal_amount("340282366920938463463374607431768211455", 0).is_ok());
+        assert!(parse_decimal_amount("340282366920938463463374607431768211456", 0).is_err());
+        assert!(parse_decimal_amount("340282366920938463463374607431768211455", 1).is_err());
+        assert!(parse_decimal_amount("1", 39).is_err());
+    }
+
     #[test]
     fn test_gas_validation() {
         // Valid legacy
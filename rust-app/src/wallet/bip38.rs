@@ -0,0 +1,217 @@
+//! BIP-38 Encrypted Private Keys
+//!
+//! Passphrase-protected private keys (`6P...`) for paper wallets, using the
+//! non-EC-multiply scheme: scrypt over the passphrase salted with a hash of
+//! the key's P2PKH address, then AES-256 over the two key halves.
+//!
+//! EC-multiply keys (made from an intermediate code) are rejected.
+//!
+//! SECURITY: The scrypt output and decrypted key bytes are zeroized on drop.
+
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+use aes::{Aes256, Block};
+use bitcoin::hashes::{sha256d, Hash};
+use bitcoin::secp256k1::{Secp256k1, SecretKey};
+use bitcoin::{Address, Network, PrivateKey};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+use crate::error::{HawalaError, HawalaResult};
+
+/// scrypt cost parameters fixed by BIP-38: N = 2^14, r = 8, p = 8
+const SCRYPT_LOG_N: u8 = 14;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 8;
+
+/// Prefix bytes of a non-EC-multiply key
+const PREFIX_NON_EC: [u8; 2] = [0x01, 0x42];
+/// Prefix bytes of an EC-multiply key
+const PREFIX_EC: [u8; 2] = [0x01, 0x43];
+
+const FLAG_NON_EC: u8 = 0xc0;
+const FLAG_COMPRESSED: u8 = 0x20;
+
+/// Payload length before the base58check checksum
+const ENCRYPTED_LEN: usize = 39;
+
+/// Encrypt a 32-byte private key with `passphrase`
+///
+/// `compressed` selects which address the key is bound to, and is restored
+/// by [`decrypt`]. The passphrase is NFC-normalized first.
+pub fn encrypt(private_key: &[u8], passphrase: &str, compressed: bool) -> HawalaResult<String> {
+    let secret = SecretKey::from_slice(private_key)?;
+    let key = if compressed {
+        PrivateKey::new(secret, Network::Bitcoin)
+    } else {
+        PrivateKey::new_uncompressed(secret, Network::Bitcoin)
+    };
+
+    let address_hash = address_hash(&key);
+    let derived = derive_key(passphrase, &address_hash)?;
+    let cipher = Aes256::new_from_slice(&derived[32..])
+        .map_err(|e| HawalaError::crypto_error(format!("AES key error: {}", e)))?;
+
+    let mut payload = Vec::with_capacity(ENCRYPTED_LEN);
+    payload.extend_from_slice(&PREFIX_NON_EC);
+    payload.push(if compressed { FLAG_NON_EC | FLAG_COMPRESSED } else { FLAG_NON_EC });
+    payload.extend_from_slice(&address_hash);
+
+    let secret_bytes = Zeroizing::new(secret.secret_bytes());
+    for half in 0..2 {
+        let range = half * 16..half * 16 + 16;
+        let mut block = Block::default();
+        for (i, byte) in block.iter_mut().enumerate() {
+            *byte = secret_bytes[range.start + i] ^ derived[range.start + i];
+        }
+        cipher.encrypt_block(&mut block);
+        payload.extend_from_slice(&block);
+    }
+
+    Ok(bitcoin::base58::encode_check(&payload))
+}
+
+/// Decrypt a `6P...` key with `passphrase`
+///
+/// Returns a mainnet [`PrivateKey`] whose `compressed` flag matches the
+/// encrypted key. A wrong passphrase is detected through the address hash.
+pub fn decrypt(encrypted: &str, passphrase: &str) -> HawalaResult<PrivateKey> {
+    let payload = bitcoin::base58::decode_check(encrypted.trim())
+        .map_err(|e| HawalaError::invalid_input(format!("Invalid BIP-38 key: {}", e)))?;
+    if payload.len() != ENCRYPTED_LEN {
+        return Err(HawalaError::invalid_input(format!(
+            "Invalid BIP-38 key length: {} bytes",
+            payload.len()
+        )));
+    }
+    if payload[..2] == PREFIX_EC {
+        return Err(HawalaError::not_implemented("EC-multiply BIP-38 keys are not supported"));
+    }
+    if payload[..2] != PREFIX_NON_EC {
+        return Err(HawalaError::invalid_input("Not a BIP-38 encrypted key"));
+    }
+
+    let flag = payload[2];
+    if flag & !FLAG_COMPRESSED != FLAG_NON_EC {
+        return Err(HawalaError::invalid_input(format!("Invalid BIP-38 flag byte: {:#04x}", flag)));
+    }
+    let compressed = flag & FLAG_COMPRESSED != 0;
+    let address_hash: [u8; 4] = payload[3..7].try_into().expect("slice is 4 bytes");
+
+    let derived = derive_key(passphrase, &address_hash)?;
+    let cipher = Aes256::new_from_slice(&derived[32..])
+        .map_err(|e| HawalaError::crypto_error(format!("AES key error: {}", e)))?;
+
+    let mut secret_bytes = Zeroizing::new([0u8; 32]);
+    for half in 0..2 {
+        let offset = half * 16;
+        let mut block = Block::default();
+        block.copy_from_slice(&payload[7 + offset..7 + offset + 16]);
+        cipher.decrypt_block(&mut block);
+        for (i, byte) in block.iter().enumerate() {
+            secret_bytes[offset + i] = byte ^ derived[offset + i];
+        }
+    }
+
+    let invalid_passphrase = || HawalaError::crypto_error("Incorrect BIP-38 passphrase");
+    let secret = SecretKey::from_slice(&secret_bytes[..]).map_err(|_| invalid_passphrase())?;
+    let key = if compressed {
+        PrivateKey::new(secret, Network::Bitcoin)
+    } else {
+        PrivateKey::new_uncompressed(secret, Network::Bitcoin)
+    };
+
+    if self::address_hash(&key) != address_hash {
+        return Err(invalid_passphrase());
+    }
+    Ok(key)
+}
+
+/// Mainnet P2PKH address of `key`, which BIP-38 binds the ciphertext to
+pub fn bip38_address(key: &PrivateKey) -> String {
+    let secp = Secp256k1::signing_only();
+    Address::p2pkh(key.public_key(&secp), Network::Bitcoin).to_string()
+}
+
+/// First four bytes of SHA256d over the ASCII address
+fn address_hash(key: &PrivateKey) -> [u8; 4] {
+    let hash = sha256d::Hash::hash(bip38_address(key).as_bytes());
+    let mut out = [0u8; 4];
+    out.copy_from_slice(&hash.as_byte_array()[..4]);
+    out
+}
+
+/// 64 bytes of scrypt output: `derivedhalf1 || derivedhalf2`
+fn derive_key(passphrase: &str, salt: &[u8; 4]) -> HawalaResult<Zeroizing<[u8; 64]>> {
+    let passphrase = Zeroizing::new(passphrase.nfc().collect::<String>());
+    let params = scrypt::Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P, 64)
+        .map_err(|e| HawalaError::internal(format!("Invalid scrypt parameters: {}", e)))?;
+
+    let mut derived = Zeroizing::new([0u8; 64]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut derived[..])
+        .map_err(|e| HawalaError::crypto_error(format!("scrypt failed: {}", e)))?;
+    Ok(derived)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BIP-38 spec vectors: (passphrase, encrypted, WIF, key hex)
+    const VECTORS: &[(&str, &str, &str, &str)] = &[
+        (
+            "TestingOneTwoThree",
+            "6PRVWUbkzzsbcVac2qwfssoUJAN1Xhrg6bNk8J7Nzm5H7kxEbn2Nh2ZoGg",
+            "5KN7MzqK5wt2TP1fQCYyHBtDrXdJuXbUzm4A9rKAteGu3Qi5CVR",
+            "cbf4b9f70470856bb4f40f80b87edb90865997ffee6df315ab166d713af433a5",
+        ),
+        (
+            "Satoshi",
+            "6PRNFFkZc2NZ6dJqFfhRoFNMR9Lnyj7dYGrzdgXXVMXcxoKTePPX1dWByq",
+            "5HtasZ6ofTHP6HCwTqTkLDuLQisYPah7aUnSKfC7h4hMUVw2gi5",
+            "09c2686880095b1a4c249ee3ac4eea8a014f11e6f986d0b5025ac1f39afbd9ae",
+        ),
+        (
+            "TestingOneTwoThree",
+            "6PYNKZ1EAgYgmQfmNVamxyXVWHzK5s6DGhwP4J5o44cvXdoY7sRzhtpUeo",
+            "L44B5gGEpqEDRS9vVPz7QT35jcBG2r3CZwSwQ4fCewXAhAhqGVpP",
+            "cbf4b9f70470856bb4f40f80b87edb90865997ffee6df315ab166d713af433a5",
+        ),
+        (
+            "Satoshi",
+            "6PYLtMnXvfG3oJde97zRyLYFZCYizPU5T3LwgdYJz1fRhh16bU7u6PPmY7",
+            "KwYgW8gcxj1JWJXhPSu4Fqwzfhp5Yfi42mdYmMa4XqK7NJxXUSK7",
+            "09c2686880095b1a4c249ee3ac4eea8a014f11e6f986d0b5025ac1f39afbd9ae",
+        ),
+    ];
+
+    #[test]
+    fn test_encrypt_spec_vectors() {
+        for (passphrase, encrypted, wif, key_hex) in VECTORS {
+            let compressed = !wif.starts_with('5');
+            let key = hex::decode(key_hex).unwrap();
+            assert_eq!(encrypt(&key, passphrase, compressed).unwrap(), *encrypted);
+        }
+    }
+
+    #[test]
+    fn test_decrypt_spec_vectors() {
+        for (passphrase, encrypted, wif, key_hex) in VECTORS {
+            let key = decrypt(encrypted, passphrase).unwrap();
+            assert_eq!(key.to_wif(), *wif);
+            assert_eq!(hex::encode(key.inner.secret_bytes()), *key_hex);
+        }
+    }
+
+    #[test]
+    fn test_decrypt_rejects_bad_input() {
+        let (_, encrypted, _, _) = VECTORS[0];
+        let err = decrypt(encrypted, "wrong passphrase").unwrap_err();
+        assert!(err.message.contains("passphrase"));
+
+        assert!(decrypt("5KN7MzqK5wt2TP1fQCYyHBtDrXdJuXbUzm4A9rKAteGu3Qi5CVR", "x").is_err());
+        // EC-multiply vector from the spec
+        let err = decrypt("6PfQu77ygVyJLZjfvMLyhLMQbYnu5uguoJJ4kMCLqWwPEdfpwANVS76gTX", "TestingOneTwoThree")
+            .unwrap_err();
+        assert!(err.message.contains("EC-multiply"));
+    }
+}
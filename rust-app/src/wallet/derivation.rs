@@ -4,7 +4,7 @@
 //!
 //! SECURITY: All private key material is zeroized when no longer needed.
 
-use bitcoin::bip32::{DerivationPath, Xpriv};
+use bitcoin::bip32::{ChildNumber, DerivationPath, Xpriv, Xpub};
 use bitcoin::hashes::{Hash, hash160, sha256d};
 use bitcoin::key::{CompressedPublicKey, PublicKey as BitcoinPublicKey};
 use bitcoin::secp256k1::{Secp256k1, SecretKey};
@@ -20,27 +20,105 @@ use std::convert::TryFrom;
 use std::str::FromStr;
 use tiny_keccak::{Hasher, Keccak};
 
+use crate::crypto::curves::{CurveError, Ed25519Curve, KeyDerivation};
 use crate::error::{HawalaError, HawalaResult};
 use crate::types::*;
 use crate::taproot_wallet::derive_taproot_address;
 
+use super::derivation_path;
+
+/// Chains in [`AllKeys`] whose keys follow the requested BIP-44 account/index.
+///
+/// Every other chain uses a fixed, chain-specific derivation and is always
+/// returned at its default (account 0, index 0) key.
+pub const INDEXED_CHAINS: &[&str] = &[
+    "bitcoin",
+    "bitcoin_testnet",
+    "litecoin",
+    "ethereum",
+    "ethereum_sepolia",
+    "bnb",
+    "xrp",
+];
+
 /// Derive all keys from a seed
 /// 
 /// SECURITY: The seed should be wrapped in Zeroizing by the caller
 pub fn derive_all_keys(seed: &[u8]) -> HawalaResult<AllKeys> {
+    derive_all_keys_at(seed, 0, 0)
+}
+
+/// Derive all keys from a seed at a BIP-44 account and address index
+///
+/// Paths are `m/purpose'/coin_type'/account'/0/index` with the chain's own
+/// coin type (0 BTC, 1 testnet, 2 LTC, 60 ETH/BNB, 144 XRP). Only the chains
+/// listed in [`INDEXED_CHAINS`] are affected; `(0, 0)` is identical to
+/// [`derive_all_keys`].
+///
+/// SECURITY: The seed should be wrapped in Zeroizing by the caller
+pub fn derive_all_keys_at(seed: &[u8], account: u32, index: u32) -> HawalaResult<AllKeys> {
+    derive_keys(seed, KeyNetwork::Mainnet, account, index)
+}
+
+/// Keys for every chain that can be derived at a BIP-44 account and index
+///
+/// The [`INDEXED_CHAINS`] use the same paths as [`derive_all_keys_at`].
+/// Solana uses its per-account path `m/44'/501'/index'/0'` (as in
+/// [`derive_addresses`]), which has no account level, so it is only included
+/// for account 0. Chains with a fixed derivation are left out instead of
+/// being returned at their default key.
+///
+/// SECURITY: The seed should be wrapped in Zeroizing by the caller
+pub fn derive_account_keys(seed: &[u8], account: u32, index: u32) -> HawalaResult<PartialKeys> {
+    let keys = derive_keys(seed, KeyNetwork::Mainnet, account, index)?;
+    Ok(PartialKeys {
+        bitcoin: Some(keys.bitcoin),
+        bitcoin_testnet: Some(keys.bitcoin_testnet),
+        litecoin: Some(keys.litecoin),
+        ethereum: Some(keys.ethereum),
+        ethereum_sepolia: Some(keys.ethereum_sepolia),
+        bnb: Some(keys.bnb),
+        xrp: Some(keys.xrp),
+        solana: if account == 0 { Some(derive_solana_account_keys(seed, index)?) } else { None },
+        ..PartialKeys::default()
+    })
+}
+
+/// Derive all keys from a seed for `network`
+///
+/// See [`KeyNetwork`] for which chains change on testnet.
+///
+/// SECURITY: The seed should be wrapped in Zeroizing by the caller
+pub fn derive_all_keys_on(seed: &[u8], network: KeyNetwork) -> HawalaResult<AllKeys> {
+    derive_keys(seed, network, 0, 0)
+}
+
+fn derive_keys(seed: &[u8], network: KeyNetwork, account: u32, index: u32) -> HawalaResult<AllKeys> {
+    if account >= HARDENED_OFFSET || index >= HARDENED_OFFSET {
+        return Err(HawalaError::invalid_input(format!(
+            "Account and index must be below {}",
+            HARDENED_OFFSET
+        )));
+    }
+
     let secp = Secp256k1::new();
     let master = Xpriv::new_master(Network::Bitcoin, seed)?;
+    let at = AccountIndex { account, index };
+    let (bitcoin_network, monero_network) = match network {
+        KeyNetwork::Mainnet => (Network::Bitcoin, MoneroNetwork::Mainnet),
+        KeyNetwork::Testnet => (Network::Testnet, MoneroNetwork::Testnet),
+    };
 
     Ok(AllKeys {
-        bitcoin: derive_bitcoin_keys(&secp, &master, Network::Bitcoin)?,
-        bitcoin_testnet: derive_bitcoin_keys(&secp, &master, Network::Testnet)?,
-        litecoin: derive_litecoin_keys(&secp, &master)?,
-        monero: derive_monero_keys(seed)?,
+        bitcoin: derive_bitcoin_keys(&secp, &master, bitcoin_network, at)?,
+        bitcoin_testnet: derive_bitcoin_keys(&secp, &master, Network::Testnet, at)?,
+        litecoin: derive_litecoin_keys(&secp, &master, network, at)?,
+        monero: derive_monero_keys(seed, monero_network)?,
         solana: derive_solana_keys(seed)?,
-        ethereum: derive_ethereum_keys(&secp, &master)?,
-        ethereum_sepolia: derive_ethereum_keys(&secp, &master)?, // Same keys, different network
-        bnb: derive_bnb_keys(&secp, &master)?,
-        xrp: derive_xrp_keys(&secp, &master)?,
+        ethereum: derive_ethereum_keys(&secp, &master, at)?,
+        ethereum_sepolia: derive_ethereum_keys(&secp, &master, at)?, // Same keys, different network
+        bnb: derive_bnb_keys(&secp, &master, at)?,
+        xrp: derive_xrp_keys(&secp, &master, at)?,
         // New chains from wallet-core integration
         ton: derive_ton_keys(seed)?,
         aptos: derive_aptos_keys(seed)?,
@@ -78,15 +156,106 @@ pub fn derive_all_keys(seed: &[u8]) -> HawalaResult<AllKeys> {
     })
 }
 
+/// Derive keys for the given chains only
+///
+/// Each chain fills the [`PartialKeys`] field it has in [`AllKeys`]; devnet
+/// and testnet variants of Solana and XRP map to `solana`/`xrp`. Chains
+/// without their own key section (EVM L2s, Cosmos zones other than the Hub)
+/// are rejected.
+///
+/// SECURITY: The seed should be wrapped in Zeroizing by the caller
+pub fn derive_selected_keys(seed: &[u8], network: KeyNetwork, chains: &[Chain]) -> HawalaResult<PartialKeys> {
+    let secp = Secp256k1::new();
+    let master = Xpriv::new_master(Network::Bitcoin, seed)?;
+    let at = AccountIndex { account: 0, index: 0 };
+    let (bitcoin_network, monero_network) = match network {
+        KeyNetwork::Mainnet => (Network::Bitcoin, MoneroNetwork::Mainnet),
+        KeyNetwork::Testnet => (Network::Testnet, MoneroNetwork::Testnet),
+    };
+
+    let mut keys = PartialKeys::default();
+    for chain in chains {
+        match chain {
+            Chain::Bitcoin => keys.bitcoin = Some(derive_bitcoin_keys(&secp, &master, bitcoin_network, at)?),
+            Chain::BitcoinTestnet => {
+                keys.bitcoin_testnet = Some(derive_bitcoin_keys(&secp, &master, Network::Testnet, at)?)
+            }
+            Chain::Litecoin => keys.litecoin = Some(derive_litecoin_keys(&secp, &master, network, at)?),
+            Chain::Monero => keys.monero = Some(derive_monero_keys(seed, monero_network)?),
+            Chain::Solana | Chain::SolanaDevnet => keys.solana = Some(derive_solana_keys(seed)?),
+            Chain::Ethereum => keys.ethereum = Some(derive_ethereum_keys(&secp, &master, at)?),
+            Chain::EthereumSepolia => keys.ethereum_sepolia = Some(derive_ethereum_keys(&secp, &master, at)?),
+            Chain::Bnb => keys.bnb = Some(derive_bnb_keys(&secp, &master, at)?),
+            Chain::Xrp | Chain::XrpTestnet => keys.xrp = Some(derive_xrp_keys(&secp, &master, at)?),
+            Chain::Ton => keys.ton = Some(derive_ton_keys(seed)?),
+            Chain::Aptos => keys.aptos = Some(derive_aptos_keys(seed)?),
+            Chain::Sui => keys.sui = Some(derive_sui_keys(seed)?),
+            Chain::Polkadot => keys.polkadot = Some(derive_polkadot_keys(seed)?),
+            Chain::Dogecoin => keys.dogecoin = Some(derive_dogecoin_keys_wrapper(seed)?),
+            Chain::BitcoinCash => keys.bitcoin_cash = Some(derive_bitcoin_cash_keys_wrapper(seed)?),
+            Chain::Cosmos => keys.cosmos = Some(derive_cosmos_keys_wrapper(seed)?),
+            Chain::Cardano => keys.cardano = Some(derive_cardano_keys_wrapper(seed)?),
+            Chain::Tron => keys.tron = Some(derive_tron_keys_wrapper(seed)?),
+            Chain::Algorand => keys.algorand = Some(derive_algorand_keys_wrapper(seed)?),
+            Chain::Stellar => keys.stellar = Some(derive_stellar_keys_wrapper(seed)?),
+            Chain::Near => keys.near = Some(derive_near_keys_wrapper(seed)?),
+            Chain::Tezos => keys.tezos = Some(derive_tezos_keys_wrapper(seed)?),
+            Chain::Hedera => keys.hedera = Some(derive_hedera_keys_wrapper(seed)?),
+            Chain::Zcash => keys.zcash = Some(derive_zcash_keys_wrapper(seed)?),
+            Chain::Dash => keys.dash = Some(derive_dash_keys_wrapper(seed)?),
+            Chain::Ravencoin => keys.ravencoin = Some(derive_ravencoin_keys_wrapper(seed)?),
+            Chain::Vechain => keys.vechain = Some(derive_vechain_keys_wrapper(seed)?),
+            Chain::Filecoin => keys.filecoin = Some(derive_filecoin_keys_wrapper(seed)?),
+            Chain::Harmony => keys.harmony = Some(derive_harmony_keys_wrapper(seed)?),
+            Chain::Oasis => keys.oasis = Some(derive_oasis_keys_wrapper(seed)?),
+            Chain::InternetComputer => keys.internet_computer = Some(derive_icp_keys_wrapper(seed)?),
+            Chain::Waves => keys.waves = Some(derive_waves_keys_wrapper(seed)?),
+            Chain::MultiversX => keys.multiversx = Some(derive_multiversx_keys_wrapper(seed)?),
+            Chain::Flow => keys.flow = Some(derive_flow_keys_wrapper(seed)?),
+            Chain::Mina => keys.mina = Some(derive_mina_keys_wrapper(seed)?),
+            Chain::Zilliqa => keys.zilliqa = Some(derive_zilliqa_keys_wrapper(seed)?),
+            Chain::Eos => keys.eos = Some(derive_eos_keys_wrapper(seed)?),
+            Chain::Neo => keys.neo = Some(derive_neo_keys_wrapper(seed)?),
+            Chain::Nervos => keys.nervos = Some(derive_nervos_keys_wrapper(seed)?),
+            other => {
+                return Err(HawalaError::invalid_input(format!(
+                    "{:?} has no separate key set; derive its parent chain instead",
+                    other
+                )))
+            }
+        }
+    }
+    Ok(keys)
+}
+
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// BIP-44 account and external address index
+#[derive(Debug, Clone, Copy)]
+struct AccountIndex {
+    account: u32,
+    index: u32,
+}
+
+impl AccountIndex {
+    /// Build `m/purpose'/coin_type'/account'/0/index`
+    fn path(&self, purpose: u32, coin_type: u32) -> HawalaResult<DerivationPath> {
+        Ok(DerivationPath::from_str(&format!(
+            "m/{}'/{}'/{}'/0/{}",
+            purpose, coin_type, self.account, self.index
+        ))?)
+    }
+}
+
 fn derive_bitcoin_keys(
     secp: &Secp256k1<bitcoin::secp256k1::All>,
     master: &Xpriv,
     network: Network,
+    at: AccountIndex,
 ) -> HawalaResult<BitcoinKeys> {
     let path = match network {
-        Network::Bitcoin => DerivationPath::from_str("m/84'/0'/0'/0/0")?,
-        Network::Testnet => DerivationPath::from_str("m/84'/1'/0'/0/0")?,
-        _ => DerivationPath::from_str("m/84'/0'/0'/0/0")?,
+        Network::Testnet => at.path(84, 1)?,
+        _ => at.path(84, 0)?,
     };
     
     let child = master.derive_priv(secp, &path)?;
@@ -119,8 +288,13 @@ fn derive_bitcoin_keys(
 fn derive_litecoin_keys(
     secp: &Secp256k1<bitcoin::secp256k1::All>,
     master: &Xpriv,
+    network: KeyNetwork,
+    at: AccountIndex,
 ) -> HawalaResult<LitecoinKeys> {
-    let path = DerivationPath::from_str("m/84'/2'/0'/0/0")?;
+    let path = match network {
+        KeyNetwork::Mainnet => at.path(84, 2)?,
+        KeyNetwork::Testnet => at.path(84, 1)?,
+    };
     let child = master.derive_priv(secp, &path)?;
     let secret_key = child.private_key;
 
@@ -131,10 +305,25 @@ fn derive_litecoin_keys(
         .map_err(|e| HawalaError::crypto_error(format!("Key compression failed: {}", e)))?;
     let compressed_bytes = compressed.to_bytes();
 
-    let private_wif = encode_litecoin_wif(&secret_key);
-    let pubkey_hash = hash160::Hash::hash(&compressed_bytes);
+    // Litecoin testnet shares Bitcoin testnet's WIF prefix (0xef)
+    let (private_wif, hrp) = match network {
+        KeyNetwork::Mainnet => (encode_litecoin_wif(&secret_key), "ltc"),
+        KeyNetwork::Testnet => (PrivateKey::new(secret_key, Network::Testnet).to_wif(), "tltc"),
+    };
+    let address = litecoin_p2wpkh_address(&compressed_bytes, hrp)?;
+
+    Ok(LitecoinKeys {
+        private_hex,
+        private_wif,
+        public_compressed_hex: hex::encode(compressed_bytes),
+        address,
+    })
+}
+
+/// Bech32 P2WPKH address with a Litecoin prefix (`ltc` or `tltc`)
+pub(super) fn litecoin_p2wpkh_address(compressed_pubkey: &[u8], hrp: &str) -> HawalaResult<String> {
+    let pubkey_hash = hash160::Hash::hash(compressed_pubkey);
 
-    // Bech32 encode for Litecoin
     let version = bech32::u5::try_from_u8(0)
         .map_err(|e| HawalaError::crypto_error(format!("Bech32 error: {}", e)))?;
     let converted = bech32::convert_bits(pubkey_hash.as_ref(), 8, 5, true)
@@ -146,18 +335,11 @@ fn derive_litecoin_keys(
             .map_err(|e| HawalaError::crypto_error(format!("Bech32 error: {}", e)))?;
         bech32_data.push(u5);
     }
-    let address = bech32::encode("ltc", bech32_data, Variant::Bech32)
-        .map_err(|e| HawalaError::crypto_error(format!("Bech32 error: {}", e)))?;
-
-    Ok(LitecoinKeys {
-        private_hex,
-        private_wif,
-        public_compressed_hex: hex::encode(compressed_bytes),
-        address,
-    })
+    bech32::encode(hrp, bech32_data, Variant::Bech32)
+        .map_err(|e| HawalaError::crypto_error(format!("Bech32 error: {}", e)))
 }
 
-fn derive_monero_keys(seed: &[u8]) -> HawalaResult<MoneroKeys> {
+fn derive_monero_keys(seed: &[u8], network: MoneroNetwork) -> HawalaResult<MoneroKeys> {
     let mut hasher = Sha256::new();
     hasher.update(seed);
     hasher.update(b"MONERO_DERIVATION");
@@ -182,7 +364,7 @@ fn derive_monero_keys(seed: &[u8]) -> HawalaResult<MoneroKeys> {
         .map_err(|e| HawalaError::crypto_error(format!("Monero key error: {}", e)))?;
     let public_view_key = MoneroPublicKey::from_slice(&public_view_bytes)
         .map_err(|e| HawalaError::crypto_error(format!("Monero key error: {}", e)))?;
-    let address = MoneroAddress::standard(MoneroNetwork::Mainnet, public_spend_key, public_view_key);
+    let address = MoneroAddress::standard(network, public_spend_key, public_view_key);
 
     Ok(MoneroKeys {
         private_spend_hex: hex::encode(private_spend),
@@ -214,11 +396,28 @@ fn derive_solana_keys(seed: &[u8]) -> HawalaResult<SolanaKeys> {
     })
 }
 
+/// Solana keys at the per-account path `m/44'/501'/slot'/0'`
+fn derive_solana_account_keys(seed: &[u8], slot: u32) -> HawalaResult<SolanaKeys> {
+    let path = derivation_path::DerivationPath::parse(&format!("m/44'/501'/{}'/0'", slot))?;
+    let (private_seed, public_key, _) = derive_ed25519_key(seed, &path)?;
+
+    let mut keypair_bytes = [0u8; 64];
+    keypair_bytes[..32].copy_from_slice(&private_seed);
+    keypair_bytes[32..].copy_from_slice(&public_key);
+
+    Ok(SolanaKeys {
+        private_seed_hex: hex::encode(private_seed),
+        private_key_base58: bs58::encode(keypair_bytes).into_string(),
+        public_key_base58: bs58::encode(public_key).into_string(),
+    })
+}
+
 fn derive_ethereum_keys(
     secp: &Secp256k1<bitcoin::secp256k1::All>,
     master: &Xpriv,
+    at: AccountIndex,
 ) -> HawalaResult<EthereumKeys> {
-    let path = DerivationPath::from_str("m/44'/60'/0'/0/0")?;
+    let path = at.path(44, 60)?;
     let child = master.derive_priv(secp, &path)?;
     let secret_key = child.private_key;
 
@@ -241,9 +440,10 @@ fn derive_ethereum_keys(
 fn derive_bnb_keys(
     secp: &Secp256k1<bitcoin::secp256k1::All>,
     master: &Xpriv,
+    at: AccountIndex,
 ) -> HawalaResult<EvmKeys> {
     // BNB uses same derivation as Ethereum
-    let path = DerivationPath::from_str("m/44'/60'/0'/0/0")?;
+    let path = at.path(44, 60)?;
     let child = master.derive_priv(secp, &path)?;
     let secret_key = child.private_key;
 
@@ -266,8 +466,9 @@ fn derive_bnb_keys(
 fn derive_xrp_keys(
     secp: &Secp256k1<bitcoin::secp256k1::All>,
     master: &Xpriv,
+    at: AccountIndex,
 ) -> HawalaResult<XrpKeys> {
-    let path = DerivationPath::from_str("m/44'/144'/0'/0/0")?;
+    let path = at.path(44, 144)?;
     let child = master.derive_priv(secp, &path)?;
     let secret_key = child.private_key;
 
@@ -294,6 +495,353 @@ fn derive_xrp_keys(
     })
 }
 
+// =============================================================================
+// Address Discovery (gap-limit scanning)
+// =============================================================================
+
+/// Default BIP-44 gap limit
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// Key source for address discovery
+#[derive(Debug, Clone, Copy)]
+pub enum ScanSource<'a> {
+    /// BIP-39 seed; account 0 is scanned
+    Seed(&'a [u8]),
+    /// Account-level extended public key (`xpub`/`tpub` at m/purpose'/coin'/account')
+    Xpub(&'a str),
+}
+
+/// A discovered address and where it sits in the account
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ScannedAddress {
+    pub address: String,
+    /// Path relative to the account key, e.g. "0/3" or "1/0"
+    pub path: String,
+    pub change: bool,
+    pub index: u32,
+}
+
+/// Result of gap-limit scanning
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct AddressScanResult {
+    /// Used addresses in derivation order (receive chain first, then change)
+    pub used: Vec<ScannedAddress>,
+    /// First receive index after the last used one
+    pub next_receive_index: u32,
+    /// First change index after the last used one (always 0 for account-based chains)
+    pub next_change_index: u32,
+}
+
+/// Discover used addresses by deriving until `gap_limit` consecutive unused ones
+///
+/// Scans the external (`0/i`) chain and, for UTXO chains, the internal change
+/// (`1/i`) chain. Supported chains: Bitcoin, Bitcoin testnet, Litecoin (BIP-84)
+/// and EVM chains (BIP-44, receive chain only).
+pub fn scan_addresses(
+    source: ScanSource<'_>,
+    chain: Chain,
+    gap_limit: u32,
+    check_used: impl Fn(&str) -> bool,
+) -> HawalaResult<AddressScanResult> {
+    if gap_limit == 0 {
+        return Err(HawalaError::invalid_input("Gap limit must be at least 1"));
+    }
+    if gap_limit > MAX_DERIVED_ADDRESSES {
+        return Err(HawalaError::invalid_input(format!(
+            "Gap limit must be at most {}",
+            MAX_DERIVED_ADDRESSES
+        )));
+    }
+
+    let secp = Secp256k1::new();
+    let account_xpub = scan_account_xpub(&secp, source, chain)?;
+
+    let mut result = AddressScanResult::default();
+    let branches: &[bool] = if chain.is_utxo() { &[false, true] } else { &[false] };
+
+    for &change in branches {
+        let branch = account_xpub.derive_pub(&secp, &[ChildNumber::from_normal_idx(change as u32)?])?;
+        let mut next_unused = 0u32;
+        let mut gap = 0u32;
+        let mut index = 0u32;
+
+        while gap < gap_limit && index < HARDENED_OFFSET {
+            let child = branch.derive_pub(&secp, &[ChildNumber::from_normal_idx(index)?])?;
+            let address = scan_address_for(chain, &child.public_key)?;
+
+            if check_used(&address) {
+                result.used.push(ScannedAddress {
+                    address,
+                    path: format!("{}/{}", change as u32, index),
+                    change,
+                    index,
+                });
+                next_unused = index + 1;
+                gap = 0;
+            } else {
+                gap += 1;
+            }
+            index += 1;
+        }
+
+        if change {
+            result.next_change_index = next_unused;
+        } else {
+            result.next_receive_index = next_unused;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Derive the change address `m/purpose'/coin'/account'/1/index` for a UTXO chain
+pub fn derive_change_address(
+    source: ScanSource<'_>,
+    chain: Chain,
+    index: u32,
+) -> HawalaResult<ChangeOutput> {
+    if !chain.is_utxo() {
+        return Err(HawalaError::invalid_input(format!(
+            "{:?} does not use change addresses",
+            chain
+        )));
+    }
+
+    let secp = Secp256k1::new();
+    let (purpose, coin_type) = scan_purpose_and_coin(chain)?;
+    let account_xpub = scan_account_xpub(&secp, source, chain)?;
+
+    let child = account_xpub.derive_pub(
+        &secp,
+        &[ChildNumber::from_normal_idx(1)?, ChildNumber::from_normal_idx(index)?],
+    )?;
+    let account = match account_xpub.child_number {
+        ChildNumber::Hardened { index } => index,
+        ChildNumber::Normal { index } => index,
+    };
+
+    Ok(ChangeOutput {
+        address: scan_address_for(chain, &child.public_key)?,
+        path: format!("m/{}'/{}'/{}'/1/{}", purpose, coin_type, account, index),
+    })
+}
+
+/// Maximum addresses [`derive_addresses`] returns per call, and the largest
+/// gap limit [`scan_addresses`] accepts
+pub const MAX_DERIVED_ADDRESSES: u32 = 1000;
+
+/// An address derived by [`derive_addresses`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DerivedAddress {
+    pub index: u32,
+    pub address: String,
+    /// Full path, e.g. "m/84'/0'/0'/0/3"
+    pub path: String,
+    /// Public key, hex: compressed secp256k1, or the 32-byte ed25519 key
+    pub public_key: String,
+}
+
+/// Derive `count` receive addresses (`.../0/index`) of a BIP-44 account,
+/// starting at `start`
+///
+/// The master and account keys are derived once; each address costs one
+/// unhardened step. Supports the same chains as [`scan_addresses`], plus
+/// Solana: ed25519 (SLIP-0010) has only hardened derivation, and Solana
+/// wallets give each address its own account (`m/44'/501'/index'/0'`, as
+/// Phantom and Solflare do), so there `account` must be 0.
+pub fn derive_addresses(
+    seed: &[u8],
+    chain: Chain,
+    account: u32,
+    start: u32,
+    count: u32,
+) -> HawalaResult<Vec<DerivedAddress>> {
+    if count > MAX_DERIVED_ADDRESSES {
+        return Err(HawalaError::invalid_input(format!(
+            "At most {} addresses can be derived at once",
+            MAX_DERIVED_ADDRESSES
+        )));
+    }
+    let end = start
+        .checked_add(count)
+        .filter(|end| *end <= HARDENED_OFFSET)
+        .ok_or_else(|| HawalaError::invalid_input(format!("Address index must be below {}", HARDENED_OFFSET)))?;
+
+    if matches!(chain, Chain::Solana | Chain::SolanaDevnet) {
+        return derive_solana_addresses(seed, account, start, end);
+    }
+
+    let (purpose, coin_type) = scan_purpose_and_coin(chain)?;
+    let secp = Secp256k1::new();
+    let master = Xpriv::new_master(Network::Bitcoin, seed)?;
+    let account_path = [
+        ChildNumber::from_hardened_idx(purpose)?,
+        ChildNumber::from_hardened_idx(coin_type)?,
+        ChildNumber::from_hardened_idx(account)?,
+    ];
+    let account_xpub = Xpub::from_priv(&secp, &master.derive_priv(&secp, &account_path)?);
+    let receive = account_xpub.derive_pub(&secp, &[ChildNumber::from_normal_idx(0)?])?;
+
+    (start..end)
+        .map(|index| {
+            let child = receive.derive_pub(&secp, &[ChildNumber::from_normal_idx(index)?])?;
+            Ok(DerivedAddress {
+                index,
+                address: scan_address_for(chain, &child.public_key)?,
+                path: format!("m/{}'/{}'/{}'/0/{}", purpose, coin_type, account, index),
+                public_key: hex::encode(child.public_key.serialize()),
+            })
+        })
+        .collect()
+}
+
+// Solana addresses at `m/44'/501'/index'/0'`; the SLIP-0010 master and the
+// `m/44'/501'` prefix are derived once, then two hardened steps per index
+fn derive_solana_addresses(seed: &[u8], account: u32, start: u32, end: u32) -> HawalaResult<Vec<DerivedAddress>> {
+    if account != 0 {
+        return Err(HawalaError::invalid_input(
+            "Solana addresses are derived per account (m/44'/501'/index'/0'); account must be 0",
+        ));
+    }
+
+    let (coin_key, _, coin_chain_code) = derive_ed25519_key(seed, &derivation_path::DerivationPath::parse("m/44'/501'")?)?;
+    (start..end)
+        .map(|index| {
+            let (key, chain_code) = Ed25519Curve::derive_child(&coin_key, &coin_chain_code, index, true)?;
+            let (key, _) = Ed25519Curve::derive_child(&key, &chain_code, 0, true)?;
+            let public_key = SigningKey::from_bytes(&key).verifying_key().to_bytes();
+            Ok(DerivedAddress {
+                index,
+                address: bs58::encode(public_key).into_string(),
+                path: format!("m/44'/501'/{}'/0'", index),
+                public_key: hex::encode(public_key),
+            })
+        })
+        .collect()
+}
+
+/// Re-derive the address at `path` and check it against `claimed_address`
+///
+/// For a seed, `path` is absolute (`m/84'/0'/0'/0/5`); for an account xpub it
+/// is relative to the account and must be unhardened (`0/5`). Addresses are
+/// compared case-insensitively after trimming, in constant time. Supports the
+/// same chains as [`scan_addresses`].
+pub fn verify_address(
+    source: ScanSource<'_>,
+    path: &str,
+    chain: Chain,
+    claimed_address: &str,
+) -> HawalaResult<bool> {
+    use subtle::ConstantTimeEq;
+
+    scan_purpose_and_coin(chain)?;
+    let path = match source {
+        ScanSource::Seed(_) => derivation_path::DerivationPath::parse(path)?,
+        ScanSource::Xpub(_) => derivation_path::DerivationPath::parse_relative(path)?,
+    };
+    let children: Vec<ChildNumber> = path.indices().into_iter().map(ChildNumber::from).collect();
+    let secp = Secp256k1::new();
+
+    let public_key = match source {
+        ScanSource::Seed(seed) => {
+            let master = Xpriv::new_master(Network::Bitcoin, seed)?;
+            Xpub::from_priv(&secp, &master.derive_priv(&secp, &children)?).public_key
+        }
+        ScanSource::Xpub(xpub) => {
+            if children.iter().any(ChildNumber::is_hardened) {
+                return Err(HawalaError::invalid_input(
+                    "Hardened steps cannot be derived from an extended public key",
+                ));
+            }
+            let account = Xpub::from_str(xpub.trim())
+                .map_err(|e| HawalaError::invalid_input(format!("Invalid extended public key: {}", e)))?;
+            account.derive_pub(&secp, &children)?.public_key
+        }
+    };
+
+    let derived = scan_address_for(chain, &public_key)?.to_ascii_lowercase();
+    let claimed = claimed_address.trim().to_ascii_lowercase();
+    Ok(derived.as_bytes().ct_eq(claimed.as_bytes()).into())
+}
+
+/// Master key fingerprint and `(path, xpub)` pairs
+pub type AccountXpubs = ([u8; 4], Vec<(String, String)>);
+
+/// Master key fingerprint and the `(path, xpub)` of each account in `paths`
+///
+/// Used to hand account public keys to watch-only software; `paths` are
+/// absolute (`m/84'/0'/0'`).
+pub fn account_xpubs(seed: &[u8], paths: &[String]) -> HawalaResult<AccountXpubs> {
+    let secp = Secp256k1::new();
+    let master = Xpriv::new_master(Network::Bitcoin, seed)?;
+
+    let fingerprint = master.fingerprint(&secp).to_bytes();
+
+    let accounts = paths
+        .iter()
+        .map(|path| {
+            let children: Vec<ChildNumber> = derivation_path::DerivationPath::parse(path)?
+                .indices()
+                .into_iter()
+                .map(ChildNumber::from)
+                .collect();
+            let account = Xpub::from_priv(&secp, &master.derive_priv(&secp, &children)?);
+            Ok((path.trim().to_string(), account.to_string()))
+        })
+        .collect::<HawalaResult<Vec<_>>>()?;
+
+    Ok((fingerprint, accounts))
+}
+
+fn scan_account_xpub(
+    secp: &Secp256k1<bitcoin::secp256k1::All>,
+    source: ScanSource<'_>,
+    chain: Chain,
+) -> HawalaResult<Xpub> {
+    match source {
+        ScanSource::Seed(seed) => {
+            let (purpose, coin_type) = scan_purpose_and_coin(chain)?;
+            let master = Xpriv::new_master(Network::Bitcoin, seed)?;
+            let path = DerivationPath::from_str(&format!("m/{}'/{}'/0'", purpose, coin_type))?;
+            Ok(Xpub::from_priv(secp, &master.derive_priv(secp, &path)?))
+        }
+        ScanSource::Xpub(xpub) => {
+            scan_purpose_and_coin(chain)?;
+            Xpub::from_str(xpub.trim())
+                .map_err(|e| HawalaError::invalid_input(format!("Invalid extended public key: {}", e)))
+        }
+    }
+}
+
+fn scan_purpose_and_coin(chain: Chain) -> HawalaResult<(u32, u32)> {
+    match chain {
+        Chain::Bitcoin => Ok((84, 0)),
+        Chain::BitcoinTestnet => Ok((84, 1)),
+        Chain::Litecoin => Ok((84, 2)),
+        c if c.is_evm() => Ok((44, 60)),
+        other => Err(HawalaError::not_implemented(format!(
+            "Address scanning not supported for {:?}",
+            other
+        ))),
+    }
+}
+
+fn scan_address_for(chain: Chain, public_key: &bitcoin::secp256k1::PublicKey) -> HawalaResult<String> {
+    match chain {
+        Chain::Bitcoin | Chain::BitcoinTestnet => {
+            let network = if chain == Chain::Bitcoin { Network::Bitcoin } else { Network::Testnet };
+            let compressed = CompressedPublicKey(*public_key);
+            Ok(Address::p2wpkh(&compressed, network).to_string())
+        }
+        Chain::Litecoin => litecoin_p2wpkh_address(&public_key.serialize(), "ltc"),
+        _ => {
+            let uncompressed = public_key.serialize_uncompressed();
+            let hash = keccak256(&uncompressed[1..]);
+            Ok(to_checksum_address(&hash[12..]))
+        }
+    }
+}
+
 // Helper functions
 
 fn keccak256(data: &[u8]) -> [u8; 32] {
@@ -369,11 +917,7 @@ fn derive_aptos_keys(seed: &[u8]) -> HawalaResult<AptosKeys> {
     
     let keypair = AptosKeyPair::from_mnemonic_seed(&seed64, 0)?;
     
-    Ok(AptosKeys {
-        private_hex: hex::encode(keypair.signing_key.to_bytes()),
-        public_hex: hex::encode(keypair.public_key),
-        address: keypair.address.to_hex(),
-    })
+    Ok(crate::aptos_wallet::generate_aptos_keys(&keypair.signing_key))
 }
 
 fn derive_sui_keys(seed: &[u8]) -> HawalaResult<SuiKeys> {
@@ -385,11 +929,7 @@ fn derive_sui_keys(seed: &[u8]) -> HawalaResult<SuiKeys> {
     
     let keypair = SuiKeyPair::from_mnemonic_seed(&seed64, 0)?;
     
-    Ok(SuiKeys {
-        private_hex: hex::encode(keypair.signing_key.to_bytes()),
-        public_hex: hex::encode(keypair.public_key),
-        address: keypair.address.to_hex(),
-    })
+    Ok(crate::sui_wallet::generate_sui_keys(&keypair.signing_key))
 }
 
 fn derive_polkadot_keys(seed: &[u8]) -> HawalaResult<PolkadotKeys> {
@@ -478,6 +1018,9 @@ fn derive_cardano_keys_wrapper(seed: &[u8]) -> HawalaResult<CardanoKeys> {
         private_hex: ada.private_hex,
         public_hex: ada.public_hex,
         address: ada.address,
+        stake_public_hex: ada.stake_public_hex,
+        enterprise_address: ada.enterprise_address,
+        stake_address: ada.stake_address,
     })
 }
 
@@ -614,4 +1157,383 @@ fn derive_neo_keys_wrapper(seed: &[u8]) -> HawalaResult<NeoKeys> {
 
 fn derive_nervos_keys_wrapper(seed: &[u8]) -> HawalaResult<NervosKeys> {
     crate::nervos_wallet::derive_nervos_keys(seed)
-}
\ No newline at end of file
+}
+
+// =============================================================================
+// SLIP-0010 ed25519
+// =============================================================================
+
+/// `(private_key, public_key, chain_code)`
+pub type Ed25519Key = ([u8; 32], [u8; 32], [u8; 32]);
+
+/// SLIP-0010 ed25519 key at `path`: `(private_key, public_key, chain_code)`
+///
+/// ed25519 has no public derivation, so a non-hardened component is an error
+/// instead of being hardened behind the caller's back.
+pub fn derive_ed25519_key(
+    seed: &[u8],
+    path: &derivation_path::DerivationPath,
+) -> Result<Ed25519Key, CurveError> {
+    use hmac::{Hmac, Mac};
+
+    if path.components.iter().any(|c| !c.hardened) {
+        return Err(CurveError::DerivationFailed("ed25519 requires hardened path".into()));
+    }
+
+    let mut mac = Hmac::<sha2::Sha512>::new_from_slice(b"ed25519 seed")
+        .map_err(|e| CurveError::DerivationFailed(e.to_string()))?;
+    mac.update(seed);
+    let master = mac.finalize().into_bytes();
+
+    let mut key = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    key.copy_from_slice(&master[..32]);
+    chain_code.copy_from_slice(&master[32..]);
+
+    for component in &path.components {
+        (key, chain_code) = Ed25519Curve::derive_child(&key, &chain_code, component.index, true)?;
+    }
+
+    let public_key = SigningKey::from_bytes(&key).verifying_key().to_bytes();
+    Ok((key, public_key, chain_code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bip39::Mnemonic;
+
+    #[test]
+    fn test_derive_ed25519_key_hardened_path() {
+        // SLIP-0010 test vector 1 for ed25519, chain m/0H
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let path = derivation_path::DerivationPath::parse("m/0'").unwrap();
+        let (private_key, public_key, chain_code) = derive_ed25519_key(&seed, &path).unwrap();
+        assert_eq!(hex::encode(private_key), "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3");
+        assert_eq!(hex::encode(public_key), "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c");
+        assert_eq!(hex::encode(chain_code), "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69");
+
+        let solana = derivation_path::DerivationPath::parse("m/44'/501'/0'/0'").unwrap();
+        assert!(derive_ed25519_key(&seed, &solana).is_ok());
+    }
+
+    #[test]
+    fn test_derive_ed25519_key_rejects_unhardened() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let path = derivation_path::DerivationPath::parse("m/44'/501'/0'/0").unwrap();
+        match derive_ed25519_key(&seed, &path) {
+            Err(CurveError::DerivationFailed(msg)) => assert_eq!(msg, "ed25519 requires hardened path"),
+            other => panic!("expected a derivation error, got {:?}", other),
+        }
+    }
+
+    fn abandon_seed() -> [u8; 64] {
+        Mnemonic::parse(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .unwrap()
+        .to_seed("")
+    }
+
+    #[test]
+    fn test_verify_address_matches() {
+        let seed = abandon_seed();
+        let btc = "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g";
+        assert!(verify_address(ScanSource::Seed(&seed), "m/84'/0'/0'/0/1", Chain::Bitcoin, btc).unwrap());
+        assert!(verify_address(ScanSource::Seed(&seed), "m/84'/0'/0'/0/1", Chain::Bitcoin, &format!(" {} ", btc.to_uppercase())).unwrap());
+        assert!(verify_address(
+            ScanSource::Seed(&seed),
+            "m/44'/60'/0'/0/0",
+            Chain::Ethereum,
+            "0x9858effd232b4033e47d90003d41ec34ecaeda94"
+        )
+        .unwrap());
+
+        // Account xpub with a relative path gives the same answer
+        let secp = Secp256k1::new();
+        let xpub = scan_account_xpub(&secp, ScanSource::Seed(&seed), Chain::Bitcoin).unwrap().to_string();
+        assert!(verify_address(ScanSource::Xpub(&xpub), "0/1", Chain::Bitcoin, btc).unwrap());
+    }
+
+    #[test]
+    fn test_verify_address_mismatch() {
+        let seed = abandon_seed();
+        // Index 0 address checked against index 1
+        assert!(!verify_address(
+            ScanSource::Seed(&seed),
+            "m/84'/0'/0'/0/1",
+            Chain::Bitcoin,
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+        )
+        .unwrap());
+        assert!(!verify_address(
+            ScanSource::Seed(&seed),
+            "m/44'/60'/0'/0/0",
+            Chain::Ethereum,
+            "0x6Fac4D18c912343BF86fa7049364Dd4E424Ab9C0"
+        )
+        .unwrap());
+
+        let secp = Secp256k1::new();
+        let xpub = scan_account_xpub(&secp, ScanSource::Seed(&seed), Chain::Bitcoin).unwrap().to_string();
+        assert!(verify_address(ScanSource::Xpub(&xpub), "0'/1", Chain::Bitcoin, "bc1q").is_err());
+        assert!(verify_address(ScanSource::Seed(&seed), "m/84'/x", Chain::Bitcoin, "bc1q").is_err());
+        assert!(verify_address(ScanSource::Seed(&seed), "m/0", Chain::Solana, "abc").is_err());
+    }
+
+    #[test]
+    fn test_account_xpubs() {
+        let seed = abandon_seed();
+        let (fingerprint, accounts) = account_xpubs(&seed, &["m/84'/0'/0'".to_string()]).unwrap();
+        assert_eq!(hex::encode(fingerprint), "73c5da0a");
+
+        let secp = Secp256k1::new();
+        let xpub = scan_account_xpub(&secp, ScanSource::Seed(&seed), Chain::Bitcoin).unwrap().to_string();
+        assert_eq!(accounts, vec![("m/84'/0'/0'".to_string(), xpub)]);
+
+        assert!(account_xpubs(&seed, &["m/84'/x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_index_zero_matches_default() {
+        let seed = abandon_seed();
+        let default = derive_all_keys(&seed).unwrap();
+        let at_zero = derive_all_keys_at(&seed, 0, 0).unwrap();
+
+        assert_eq!(default.bitcoin.address, at_zero.bitcoin.address);
+        assert_eq!(default.litecoin.address, at_zero.litecoin.address);
+        assert_eq!(default.ethereum.address, at_zero.ethereum.address);
+        assert_eq!(default.xrp.classic_address, at_zero.xrp.classic_address);
+        assert_eq!(default.bitcoin.address, "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu");
+        assert_eq!(default.ethereum.address, "0x9858EfFD232B4033E47d90003D41EC34EcaEda94");
+    }
+
+    #[test]
+    fn test_index_one_matches_reference() {
+        let seed = abandon_seed();
+        let keys = derive_all_keys_at(&seed, 0, 1).unwrap();
+
+        // BIP-84 test vector m/84'/0'/0'/0/1
+        assert_eq!(keys.bitcoin.address, "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g");
+        // m/44'/60'/0'/0/1
+        assert_eq!(keys.ethereum.address, "0x6Fac4D18c912343BF86fa7049364Dd4E424Ab9C0");
+        assert_eq!(keys.bnb.address, keys.ethereum.address);
+    }
+
+    #[test]
+    fn test_account_one_differs() {
+        let seed = abandon_seed();
+        let keys = derive_all_keys_at(&seed, 1, 0).unwrap();
+
+        assert_eq!(keys.bitcoin.address, "bc1qku0qh0mc00y8tk0n65x2tqw4trlspak0fnjmfz");
+        assert_eq!(keys.ethereum.address, "0x78839F6054d7ed13918bAe0473BA31b1Ca9D7265");
+    }
+
+    #[test]
+    fn test_non_indexed_chains_unchanged() {
+        let seed = abandon_seed();
+        let default = derive_all_keys(&seed).unwrap();
+        let keys = derive_all_keys_at(&seed, 0, 5).unwrap();
+
+        assert_eq!(default.solana.public_key_base58, keys.solana.public_key_base58);
+        assert_ne!(default.xrp.classic_address, keys.xrp.classic_address);
+        assert_ne!(default.litecoin.address, keys.litecoin.address);
+    }
+
+    #[test]
+    fn test_account_keys_only_derived_chains() {
+        let seed = abandon_seed();
+        let keys = derive_account_keys(&seed, 0, 3).unwrap();
+
+        assert_eq!(keys.bitcoin.unwrap().address, derive_all_keys_at(&seed, 0, 3).unwrap().bitcoin.address);
+        // Same key as the per-account Solana address list
+        let solana = derive_addresses(&seed, Chain::Solana, 0, 3, 1).unwrap();
+        assert_eq!(keys.solana.unwrap().public_key_base58, solana[0].address);
+        assert!(keys.monero.is_none());
+        assert!(keys.cosmos.is_none());
+
+        // Solana's path has no account level
+        let keys = derive_account_keys(&seed, 1, 0).unwrap();
+        assert!(keys.solana.is_none());
+        assert!(keys.ethereum.is_some());
+    }
+
+    #[test]
+    fn test_testnet_keys() {
+        let seed = abandon_seed();
+        let mainnet = derive_all_keys_on(&seed, KeyNetwork::Mainnet).unwrap();
+        let testnet = derive_all_keys_on(&seed, KeyNetwork::Testnet).unwrap();
+
+        assert_eq!(mainnet.bitcoin.address, derive_all_keys(&seed).unwrap().bitcoin.address);
+        assert_eq!(testnet.bitcoin.address, mainnet.bitcoin_testnet.address);
+        assert!(testnet.bitcoin.address.starts_with("tb1q"));
+        assert!(testnet.litecoin.address.starts_with("tltc1q"));
+        assert!(testnet.litecoin.private_wif.starts_with('c'));
+        assert_eq!(testnet.ethereum.address, mainnet.ethereum.address);
+        assert_ne!(testnet.monero.address, mainnet.monero.address);
+        assert_eq!(testnet.monero.public_spend_hex, mainnet.monero.public_spend_hex);
+    }
+
+    #[test]
+    fn test_selected_keys_match_full_derivation() {
+        let seed = abandon_seed();
+        let all = derive_all_keys(&seed).unwrap();
+        let selected = derive_selected_keys(&seed, KeyNetwork::Mainnet, &[Chain::Bitcoin, Chain::Ethereum]).unwrap();
+
+        assert_eq!(selected.bitcoin.unwrap().address, all.bitcoin.address);
+        assert_eq!(selected.ethereum.unwrap().address, all.ethereum.address);
+        assert!(selected.solana.is_none());
+        assert!(selected.monero.is_none());
+
+        let json = serde_json::to_value(derive_selected_keys(&seed, KeyNetwork::Mainnet, &[Chain::Solana]).unwrap()).unwrap();
+        assert_eq!(json.as_object().unwrap().keys().collect::<Vec<_>>(), vec!["solana"]);
+
+        assert!(derive_selected_keys(&seed, KeyNetwork::Mainnet, &[Chain::Arbitrum]).is_err());
+    }
+
+    #[test]
+    fn test_hardened_range_rejected() {
+        let seed = abandon_seed();
+        assert!(derive_all_keys_at(&seed, HARDENED_OFFSET, 0).is_err());
+        assert!(derive_all_keys_at(&seed, 0, HARDENED_OFFSET).is_err());
+    }
+
+    #[test]
+    fn test_scan_stops_at_gap() {
+        let seed = abandon_seed();
+        let receive: Vec<String> = (0..10)
+            .map(|i| derive_all_keys_at(&seed, 0, i).unwrap().bitcoin.address)
+            .collect();
+
+        // Used: 0, 1, 4 -- the 2-3 gap is shorter than the limit, 5-7 ends the scan
+        let used = [receive[0].clone(), receive[1].clone(), receive[4].clone()];
+        let result = scan_addresses(ScanSource::Seed(&seed), Chain::Bitcoin, 3, |a| {
+            used.iter().any(|u| u == a)
+        })
+        .unwrap();
+
+        assert_eq!(result.used.len(), 3);
+        assert_eq!(result.next_receive_index, 5);
+        assert_eq!(result.next_change_index, 0);
+        assert_eq!(result.used[2].path, "0/4");
+    }
+
+    #[test]
+    fn test_scan_misses_beyond_gap() {
+        let seed = abandon_seed();
+        let receive: Vec<String> = (0..8)
+            .map(|i| derive_all_keys_at(&seed, 0, i).unwrap().bitcoin.address)
+            .collect();
+
+        // Gap of 3 (1, 2, 3) hits the limit before index 4 is checked
+        let used = [receive[0].clone(), receive[4].clone()];
+        let result = scan_addresses(ScanSource::Seed(&seed), Chain::Bitcoin, 3, |a| {
+            used.iter().any(|u| u == a)
+        })
+        .unwrap();
+
+        assert_eq!(result.used.len(), 1);
+        assert_eq!(result.next_receive_index, 1);
+    }
+
+    #[test]
+    fn test_scan_finds_change_addresses() {
+        let seed = abandon_seed();
+        // BIP-84 test vector m/84'/0'/0'/1/0
+        let change0 = "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el".to_string();
+        let result = scan_addresses(ScanSource::Seed(&seed), Chain::Bitcoin, 5, |a| a == change0).unwrap();
+
+        assert_eq!(result.used.len(), 1);
+        assert!(result.used[0].change);
+        assert_eq!(result.next_change_index, 1);
+        assert_eq!(result.next_receive_index, 0);
+    }
+
+    #[test]
+    fn test_scan_evm_receive_only() {
+        let seed = abandon_seed();
+        let result = scan_addresses(ScanSource::Seed(&seed), Chain::Ethereum, 2, |a| {
+            a == "0x9858EfFD232B4033E47d90003D41EC34EcaEda94"
+        })
+        .unwrap();
+
+        assert_eq!(result.used.len(), 1);
+        assert_eq!(result.next_receive_index, 1);
+    }
+
+    #[test]
+    fn test_scan_from_xpub_matches_seed() {
+        let seed = abandon_seed();
+        let secp = Secp256k1::new();
+        let master = Xpriv::new_master(Network::Bitcoin, &seed).unwrap();
+        let account = master
+            .derive_priv(&secp, &DerivationPath::from_str("m/84'/0'/0'").unwrap())
+            .unwrap();
+        let xpub = Xpub::from_priv(&secp, &account).to_string();
+
+        let target = "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g";
+        let result = scan_addresses(ScanSource::Xpub(&xpub), Chain::Bitcoin, 20, |a| a == target).unwrap();
+
+        assert_eq!(result.used.len(), 1);
+        assert_eq!(result.used[0].index, 1);
+        assert_eq!(result.next_receive_index, 2);
+    }
+
+    #[test]
+    fn test_scan_rejects_zero_gap_and_unsupported_chain() {
+        let seed = abandon_seed();
+        assert!(scan_addresses(ScanSource::Seed(&seed), Chain::Bitcoin, 0, |_| false).is_err());
+        assert!(scan_addresses(ScanSource::Seed(&seed), Chain::Bitcoin, MAX_DERIVED_ADDRESSES + 1, |_| false).is_err());
+        assert!(scan_addresses(ScanSource::Seed(&seed), Chain::Solana, 20, |_| false).is_err());
+    }
+
+    #[test]
+    fn test_derive_change_address() {
+        let seed = abandon_seed();
+        let change = derive_change_address(ScanSource::Seed(&seed), Chain::Bitcoin, 0).unwrap();
+
+        // BIP-84 test vector m/84'/0'/0'/1/0
+        assert_eq!(change.address, "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el");
+        assert_eq!(change.path, "m/84'/0'/0'/1/0");
+        assert!(derive_change_address(ScanSource::Seed(&seed), Chain::Ethereum, 0).is_err());
+    }
+
+    #[test]
+    fn test_derive_addresses_batch() {
+        let seed = abandon_seed();
+        let addresses = derive_addresses(&seed, Chain::Bitcoin, 0, 0, 20).unwrap();
+        assert_eq!(addresses.len(), 20);
+
+        let single = derive_all_keys_at(&seed, 0, 0).unwrap();
+        assert_eq!(addresses[0].address, single.bitcoin.address);
+        assert_eq!(addresses[0].public_key, single.bitcoin.public_compressed_hex);
+        assert_eq!(addresses[0].path, "m/84'/0'/0'/0/0");
+        assert_eq!(addresses[19].address, derive_all_keys_at(&seed, 0, 19).unwrap().bitcoin.address);
+
+        let evm = derive_addresses(&seed, Chain::Ethereum, 0, 5, 2).unwrap();
+        assert_eq!(evm[0].index, 5);
+        assert_eq!(evm[0].address, derive_all_keys_at(&seed, 0, 5).unwrap().ethereum.address);
+
+        assert!(derive_addresses(&seed, Chain::Bitcoin, 0, HARDENED_OFFSET - 1, 2).is_err());
+        assert!(derive_addresses(&seed, Chain::Bitcoin, 0, 0, MAX_DERIVED_ADDRESSES + 1).is_err());
+    }
+
+    #[test]
+    fn test_derive_solana_addresses() {
+        // Phantom's first accounts for the test mnemonic
+        let seed = abandon_seed();
+        let addresses = derive_addresses(&seed, Chain::Solana, 0, 0, 2).unwrap();
+        assert_eq!(addresses[0].address, "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+        assert_eq!(addresses[0].path, "m/44'/501'/0'/0'");
+        assert_eq!(addresses[0].public_key, "f036276246a75b9de3349ed42b15e232f6518fc20f5fcd4f1d64e81f9bd258f7");
+        assert_eq!(addresses[1].address, "Hh8QwFUA6MtVu1qAoq12ucvFHNwCcVTV7hpWjeY1Hztb");
+        assert_eq!(addresses[1].path, "m/44'/501'/1'/0'");
+
+        let later = derive_addresses(&seed, Chain::SolanaDevnet, 0, 2, 1).unwrap();
+        assert_eq!(later[0].index, 2);
+        assert_eq!(later[0].address, "7WktogJEd2wQ9eH2oWusmcoFTgeYi6rS632UviTBJ2jm");
+
+        assert!(derive_addresses(&seed, Chain::Solana, 1, 0, 1).is_err());
+        assert!(derive_addresses(&seed, Chain::Solana, 0, HARDENED_OFFSET - 1, 2).is_err());
+    }
+}
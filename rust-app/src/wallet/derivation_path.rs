@@ -4,6 +4,10 @@
 //! - Correct format and syntax
 //! - Chain-appropriate paths
 //! - Security warnings for unusual paths
+//!
+//! [`DerivationPath::parse`] is the one path parser; the FFI key derivation,
+//! the curve implementations, address verification and UR account export go
+//! through it.
 
 use crate::error::{HawalaError, HawalaResult};
 use crate::types::Chain;
@@ -31,7 +35,7 @@ pub mod coin_types {
 pub const HARDENED: u32 = 0x80000000;
 
 /// Parsed derivation path
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DerivationPath {
     pub components: Vec<DerivationComponent>,
     pub purpose: Option<u32>,
@@ -42,7 +46,7 @@ pub struct DerivationPath {
 }
 
 /// Single component of a derivation path
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DerivationComponent {
     pub index: u32,
     pub hardened: bool,
@@ -63,6 +67,44 @@ impl DerivationComponent {
     }
 }
 
+impl DerivationPath {
+    /// Parse a path such as `m/44'/0'/0'/0/0`
+    ///
+    /// Each component is a decimal index below 2^31 followed by at most one
+    /// hardened marker (`'`, `h` or `H`). `m` on its own is the master key.
+    pub fn parse(path: &str) -> HawalaResult<Self> {
+        parse_path(path).map_err(|e| {
+            HawalaError::invalid_input(format!("Invalid derivation path '{}': {}", path, e))
+        })
+    }
+    
+    /// Parse a path relative to an extended key, such as `0/5`; a leading
+    /// `m/` is accepted too
+    pub fn parse_relative(path: &str) -> HawalaResult<Self> {
+        let trimmed = path.trim();
+        if trimmed.starts_with(['m', 'M']) {
+            Self::parse(trimmed)
+        } else {
+            parse_path(&format!("m/{}", trimmed)).map_err(|e| {
+                HawalaError::invalid_input(format!("Invalid derivation path '{}': {}", path, e))
+            })
+        }
+    }
+    
+    /// Child indices with the hardened bit applied
+    pub fn indices(&self) -> Vec<u32> {
+        self.components.iter().map(|c| c.full_index()).collect()
+    }
+}
+
+impl std::str::FromStr for DerivationPath {
+    type Err = HawalaError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
 impl std::fmt::Display for DerivationComponent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.hardened {
@@ -205,22 +247,21 @@ pub fn validate_derivation_path(path: &str, chain: Chain) -> PathValidation {
 fn parse_path(path: &str) -> Result<DerivationPath, String> {
     let trimmed = path.trim();
     
-    // Must start with m/
-    if !trimmed.starts_with("m/") && !trimmed.starts_with("M/") {
-        return Err("Derivation path must start with 'm/'".to_string());
-    }
-    
-    let path_part = &trimmed[2..];
-    if path_part.is_empty() {
-        return Err("Empty derivation path".to_string());
-    }
-    
-    let mut components = Vec::new();
-    
-    for component_str in path_part.split('/') {
-        let component = parse_component(component_str)?;
-        components.push(component);
-    }
+    // Must start with m/, or be the master key itself
+    let components = match trimmed.strip_prefix(['m', 'M']) {
+        Some("") => Vec::new(),
+        Some(rest) => {
+            let path_part = rest.strip_prefix('/')
+                .ok_or_else(|| "Derivation path must start with 'm/'".to_string())?;
+            if path_part.is_empty() {
+                return Err("Empty derivation path".to_string());
+            }
+            path_part.split('/')
+                .map(parse_component)
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        None => return Err("Derivation path must start with 'm/'".to_string()),
+    };
     
     // Extract standard components
     let purpose = components.get(0).map(|c| c.index);
@@ -241,28 +282,29 @@ fn parse_path(path: &str) -> Result<DerivationPath, String> {
 
 /// Parse a single path component
 fn parse_component(s: &str) -> Result<DerivationComponent, String> {
-    let trimmed = s.trim();
-    
-    if trimmed.is_empty() {
+    if s.is_empty() {
         return Err("Empty path component".to_string());
     }
     
     // Check for hardened indicator
-    let (number_str, hardened) = if trimmed.ends_with('\'') || trimmed.ends_with('h') || trimmed.ends_with('H') {
-        (&trimmed[..trimmed.len() - 1], true)
-    } else {
-        (trimmed, false)
+    const MARKERS: [char; 3] = ['\'', 'h', 'H'];
+    let (number_str, hardened) = match s.strip_suffix(MARKERS) {
+        Some(number_str) => (number_str, true),
+        None => (s, false),
     };
-    
-    let index: u32 = number_str.parse()
-        .map_err(|e| format!("Invalid path component '{}': {}", s, e))?;
-    
-    // Check for overflow (excluding hardened bit)
-    if index >= HARDENED {
-        return Err(format!("Path component {} exceeds maximum value", index));
+    if number_str.ends_with(MARKERS) {
+        return Err(format!("Path component '{}' has more than one hardened marker", s));
+    }
+    if number_str.is_empty() || !number_str.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("Invalid path component '{}': expected a decimal index", s));
     }
     
-    Ok(DerivationComponent::new(index, hardened))
+    // Hardening is the marker's job, so the index itself stays below 2^31
+    let index = number_str.parse::<u64>().ok()
+        .filter(|index| *index < HARDENED as u64)
+        .ok_or_else(|| format!("Path component '{}' is out of range (maximum index is {})", s, HARDENED - 1))?;
+    
+    Ok(DerivationComponent::new(index as u32, hardened))
 }
 
 /// Get expected coin type for a chain
@@ -424,6 +466,43 @@ mod tests {
         assert!(!result.is_valid);
     }
 
+    #[test]
+    fn test_derivation_path_parse() {
+        let path = DerivationPath::parse("m/44'/0'/0'/0/0").unwrap();
+        assert_eq!(path.components.len(), 5);
+        assert_eq!(path.indices(), vec![44 | HARDENED, HARDENED, HARDENED, 0, 0]);
+        assert_eq!(path.to_string(), "m/44'/0'/0'/0/0");
+        
+        // `h` markers and leading zeros print in canonical form
+        let path: DerivationPath = "m/84h/0H/007'/1/2147483647".parse().unwrap();
+        assert_eq!(path.to_string(), "m/84'/0'/7'/1/2147483647");
+        
+        let master = DerivationPath::parse("m").unwrap();
+        assert!(master.components.is_empty());
+        assert_eq!(master.to_string(), "m");
+    }
+    
+    #[test]
+    fn test_derivation_path_rejects_out_of_range_index() {
+        let err = DerivationPath::parse("m/44'/2147483648/0").unwrap_err();
+        assert!(err.message.contains("'2147483648' is out of range"));
+        
+        // Already-hardened indices can't be smuggled in without the marker
+        assert!(DerivationPath::parse("m/2147483692'").is_err());
+        assert!(DerivationPath::parse("m/99999999999999999999999").is_err());
+    }
+    
+    #[test]
+    fn test_derivation_path_rejects_malformed() {
+        let err = DerivationPath::parse("m/44''/0'/0'").unwrap_err();
+        assert!(err.message.contains("'44''' has more than one hardened marker"));
+        assert!(DerivationPath::parse("m/44'h").is_err());
+        
+        for path in ["", "m/", "m//0", "m/0/", "m/-1", "m/+1", "m/ 1", "m/'", "44'/0'", "m44'"] {
+            assert!(DerivationPath::parse(path).is_err(), "{} should be rejected", path);
+        }
+    }
+
     #[test]
     fn test_path_display() {
         let result = validate_derivation_path("m/84'/0'/0'/0/0", Chain::Bitcoin);
@@ -0,0 +1,351 @@
+//! Output Descriptors
+//!
+//! Parses single-key output descriptors (BIP-380) as exported by Sparrow,
+//! Bitcoin Core and hardware wallets, and derives their addresses:
+//!
+//! - `pkh(KEY)` (BIP-44 legacy)
+//! - `wpkh(KEY)` (BIP-84 native segwit)
+//! - `sh(wpkh(KEY))` (BIP-49 nested segwit)
+//! - `tr(KEY)` (BIP-86 key-path taproot)
+//!
+//! `KEY` is an extended public key with an optional `[fingerprint/path]`
+//! origin and unhardened steps, usually ending in a `/0/*` or `/1/*` range.
+//! A trailing `#checksum` is verified when present.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::bip32::{ChildNumber, Xpub};
+use bitcoin::key::CompressedPublicKey;
+use bitcoin::secp256k1::Secp256k1;
+use bitcoin::{Address, Network, NetworkKind};
+
+use crate::error::{HawalaError, HawalaResult};
+
+/// Characters allowed in a descriptor, in checksum symbol order
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const CHECKSUM_LENGTH: usize = 8;
+
+/// Script template wrapped around the key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DescriptorKind {
+    Pkh,
+    Wpkh,
+    ShWpkh,
+    Tr,
+}
+
+/// `[fingerprint/path]` prefix recording where the key came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyOrigin {
+    pub fingerprint: [u8; 4],
+    pub path: Vec<ChildNumber>,
+}
+
+/// A parsed single-key descriptor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Descriptor {
+    pub kind: DescriptorKind,
+    pub origin: Option<KeyOrigin>,
+    pub xpub: Xpub,
+    /// Unhardened steps below `xpub`, not counting the range
+    pub path: Vec<ChildNumber>,
+    /// Whether the key ends in `/*`
+    pub ranged: bool,
+}
+
+impl Descriptor {
+    /// Network the extended key belongs to (`xpub` mainnet, `tpub` testnet)
+    pub fn network(&self) -> Network {
+        match self.xpub.network {
+            NetworkKind::Main => Network::Bitcoin,
+            NetworkKind::Test => Network::Testnet,
+        }
+    }
+
+    /// Address at `index` of the range (must be 0 for non-ranged descriptors)
+    pub fn address_at(&self, index: u32) -> HawalaResult<Address> {
+        let secp = Secp256k1::new();
+
+        let mut steps = self.path.clone();
+        if self.ranged {
+            steps.push(ChildNumber::from_normal_idx(index)?);
+        } else if index != 0 {
+            return Err(HawalaError::invalid_input("Descriptor has no range to index into"));
+        }
+
+        let public_key = self.xpub.derive_pub(&secp, &steps)?.public_key;
+        let compressed = CompressedPublicKey(public_key);
+        let network = self.network();
+
+        Ok(match self.kind {
+            DescriptorKind::Pkh => Address::p2pkh(compressed.pubkey_hash(), network),
+            DescriptorKind::Wpkh => Address::p2wpkh(&compressed, network),
+            DescriptorKind::ShWpkh => Address::p2shwpkh(&compressed, network),
+            DescriptorKind::Tr => {
+                let (x_only, _) = public_key.x_only_public_key();
+                Address::p2tr(&secp, x_only, None, network)
+            }
+        })
+    }
+
+    /// Descriptor text without the checksum
+    fn body(&self) -> String {
+        let mut key = String::new();
+        if let Some(origin) = &self.origin {
+            key.push('[');
+            key.push_str(&hex::encode(origin.fingerprint));
+            for step in &origin.path {
+                key.push_str(&format!("/{}", step));
+            }
+            key.push(']');
+        }
+        key.push_str(&self.xpub.to_string());
+        for step in &self.path {
+            key.push_str(&format!("/{}", step));
+        }
+        if self.ranged {
+            key.push_str("/*");
+        }
+
+        match self.kind {
+            DescriptorKind::Pkh => format!("pkh({})", key),
+            DescriptorKind::Wpkh => format!("wpkh({})", key),
+            DescriptorKind::ShWpkh => format!("sh(wpkh({}))", key),
+            DescriptorKind::Tr => format!("tr({})", key),
+        }
+    }
+}
+
+/// Canonical form, always with its checksum
+impl fmt::Display for Descriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let body = self.body();
+        let checksum = descriptor_checksum(&body).map_err(|_| fmt::Error)?;
+        write!(f, "{}#{}", body, checksum)
+    }
+}
+
+impl FromStr for Descriptor {
+    type Err = HawalaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
+/// Parse a descriptor, verifying its `#checksum` if it has one
+pub fn parse(descriptor: &str) -> HawalaResult<Descriptor> {
+    let descriptor = descriptor.trim();
+    let body = match descriptor.split_once('#') {
+        Some((body, checksum)) => {
+            let expected = descriptor_checksum(body)?;
+            if checksum != expected {
+                return Err(HawalaError::invalid_input(format!(
+                    "Descriptor checksum mismatch: expected {}, got {}",
+                    expected, checksum
+                )));
+            }
+            body
+        }
+        None => descriptor,
+    };
+
+    let (kind, key) = if let Some(inner) = unwrap_call(body, "sh") {
+        let key = unwrap_call(inner, "wpkh").ok_or_else(|| {
+            HawalaError::not_implemented("Only sh(wpkh(...)) is supported inside sh()")
+        })?;
+        (DescriptorKind::ShWpkh, key)
+    } else if let Some(key) = unwrap_call(body, "wpkh") {
+        (DescriptorKind::Wpkh, key)
+    } else if let Some(key) = unwrap_call(body, "pkh") {
+        (DescriptorKind::Pkh, key)
+    } else if let Some(key) = unwrap_call(body, "tr") {
+        if key.contains(',') {
+            return Err(HawalaError::not_implemented("Taproot script trees are not supported"));
+        }
+        (DescriptorKind::Tr, key)
+    } else {
+        return Err(HawalaError::invalid_input(format!(
+            "Unsupported descriptor (expected pkh, wpkh, sh(wpkh) or tr): {}",
+            body
+        )));
+    };
+
+    let (origin, key) = parse_origin(key)?;
+
+    let mut parts = key.split('/');
+    let xpub_str = parts.next().unwrap_or_default();
+    let xpub = Xpub::from_str(xpub_str).map_err(|e| {
+        HawalaError::invalid_input(format!("Descriptor key must be an extended public key: {}", e))
+    })?;
+
+    let mut path = Vec::new();
+    let mut ranged = false;
+    for step in parts {
+        if ranged {
+            return Err(HawalaError::invalid_input("The range must be the last derivation step"));
+        }
+        match step {
+            "*" => ranged = true,
+            "*'" | "*h" | "*H" => {
+                return Err(HawalaError::invalid_input(
+                    "Hardened ranges cannot be derived from an extended public key",
+                ))
+            }
+            _ => {
+                let child = ChildNumber::from_str(step).map_err(|_| {
+                    HawalaError::invalid_input(format!("Invalid derivation step: {}", step))
+                })?;
+                if child.is_hardened() {
+                    return Err(HawalaError::invalid_input(
+                        "Hardened steps cannot be derived from an extended public key",
+                    ));
+                }
+                path.push(child);
+            }
+        }
+    }
+
+    Ok(Descriptor { kind, origin, xpub, path, ranged })
+}
+
+/// Parse `descriptor` and derive the address at `index` of its range
+pub fn derive_descriptor_address(descriptor: &str, index: u32) -> HawalaResult<String> {
+    Ok(parse(descriptor)?.address_at(index)?.to_string())
+}
+
+/// BIP-380 checksum of a descriptor (without the `#`)
+pub fn descriptor_checksum(descriptor: &str) -> HawalaResult<String> {
+    let mut symbols = Vec::with_capacity(descriptor.len() * 4 / 3 + CHECKSUM_LENGTH + 1);
+    let mut groups = Vec::with_capacity(3);
+
+    for c in descriptor.chars() {
+        let value = INPUT_CHARSET.find(c).ok_or_else(|| {
+            HawalaError::invalid_input(format!("Invalid character in descriptor: {:?}", c))
+        })? as u64;
+        symbols.push(value & 31);
+        groups.push(value >> 5);
+        if groups.len() == 3 {
+            symbols.push(groups[0] * 9 + groups[1] * 3 + groups[2]);
+            groups.clear();
+        }
+    }
+    match groups.len() {
+        1 => symbols.push(groups[0]),
+        2 => symbols.push(groups[0] * 3 + groups[1]),
+        _ => {}
+    }
+    symbols.extend([0; CHECKSUM_LENGTH]);
+
+    let checksum = polymod(&symbols) ^ 1;
+    Ok((0..CHECKSUM_LENGTH)
+        .map(|i| CHECKSUM_CHARSET[((checksum >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect())
+}
+
+fn polymod(symbols: &[u64]) -> u64 {
+    const GENERATOR: [u64; 5] = [0xf5dee51989, 0xa9fdca3312, 0x1bab10e32d, 0x3706b1677a, 0x644d626ffd];
+
+    let mut chk = 1u64;
+    for &value in symbols {
+        let top = chk >> 35;
+        chk = ((chk & 0x7ffffffff) << 5) ^ value;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+/// `name(inner)` → `inner`
+fn unwrap_call<'a>(s: &'a str, name: &str) -> Option<&'a str> {
+    s.strip_prefix(name)?.strip_prefix('(')?.strip_suffix(')')
+}
+
+/// Split an optional `[fingerprint/path]` off the front of a key expression
+fn parse_origin(key: &str) -> HawalaResult<(Option<KeyOrigin>, &str)> {
+    let Some(rest) = key.strip_prefix('[') else {
+        return Ok((None, key));
+    };
+    let (origin, key) = rest
+        .split_once(']')
+        .ok_or_else(|| HawalaError::invalid_input("Unterminated key origin"))?;
+
+    let mut parts = origin.split('/');
+    let fingerprint_hex = parts.next().unwrap_or_default();
+    let fingerprint: [u8; 4] = hex::decode(fingerprint_hex)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| {
+            HawalaError::invalid_input(format!("Invalid key origin fingerprint: {}", fingerprint_hex))
+        })?;
+    let path = parts
+        .map(|step| {
+            ChildNumber::from_str(step)
+                .map_err(|_| HawalaError::invalid_input(format!("Invalid key origin step: {}", step)))
+        })
+        .collect::<HawalaResult<Vec<_>>>()?;
+
+    Ok((Some(KeyOrigin { fingerprint, path }), key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Account keys for the "abandon ... about" test mnemonic from the BIP-84 and BIP-86 test vectors
+    const WPKH: &str = "wpkh([73c5da0a/84'/0'/0']xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V/0/*)#wc3n3van";
+    const TR: &str = "tr([73c5da0a/86'/0'/0']xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ/0/*)#rg247h69";
+
+    #[test]
+    fn test_derive_wpkh_address() {
+        assert_eq!(
+            derive_descriptor_address(WPKH, 0).unwrap(),
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+        );
+        assert_eq!(parse(WPKH).unwrap().to_string(), WPKH);
+    }
+
+    #[test]
+    fn test_derive_tr_address() {
+        assert_eq!(
+            derive_descriptor_address(TR, 0).unwrap(),
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        );
+        assert_eq!(parse(TR).unwrap().kind, DescriptorKind::Tr);
+    }
+
+    #[test]
+    fn test_descriptor_checksum() {
+        // Example from the Bitcoin Core descriptor docs
+        let body = "pkh([d34db33f/44'/0'/0']xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL/1/*)";
+        assert_eq!(descriptor_checksum(body).unwrap(), "ml40v0wf");
+
+        let tampered = WPKH.replace("#wc3n3van", "#wc3n3vaa");
+        assert!(parse(&tampered).is_err());
+        // The checksum is optional
+        assert!(parse(WPKH.split('#').next().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_parse_rejects_unsupported() {
+        let key = "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+        assert!(parse(&format!("wpkh({}/0h/*)", key)).is_err());
+        assert!(parse(&format!("wpkh({}/0/*')", key)).is_err());
+        assert!(parse(&format!("wsh({}/0/*)", key)).is_err());
+        assert!(parse(&format!("sh(pkh({}/0/*))", key)).is_err());
+
+        let sh = parse(&format!("sh(wpkh({}/0/*))", key)).unwrap();
+        assert!(sh.address_at(0).unwrap().to_string().starts_with('3'));
+
+        let fixed = parse(&format!("pkh({}/0/0)", key)).unwrap();
+        assert!(fixed.address_at(0).is_ok());
+        assert!(fixed.address_at(1).is_err());
+    }
+}
@@ -4,38 +4,221 @@
 //! 
 //! SECURITY: All sensitive data (entropy, seeds) is zeroized on drop.
 
-use bip39::Mnemonic;
+use bip39::{Language, Mnemonic};
+use bitcoin::hashes::{hash160, sha256d, Hash};
+use bitcoin::secp256k1::{Secp256k1, SecretKey};
+use bitcoin::{Address, Network, PublicKey, ScriptBuf};
 use rand::rngs::OsRng;
 use rand::RngCore;
+use serde::{Deserialize, Serialize};
 use zeroize::Zeroizing;
 
 use crate::error::{HawalaError, HawalaResult};
 use crate::types::*;
+use crate::utils::crypto::{base58check_decode, keccak256, to_checksum_address};
 
 use super::derivation;
 
+/// Default mnemonic strength in bits (12 words)
+pub const DEFAULT_MNEMONIC_STRENGTH: usize = 128;
+
+/// BIP-39 wordlist a mnemonic is written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MnemonicLanguage {
+    #[default]
+    English,
+    Japanese,
+    Spanish,
+    French,
+    Italian,
+    Korean,
+    ChineseSimplified,
+    ChineseTraditional,
+    Czech,
+    Portuguese,
+}
+
+impl MnemonicLanguage {
+    /// All wordlists, in the order auto-detection tries them
+    pub const ALL: [MnemonicLanguage; 10] = [
+        MnemonicLanguage::English,
+        MnemonicLanguage::Japanese,
+        MnemonicLanguage::Spanish,
+        MnemonicLanguage::French,
+        MnemonicLanguage::Italian,
+        MnemonicLanguage::Korean,
+        MnemonicLanguage::ChineseSimplified,
+        MnemonicLanguage::ChineseTraditional,
+        MnemonicLanguage::Czech,
+        MnemonicLanguage::Portuguese,
+    ];
+
+    pub(crate) fn to_bip39(self) -> Language {
+        match self {
+            MnemonicLanguage::English => Language::English,
+            MnemonicLanguage::Japanese => Language::Japanese,
+            MnemonicLanguage::Spanish => Language::Spanish,
+            MnemonicLanguage::French => Language::French,
+            MnemonicLanguage::Italian => Language::Italian,
+            MnemonicLanguage::Korean => Language::Korean,
+            MnemonicLanguage::ChineseSimplified => Language::SimplifiedChinese,
+            MnemonicLanguage::ChineseTraditional => Language::TraditionalChinese,
+            MnemonicLanguage::Czech => Language::Czech,
+            MnemonicLanguage::Portuguese => Language::Portuguese,
+        }
+    }
+
+    fn from_bip39(language: Language) -> Self {
+        match language {
+            Language::English => MnemonicLanguage::English,
+            Language::Japanese => MnemonicLanguage::Japanese,
+            Language::Spanish => MnemonicLanguage::Spanish,
+            Language::French => MnemonicLanguage::French,
+            Language::Italian => MnemonicLanguage::Italian,
+            Language::Korean => MnemonicLanguage::Korean,
+            Language::SimplifiedChinese => MnemonicLanguage::ChineseSimplified,
+            Language::TraditionalChinese => MnemonicLanguage::ChineseTraditional,
+            Language::Czech => MnemonicLanguage::Czech,
+            Language::Portuguese => MnemonicLanguage::Portuguese,
+        }
+    }
+
+    /// Word separator for displaying a phrase
+    ///
+    /// BIP-39 asks for the ideographic space (U+3000) between Japanese words.
+    /// It NFKD-normalizes to a plain space, so the seed is the same either way.
+    pub fn separator(self) -> char {
+        match self {
+            MnemonicLanguage::Japanese => '\u{3000}',
+            _ => ' ',
+        }
+    }
+}
+
 /// Create a new wallet from random entropy
 /// 
 /// SECURITY: Entropy is securely zeroized after mnemonic generation
 pub fn create_wallet_from_entropy() -> HawalaResult<(String, AllKeys)> {
-    // Use Zeroizing wrapper to ensure entropy is cleared on drop
-    let mut entropy = Zeroizing::new([0u8; 16]); // 128 bits = 12 words
-    OsRng.fill_bytes(entropy.as_mut());
-    
-    let mnemonic = Mnemonic::from_entropy(entropy.as_ref())
-        .map_err(|e| HawalaError::crypto_error(format!("Failed to create mnemonic: {}", e)))?;
-    
-    let phrase = mnemonic.to_string();
+    create_wallet_with_strength(DEFAULT_MNEMONIC_STRENGTH)
+}
+
+/// Create a new wallet with the given entropy strength in bits
+/// 
+/// Accepts 128/160/192/224/256 bits for 12/15/18/21/24-word mnemonics.
+/// 
+/// SECURITY: Entropy is securely zeroized after mnemonic generation
+pub fn create_wallet_with_strength(bits: usize) -> HawalaResult<(String, AllKeys)> {
+    create_wallet_on(bits, KeyNetwork::Mainnet)
+}
+
+/// Create a new wallet with the given entropy strength, with keys for `network`
+/// 
+/// SECURITY: Entropy is securely zeroized after mnemonic generation
+pub fn create_wallet_on(bits: usize, network: KeyNetwork) -> HawalaResult<(String, AllKeys)> {
+    create_wallet_in(bits, network, MnemonicLanguage::English)
+}
+
+/// Create a new wallet whose mnemonic is written in `language`
+/// 
+/// SECURITY: Entropy is securely zeroized after mnemonic generation
+pub fn create_wallet_in(
+    bits: usize,
+    network: KeyNetwork,
+    language: MnemonicLanguage,
+) -> HawalaResult<(String, AllKeys)> {
+    let mnemonic = generate_mnemonic_in(bits, language)?;
+    let phrase = mnemonic_phrase(&mnemonic);
     
     // Seed is 64 bytes - wrap in Zeroizing for automatic cleanup
     let seed = Zeroizing::new(mnemonic.to_seed(""));
     
-    let keys = derivation::derive_all_keys(seed.as_ref())?;
+    let keys = derivation::derive_all_keys_on(seed.as_ref(), network)?;
     
     // entropy is automatically zeroized when dropped here
     Ok((phrase, keys))
 }
 
+/// Generate a random mnemonic without deriving any keys
+/// 
+/// SECURITY: Entropy is securely zeroized after mnemonic generation
+pub fn generate_mnemonic(bits: usize) -> HawalaResult<Mnemonic> {
+    generate_mnemonic_in(bits, MnemonicLanguage::English)
+}
+
+/// Generate a random mnemonic in `language` without deriving any keys
+/// 
+/// SECURITY: Entropy is securely zeroized after mnemonic generation
+pub fn generate_mnemonic_in(bits: usize, language: MnemonicLanguage) -> HawalaResult<Mnemonic> {
+    if !matches!(bits, 128 | 160 | 192 | 224 | 256) {
+        return Err(HawalaError::invalid_input(format!(
+            "Invalid mnemonic strength {} bits: expected 128, 160, 192, 224 or 256",
+            bits
+        )));
+    }
+
+    // Use Zeroizing wrapper to ensure entropy is cleared on drop
+    let mut entropy = Zeroizing::new([0u8; 32]);
+    let entropy = &mut entropy[..bits / 8];
+    OsRng.fill_bytes(entropy);
+    
+    Mnemonic::from_entropy_in(language.to_bip39(), entropy)
+        .map_err(|e| HawalaError::crypto_error(format!("Failed to create mnemonic: {}", e)))
+}
+
+/// Format a mnemonic with its language's word separator
+pub fn mnemonic_phrase(mnemonic: &Mnemonic) -> String {
+    let separator = MnemonicLanguage::from_bip39(mnemonic.language()).separator().to_string();
+    mnemonic.words().collect::<Vec<_>>().join(&separator)
+}
+
+/// Parse a mnemonic phrase, auto-detecting the wordlist when `language` is `None`
+/// 
+/// Input is NFKD-normalized first, so Japanese phrases separated by
+/// ideographic spaces and composed/decomposed kana both parse.
+pub fn parse_mnemonic(phrase: &str, language: Option<MnemonicLanguage>) -> HawalaResult<Mnemonic> {
+    let language = match language {
+        Some(language) => language,
+        None => detect_mnemonic_language(phrase).ok_or_else(|| {
+            HawalaError::new(
+                crate::error::ErrorCode::InvalidMnemonic,
+                "Invalid mnemonic: not a valid phrase in any supported language",
+            )
+        })?,
+    };
+    Mnemonic::parse_in(language.to_bip39(), phrase)
+        .map_err(|e| HawalaError::new(crate::error::ErrorCode::InvalidMnemonic, format!("Invalid mnemonic: {}", e)))
+}
+
+/// Wordlist a phrase is valid in, if any
+/// 
+/// Some words appear in several lists (e.g. English and French), so each
+/// language is tried with its checksum in [`MnemonicLanguage::ALL`] order.
+pub fn detect_mnemonic_language(phrase: &str) -> Option<MnemonicLanguage> {
+    MnemonicLanguage::ALL
+        .into_iter()
+        .find(|language| Mnemonic::parse_in(language.to_bip39(), phrase).is_ok())
+}
+
+/// Recover the BIP-39 entropy behind a mnemonic (any supported language)
+/// 
+/// The checksum word is verified before the entropy is returned.
+pub fn mnemonic_to_entropy(mnemonic_phrase: &str) -> HawalaResult<Vec<u8>> {
+    Ok(parse_mnemonic(mnemonic_phrase, None)?.to_entropy())
+}
+
+/// Encode 16/20/24/28/32 bytes of entropy as an English mnemonic
+pub fn entropy_to_mnemonic(entropy: &[u8]) -> HawalaResult<String> {
+    entropy_to_mnemonic_in(entropy, MnemonicLanguage::English)
+}
+
+/// Encode entropy as a mnemonic in `language`
+pub fn entropy_to_mnemonic_in(entropy: &[u8], language: MnemonicLanguage) -> HawalaResult<String> {
+    let mnemonic = Mnemonic::from_entropy_in(language.to_bip39(), entropy)
+        .map_err(|e| HawalaError::invalid_input(format!("Invalid entropy: {}", e)))?;
+    Ok(mnemonic_phrase(&mnemonic))
+}
+
 /// Restore wallet from mnemonic phrase
 /// 
 /// SECURITY: Seed is securely zeroized after key derivation
@@ -47,14 +230,202 @@ pub fn restore_wallet(mnemonic_phrase: &str) -> HawalaResult<AllKeys> {
 /// 
 /// SECURITY: Seed is securely zeroized after key derivation
 pub fn restore_wallet_with_passphrase(mnemonic_phrase: &str, passphrase: &str) -> HawalaResult<AllKeys> {
-    let mnemonic = Mnemonic::parse(mnemonic_phrase)
-        .map_err(|e| HawalaError::new(crate::error::ErrorCode::InvalidMnemonic, format!("Invalid mnemonic: {}", e)))?;
+    restore_wallet_in(mnemonic_phrase, passphrase, None)
+}
+
+/// Restore wallet from a mnemonic in `language`, or any supported language when `None`
+/// 
+/// SECURITY: Seed is securely zeroized after key derivation
+pub fn restore_wallet_in(
+    mnemonic_phrase: &str,
+    passphrase: &str,
+    language: Option<MnemonicLanguage>,
+) -> HawalaResult<AllKeys> {
+    let mnemonic = parse_mnemonic(mnemonic_phrase, language)?;
     
     // Wrap seed in Zeroizing for automatic cleanup
     let seed = Zeroizing::new(mnemonic.to_seed(passphrase));
     derivation::derive_all_keys(seed.as_ref())
 }
 
+/// Restore the keys of every chain derivable at a BIP-44 account and
+/// address index (see [`derivation::derive_account_keys`])
+/// 
+/// SECURITY: Seed is securely zeroized after key derivation
+pub fn restore_wallet_at(
+    mnemonic_phrase: &str,
+    passphrase: &str,
+    account: u32,
+    index: u32,
+) -> HawalaResult<PartialKeys> {
+    let mnemonic = parse_mnemonic(mnemonic_phrase, None)?;
+    
+    let seed = Zeroizing::new(mnemonic.to_seed(passphrase));
+    derivation::derive_account_keys(seed.as_ref(), account, index)
+}
+
+/// WIF version byte for Bitcoin mainnet keys
+const WIF_BITCOIN: u8 = 0x80;
+/// WIF version byte shared by Bitcoin and Litecoin testnet keys
+const WIF_TESTNET: u8 = 0xEF;
+/// WIF version byte for Litecoin mainnet keys
+const WIF_LITECOIN: u8 = 0xB0;
+/// Litecoin mainnet P2PKH address version
+const LITECOIN_P2PKH_VERSION: u8 = 0x30;
+/// Litecoin mainnet P2SH address version (`M...`)
+const LITECOIN_P2SH_VERSION: u8 = 0x32;
+
+/// A private key imported from WIF
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedKey {
+    /// `bitcoin`, `bitcoin-testnet` or `litecoin`; testnet WIFs are shared
+    /// by both coins and reported as Bitcoin testnet
+    pub network: Chain,
+    /// Whether the key maps to a compressed public key
+    pub compressed: bool,
+    /// Private key as hex
+    pub private_key: String,
+    /// P2WPKH address for compressed keys, P2PKH for uncompressed ones
+    pub address: String,
+    /// Legacy P2PKH address
+    pub p2pkh_address: String,
+    /// P2SH-wrapped P2WPKH address; `None` for uncompressed keys
+    pub p2sh_p2wpkh_address: Option<String>,
+    /// Native P2WPKH address; `None` for uncompressed keys
+    pub p2wpkh_address: Option<String>,
+}
+
+/// Import a WIF private key, detecting its network and compression flag
+/// 
+/// SECURITY: The checksum is compared in constant time and the decoded key
+/// bytes are zeroized on drop.
+pub fn import_wif(wif: &str) -> HawalaResult<ImportedKey> {
+    let (version, payload) = base58check_decode(wif, 1)
+        .map_err(|e| HawalaError::invalid_input(format!("Invalid WIF: {}", e.message)))?;
+    let payload = Zeroizing::new(payload);
+
+    let network = match version[0] {
+        WIF_BITCOIN => Chain::Bitcoin,
+        WIF_TESTNET => Chain::BitcoinTestnet,
+        WIF_LITECOIN => Chain::Litecoin,
+        other => {
+            return Err(HawalaError::invalid_input(format!("Unknown WIF version byte 0x{:02x}", other)));
+        }
+    };
+
+    let compressed = match payload.len() {
+        32 => false,
+        33 if payload[32] == 0x01 => true,
+        33 => return Err(HawalaError::invalid_input("Invalid WIF compression flag")),
+        len => return Err(HawalaError::invalid_input(format!("Invalid WIF key length {}", len))),
+    };
+
+    let secret_key = SecretKey::from_slice(&payload[..32])
+        .map_err(|_| HawalaError::invalid_input("WIF key is not a valid secp256k1 scalar"))?;
+    let secp = Secp256k1::new();
+    let public_key = PublicKey {
+        inner: bitcoin::secp256k1::PublicKey::from_secret_key(&secp, &secret_key),
+        compressed,
+    };
+
+    // The WIF doesn't say which script the key was used with, so every
+    // standard single-key script is offered
+    let (p2pkh_address, segwit_addresses) = if network == Chain::Litecoin {
+        let p2pkh = litecoin_base58_address(LITECOIN_P2PKH_VERSION, &hash160::Hash::hash(&public_key.to_bytes()));
+        let segwit = if compressed {
+            let redeem_script = ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash().expect("compressed key"));
+            let p2sh = litecoin_base58_address(LITECOIN_P2SH_VERSION, &hash160::Hash::hash(redeem_script.as_bytes()));
+            Some((p2sh, derivation::litecoin_p2wpkh_address(&public_key.to_bytes(), "ltc")?))
+        } else {
+            None
+        };
+        (p2pkh, segwit)
+    } else {
+        let btc_network = if network == Chain::Bitcoin { Network::Bitcoin } else { Network::Testnet };
+        let p2pkh = Address::p2pkh(public_key.pubkey_hash(), btc_network).to_string();
+        let segwit = compressed.then(|| {
+            let compressed_key = bitcoin::CompressedPublicKey(public_key.inner);
+            (
+                Address::p2shwpkh(&compressed_key, btc_network).to_string(),
+                Address::p2wpkh(&compressed_key, btc_network).to_string(),
+            )
+        });
+        (p2pkh, segwit)
+    };
+    let (p2sh_p2wpkh_address, p2wpkh_address) = segwit_addresses.unzip();
+
+    Ok(ImportedKey {
+        network,
+        compressed,
+        private_key: hex::encode(&payload[..32]),
+        address: p2wpkh_address.clone().unwrap_or_else(|| p2pkh_address.clone()),
+        p2pkh_address,
+        p2sh_p2wpkh_address,
+        p2wpkh_address,
+    })
+}
+
+/// Base58Check Litecoin address for a 20-byte key or script hash
+fn litecoin_base58_address(version: u8, hash: &hash160::Hash) -> String {
+    let mut data = Vec::with_capacity(25);
+    data.push(version);
+    data.extend_from_slice(hash.as_ref());
+    let checksum = sha256d::Hash::hash(&data);
+    data.extend_from_slice(&checksum[..4]);
+    bs58::encode(data).into_string()
+}
+
+/// A raw secp256k1 private key imported for EVM chains
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedEvmKey {
+    /// Private key as lowercase hex, without `0x`
+    pub private_key: String,
+    /// Uncompressed public key as hex, without the `04` prefix
+    pub public_key: String,
+    /// EIP-55 checksummed address
+    pub checksummed_address: String,
+}
+
+/// Import a hex private key for Ethereum and other EVM chains
+/// 
+/// The key must be 32 bytes (64 hex digits, `0x` optional) and a valid
+/// secp256k1 scalar: non-zero and below the curve order.
+/// 
+/// SECURITY: The decoded key bytes are zeroized on drop.
+pub fn import_evm_key(key_hex: &str) -> HawalaResult<ImportedEvmKey> {
+    let trimmed = key_hex.trim();
+    let digits = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    if digits.len() != 64 {
+        return Err(HawalaError::invalid_input(format!(
+            "Private key must be 64 hex digits, got {}",
+            digits.len()
+        )));
+    }
+
+    let key_bytes = Zeroizing::new(
+        hex::decode(digits).map_err(|_| HawalaError::invalid_input("Private key is not valid hex"))?,
+    );
+    if key_bytes.iter().all(|&b| b == 0) {
+        return Err(HawalaError::invalid_input("Private key cannot be zero"));
+    }
+    let secret_key = SecretKey::from_slice(&key_bytes)
+        .map_err(|_| HawalaError::invalid_input("Private key must be below the secp256k1 curve order"))?;
+
+    let secp = Secp256k1::new();
+    let uncompressed = secret_key.public_key(&secp).serialize_uncompressed();
+    let public_key_bytes = &uncompressed[1..];
+    let address_hash = keccak256(public_key_bytes);
+
+    Ok(ImportedEvmKey {
+        private_key: hex::encode(&key_bytes[..]),
+        public_key: hex::encode(public_key_bytes),
+        checksummed_address: to_checksum_address(&address_hash[12..]),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,6 +441,28 @@ mod tests {
         assert!(!keys.ethereum.address.is_empty());
     }
 
+    #[test]
+    fn test_create_wallet_24_words() {
+        let (mnemonic, _) = create_wallet_with_strength(256).unwrap();
+        assert_eq!(mnemonic.split_whitespace().count(), 24);
+        assert!(restore_wallet(&mnemonic).is_ok());
+    }
+
+    #[test]
+    fn test_create_wallet_all_strengths() {
+        for (bits, words) in [(128, 12), (160, 15), (192, 18), (224, 21), (256, 24)] {
+            let (mnemonic, _) = create_wallet_with_strength(bits).unwrap();
+            assert_eq!(mnemonic.split_whitespace().count(), words);
+        }
+    }
+
+    #[test]
+    fn test_create_wallet_invalid_strength() {
+        assert!(create_wallet_with_strength(0).is_err());
+        assert!(create_wallet_with_strength(127).is_err());
+        assert!(create_wallet_with_strength(512).is_err());
+    }
+
     #[test]
     fn test_restore_wallet() {
         // Test vector mnemonic
@@ -77,4 +470,153 @@ mod tests {
         let result = restore_wallet(mnemonic);
         assert!(result.is_ok());
     }
+
+    /// First Japanese vector from the bip32JP reference test set
+    const JA_MNEMONIC: &str = "あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あおぞら";
+    const JA_PASSPHRASE: &str = "㍍ガバヴァぱばぐゞちぢ十人十色";
+    const JA_SEED: &str = "a262d6fb6122ecf45be09c50492b31f92e9beb7d9a845987a02cefda57a15f9c467a17872029a9e92299b5cbdf306e3a0ee620245cbd508959b6cb7ca637bd55";
+
+    #[test]
+    fn test_japanese_reference_vector() {
+        let mnemonic = parse_mnemonic(JA_MNEMONIC, Some(MnemonicLanguage::Japanese)).unwrap();
+        // The passphrase only matches the reference once NFKD-normalized
+        assert_eq!(hex::encode(mnemonic.to_seed(JA_PASSPHRASE)), JA_SEED);
+
+        // Plain spaces give the same seed
+        let ascii_spaced = JA_MNEMONIC.replace('\u{3000}', " ");
+        let mnemonic = parse_mnemonic(&ascii_spaced, None).unwrap();
+        assert_eq!(hex::encode(mnemonic.to_seed(JA_PASSPHRASE)), JA_SEED);
+    }
+
+    #[test]
+    fn test_detect_mnemonic_language() {
+        assert_eq!(detect_mnemonic_language(JA_MNEMONIC), Some(MnemonicLanguage::Japanese));
+        assert_eq!(
+            detect_mnemonic_language("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"),
+            Some(MnemonicLanguage::English)
+        );
+        assert_eq!(detect_mnemonic_language("invalid mnemonic phrase"), None);
+
+        // Explicit language must match the words
+        assert!(parse_mnemonic(JA_MNEMONIC, Some(MnemonicLanguage::English)).is_err());
+        assert!(restore_wallet_in(JA_MNEMONIC, "", None).is_ok());
+    }
+
+    #[test]
+    fn test_create_wallet_in_language() {
+        for language in MnemonicLanguage::ALL {
+            let (phrase, keys) = create_wallet_in(128, KeyNetwork::Mainnet, language).unwrap();
+            assert_eq!(phrase.split_whitespace().count(), 12);
+            let restored = restore_wallet_in(&phrase, "", Some(language)).unwrap();
+            assert_eq!(restored.bitcoin.address, keys.bitcoin.address);
+        }
+
+        let (phrase, _) = create_wallet_in(128, KeyNetwork::Mainnet, MnemonicLanguage::Japanese).unwrap();
+        assert_eq!(phrase.matches('\u{3000}').count(), 11);
+    }
+
+    #[test]
+    fn test_entropy_roundtrip() {
+        let vectors = [
+            (
+                "00000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            ),
+            (
+                "80808080808080808080808080808080",
+                "letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+            ),
+        ];
+        for (entropy_hex, phrase) in vectors {
+            let entropy = hex::decode(entropy_hex).unwrap();
+            assert_eq!(entropy_to_mnemonic(&entropy).unwrap(), phrase);
+            assert_eq!(hex::encode(mnemonic_to_entropy(phrase).unwrap()), entropy_hex);
+        }
+    }
+
+    #[test]
+    fn test_entropy_conversion_rejects_bad_input() {
+        assert!(entropy_to_mnemonic(&[0u8; 15]).is_err());
+        // Valid words, wrong checksum
+        assert!(mnemonic_to_entropy("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon").is_err());
+    }
+
+    #[test]
+    fn test_import_wif_compressed() {
+        let key = import_wif("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn").unwrap();
+        assert_eq!(key.network, Chain::Bitcoin);
+        assert!(key.compressed);
+        assert_eq!(key.private_key, format!("{:064x}", 1));
+        assert_eq!(key.address, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        assert_eq!(key.p2pkh_address, "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+        assert_eq!(key.p2sh_p2wpkh_address.as_deref(), Some("3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN"));
+        assert_eq!(key.p2wpkh_address.as_deref(), Some(key.address.as_str()));
+
+        let testnet = import_wif("cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN87JcbXMTcA").unwrap();
+        assert_eq!(testnet.network, Chain::BitcoinTestnet);
+        assert_eq!(testnet.address, "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx");
+        assert_eq!(testnet.p2pkh_address, "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r");
+        assert_eq!(testnet.p2sh_p2wpkh_address.as_deref(), Some("2NAUYAHhujozruyzpsFRP63mbrdaU5wnEpN"));
+
+        let litecoin = import_wif("T33ydQRKp4FCW5LCLLUB7deioUMoveiwekdwUwyfRDeGZm76aUjV").unwrap();
+        assert_eq!(litecoin.network, Chain::Litecoin);
+        assert_eq!(litecoin.address, "ltc1qw508d6qejxtdg4y5r3zarvary0c5xw7kgmn4n9");
+        assert_eq!(litecoin.p2pkh_address, "LVuDpNCSSj6pQ7t9Pv6d6sUkLKoqDEVUnJ");
+        assert_eq!(litecoin.p2sh_p2wpkh_address.as_deref(), Some("MR8UQSBr5ULwWheBHznrHk2jxyxkHQu8vB"));
+    }
+
+    #[test]
+    fn test_import_wif_uncompressed() {
+        let key = import_wif("5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDf").unwrap();
+        assert_eq!(key.network, Chain::Bitcoin);
+        assert!(!key.compressed);
+        assert_eq!(key.private_key, format!("{:064x}", 1));
+        assert_eq!(key.address, "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm");
+        assert_eq!(key.p2pkh_address, key.address);
+        assert!(key.p2sh_p2wpkh_address.is_none());
+        assert!(key.p2wpkh_address.is_none());
+    }
+
+    #[test]
+    fn test_import_wif_rejects_bad_checksum() {
+        assert!(import_wif("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWo").is_err());
+        assert!(import_wif("5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDg").is_err());
+        assert!(import_wif("").is_err());
+    }
+
+    #[test]
+    fn test_import_evm_key() {
+        let key = import_evm_key("0x0000000000000000000000000000000000000000000000000000000000000001").unwrap();
+        assert_eq!(key.private_key, format!("{:064x}", 1));
+        assert_eq!(
+            key.public_key,
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+        );
+        assert_eq!(key.checksummed_address, "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+
+        // Prefix is optional and case is normalized
+        let upper = import_evm_key("4C0883A69102937D6231471B5DBB6204FE5129617082792AE468D01A3F362318").unwrap();
+        assert_eq!(upper.private_key, "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        assert_eq!(upper.checksummed_address, "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23");
+    }
+
+    #[test]
+    fn test_import_evm_key_rejects_invalid_scalars() {
+        assert!(import_evm_key(&"0".repeat(64)).is_err());
+        // Curve order n
+        assert!(import_evm_key("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").is_err());
+        assert!(import_evm_key(&"f".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn test_import_evm_key_rejects_malformed() {
+        assert!(import_evm_key("").is_err());
+        assert!(import_evm_key("0x1234").is_err());
+        assert!(import_evm_key(&"g".repeat(64)).is_err());
+        assert!(import_evm_key(&"1".repeat(66)).is_err());
+    }
 }
@@ -11,6 +11,8 @@ mod amount_validation;
 mod derivation_path;
 pub mod utxo;
 pub mod nonce;
+pub mod descriptor;
+pub mod bip38;
 
 pub use keygen::*;
 pub use derivation::*;
@@ -27,10 +29,30 @@ pub fn create_new_wallet() -> HawalaResult<(String, AllKeys)> {
     keygen::create_wallet_from_entropy()
 }
 
+/// Create a new wallet with 128/160/192/224/256 bits of entropy (12-24 words)
+pub fn create_new_wallet_with_strength(bits: usize) -> HawalaResult<(String, AllKeys)> {
+    keygen::create_wallet_with_strength(bits)
+}
+
+/// Create a new wallet with random entropy, with keys for `network`
+pub fn create_new_wallet_on(network: KeyNetwork) -> HawalaResult<(String, AllKeys)> {
+    keygen::create_wallet_on(keygen::DEFAULT_MNEMONIC_STRENGTH, network)
+}
+
+/// Create a new wallet whose mnemonic uses the `language` wordlist
+pub fn create_new_wallet_in(bits: usize, language: MnemonicLanguage) -> HawalaResult<(String, AllKeys)> {
+    keygen::create_wallet_in(bits, KeyNetwork::Mainnet, language)
+}
+
 /// Generate keys directly from a BIP39 seed
 /// This is the lower-level function used by create_new_wallet and restore_from_mnemonic
-pub fn generate_keys_from_seed(seed: &[u8]) -> HawalaResult<AllKeys> {
-    derivation::derive_all_keys(seed)
+pub fn generate_keys_from_seed(seed: &[u8], network: KeyNetwork) -> HawalaResult<AllKeys> {
+    derivation::derive_all_keys_on(seed, network)
+}
+
+/// Generate keys for the given chains only from a BIP39 seed
+pub fn generate_selected_keys(seed: &[u8], network: KeyNetwork, chains: &[Chain]) -> HawalaResult<PartialKeys> {
+    derivation::derive_selected_keys(seed, network, chains)
 }
 
 /// Restore wallet from mnemonic phrase
@@ -43,6 +65,16 @@ pub fn restore_from_mnemonic_with_passphrase(mnemonic: &str, passphrase: &str) -
     keygen::restore_wallet_with_passphrase(mnemonic, passphrase)
 }
 
+/// Restore wallet from a mnemonic in `language`, auto-detected when `None`
+pub fn restore_from_mnemonic_in(mnemonic: &str, passphrase: &str, language: Option<MnemonicLanguage>) -> HawalaResult<AllKeys> {
+    keygen::restore_wallet_in(mnemonic, passphrase, language)
+}
+
+/// Restore the keys derivable at a BIP-44 account and address index
+pub fn restore_account(mnemonic: &str, passphrase: &str, account: u32, index: u32) -> HawalaResult<PartialKeys> {
+    keygen::restore_wallet_at(mnemonic, passphrase, account, index)
+}
+
 /// Validate a mnemonic phrase
 pub fn validate_mnemonic(mnemonic: &str) -> bool {
     validation::is_valid_mnemonic(mnemonic)
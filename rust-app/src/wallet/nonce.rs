@@ -2,11 +2,16 @@
 //!
 //! Manages nonces for EVM transactions to prevent conflicts
 //! and enable proper transaction replacement (RBF/cancel).
+//!
+//! Nonce state is read and written through a [`NonceStore`]. The default
+//! store is in-memory; call [`set_nonce_storage_path`] at startup to persist
+//! reservations and pending nonces across app restarts.
 
 use crate::error::{HawalaError, HawalaResult};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::sync::Mutex;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
 use std::time::Duration;
 
 // =============================================================================
@@ -14,7 +19,7 @@ use std::time::Duration;
 // =============================================================================
 
 /// Nonce state for an address on a chain
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct NonceState {
     /// Last confirmed nonce (on-chain)
     pub confirmed_nonce: u64,
@@ -46,108 +51,277 @@ pub enum NonceSource {
 }
 
 /// Nonce gap detection result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NonceGap {
     pub start: u64,
     pub end: u64,
     pub count: u64,
 }
 
+// =============================================================================
+// Storage
+// =============================================================================
+
+/// Backend that nonce state is read from and written through to
+///
+/// Addresses are passed already lowercased. Calls are serialized by the
+/// nonce manager, so implementations need not synchronize internally.
+pub trait NonceStore: Send {
+    /// Load the state for an address, if any has been stored
+    fn load(&self, chain_id: u64, address: &str) -> HawalaResult<Option<NonceState>>;
+    /// Store the state for an address
+    fn save(&mut self, chain_id: u64, address: &str, state: &NonceState) -> HawalaResult<()>;
+    /// Forget all state for an address
+    fn remove(&mut self, chain_id: u64, address: &str) -> HawalaResult<()>;
+}
+
+/// chain_id -> address -> NonceState
+type NonceStates = HashMap<u64, HashMap<String, NonceState>>;
+
+/// In-memory store (state is lost when the process exits)
+#[derive(Debug, Default)]
+pub struct MemoryNonceStore {
+    states: NonceStates,
+}
+
+impl NonceStore for MemoryNonceStore {
+    fn load(&self, chain_id: u64, address: &str) -> HawalaResult<Option<NonceState>> {
+        Ok(self.states.get(&chain_id).and_then(|c| c.get(address)).cloned())
+    }
+
+    fn save(&mut self, chain_id: u64, address: &str, state: &NonceState) -> HawalaResult<()> {
+        self.states.entry(chain_id).or_default().insert(address.to_string(), state.clone());
+        Ok(())
+    }
+
+    fn remove(&mut self, chain_id: u64, address: &str) -> HawalaResult<()> {
+        if let Some(chain_states) = self.states.get_mut(&chain_id) {
+            chain_states.remove(address);
+        }
+        Ok(())
+    }
+}
+
+/// JSON-file-backed store
+///
+/// The whole file is kept in memory and rewritten on every change, via a
+/// temporary file and rename so a crash never leaves a truncated file behind.
+#[derive(Debug)]
+pub struct JsonFileNonceStore {
+    path: PathBuf,
+    states: NonceStates,
+}
+
+impl JsonFileNonceStore {
+    /// Open a store at `path`, loading existing state if the file exists
+    pub fn open(path: impl Into<PathBuf>) -> HawalaResult<Self> {
+        let path = path.into();
+        let states = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => NonceStates::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path, states })
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    fn flush(&self) -> HawalaResult<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec(&self.states)?)?;
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+impl NonceStore for JsonFileNonceStore {
+    fn load(&self, chain_id: u64, address: &str) -> HawalaResult<Option<NonceState>> {
+        Ok(self.states.get(&chain_id).and_then(|c| c.get(address)).cloned())
+    }
+
+    fn save(&mut self, chain_id: u64, address: &str, state: &NonceState) -> HawalaResult<()> {
+        self.states.entry(chain_id).or_default().insert(address.to_string(), state.clone());
+        self.flush()
+    }
+
+    fn remove(&mut self, chain_id: u64, address: &str) -> HawalaResult<()> {
+        if let Some(chain_states) = self.states.get_mut(&chain_id) {
+            if chain_states.remove(address).is_some() {
+                return self.flush();
+            }
+        }
+        Ok(())
+    }
+}
+
 lazy_static::lazy_static! {
-    /// Global nonce cache: chain_id -> address -> NonceState
-    static ref NONCE_CACHE: Mutex<HashMap<u64, HashMap<String, NonceState>>> = 
-        Mutex::new(HashMap::new());
+    /// Global nonce store
+    static ref NONCE_STORE: Mutex<Box<dyn NonceStore>> =
+        Mutex::new(Box::new(MemoryNonceStore::default()));
+}
+
+fn lock_store() -> HawalaResult<MutexGuard<'static, Box<dyn NonceStore>>> {
+    NONCE_STORE.lock().map_err(|_| HawalaError::internal("Lock failed"))
+}
+
+/// Load, modify and save the state for an address in one step
+fn update_state<T>(
+    store: &mut dyn NonceStore,
+    address: &str,
+    chain_id: u64,
+    f: impl FnOnce(&mut NonceState) -> T,
+) -> HawalaResult<T> {
+    let address = address.to_lowercase();
+    let mut state = store.load(chain_id, &address)?.unwrap_or_default();
+    let result = f(&mut state);
+    store.save(chain_id, &address, &state)?;
+    Ok(result)
 }
 
 // =============================================================================
 // Public API
 // =============================================================================
 
+/// Replace the global nonce store
+pub fn set_nonce_store(store: Box<dyn NonceStore>) -> HawalaResult<()> {
+    *lock_store()? = store;
+    Ok(())
+}
+
+/// Persist nonce state to a JSON file at `path`, loading any state already there
+pub fn set_nonce_storage_path(path: impl Into<PathBuf>) -> HawalaResult<()> {
+    set_nonce_store(Box::new(JsonFileNonceStore::open(path)?))
+}
+
 /// Get the next available nonce for an address
 pub fn get_next_nonce(address: &str, chain_id: u64) -> HawalaResult<NonceResult> {
     // Fetch current nonce from network
     let network_nonce = fetch_network_nonce(address, chain_id)?;
     
     // Check local state for pending/reserved nonces
-    let mut cache = NONCE_CACHE.lock().map_err(|_| HawalaError::internal("Lock failed"))?;
-    let chain_cache = cache.entry(chain_id).or_default();
-    let state = chain_cache.entry(address.to_lowercase()).or_default();
+    let next_nonce = update_state(lock_store()?.as_mut(), address, chain_id, |state| {
+        sync_confirmed_nonce(state, network_nonce);
+        next_free_nonce(state, network_nonce)
+    })?;
     
-    // Update confirmed nonce if network is ahead
+    Ok(nonce_result(address, chain_id, next_nonce, network_nonce))
+}
+
+/// Pick the next available nonce and reserve it in one step
+///
+/// The nonce is chosen and reserved under the same lock, so concurrent
+/// callers always get distinct nonces.
+pub fn reserve_next_nonce(address: &str, chain_id: u64) -> HawalaResult<NonceResult> {
+    let network_nonce = fetch_network_nonce(address, chain_id)?;
+    reserve_next_nonce_from(address, chain_id, network_nonce)
+}
+
+fn reserve_next_nonce_from(address: &str, chain_id: u64, network_nonce: u64) -> HawalaResult<NonceResult> {
+    let next_nonce = update_state(lock_store()?.as_mut(), address, chain_id, |state| {
+        sync_confirmed_nonce(state, network_nonce);
+        let nonce = next_free_nonce(state, network_nonce);
+        state.reserved_nonces.insert(nonce);
+        nonce
+    })?;
+    
+    Ok(nonce_result(address, chain_id, next_nonce, network_nonce))
+}
+
+/// Reserve a specific nonce for a pending transaction
+///
+/// Fails if the nonce is already reserved or pending, so two transactions
+/// can't be signed with it.
+pub fn reserve_nonce(address: &str, chain_id: u64, nonce: u64) -> HawalaResult<()> {
+    update_state(lock_store()?.as_mut(), address, chain_id, |state| {
+        if state.reserved_nonces.contains(&nonce) || state.pending_nonces.contains(&nonce) {
+            return Err(HawalaError::invalid_input(format!(
+                "Nonce {} is already reserved or pending", nonce
+            )));
+        }
+        state.reserved_nonces.insert(nonce);
+        Ok(())
+    })?
+}
+
+/// Move the confirmed nonce forward if the network is ahead
+fn sync_confirmed_nonce(state: &mut NonceState, network_nonce: u64) {
     if network_nonce > state.confirmed_nonce {
         state.confirmed_nonce = network_nonce;
         // Clear pending nonces that are now confirmed
         state.pending_nonces.retain(|&n| n >= network_nonce);
     }
-    
-    // Find next available nonce
-    let mut next_nonce = network_nonce;
-    while state.pending_nonces.contains(&next_nonce) || state.reserved_nonces.contains(&next_nonce) {
-        next_nonce += 1;
-    }
-    
-    let source = if next_nonce == network_nonce {
+}
+
+fn nonce_result(address: &str, chain_id: u64, nonce: u64, network_nonce: u64) -> NonceResult {
+    let source = if nonce == network_nonce {
         NonceSource::Network
     } else {
         NonceSource::Local
     };
     
-    Ok(NonceResult {
+    NonceResult {
         address: address.to_string(),
         chain_id,
-        nonce: next_nonce,
+        nonce,
         source,
-    })
-}
-
-/// Reserve a nonce for a pending transaction
-pub fn reserve_nonce(address: &str, chain_id: u64, nonce: u64) -> HawalaResult<()> {
-    let mut cache = NONCE_CACHE.lock().map_err(|_| HawalaError::internal("Lock failed"))?;
-    let chain_cache = cache.entry(chain_id).or_default();
-    let state = chain_cache.entry(address.to_lowercase()).or_default();
-    
-    state.reserved_nonces.insert(nonce);
-    Ok(())
+    }
 }
 
 /// Mark a nonce as pending (transaction broadcast)
 pub fn mark_nonce_pending(address: &str, chain_id: u64, nonce: u64) -> HawalaResult<()> {
-    let mut cache = NONCE_CACHE.lock().map_err(|_| HawalaError::internal("Lock failed"))?;
-    let chain_cache = cache.entry(chain_id).or_default();
-    let state = chain_cache.entry(address.to_lowercase()).or_default();
-    
-    // Move from reserved to pending
-    state.reserved_nonces.remove(&nonce);
-    state.pending_nonces.insert(nonce);
-    Ok(())
+    update_state(lock_store()?.as_mut(), address, chain_id, |state| {
+        // Move from reserved to pending
+        state.reserved_nonces.remove(&nonce);
+        state.pending_nonces.insert(nonce);
+    })
 }
 
 /// Confirm a nonce (transaction included in block)
 pub fn confirm_nonce(address: &str, chain_id: u64, nonce: u64) -> HawalaResult<()> {
-    let mut cache = NONCE_CACHE.lock().map_err(|_| HawalaError::internal("Lock failed"))?;
-    let chain_cache = cache.entry(chain_id).or_default();
-    let state = chain_cache.entry(address.to_lowercase()).or_default();
-    
-    state.pending_nonces.remove(&nonce);
-    state.reserved_nonces.remove(&nonce);
-    
-    // Update confirmed nonce if this is higher
-    if nonce >= state.confirmed_nonce {
-        state.confirmed_nonce = nonce + 1;
-    }
-    
-    Ok(())
+    update_state(lock_store()?.as_mut(), address, chain_id, |state| {
+        state.pending_nonces.remove(&nonce);
+        state.reserved_nonces.remove(&nonce);
+        
+        // Update confirmed nonce if this is higher
+        if nonce >= state.confirmed_nonce {
+            state.confirmed_nonce = nonce + 1;
+        }
+    })
 }
 
-/// Release a reserved/pending nonce (transaction failed/cancelled)
-pub fn release_nonce(address: &str, chain_id: u64, nonce: u64) -> HawalaResult<()> {
-    let mut cache = NONCE_CACHE.lock().map_err(|_| HawalaError::internal("Lock failed"))?;
-    let chain_cache = cache.entry(chain_id).or_default();
-    let state = chain_cache.entry(address.to_lowercase()).or_default();
-    
-    state.reserved_nonces.remove(&nonce);
-    state.pending_nonces.remove(&nonce);
-    Ok(())
+/// Release a reserved/pending nonce (transaction failed/cancelled/dropped)
+///
+/// The nonce becomes available again from `get_next_nonce`. Returns whether
+/// the nonce was being tracked.
+pub fn release_nonce(address: &str, chain_id: u64, nonce: u64) -> HawalaResult<bool> {
+    update_state(lock_store()?.as_mut(), address, chain_id, |state| {
+        let reserved = state.reserved_nonces.remove(&nonce);
+        let pending = state.pending_nonces.remove(&nonce);
+        reserved || pending
+    })
+}
+
+/// Reconcile local state with the node's `eth_getTransactionCount(address, "pending")`
+///
+/// Everything below `onchain_nonce` has been mined or is in the mempool, so
+/// reservations and pending entries there are dropped. Pending nonces at or
+/// above it were never seen by the node (dropped from the mempool) and are
+/// re-opened; reservations at or above it are kept since they haven't been
+/// broadcast yet.
+pub fn resync_from_chain(address: &str, chain_id: u64, onchain_nonce: u64) -> HawalaResult<NonceState> {
+    update_state(lock_store()?.as_mut(), address, chain_id, |state| {
+        state.confirmed_nonce = onchain_nonce;
+        state.pending_nonces.clear();
+        state.reserved_nonces.retain(|&n| n >= onchain_nonce);
+        state.clone()
+    })
 }
 
 /// Get nonce for replacement transaction (uses same nonce)
@@ -155,23 +329,23 @@ pub fn get_replacement_nonce(original_nonce: u64) -> u64 {
     original_nonce
 }
 
+/// Lowest nonce at or above `network_nonce` that is neither pending nor reserved
+fn next_free_nonce(state: &NonceState, network_nonce: u64) -> u64 {
+    let mut next_nonce = network_nonce;
+    while state.pending_nonces.contains(&next_nonce) || state.reserved_nonces.contains(&next_nonce) {
+        next_nonce += 1;
+    }
+    next_nonce
+}
+
 /// Detect nonce gaps in pending transactions
 pub fn detect_nonce_gaps(address: &str, chain_id: u64) -> HawalaResult<Vec<NonceGap>> {
-    let cache = NONCE_CACHE.lock().map_err(|_| HawalaError::internal("Lock failed"))?;
-    
-    let state = cache
-        .get(&chain_id)
-        .and_then(|c| c.get(&address.to_lowercase()));
-    
-    let state = match state {
-        Some(s) => s,
-        None => return Ok(Vec::new()),
-    };
-    
-    if state.pending_nonces.is_empty() {
-        return Ok(Vec::new());
-    }
-    
+    let state = lock_store()?.load(chain_id, &address.to_lowercase())?;
+    Ok(state.map(|s| find_nonce_gaps(&s)).unwrap_or_default())
+}
+
+/// Gaps between the confirmed nonce and the pending nonces
+fn find_nonce_gaps(state: &NonceState) -> Vec<NonceGap> {
     let mut sorted: Vec<_> = state.pending_nonces.iter().copied().collect();
     sorted.sort();
     
@@ -189,42 +363,26 @@ pub fn detect_nonce_gaps(address: &str, chain_id: u64) -> HawalaResult<Vec<Nonce
         expected = nonce + 1;
     }
     
-    Ok(gaps)
+    gaps
 }
 
 /// Clear all cached state for an address
 pub fn clear_nonce_cache(address: &str, chain_id: u64) -> HawalaResult<()> {
-    let mut cache = NONCE_CACHE.lock().map_err(|_| HawalaError::internal("Lock failed"))?;
-    
-    if let Some(chain_cache) = cache.get_mut(&chain_id) {
-        chain_cache.remove(&address.to_lowercase());
-    }
-    
-    Ok(())
+    lock_store()?.remove(chain_id, &address.to_lowercase())
 }
 
 /// Sync nonce state with network
 pub fn sync_nonce(address: &str, chain_id: u64) -> HawalaResult<u64> {
     let network_nonce = fetch_network_nonce(address, chain_id)?;
-    
-    let mut cache = NONCE_CACHE.lock().map_err(|_| HawalaError::internal("Lock failed"))?;
-    let chain_cache = cache.entry(chain_id).or_default();
-    let state = chain_cache.entry(address.to_lowercase()).or_default();
-    
-    state.confirmed_nonce = network_nonce;
-    // Clear old pending nonces
-    state.pending_nonces.retain(|&n| n >= network_nonce);
-    state.reserved_nonces.retain(|&n| n >= network_nonce);
-    
+    resync_from_chain(address, chain_id, network_nonce)?;
     Ok(network_nonce)
 }
 
 /// Get current nonce state for an address
 pub fn get_nonce_state(address: &str, chain_id: u64) -> Option<NonceState> {
-    NONCE_CACHE.lock().ok()?
-        .get(&chain_id)?
-        .get(&address.to_lowercase())
-        .cloned()
+    lock_store().ok()?
+        .load(chain_id, &address.to_lowercase())
+        .ok()?
 }
 
 // =============================================================================
@@ -322,6 +480,13 @@ fn get_rpc_endpoints(chain_id: u64) -> Vec<&'static str> {
 mod tests {
     use super::*;
     
+    fn temp_store_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("hawala_nonce_{}_{}.json", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+    
     #[test]
     fn test_nonce_gap_detection() {
         let mut state = NonceState::default();
@@ -331,23 +496,122 @@ mod tests {
         state.pending_nonces.insert(8);
         state.pending_nonces.insert(10); // Gap at 9
         
-        // Manually test gap detection logic
-        let mut sorted: Vec<_> = state.pending_nonces.iter().copied().collect();
-        sorted.sort();
+        let gaps = find_nonce_gaps(&state);
+        assert_eq!(gaps.len(), 2);
+        assert_eq!((gaps[0].start, gaps[0].end), (6, 6)); // Gap at 6
+        assert_eq!((gaps[1].start, gaps[1].end), (9, 9)); // Gap at 9
+    }
+    
+    #[test]
+    fn test_reserved_nonces_survive_reload() {
+        let path = temp_store_path("reload");
+        let address = "0xAbC0000000000000000000000000000000000001";
         
-        let mut gaps = Vec::new();
-        let mut expected = state.confirmed_nonce;
+        let mut store = JsonFileNonceStore::open(&path).unwrap();
+        update_state(&mut store, address, 1, |state| {
+            state.reserved_nonces.insert(7);
+            state.reserved_nonces.insert(8);
+        }).unwrap();
+        drop(store);
         
-        for &nonce in &sorted {
-            if nonce > expected {
-                gaps.push((expected, nonce - 1));
-            }
-            expected = nonce + 1;
+        let reloaded = JsonFileNonceStore::open(&path).unwrap();
+        let state = reloaded.load(1, &address.to_lowercase()).unwrap().unwrap();
+        assert_eq!(state.reserved_nonces, HashSet::from([7, 8]));
+        assert!(reloaded.load(137, &address.to_lowercase()).unwrap().is_none());
+        
+        std::fs::remove_file(&path).unwrap();
+    }
+    
+    #[test]
+    fn test_gaps_detected_from_persisted_state() {
+        let path = temp_store_path("gaps");
+        let address = "0xabc0000000000000000000000000000000000002";
+        
+        let mut store = JsonFileNonceStore::open(&path).unwrap();
+        update_state(&mut store, address, 1, |state| {
+            state.confirmed_nonce = 3;
+            state.pending_nonces.extend([3, 6]);
+        }).unwrap();
+        drop(store);
+        
+        let reloaded = JsonFileNonceStore::open(&path).unwrap();
+        let state = reloaded.load(1, address).unwrap().unwrap();
+        assert_eq!(find_nonce_gaps(&state), vec![NonceGap { start: 4, end: 5, count: 2 }]);
+        
+        std::fs::remove_file(&path).unwrap();
+    }
+    
+    #[test]
+    fn test_memory_store_remove() {
+        let mut store = MemoryNonceStore::default();
+        update_state(&mut store, "0xabc", 1, |state| state.confirmed_nonce = 9).unwrap();
+        assert_eq!(store.load(1, "0xabc").unwrap().unwrap().confirmed_nonce, 9);
+        
+        store.remove(1, "0xabc").unwrap();
+        assert!(store.load(1, "0xabc").unwrap().is_none());
+    }
+    
+    #[test]
+    fn test_released_nonce_is_reused() {
+        let address = "0xabc0000000000000000000000000000000001838";
+        reserve_nonce(address, 1, 5).unwrap();
+        reserve_nonce(address, 1, 6).unwrap();
+        mark_nonce_pending(address, 1, 6).unwrap();
+        
+        let state = get_nonce_state(address, 1).unwrap();
+        assert_eq!(next_free_nonce(&state, 5), 7);
+        
+        assert!(release_nonce(address, 1, 5).unwrap());
+        assert!(!release_nonce(address, 1, 5).unwrap());
+        let state = get_nonce_state(address, 1).unwrap();
+        assert_eq!(next_free_nonce(&state, 5), 5);
+        
+        assert!(release_nonce(address, 1, 6).unwrap());
+        let state = get_nonce_state(address, 1).unwrap();
+        assert!(state.pending_nonces.is_empty());
+        
+        clear_nonce_cache(address, 1).unwrap();
+    }
+    
+    #[test]
+    fn test_reserve_next_nonce_is_exclusive() {
+        let address = "0xabc0000000000000000000000000000000003838";
+        
+        let first = reserve_next_nonce_from(address, 1, 4).unwrap();
+        let second = reserve_next_nonce_from(address, 1, 4).unwrap();
+        assert_eq!((first.nonce, first.source), (4, NonceSource::Network));
+        assert_eq!((second.nonce, second.source), (5, NonceSource::Local));
+        
+        // Taken nonces can't be reserved again, reserved or pending
+        assert!(reserve_nonce(address, 1, 4).is_err());
+        mark_nonce_pending(address, 1, 5).unwrap();
+        assert!(reserve_nonce(address, 1, 5).is_err());
+        
+        // A failed duplicate doesn't disturb the original holder
+        let state = get_nonce_state(address, 1).unwrap();
+        assert!(state.reserved_nonces.contains(&4));
+        assert!(state.pending_nonces.contains(&5));
+        
+        clear_nonce_cache(address, 1).unwrap();
+    }
+    
+    #[test]
+    fn test_resync_clears_stale_reservations() {
+        let address = "0xABC0000000000000000000000000000000002838";
+        for nonce in [3, 4, 6] {
+            reserve_nonce(address, 10, nonce).unwrap();
         }
+        mark_nonce_pending(address, 10, 5).unwrap();
+        mark_nonce_pending(address, 10, 7).unwrap();
         
-        assert_eq!(gaps.len(), 2);
-        assert_eq!(gaps[0], (6, 6)); // Gap at 6
-        assert_eq!(gaps[1], (9, 9)); // Gap at 9
+        let state = resync_from_chain(address, 10, 5).unwrap();
+        assert_eq!(state.confirmed_nonce, 5);
+        assert_eq!(state.reserved_nonces, HashSet::from([6]));
+        assert!(state.pending_nonces.is_empty());
+        assert_eq!(next_free_nonce(&state, 5), 5);
+        assert_eq!(get_nonce_state(address, 10), Some(state));
+        
+        clear_nonce_cache(address, 10).unwrap();
     }
     
     #[test]
@@ -126,6 +126,19 @@ lazy_static::lazy_static! {
 
 /// Fetch UTXOs for an address
 pub fn fetch_utxos(address: &str, chain: Chain) -> HawalaResult<Vec<UTXO>> {
+    // A configured Electrum server takes precedence over the explorers
+    if crate::api::electrum::electrum_server(chain).is_some() {
+        if let Ok(unspent) = crate::api::electrum::fetch_electrum_utxos(address, chain) {
+            return Ok(unspent.into_iter().map(|u| UTXO {
+                txid: u.tx_hash,
+                vout: u.tx_pos,
+                value: u.value,
+                confirmations: if u.height > 0 { 6 } else { 0 }, // Simplified, as below
+                script_pubkey: String::new(),
+            }).collect());
+        }
+    }
+    
     let base_url = match chain {
         Chain::Bitcoin => "https://mempool.space/api",
         Chain::BitcoinTestnet => "https://mempool.space/testnet/api",
@@ -164,6 +177,8 @@ pub fn fetch_utxos(address: &str, chain: Chain) -> HawalaResult<Vec<UTXO>> {
 /// Fetch and enrich UTXOs with metadata
 pub fn fetch_managed_utxos(address: &str, chain: Chain) -> HawalaResult<Vec<ManagedUTXO>> {
     let utxos = fetch_utxos(address, chain)?;
+    // Inputs of our own broadcasts stay held until they drop out of this list
+    crate::tx::release_spent_outpoints(address, utxos.iter().map(|u| (u.txid.as_str(), u.vout)))?;
     let metadata_map = UTXO_METADATA.lock()
         .map_err(|_| HawalaError::internal("UTXO metadata lock poisoned"))?;
     
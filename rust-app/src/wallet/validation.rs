@@ -2,16 +2,171 @@
 //!
 //! Validates addresses for all supported chains and mnemonic phrases.
 
-use bip39::Mnemonic;
 use bitcoin::Address;
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use tiny_keccak::{Hasher, Keccak};
 
+use super::address_validation::decode_witness_address;
+use super::keygen::{parse_mnemonic, MnemonicLanguage};
 use crate::types::Chain;
+use crate::utils::crypto::{base58check_decode, base58check_decode_with};
 
-/// Check if a mnemonic phrase is valid
+/// Check if a mnemonic phrase is valid in any supported language
 pub fn is_valid_mnemonic(phrase: &str) -> bool {
-    Mnemonic::parse(phrase).is_ok()
+    parse_mnemonic(phrase, None).is_ok()
+}
+
+/// Check if a mnemonic phrase is valid in `language`
+pub fn is_valid_mnemonic_in(phrase: &str, language: MnemonicLanguage) -> bool {
+    parse_mnemonic(phrase, Some(language)).is_ok()
+}
+
+/// Word counts BIP-39 defines (128 to 256 bits of entropy)
+const MNEMONIC_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+/// Run of wordlist-adjacent words long enough to be deliberate
+const SEQUENTIAL_RUN_WARNING: usize = 3;
+
+/// Quality report for a user-supplied mnemonic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MnemonicAssessment {
+    /// Phrase is a valid BIP-39 mnemonic
+    pub valid: bool,
+    pub word_count: usize,
+    /// Entropy the word count encodes; 0 for a non-standard count
+    pub bits_of_entropy: usize,
+    /// Checksum word matches (false when words are unknown or the count is wrong)
+    pub checksum_ok: bool,
+    pub all_words_in_wordlist: bool,
+    /// Words that occur more than once, in order of first repeat
+    pub duplicate_words: Vec<String>,
+    /// Words missing from the best-matching wordlist
+    pub unknown_words: Vec<String>,
+    /// Wordlist most of the words come from
+    pub language: Option<MnemonicLanguage>,
+    /// Patterns suggesting the phrase was not randomly generated
+    pub warnings: Vec<String>,
+}
+
+/// Assess a mnemonic beyond valid/invalid
+///
+/// Reports which words are wrong, whether the checksum holds, and flags
+/// phrases that are valid but unlikely to come from a random generator:
+/// identical or mostly repeated words, runs of consecutive wordlist entries,
+/// and uniform entropy such as the `abandon ... about` test phrase.
+pub fn assess_mnemonic(phrase: &str) -> MnemonicAssessment {
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    let word_count = words.len();
+    let bits_of_entropy = if MNEMONIC_WORD_COUNTS.contains(&word_count) {
+        word_count * 11 - word_count / 3
+    } else {
+        0
+    };
+
+    let mut duplicate_words: Vec<String> = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        if words[..i].contains(word) && !duplicate_words.iter().any(|d| d.as_str() == *word) {
+            duplicate_words.push(word.to_string());
+        }
+    }
+
+    let parsed = parse_mnemonic(phrase, None).ok();
+    let language = match &parsed {
+        Some(_) => super::keygen::detect_mnemonic_language(phrase),
+        None => best_matching_wordlist(&words),
+    };
+    let indices: Vec<Option<u16>> = words
+        .iter()
+        .map(|word| language.and_then(|l| l.to_bip39().find_word(word)))
+        .collect();
+    let unknown_words: Vec<String> = words
+        .iter()
+        .zip(&indices)
+        .filter(|(_, index)| index.is_none())
+        .map(|(word, _)| word.to_string())
+        .collect();
+    let all_words_in_wordlist = word_count > 0 && (parsed.is_some() || unknown_words.is_empty());
+
+    let mut warnings = Vec::new();
+    if word_count > 0 && bits_of_entropy == 0 {
+        warnings.push(format!("{} words is not a standard mnemonic length", word_count));
+    }
+    if word_count > 1 && words.iter().all(|w| *w == words[0]) {
+        warnings.push("All words are identical".to_string());
+    } else if word_count > 1 && unique_count(&words) * 2 < word_count {
+        warnings.push("More than half of the words are repeats".to_string());
+    }
+    if let Some(run) = longest_sequential_run(&indices) {
+        if run >= SEQUENTIAL_RUN_WARNING {
+            warnings.push(format!("{} consecutive words are sequential in the wordlist", run));
+        }
+    }
+    if let Some(mnemonic) = &parsed {
+        let entropy = mnemonic.to_entropy();
+        if entropy.iter().all(|b| *b == entropy[0]) {
+            warnings.push(format!(
+                "Entropy is the single byte 0x{:02x} repeated; this phrase is publicly known",
+                entropy[0]
+            ));
+        }
+    }
+
+    MnemonicAssessment {
+        valid: parsed.is_some(),
+        word_count,
+        bits_of_entropy,
+        checksum_ok: parsed.is_some(),
+        all_words_in_wordlist,
+        duplicate_words,
+        unknown_words,
+        language,
+        warnings,
+    }
+}
+
+/// Wordlist containing the most of `words`, ties going to the earlier list
+fn best_matching_wordlist(words: &[&str]) -> Option<MnemonicLanguage> {
+    let mut best = None;
+    let mut best_matches = 0;
+    for language in MnemonicLanguage::ALL {
+        let list = language.to_bip39();
+        let matches = words.iter().filter(|word| list.find_word(word).is_some()).count();
+        if matches > best_matches {
+            best = Some(language);
+            best_matches = matches;
+        }
+    }
+    best
+}
+
+fn unique_count(words: &[&str]) -> usize {
+    words.iter().collect::<std::collections::HashSet<_>>().len()
+}
+
+/// Longest run of words whose wordlist indices go up or down by one
+fn longest_sequential_run(indices: &[Option<u16>]) -> Option<usize> {
+    let mut longest = None;
+    let mut run = 1;
+    let mut step = 0i32;
+    for pair in indices.windows(2) {
+        let (Some(a), Some(b)) = (pair[0], pair[1]) else {
+            run = 1;
+            continue;
+        };
+        let diff = i32::from(b) - i32::from(a);
+        if diff.abs() == 1 && (run == 1 || diff == step) {
+            run += 1;
+            step = diff;
+        } else if diff.abs() == 1 {
+            run = 2;
+            step = diff;
+        } else {
+            run = 1;
+        }
+        longest = longest.max(Some(run));
+    }
+    longest
 }
 
 /// Validate an address for a specific chain
@@ -32,6 +187,7 @@ pub fn validate_chain_address(address: &str, chain: Chain) -> (bool, Option<Stri
         Chain::Solana | Chain::SolanaDevnet => validate_solana_address(address),
         Chain::Xrp | Chain::XrpTestnet => validate_xrp_address(address),
         Chain::Monero => validate_monero_address(address),
+        Chain::Tron => validate_tron_address(address),
         // EVM-compatible chains
         chain if chain.is_evm() => validate_ethereum_address(address),
         // Default: basic validation for other chains
@@ -75,29 +231,21 @@ fn validate_litecoin_address(address: &str) -> (bool, Option<String>) {
     let trimmed = address.trim();
     
     // Litecoin addresses: L/M (P2PKH), ltc1 (Bech32), 3 (P2SH)
-    if trimmed.starts_with("ltc1") {
-        // Bech32 - validate format
-        if trimmed.len() >= 42 && trimmed.len() <= 62 {
-            // Basic Bech32 validation
-            if trimmed.chars().skip(4).all(|c| {
-                "qpzry9x8gf2tvdw0s3jn54khce6mua7l".contains(c.to_ascii_lowercase())
-            }) {
-                return (true, Some(trimmed.to_lowercase()));
-            }
+    let lower = trimmed.to_lowercase();
+    if lower.starts_with("ltc1") {
+        // Bech32 for witness v0, bech32m for v1+
+        if decode_witness_address(&lower, &["ltc"]).is_ok() {
+            return (true, Some(lower));
         }
     } else if trimmed.starts_with('L') || trimmed.starts_with('M') {
         // P2PKH legacy
-        if trimmed.len() >= 26 && trimmed.len() <= 35 {
-            if let Ok(_) = bs58::decode(trimmed).into_vec() {
-                return (true, Some(trimmed.to_string()));
-            }
+        if trimmed.len() >= 26 && trimmed.len() <= 35 && is_base58check_hash(trimmed) {
+            return (true, Some(trimmed.to_string()));
         }
     } else if trimmed.starts_with('3') {
         // P2SH (compatible with Bitcoin)
-        if trimmed.len() >= 26 && trimmed.len() <= 35 {
-            if let Ok(_) = bs58::decode(trimmed).into_vec() {
-                return (true, Some(trimmed.to_string()));
-            }
+        if trimmed.len() >= 26 && trimmed.len() <= 35 && is_base58check_hash(trimmed) {
+            return (true, Some(trimmed.to_string()));
         }
     }
     
@@ -124,6 +272,14 @@ fn validate_ethereum_address(address: &str) -> (bool, Option<String>) {
     };
 
     let checksummed = to_checksum_address(&bytes);
+
+    // Single-case addresses carry no checksum; mixed case must match EIP-55
+    let is_mixed_case = hex_part.chars().any(|c| c.is_ascii_uppercase())
+        && hex_part.chars().any(|c| c.is_ascii_lowercase());
+    if is_mixed_case && checksummed[2..] != *hex_part {
+        return (false, None);
+    }
+
     (true, Some(checksummed))
 }
 
@@ -146,31 +302,35 @@ fn validate_xrp_address(address: &str) -> (bool, Option<String>) {
     }
 
     // Decode with Ripple alphabet
-    match bs58::decode(trimmed)
-        .with_alphabet(bs58::Alphabet::RIPPLE)
-        .into_vec()
-    {
-        Ok(bytes) if bytes.len() >= 21 => (true, Some(trimmed.to_string())),
+    match base58check_decode_with(trimmed, 1, bs58::Alphabet::RIPPLE) {
+        Ok((version, account_id)) if version == [0x00] && account_id.len() == 20 => (true, Some(trimmed.to_string())),
         _ => (false, None),
     }
 }
 
-fn validate_monero_address(address: &str) -> (bool, Option<String>) {
+fn validate_tron_address(address: &str) -> (bool, Option<String>) {
     let trimmed = address.trim();
     
-    // Monero mainnet addresses start with '4' and are 95 characters
-    // Subaddresses start with '8'
-    if (trimmed.starts_with('4') || trimmed.starts_with('8')) && trimmed.len() == 95 {
-        // Basic validation - could use monero crate for full validation
-        return (true, Some(trimmed.to_string()));
+    // Tron addresses are Base58Check with version 0x41 ('T...')
+    match base58check_decode(trimmed, 1) {
+        Ok((version, hash)) if version == [0x41] && hash.len() == 20 => (true, Some(trimmed.to_string())),
+        _ => (false, None),
     }
+}
+
+/// Base58Check string carrying a version byte and a 20-byte hash
+fn is_base58check_hash(address: &str) -> bool {
+    matches!(base58check_decode(address, 1), Ok((_, hash)) if hash.len() == 20)
+}
+
+fn validate_monero_address(address: &str) -> (bool, Option<String>) {
+    let trimmed = address.trim();
     
-    // Integrated addresses are 106 characters
-    if trimmed.starts_with('4') && trimmed.len() == 106 {
-        return (true, Some(trimmed.to_string()));
+    // Checks the Keccak checksum and both public keys; mainnet only
+    match crate::monero_wallet::parse_address(trimmed) {
+        Ok(parsed) if parsed.network == "mainnet" => (true, Some(trimmed.to_string())),
+        _ => (false, None),
     }
-    
-    (false, None)
 }
 
 fn to_checksum_address(address: &[u8]) -> String {
@@ -211,6 +371,66 @@ mod tests {
         assert!(is_valid_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"));
         assert!(!is_valid_mnemonic("invalid mnemonic phrase"));
         assert!(!is_valid_mnemonic(""));
+        assert!(is_valid_mnemonic_in("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", MnemonicLanguage::English));
+        assert!(!is_valid_mnemonic_in("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", MnemonicLanguage::Spanish));
+    }
+
+    const VALID_24: &str = "void come effort suffer camp survey warrior heavy shoot primary clutch crush open amazing screen patrol group space point ten exist slush involve unfold";
+
+    #[test]
+    fn test_assess_valid_mnemonic() {
+        let assessment = assess_mnemonic(VALID_24);
+        assert!(assessment.valid);
+        assert!(assessment.checksum_ok);
+        assert!(assessment.all_words_in_wordlist);
+        assert_eq!(assessment.word_count, 24);
+        assert_eq!(assessment.bits_of_entropy, 256);
+        assert_eq!(assessment.language, Some(MnemonicLanguage::English));
+        assert!(assessment.duplicate_words.is_empty());
+        assert!(assessment.unknown_words.is_empty());
+        assert!(assessment.warnings.is_empty());
+
+        // Valid, but the all-zero entropy test phrase
+        let known = assess_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+        assert!(known.valid);
+        assert_eq!(known.bits_of_entropy, 128);
+        assert_eq!(known.duplicate_words, vec!["abandon".to_string()]);
+        assert!(known.warnings.iter().any(|w| w.contains("0x00")));
+    }
+
+    #[test]
+    fn test_assess_bad_checksum() {
+        let assessment = assess_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon");
+        assert!(!assessment.valid);
+        assert!(!assessment.checksum_ok);
+        assert!(assessment.all_words_in_wordlist);
+        assert_eq!(assessment.language, Some(MnemonicLanguage::English));
+        assert!(assessment.warnings.iter().any(|w| w == "All words are identical"));
+    }
+
+    #[test]
+    fn test_assess_unknown_word() {
+        let phrase = VALID_24.replace("unfold", "unfoldx");
+        let assessment = assess_mnemonic(&phrase);
+        assert!(!assessment.valid);
+        assert!(!assessment.checksum_ok);
+        assert!(!assessment.all_words_in_wordlist);
+        assert_eq!(assessment.unknown_words, vec!["unfoldx".to_string()]);
+        assert_eq!(assessment.language, Some(MnemonicLanguage::English));
+    }
+
+    #[test]
+    fn test_assess_suspicious_patterns() {
+        let sequential = assess_mnemonic("abandon ability able about above absent absorb abstract absurd abuse access accident");
+        assert!(sequential.warnings.iter().any(|w| w.contains("sequential")));
+
+        let short = assess_mnemonic("void come effort");
+        assert_eq!(short.bits_of_entropy, 0);
+        assert!(short.warnings.iter().any(|w| w.contains("not a standard")));
+
+        let empty = assess_mnemonic("");
+        assert!(!empty.valid);
+        assert!(!empty.all_words_in_wordlist);
     }
 
     #[test]
@@ -223,6 +443,29 @@ mod tests {
         assert!(!valid);
     }
 
+    #[test]
+    fn test_ethereum_checksum_enforced() {
+        let checksummed = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+        let (valid, normalized) = validate_ethereum_address(checksummed);
+        assert!(valid);
+        assert_eq!(normalized.as_deref(), Some(checksummed));
+
+        // One letter flipped to the wrong case
+        let (valid, normalized) = validate_ethereum_address(&checksummed.replace("dA6", "Da6"));
+        assert!(!valid);
+        assert!(normalized.is_none());
+
+        // Unchecksummed forms are accepted and normalized
+        let (valid, normalized) = validate_ethereum_address("0xd8da6bf26964af9d7eed9e03e53415d37aa96045");
+        assert!(valid);
+        assert_eq!(normalized.as_deref(), Some(checksummed));
+
+        let (valid, normalized) = validate_ethereum_address("0xD8DA6BF26964AF9D7EED9E03E53415D37AA96045");
+        assert!(valid);
+        assert_eq!(normalized.as_deref(), Some(checksummed));
+    }
+
     #[test]
     fn test_bitcoin_address_validation() {
         // Mainnet bech32
@@ -0,0 +1,449 @@
+//! WalletConnect v2 Session Requests
+//!
+//! Normalizes the `method` + `params` of a `wc_sessionRequest` into the
+//! crate's own request types so they can be handed to the existing signers:
+//!
+//! - `eth_sendTransaction` → [`TransactionRequest`]
+//! - `personal_sign` → hex message for `message_signer::ethereum::personal_sign_hex`
+//! - `eth_signTypedData_v4` → [`TypedData`]
+//! - `solana_signTransaction` → serialized transaction (base64)
+//!
+//! The chain comes from the request itself where it names one (`chainId` of
+//! a transaction, the EIP-712 domain) or from the session's CAIP-2 `chainId`
+//! (`eip155:137`, `solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp`). The two must
+//! agree, and a request with neither is rejected rather than guessed.
+//!
+//! Keys are never part of a WalletConnect request; `private_key` on the
+//! returned [`TransactionRequest`] is left empty for the wallet to fill in.
+
+use base64::Engine;
+use ethers_core::types::U256;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::eip712::TypedData;
+use crate::error::{HawalaError, HawalaResult};
+use crate::fees::units::Wei;
+use crate::types::*;
+
+/// A WalletConnect request, ready to route to a signer
+#[derive(Debug, Clone, Serialize)]
+pub struct ParsedRequest {
+    /// JSON-RPC method as received
+    pub method: String,
+    /// Account the dapp asked to sign with
+    pub signer: String,
+    pub chain: Chain,
+    pub payload: WcPayload,
+}
+
+/// What the signer is asked to sign
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WcPayload {
+    /// `eth_sendTransaction`
+    Transaction(Box<TransactionRequest>),
+    /// `personal_sign`; `text` is set when the message is valid UTF-8
+    PersonalMessage { message_hex: String, text: Option<String> },
+    /// `eth_signTypedData_v4`
+    TypedData(Box<TypedData>),
+    /// `solana_signTransaction`, signed as-is
+    SolanaTransaction { transaction_base64: String },
+}
+
+/// CAIP-2 references of the Solana clusters (genesis hash prefixes)
+const SOLANA_MAINNET_CAIP2: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp";
+const SOLANA_DEVNET_CAIP2: &str = "EtWTRABZaYq6iMfeYKouRu166VU2xqa1";
+
+/// Normalize a WalletConnect `method` and its `params`
+///
+/// `chain_id` is the CAIP-2 chain of the session request, e.g. `"eip155:1"`.
+pub fn parse_request(method: &str, params: Value, chain_id: Option<&str>) -> HawalaResult<ParsedRequest> {
+    let session_chain = chain_id.map(chain_from_caip2).transpose()?;
+    let (signer, request_chain, mut payload) = match method {
+        "eth_sendTransaction" => parse_send_transaction(&params)?,
+        "personal_sign" => parse_personal_sign(&params)?,
+        "eth_signTypedData_v4" => parse_typed_data(&params)?,
+        "solana_signTransaction" => parse_solana_transaction(&params)?,
+        other => {
+            return Err(HawalaError::not_implemented(format!(
+                "Unsupported WalletConnect method: {}", other
+            )));
+        }
+    };
+
+    let chain = match (request_chain, session_chain) {
+        (Some(request), Some(session)) if request != session => {
+            return Err(HawalaError::invalid_input(format!(
+                "Request is for {:?} but the session chain is {:?}", request, session
+            )));
+        }
+        (Some(chain), _) | (None, Some(chain)) => chain,
+        (None, None) => {
+            return Err(HawalaError::invalid_input(
+                "No chain for the request: pass the session chainId, e.g. \"eip155:1\"",
+            ));
+        }
+    };
+    let is_solana = matches!(chain, Chain::Solana | Chain::SolanaDevnet);
+    if is_solana != matches!(payload, WcPayload::SolanaTransaction { .. }) {
+        return Err(HawalaError::invalid_input(format!("{} cannot be signed on {:?}", method, chain)));
+    }
+    if let WcPayload::Transaction(tx) = &mut payload {
+        tx.chain = chain;
+    }
+
+    Ok(ParsedRequest { method: method.to_string(), signer, chain, payload })
+}
+
+/// Chain named by a CAIP-2 id: `eip155:<chain id>` or `solana:<genesis hash>`
+pub fn chain_from_caip2(chain_id: &str) -> HawalaResult<Chain> {
+    let unsupported = || HawalaError::invalid_input(format!("Unsupported chain: {}", chain_id));
+    match chain_id.split_once(':').ok_or_else(unsupported)? {
+        ("eip155", reference) => reference.parse().ok()
+            .and_then(Chain::from_chain_id)
+            .ok_or_else(unsupported),
+        ("solana", SOLANA_MAINNET_CAIP2) => Ok(Chain::Solana),
+        ("solana", SOLANA_DEVNET_CAIP2) => Ok(Chain::SolanaDevnet),
+        _ => Err(unsupported()),
+    }
+}
+
+/// `[{ from, to, value, data, gas, gasPrice, maxFeePerGas, maxPriorityFeePerGas, nonce, chainId }]`
+fn parse_send_transaction(params: &Value) -> HawalaResult<(String, Option<Chain>, WcPayload)> {
+    let tx = params.get(0).filter(|tx| tx.is_object())
+        .ok_or_else(|| HawalaError::invalid_input("eth_sendTransaction expects [transaction]"))?;
+
+    let from = evm_address(&tx["from"], "from")?;
+    if tx["to"].is_null() {
+        return Err(HawalaError::not_implemented("Contract deployment requests are not supported"));
+    }
+    let to = evm_address(&tx["to"], "to")?;
+
+    let small = |field: &str| -> HawalaResult<Option<u64>> {
+        match quantity(tx, field)? {
+            Some(q) if q > U256::from(u64::MAX) => {
+                Err(HawalaError::invalid_input(format!("{} out of range", field)))
+            }
+            q => Ok(q.map(|q| q.as_u64())),
+        }
+    };
+    let wei = |field: &str| -> HawalaResult<Option<Wei>> {
+        match quantity(tx, field)? {
+            Some(q) if q > U256::from(u128::MAX) => {
+                Err(HawalaError::invalid_input(format!("{} out of range", field)))
+            }
+            q => Ok(q.map(|q| Wei(q.as_u128()))),
+        }
+    };
+    let chain = small("chainId")?
+        .map(|id| Chain::from_chain_id(id)
+            .ok_or_else(|| HawalaError::invalid_input(format!("Unsupported chain id: {}", id))))
+        .transpose()?;
+
+    let data = match &tx["data"] {
+        Value::Null => None,
+        Value::String(data) if data == "0x" => None,
+        Value::String(data) => {
+            hex::decode(data.trim_start_matches("0x"))
+                .map_err(|_| HawalaError::invalid_input("data is not hex"))?;
+            Some(data.clone())
+        }
+        _ => return Err(HawalaError::invalid_input("data must be a hex string")),
+    };
+
+    let request = TransactionRequest {
+        // Set by parse_request once the session chain is known
+        chain: chain.unwrap_or(Chain::Ethereum),
+        from: from.clone(),
+        to,
+        amount: quantity(tx, "value")?.unwrap_or_default().to_string(),
+        private_key: String::new(),
+        utxos: None,
+        fee_rate: None,
+        nonce: small("nonce")?,
+        gas_limit: small("gas")?,
+        auto_gas: false,
+        gas_price: wei("gasPrice")?,
+        max_fee_per_gas: wei("maxFeePerGas")?,
+        max_priority_fee_per_gas: wei("maxPriorityFeePerGas")?,
+        data,
+        balance: None,
+        access_list: None,
+        recent_blockhash: None,
+        sequence: None,
+        destination_tag: None,
+        branch: None,
+        counter: None,
+        fee_mutez: None,
+        storage_limit: None,
+        change_address: None,
+        account_xpub: None,
+        change_index: None,
+        dry_run: false,
+        send_max: false,
+        fee_guard: None,
+        allow_high_fee: false,
+    };
+
+    Ok((from, chain, WcPayload::Transaction(Box::new(request))))
+}
+
+/// `[message, address]`; a few dapps send `[address, message]`
+fn parse_personal_sign(params: &Value) -> HawalaResult<(String, Option<Chain>, WcPayload)> {
+    let (Some(first), Some(second)) = (params[0].as_str(), params[1].as_str()) else {
+        return Err(HawalaError::invalid_input("personal_sign expects [message, address]"));
+    };
+    let is_address = |s: &str| s.len() == 42 && s.starts_with("0x") && hex::decode(&s[2..]).is_ok();
+    let (message, address) = if is_address(first) && !is_address(second) {
+        (second, first)
+    } else {
+        (first, second)
+    };
+    let signer = evm_address(&Value::from(address), "address")?;
+
+    // Hex is decoded; anything else is the UTF-8 text itself
+    let bytes = message.strip_prefix("0x")
+        .and_then(|hex_message| hex::decode(hex_message).ok())
+        .unwrap_or_else(|| message.as_bytes().to_vec());
+    let text = String::from_utf8(bytes.clone()).ok();
+
+    let payload = WcPayload::PersonalMessage { message_hex: format!("0x{}", hex::encode(bytes)), text };
+    Ok((signer, None, payload))
+}
+
+/// `[address, typedData]`, with the typed data as a JSON string or object
+fn parse_typed_data(params: &Value) -> HawalaResult<(String, Option<Chain>, WcPayload)> {
+    let signer = evm_address(&params[0], "address")?;
+    let typed_data: TypedData = match &params[1] {
+        Value::String(json) => TypedData::from_json(json),
+        Value::Object(_) => serde_json::from_value(params[1].clone())
+            .map_err(|e| crate::eip712::Eip712Error::InvalidJson(e.to_string())),
+        _ => return Err(HawalaError::invalid_input("eth_signTypedData_v4 expects [address, typedData]")),
+    }
+    .and_then(|typed_data| typed_data.validate().map(|_| typed_data))
+    .map_err(|e| HawalaError::invalid_input(format!("Invalid typed data: {}", e)))?;
+
+    // Sign for the chain the domain names, when it names one
+    let chain = typed_data.domain.chain_id_u64()
+        .map(|id| Chain::from_chain_id(id)
+            .ok_or_else(|| HawalaError::invalid_input(format!("Unsupported chain id: {}", id))))
+        .transpose()?;
+
+    Ok((signer, chain, WcPayload::TypedData(Box::new(typed_data))))
+}
+
+/// `{ "transaction": "<base64>" }`; the signer is the fee payer
+fn parse_solana_transaction(params: &Value) -> HawalaResult<(String, Option<Chain>, WcPayload)> {
+    let encoded = params["transaction"].as_str()
+        .ok_or_else(|| HawalaError::invalid_input("solana_signTransaction expects { transaction }"))?;
+    let bytes = base64::engine::general_purpose::STANDARD.decode(encoded)
+        .map_err(|e| HawalaError::invalid_input(format!("Transaction is not base64: {}", e)))?;
+    let tx: solana_sdk::transaction::VersionedTransaction = bincode::deserialize(&bytes)
+        .map_err(|e| HawalaError::parse_error(format!("Invalid Solana transaction: {}", e)))?;
+
+    let fee_payer = tx.message.static_account_keys().first()
+        .ok_or_else(|| HawalaError::invalid_input("Solana transaction has no accounts"))?
+        .to_string();
+    if let Some(pubkey) = params["pubkey"].as_str() {
+        if pubkey != fee_payer {
+            return Err(HawalaError::invalid_input(format!(
+                "pubkey {} is not the transaction's fee payer {}", pubkey, fee_payer
+            )));
+        }
+    }
+
+    let payload = WcPayload::SolanaTransaction { transaction_base64: encoded.to_string() };
+    Ok((fee_payer, None, payload))
+}
+
+/// Checksummed EVM address from a JSON string
+fn evm_address(value: &Value, field: &str) -> HawalaResult<String> {
+    let address = value.as_str()
+        .ok_or_else(|| HawalaError::invalid_input(format!("Missing {}", field)))?;
+    match crate::wallet::validate_chain_address(address, Chain::Ethereum) {
+        (true, Some(checksummed)) => Ok(checksummed),
+        _ => Err(HawalaError::invalid_input(format!("Invalid {} address: {}", field, address))),
+    }
+}
+
+/// JSON-RPC quantity (`"0x..."`) at `tx[field]`
+fn quantity(tx: &Value, field: &str) -> HawalaResult<Option<U256>> {
+    match &tx[field] {
+        Value::Null => Ok(None),
+        Value::String(s) => {
+            let parsed = match s.strip_prefix("0x") {
+                Some("") => Some(U256::zero()),
+                Some(digits) => U256::from_str_radix(digits, 16).ok(),
+                None => U256::from_dec_str(s).ok(),
+            };
+            parsed.map(Some)
+                .ok_or_else(|| HawalaError::invalid_input(format!("Invalid {}: {}", field, s)))
+        }
+        Value::Number(n) => n.as_u64().map(|n| Some(U256::from(n)))
+            .ok_or_else(|| HawalaError::invalid_input(format!("Invalid {}: {}", field, n))),
+        _ => Err(HawalaError::invalid_input(format!("Invalid {}", field))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ACCOUNT: &str = "0x9858EfFD232B4033E47d90003D41EC34EcaEda94";
+
+    #[test]
+    fn test_parse_personal_sign() {
+        // "Hello, Hawala" as the dapp sends it: hex message, then the account
+        let params = json!(["0x48656c6c6f2c20486177616c61", ACCOUNT.to_lowercase()]);
+        let parsed = parse_request("personal_sign", params.clone(), Some("eip155:1")).unwrap();
+        assert_eq!(parsed.signer, ACCOUNT);
+        assert_eq!(parsed.chain, Chain::Ethereum);
+        match parsed.payload {
+            WcPayload::PersonalMessage { message_hex, text } => {
+                assert_eq!(message_hex, "0x48656c6c6f2c20486177616c61");
+                assert_eq!(text.as_deref(), Some("Hello, Hawala"));
+            }
+            other => panic!("unexpected payload {:?}", other),
+        }
+
+        // Swapped order and a plain-text message
+        let parsed = parse_request("personal_sign", json!([ACCOUNT, "Sign in"]), Some("eip155:137")).unwrap();
+        assert_eq!(parsed.signer, ACCOUNT);
+        assert_eq!(parsed.chain, Chain::Polygon);
+        assert!(matches!(
+            parsed.payload,
+            WcPayload::PersonalMessage { ref message_hex, .. } if message_hex == "0x5369676e20696e"
+        ));
+
+        assert!(parse_request("personal_sign", json!(["0x00"]), Some("eip155:1")).is_err());
+
+        // Nothing names a chain: rejected instead of assuming Ethereum
+        assert!(parse_request("personal_sign", params, None).is_err());
+    }
+
+    #[test]
+    fn test_parse_sign_typed_data_v4() {
+        let typed_data = json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "chainId", "type": "uint256" }
+                ],
+                "Mail": [
+                    { "name": "to", "type": "address" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": { "name": "Ether Mail", "chainId": 137 },
+            "message": { "to": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB", "contents": "Hello, Bob!" }
+        });
+
+        // WalletConnect sends the typed data as a JSON string
+        let params = json!([ACCOUNT, typed_data.to_string()]);
+        let parsed = parse_request("eth_signTypedData_v4", params, None).unwrap();
+        assert_eq!(parsed.signer, ACCOUNT);
+        assert_eq!(parsed.chain, Chain::Polygon);
+        let WcPayload::TypedData(parsed_data) = parsed.payload else {
+            panic!("expected typed data");
+        };
+        assert_eq!(parsed_data.primary_type, "Mail");
+        assert_eq!(parsed_data.message["contents"], "Hello, Bob!");
+        assert!(crate::eip712::hash_typed_data(&parsed_data).is_ok());
+
+        // ...or as an object
+        assert!(parse_request("eth_signTypedData_v4", json!([ACCOUNT, typed_data]), Some("eip155:137")).is_ok());
+
+        // The domain and the session must agree
+        assert!(parse_request("eth_signTypedData_v4", json!([ACCOUNT, typed_data]), Some("eip155:1")).is_err());
+
+        // primaryType must be defined
+        let mut broken = typed_data.clone();
+        broken["primaryType"] = json!("Letter");
+        assert!(parse_request("eth_signTypedData_v4", json!([ACCOUNT, broken]), None).is_err());
+    }
+
+    #[test]
+    fn test_parse_send_transaction() {
+        let params = json!([{
+            "from": ACCOUNT,
+            "to": "0xd8da6bf26964af9d7eed9e03e53415d37aa96045",
+            "value": "0xde0b6b3a7640000",
+            "gas": "0x5208",
+            "maxFeePerGas": "0x6fc23ac00",
+            "data": "0x",
+            "chainId": "0xa4b1"
+        }]);
+        let parsed = parse_request("eth_sendTransaction", params.clone(), None).unwrap();
+        assert_eq!(parsed.signer, ACCOUNT);
+        assert_eq!(parsed.chain, Chain::Arbitrum);
+        let WcPayload::Transaction(tx) = parsed.payload else {
+            panic!("expected a transaction");
+        };
+        assert_eq!(tx.chain, Chain::Arbitrum);
+        assert_eq!(tx.to, "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        assert_eq!(tx.amount, "1000000000000000000");
+        assert_eq!(tx.gas_limit, Some(21_000));
+        assert_eq!(tx.max_fee_per_gas, Some(Wei(30_000_000_000)));
+        assert_eq!(tx.nonce, None);
+        assert_eq!(tx.data, None);
+        assert!(tx.private_key.is_empty());
+
+        // Without chainId in the transaction the session chain is used
+        let mut without_id = params;
+        without_id[0].as_object_mut().unwrap().remove("chainId");
+        let parsed = parse_request("eth_sendTransaction", without_id.clone(), Some("eip155:137")).unwrap();
+        assert_eq!(parsed.chain, Chain::Polygon);
+        let WcPayload::Transaction(tx) = parsed.payload else {
+            panic!("expected a transaction");
+        };
+        assert_eq!(tx.chain, Chain::Polygon);
+
+        assert!(parse_request("eth_sendTransaction", without_id, None).is_err());
+    }
+
+    #[test]
+    fn test_chain_from_caip2() {
+        assert_eq!(chain_from_caip2("eip155:1").unwrap(), Chain::Ethereum);
+        assert_eq!(chain_from_caip2("eip155:42161").unwrap(), Chain::Arbitrum);
+        assert_eq!(chain_from_caip2("solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp").unwrap(), Chain::Solana);
+        assert_eq!(chain_from_caip2("solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1").unwrap(), Chain::SolanaDevnet);
+        assert!(chain_from_caip2("eip155:999999").is_err());
+        assert!(chain_from_caip2("eip155").is_err());
+        assert!(chain_from_caip2("cosmos:cosmoshub-4").is_err());
+
+        // An EVM method cannot run on a Solana session
+        let params = json!(["0x00", ACCOUNT]);
+        assert!(parse_request("personal_sign", params, Some("solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp")).is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_parse_solana_sign_transaction() {
+        use solana_sdk::{message::Message, pubkey::Pubkey, system_instruction, transaction::Transaction};
+
+        let payer = Pubkey::new_unique();
+        let transfer = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000);
+        let tx = Transaction::new_unsigned(Message::new(&[transfer], Some(&payer)));
+        let encoded = base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&tx).unwrap());
+
+        let mainnet = Some("solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp");
+        let parsed = parse_request("solana_signTransaction", json!({ "transaction": encoded }), mainnet).unwrap();
+        assert_eq!(parsed.signer, payer.to_string());
+        assert_eq!(parsed.chain, Chain::Solana);
+
+        let other = Pubkey::new_unique().to_string();
+        let params = json!({ "transaction": encoded, "pubkey": other });
+        assert!(parse_request("solana_signTransaction", params, mainnet).is_err());
+        assert!(parse_request("solana_signTransaction", json!({ "transaction": encoded }), None).is_err());
+        assert!(parse_request("solana_signTransaction", json!({ "transaction": encoded }), Some("eip155:1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_unsupported_method() {
+        let err = parse_request("eth_sign", json!([ACCOUNT, "0x00"]), Some("eip155:1")).unwrap_err();
+        assert_eq!(err.code, crate::error::ErrorCode::NotImplemented);
+    }
+}
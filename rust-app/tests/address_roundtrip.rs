@@ -0,0 +1,106 @@
+//! Address Round-Trip Tests
+//!
+//! Every address produced by key generation must pass the validator for
+//! its chain, on both mainnet and testnet key sets.
+
+use rust_app::wallet::{generate_keys_from_seed, validate_address, validate_address_detailed};
+use rust_app::{AllKeys, Chain, KeyNetwork};
+
+const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+fn keys_on(network: KeyNetwork) -> AllKeys {
+    let seed = bip39::Mnemonic::parse(MNEMONIC).unwrap().to_seed("");
+    generate_keys_from_seed(&seed, network).unwrap()
+}
+
+/// Addresses with a chain-specific validator
+///
+/// Litecoin and Monero have no testnet [`Chain`], so their testnet addresses
+/// are checked for rejection separately.
+fn validated_addresses(keys: &AllKeys, network: KeyNetwork) -> Vec<(Chain, String)> {
+    let bitcoin_chain = match network {
+        KeyNetwork::Mainnet => Chain::Bitcoin,
+        KeyNetwork::Testnet => Chain::BitcoinTestnet,
+    };
+
+    let mut addresses = vec![
+        (bitcoin_chain, keys.bitcoin.address.clone()),
+        (Chain::BitcoinTestnet, keys.bitcoin_testnet.address.clone()),
+        (Chain::Ethereum, keys.ethereum.address.clone()),
+        (Chain::EthereumSepolia, keys.ethereum_sepolia.address.clone()),
+        (Chain::Bnb, keys.bnb.address.clone()),
+        (Chain::Solana, keys.solana.public_key_base58.clone()),
+        (Chain::Xrp, keys.xrp.classic_address.clone()),
+        (Chain::Tron, keys.tron.address.clone()),
+    ];
+    if network == KeyNetwork::Mainnet {
+        addresses.push((Chain::Litecoin, keys.litecoin.address.clone()));
+        addresses.push((Chain::Monero, keys.monero.address.clone()));
+    }
+    if let Some(taproot) = &keys.bitcoin.taproot_address {
+        addresses.push((bitcoin_chain, taproot.clone()));
+    }
+    if let Some(taproot) = &keys.bitcoin_testnet.taproot_address {
+        addresses.push((Chain::BitcoinTestnet, taproot.clone()));
+    }
+    addresses
+}
+
+fn assert_all_valid(network: KeyNetwork) {
+    let keys = keys_on(network);
+    for (chain, address) in validated_addresses(&keys, network) {
+        let (valid, _) = validate_address(&address, chain);
+        assert!(valid, "{:?} rejected generated address {} ({:?})", chain, address, network);
+
+        let detailed = validate_address_detailed(&address, chain);
+        assert!(
+            detailed.is_valid,
+            "{:?} detailed check rejected {} ({:?}): {:?}",
+            chain, address, network, detailed.warnings
+        );
+    }
+}
+
+#[test]
+fn test_mainnet_addresses_validate() {
+    assert_all_valid(KeyNetwork::Mainnet);
+}
+
+#[test]
+fn test_testnet_addresses_validate() {
+    assert_all_valid(KeyNetwork::Testnet);
+}
+
+#[test]
+fn test_taproot_addresses_present() {
+    let keys = keys_on(KeyNetwork::Mainnet);
+    let taproot = keys.bitcoin.taproot_address.expect("taproot address");
+    assert!(taproot.starts_with("bc1p"));
+    assert!(validate_address_detailed(&taproot, Chain::Bitcoin).is_valid);
+}
+
+#[test]
+fn test_testnet_keys_use_testnet_encodings() {
+    let keys = keys_on(KeyNetwork::Testnet);
+    assert!(keys.bitcoin.address.starts_with("tb1"));
+    assert!(keys.litecoin.address.starts_with("tltc1"));
+
+    // Mainnet-only chains reject them as a network mismatch
+    for (chain, address) in [(Chain::Litecoin, &keys.litecoin.address), (Chain::Monero, &keys.monero.address)] {
+        let detailed = validate_address_detailed(address, chain);
+        assert!(!detailed.is_valid, "{:?} accepted testnet address {}", chain, address);
+        assert!(!detailed.network_match);
+        assert!(!validate_address(address, chain).0);
+    }
+    let monero = validate_address_detailed(&keys.monero.address, Chain::Monero);
+    assert!(monero.warnings.iter().any(|w| w.contains("testnet")));
+}
+
+#[test]
+fn test_addresses_rejected_on_other_chain() {
+    let keys = keys_on(KeyNetwork::Mainnet);
+    assert!(!validate_address(&keys.bitcoin.address, Chain::Litecoin).0);
+    assert!(!validate_address(&keys.litecoin.address, Chain::Bitcoin).0);
+    assert!(!validate_address(&keys.tron.address, Chain::Xrp).0);
+    assert!(!validate_address(&keys.xrp.classic_address, Chain::Tron).0);
+}
@@ -17,10 +17,15 @@ struct CliOutput {
 }
 
 fn decode_keys_from_cli() -> AllKeys {
+    decode_keys_from_cli_with(&[])
+}
+
+fn decode_keys_from_cli_with(args: &[&str]) -> AllKeys {
     let binary_path = assert_cmd::cargo::cargo_bin!("rust-app");
     let output = Command::new(binary_path)
         .arg("gen-keys")
         .arg("--json")
+        .args(args)
         .output()
         .expect("cli run succeeds");
 
@@ -292,3 +297,297 @@ fn litecoin_wif_checksum_validates() {
     let checksum = sha256d::Hash::hash(&decoded[..34]);
     assert_eq!(&decoded[34..], &checksum[..4], "litecoin checksum valid");
 }
+
+#[test]
+fn cli_testnet_flag_switches_networks() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let mainnet = decode_keys_from_cli_with(&["--mnemonic", mnemonic]);
+    let testnet = decode_keys_from_cli_with(&["--mnemonic", mnemonic, "--network", "testnet"]);
+
+    // Bitcoin: testnet bech32 address and WIF
+    assert!(testnet.bitcoin.address.starts_with("tb1"), "bitcoin testnet address: {}", testnet.bitcoin.address);
+    let btc_private = PrivateKey::from_wif(&testnet.bitcoin.private_wif).expect("bitcoin testnet wif valid");
+    assert_eq!(btc_private.network, Network::Testnet.into());
+    let secp = Secp256k1::new();
+    let btc_compressed = CompressedPublicKey::try_from(btc_private.public_key(&secp)).expect("compressed pubkey");
+    assert_eq!(Address::p2wpkh(&btc_compressed, Network::Testnet).to_string(), testnet.bitcoin.address);
+    assert_eq!(testnet.bitcoin.address, testnet.bitcoin_testnet.address);
+
+    // Litecoin: tltc bech32 address, 0xef WIF
+    assert!(testnet.litecoin.address.starts_with("tltc1"), "litecoin testnet address: {}", testnet.litecoin.address);
+    let ltc_wif = bs58::decode(&testnet.litecoin.private_wif).into_vec().expect("decode litecoin wif");
+    assert_eq!(ltc_wif[0], 0xEF, "litecoin testnet wif prefix");
+
+    // Account-based chains keep their keys
+    assert_eq!(testnet.ethereum.address, mainnet.ethereum.address);
+    assert_eq!(testnet.solana.public_key_base58, mainnet.solana.public_key_base58);
+    assert_eq!(testnet.xrp.classic_address, mainnet.xrp.classic_address);
+
+    // Mainnet default is unchanged
+    assert!(mainnet.bitcoin.address.starts_with("bc1"));
+    assert!(mainnet.litecoin.address.starts_with("ltc1"));
+    assert_ne!(testnet.monero.address, mainnet.monero.address);
+}
+
+#[test]
+fn cli_rejects_unknown_network() {
+    let binary_path = assert_cmd::cargo::cargo_bin!("rust-app");
+    let output = Command::new(binary_path)
+        .args(["gen-keys", "--json", "--network", "regtest"])
+        .output()
+        .expect("cli runs");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn cli_chains_flag_limits_output() {
+    let binary_path = assert_cmd::cargo::cargo_bin!("rust-app");
+    let output = Command::new(binary_path)
+        .args(["gen-keys", "--json", "--chains", "btc"])
+        .output()
+        .expect("cli runs");
+    assert!(output.status.success(), "cli exited unsuccessfully: {:?}", output);
+
+    let json: Value = serde_json::from_slice(&output.stdout).expect("stdout is valid json");
+    let keys = json["keys"].as_object().expect("keys object");
+    assert_eq!(keys.keys().collect::<Vec<_>>(), vec!["bitcoin"]);
+    assert!(keys["bitcoin"]["address"].as_str().unwrap().starts_with("bc1"));
+
+    let output = Command::new(binary_path)
+        .args(["gen-keys", "--chains", "btc,eth"])
+        .output()
+        .expect("cli runs");
+    let stdout = String::from_utf8(output.stdout).expect("stdout is utf8");
+    assert!(stdout.contains("=== Bitcoin (P2WPKH) ==="));
+    assert!(stdout.contains("=== Ethereum ==="));
+    assert!(!stdout.contains("=== Solana ==="));
+    assert!(!stdout.contains("=== Bitcoin Testnet (P2WPKH) ==="));
+
+    // Chains without a dedicated section are still printed
+    let output = Command::new(binary_path)
+        .args(["gen-keys", "--chains", "cosmos"])
+        .output()
+        .expect("cli runs");
+    assert!(output.status.success(), "cli exited unsuccessfully: {:?}", output);
+    let stdout = String::from_utf8(output.stdout).expect("stdout is utf8");
+    assert!(stdout.contains("=== Cosmos ==="));
+    assert!(stdout.lines().any(|line| line.starts_with("cosmos_address: cosmos1")), "{}", stdout);
+    assert!(!stdout.contains("=== Bitcoin (P2WPKH) ==="));
+
+    let output = Command::new(binary_path)
+        .args(["gen-keys", "--chains", "btc,notachain"])
+        .output()
+        .expect("cli runs");
+    assert!(!output.status.success());
+}
+
+/// Fresh, empty directory under the system temp dir
+fn scratch_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("hawala-cli-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("create scratch dir");
+    dir
+}
+
+#[test]
+fn cli_out_writes_private_file() {
+    let dir = scratch_dir("out");
+    let path = dir.join("keys.json");
+
+    let binary_path = assert_cmd::cargo::cargo_bin!("rust-app");
+    let output = Command::new(binary_path)
+        .args(["gen-keys", "--chains", "btc", "--out"])
+        .arg(&path)
+        .output()
+        .expect("cli runs");
+    assert!(output.status.success(), "cli exited unsuccessfully: {:?}", output);
+
+    // Secrets go to the file, not the terminal
+    let stdout = String::from_utf8(output.stdout).expect("stdout is utf8");
+    assert!(stdout.is_empty(), "unexpected stdout: {}", stdout);
+
+    let contents = std::fs::read_to_string(&path).expect("output file exists");
+    let json: Value = serde_json::from_str(&contents).expect("file is valid json");
+    assert!(json["keys"]["bitcoin"]["private_wif"].is_string());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600, "mode was {:o}", mode);
+    }
+
+    // No temporary files left behind
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn cli_out_refuses_to_clobber() {
+    let dir = scratch_dir("clobber");
+    let path = dir.join("keys.json");
+    std::fs::write(&path, "existing").unwrap();
+
+    let binary_path = assert_cmd::cargo::cargo_bin!("rust-app");
+    let output = Command::new(binary_path)
+        .args(["gen-keys", "--chains", "btc", "--out"])
+        .arg(&path)
+        .output()
+        .expect("cli runs");
+    assert!(!output.status.success());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "existing");
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    let output = Command::new(binary_path)
+        .args(["gen-keys", "--chains", "btc", "--force", "--out"])
+        .arg(&path)
+        .output()
+        .expect("cli runs");
+    assert!(output.status.success(), "cli exited unsuccessfully: {:?}", output);
+    let json: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).expect("file is valid json");
+    assert!(json["mnemonic"].is_string());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn cli_qr_renders_selected_address() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let binary_path = assert_cmd::cargo::cargo_bin!("rust-app");
+    let output = Command::new(binary_path)
+        .args(["gen-keys", "--mnemonic", mnemonic, "--chains", "btc,eth", "--qr", "--qr-chain", "btc"])
+        .output()
+        .expect("cli runs");
+    assert!(output.status.success(), "cli exited unsuccessfully: {:?}", output);
+    let stdout = String::from_utf8(output.stdout).expect("stdout is utf8");
+
+    assert!(stdout.contains("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"));
+    assert!(!stdout.contains("=== Ethereum address (QR) ==="));
+
+    // The 42-character address encodes as version 3 (29x29 modules); with
+    // the 4-module quiet zone that is 37 columns by 19 half-block lines
+    let qr: Vec<&str> = stdout
+        .split("=== Bitcoin address (QR) ===\n")
+        .nth(1)
+        .expect("bitcoin QR heading")
+        .lines()
+        .collect();
+    assert_eq!(qr.len(), 19);
+    assert!(qr.iter().all(|line| line.chars().count() == 37));
+}
+
+#[test]
+fn cli_sign_batch_signs_each_entry() {
+    let dir = scratch_dir("batch");
+    let path = dir.join("requests.json");
+    let batch = serde_json::json!([
+        {
+            "chain": "btc",
+            "recipient": "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            "amount_sats": 50_000,
+            "fee_rate": 2,
+            "sender_wif": "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn",
+            "utxos": [{
+                "txid": "aa".repeat(32),
+                "vout": 0,
+                "status": { "confirmed": true },
+                "value": 100_000
+            }]
+        },
+        {
+            "chain": "eth",
+            "recipient": "0x742d35Cc6634C0532925a3b844Bc454e4438f44e",
+            "amount_wei": "1000000000000000",
+            "chain_id": 1,
+            "sender_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+            "nonce": 0,
+            "gas_limit": 21_000,
+            "gas_price": "20000000000"
+        }
+    ]);
+    std::fs::write(&path, batch.to_string()).expect("write batch file");
+
+    let binary_path = assert_cmd::cargo::cargo_bin!("rust-app");
+    let output = Command::new(binary_path)
+        .args(["sign-batch", "--file"])
+        .arg(&path)
+        .output()
+        .expect("cli runs");
+    assert!(output.status.success(), "cli exited unsuccessfully: {:?}", output);
+
+    let results: Value = serde_json::from_slice(&output.stdout).expect("stdout is json");
+    let results = results.as_array().expect("results array");
+    assert_eq!(results.len(), 2);
+    for (index, (result, chain)) in results.iter().zip(["btc", "eth"]).enumerate() {
+        assert_eq!(result["index"], index);
+        assert_eq!(result["chain"], chain);
+        assert_eq!(result["success"], true, "entry {} failed: {}", index, result);
+        assert!(!result["signed_tx"].as_str().unwrap_or_default().is_empty());
+        assert!(result.get("error").is_none());
+    }
+    assert!(results[1]["signed_tx"].as_str().unwrap().starts_with("0x"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn cli_sign_batch_reports_failed_entries() {
+    let dir = scratch_dir("batch-fail");
+    let path = dir.join("requests.json");
+    let batch = serde_json::json!([
+        { "chain": "btc", "recipient": "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu" },
+        { "chain": "doge" }
+    ]);
+    std::fs::write(&path, batch.to_string()).expect("write batch file");
+
+    let binary_path = assert_cmd::cargo::cargo_bin!("rust-app");
+    let output = Command::new(binary_path)
+        .args(["sign-batch", "--file"])
+        .arg(&path)
+        .output()
+        .expect("cli runs");
+    assert!(!output.status.success(), "expected failure exit status");
+
+    // Results are still written, with each failure annotated
+    let results: Value = serde_json::from_slice(&output.stdout).expect("stdout is json");
+    let results = results.as_array().expect("results array");
+    assert_eq!(results.len(), 2);
+    for result in results {
+        assert_eq!(result["success"], false);
+        assert!(result["error"].as_str().unwrap().starts_with("Invalid request"));
+        assert!(result.get("signed_tx").is_none());
+    }
+    assert_eq!(results[1]["chain"], "doge");
+
+    let stderr = String::from_utf8(output.stderr).expect("stderr is utf8");
+    assert!(stderr.contains("2 of 2 requests failed"), "stderr: {}", stderr);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn cli_passphrase_changes_derived_keys() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let plain = decode_keys_from_cli_with(&["--mnemonic", mnemonic]);
+    let empty = decode_keys_from_cli_with(&["--mnemonic", mnemonic, "--passphrase", ""]);
+    let trezor = decode_keys_from_cli_with(&["--mnemonic", mnemonic, "--passphrase", "TREZOR"]);
+    let other = decode_keys_from_cli_with(&["--mnemonic", mnemonic, "--passphrase", "TREZOR2"]);
+
+    assert_eq!(plain.bitcoin.address, empty.bitcoin.address);
+    assert_ne!(plain.bitcoin.address, trezor.bitcoin.address);
+    assert_ne!(trezor.bitcoin.address, other.bitcoin.address);
+
+    // --passphrase-stdin reads the same passphrase from a pipe
+    let binary_path = assert_cmd::cargo::cargo_bin!("rust-app");
+    let mut child = Command::new(binary_path)
+        .args(["gen-keys", "--json", "--mnemonic", mnemonic, "--passphrase-stdin"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .expect("cli runs");
+    {
+        use std::io::Write;
+        child.stdin.take().unwrap().write_all(b"TREZOR\n").expect("write passphrase");
+    }
+    let output = child.wait_with_output().expect("cli exits");
+    assert!(output.status.success(), "cli exited unsuccessfully: {:?}", output);
+    let piped: CliOutput = serde_json::from_slice(&output.stdout).expect("json matches CliOutput schema");
+    assert_eq!(piped.keys.bitcoin.address, trezor.bitcoin.address);
+}
@@ -363,6 +363,29 @@ fn test_redact_ffi() {
     assert!(!redacted_str.contains("1234567890abcdef1234567890abcdef"));
 }
 
+// MARK: - Address Scanning Tests
+
+#[test]
+fn test_scan_addresses_ignores_evm_address_case() {
+    #[derive(serde::Deserialize)]
+    struct ScanResponse {
+        next_receive_index: u32,
+    }
+
+    // m/44'/60'/0'/0/0 for the "abandon ... about" mnemonic, checksummed and lowercase
+    for used in ["0x9858EfFD232B4033E47d90003D41EC34EcaEda94", "0x9858effd232b4033e47d90003d41ec34ecaeda94"] {
+        let input = serde_json::json!({
+            "mnemonic": "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "chain": "ethereum",
+            "gap_limit": 2,
+            "used_addresses": [used]
+        });
+        let result = call_ffi(hawala_scan_addresses, &input.to_string());
+        let scan: ScanResponse = parse_result(&result).expect("scan succeeds");
+        assert_eq!(scan.next_receive_index, 1, "{}", used);
+    }
+}
+
 // MARK: - End-to-End Security Flow Tests
 
 #[test]
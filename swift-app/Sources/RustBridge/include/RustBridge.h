@@ -14,6 +14,13 @@
 // Health Check
 // ----------------------------------------------------------------------------
 const char* hawala_health_check(void);
+const char* hawala_provider_health(const char* json_input);
+
+// ----------------------------------------------------------------------------
+// Network Configuration
+// ----------------------------------------------------------------------------
+const char* hawala_set_network_config(const char* json_input);
+const char* hawala_set_electrum_server(const char* json_input);
 
 // ----------------------------------------------------------------------------
 // Memory Management
@@ -23,18 +30,39 @@ void hawala_free_string(char* s);
 // ----------------------------------------------------------------------------
 // Wallet Operations
 // ----------------------------------------------------------------------------
-const char* hawala_generate_wallet(void);
+const char* hawala_generate_wallet(const char* json_input);
 const char* hawala_restore_wallet(const char* json_input);
+const char* hawala_derive_account(const char* json_input);
+const char* hawala_scan_addresses(const char* json_input);
+const char* hawala_derive_descriptor(const char* json_input);
 const char* hawala_validate_mnemonic(const char* json_input);
+const char* hawala_assess_mnemonic(const char* json_input);
+const char* hawala_mnemonic_to_entropy(const char* json_input);
+const char* hawala_entropy_to_mnemonic(const char* json_input);
+const char* hawala_bip38_encrypt(const char* json_input);
+const char* hawala_bip38_decrypt(const char* json_input);
 const char* hawala_validate_address(const char* json_input);
+const char* hawala_verify_receive_address(const char* json_input);
 const char* hawala_derive_address_from_key(const char* json_input);
+const char* hawala_import_wif(const char* json_input);
+const char* hawala_import_evm_key(const char* json_input);
+
+// ----------------------------------------------------------------------------
+// ENS Name Resolution
+// ----------------------------------------------------------------------------
+const char* hawala_ens_resolve(const char* json_input);
+const char* hawala_ens_reverse(const char* json_input);
 
 // ----------------------------------------------------------------------------
 // Transaction Pipeline (Phase 2)
 // ----------------------------------------------------------------------------
 const char* hawala_prepare_transaction(const char* json_input);
+const char* hawala_build_contract_call(const char* json_input);
+const char* hawala_abi_decode_log(const char* json_input);
 const char* hawala_sign_transaction(const char* json_input);
 const char* hawala_broadcast_transaction(const char* json_input);
+const char* hawala_send_transaction(const char* json_input);
+const char* hawala_decode_transaction(const char* json_input);
 
 // ----------------------------------------------------------------------------
 // Fee Estimation (Phase 3)
@@ -50,6 +78,7 @@ const char* hawala_cancel_bitcoin(const char* json_input);
 const char* hawala_speedup_bitcoin(const char* json_input);
 const char* hawala_cancel_evm(const char* json_input);
 const char* hawala_speedup_evm(const char* json_input);
+const char* hawala_build_cpfp(const char* json_input);
 
 // ----------------------------------------------------------------------------
 // Transaction Tracking (Phase 4)
@@ -71,6 +100,8 @@ const char* hawala_fetch_balances(const char* json_input);
 const char* hawala_fetch_balance(const char* json_input);
 const char* hawala_fetch_token_balance(const char* json_input);
 const char* hawala_fetch_spl_balance(const char* json_input);
+const char* hawala_fetch_all_spl(const char* json_input);
+const char* hawala_token_metadata(const char* json_input);
 
 // ----------------------------------------------------------------------------
 // UTXO Management (Phase 6)
@@ -86,6 +117,9 @@ const char* hawala_get_nonce(const char* json_input);
 const char* hawala_reserve_nonce(const char* json_input);
 const char* hawala_confirm_nonce(const char* json_input);
 const char* hawala_detect_nonce_gaps(const char* json_input);
+const char* hawala_release_nonce(const char* json_input);
+const char* hawala_resync_nonce(const char* json_input);
+const char* hawala_set_nonce_storage_path(const char* json_input);
 
 // ----------------------------------------------------------------------------
 // Security Operations (Phase 5 - Security Hardening)
@@ -135,23 +169,33 @@ const char* hawala_tezos_sign_message(const char* json_input);
 // EIP-7702 Account Delegation (Advanced Signing & Security)
 // ----------------------------------------------------------------------------
 const char* hawala_eip7702_sign_authorization(const char* json_input);
+const char* hawala_eip7702_revoke_authorization(const char* json_input);
 const char* hawala_eip7702_sign_transaction(const char* json_input);
 const char* hawala_eip7702_recover_authorization_signer(const char* json_input);
 
+// ----------------------------------------------------------------------------
+// Safe Multisig
+// ----------------------------------------------------------------------------
+const char* hawala_safe_tx_hash(const char* json_input);
+
 // ----------------------------------------------------------------------------
 // External Signature Compilation (Hardware Wallet / Air-Gapped Signing)
 // ----------------------------------------------------------------------------
 // Pre-image hash generation
 const char* hawala_get_bitcoin_sighashes(const char* json_input);
+const char* hawala_get_psbt_sighashes(const char* json_input);
 const char* hawala_get_ethereum_signing_hash(const char* json_input);
 const char* hawala_get_cosmos_sign_doc_hash(const char* json_input);
 const char* hawala_get_solana_message_hash(const char* json_input);
+const char* hawala_get_near_signing_hash(const char* json_input);
 
 // Transaction compilation with external signatures
 const char* hawala_compile_bitcoin_transaction(const char* json_input);
+const char* hawala_finalize_psbt(const char* json_input);
 const char* hawala_compile_ethereum_transaction(const char* json_input);
 const char* hawala_compile_cosmos_transaction(const char* json_input);
 const char* hawala_compile_solana_transaction(const char* json_input);
+const char* hawala_compile_near_transaction(const char* json_input);
 
 // ----------------------------------------------------------------------------
 // BIP-340 Schnorr Signatures (Bitcoin Taproot)
@@ -162,6 +206,10 @@ const char* hawala_taproot_tweak_pubkey(const char* json_input);
 const char* hawala_taproot_sign_key_path(const char* json_input);
 const char* hawala_taproot_leaf_hash(const char* json_input);
 const char* hawala_taproot_merkle_root(const char* json_input);
+const char* hawala_schnorr_adaptor_sign(const char* json_input);
+const char* hawala_schnorr_adaptor_verify(const char* json_input);
+const char* hawala_schnorr_adaptor_decrypt(const char* json_input);
+const char* hawala_schnorr_adaptor_recover(const char* json_input);
 
 // ----------------------------------------------------------------------------
 // Multi-Curve Cryptography (secp256k1, ed25519, sr25519, secp256r1)
@@ -171,6 +219,9 @@ const char* hawala_curve_public_key(const char* json_input);
 const char* hawala_curve_sign(const char* json_input);
 const char* hawala_curve_verify(const char* json_input);
 const char* hawala_curve_info(const char* json_input);
+const char* hawala_sr25519_vrf_sign(const char* json_input);
+const char* hawala_sr25519_vrf_verify(const char* json_input);
+const char* hawala_hash(const char* json_input);
 
 // ----------------------------------------------------------------------------
 // QR Code Encoding/Decoding (Air-Gapped Signing, UR Format)
@@ -178,6 +229,7 @@ const char* hawala_curve_info(const char* json_input);
 const char* hawala_qr_encode_ur(const char* json_input);
 const char* hawala_qr_encode_simple(const char* json_input);
 const char* hawala_qr_decode_ur(const char* json_input);
+const char* hawala_qr_export_account(const char* json_input);
 const char* hawala_qr_decoder_create(void);
 const char* hawala_qr_supported_types(void);
 
@@ -185,6 +237,7 @@ const char* hawala_qr_supported_types(void);
 // HD Key Derivation (BIP-32 / SLIP-0010)
 // ----------------------------------------------------------------------------
 const char* hawala_derive_key(const char* json_input);
+const char* hawala_derive_addresses(const char* json_input);
 
 // ----------------------------------------------------------------------------
 // DEX Aggregator (1inch, 0x, THORChain)
@@ -239,6 +292,7 @@ const char* hawala_batch_revoke(const char* json_input);
 // Phishing & Scam Detection
 const char* hawala_check_phishing_address(const char* json_input);
 const char* hawala_check_phishing_domain(const char* json_input);
+const char* hawala_check_phishing(const char* json_input);
 
 // Address Whitelisting
 const char* hawala_whitelist_add(const char* json_input);
@@ -261,14 +315,22 @@ const char* hawala_suggest_chain(const char* json_input);
 // Payment Request Links
 const char* hawala_create_payment_link(const char* json_input);
 const char* hawala_parse_payment_link(const char* json_input);
+const char* hawala_parse_payment_uri(const char* json_input);
+const char* hawala_generate_payment_uri(const char* json_input);
 const char* hawala_create_bip21_link(const char* json_input);
 const char* hawala_create_eip681_link(const char* json_input);
 
+// Lightning
+const char* hawala_lightning_parse_offer(const char* json_input);
+
 // Transaction Notes
 const char* hawala_add_note(const char* json_input);
 const char* hawala_search_notes(const char* json_input);
 const char* hawala_export_notes(const char* json_input);
 
+// Fiat On-Ramp
+const char* hawala_onramp_compare(const char* json_input);
+
 // Fiat Off-Ramp
 const char* hawala_offramp_quote(const char* json_input);
 const char* hawala_offramp_compare(const char* json_input);
@@ -280,6 +342,9 @@ const char* hawala_create_alert(const char* json_input);
 const char* hawala_get_price(const char* json_input);
 const char* hawala_alert_stats(void);
 
+// WalletConnect
+const char* hawala_parse_wc_request(const char* json_input);
+
 // ============================================================================
 // LEGACY API - Backward compatibility (deprecated, will be removed)
 // ============================================================================
@@ -436,7 +436,7 @@ final class BatchTransactionManager: ObservableObject {
             throw NSError(domain: "BatchTx", code: -1, userInfo: [NSLocalizedDescriptionKey: "Failed to get blockhash"])
         }
         
-        let amountSol = recipient.amountDouble
+        let amountSol = recipient.amount.trimmingCharacters(in: .whitespaces)
         let senderBase58 = keys.solana.privateKeyBase58
         
         let signedTx = try RustService.shared.signSolanaThrowing(
@@ -545,8 +545,11 @@ public final class HawalaBridge: @unchecked Sendable {
     // MARK: - Wallet Operations
     
     /// Generate a new wallet with mnemonic and keys
-    public func generateWallet() throws -> HawalaWalletResponse {
-        try callRustFFI { hawala_generate_wallet() }
+    /// - Parameter strength: Entropy bits (128/160/192/224/256 for 12-24 words)
+    public func generateWallet(strength: Int = 128) throws -> HawalaWalletResponse {
+        struct GenerateRequest: Encodable { let strength: Int }
+        let input = try encodeJSON(GenerateRequest(strength: strength))
+        return try callRustFFIWithInput(input) { hawala_generate_wallet($0) }
     }
     
     /// Restore wallet from mnemonic
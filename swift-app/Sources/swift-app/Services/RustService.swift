@@ -425,7 +425,7 @@ final class RustService: @unchecked Sendable {
     }
     
     /// Sign a Solana transaction
-    func signSolana(recipient: String, amountSol: Double, recentBlockhash: String, senderBase58: String) -> String {
+    func signSolana(recipient: String, amountSol: String, recentBlockhash: String, senderBase58: String) -> String {
         let request: [String: Any] = [
             "chain": "solana",
             "recipient": recipient,
@@ -563,7 +563,7 @@ final class RustService: @unchecked Sendable {
     }
     
     /// Sign a Solana transaction (throwing variant)
-    func signSolanaThrowing(recipient: String, amountSol: Double, recentBlockhash: String, senderBase58: String) throws -> String {
+    func signSolanaThrowing(recipient: String, amountSol: String, recentBlockhash: String, senderBase58: String) throws -> String {
         let result = signSolana(recipient: recipient, amountSol: amountSol, recentBlockhash: recentBlockhash, senderBase58: senderBase58)
         return try extractSignedTx(result)
     }
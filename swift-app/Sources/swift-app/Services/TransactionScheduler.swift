@@ -778,7 +778,7 @@ class TransactionScheduler: ObservableObject {
             throw SchedulerError.executionFailed("Failed to get blockhash")
         }
         
-        let amountSol = Double(truncating: tx.amount as NSDecimalNumber)
+        let amountSol = (tx.amount as NSDecimalNumber).stringValue
         let senderBase58 = keys.solana.privateKeyBase58
         
         let signedTx = try RustService.shared.signSolanaThrowing(
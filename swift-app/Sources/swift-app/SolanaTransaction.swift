@@ -30,8 +30,8 @@ struct SolanaTransaction {
         privateKeyBase58: String
     ) throws -> String {
         
-        // Convert Lamports to SOL
-        let amountSol = Double(amount) / 1_000_000_000.0
+        // Convert Lamports to an exact decimal SOL string
+        let amountSol = "\(amount / 1_000_000_000)." + String(format: "%09llu", amount % 1_000_000_000)
         
         return try RustService.shared.signSolanaThrowing(
             recipient: recipient,
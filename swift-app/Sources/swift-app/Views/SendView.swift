@@ -3393,7 +3393,7 @@ struct SendView: View {
     }
 
     private func sendSolana(isDevnet: Bool) async throws -> String {
-        let amountSol = Double(amount) ?? 0
+        let amountSol = amount.trimmingCharacters(in: .whitespaces)
         let recipient = recipientAddress
         let senderBase58 = keys.solana.privateKeyBase58
         